@@ -11,6 +11,9 @@
 
 //! Benchmarks for FROST.
 
+// The benchmarks pass `&mut rng` to the generic constructors for readability.
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 #[macro_use]
 extern crate criterion;
 
@@ -20,9 +23,11 @@ use rand::rngs::OsRng;
 
 use ice_frost::compute_message_hash;
 use ice_frost::generate_commitment_share_lists;
-use ice_frost::keygen::{Coefficients, DHPrivateKey, EncryptedSecretShare};
+use ice_frost::keygen::{Coefficients, DHPrivateKey, DHPublicKey, EncryptedSecretShare};
 use ice_frost::precomputation::{PublicCommitmentShareList, SecretCommitmentShareList};
+use ice_frost::roster::RosterTree;
 use ice_frost::DistributedKeyGeneration;
+use ice_frost::DkgSigner;
 use ice_frost::IndividualSecretKey;
 use ice_frost::Parameters;
 use ice_frost::Participant;
@@ -34,6 +39,10 @@ const THRESHOLD_OF_PARTICIPANTS: u32 = 3;
 mod dkg_benches {
     use super::*;
 
+    use curve25519_dalek::constants::{RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_TABLE};
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+
     fn participant_new_dealer(c: &mut Criterion) {
         let params = Parameters {
             n: NUMBER_OF_PARTICIPANTS,
@@ -51,10 +60,10 @@ mod dkg_benches {
             n: NUMBER_OF_PARTICIPANTS,
             t: THRESHOLD_OF_PARTICIPANTS,
         };
-        let mut rng = OsRng;
+        let rng = OsRng;
 
         c.bench_function("Participant creation (signer)", move |b| {
-            b.iter(|| Participant::new_signer(&params, 1, "Φ", &mut rng))
+            b.iter(|| DkgSigner::new(&params, 1, "Φ", rng))
         });
     }
 
@@ -144,7 +153,7 @@ mod dkg_benches {
         }
         participants_states_2.push(
             participants_states_1[0]
-                .clone()
+                .duplicate()
                 .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                 .unwrap(),
         );
@@ -160,7 +169,7 @@ mod dkg_benches {
 
             participants_states_2.push(
                 participants_states_1[(i - 1) as usize]
-                    .clone()
+                    .duplicate()
                     .to_round_two(pi_my_encrypted_secret_shares, &mut rng)
                     .unwrap(),
             );
@@ -168,37 +177,37 @@ mod dkg_benches {
 
         let mut participants_secret_keys =
             Vec::<IndividualSecretKey>::with_capacity(THRESHOLD_OF_PARTICIPANTS as usize);
-        let (_group_key, p1_sk) = participants_states_2[0].clone().finish().unwrap();
+        let (_group_key, p1_sk) = participants_states_2[0].duplicate().finish().unwrap();
         participants_secret_keys.push(p1_sk);
 
         for i in 2..THRESHOLD_OF_PARTICIPANTS + 1 {
             let (_, pi_sk) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
             participants_secret_keys.push(pi_sk);
         }
         for i in (THRESHOLD_OF_PARTICIPANTS + 2)..NUMBER_OF_PARTICIPANTS + 1 {
             let (_, _) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
         }
 
         let mut dealers = Vec::<Participant>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
 
-        let mut signers = Vec::<Participant>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
-        let (s1, s1_dh_sk) = Participant::new_signer(&params, 1, "Φ", &mut rng);
+        let mut signers = Vec::<DkgSigner>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
+        let (s1, s1_dh_sk) = DkgSigner::new(&params, 1, "Φ", rng);
         signers.push(s1.clone());
 
         for i in 2..NUMBER_OF_PARTICIPANTS + 1 {
-            let (s, _) = Participant::new_signer(&params, i, "Φ", &mut rng);
+            let (s, _) = DkgSigner::new(&params, i, "Φ", rng);
             signers.push(s);
         }
 
         for secret_key in participants_secret_keys.iter() {
             let (dealer, _, _) =
-                Participant::reshare(&params, secret_key.clone(), &signers, "Φ", &mut rng)
+                Participant::reshare(&params, secret_key.duplicate(), &signers, "Φ", rng)
                     .map_err(|_| ())
                     .unwrap();
             dealers.push(dealer);
@@ -267,7 +276,7 @@ mod dkg_benches {
         c.bench_function("Round Two", move |b| {
             b.iter(|| {
                 p1_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p1_my_encrypted_secret_shares.clone(), &mut rng)
             });
         });
@@ -329,7 +338,7 @@ mod dkg_benches {
             .unwrap();
 
         c.bench_function("Finish", move |b| {
-            b.iter(|| p1_state.clone().finish());
+            b.iter(|| p1_state.duplicate().finish());
         });
     }
 
@@ -388,7 +397,7 @@ mod dkg_benches {
         }
         participants_states_2.push(
             participants_states_1[0]
-                .clone()
+                .duplicate()
                 .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                 .unwrap(),
         );
@@ -404,25 +413,75 @@ mod dkg_benches {
 
             participants_states_2.push(
                 participants_states_1[(i - 1) as usize]
-                    .clone()
+                    .duplicate()
                     .to_round_two(pi_my_encrypted_secret_shares, &mut rng)
                     .unwrap(),
             );
         }
 
-        let (_group_key, p1_sk) = participants_states_2[0].clone().finish().unwrap();
+        let (_group_key, p1_sk) = participants_states_2[0].duplicate().finish().unwrap();
 
-        let mut signers = Vec::<Participant>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
-        let (s1, _s1_dh_sk) = Participant::new_signer(&params, 1, "Φ", &mut rng);
+        let mut signers = Vec::<DkgSigner>::with_capacity(NUMBER_OF_PARTICIPANTS as usize);
+        let (s1, _s1_dh_sk) = DkgSigner::new(&params, 1, "Φ", rng);
         signers.push(s1);
 
         for i in 2..NUMBER_OF_PARTICIPANTS + 1 {
-            let (s, _) = Participant::new_signer(&params, i, "Φ", &mut rng);
+            let (s, _) = DkgSigner::new(&params, i, "Φ", rng);
             signers.push(s);
         }
 
         c.bench_function("Reshare", move |b| {
-            b.iter(|| Participant::reshare(&params, p1_sk.clone(), &signers, "Φ", &mut rng));
+            b.iter(|| Participant::reshare(&params, p1_sk.duplicate(), &signers, "Φ", rng));
+        });
+    }
+
+    const LARGE_THRESHOLD: u32 = 500;
+
+    fn coefficient_commitments_basepoint_table(c: &mut Criterion) {
+        let coefficients: Vec<Scalar> = (0..LARGE_THRESHOLD)
+            .map(|_| Scalar::random(&mut OsRng))
+            .collect();
+
+        c.bench_function(
+            "Coefficient commitments (t = 500, basepoint table)",
+            move |b| {
+                b.iter(|| {
+                    coefficients
+                        .iter()
+                        .map(|a| a * &RISTRETTO_BASEPOINT_TABLE)
+                        .collect::<Vec<RistrettoPoint>>()
+                })
+            },
+        );
+    }
+
+    fn coefficient_commitments_variable_base(c: &mut Criterion) {
+        let coefficients: Vec<Scalar> = (0..LARGE_THRESHOLD)
+            .map(|_| Scalar::random(&mut OsRng))
+            .collect();
+
+        c.bench_function(
+            "Coefficient commitments (t = 500, variable base)",
+            move |b| {
+                b.iter(|| {
+                    coefficients
+                        .iter()
+                        .map(|a| RISTRETTO_BASEPOINT_POINT * a)
+                        .collect::<Vec<RistrettoPoint>>()
+                })
+            },
+        );
+    }
+
+    fn participant_new_dealer_large_threshold(c: &mut Criterion) {
+        let params = Parameters {
+            n: LARGE_THRESHOLD,
+            t: LARGE_THRESHOLD,
+        };
+        let mut rng = OsRng;
+
+        c.bench_function("Participant creation (dealer, t = 500)", move |b| {
+            b.iter(|| Participant::new_dealer(&params, 1, "Φ", &mut rng))
         });
     }
 
@@ -437,6 +496,9 @@ mod dkg_benches {
             round_two_t_out_of_n,
             finish_t_out_of_n,
             reshare_t_out_of_n,
+            coefficient_commitments_basepoint_table,
+            coefficient_commitments_variable_base,
+            participant_new_dealer_large_threshold,
     }
 }
 
@@ -498,7 +560,7 @@ mod sign_benches {
         }
         participants_states_2.push(
             participants_states_1[0]
-                .clone()
+                .duplicate()
                 .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                 .unwrap(),
         );
@@ -514,7 +576,7 @@ mod sign_benches {
 
             participants_states_2.push(
                 participants_states_1[(i - 1) as usize]
-                    .clone()
+                    .duplicate()
                     .to_round_two(pi_my_encrypted_secret_shares, &mut rng)
                     .unwrap(),
             );
@@ -522,19 +584,19 @@ mod sign_benches {
 
         let mut participants_secret_keys =
             Vec::<IndividualSecretKey>::with_capacity(THRESHOLD_OF_PARTICIPANTS as usize);
-        let (group_key, p1_sk) = participants_states_2[0].clone().finish().unwrap();
+        let (group_key, p1_sk) = participants_states_2[0].duplicate().finish().unwrap();
         participants_secret_keys.push(p1_sk);
 
         for i in 2..THRESHOLD_OF_PARTICIPANTS + 1 {
             let (_, pi_sk) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
             participants_secret_keys.push(pi_sk);
         }
         for i in (THRESHOLD_OF_PARTICIPANTS + 2)..NUMBER_OF_PARTICIPANTS + 1 {
             let (_, _) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
         }
@@ -558,11 +620,13 @@ mod sign_benches {
             SignatureAggregator::new(params, group_key, &context[..], &message[..]);
 
         for i in 1..THRESHOLD_OF_PARTICIPANTS + 1 {
-            aggregator.include_signer(
-                i,
-                participants_public_comshares[(i - 1) as usize].commitments[0],
-                (&participants_secret_keys[(i - 1) as usize]).into(),
-            );
+            aggregator
+                .include_signer(
+                    i,
+                    participants_public_comshares[(i - 1) as usize].commitments[0],
+                    (&participants_secret_keys[(i - 1) as usize]).into(),
+                )
+                .unwrap();
         }
 
         let signers = aggregator.get_signers();
@@ -573,7 +637,7 @@ mod sign_benches {
                 participants_secret_keys[0].sign(
                     &message_hash,
                     &group_key,
-                    &mut p1_secret_comshares.clone(),
+                    &mut p1_secret_comshares.duplicate(),
                     0,
                     signers,
                 )
@@ -636,7 +700,7 @@ mod sign_benches {
         }
         participants_states_2.push(
             participants_states_1[0]
-                .clone()
+                .duplicate()
                 .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                 .unwrap(),
         );
@@ -652,7 +716,7 @@ mod sign_benches {
 
             participants_states_2.push(
                 participants_states_1[(i - 1) as usize]
-                    .clone()
+                    .duplicate()
                     .to_round_two(pi_my_encrypted_secret_shares, &mut rng)
                     .unwrap(),
             );
@@ -660,19 +724,19 @@ mod sign_benches {
 
         let mut participants_secret_keys =
             Vec::<IndividualSecretKey>::with_capacity(THRESHOLD_OF_PARTICIPANTS as usize);
-        let (group_key, p1_sk) = participants_states_2[0].clone().finish().unwrap();
+        let (group_key, p1_sk) = participants_states_2[0].duplicate().finish().unwrap();
         participants_secret_keys.push(p1_sk);
 
         for i in 2..THRESHOLD_OF_PARTICIPANTS + 1 {
             let (_, pi_sk) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
             participants_secret_keys.push(pi_sk);
         }
         for i in (THRESHOLD_OF_PARTICIPANTS + 2)..NUMBER_OF_PARTICIPANTS + 1 {
             let (_, _) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
         }
@@ -700,11 +764,13 @@ mod sign_benches {
             SignatureAggregator::new(params, group_key, &context[..], &message[..]);
 
         for i in 1..THRESHOLD_OF_PARTICIPANTS + 1 {
-            aggregator.include_signer(
-                i,
-                participants_public_comshares[(i - 1) as usize].commitments[0],
-                (&participants_secret_keys[(i - 1) as usize]).into(),
-            );
+            aggregator
+                .include_signer(
+                    i,
+                    participants_public_comshares[(i - 1) as usize].commitments[0],
+                    (&participants_secret_keys[(i - 1) as usize]).into(),
+                )
+                .unwrap();
         }
 
         let signers = aggregator.get_signers().clone();
@@ -720,7 +786,9 @@ mod sign_benches {
                     &signers,
                 )
                 .unwrap();
-            aggregator.include_partial_signature(pi_partial_signature);
+            aggregator
+                .include_partial_signature(pi_partial_signature)
+                .unwrap();
         }
 
         let aggregator = aggregator.finalize().unwrap();
@@ -785,7 +853,7 @@ mod sign_benches {
         }
         participants_states_2.push(
             participants_states_1[0]
-                .clone()
+                .duplicate()
                 .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                 .unwrap(),
         );
@@ -801,7 +869,7 @@ mod sign_benches {
 
             participants_states_2.push(
                 participants_states_1[(i - 1) as usize]
-                    .clone()
+                    .duplicate()
                     .to_round_two(pi_my_encrypted_secret_shares, &mut rng)
                     .unwrap(),
             );
@@ -809,19 +877,19 @@ mod sign_benches {
 
         let mut participants_secret_keys =
             Vec::<IndividualSecretKey>::with_capacity(THRESHOLD_OF_PARTICIPANTS as usize);
-        let (group_key, p1_sk) = participants_states_2[0].clone().finish().unwrap();
+        let (group_key, p1_sk) = participants_states_2[0].duplicate().finish().unwrap();
         participants_secret_keys.push(p1_sk);
 
         for i in 2..THRESHOLD_OF_PARTICIPANTS + 1 {
             let (_, pi_sk) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
             participants_secret_keys.push(pi_sk);
         }
         for i in (THRESHOLD_OF_PARTICIPANTS + 2)..NUMBER_OF_PARTICIPANTS + 1 {
             let (_, _) = participants_states_2[(i - 1) as usize]
-                .clone()
+                .duplicate()
                 .finish()
                 .unwrap();
         }
@@ -849,11 +917,13 @@ mod sign_benches {
             SignatureAggregator::new(params, group_key, &context[..], &message[..]);
 
         for i in 1..THRESHOLD_OF_PARTICIPANTS + 1 {
-            aggregator.include_signer(
-                i,
-                participants_public_comshares[(i - 1) as usize].commitments[0],
-                (&participants_secret_keys[(i - 1) as usize]).into(),
-            );
+            aggregator
+                .include_signer(
+                    i,
+                    participants_public_comshares[(i - 1) as usize].commitments[0],
+                    (&participants_secret_keys[(i - 1) as usize]).into(),
+                )
+                .unwrap();
         }
 
         let signers = aggregator.get_signers().clone();
@@ -869,7 +939,9 @@ mod sign_benches {
                     &signers,
                 )
                 .unwrap();
-            aggregator.include_partial_signature(pi_partial_signature);
+            aggregator
+                .include_partial_signature(pi_partial_signature)
+                .unwrap();
         }
 
         let aggregator = aggregator.finalize().unwrap();
@@ -890,4 +962,244 @@ mod sign_benches {
     }
 }
 
-criterion_main!(dkg_benches::dkg_benches, sign_benches::sign_benches,);
+mod roster_benches {
+    use super::*;
+
+    const ROSTER_SIZE: u32 = 4096;
+
+    fn roster(rng: &mut OsRng) -> Vec<(u32, DHPublicKey)> {
+        let params = Parameters {
+            n: ROSTER_SIZE,
+            t: THRESHOLD_OF_PARTICIPANTS,
+        };
+
+        (1..=ROSTER_SIZE)
+            .map(|i| {
+                let (p, _) = DkgSigner::new(&params, i, "Φ", *rng);
+                (i, p.dh_public_key)
+            })
+            .collect()
+    }
+
+    fn roster_update_incremental(c: &mut Criterion) {
+        let mut rng = OsRng;
+        let roster = roster(&mut rng);
+        let mut tree = RosterTree::new(&roster).unwrap();
+        let key = roster[0].1.clone();
+
+        c.bench_function("Roster update (incremental, n = 4096)", move |b| {
+            b.iter(|| {
+                tree.update_leaf(ROSTER_SIZE / 2, &key).unwrap();
+                tree.root()
+            })
+        });
+    }
+
+    fn roster_update_from_scratch(c: &mut Criterion) {
+        let mut rng = OsRng;
+        let mut roster = roster(&mut rng);
+        let key = roster[0].1.clone();
+
+        c.bench_function("Roster update (from scratch, n = 4096)", move |b| {
+            b.iter(|| {
+                roster[ROSTER_SIZE as usize / 2].1 = key.clone();
+                RosterTree::new(&roster).unwrap().root()
+            })
+        });
+    }
+
+    criterion_group! {
+        name = roster_benches;
+        config = Criterion::default().sample_size(10);
+        targets =
+            roster_update_incremental,
+            roster_update_from_scratch,
+    }
+}
+
+mod serialise_benches {
+    use super::*;
+
+    use curve25519_dalek::ristretto::RistrettoPoint;
+
+    use ice_frost::bundle::SigningRequestBundle;
+    use ice_frost::IndividualPublicKey;
+
+    const SESSION_SIZE: u32 = 100;
+
+    /// A signing request for a session of 100 signers, along with their
+    /// public keys and published commitments.
+    fn session(
+        rng: &mut OsRng,
+    ) -> (
+        SigningRequestBundle,
+        Vec<IndividualPublicKey>,
+        Vec<(RistrettoPoint, RistrettoPoint)>,
+    ) {
+        let params = Parameters {
+            n: SESSION_SIZE,
+            t: SESSION_SIZE,
+        };
+        let group_key = ice_frost::GroupKey::from_bytes(
+            &RistrettoPoint::random(&mut *rng).compress().to_bytes(),
+        )
+        .unwrap();
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &b"CONTEXT"[..], &b"MESSAGE"[..]);
+        let mut public_keys = Vec::new();
+        let mut commitments = Vec::new();
+
+        for i in 1..=SESSION_SIZE {
+            let (public_comshares, _) = generate_commitment_share_lists(&mut *rng, i, 1);
+            let public_key = IndividualPublicKey::new(i, RistrettoPoint::random(&mut *rng));
+            aggregator
+                .include_signer(i, public_comshares.commitments[0], public_key.clone())
+                .unwrap();
+            public_keys.push(public_key);
+            commitments.push(public_comshares.commitments[0]);
+        }
+
+        (
+            aggregator.signing_request(1, 0).unwrap(),
+            public_keys,
+            commitments,
+        )
+    }
+
+    fn serialise_session_cached(c: &mut Criterion) {
+        let mut rng = OsRng;
+        let (request, public_keys, _) = session(&mut rng);
+
+        c.bench_function("Serialise session (n = 100, cached encodings)", move |b| {
+            b.iter(|| {
+                let bytes = request.to_bytes();
+                let keys: Vec<[u8; 36]> = public_keys.iter().map(|pk| pk.to_bytes()).collect();
+                (bytes, keys)
+            })
+        });
+    }
+
+    fn serialise_session_recompressed(c: &mut Criterion) {
+        let mut rng = OsRng;
+        let (_, public_keys, commitments) = session(&mut rng);
+
+        // This is the work serialisation did before encodings were cached.
+        c.bench_function("Serialise session (n = 100, recompressed)", move |b| {
+            b.iter(|| {
+                let mut bytes = Vec::with_capacity(commitments.len() * 64);
+                for (hiding, binding) in commitments.iter() {
+                    bytes.extend_from_slice(hiding.compress().as_bytes());
+                    bytes.extend_from_slice(binding.compress().as_bytes());
+                }
+                let keys: Vec<[u8; 32]> = public_keys
+                    .iter()
+                    .map(|pk| pk.share.compress().to_bytes())
+                    .collect();
+                (bytes, keys)
+            })
+        });
+    }
+
+    criterion_group! {
+        name = serialise_benches;
+        config = Criterion::default().sample_size(10);
+        targets =
+            serialise_session_cached,
+            serialise_session_recompressed,
+    }
+}
+
+mod batch_benches {
+    use super::*;
+
+    use ice_frost::signature::{verify_heterogeneous_batch, ThresholdSignature};
+    use ice_frost::GroupKey;
+
+    const BATCH_SIZE: usize = 64;
+
+    /// A signature from a 1-out-of-1 group, each under its own group key.
+    fn signed_message(rng: &mut OsRng, i: usize) -> (GroupKey, [u8; 32], ThresholdSignature) {
+        let params = Parameters { n: 1, t: 1 };
+        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", *rng);
+        let (p1_state, _) = DistributedKeyGeneration::<_>::new_initial(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1coeffs,
+            &[p1.clone()],
+            "Φ",
+            *rng,
+        )
+        .unwrap();
+        let shares = p1_state.their_encrypted_secret_shares().unwrap().clone();
+        let p1_state = p1_state.to_round_two(shares, *rng).unwrap();
+        let (group_key, p1_sk) = p1_state.finish().unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = i.to_le_bytes();
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut *rng, 1, 1);
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator
+            .include_signer(1, public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        let signers = aggregator.get_signers().clone();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+        let partial = p1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        (group_key, message_hash, signature)
+    }
+
+    fn batch(rng: &mut OsRng) -> Vec<(GroupKey, [u8; 32], ThresholdSignature)> {
+        (0..BATCH_SIZE).map(|i| signed_message(rng, i)).collect()
+    }
+
+    fn verify_heterogeneous(c: &mut Criterion) {
+        let mut rng = OsRng;
+        let items = batch(&mut rng);
+
+        c.bench_function("Heterogeneous batch verification (64 keys)", move |b| {
+            b.iter(|| verify_heterogeneous_batch(&items, OsRng).unwrap())
+        });
+    }
+
+    fn verify_sequential(c: &mut Criterion) {
+        let mut rng = OsRng;
+        let items = batch(&mut rng);
+
+        c.bench_function("Sequential verification (64 keys)", move |b| {
+            b.iter(|| {
+                for (group_key, message_hash, signature) in items.iter() {
+                    signature.verify(group_key, message_hash).unwrap();
+                }
+            })
+        });
+    }
+
+    criterion_group! {
+        name = batch_benches;
+        config = Criterion::default().sample_size(10);
+        targets =
+            verify_heterogeneous,
+            verify_sequential,
+    }
+}
+
+criterion_main!(
+    dkg_benches::dkg_benches,
+    sign_benches::sign_benches,
+    roster_benches::roster_benches,
+    serialise_benches::serialise_benches,
+    batch_benches::batch_benches,
+);
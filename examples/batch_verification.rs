@@ -0,0 +1,155 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Batch verification of several threshold signatures with a single
+//! multiscalar multiplication.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul};
+
+use rand::rngs::OsRng;
+
+use ice_frost::compute_message_hash;
+use ice_frost::generate_commitment_share_lists;
+use ice_frost::signature::{BatchVerifiable, ThresholdSignature, VerificationEquation};
+use ice_frost::DistributedKeyGeneration;
+use ice_frost::GroupKey;
+use ice_frost::IndividualPublicKey;
+use ice_frost::IndividualSecretKey;
+use ice_frost::Parameters;
+use ice_frost::Participant;
+use ice_frost::SignatureAggregator;
+
+/// Check that every equation \\(z_i B = R_i + c_i Y_i\\) holds at once, by
+/// checking a random linear combination of them.
+fn verify_batch(equations: &[VerificationEquation]) -> bool {
+    let mut rng = OsRng;
+
+    let mut scalars: Vec<Scalar> = Vec::with_capacity(2 * equations.len() + 1);
+    let mut points: Vec<RistrettoPoint> = Vec::with_capacity(2 * equations.len() + 1);
+
+    let mut z = Scalar::zero();
+    for equation in equations.iter() {
+        let a = Scalar::random(&mut rng);
+
+        z += a * equation.z;
+        scalars.push(-a);
+        points.push(equation.R);
+        scalars.push(-(a * equation.challenge));
+        points.push(equation.public_key);
+    }
+    scalars.push(z);
+    points.push(RISTRETTO_BASEPOINT_POINT);
+
+    RistrettoPoint::vartime_multiscalar_mul(scalars, points) == RistrettoPoint::identity()
+}
+
+fn keygen(params: &Parameters) -> (GroupKey, Vec<IndividualSecretKey>) {
+    let rng = OsRng;
+
+    let mut participants = Vec::new();
+    let mut coefficients = Vec::new();
+    let mut dh_secret_keys = Vec::new();
+    for i in 1..=params.n {
+        let (p, coeffs, dh_sk) = Participant::new_dealer(params, i, "Φ", rng);
+        participants.push(p);
+        coefficients.push(coeffs);
+        dh_secret_keys.push(dh_sk);
+    }
+
+    let mut states = Vec::new();
+    let mut encrypted_shares = Vec::new();
+    for i in 0..params.n as usize {
+        let (state, _participant_lists) = DistributedKeyGeneration::<_>::new_initial(
+            params,
+            &dh_secret_keys[i],
+            &participants[i].index,
+            &coefficients[i],
+            &participants,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        encrypted_shares.extend_from_slice(state.their_encrypted_secret_shares().unwrap());
+        states.push(state);
+    }
+
+    let mut group_key = None;
+    let mut secret_keys = Vec::new();
+    for (i, state) in states.into_iter().enumerate() {
+        let my_encrypted_secret_shares = encrypted_shares
+            .iter()
+            .filter(|s| s.receiver_index == participants[i].index)
+            .cloned()
+            .collect();
+        let state = state.to_round_two(my_encrypted_secret_shares, rng).unwrap();
+        let (gk, sk) = state.finish().unwrap();
+        group_key = Some(gk);
+        secret_keys.push(sk);
+    }
+
+    (group_key.unwrap(), secret_keys)
+}
+
+fn sign(
+    params: Parameters,
+    group_key: GroupKey,
+    signers: &[IndividualSecretKey],
+    context: &[u8],
+    message: &[u8],
+) -> ThresholdSignature {
+    let mut aggregator = SignatureAggregator::new(params, group_key, context, message);
+
+    let mut secret_comshares = Vec::new();
+    for sk in signers.iter() {
+        let pk: IndividualPublicKey = sk.into();
+        let (public_comshares, secret) = generate_commitment_share_lists(&mut OsRng, pk.index, 1);
+        aggregator
+            .include_signer(pk.index, public_comshares.commitments[0], pk)
+            .unwrap();
+        secret_comshares.push(secret);
+    }
+
+    let all_signers = aggregator.get_signers().clone();
+    let message_hash = compute_message_hash(context, message);
+    for (sk, secret) in signers.iter().zip(secret_comshares.iter_mut()) {
+        let partial = sk
+            .sign(&message_hash, &group_key, secret, 0, &all_signers)
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+    }
+
+    aggregator.finalize().unwrap().aggregate().unwrap()
+}
+
+fn main() {
+    let params = Parameters { n: 3, t: 2 };
+    let context = b"CONTEXT STRING FOR THE BATCH VERIFICATION EXAMPLE";
+
+    let (group_key, secret_keys) = keygen(&params);
+
+    let mut equations = Vec::new();
+    for i in 0..8u8 {
+        let message = [i; 32];
+        let signature = sign(params, group_key, &secret_keys[..2], &context[..], &message);
+
+        let message_hash = compute_message_hash(&context[..], &message);
+        equations.push(signature.verification_equation(&group_key, &message_hash));
+    }
+
+    assert!(verify_batch(&equations));
+    println!(
+        "Verified {} threshold signatures in a batch.",
+        equations.len()
+    );
+}
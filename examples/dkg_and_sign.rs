@@ -0,0 +1,277 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! An end-to-end distributed key generation and threshold signing between
+//! participants running in their own threads.
+//!
+//! The participants only talk to an untrusted coordinator, here the main
+//! thread, over in-memory channels, and every message crosses a channel in
+//! its serialised form, as it would over a network.
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+use rand::rngs::OsRng;
+
+use ice_frost::bundle::{SigningRequestBundle, SigningResponseBundle};
+use ice_frost::compute_message_hash;
+use ice_frost::coordinator::DkgCoordinator;
+use ice_frost::generate_commitment_share_lists;
+use ice_frost::keygen::EncryptedSecretShare;
+use ice_frost::precomputation::PublicCommitmentShareList;
+use ice_frost::signature::ThresholdSignature;
+use ice_frost::DistributedKeyGeneration;
+use ice_frost::GroupKey;
+use ice_frost::IndividualPublicKey;
+use ice_frost::Parameters;
+use ice_frost::Participant;
+use ice_frost::SignatureAggregator;
+
+const CONTEXT_STRING: &str = "ICE-FROST DKG AND SIGN EXAMPLE";
+
+/// A message from a participant to the coordinator.
+enum ToCoordinator {
+    /// The round one package of a participant.
+    RoundOne(Vec<u8>),
+    /// The round two encrypted secret shares of a participant.
+    RoundTwo(Vec<Vec<u8>>),
+    /// The outcome of the key generation for a participant: its index, the
+    /// group key, its public verification share and its published signing
+    /// commitments.
+    Finished(u32, [u8; 32], [u8; 36], Vec<u8>),
+    /// The answer of a participant to a signing request.
+    SigningResponse([u8; 68]),
+}
+
+/// A message from the coordinator to a participant.
+enum ToParticipant {
+    /// The round one packages of all the participants.
+    RoundOne(Vec<Vec<u8>>),
+    /// The encrypted secret shares addressed to the participant.
+    RoundTwo(Vec<Vec<u8>>),
+    /// A request to sign, or nothing if the participant is not a signer.
+    SigningRequest(Option<Vec<u8>>),
+}
+
+fn participant(
+    params: Parameters,
+    index: u32,
+    inbox: Receiver<ToParticipant>,
+    outbox: Sender<ToCoordinator>,
+) {
+    let mut rng = OsRng;
+
+    // Round one: publish our commitments and proofs of knowledge.
+    let (me, coefficients, dh_private_key) =
+        Participant::new_dealer(&params, index, CONTEXT_STRING, rng);
+    outbox.send(ToCoordinator::RoundOne(me.to_bytes())).unwrap();
+
+    let participants: Vec<Participant> = match inbox.recv().unwrap() {
+        ToParticipant::RoundOne(packages) => packages
+            .iter()
+            .map(|bytes| Participant::from_bytes(bytes).unwrap())
+            .collect(),
+        _ => panic!("expected the round one packages"),
+    };
+    let (state, _participant_lists) = DistributedKeyGeneration::new_initial(
+        &params,
+        &dh_private_key,
+        &index,
+        &coefficients,
+        &participants,
+        CONTEXT_STRING,
+        rng,
+    )
+    .unwrap();
+
+    // Round two: send each participant its encrypted share.
+    let shares = state
+        .their_encrypted_secret_shares()
+        .unwrap()
+        .iter()
+        .map(|share| share.to_bytes())
+        .collect();
+    outbox.send(ToCoordinator::RoundTwo(shares)).unwrap();
+
+    let my_shares: Vec<EncryptedSecretShare> = match inbox.recv().unwrap() {
+        ToParticipant::RoundTwo(shares) => shares
+            .iter()
+            .map(|bytes| EncryptedSecretShare::from_bytes(bytes).unwrap())
+            .collect(),
+        _ => panic!("expected the round two shares"),
+    };
+    let state = state.to_round_two(my_shares, rng).unwrap();
+    let (group_key, secret_key) = state.finish().unwrap();
+    println!("Participant {} computed its secret key share.", index);
+
+    // Publish our signing commitments along with our public key share.
+    let (public_comshares, mut secret_comshares) =
+        generate_commitment_share_lists(&mut rng, index, 1);
+    outbox
+        .send(ToCoordinator::Finished(
+            index,
+            group_key.to_bytes(),
+            secret_key.to_public().to_bytes(),
+            public_comshares.to_bytes(),
+        ))
+        .unwrap();
+
+    let request = match inbox.recv().unwrap() {
+        ToParticipant::SigningRequest(Some(bytes)) => {
+            SigningRequestBundle::from_bytes(&bytes).unwrap()
+        }
+        ToParticipant::SigningRequest(None) => return,
+        _ => panic!("expected a signing request"),
+    };
+
+    // A real signer would inspect `request.session()` before signing.
+    let response = secret_key
+        .sign_request(&request, &group_key, &mut secret_comshares)
+        .unwrap();
+    println!("Participant {} signed the message.", index);
+    outbox
+        .send(ToCoordinator::SigningResponse(response.to_bytes()))
+        .unwrap();
+}
+
+fn main() {
+    let params = Parameters { n: 5, t: 3 };
+    let context = b"CONTEXT STRING FOR THE DKG AND SIGN EXAMPLE";
+    let message = b"This is a test of the tsunami alert system. This is only a test.";
+
+    let (outbox, inbox) = channel();
+    let mut participants = Vec::new();
+    let mut handles = Vec::new();
+    for index in 1..=params.n {
+        let (to_participant, participant_inbox) = channel();
+        let outbox = outbox.clone();
+        handles.push(thread::spawn(move || {
+            participant(params, index, participant_inbox, outbox)
+        }));
+        participants.push(to_participant);
+    }
+    println!(
+        "Started {} participants for a {}-out-of-{} key generation.",
+        params.n, params.t, params.n
+    );
+
+    // Round one: collect the packages and broadcast them.
+    let mut coordinator = DkgCoordinator::new(params);
+    while !coordinator.is_round_one_complete() {
+        match inbox.recv().unwrap() {
+            ToCoordinator::RoundOne(bytes) => coordinator
+                .include_participant(Participant::from_bytes(&bytes).unwrap())
+                .unwrap(),
+            _ => panic!("expected a round one package"),
+        }
+    }
+    let packages: Vec<Vec<u8>> = coordinator
+        .participants()
+        .iter()
+        .map(|p| p.to_bytes())
+        .collect();
+    for to_participant in participants.iter() {
+        to_participant
+            .send(ToParticipant::RoundOne(packages.clone()))
+            .unwrap();
+    }
+    println!("Round one: forwarded {} packages.", packages.len());
+
+    // Round two: collect the encrypted shares and route them.
+    while !coordinator.is_round_two_complete() {
+        match inbox.recv().unwrap() {
+            ToCoordinator::RoundTwo(shares) => {
+                let shares: Vec<EncryptedSecretShare> = shares
+                    .iter()
+                    .map(|bytes| EncryptedSecretShare::from_bytes(bytes).unwrap())
+                    .collect();
+                coordinator.include_encrypted_shares(&shares).unwrap();
+            }
+            _ => panic!("expected round two shares"),
+        }
+    }
+    for (to_participant, index) in participants.iter().zip(1..) {
+        let shares = coordinator
+            .encrypted_shares_for(index)
+            .iter()
+            .map(|share| share.to_bytes())
+            .collect();
+        to_participant
+            .send(ToParticipant::RoundTwo(shares))
+            .unwrap();
+    }
+    println!("Round two: routed the encrypted shares.");
+
+    // Everyone should agree on the group key.
+    let mut group_key: Option<GroupKey> = None;
+    let mut signers = Vec::new();
+    for _ in 0..params.n {
+        match inbox.recv().unwrap() {
+            ToCoordinator::Finished(index, key, public_key, comshares) => {
+                let key = GroupKey::from_bytes_strict(&key).unwrap();
+                assert!(group_key.map_or(true, |group_key| group_key == key));
+                group_key = Some(key);
+                signers.push((
+                    index,
+                    IndividualPublicKey::from_bytes(&public_key).unwrap(),
+                    PublicCommitmentShareList::from_bytes(&comshares).unwrap(),
+                ));
+            }
+            _ => panic!("expected the outcome of the key generation"),
+        }
+    }
+    let group_key = group_key.unwrap();
+    println!(
+        "All participants agree on the group key {:02x?}.",
+        group_key.to_bytes()
+    );
+
+    // Sign with the first t participants.
+    signers.sort_by_key(|(index, _, _)| *index);
+    signers.truncate(params.t as usize);
+    let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    for (index, public_key, comshares) in signers.iter() {
+        aggregator
+            .include_signer(*index, comshares.commitments[0], public_key.clone())
+            .unwrap();
+    }
+    for (to_participant, index) in participants.iter().zip(1..) {
+        let request = match signers.iter().any(|(i, _, _)| *i == index) {
+            true => Some(aggregator.signing_request(index, 0).unwrap().to_bytes()),
+            false => None,
+        };
+        to_participant
+            .send(ToParticipant::SigningRequest(request))
+            .unwrap();
+    }
+    println!(
+        "Requested partial signatures from {} signers.",
+        signers.len()
+    );
+
+    for _ in 0..signers.len() {
+        match inbox.recv().unwrap() {
+            ToCoordinator::SigningResponse(bytes) => aggregator
+                .include_signing_response(SigningResponseBundle::from_bytes(&bytes).unwrap())
+                .unwrap(),
+            _ => panic!("expected a signing response"),
+        }
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+    let signature = ThresholdSignature::from_bytes(&signature.to_bytes()).unwrap();
+    let message_hash = compute_message_hash(&context[..], &message[..]);
+    signature.verify(&group_key, &message_hash).unwrap();
+    println!("Verified the threshold signature over the message.");
+}
@@ -0,0 +1,1890 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Attestation of the outcome of a distributed key generation.
+//!
+//! Once the key generation is finished, every participant signs the
+//! [`GroupPublicInfo`], i.e. the group key and the roster of participants,
+//! with its long-term Diffie-Hellman key.  The signatures are collected into a
+//! [`KeygenAttestation`], a single artifact proving that all \\(n\\)
+//! participants agree on the group key and roster.
+//!
+//! Likewise, once the complaints of a key generation have been adjudicated,
+//! the remaining participants co-sign a [`ResolutionRecord`] stating which
+//! dealers were disqualified, so that downstream automation can trust the
+//! qualified set without verifying the complaints again.
+//!
+//! Finally, every participant can export a [`DkgReceipt`] of the group key
+//! and roster it saw, signed on its own, for governance audit trails, and
+//! the participants of a resharing a [`RefreshProof`] that it did not change
+//! the group key.
+//!
+//! A third party holding none of the keys can also check the public messages
+//! of a key generation after the fact with [`audit_dkg`].
+//!
+//! Signers can also sign the commitment shares they publish for signing with
+//! [`sign_commitment_share`], so that an aggregator requiring it can check
+//! that no commitment share was chosen or substituted by the coordinator.
+
+#[cfg(feature = "std")]
+use std::string::ToString;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use core::convert::TryInto;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+
+use rand::CryptoRng;
+use rand::RngCore;
+
+use sha2::Digest;
+use sha2::{Sha256, Sha512};
+
+use crate::encoding::decode_point_checked;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_point;
+use crate::encoding::encode_scalar;
+use crate::keygen::DHPrivateKey;
+use crate::keygen::DHPublicKey;
+use crate::keygen::EncryptedSecretShare;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::Participant;
+use crate::keygen::VerifiableSecretSharingCommitment;
+use crate::parameters::Parameters;
+use crate::roster::RosterTree;
+use crate::signature::calculate_lagrange_coefficients;
+
+/// The public outcome of a distributed key generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupPublicInfo {
+    /// The parameters of the group.
+    pub parameters: Parameters,
+    /// The group public key.
+    pub group_key: GroupKey,
+    /// The index and long-term Diffie-Hellman public key of every
+    /// participant, sorted by index.
+    pub roster: Vec<(u32, DHPublicKey)>,
+}
+
+impl GroupPublicInfo {
+    /// Gather the public outcome of a distributed key generation between all
+    /// the `participants`, which resulted in the `group_key`.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidNumberOfParticipants` error if there are not exactly
+    /// \\(n\\) distinct participants.
+    pub fn new(
+        parameters: Parameters,
+        group_key: GroupKey,
+        participants: &[Participant],
+    ) -> Result<GroupPublicInfo, Error> {
+        let mut roster: Vec<(u32, DHPublicKey)> = participants
+            .iter()
+            .map(|p| (p.index, p.dh_public_key.clone()))
+            .collect();
+        roster.sort_by_key(|(index, _)| *index);
+        roster.dedup_by_key(|(index, _)| *index);
+
+        if roster.len() != parameters.n as usize || roster.len() != participants.len() {
+            return Err(Error::InvalidNumberOfParticipants(
+                participants.len(),
+                parameters.n,
+            ));
+        }
+
+        Ok(GroupPublicInfo {
+            parameters,
+            group_key,
+            roster,
+        })
+    }
+
+    /// Serialise this group public information to a Vec of bytes
+    ///
+    /// The serialisation is canonical: the roster is written sorted by
+    /// index, whatever its order in this group public information.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::with_capacity(44 + 36 * self.roster.len());
+        res.extend_from_slice(&self.parameters.to_bytes());
+        res.extend_from_slice(&self.group_key.to_bytes());
+
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.roster.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        let mut roster: Vec<&(u32, DHPublicKey)> = self.roster.iter().collect();
+        roster.sort_by_key(|(index, _)| *index);
+        for (index, dh_public_key) in roster {
+            res.extend_from_slice(&index.to_le_bytes());
+            res.extend_from_slice(&dh_public_key.to_bytes());
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `GroupPublicInfo`
+    pub fn from_bytes(bytes: &[u8]) -> Result<GroupPublicInfo, Error> {
+        let parameters = Parameters::from_bytes(
+            bytes
+                .get(0..8)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let group_key = GroupKey::from_bytes_strict(
+            bytes
+                .get(8..40)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let len = u32::from_le_bytes(
+            bytes
+                .get(40..44)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+
+        let mut index_slice = 44usize;
+        let mut roster: Vec<(u32, DHPublicKey)> = Vec::new();
+        for _ in 0..len {
+            let entry = bytes
+                .get(index_slice..index_slice + 36)
+                .ok_or(Error::SerialisationError)?;
+            let index = u32::from_le_bytes(
+                entry[0..4]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            );
+            if roster.last().map_or(false, |(last, _)| *last >= index) {
+                return Err(Error::SerialisationError);
+            }
+            let dh_public_key = DHPublicKey::from_bytes(
+                entry[4..36]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            )?;
+            roster.push((index, dh_public_key));
+            index_slice += 36;
+        }
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(GroupPublicInfo {
+            parameters,
+            group_key,
+            roster,
+        })
+    }
+
+    /// The Merkle tree over the roster, whose root is bound in the
+    /// [`GroupPublicInfo::hash`].
+    ///
+    /// # Returns
+    ///
+    /// A `DuplicateParticipant` error if an index appears twice in the roster.
+    pub fn roster_tree(&self) -> Result<RosterTree, Error> {
+        RosterTree::new(&self.roster)
+    }
+
+    /// Compute the hash of this group public information, which is what the
+    /// participants attest.
+    ///
+    /// The roster is bound through the root of its [`RosterTree`], so that a
+    /// light verifier holding a [`RosterProof`](crate::roster::RosterProof)
+    /// can check a participant's membership against an attested root.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+
+        h.update(b"ICE-FROST-GROUP-INFO");
+        h.update(self.parameters.to_bytes());
+        h.update(self.group_key.to_bytes());
+        // A roster with duplicates can neither be attested nor verified.
+        h.update(self.roster_tree().map_or([0u8; 32], |tree| tree.root()));
+
+        let mut output = [0u8; 32];
+
+        output.copy_from_slice(h.finalize().as_slice());
+        output
+    }
+
+    /// Compute the hash of the canonical serialisation of this group public
+    /// information, which does not depend on the order of its roster.
+    ///
+    /// Unlike [`GroupPublicInfo::hash`], this covers the Diffie-Hellman keys
+    /// of the roster directly rather than through its [`RosterTree`], whose
+    /// root depends on the order of the leaves.
+    pub fn canonical_hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+
+        h.update(b"ICE-FROST-GROUP-INFO-CANONICAL");
+        h.update(self.to_bytes());
+
+        h.finalize().into()
+    }
+
+    /// Attest this group public information as the participant with the
+    /// given `index`, with its long-term `dh_private_key`.
+    ///
+    /// # Returns
+    ///
+    /// An [`AttestationSignature`], or an `InvalidAttestation` error if the
+    /// participant is not in the roster under the corresponding public key.
+    pub fn attest(
+        &self,
+        index: u32,
+        dh_private_key: &DHPrivateKey,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<AttestationSignature, Error> {
+        self.roster_tree()?;
+
+        sign_hash(
+            ATTESTATION_TAG,
+            &self.hash(),
+            &self.roster,
+            index,
+            dh_private_key,
+            &mut rng,
+        )
+    }
+
+    /// Check an [`AttestationSignature`] of this group public information.
+    pub fn verify_attestation(&self, signature: &AttestationSignature) -> Result<(), Error> {
+        self.roster_tree()?;
+
+        verify_hash(ATTESTATION_TAG, &self.hash(), &self.roster, signature)
+    }
+}
+
+const ATTESTATION_TAG: &[u8] = b"ICE-FROST-ATTESTATION";
+const RESOLUTION_TAG: &[u8] = b"ICE-FROST-RESOLUTION";
+const RECEIPT_TAG: &[u8] = b"ICE-FROST-DKG-RECEIPT";
+const COMMITMENT_TAG: &[u8] = b"ICE-FROST-COMMITMENT-SHARE";
+
+fn dh_public_key(roster: &[(u32, DHPublicKey)], index: u32) -> Option<&DHPublicKey> {
+    roster.iter().find(|(i, _)| *i == index).map(|(_, key)| key)
+}
+
+fn challenge(
+    tag: &[u8],
+    hash: &[u8; 32],
+    index: u32,
+    public_key: &RistrettoPoint,
+    R: &RistrettoPoint,
+) -> Scalar {
+    let mut h = Sha512::new();
+
+    h.update(tag);
+    h.update(index.to_le_bytes());
+    h.update(public_key.compress().as_bytes());
+    h.update(R.compress().as_bytes());
+    h.update(hash);
+
+    Scalar::from_hash(h)
+}
+
+/// The root of the [`RosterTree`] over the `roster` sorted by index, so
+/// that it does not depend on the order in which participants were received.
+fn sorted_roster_root(roster: &[(u32, DHPublicKey)]) -> Result<[u8; 32], Error> {
+    let mut roster = roster.to_vec();
+    roster.sort_by_key(|(index, _)| *index);
+
+    Ok(RosterTree::new(&roster)?.root())
+}
+
+/// Read a little-endian `u32` at `index_slice`, and move past it.
+fn read_u32(bytes: &[u8], index_slice: &mut usize) -> Result<u32, Error> {
+    let value = u32::from_le_bytes(
+        bytes
+            .get(*index_slice..*index_slice + 4)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    );
+    *index_slice += 4;
+
+    Ok(value)
+}
+
+/// Sign a `hash` as the participant with the given `index` of the `roster`,
+/// with its long-term `dh_private_key`.
+fn sign_hash(
+    tag: &[u8],
+    hash: &[u8; 32],
+    roster: &[(u32, DHPublicKey)],
+    index: u32,
+    dh_private_key: &DHPrivateKey,
+    mut rng: impl RngCore + CryptoRng,
+) -> Result<AttestationSignature, Error> {
+    let public_key = &RISTRETTO_BASEPOINT_TABLE * &dh_private_key.0;
+    match dh_public_key(roster, index) {
+        Some(key) if key.0.compress() == public_key.compress() => (),
+        _ => return Err(Error::InvalidAttestation(index)),
+    }
+
+    let k = Scalar::random(&mut rng);
+    let R = &RISTRETTO_BASEPOINT_TABLE * &k;
+    let c = challenge(tag, hash, index, &public_key, &R);
+
+    Ok(AttestationSignature {
+        index,
+        R,
+        z: k + c * dh_private_key.0,
+    })
+}
+
+/// Check a signature of a `hash` by a participant of the `roster`.
+fn verify_hash(
+    tag: &[u8],
+    hash: &[u8; 32],
+    roster: &[(u32, DHPublicKey)],
+    signature: &AttestationSignature,
+) -> Result<(), Error> {
+    let public_key = dh_public_key(roster, signature.index)
+        .ok_or(Error::InvalidAttestation(signature.index))?
+        .0;
+    let c = challenge(tag, hash, signature.index, &public_key, &signature.R);
+
+    match &RISTRETTO_BASEPOINT_TABLE * &signature.z == signature.R + c * public_key {
+        true => Ok(()),
+        false => Err(Error::InvalidAttestation(signature.index)),
+    }
+}
+
+/// A participant's signature of a [`GroupPublicInfo`] with its long-term
+/// Diffie-Hellman key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationSignature {
+    /// The index of the attesting participant.
+    pub index: u32,
+    R: RistrettoPoint,
+    z: Scalar,
+}
+
+impl AttestationSignature {
+    /// Serialise this attestation signature to an array of 68 bytes.
+    pub fn to_bytes(&self) -> [u8; 68] {
+        let mut bytes = [0u8; 68];
+
+        bytes[..4].copy_from_slice(&self.index.to_le_bytes());
+        bytes[4..36].copy_from_slice(&encode_point(&self.R));
+        bytes[36..].copy_from_slice(&encode_scalar(&self.z));
+
+        bytes
+    }
+
+    /// Attempt to deserialise an attestation signature from an array of 68 bytes.
+    pub fn from_bytes(bytes: &[u8; 68]) -> Result<AttestationSignature, Error> {
+        let index = u32::from_le_bytes(
+            bytes[0..4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let R = decode_point_checked(
+            &bytes[4..36]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let z = decode_scalar_canonical(
+            &bytes[36..68]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        Ok(AttestationSignature { index, R, z })
+    }
+}
+
+/// A participant's signed receipt of the outcome of a distributed key
+/// generation it took part in, i.e. the group key and the roster it saw.
+///
+/// A receipt is produced by
+/// [`DistributedKeyGeneration::finish_with_receipt`] and checked with
+/// [`verify_receipt`].  Unlike a [`KeygenAttestation`], it is signed by a
+/// single participant, and is meant to be archived for audit rather than
+/// collected into a proof of agreement.
+///
+/// [`DistributedKeyGeneration::finish_with_receipt`]: crate::keygen::DistributedKeyGeneration::finish_with_receipt
+#[derive(Clone, Debug, PartialEq)]
+pub struct DkgReceipt {
+    /// The outcome of the key generation as seen by the participant.
+    pub info: GroupPublicInfo,
+    /// The participant's signature of the outcome.
+    pub signature: AttestationSignature,
+}
+
+impl DkgReceipt {
+    /// Sign a receipt of the outcome `info` as the participant with the given
+    /// `index`, with its long-term `dh_private_key`.
+    pub(crate) fn sign(
+        info: GroupPublicInfo,
+        index: u32,
+        dh_private_key: &DHPrivateKey,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<DkgReceipt, Error> {
+        info.roster_tree()?;
+        // The signer is not necessarily in the roster, as when it only
+        // received shares from the dealers of a resharing.
+        let public_key = DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &dh_private_key.0);
+        let signature = sign_hash(
+            RECEIPT_TAG,
+            &info.hash(),
+            &[(index, public_key)],
+            index,
+            dh_private_key,
+            &mut rng,
+        )?;
+
+        Ok(DkgReceipt { info, signature })
+    }
+
+    /// The index of the participant which signed this receipt.
+    pub fn index(&self) -> u32 {
+        self.signature.index
+    }
+
+    /// Serialise this receipt to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = self.signature.to_bytes().to_vec();
+        res.extend_from_slice(&self.info.to_bytes());
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `DkgReceipt`
+    ///
+    /// The signature is not verified, which is the purpose of
+    /// [`verify_receipt`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<DkgReceipt, Error> {
+        let signature = AttestationSignature::from_bytes(
+            bytes
+                .get(0..68)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let info = GroupPublicInfo::from_bytes(&bytes[68..])?;
+
+        Ok(DkgReceipt { info, signature })
+    }
+}
+
+/// Verify that a [`DkgReceipt`] was signed by the holder of the long-term
+/// Diffie-Hellman key `dh_public_key`.
+///
+/// # Returns
+///
+/// An `InvalidAttestation` error naming the signer of the receipt if the
+/// signature does not verify, or a `DuplicateParticipant` error if an index
+/// appears twice in the roster of the receipt.
+pub fn verify_receipt(receipt: &DkgReceipt, dh_public_key: &DHPublicKey) -> Result<(), Error> {
+    receipt.info.roster_tree()?;
+
+    verify_hash(
+        RECEIPT_TAG,
+        &receipt.info.hash(),
+        &[(receipt.index(), dh_public_key.clone())],
+        &receipt.signature,
+    )
+}
+
+/// The hash of the `published_commitment_share` of the signer with this
+/// `index`.
+fn commitment_share_hash(
+    index: u32,
+    published_commitment_share: &(RistrettoPoint, RistrettoPoint),
+) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(index.to_le_bytes());
+    h.update(encode_point(&published_commitment_share.0));
+    h.update(encode_point(&published_commitment_share.1));
+
+    h.finalize().into()
+}
+
+/// Sign the `published_commitment_share` of the signer with the given
+/// `index` with its long-term `dh_private_key`, for an aggregator requiring
+/// signed commitment shares with
+/// [`SignatureAggregator::require_signed_commitments`].
+///
+/// [`SignatureAggregator::require_signed_commitments`]: crate::signature::SignatureAggregator::require_signed_commitments
+pub fn sign_commitment_share(
+    index: u32,
+    published_commitment_share: &(RistrettoPoint, RistrettoPoint),
+    dh_private_key: &DHPrivateKey,
+    mut rng: impl RngCore + CryptoRng,
+) -> AttestationSignature {
+    let public_key = DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &dh_private_key.0);
+
+    // Unwrapping cannot panic here, as the roster holds our own key.
+    sign_hash(
+        COMMITMENT_TAG,
+        &commitment_share_hash(index, published_commitment_share),
+        &[(index, public_key)],
+        index,
+        dh_private_key,
+        &mut rng,
+    )
+    .unwrap()
+}
+
+/// Verify that the `published_commitment_share` of the signer with the given
+/// `index` was signed by its long-term Diffie-Hellman key in the `roster`.
+///
+/// # Returns
+///
+/// An `InvalidAttestation` error naming the signer if the signature does not
+/// verify, was made by another signer, or if the signer is not in the roster.
+pub fn verify_commitment_share(
+    index: u32,
+    published_commitment_share: &(RistrettoPoint, RistrettoPoint),
+    signature: &AttestationSignature,
+    roster: &[(u32, DHPublicKey)],
+) -> Result<(), Error> {
+    if signature.index != index {
+        return Err(Error::InvalidAttestation(index));
+    }
+
+    verify_hash(
+        COMMITMENT_TAG,
+        &commitment_share_hash(index, published_commitment_share),
+        roster,
+        signature,
+    )
+}
+
+/// Audit the public transcript of a distributed key generation, i.e. the
+/// serialised [`Participant`] of every dealer in `round_one`, and the
+/// serialised [`EncryptedSecretShare`]s they sent in `round_two`.
+///
+/// The audit checks that every dealer published \\(t\\) commitments and
+/// valid proofs of knowledge of its secret key and of its Diffie-Hellman
+/// private key, and sent exactly one share to every participant.  The
+/// shares themselves cannot be decrypted without the keys of their
+/// recipients, and are therefore not checked against the commitments.
+///
+/// # Returns
+///
+/// The group key of the key generation, or:
+///
+/// * an `InvalidNumberOfParticipants` error if there are not \\(n\\) dealers,
+/// * a `DuplicateParticipant` error if an index appears twice in `round_one`,
+/// * an `UnknownParticipant` error for a share from an unknown dealer,
+/// * an `InvalidTranscript` error naming the participant whose messages are
+///   inconsistent or whose proofs do not verify, or
+/// * an `InvalidGroupKey` error if the group key is the identity.
+pub fn audit_dkg(
+    parameters: &Parameters,
+    context_string: &str,
+    round_one: &[Vec<u8>],
+    round_two: &[Vec<u8>],
+) -> Result<GroupKey, Error> {
+    let mut participants = Vec::with_capacity(round_one.len());
+    for bytes in round_one.iter() {
+        participants.push(Participant::from_bytes_with_parameters(bytes, parameters)?);
+    }
+    if participants.len() != parameters.n as usize {
+        return Err(Error::InvalidNumberOfParticipants(
+            participants.len(),
+            parameters.n,
+        ));
+    }
+
+    let mut indices = BTreeSet::new();
+    let mut public_keys = Vec::with_capacity(participants.len());
+    for participant in participants.iter() {
+        let index = participant.index;
+        if !indices.insert(index) {
+            return Err(Error::DuplicateParticipant(index));
+        }
+
+        let invalid = |reason| Error::InvalidTranscript {
+            participant: index,
+            reason,
+        };
+        if participant.commitments.index != index {
+            return Err(invalid("commitments of another participant"));
+        }
+        // The commitments were checked to hold t points on deserialisation.
+        let public_key = participant
+            .public_key()
+            .ok_or_else(|| invalid("no commitments"))?;
+        participant
+            .proof_of_secret_key
+            .verify(&index, public_key, context_string)
+            .map_err(|_| invalid("invalid proof of knowledge of the secret key"))?;
+        participant
+            .proof_of_dh_private_key
+            .verify(&index, &participant.dh_public_key, context_string)
+            .map_err(|_| invalid("invalid proof of knowledge of the DH private key"))?;
+
+        public_keys.push((index, public_key));
+    }
+
+    let mut shares = BTreeSet::new();
+    for bytes in round_two.iter() {
+        let share = EncryptedSecretShare::from_bytes(bytes)?;
+        if !indices.contains(&share.sender_index) {
+            return Err(Error::UnknownParticipant(share.sender_index));
+        }
+        if !indices.contains(&share.receiver_index) {
+            return Err(Error::InvalidTranscript {
+                participant: share.sender_index,
+                reason: "share for an unknown participant",
+            });
+        }
+        if !shares.insert((share.sender_index, share.receiver_index)) {
+            return Err(Error::InvalidTranscript {
+                participant: share.sender_index,
+                reason: "two shares for the same participant",
+            });
+        }
+    }
+    for sender in indices.iter() {
+        if indices
+            .iter()
+            .any(|receiver| !shares.contains(&(*sender, *receiver)))
+        {
+            return Err(Error::InvalidTranscript {
+                participant: *sender,
+                reason: "missing share",
+            });
+        }
+    }
+
+    // The group key is the interpolation at 0 of the public keys of the
+    // dealers, as in `DistributedKeyGeneration::<RoundTwo>::finish`.
+    let all_indices: Vec<u32> = indices.into_iter().collect();
+    let mut group_key = RistrettoPoint::identity();
+    for (index, public_key) in public_keys.into_iter() {
+        let coefficient = calculate_lagrange_coefficients(&index, &all_indices)
+            .map_err(|error| Error::Custom(error.to_string()))?;
+        group_key += coefficient * public_key;
+    }
+    if group_key == RistrettoPoint::identity() {
+        return Err(Error::InvalidGroupKey);
+    }
+
+    Ok(GroupKey::new(group_key))
+}
+
+/// The attestation of a [`GroupPublicInfo`] by all of its participants.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeygenAttestation {
+    /// The attested group public information.
+    pub info: GroupPublicInfo,
+    /// The signatures collected thus far, sorted by participant index.
+    signatures: Vec<AttestationSignature>,
+}
+
+impl KeygenAttestation {
+    /// Start collecting attestations of the given group public information.
+    pub fn new(info: GroupPublicInfo) -> KeygenAttestation {
+        KeygenAttestation {
+            info,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Collect a participant's [`AttestationSignature`].
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidAttestation` error if the signature does not verify, in
+    /// which case it is not collected.
+    pub fn add(&mut self, signature: AttestationSignature) -> Result<(), Error> {
+        self.info.verify_attestation(&signature)?;
+
+        if let Err(position) = self
+            .signatures
+            .binary_search_by_key(&signature.index, |s| s.index)
+        {
+            self.signatures.insert(position, signature);
+        }
+
+        Ok(())
+    }
+
+    /// The signatures collected thus far, sorted by participant index.
+    pub fn signatures(&self) -> &[AttestationSignature] {
+        &self.signatures
+    }
+
+    /// Verify that every participant of the roster attested the group public
+    /// information, for the expected `group_key`.
+    ///
+    /// This only requires the attestation itself, and can be performed by
+    /// anyone.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidGroupKey` error if the attested group key is not the
+    /// expected one, a `MissingAttestation` error if a participant did not
+    /// attest, and an `InvalidAttestation` error if a signature is invalid.
+    pub fn verify(&self, group_key: &GroupKey) -> Result<(), Error> {
+        if self.info.group_key != *group_key {
+            return Err(Error::InvalidGroupKey);
+        }
+        if self.info.roster.len() != self.info.parameters.n as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                self.info.roster.len(),
+                self.info.parameters.n,
+            ));
+        }
+
+        for (index, _) in self.info.roster.iter() {
+            let signature = self
+                .signatures
+                .iter()
+                .find(|s| s.index == *index)
+                .ok_or(Error::MissingAttestation(*index))?;
+            self.info.verify_attestation(signature)?;
+        }
+        if let Some(signature) = self
+            .signatures
+            .iter()
+            .find(|s| !self.info.roster.iter().any(|(index, _)| *index == s.index))
+        {
+            return Err(Error::InvalidAttestation(signature.index));
+        }
+
+        Ok(())
+    }
+
+    /// Serialise this keygen attestation to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let info = self.info.to_bytes();
+
+        let mut res: Vec<u8> = Vec::with_capacity(8 + info.len() + 68 * self.signatures.len());
+        res.extend_from_slice(&TryInto::<u32>::try_into(info.len()).unwrap().to_le_bytes());
+        res.extend_from_slice(&info);
+
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.signatures.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for signature in self.signatures.iter() {
+            res.extend_from_slice(&signature.to_bytes());
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `KeygenAttestation`
+    ///
+    /// The signatures are not verified, which is the purpose of
+    /// [`KeygenAttestation::verify`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<KeygenAttestation, Error> {
+        let info_len = u32::from_le_bytes(
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        ) as usize;
+        let info = GroupPublicInfo::from_bytes(
+            bytes
+                .get(4..4 + info_len)
+                .ok_or(Error::SerialisationError)?,
+        )?;
+        let mut index_slice = 4 + info_len;
+
+        let len = u32::from_le_bytes(
+            bytes
+                .get(index_slice..index_slice + 4)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        index_slice += 4;
+
+        let mut signatures: Vec<AttestationSignature> = Vec::new();
+        for _ in 0..len {
+            let signature = AttestationSignature::from_bytes(
+                bytes
+                    .get(index_slice..index_slice + 68)
+                    .ok_or(Error::SerialisationError)?
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            )?;
+            if signatures
+                .last()
+                .map_or(false, |last: &AttestationSignature| {
+                    last.index >= signature.index
+                })
+            {
+                return Err(Error::SerialisationError);
+            }
+            signatures.push(signature);
+            index_slice += 68;
+        }
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(KeygenAttestation { info, signatures })
+    }
+}
+
+/// The outcome of the adjudication of the complaints of a distributed key
+/// generation, co-signed by the remaining participants with their long-term
+/// Diffie-Hellman keys.
+///
+/// A record is produced by [`ComplaintAdjudicator::resolution`], which
+/// every participant runs on its own, so that each of them signs the record
+/// it computed rather than one handed over by a coordinator.
+///
+/// [`ComplaintAdjudicator::resolution`]: crate::keygen::ComplaintAdjudicator::resolution
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionRecord {
+    /// The parameters of the key generation.
+    pub parameters: Parameters,
+    /// The root of the [`RosterTree`] of the participants of the key
+    /// generation, sorted by index.
+    pub roster_root: [u8; 32],
+    /// The indices of the disqualified dealers, sorted.
+    pub disqualified: Vec<u32>,
+    /// The indices of the remaining participants, sorted.
+    pub qualified: Vec<u32>,
+    /// The signatures collected thus far, sorted by participant index.
+    signatures: Vec<AttestationSignature>,
+}
+
+impl ResolutionRecord {
+    /// Record the disqualification of the `disqualified` dealers of a key
+    /// generation between the participants of the `roster`.
+    ///
+    /// # Returns
+    ///
+    /// A `DuplicateParticipant` error if an index appears twice in the
+    /// roster, or an `UnknownParticipant` error if a disqualified dealer is
+    /// not in the roster.
+    pub fn new(
+        parameters: Parameters,
+        roster: &[(u32, DHPublicKey)],
+        disqualified: &[u32],
+    ) -> Result<ResolutionRecord, Error> {
+        let roster_root = sorted_roster_root(roster)?;
+        let disqualified: BTreeSet<u32> = disqualified.iter().copied().collect();
+        if let Some(index) = disqualified
+            .iter()
+            .find(|index| dh_public_key(roster, **index).is_none())
+        {
+            return Err(Error::UnknownParticipant(*index));
+        }
+
+        let mut qualified: Vec<u32> = roster
+            .iter()
+            .map(|(index, _)| *index)
+            .filter(|index| !disqualified.contains(index))
+            .collect();
+        qualified.sort_unstable();
+
+        Ok(ResolutionRecord {
+            parameters,
+            roster_root,
+            disqualified: disqualified.into_iter().collect(),
+            qualified,
+            signatures: Vec::new(),
+        })
+    }
+
+    /// Compute the hash of the outcome stated by this record, which is what
+    /// the participants sign.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+
+        h.update(b"ICE-FROST-RESOLUTION-RECORD");
+        h.update(self.outcome_bytes());
+
+        h.finalize().into()
+    }
+
+    /// Sign this record as the qualified participant with the given `index`
+    /// of the `roster`, with its long-term `dh_private_key`.
+    ///
+    /// # Returns
+    ///
+    /// An [`AttestationSignature`], an `InvalidResolutionRecord` error if the
+    /// roster is not the one of the record, or an `InvalidAttestation` error if the
+    /// participant is not qualified, or not in the roster under the
+    /// corresponding public key.
+    pub fn sign(
+        &self,
+        index: u32,
+        dh_private_key: &DHPrivateKey,
+        roster: &[(u32, DHPublicKey)],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<AttestationSignature, Error> {
+        self.check_roster(roster)?;
+        if self.qualified.binary_search(&index).is_err() {
+            return Err(Error::InvalidAttestation(index));
+        }
+
+        sign_hash(
+            RESOLUTION_TAG,
+            &self.hash(),
+            roster,
+            index,
+            dh_private_key,
+            &mut rng,
+        )
+    }
+
+    /// Collect a qualified participant's signature of this record.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidResolutionRecord` error if the roster is not the one of
+    /// the record, or an `InvalidAttestation` error if the signature does not
+    /// verify, or does not come from a qualified participant, in which case
+    /// it is not collected.
+    pub fn add(
+        &mut self,
+        signature: AttestationSignature,
+        roster: &[(u32, DHPublicKey)],
+    ) -> Result<(), Error> {
+        self.check_roster(roster)?;
+        self.verify_signature(&signature, roster)?;
+
+        if let Err(position) = self
+            .signatures
+            .binary_search_by_key(&signature.index, |s| s.index)
+        {
+            self.signatures.insert(position, signature);
+        }
+
+        Ok(())
+    }
+
+    /// The signatures collected thus far, sorted by participant index.
+    pub fn signatures(&self) -> &[AttestationSignature] {
+        &self.signatures
+    }
+
+    /// Verify that a quorum of \\(t\\) qualified participants of the
+    /// `roster` signed this record.
+    ///
+    /// This only requires the record and the roster, and can be performed by
+    /// anyone.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidResolutionRecord` error if the roster is not the one of the
+    /// record, an `InvalidAttestation` error if a signature is invalid or does not
+    /// come from a qualified participant, and a `MissingAttestation` error
+    /// naming the first qualified participant which did not sign if there
+    /// are fewer than \\(t\\) signatures.
+    pub fn verify(&self, roster: &[(u32, DHPublicKey)]) -> Result<(), Error> {
+        self.check_roster(roster)?;
+
+        for signature in self.signatures.iter() {
+            self.verify_signature(signature, roster)?;
+        }
+
+        if self.signatures.len() < self.parameters.t as usize {
+            let missing = self
+                .qualified
+                .iter()
+                .find(|index| {
+                    self.signatures
+                        .binary_search_by_key(*index, |s| s.index)
+                        .is_err()
+                })
+                .copied()
+                .unwrap_or(0);
+            return Err(Error::MissingAttestation(missing));
+        }
+
+        Ok(())
+    }
+
+    fn check_roster(&self, roster: &[(u32, DHPublicKey)]) -> Result<(), Error> {
+        match sorted_roster_root(roster)? == self.roster_root {
+            true => Ok(()),
+            false => Err(Error::InvalidResolutionRecord),
+        }
+    }
+
+    fn verify_signature(
+        &self,
+        signature: &AttestationSignature,
+        roster: &[(u32, DHPublicKey)],
+    ) -> Result<(), Error> {
+        if self.qualified.binary_search(&signature.index).is_err() {
+            return Err(Error::InvalidAttestation(signature.index));
+        }
+
+        verify_hash(RESOLUTION_TAG, &self.hash(), roster, signature)
+    }
+
+    fn outcome_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> =
+            Vec::with_capacity(48 + 4 * (self.disqualified.len() + self.qualified.len()));
+        res.extend_from_slice(&self.parameters.to_bytes());
+        res.extend_from_slice(&self.roster_root);
+
+        for indices in [&self.disqualified, &self.qualified] {
+            res.extend_from_slice(
+                &TryInto::<u32>::try_into(indices.len())
+                    .unwrap()
+                    .to_le_bytes(),
+            );
+            for index in indices.iter() {
+                res.extend_from_slice(&index.to_le_bytes());
+            }
+        }
+
+        res
+    }
+
+    /// Serialise this resolution record, along with its signatures, to a Vec
+    /// of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = self.outcome_bytes();
+
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.signatures.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for signature in self.signatures.iter() {
+            res.extend_from_slice(&signature.to_bytes());
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `ResolutionRecord`
+    ///
+    /// The index lists must be strictly increasing and disjoint.  The
+    /// signatures are not verified, which is the purpose of
+    /// [`ResolutionRecord::verify`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<ResolutionRecord, Error> {
+        let parameters = Parameters::from_bytes(
+            bytes
+                .get(0..8)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let roster_root: [u8; 32] = bytes
+            .get(8..40)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let mut index_slice = 40usize;
+
+        let mut lists: [Vec<u32>; 2] = [Vec::new(), Vec::new()];
+        for list in lists.iter_mut() {
+            let len = read_u32(bytes, &mut index_slice)?;
+            for _ in 0..len {
+                let index = read_u32(bytes, &mut index_slice)?;
+                if list.last().map_or(false, |last| *last >= index) {
+                    return Err(Error::SerialisationError);
+                }
+                list.push(index);
+            }
+        }
+        let [disqualified, qualified] = lists;
+        if disqualified
+            .iter()
+            .any(|index| qualified.binary_search(index).is_ok())
+        {
+            return Err(Error::SerialisationError);
+        }
+
+        let len = read_u32(bytes, &mut index_slice)?;
+        let mut signatures: Vec<AttestationSignature> = Vec::new();
+        for _ in 0..len {
+            let signature = AttestationSignature::from_bytes(
+                bytes
+                    .get(index_slice..index_slice + 68)
+                    .ok_or(Error::SerialisationError)?
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            )?;
+            if signatures
+                .last()
+                .map_or(false, |last: &AttestationSignature| {
+                    last.index >= signature.index
+                })
+            {
+                return Err(Error::SerialisationError);
+            }
+            signatures.push(signature);
+            index_slice += 68;
+        }
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(ResolutionRecord {
+            parameters,
+            roster_root,
+            disqualified,
+            qualified,
+            signatures,
+        })
+    }
+}
+
+/// Evidence that a refresh or a resharing of a group key did not change it.
+///
+/// The dealers of a resharing share their old secret keys, so that the
+/// constant terms of their polynomials interpolate to the old group secret
+/// key: their refresh polynomials, i.e. their polynomials minus the old
+/// group secret key, sum to zero at the constant term once weighted by
+/// their Lagrange coefficients.  This is publicly checkable from the
+/// commitments of the dealers, which the proof holds.
+///
+/// A proof is produced by
+/// [`DistributedKeyGeneration::finish_with_refresh_proof`] and checked with
+/// [`RefreshProof::verify`].  The digest of the old commitments, i.e. of the
+/// constant terms committed to by the dealers, can moreover be matched
+/// against the public shares of the old group.
+///
+/// [`DistributedKeyGeneration::finish_with_refresh_proof`]: crate::keygen::DistributedKeyGeneration::finish_with_refresh_proof
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefreshProof {
+    /// The digest of the constant terms committed to by the dealers, which
+    /// are their public shares in the old group.
+    pub old_commitments_digest: [u8; 32],
+    /// The digest of the commitments of the dealers to their polynomials,
+    /// from which the new public shares are derived.
+    pub new_commitments_digest: [u8; 32],
+    /// The commitments of the dealers, sorted by index.
+    commitments: Vec<VerifiableSecretSharingCommitment>,
+}
+
+impl RefreshProof {
+    /// Gather the proof of a resharing from the `commitments` of its dealers.
+    pub(crate) fn new(commitments: &[VerifiableSecretSharingCommitment]) -> RefreshProof {
+        let mut commitments = commitments.to_vec();
+        commitments.sort_by_key(|commitment| commitment.index);
+
+        RefreshProof {
+            old_commitments_digest: old_commitments_digest(&commitments),
+            new_commitments_digest: new_commitments_digest(&commitments),
+            commitments,
+        }
+    }
+
+    /// The commitments of the dealers, sorted by index.
+    pub fn commitments(&self) -> &[VerifiableSecretSharingCommitment] {
+        &self.commitments
+    }
+
+    /// Verify that the refresh or resharing left the `old_group_key`
+    /// unchanged, and resulted in the group described by `new_public_info`.
+    ///
+    /// # Returns
+    ///
+    /// A `WrongCommitmentCount` error naming the first dealer whose
+    /// commitments do not match the new threshold, or an
+    /// `InvalidRefreshProof` error if the digests do not match the
+    /// commitments, the constant terms of the dealers do not interpolate to
+    /// the old group key, or the new group key differs from it.
+    pub fn verify(
+        &self,
+        old_group_key: &GroupKey,
+        new_public_info: &GroupPublicInfo,
+    ) -> Result<(), Error> {
+        if self.commitments.is_empty()
+            || self.old_commitments_digest != old_commitments_digest(&self.commitments)
+            || self.new_commitments_digest != new_commitments_digest(&self.commitments)
+        {
+            return Err(Error::InvalidRefreshProof);
+        }
+        for commitment in self.commitments.iter() {
+            commitment.check_count(new_public_info.parameters.t as usize)?;
+        }
+
+        let indices: Vec<u32> = self.commitments.iter().map(|c| c.index).collect();
+        let mut difference = -old_group_key.0;
+        for commitment in self.commitments.iter() {
+            let lambda = calculate_lagrange_coefficients(&commitment.index, &indices)
+                .map_err(|_| Error::InvalidRefreshProof)?;
+            difference += lambda * commitment.public_key().ok_or(Error::InvalidRefreshProof)?;
+        }
+
+        if difference != RistrettoPoint::identity() || new_public_info.group_key != *old_group_key {
+            return Err(Error::InvalidRefreshProof);
+        }
+
+        Ok(())
+    }
+
+    /// Serialise this refresh proof to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+        res.extend_from_slice(&self.old_commitments_digest);
+        res.extend_from_slice(&self.new_commitments_digest);
+
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.commitments.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for commitment in self.commitments.iter() {
+            res.extend_from_slice(&commitment.to_bytes());
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `RefreshProof`
+    ///
+    /// The commitments must be strictly sorted by index.  The digests are
+    /// not checked, which is part of [`RefreshProof::verify`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<RefreshProof, Error> {
+        let old_commitments_digest: [u8; 32] = bytes
+            .get(0..32)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let new_commitments_digest: [u8; 32] = bytes
+            .get(32..64)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let mut index_slice = 64usize;
+
+        let len = read_u32(bytes, &mut index_slice)?;
+        let mut commitments: Vec<VerifiableSecretSharingCommitment> = Vec::new();
+        for _ in 0..len {
+            let commitment = VerifiableSecretSharingCommitment::from_bytes(
+                bytes.get(index_slice..).ok_or(Error::SerialisationError)?,
+            )?;
+            if commitments
+                .last()
+                .map_or(false, |last| last.index >= commitment.index)
+            {
+                return Err(Error::SerialisationError);
+            }
+            index_slice += 8 + 32 * commitment.points().len();
+            commitments.push(commitment);
+        }
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(RefreshProof {
+            old_commitments_digest,
+            new_commitments_digest,
+            commitments,
+        })
+    }
+}
+
+fn old_commitments_digest(commitments: &[VerifiableSecretSharingCommitment]) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-REFRESH-OLD-COMMITMENTS");
+    h.update(
+        TryInto::<u32>::try_into(commitments.len())
+            .unwrap()
+            .to_le_bytes(),
+    );
+    for commitment in commitments.iter() {
+        h.update(commitment.index.to_le_bytes());
+        h.update(commitment.public_key().map_or([0u8; 32], encode_point));
+    }
+
+    h.finalize().into()
+}
+
+fn new_commitments_digest(commitments: &[VerifiableSecretSharingCommitment]) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-REFRESH-NEW-COMMITMENTS");
+    h.update(
+        TryInto::<u32>::try_into(commitments.len())
+            .unwrap()
+            .to_le_bytes(),
+    );
+    for commitment in commitments.iter() {
+        h.update(commitment.to_bytes());
+    }
+
+    h.finalize().into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keygen::{
+        ComplaintAdjudicator, DistributedKeyGeneration, DkgSigner, RoundOne, SecretKey,
+    };
+    use crate::test_utils::{run_full_dkg, Dkg};
+
+    use rand::rngs::OsRng;
+    use rand::seq::SliceRandom;
+
+    #[test]
+    fn audit_dkg_transcript() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, coeffs, dh_sk) = Participant::new_dealer(&params, i, "Φ", rng);
+            participants.push(p);
+            coefficients.push(coeffs);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let mut states = Vec::new();
+        let mut encrypted_shares = Vec::new();
+        for i in 0..params.n as usize {
+            let (state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &dh_secret_keys[i],
+                &participants[i].index,
+                &coefficients[i],
+                &participants,
+                "Φ",
+                rng,
+            )
+            .unwrap();
+            encrypted_shares.extend_from_slice(state.their_encrypted_secret_shares().unwrap());
+            states.push(state);
+        }
+
+        let my_encrypted_secret_shares = encrypted_shares
+            .iter()
+            .filter(|s| s.receiver_index == 1)
+            .cloned()
+            .collect();
+        let (group_key, _secret_key) = states
+            .remove(0)
+            .to_round_two(my_encrypted_secret_shares, rng)
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        let round_one: Vec<Vec<u8>> = participants.iter().map(|p| p.to_bytes()).collect();
+        let round_two: Vec<Vec<u8>> = encrypted_shares.iter().map(|s| s.to_bytes()).collect();
+
+        assert_eq!(
+            audit_dkg(&params, "Φ", &round_one, &round_two).unwrap(),
+            group_key
+        );
+        assert!(audit_dkg(&params, "Ψ", &round_one, &round_two).is_err());
+
+        // A tampered commitment no longer matches the proof of knowledge.
+        let mut tampered = participants[1].clone();
+        tampered
+            .commitments
+            .set_point(0, &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut rng))
+            .unwrap();
+        let mut tampered_round_one = round_one.clone();
+        tampered_round_one[1] = tampered.to_bytes();
+        assert!(matches!(
+            audit_dkg(&params, "Φ", &tampered_round_one, &round_two),
+            Err(Error::InvalidTranscript { participant: 2, .. })
+        ));
+
+        assert!(matches!(
+            audit_dkg(&params, "Φ", &round_one[..2], &round_two),
+            Err(Error::InvalidNumberOfParticipants(2, 3))
+        ));
+        let mut truncated_round_one = round_one.clone();
+        truncated_round_one[2].truncate(40);
+        assert!(audit_dkg(&params, "Φ", &truncated_round_one, &round_two).is_err());
+
+        let missing = encrypted_shares
+            .iter()
+            .position(|s| s.sender_index == 3 && s.receiver_index == 2)
+            .unwrap();
+        let mut missing_round_two = round_two.clone();
+        missing_round_two.remove(missing);
+        assert!(matches!(
+            audit_dkg(&params, "Φ", &round_one, &missing_round_two),
+            Err(Error::InvalidTranscript { participant: 3, .. })
+        ));
+
+        let mut duplicated_round_two = round_two.clone();
+        duplicated_round_two.push(round_two[0].clone());
+        assert!(audit_dkg(&params, "Φ", &round_one, &duplicated_round_two).is_err());
+    }
+
+    #[test]
+    fn group_info_canonical_serialisation() {
+        let random_key = || &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng);
+        let info = GroupPublicInfo {
+            parameters: Parameters { n: 7, t: 4 },
+            group_key: GroupKey::new(random_key()),
+            roster: (1..=7).map(|i| (i, DHPublicKey(random_key()))).collect(),
+        };
+        let bytes = info.to_bytes();
+
+        let mut shuffled = info.clone();
+        for _ in 0..16 {
+            shuffled.roster.shuffle(&mut OsRng);
+            assert_eq!(shuffled.to_bytes(), bytes);
+            assert_eq!(shuffled.canonical_hash(), info.canonical_hash());
+            assert_eq!(
+                GroupPublicInfo::from_bytes(&shuffled.to_bytes()).unwrap(),
+                info
+            );
+        }
+
+        shuffled.roster[0].1 = DHPublicKey(random_key());
+        assert_ne!(shuffled.canonical_hash(), info.canonical_hash());
+    }
+
+    #[test]
+    fn keygen_attestation() {
+        let params = Parameters { n: 3, t: 2 };
+        let Dkg {
+            group_key,
+            participants,
+            dh_secret_keys,
+            ..
+        } = run_full_dkg(&params);
+
+        let info = GroupPublicInfo::new(params, group_key, &participants).unwrap();
+        assert_eq!(GroupPublicInfo::from_bytes(&info.to_bytes()).unwrap(), info);
+
+        let mut attestation = KeygenAttestation::new(info.clone());
+        for (p, dh_sk) in participants.iter().zip(dh_secret_keys.iter()) {
+            // Every participant builds the group public information on its own.
+            let my_info = GroupPublicInfo::new(params, group_key, &participants).unwrap();
+            let signature = my_info.attest(p.index, dh_sk, OsRng).unwrap();
+            attestation.add(signature).unwrap();
+        }
+        assert!(attestation.verify(&group_key).is_ok());
+
+        // A light verifier can check a participant's membership in the roster.
+        let tree = info.roster_tree().unwrap();
+        let proof = tree.prove(2).unwrap();
+        assert!(proof
+            .verify(&tree.root(), 2, &participants[1].dh_public_key)
+            .is_ok());
+
+        let bytes = attestation.to_bytes();
+        let attestation = KeygenAttestation::from_bytes(&bytes).unwrap();
+        assert!(attestation.verify(&group_key).is_ok());
+        assert!(KeygenAttestation::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // Another group key is not attested.
+        let other_group_key = run_full_dkg(&params).group_key;
+        assert_eq!(
+            attestation.verify(&other_group_key),
+            Err(Error::InvalidGroupKey)
+        );
+
+        // A participant cannot attest for another one.
+        assert_eq!(
+            info.attest(1, &dh_secret_keys[1], OsRng),
+            Err(Error::InvalidAttestation(1))
+        );
+    }
+
+    #[test]
+    fn dkg_receipt() {
+        let params = Parameters { n: 3, t: 2 };
+        let Dkg {
+            group_key,
+            participants,
+            receipts,
+            ..
+        } = run_full_dkg(&params);
+
+        let info = GroupPublicInfo::new(params, group_key, &participants).unwrap();
+        for (p, receipt) in participants.iter().zip(receipts.iter()) {
+            assert_eq!(receipt.index(), p.index);
+            assert_eq!(receipt.info, info);
+            assert!(verify_receipt(receipt, &p.dh_public_key).is_ok());
+
+            let decoded = DkgReceipt::from_bytes(&receipt.to_bytes()).unwrap();
+            assert_eq!(&decoded, receipt);
+            assert!(verify_receipt(&decoded, &p.dh_public_key).is_ok());
+        }
+
+        // A receipt does not verify under another participant's key.
+        assert_eq!(
+            verify_receipt(&receipts[0], &participants[1].dh_public_key),
+            Err(Error::InvalidAttestation(1))
+        );
+
+        // Nor once the group key or the roster it states is tampered with.
+        let Dkg {
+            group_key: other_group_key,
+            participants: other_participants,
+            ..
+        } = run_full_dkg(&params);
+        let mut tampered = receipts[0].clone();
+        tampered.info.group_key = other_group_key;
+        assert_eq!(
+            verify_receipt(&tampered, &participants[0].dh_public_key),
+            Err(Error::InvalidAttestation(1))
+        );
+
+        let mut tampered = receipts[0].clone();
+        tampered.info.roster[2] = (3, other_participants[2].dh_public_key.clone());
+        assert_eq!(
+            verify_receipt(&tampered, &participants[0].dh_public_key),
+            Err(Error::InvalidAttestation(1))
+        );
+
+        let mut bytes = receipts[0].to_bytes();
+        assert!(DkgReceipt::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        bytes[0] = 2;
+        let tampered = DkgReceipt::from_bytes(&bytes).unwrap();
+        assert!(verify_receipt(&tampered, &participants[1].dh_public_key).is_err());
+    }
+
+    #[test]
+    fn keygen_attestation_missing_or_wrong_participant() {
+        let params = Parameters { n: 3, t: 2 };
+        let Dkg {
+            group_key,
+            participants,
+            dh_secret_keys,
+            ..
+        } = run_full_dkg(&params);
+        let info = GroupPublicInfo::new(params, group_key, &participants).unwrap();
+
+        assert!(GroupPublicInfo::new(params, group_key, &participants[..2]).is_err());
+
+        let mut attestation = KeygenAttestation::new(info.clone());
+        attestation
+            .add(info.attest(1, &dh_secret_keys[0], OsRng).unwrap())
+            .unwrap();
+        attestation
+            .add(info.attest(3, &dh_secret_keys[2], OsRng).unwrap())
+            .unwrap();
+        assert_eq!(
+            attestation.verify(&group_key),
+            Err(Error::MissingAttestation(2))
+        );
+
+        // Participant 2 signs over a different group key.
+        let other_group_key = run_full_dkg(&params).group_key;
+        let other_info = GroupPublicInfo::new(params, other_group_key, &participants).unwrap();
+        let signature = other_info.attest(2, &dh_secret_keys[1], OsRng).unwrap();
+        assert_eq!(
+            attestation.add(signature.clone()),
+            Err(Error::InvalidAttestation(2))
+        );
+
+        // Even if smuggled into a serialised attestation.
+        let mut bytes = attestation.to_bytes();
+        let info_len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        bytes[4 + info_len..8 + info_len].copy_from_slice(&3u32.to_le_bytes());
+        let signature_bytes = signature.to_bytes();
+        let position = 8 + info_len + 68;
+        let mut tampered = bytes[..position].to_vec();
+        tampered.extend_from_slice(&signature_bytes);
+        tampered.extend_from_slice(&bytes[position..]);
+        let tampered = KeygenAttestation::from_bytes(&tampered).unwrap();
+        assert_eq!(
+            tampered.verify(&group_key),
+            Err(Error::InvalidAttestation(2))
+        );
+    }
+
+    #[test]
+    fn resolution_record_after_tampered_share() {
+        let params = Parameters { n: 4, t: 2 };
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, coeffs, dh_sk) = Participant::new_dealer(&params, i, "Φ", rng);
+            participants.push(p);
+            coefficients.push(coeffs);
+            dh_secret_keys.push(dh_sk);
+        }
+        let states: Vec<DistributedKeyGeneration<RoundOne>> = (0..params.n as usize)
+            .map(|i| {
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    &dh_secret_keys[i],
+                    &participants[i].index,
+                    &coefficients[i],
+                    &participants,
+                    "Φ",
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+        let mut shares: Vec<Vec<EncryptedSecretShare>> = states
+            .iter()
+            .map(|state| state.their_encrypted_secret_shares().unwrap().clone())
+            .collect();
+        let my_shares = |shares: &Vec<Vec<EncryptedSecretShare>>, index: usize| {
+            shares
+                .iter()
+                .map(|s| s[index - 1].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Dealer 1 tampers with the share of participant 2, who complains.
+        shares[0][1].encrypted_polynomial_evaluation = [42; 32];
+        let bad_share = shares[0][1].clone();
+        let complaint = match states[1]
+            .duplicate()
+            .to_round_two(my_shares(&shares, 2), rng)
+        {
+            Err(Error::Complaint(complaints)) => complaints[0].clone(),
+            _ => panic!("expected a complaint"),
+        };
+
+        // Participants 3 and 4 adjudicate the complaint on their own, and
+        // reach the same record.
+        let roster: Vec<(u32, DHPublicKey)> = participants
+            .iter()
+            .rev()
+            .map(|p| (p.index, p.dh_public_key.clone()))
+            .collect();
+        let mut records = Vec::new();
+        for index in [3, 4] {
+            let state = states[index - 1]
+                .duplicate()
+                .to_round_two(my_shares(&shares, index), rng)
+                .unwrap();
+            let mut adjudicator = ComplaintAdjudicator::new(&state);
+            assert_eq!(adjudicator.adjudicate(&bad_share, &complaint), Ok(1));
+            records.push(adjudicator.resolution().unwrap());
+        }
+        assert_eq!(records[0], records[1]);
+        let mut record = records.pop().unwrap();
+        assert_eq!(record.disqualified, [1]);
+        assert_eq!(record.qualified, [2, 3, 4]);
+        assert_eq!(
+            record,
+            ResolutionRecord::new(params, &roster, &[1]).unwrap()
+        );
+
+        // The disqualified dealer cannot sign.
+        assert_eq!(
+            record.sign(1, &dh_secret_keys[0], &roster, rng),
+            Err(Error::InvalidAttestation(1))
+        );
+
+        // A single signature is below the quorum.
+        let signature = record.sign(2, &dh_secret_keys[1], &roster, rng).unwrap();
+        record.add(signature, &roster).unwrap();
+        assert_eq!(record.verify(&roster), Err(Error::MissingAttestation(3)));
+        let bytes = record.to_bytes();
+        assert_eq!(
+            ResolutionRecord::from_bytes(&bytes)
+                .unwrap()
+                .verify(&roster),
+            Err(Error::MissingAttestation(3))
+        );
+
+        for index in [3, 4] {
+            let signature = record
+                .sign(index, &dh_secret_keys[index as usize - 1], &roster, rng)
+                .unwrap();
+            record.add(signature, &roster).unwrap();
+        }
+        assert!(record.verify(&roster).is_ok());
+
+        let bytes = record.to_bytes();
+        let decoded = ResolutionRecord::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, record);
+        assert!(decoded.verify(&roster).is_ok());
+        assert!(ResolutionRecord::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // The record does not verify against another roster, nor once its
+        // outcome has been altered.
+        let mut other_roster = roster.clone();
+        other_roster[0].1 = participants[0].dh_public_key.clone();
+        assert_eq!(
+            record.verify(&other_roster),
+            Err(Error::InvalidResolutionRecord)
+        );
+        let mut altered = record.clone();
+        altered.disqualified = [1, 2].to_vec();
+        altered.qualified = [3, 4].to_vec();
+        assert_eq!(altered.verify(&roster), Err(Error::InvalidAttestation(2)));
+    }
+
+    /// Reshare the `old_keys` of a group to three new signers, with the
+    /// dealer of index `cheater` adding one to its old share, and return the
+    /// new group key, roster and refresh proofs.
+    fn reshare(
+        params: &Parameters,
+        old_keys: &[SecretKey],
+        cheater: Option<u32>,
+    ) -> (GroupKey, Vec<(u32, DHPublicKey)>, Vec<RefreshProof>) {
+        let rng = OsRng;
+
+        let mut signers = Vec::new();
+        let mut signer_dh_keys = Vec::new();
+        for index in 1..=params.n {
+            let (signer, dh_private_key) = DkgSigner::new(params, index, "Φ", rng);
+            signers.push(signer);
+            signer_dh_keys.push(dh_private_key);
+        }
+
+        let mut dealers = Vec::new();
+        let mut encrypted_shares = Vec::new();
+        for old_key in old_keys.iter() {
+            let mut key = old_key.key;
+            if cheater == Some(old_key.index) {
+                key += Scalar::one();
+            }
+            let secret_key = SecretKey {
+                index: old_key.index,
+                key,
+            };
+            let (dealer, shares, _) =
+                Participant::reshare(params, secret_key, &signers, "Φ", rng).unwrap();
+            dealers.push(dealer);
+            encrypted_shares.extend(shares);
+        }
+
+        let mut group_key = None;
+        let mut proofs = Vec::new();
+        for (signer, dh_private_key) in signers.iter().zip(signer_dh_keys.iter()) {
+            let (state, _) = DistributedKeyGeneration::<RoundOne>::new(
+                params,
+                dh_private_key,
+                &signer.index,
+                &dealers,
+                "Φ",
+                rng,
+            )
+            .unwrap();
+            let my_encrypted_secret_shares = encrypted_shares
+                .iter()
+                .filter(|s| s.receiver_index == signer.index)
+                .cloned()
+                .collect();
+            let state = state.to_round_two(my_encrypted_secret_shares, rng).unwrap();
+            let (gk, _sk, proof) = state.finish_with_refresh_proof().unwrap();
+            group_key = Some(gk);
+            proofs.push(proof);
+        }
+
+        let roster = signers
+            .iter()
+            .map(|s| (s.index, s.dh_public_key.clone()))
+            .collect();
+
+        (group_key.unwrap(), roster, proofs)
+    }
+
+    #[test]
+    fn refresh_proof() {
+        let params = Parameters { n: 3, t: 2 };
+
+        // The old group, dealt from a random polynomial.
+        let coefficients = [Scalar::random(&mut OsRng), Scalar::random(&mut OsRng)];
+        let old_group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &coefficients[0]);
+        let old_keys: Vec<SecretKey> = (1..=params.n)
+            .map(|index| SecretKey {
+                index,
+                key: coefficients[0] + coefficients[1] * Scalar::from(index),
+            })
+            .collect();
+
+        let (group_key, roster, proofs) = reshare(&params, &old_keys, None);
+        assert_eq!(group_key, old_group_key);
+        let new_info = GroupPublicInfo {
+            parameters: params,
+            group_key,
+            roster,
+        };
+        for proof in proofs.iter() {
+            assert_eq!(proof, &proofs[0]);
+            assert!(proof.verify(&old_group_key, &new_info).is_ok());
+        }
+        let proof = RefreshProof::from_bytes(&proofs[0].to_bytes()).unwrap();
+        assert_eq!(proof, proofs[0]);
+        assert!(RefreshProof::from_bytes(&proofs[0].to_bytes()[1..]).is_err());
+
+        // The old public shares are those the dealers committed to.
+        let old_public_shares: Vec<RistrettoPoint> =
+            old_keys.iter().map(|k| k.to_public().share).collect();
+        let committed: Vec<RistrettoPoint> = proof
+            .commitments()
+            .iter()
+            .map(|c| *c.public_key().unwrap())
+            .collect();
+        assert_eq!(committed, old_public_shares);
+
+        // Under another old group key, or with tampered commitments.
+        let other_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng));
+        assert_eq!(
+            proof.verify(&other_key, &new_info),
+            Err(Error::InvalidRefreshProof)
+        );
+        let mut tampered = proof.clone();
+        tampered.commitments[0]
+            .set_point(1, old_public_shares[0])
+            .unwrap();
+        assert_eq!(
+            tampered.verify(&old_group_key, &new_info),
+            Err(Error::InvalidRefreshProof)
+        );
+
+        // A dealer sneaking in a nonzero constant term changes the group key,
+        // which the proof exposes even with consistent digests.
+        let (group_key, roster, proofs) = reshare(&params, &old_keys, Some(2));
+        assert_ne!(group_key, old_group_key);
+        let new_info = GroupPublicInfo {
+            parameters: params,
+            group_key,
+            roster,
+        };
+        assert_eq!(
+            proofs[0].verify(&old_group_key, &new_info),
+            Err(Error::InvalidRefreshProof)
+        );
+        let claimed = GroupPublicInfo {
+            group_key: old_group_key,
+            ..new_info
+        };
+        assert_eq!(
+            proofs[0].verify(&old_group_key, &claimed),
+            Err(Error::InvalidRefreshProof)
+        );
+    }
+}
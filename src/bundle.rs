@@ -0,0 +1,1302 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Single-file bundles for offline signers.
+//!
+//! An air-gapped signer receives a [`SigningRequestBundle`] holding everything
+//! it needs to produce its [`PartialThresholdSignature`], and returns a
+//! [`SigningResponseBundle`] to the [`SignatureAggregator`].  Both are
+//! serialised compactly: a request takes \\(66 + 68 t\\) bytes plus the length
+//! of the context and message, 8 bytes plus the length of each metadata pair,
+//! and 4 bytes plus the length of the coordinator identity if any, and a
+//! response 68 bytes, which comfortably fits in a QR code for a
+//! typical threshold \\(t\\).
+//!
+//! For bursts of sessions, an aggregator can reserve a contiguous range of
+//! commitment shares of a signer in its [`CommitmentReservations`], include
+//! the signer with one of them in each queued session, and receive all the
+//! partial signatures of the signer in a single [`MultiSigningResponseBundle`].
+//!
+//! A [`SessionTranscript`] archives the public data of a completed session,
+//! from which [`recompute_group_commitment`] recomputes the group commitment
+//! and the challenge, e.g. to settle a dispute over the commitments an
+//! aggregator used.  Transcripts are serialised canonically, with the
+//! metadata sorted by key and the signers by index, so that independent
+//! aggregators archiving the same session can compare their
+//! [`SessionTranscript::canonical_hash`].
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::string::{String, ToString};
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use core::convert::TryInto;
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::ciphersuite::Ristretto25519;
+use crate::encoding::decode_point_checked;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::IndividualPublicKey;
+use crate::keygen::SecretKey;
+use crate::parameters::Parameters;
+use crate::precomputation::PublicCommitmentShareList;
+use crate::precomputation::SecretCommitmentShareList;
+use crate::signature::bind_canonical_metadata;
+use crate::signature::bind_coordinator_id;
+use crate::signature::compute_challenge;
+use crate::signature::compute_group_commitment_and_challenge;
+use crate::signature::compute_message_hash;
+use crate::signature::metadata_digest;
+use crate::signature::Initial;
+use crate::signature::PartialThresholdSignature;
+use crate::signature::SequenceBinding;
+use crate::signature::SignatureAggregator;
+use crate::signature::SignatureError;
+use crate::signature::Signer;
+use crate::signature::SigningSession;
+use crate::signature::ThresholdSignature;
+use crate::signature::VerificationDescriptor;
+
+/// Everything an offline signer needs to produce its
+/// [`PartialThresholdSignature`] for a signing session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SigningRequestBundle {
+    /// The parameters of the signing group.
+    pub parameters: Parameters,
+    /// The index of the signer this request is addressed to.
+    pub signer_index: u32,
+    /// The index of the signer's own commitment share to use.
+    pub commitment_share_index: u32,
+    /// Whether the parameters are bound into the challenge.
+    pub bind_parameters: bool,
+    /// The nonce distinguishing the session from others over the same inputs.
+    pub session_nonce: [u8; 32],
+    /// The context string of the message.
+    pub context: Vec<u8>,
+    /// The message to be signed.
+    pub message: Vec<u8>,
+    /// The metadata bound into the signature, sorted by key.
+    pub metadata: Vec<(String, Vec<u8>)>,
+    /// The identity of the coordinator bound into the challenge, if any.
+    pub coordinator_id: Option<Vec<u8>>,
+    /// The participating signers and their commitments, sorted by index.
+    pub signers: Vec<Signer>,
+}
+
+impl SigningRequestBundle {
+    /// Serialise this signing request to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+        res.extend_from_slice(&self.parameters.to_bytes());
+        res.extend_from_slice(&self.signer_index.to_le_bytes());
+        res.extend_from_slice(&self.commitment_share_index.to_le_bytes());
+        res.push(self.bind_parameters as u8);
+        res.extend_from_slice(&self.session_nonce);
+
+        write_bytes(&mut res, &self.context);
+        write_bytes(&mut res, &self.message);
+
+        write_len(&mut res, self.metadata.len());
+        for (key, value) in self.metadata.iter() {
+            write_bytes(&mut res, key.as_bytes());
+            write_bytes(&mut res, value);
+        }
+        match self.coordinator_id.as_ref() {
+            Some(coordinator_id) => {
+                res.push(1);
+                write_bytes(&mut res, coordinator_id);
+            }
+            None => res.push(0),
+        }
+
+        write_len(&mut res, self.signers.len());
+        for signer in self.signers.iter() {
+            let (hiding, binding) = signer.compressed_commitment_share();
+            res.extend_from_slice(&signer.participant_index.to_le_bytes());
+            res.extend_from_slice(hiding.as_bytes());
+            res.extend_from_slice(binding.as_bytes());
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `SigningRequestBundle`
+    ///
+    /// The request is strictly validated: the parameters must be consistent,
+    /// the metadata keys strictly sorted, the signers strictly sorted by index
+    /// and between \\(t\\) and \\(n\\) in number, the addressed signer among
+    /// them, and there must be no trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SigningRequestBundle, Error> {
+        let parameters = Parameters::from_bytes(
+            bytes
+                .get(0..8)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        if parameters.t == 0 || parameters.t > parameters.n {
+            return Err(Error::SerialisationError);
+        }
+        let mut index_slice = 8usize;
+
+        let signer_index = read_u32(bytes, &mut index_slice)?;
+        let commitment_share_index = read_u32(bytes, &mut index_slice)?;
+        let bind_parameters = match bytes.get(index_slice) {
+            Some(0) => false,
+            Some(1) => true,
+            _ => return Err(Error::SerialisationError),
+        };
+        index_slice += 1;
+        let session_nonce: [u8; 32] = bytes
+            .get(index_slice..index_slice + 32)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        index_slice += 32;
+
+        let context = read_bytes(bytes, &mut index_slice)?.to_vec();
+        let message = read_bytes(bytes, &mut index_slice)?.to_vec();
+
+        let len = read_u32(bytes, &mut index_slice)?;
+        let mut metadata: Vec<(String, Vec<u8>)> = Vec::new();
+        for _ in 0..len {
+            let key = core::str::from_utf8(read_bytes(bytes, &mut index_slice)?)
+                .map_err(|_| Error::SerialisationError)?;
+            let value = read_bytes(bytes, &mut index_slice)?;
+            if let Some((previous, _)) = metadata.last() {
+                if previous.as_str() >= key {
+                    return Err(Error::SerialisationError);
+                }
+            }
+            metadata.push((String::from(key), value.to_vec()));
+        }
+        let coordinator_id = read_coordinator_id(bytes, &mut index_slice)?;
+
+        let len = read_u32(bytes, &mut index_slice)?;
+        if len < parameters.t || len > parameters.n {
+            return Err(Error::SerialisationError);
+        }
+        let mut signers: Vec<Signer> = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            let participant_index = read_u32(bytes, &mut index_slice)?;
+            if let Some(previous) = signers.last() {
+                if previous.participant_index >= participant_index {
+                    return Err(Error::SerialisationError);
+                }
+            }
+            let hiding = read_point(bytes, &mut index_slice)?;
+            let binding = read_point(bytes, &mut index_slice)?;
+            signers.push(Signer::new(participant_index, (hiding, binding)));
+        }
+
+        if !signers.iter().any(|s| s.participant_index == signer_index) {
+            return Err(Error::SerialisationError);
+        }
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(SigningRequestBundle {
+            parameters,
+            signer_index,
+            commitment_share_index,
+            bind_parameters,
+            session_nonce,
+            context,
+            message,
+            metadata,
+            coordinator_id,
+            signers,
+        })
+    }
+
+    /// Get the [`SigningSession`] described by this request, for the signer
+    /// to inspect before deciding whether or not to sign.
+    pub fn session(&self) -> SigningSession {
+        let mut message_hash = compute_message_hash(&self.context, &self.message);
+        message_hash = bind_canonical_metadata(&message_hash, &self.metadata);
+        if let Some(coordinator_id) = self.coordinator_id.as_ref() {
+            message_hash = bind_coordinator_id(&message_hash, coordinator_id);
+        }
+
+        SigningSession {
+            context: self.context.clone(),
+            message: self.message.clone(),
+            metadata: self.metadata.clone(),
+            signers: self.signers.clone(),
+            message_hash,
+            parameters: match self.bind_parameters {
+                true => Some(self.parameters),
+                false => None,
+            },
+            coordinator_id: self.coordinator_id.clone(),
+            nonce: self.session_nonce,
+            authorized_by: None,
+        }
+    }
+}
+
+/// The answer of an offline signer to a [`SigningRequestBundle`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct SigningResponseBundle {
+    /// The hash of the context, message and metadata which was signed.
+    pub message_hash: [u8; 32],
+    /// The signer's partial signature.
+    pub partial_signature: PartialThresholdSignature,
+}
+
+impl SigningResponseBundle {
+    /// Serialise this signing response to an array of 68 bytes.
+    pub fn to_bytes(&self) -> [u8; 68] {
+        let mut bytes = [0u8; 68];
+
+        bytes[..32].copy_from_slice(&self.message_hash);
+        bytes[32..].copy_from_slice(&self.partial_signature.to_bytes());
+
+        bytes
+    }
+
+    /// Attempt to deserialise a signing response from an array of 68 bytes.
+    pub fn from_bytes(bytes: &[u8; 68]) -> Result<SigningResponseBundle, Error> {
+        let message_hash: [u8; 32] = bytes[..32]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let partial_signature = PartialThresholdSignature::from_bytes(
+            bytes[32..]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        Ok(SigningResponseBundle {
+            message_hash,
+            partial_signature,
+        })
+    }
+}
+
+impl SignatureAggregator<Initial<'_>> {
+    /// Prepare the [`SigningRequestBundle`] for the offline signer with the
+    /// given `participant_index`, who committed to the nonces of its
+    /// `commitment_share_index`-th commitment share.
+    ///
+    /// This should be called once all signers have been included.
+    ///
+    /// # Returns
+    ///
+    /// A [`SigningRequestBundle`], or a [`SignatureError::UnknownSigner`] if
+    /// the participant is not an included signer.
+    pub fn signing_request(
+        &mut self,
+        participant_index: u32,
+        commitment_share_index: u32,
+    ) -> Result<SigningRequestBundle, SignatureError> {
+        let session = self.session();
+        if !session
+            .signers
+            .iter()
+            .any(|s| s.participant_index == participant_index)
+        {
+            return Err(SignatureError::UnknownSigner(participant_index));
+        }
+
+        Ok(SigningRequestBundle {
+            parameters: self.state.parameters,
+            signer_index: participant_index,
+            commitment_share_index,
+            bind_parameters: session.parameters.is_some(),
+            session_nonce: session.nonce,
+            context: session.context,
+            message: session.message,
+            metadata: session.metadata,
+            coordinator_id: session.coordinator_id,
+            signers: session.signers,
+        })
+    }
+
+    /// Add the partial signature of a [`SigningResponseBundle`] to be included
+    /// in the aggregation.
+    ///
+    /// # Returns
+    ///
+    /// A [`SignatureError::SessionMismatch`] if the signer signed a different
+    /// context, message or metadata than that of this aggregator, otherwise
+    /// as for [`SignatureAggregator::include_partial_signature`].
+    pub fn include_signing_response(
+        &mut self,
+        response: SigningResponseBundle,
+    ) -> Result<(), SignatureError> {
+        if response.message_hash != self.session().message_hash {
+            return Err(SignatureError::SessionMismatch(
+                response.partial_signature.index,
+            ));
+        }
+
+        self.include_partial_signature(response.partial_signature)
+    }
+}
+
+impl SecretKey {
+    /// Answer a [`SigningRequestBundle`] with this signer's partial signature.
+    ///
+    /// The signer should inspect the request's [`SigningSession`] before
+    /// deciding whether or not to sign.
+    ///
+    /// # Returns
+    ///
+    /// A [`SigningResponseBundle`], or a [`SignatureError::UnknownSigner`] if
+    /// the request is addressed to another signer.
+    pub fn sign_request(
+        &self,
+        request: &SigningRequestBundle,
+        group_key: &GroupKey,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList,
+    ) -> Result<SigningResponseBundle, SignatureError> {
+        if request.signer_index != self.index {
+            return Err(SignatureError::UnknownSigner(self.index));
+        }
+
+        let session = request.session();
+        let partial_signature = self.sign_session(
+            &session,
+            group_key,
+            my_secret_commitment_share_list,
+            request.commitment_share_index as usize,
+        )?;
+
+        Ok(SigningResponseBundle {
+            message_hash: session.message_hash,
+            partial_signature,
+        })
+    }
+}
+
+/// The answer of an offline signer to several [`SigningRequestBundle`]s
+/// using consecutive commitment shares.
+#[derive(Debug, Eq, PartialEq)]
+pub struct MultiSigningResponseBundle {
+    /// The index of the signer.
+    pub signer_index: u32,
+    /// The index of the commitment share used for the first response.
+    pub first_commitment_share_index: u32,
+    /// The responses, in the order of the commitment shares they used.
+    pub responses: Vec<SigningResponseBundle>,
+}
+
+impl MultiSigningResponseBundle {
+    /// Serialise this multi-signing response to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(12 + 68 * self.responses.len());
+        res.extend_from_slice(&self.signer_index.to_le_bytes());
+        res.extend_from_slice(&self.first_commitment_share_index.to_le_bytes());
+        write_len(&mut res, self.responses.len());
+        for response in self.responses.iter() {
+            res.extend_from_slice(&response.to_bytes());
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `MultiSigningResponseBundle`
+    ///
+    /// Every partial signature must be from the signer, there must be at
+    /// least one, and no trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MultiSigningResponseBundle, Error> {
+        let mut index_slice = 0usize;
+
+        let signer_index = read_u32(bytes, &mut index_slice)?;
+        let first_commitment_share_index = read_u32(bytes, &mut index_slice)?;
+        let len = read_u32(bytes, &mut index_slice)?;
+        if len == 0 || first_commitment_share_index.checked_add(len).is_none() {
+            return Err(Error::SerialisationError);
+        }
+
+        let mut responses = Vec::new();
+        for _ in 0..len {
+            let response =
+                SigningResponseBundle::from_bytes(&read_array(bytes, &mut index_slice)?)?;
+            if response.partial_signature.index != signer_index {
+                return Err(Error::SerialisationError);
+            }
+            responses.push(response);
+        }
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(MultiSigningResponseBundle {
+            signer_index,
+            first_commitment_share_index,
+            responses,
+        })
+    }
+}
+
+impl SecretKey {
+    /// Answer several [`SigningRequestBundle`]s using consecutive commitment
+    /// shares at once, as with [`SecretKey::sign_request`].
+    ///
+    /// Unlike for a single request, the commitment share index of each
+    /// request is the index of the commitment share in the published
+    /// [`PublicCommitmentShareList`], as reserved by the aggregator in its
+    /// [`CommitmentReservations`].
+    ///
+    /// The requests are signed in order, and a failure leaves the commitment
+    /// shares of the requests signed before it consumed.
+    ///
+    /// # Returns
+    ///
+    /// A [`MultiSigningResponseBundle`], an `UnknownSigner` error if one of
+    /// the requests is addressed to another signer, or a `Custom` error if
+    /// there are no requests or their commitment shares are not consecutive.
+    pub fn sign_requests(
+        &self,
+        requests: &[SigningRequestBundle],
+        group_key: &GroupKey,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList,
+    ) -> Result<MultiSigningResponseBundle, SignatureError> {
+        let first_commitment_share_index = requests
+            .first()
+            .ok_or_else(|| SignatureError::Custom("No signing requests provided".to_string()))?
+            .commitment_share_index;
+        for (request, index) in requests.iter().zip(first_commitment_share_index..) {
+            if request.signer_index != self.index {
+                return Err(SignatureError::UnknownSigner(self.index));
+            }
+            if request.commitment_share_index != index {
+                return Err(SignatureError::Custom(
+                    "Signing requests must use consecutive commitment shares".to_string(),
+                ));
+            }
+        }
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests.iter() {
+            let session = request.session();
+            let position = my_secret_commitment_share_list
+                .position(request.commitment_share_index)
+                .ok_or(SignatureError::MissingCommitmentShares)?;
+            let partial_signature = self.sign_session(
+                &session,
+                group_key,
+                my_secret_commitment_share_list,
+                position,
+            )?;
+            responses.push(SigningResponseBundle {
+                message_hash: session.message_hash,
+                partial_signature,
+            });
+        }
+
+        Ok(MultiSigningResponseBundle {
+            signer_index: self.index,
+            first_commitment_share_index,
+            responses,
+        })
+    }
+}
+
+/// The ranges of commitment shares reserved for each signer across the
+/// sessions of an aggregator, so that no commitment share of a signer is
+/// ever used by two sessions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CommitmentReservations {
+    /// For each signer, the end of each reserved range by its start.
+    ranges: BTreeMap<u32, BTreeMap<u32, u32>>,
+}
+
+impl CommitmentReservations {
+    /// Construct an empty set of reservations.
+    pub fn new() -> CommitmentReservations {
+        CommitmentReservations::default()
+    }
+
+    /// Reserve the `count` commitment shares of the signer with the given
+    /// `participant_index` starting at position `first`.
+    ///
+    /// # Returns
+    ///
+    /// An `OverlappingReservation` error if one of them is already reserved,
+    /// or a `Custom` error if the range is empty or overflows.
+    pub fn reserve(
+        &mut self,
+        participant_index: u32,
+        first: u32,
+        count: u32,
+    ) -> Result<(), SignatureError> {
+        let end = first
+            .checked_add(count)
+            .filter(|_| count > 0)
+            .ok_or_else(|| SignatureError::Custom("Invalid commitment share range".to_string()))?;
+        let ranges = self.ranges.entry(participant_index).or_default();
+
+        let overlap = match ranges.range(..end).next_back() {
+            Some((&start, &previous_end)) if previous_end > first => Some(start.max(first)),
+            _ => None,
+        };
+        if let Some(commitment_share_index) = overlap {
+            return Err(SignatureError::OverlappingReservation {
+                signer: participant_index,
+                commitment_share_index,
+            });
+        }
+        ranges.insert(first, end);
+
+        Ok(())
+    }
+
+    /// Release the range of commitment shares of a signer starting at
+    /// position `first`, returning whether it was reserved.
+    ///
+    /// The released commitment shares must never be reserved again if they
+    /// were sent to the signer in a request.
+    pub fn release(&mut self, participant_index: u32, first: u32) -> bool {
+        self.ranges
+            .get_mut(&participant_index)
+            .map_or(false, |ranges| ranges.remove(&first).is_some())
+    }
+
+    /// Whether the commitment share at position `commitment_share_index` of
+    /// a signer is reserved.
+    pub fn is_reserved(&self, participant_index: u32, commitment_share_index: u32) -> bool {
+        self.ranges.get(&participant_index).map_or(false, |ranges| {
+            ranges
+                .range(..=commitment_share_index)
+                .next_back()
+                .map_or(false, |(_, &end)| end > commitment_share_index)
+        })
+    }
+
+    /// Reserve one commitment share of the signer for each of the queued
+    /// `aggregators`, starting at position `first` of its published
+    /// `public_comshares`, and include the signer in the aggregators with
+    /// them, in order.
+    ///
+    /// # Returns
+    ///
+    /// A `MissingCommitmentShares` error if the signer did not publish enough
+    /// commitment shares, an `OverlappingReservation` error if one of them is
+    /// already reserved, or an error of
+    /// [`SignatureAggregator::include_signer`], after which the reservation
+    /// is kept and the aggregators should be discarded.
+    pub fn include_signer(
+        &mut self,
+        aggregators: &mut [SignatureAggregator<Initial<'_>>],
+        first: u32,
+        public_comshares: &PublicCommitmentShareList,
+        public_key: IndividualPublicKey,
+    ) -> Result<(), SignatureError> {
+        let participant_index = public_comshares.participant_index;
+        let commitments = public_comshares
+            .commitments
+            .get(first as usize..)
+            .and_then(|commitments| commitments.get(..aggregators.len()))
+            .ok_or(SignatureError::MissingCommitmentShares)?;
+        self.reserve(participant_index, first, aggregators.len() as u32)?;
+
+        for (aggregator, commitment) in aggregators.iter_mut().zip(commitments.iter()) {
+            aggregator.include_signer(participant_index, *commitment, public_key.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the partial signatures of a [`MultiSigningResponseBundle`] to the
+    /// queued `aggregators` the signer was included in with
+    /// [`CommitmentReservations::include_signer`].
+    ///
+    /// # Returns
+    ///
+    /// A `SessionMismatch` error if the response does not answer for exactly
+    /// the range reserved for the signer at its first commitment share,
+    /// otherwise as for [`SignatureAggregator::include_signing_response`].
+    pub fn include_response(
+        &self,
+        aggregators: &mut [SignatureAggregator<Initial<'_>>],
+        response: MultiSigningResponseBundle,
+    ) -> Result<(), SignatureError> {
+        let signer = response.signer_index;
+        let end = self
+            .ranges
+            .get(&signer)
+            .and_then(|ranges| ranges.get(&response.first_commitment_share_index));
+        if end.copied()
+            != response
+                .first_commitment_share_index
+                .checked_add(response.responses.len() as u32)
+            || aggregators.len() != response.responses.len()
+        {
+            return Err(SignatureError::SessionMismatch(signer));
+        }
+
+        for (aggregator, response) in aggregators.iter_mut().zip(response.responses) {
+            aggregator.include_signing_response(response)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The public data of a completed signing session, as archived for later
+/// dispute resolution.
+///
+/// Only sessions signed with the [`Ristretto25519`] ciphersuite can be
+/// recomputed.
+///
+/// [`Ristretto25519`]: crate::ciphersuite::Ristretto25519
+#[derive(Debug, Eq, PartialEq)]
+pub struct SessionTranscript {
+    /// The group key the session was signed under.
+    pub group_key: GroupKey,
+    /// The parameters bound into the challenge, if any.
+    pub parameters: Option<Parameters>,
+    /// The nonce distinguishing the session from others over the same inputs.
+    pub session_nonce: [u8; 32],
+    /// The context string of the message.
+    pub context: Vec<u8>,
+    /// The metadata bound into the signature, sorted by key.
+    pub metadata: Vec<(String, Vec<u8>)>,
+    /// The hashed context, message and metadata which was signed.
+    pub message_hash: [u8; 32],
+    /// The participating signers and their commitments, sorted by index.
+    pub signers: Vec<Signer>,
+    /// The aggregated signature.
+    pub signature: ThresholdSignature,
+    /// How consumers of the signature are expected to verify it.
+    pub descriptor: VerificationDescriptor,
+}
+
+impl SessionTranscript {
+    /// Construct the transcript of a `session` aggregated into `signature`
+    /// under the `group_key`.
+    pub fn new(
+        group_key: GroupKey,
+        session: &SigningSession,
+        signature: ThresholdSignature,
+    ) -> SessionTranscript {
+        SessionTranscript {
+            group_key,
+            parameters: session.parameters,
+            session_nonce: session.nonce,
+            context: session.context.clone(),
+            metadata: session.metadata.clone(),
+            message_hash: session.message_hash,
+            signers: session.signers.clone(),
+            signature,
+            descriptor: VerificationDescriptor::for_session(session),
+        }
+    }
+
+    /// The challenge of the archived signature.
+    pub fn challenge(&self) -> Scalar {
+        compute_challenge::<Ristretto25519>(
+            &self.message_hash,
+            &self.group_key,
+            &self.signature.R,
+            self.parameters.as_ref(),
+        )
+    }
+
+    /// Serialise this transcript to a Vec of bytes
+    ///
+    /// The serialisation is canonical: the metadata is written sorted by key
+    /// and the signers sorted by index, whatever their order in the
+    /// transcript.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+        res.extend_from_slice(&self.group_key.to_bytes());
+        match self.parameters {
+            Some(parameters) => {
+                res.push(1);
+                res.extend_from_slice(&parameters.to_bytes());
+            }
+            None => res.push(0),
+        }
+        res.extend_from_slice(&self.session_nonce);
+
+        write_bytes(&mut res, &self.context);
+        let mut metadata: Vec<&(String, Vec<u8>)> = self.metadata.iter().collect();
+        metadata.sort_by(|(a, _), (b, _)| a.cmp(b));
+        write_len(&mut res, metadata.len());
+        for (key, value) in metadata {
+            write_bytes(&mut res, key.as_bytes());
+            write_bytes(&mut res, value);
+        }
+        res.extend_from_slice(&self.message_hash);
+
+        let mut signers: Vec<&Signer> = self.signers.iter().collect();
+        signers.sort();
+        write_len(&mut res, signers.len());
+        for signer in signers {
+            let (hiding, binding) = signer.compressed_commitment_share();
+            res.extend_from_slice(&signer.participant_index.to_le_bytes());
+            res.extend_from_slice(hiding.as_bytes());
+            res.extend_from_slice(binding.as_bytes());
+        }
+        res.extend_from_slice(&self.signature.to_bytes());
+
+        // The context, metadata digest and parameters of the descriptor are
+        // those of the transcript, and are not serialised twice.
+        res.push(self.descriptor.suite_id);
+        res.push(self.descriptor.key_encoding);
+        match self.descriptor.tweak {
+            Some(tweak) => {
+                res.push(1);
+                res.extend_from_slice(&tweak);
+            }
+            None => res.push(0),
+        }
+        match self.descriptor.coordinator_id.as_ref() {
+            Some(coordinator_id) => {
+                res.push(1);
+                write_bytes(&mut res, coordinator_id);
+            }
+            None => res.push(0),
+        }
+
+        res
+    }
+
+    /// Compute the hash of the canonical serialisation of this transcript,
+    /// which is identical for all the transcripts of a same session.
+    pub fn canonical_hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+
+        h.update(b"ICE-FROST-SESSION-TRANSCRIPT");
+        h.update(self.to_bytes());
+
+        h.finalize().into()
+    }
+
+    /// Deserialise this slice of bytes to a `SessionTranscript`
+    ///
+    /// As for a [`SigningRequestBundle`], the metadata keys must be strictly
+    /// sorted, the signers strictly sorted by index, and there must be no
+    /// trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SessionTranscript, Error> {
+        let mut index_slice = 0usize;
+
+        let group_key = GroupKey::from_bytes(&read_array(bytes, &mut index_slice)?)?;
+        let parameters = match bytes.get(index_slice) {
+            Some(0) => None,
+            Some(1) => {
+                index_slice += 1;
+                Some(Parameters::from_bytes(&read_array(
+                    bytes,
+                    &mut index_slice,
+                )?)?)
+            }
+            _ => return Err(Error::SerialisationError),
+        };
+        if parameters.is_none() {
+            index_slice += 1;
+        }
+        let session_nonce = read_array(bytes, &mut index_slice)?;
+
+        let context = read_bytes(bytes, &mut index_slice)?.to_vec();
+        let len = read_u32(bytes, &mut index_slice)?;
+        let mut metadata: Vec<(String, Vec<u8>)> = Vec::new();
+        for _ in 0..len {
+            let key = core::str::from_utf8(read_bytes(bytes, &mut index_slice)?)
+                .map_err(|_| Error::SerialisationError)?;
+            let value = read_bytes(bytes, &mut index_slice)?;
+            if let Some((previous, _)) = metadata.last() {
+                if previous.as_str() >= key {
+                    return Err(Error::SerialisationError);
+                }
+            }
+            metadata.push((String::from(key), value.to_vec()));
+        }
+        let message_hash = read_array(bytes, &mut index_slice)?;
+
+        let len = read_u32(bytes, &mut index_slice)?;
+        let mut signers: Vec<Signer> = Vec::new();
+        for _ in 0..len {
+            let participant_index = read_u32(bytes, &mut index_slice)?;
+            if let Some(previous) = signers.last() {
+                if previous.participant_index >= participant_index {
+                    return Err(Error::SerialisationError);
+                }
+            }
+            let hiding = read_point(bytes, &mut index_slice)?;
+            let binding = read_point(bytes, &mut index_slice)?;
+            signers.push(Signer::new(participant_index, (hiding, binding)));
+        }
+        let signature = ThresholdSignature::from_bytes(&read_array(bytes, &mut index_slice)?)?;
+
+        let [suite_id, key_encoding, has_tweak] = read_array(bytes, &mut index_slice)?;
+        let tweak = match has_tweak {
+            0 => None,
+            1 => Some(read_array(bytes, &mut index_slice)?),
+            _ => return Err(Error::SerialisationError),
+        };
+        let coordinator_id = read_coordinator_id(bytes, &mut index_slice)?;
+        let descriptor = VerificationDescriptor {
+            suite_id,
+            key_encoding,
+            tweak,
+            metadata_digest: metadata_digest(&metadata),
+            parameters,
+            coordinator_id,
+            sequence: SequenceBinding::from_metadata(&metadata),
+            context: context.clone(),
+        };
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(SessionTranscript {
+            group_key,
+            parameters,
+            session_nonce,
+            context,
+            metadata,
+            message_hash,
+            signers,
+            signature,
+            descriptor,
+        })
+    }
+}
+
+/// Recompute the group commitment \\(R\\) and the challenge of an archived
+/// session, from the public data of its `transcript` only.
+///
+/// They match the commitment and the challenge of the archived signature
+/// if and only if the aggregator used the commitments the signers saw.
+///
+/// # Returns
+///
+/// A `DuplicateParticipant` error if a signer appears more than once in the
+/// transcript.
+pub fn recompute_group_commitment(
+    transcript: &SessionTranscript,
+) -> Result<(RistrettoPoint, Scalar), Error> {
+    let mut signers = transcript.signers.clone();
+    signers.sort();
+    for pair in signers.windows(2) {
+        if pair[0].participant_index == pair[1].participant_index {
+            return Err(Error::DuplicateParticipant(pair[0].participant_index));
+        }
+    }
+
+    Ok(compute_group_commitment_and_challenge(
+        &transcript.message_hash,
+        &transcript.group_key,
+        &signers,
+        transcript.parameters.as_ref(),
+    ))
+}
+
+fn write_len(res: &mut Vec<u8>, len: usize) {
+    res.extend_from_slice(&TryInto::<u32>::try_into(len).unwrap().to_le_bytes());
+}
+
+fn write_bytes(res: &mut Vec<u8>, bytes: &[u8]) {
+    write_len(res, bytes.len());
+    res.extend_from_slice(bytes);
+}
+
+fn read_u32(bytes: &[u8], index_slice: &mut usize) -> Result<u32, Error> {
+    let value = u32::from_le_bytes(
+        bytes
+            .get(*index_slice..*index_slice + 4)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    );
+    *index_slice += 4;
+
+    Ok(value)
+}
+
+fn read_bytes<'a>(bytes: &'a [u8], index_slice: &mut usize) -> Result<&'a [u8], Error> {
+    let len = read_u32(bytes, index_slice)? as usize;
+    let value = bytes
+        .get(*index_slice..*index_slice + len)
+        .ok_or(Error::SerialisationError)?;
+    *index_slice += len;
+
+    Ok(value)
+}
+
+fn read_coordinator_id(bytes: &[u8], index_slice: &mut usize) -> Result<Option<Vec<u8>>, Error> {
+    let coordinator_id = match bytes.get(*index_slice) {
+        Some(0) => None,
+        Some(1) => {
+            *index_slice += 1;
+            Some(read_bytes(bytes, index_slice)?.to_vec())
+        }
+        _ => return Err(Error::SerialisationError),
+    };
+    if coordinator_id.is_none() {
+        *index_slice += 1;
+    }
+
+    Ok(coordinator_id)
+}
+
+fn read_array<const N: usize>(bytes: &[u8], index_slice: &mut usize) -> Result<[u8; N], Error> {
+    let array = bytes
+        .get(*index_slice..*index_slice + N)
+        .ok_or(Error::SerialisationError)?
+        .try_into()
+        .map_err(|_| Error::SerialisationError)?;
+    *index_slice += N;
+
+    Ok(array)
+}
+
+fn read_point(bytes: &[u8], index_slice: &mut usize) -> Result<RistrettoPoint, Error> {
+    let point = decode_point_checked(
+        bytes
+            .get(*index_slice..*index_slice + 32)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    )?;
+    *index_slice += 32;
+
+    Ok(point)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::precomputation::generate_commitment_share_lists;
+
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+    use rand::rngs::OsRng;
+    use rand::seq::SliceRandom;
+
+    fn request() -> SigningRequestBundle {
+        let (p1_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        SigningRequestBundle {
+            parameters: Parameters { n: 3, t: 2 },
+            signer_index: 2,
+            commitment_share_index: 0,
+            bind_parameters: true,
+            session_nonce: [7u8; 32],
+            context: b"CONTEXT".to_vec(),
+            message: b"MESSAGE".to_vec(),
+            metadata: vec![
+                (String::from("chain_id"), b"mainnet".to_vec()),
+                (String::from("request_id"), b"42".to_vec()),
+            ],
+            coordinator_id: Some(b"COORDINATOR".to_vec()),
+            signers: vec![
+                Signer::new(1, p1_public_comshares.commitments[0]),
+                Signer::new(2, p2_public_comshares.commitments[0]),
+            ],
+        }
+    }
+
+    #[test]
+    fn signing_request_strict_parsing() {
+        let request = request();
+        let bytes = request.to_bytes();
+        assert_eq!(bytes.len(), 66 + 68 * 2 + 14 + 2 * 8 + 27 + 4 + 11);
+        assert_eq!(SigningRequestBundle::from_bytes(&bytes).unwrap(), request);
+
+        // Truncated or trailing bytes.
+        assert!(SigningRequestBundle::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(SigningRequestBundle::from_bytes(&trailing).is_err());
+
+        // Unsorted metadata.
+        let mut unsorted = request.clone();
+        unsorted.metadata.reverse();
+        assert!(SigningRequestBundle::from_bytes(&unsorted.to_bytes()).is_err());
+
+        // Unsorted or duplicate signers.
+        let mut unsorted = request.clone();
+        unsorted.signers.reverse();
+        assert!(SigningRequestBundle::from_bytes(&unsorted.to_bytes()).is_err());
+        let mut duplicate = request.clone();
+        duplicate.signers[0].participant_index = 2;
+        assert!(SigningRequestBundle::from_bytes(&duplicate.to_bytes()).is_err());
+
+        // Too few signers, or not addressed to one of them.
+        let mut too_few = request.clone();
+        too_few.signers.pop();
+        too_few.signer_index = 1;
+        assert!(SigningRequestBundle::from_bytes(&too_few.to_bytes()).is_err());
+        let mut stranger = request.clone();
+        stranger.signer_index = 3;
+        assert!(SigningRequestBundle::from_bytes(&stranger.to_bytes()).is_err());
+
+        // Non-boolean parameter binding.
+        let mut non_boolean = bytes.clone();
+        non_boolean[16] = 2;
+        assert!(SigningRequestBundle::from_bytes(&non_boolean).is_err());
+
+        // Inconsistent parameters.
+        let mut inconsistent = request;
+        inconsistent.parameters.t = 4;
+        assert!(SigningRequestBundle::from_bytes(&inconsistent.to_bytes()).is_err());
+    }
+
+    fn transcript() -> SessionTranscript {
+        let key = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &key);
+        let secret_key = SecretKey { index: 1, key };
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+
+        let mut aggregator = SignatureAggregator::new_with_metadata(
+            Parameters { n: 1, t: 1 },
+            group_key,
+            b"CONTEXT",
+            b"MESSAGE",
+            &[("chain_id", b"mainnet")],
+        )
+        .unwrap();
+        aggregator.bind_parameters();
+        aggregator
+            .include_signer(1, public_comshares.commitments[0], (&secret_key).into())
+            .unwrap();
+        let session = aggregator.session();
+        let partial = secret_key
+            .sign_session(&session, &group_key, &mut secret_comshares, 0)
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        SessionTranscript::new(group_key, &session, signature)
+    }
+
+    #[test]
+    fn recompute_archived_group_commitment() {
+        let transcript = transcript();
+        let bytes = transcript.to_bytes();
+        let archived = SessionTranscript::from_bytes(&bytes).unwrap();
+        assert_eq!(archived, transcript);
+        assert!(SessionTranscript::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let (R, challenge) = recompute_group_commitment(&archived).unwrap();
+        assert_eq!(R, archived.signature.R);
+        assert_eq!(challenge, archived.challenge());
+
+        // The embedded descriptor tells a consumer how to verify the signature.
+        assert_eq!(archived.descriptor.context, b"CONTEXT");
+        assert!(archived.descriptor.metadata_digest.is_some());
+        assert_eq!(archived.descriptor.parameters, archived.parameters);
+        let metadata: [(&str, &[u8]); 1] = [("chain_id", b"mainnet")];
+        assert!(archived
+            .signature
+            .verify_with_descriptor(
+                &archived.descriptor,
+                &archived.group_key,
+                b"MESSAGE",
+                &metadata
+            )
+            .is_ok());
+
+        let mut tweaked = SessionTranscript::from_bytes(&bytes).unwrap();
+        tweaked.descriptor.key_encoding = VerificationDescriptor::X_ONLY_KEY;
+        tweaked.descriptor.tweak = Some([7; 32]);
+        let tweaked_bytes = tweaked.to_bytes();
+        assert_eq!(tweaked_bytes.len(), bytes.len() + 32);
+        assert_eq!(
+            SessionTranscript::from_bytes(&tweaked_bytes).unwrap(),
+            tweaked
+        );
+        assert_eq!(
+            tweaked.signature.verify_with_descriptor(
+                &tweaked.descriptor,
+                &tweaked.group_key,
+                b"MESSAGE",
+                &metadata
+            ),
+            Err(SignatureError::UnsupportedDescriptor("key encoding"))
+        );
+
+        // An aggregator claiming other commitments than the signer saw.
+        let (other_comshares, _) = generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let mut corrupted = SessionTranscript::from_bytes(&bytes).unwrap();
+        corrupted.signers[0] = Signer::new(1, other_comshares.commitments[0]);
+        let corrupted = SessionTranscript::from_bytes(&corrupted.to_bytes()).unwrap();
+
+        let (R, challenge) = recompute_group_commitment(&corrupted).unwrap();
+        assert_ne!(R, corrupted.signature.R);
+        assert_ne!(challenge, corrupted.challenge());
+
+        let mut duplicate = corrupted;
+        duplicate.signers.push(duplicate.signers[0]);
+        assert_eq!(
+            recompute_group_commitment(&duplicate),
+            Err(Error::DuplicateParticipant(1))
+        );
+    }
+
+    #[test]
+    fn transcript_canonical_serialisation() {
+        let mut transcript = transcript();
+        transcript
+            .metadata
+            .push((String::from("request_id"), b"42".to_vec()));
+        transcript
+            .metadata
+            .push((String::from("account"), b"alice".to_vec()));
+        for index in 2..=5 {
+            let (comshares, _) = generate_commitment_share_lists(&mut OsRng, index, 1);
+            transcript
+                .signers
+                .push(Signer::new(index, comshares.commitments[0]));
+        }
+        transcript.metadata.sort();
+        transcript.descriptor.metadata_digest = metadata_digest(&transcript.metadata);
+        let bytes = transcript.to_bytes();
+        let hash = transcript.canonical_hash();
+        assert_eq!(SessionTranscript::from_bytes(&bytes).unwrap(), transcript);
+
+        let mut shuffled = SessionTranscript::from_bytes(&bytes).unwrap();
+        for _ in 0..16 {
+            shuffled.metadata.shuffle(&mut OsRng);
+            shuffled.signers.shuffle(&mut OsRng);
+            assert_eq!(shuffled.to_bytes(), bytes);
+            assert_eq!(shuffled.canonical_hash(), hash);
+        }
+
+        shuffled.metadata[0].1 = b"other".to_vec();
+        assert_ne!(shuffled.canonical_hash(), hash);
+    }
+
+    #[test]
+    fn reserved_commitment_ranges() {
+        let key = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &key);
+        let secret_key = SecretKey { index: 1, key };
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 4);
+
+        let params = Parameters { n: 1, t: 1 };
+        let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+        let mut aggregators: Vec<_> = messages
+            .iter()
+            .map(|message| SignatureAggregator::new(params, group_key, b"CONTEXT", message))
+            .collect();
+
+        let mut reservations = CommitmentReservations::new();
+        reservations
+            .include_signer(&mut aggregators, 1, &public_comshares, (&secret_key).into())
+            .unwrap();
+        assert!(!reservations.is_reserved(1, 0));
+        assert!(reservations.is_reserved(1, 3));
+
+        let requests: Vec<_> = aggregators
+            .iter_mut()
+            .zip(1..)
+            .map(|(aggregator, index)| aggregator.signing_request(1, index).unwrap())
+            .collect();
+
+        // The requests must use consecutive commitment shares.
+        let mut gap = vec![requests[0].clone(), requests[2].clone()];
+        assert!(secret_key
+            .sign_requests(&gap, &group_key, &mut secret_comshares)
+            .is_err());
+        gap.clear();
+        assert!(secret_key
+            .sign_requests(&gap, &group_key, &mut secret_comshares)
+            .is_err());
+
+        let response = secret_key
+            .sign_requests(&requests, &group_key, &mut secret_comshares)
+            .unwrap();
+        let bytes = response.to_bytes();
+        assert_eq!(bytes.len(), 12 + 68 * 3);
+        assert!(MultiSigningResponseBundle::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let response = MultiSigningResponseBundle::from_bytes(&bytes).unwrap();
+
+        // Only the reserved range is accepted.
+        let mut shifted = MultiSigningResponseBundle::from_bytes(&bytes).unwrap();
+        shifted.first_commitment_share_index = 0;
+        assert_eq!(
+            reservations.include_response(&mut aggregators, shifted),
+            Err(SignatureError::SessionMismatch(1))
+        );
+
+        reservations
+            .include_response(&mut aggregators, response)
+            .unwrap();
+        for (aggregator, message) in aggregators.into_iter().zip(messages.iter()) {
+            let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            let message_hash = compute_message_hash(b"CONTEXT", message);
+            assert!(signature.verify(&group_key, &message_hash).is_ok());
+        }
+    }
+
+    #[test]
+    fn overlapping_reservations() {
+        let mut reservations = CommitmentReservations::new();
+        reservations.reserve(1, 4, 4).unwrap();
+
+        // Overlapping the start, the end, or the whole of the reserved range.
+        assert_eq!(
+            reservations.reserve(1, 2, 3),
+            Err(SignatureError::OverlappingReservation {
+                signer: 1,
+                commitment_share_index: 4,
+            })
+        );
+        assert_eq!(
+            reservations.reserve(1, 7, 2),
+            Err(SignatureError::OverlappingReservation {
+                signer: 1,
+                commitment_share_index: 7,
+            })
+        );
+        assert_eq!(
+            reservations.reserve(1, 0, 10),
+            Err(SignatureError::OverlappingReservation {
+                signer: 1,
+                commitment_share_index: 4,
+            })
+        );
+        assert!(reservations.reserve(1, 0, 0).is_err());
+        assert!(reservations.reserve(1, u32::MAX, 2).is_err());
+
+        // Adjacent ranges, and the same range of another signer, are fine.
+        reservations.reserve(1, 0, 4).unwrap();
+        reservations.reserve(1, 8, 1).unwrap();
+        reservations.reserve(2, 4, 4).unwrap();
+        assert!((0..9).all(|index| reservations.is_reserved(1, index)));
+        assert!(!reservations.is_reserved(1, 9));
+
+        assert!(reservations.release(1, 4));
+        assert!(!reservations.release(1, 4));
+        assert!(!reservations.is_reserved(1, 5));
+        reservations.reserve(1, 5, 2).unwrap();
+    }
+}
@@ -0,0 +1,409 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Ciphersuites, i.e. the prime-order group and the hash functions over which
+//! the protocol is instantiated.
+//!
+//! The hashes of the protocol, i.e. the message hash, the binding factors and
+//! challenges of signing and the proofs of knowledge of keys, are derived
+//! through a [`Ciphersuite`], by default [`Ristretto25519`].  Signing with
+//! another hash is possible with any [`RistrettoCiphersuite`], such as
+//! [`UniformRistretto25519`], or [`Rfc9591Ristretto255`] for signatures
+//! interoperating with other implementations of RFC 9591.
+//!
+//! # Limitations
+//!
+//! The group of a ciphersuite is not pluggable: [`Participant`],
+//! [`DistributedKeyGeneration`], [`GroupKey`] and the other key generation
+//! and signing types hold Ristretto points and scalars, hence the protocol
+//! cannot be instantiated over another group, such as secp256k1 or the
+//! Ed25519 curve.  Making these types generic over the group is a breaking
+//! change beyond the types themselves:
+//!
+//! * their wire formats are fixed-size arrays of 32-byte encodings, whose
+//!   lengths cannot depend on the ciphersuite on stable Rust;
+//! * [`Error::Complaint`] carries the complaints of the key generation, hence
+//!   the error type would have to be generic as well;
+//! * [`GroupKey`] and the public keys of the participants are `Copy`, and
+//!   cache their compressed Ristretto encodings.
+//!
+//! The group operations and encodings of a [`Ciphersuite`] are therefore
+//! only used for deriving the hashes of the protocol.
+//!
+//! [`Participant`]: crate::keygen::Participant
+//! [`DistributedKeyGeneration`]: crate::keygen::DistributedKeyGeneration
+//! [`GroupKey`]: crate::keygen::GroupKey
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use core::fmt;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use rand::CryptoRng;
+use rand::RngCore;
+
+use sha2::digest::consts::U64;
+use sha2::Digest;
+use sha2::{Sha256, Sha512};
+
+use zeroize::Zeroize;
+
+use crate::encoding::decode_point_checked;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_point;
+use crate::encoding::encode_scalar;
+use crate::keygen::Error;
+
+/// A prime-order group along with the hash functions of the protocol.
+pub trait Ciphersuite: Clone + Copy + Debug {
+    /// The identifier of this ciphersuite.
+    const ID: &'static str;
+
+    /// The scalars, i.e. the field of integers modulo the order of the group.
+    type Scalar: Clone
+        + Copy
+        + Debug
+        + Eq
+        + Zeroize
+        + Add<Output = Self::Scalar>
+        + Sub<Output = Self::Scalar>
+        + Mul<Output = Self::Scalar>
+        + Neg<Output = Self::Scalar>;
+
+    /// The elements of the group.
+    type Element: Clone
+        + Copy
+        + Debug
+        + Eq
+        + Add<Output = Self::Element>
+        + Sub<Output = Self::Element>
+        + Neg<Output = Self::Element>
+        + Mul<Self::Scalar, Output = Self::Element>;
+
+    /// The hash from which scalars are derived.
+    type Hash: Digest<OutputSize = U64> + Clone + Default;
+
+    /// The number of nonces each signer commits to in each of its
+    /// commitment shares.
+    ///
+    /// This is two for standard FROST, i.e. a hiding and a binding nonce, and
+    /// is the extension hook for protocol variants committing to more nonces.
+    /// The individual commitment of a signer with binding factor \\(\rho\\)
+    /// and nonce commitments \\(C\_0, \ldots, C\_{k-1}\\) is
+    /// \\(\sum\_j \rho^j C\_j\\), which for two nonces is the
+    /// \\(D + \rho E\\) of standard FROST.  The commitment shares of this
+    /// crate hold two nonces, hence generating commitment shares, signing or
+    /// aggregating under a ciphersuite asking for another count fails with an
+    /// `UnsupportedNonceCount` error.
+    const NONCE_COUNT: usize = 2;
+
+    /// The context string of the RFC 9591 ciphersuite implemented by this
+    /// ciphersuite, if any.
+    ///
+    /// With a context string, the binding factors and the challenges of
+    /// signing are derived as in RFC 9591, hence so are the signatures, and
+    /// the challenge does not bind the parameters of the group.  Without one,
+    /// which is the default, they are derived over the domain separators of
+    /// this crate.
+    const RFC9591_CONTEXT_STRING: Option<&'static str> = None;
+
+    /// The generator of the group.
+    fn generator() -> Self::Element;
+
+    /// Multiply the generator of the group by a `scalar`.
+    fn mul_base(scalar: &Self::Scalar) -> Self::Element {
+        Self::generator() * *scalar
+    }
+
+    /// Sample a uniformly random scalar.
+    fn random_scalar(rng: impl RngCore + CryptoRng) -> Self::Scalar;
+
+    /// Derive a scalar from the inputs fed to the `hash`.
+    fn hash_to_scalar(hash: Self::Hash) -> Self::Scalar;
+
+    /// Hash a `context_string` and a `message` into the 32 bytes to be signed.
+    fn message_hash(context_string: &[u8], message: &[u8]) -> [u8; 32];
+
+    /// Encode an `element` canonically.
+    fn encode_element(element: &Self::Element) -> Vec<u8>;
+
+    /// Decode an element from its canonical encoding.
+    fn decode_element(bytes: &[u8]) -> Result<Self::Element, Error>;
+
+    /// Encode a `scalar` canonically.
+    fn encode_scalar(scalar: &Self::Scalar) -> Vec<u8>;
+
+    /// Decode a scalar from its canonical encoding.
+    fn decode_scalar(bytes: &[u8]) -> Result<Self::Scalar, Error>;
+}
+
+/// The Ristretto group over Curve25519, with Sha-256 message hashes and
+/// Sha-512 derivations of scalars.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ristretto25519;
+
+impl Ciphersuite for Ristretto25519 {
+    const ID: &'static str = "ristretto255-sha512";
+
+    type Scalar = Scalar;
+    type Element = RistrettoPoint;
+    type Hash = Sha512;
+
+    fn generator() -> RistrettoPoint {
+        RISTRETTO_BASEPOINT_TABLE.basepoint()
+    }
+
+    fn mul_base(scalar: &Scalar) -> RistrettoPoint {
+        &RISTRETTO_BASEPOINT_TABLE * scalar
+    }
+
+    fn random_scalar(mut rng: impl RngCore + CryptoRng) -> Scalar {
+        Scalar::random(&mut rng)
+    }
+
+    fn hash_to_scalar(hash: Sha512) -> Scalar {
+        Scalar::from_hash(hash)
+    }
+
+    fn message_hash(context_string: &[u8], message: &[u8]) -> [u8; 32] {
+        let mut h = Sha256::new();
+
+        h.update(context_string);
+        h.update(message);
+
+        let mut output = [0u8; 32];
+
+        output.copy_from_slice(h.finalize().as_slice());
+        output
+    }
+
+    fn encode_element(element: &RistrettoPoint) -> Vec<u8> {
+        encode_point(element).to_vec()
+    }
+
+    fn decode_element(bytes: &[u8]) -> Result<RistrettoPoint, Error> {
+        decode_point_checked(bytes.try_into().map_err(|_| Error::SerialisationError)?)
+    }
+
+    fn encode_scalar(scalar: &Scalar) -> Vec<u8> {
+        encode_scalar(scalar).to_vec()
+    }
+
+    fn decode_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+        decode_scalar_canonical(bytes.try_into().map_err(|_| Error::SerialisationError)?)
+    }
+}
+
+/// The FROST(ristretto255, SHA-512) ciphersuite of RFC 9591, i.e. the
+/// Ristretto group over Curve25519 with the binding factors and challenges of
+/// the RFC, and the message hash of [`Ristretto25519`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rfc9591Ristretto255;
+
+impl Ciphersuite for Rfc9591Ristretto255 {
+    const ID: &'static str = "ristretto255-sha512-rfc9591";
+
+    const RFC9591_CONTEXT_STRING: Option<&'static str> = Some("FROST-RISTRETTO255-SHA512-v1");
+
+    type Scalar = Scalar;
+    type Element = RistrettoPoint;
+    type Hash = Sha512;
+
+    fn generator() -> RistrettoPoint {
+        Ristretto25519::generator()
+    }
+
+    fn mul_base(scalar: &Scalar) -> RistrettoPoint {
+        Ristretto25519::mul_base(scalar)
+    }
+
+    fn random_scalar(rng: impl RngCore + CryptoRng) -> Scalar {
+        Ristretto25519::random_scalar(rng)
+    }
+
+    fn hash_to_scalar(hash: Sha512) -> Scalar {
+        Ristretto25519::hash_to_scalar(hash)
+    }
+
+    fn message_hash(context_string: &[u8], message: &[u8]) -> [u8; 32] {
+        Ristretto25519::message_hash(context_string, message)
+    }
+
+    fn encode_element(element: &RistrettoPoint) -> Vec<u8> {
+        Ristretto25519::encode_element(element)
+    }
+
+    fn decode_element(bytes: &[u8]) -> Result<RistrettoPoint, Error> {
+        Ristretto25519::decode_element(bytes)
+    }
+
+    fn encode_scalar(scalar: &Scalar) -> Vec<u8> {
+        Ristretto25519::encode_scalar(scalar)
+    }
+
+    fn decode_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+        Ristretto25519::decode_scalar(bytes)
+    }
+}
+
+/// A [`Ciphersuite`] over the Ristretto group, i.e. one with which the key
+/// generation and signing types of this crate can be used.
+pub trait RistrettoCiphersuite: Ciphersuite<Scalar = Scalar, Element = RistrettoPoint> {}
+
+impl<C: Ciphersuite<Scalar = Scalar, Element = RistrettoPoint>> RistrettoCiphersuite for C {}
+
+/// The Ristretto group over Curve25519, with the same 64-byte hash `H`
+/// throughout, the message hash being its output truncated to 32 bytes.
+pub struct UniformRistretto25519<H>(PhantomData<H>);
+
+impl<H> Clone for UniformRistretto25519<H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H> Copy for UniformRistretto25519<H> {}
+
+impl<H> Debug for UniformRistretto25519<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UniformRistretto25519")
+    }
+}
+
+impl<H: Digest<OutputSize = U64> + Clone + Default> Ciphersuite for UniformRistretto25519<H> {
+    const ID: &'static str = "ristretto255-uniform";
+
+    type Scalar = Scalar;
+    type Element = RistrettoPoint;
+    type Hash = H;
+
+    fn generator() -> RistrettoPoint {
+        Ristretto25519::generator()
+    }
+
+    fn mul_base(scalar: &Scalar) -> RistrettoPoint {
+        Ristretto25519::mul_base(scalar)
+    }
+
+    fn random_scalar(rng: impl RngCore + CryptoRng) -> Scalar {
+        Ristretto25519::random_scalar(rng)
+    }
+
+    fn hash_to_scalar(hash: H) -> Scalar {
+        Scalar::from_hash(hash)
+    }
+
+    fn message_hash(context_string: &[u8], message: &[u8]) -> [u8; 32] {
+        let mut h = H::default();
+
+        h.update(context_string);
+        h.update(message);
+
+        let mut output = [0u8; 32];
+
+        output.copy_from_slice(&h.finalize().as_slice()[..32]);
+        output
+    }
+
+    fn encode_element(element: &RistrettoPoint) -> Vec<u8> {
+        Ristretto25519::encode_element(element)
+    }
+
+    fn decode_element(bytes: &[u8]) -> Result<RistrettoPoint, Error> {
+        Ristretto25519::decode_element(bytes)
+    }
+
+    fn encode_scalar(scalar: &Scalar) -> Vec<u8> {
+        Ristretto25519::encode_scalar(scalar)
+    }
+
+    fn decode_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+        Ristretto25519::decode_scalar(bytes)
+    }
+}
+
+/// The hash of the [`Ristretto25519`] ciphersuite.
+pub(crate) type RistrettoHash = <Ristretto25519 as Ciphersuite>::Hash;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use curve25519_dalek::traits::Identity;
+    use rand::rngs::OsRng;
+
+    /// Check the group laws and the encodings of a ciphersuite, through its
+    /// generic interface only.
+    fn check_ciphersuite<C: Ciphersuite>() {
+        let a = C::random_scalar(OsRng);
+        let b = C::random_scalar(OsRng);
+
+        assert_eq!(C::mul_base(&(a + b)), C::mul_base(&a) + C::mul_base(&b));
+        assert_eq!(C::mul_base(&(a * b)), C::mul_base(&a) * b);
+        assert_eq!(C::mul_base(&-a), -C::mul_base(&a));
+        assert_eq!(C::mul_base(&a), C::generator() * a);
+
+        let element = C::mul_base(&a);
+        assert_eq!(
+            C::decode_element(&C::encode_element(&element)).unwrap(),
+            element
+        );
+        assert_eq!(C::decode_scalar(&C::encode_scalar(&a)).unwrap(), a);
+        assert!(C::decode_element(&[]).is_err());
+        assert!(C::decode_scalar(&[]).is_err());
+
+        let mut h = C::Hash::default();
+        h.update(b"ICE-FROST");
+        assert_eq!(C::hash_to_scalar(h.clone()), C::hash_to_scalar(h));
+    }
+
+    #[test]
+    fn ristretto25519() {
+        check_ciphersuite::<Ristretto25519>();
+        check_ciphersuite::<UniformRistretto25519<Sha512>>();
+        check_ciphersuite::<Rfc9591Ristretto255>();
+
+        // The ciphersuite agrees with the Ristretto primitives it wraps.
+        let mut h = Sha512::new();
+        h.update(b"ICE-FROST");
+        assert_eq!(
+            Ristretto25519::hash_to_scalar(h.clone()),
+            Scalar::from_hash(h)
+        );
+        assert_eq!(
+            Ristretto25519::encode_element(&RistrettoPoint::identity()),
+            [0u8; 32].to_vec()
+        );
+        assert!(Ristretto25519::decode_scalar(&[0xff; 32]).is_err());
+        assert_eq!(
+            Ristretto25519::message_hash(b"context", b"message"),
+            crate::signature::compute_message_hash(b"context", b"message")
+        );
+
+        // The uniform ciphersuite truncates its hash for the message hash.
+        let mut h = Sha512::new();
+        h.update(b"context");
+        h.update(b"message");
+        assert_eq!(
+            UniformRistretto25519::<Sha512>::message_hash(b"context", b"message")[..],
+            h.finalize()[..32]
+        );
+    }
+}
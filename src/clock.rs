@@ -0,0 +1,125 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Time sources for the components enforcing deadlines.
+//!
+//! No component of this crate reads the time directly.  Those which expire
+//! state, such as a [`SignerSessionManager`](crate::store::SignerSessionManager)
+//! with a session lifetime, are given a [`Clock`] instead, which is a
+//! [`SystemClock`] in production and a [`ManualClock`] in tests, so that
+//! expiry can be tested deterministically without sleeping.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::boxed::Box;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A source of the current time, in milliseconds since the Unix epoch.
+pub trait Clock: Send + Sync {
+    /// The current time, in milliseconds since the Unix epoch.
+    fn now(&self) -> u64;
+}
+
+/// A [`Clock`] reading the system time.
+///
+/// A system time before the Unix epoch is read as the epoch itself.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64)
+    }
+}
+
+/// A [`Clock`] which only moves when told to, for tests.
+///
+/// Clones share the same time, so that a clone can be given to the
+/// component under test and the original advanced from the test.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock(Arc<AtomicU64>);
+
+impl ManualClock {
+    /// Construct a clock reading `now` milliseconds since the Unix epoch.
+    pub fn new(now: u64) -> ManualClock {
+        ManualClock(Arc::new(AtomicU64::new(now)))
+    }
+
+    /// Move this clock forward by `millis` milliseconds.
+    pub fn advance(&self, millis: u64) {
+        self.0.fetch_add(millis, Ordering::SeqCst);
+    }
+
+    /// Set this clock to `now` milliseconds since the Unix epoch, which may
+    /// be in its past, e.g. to test clock skew.
+    pub fn set(&self, now: u64) {
+        self.0.store(now, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> u64 {
+        (**self).now()
+    }
+}
+
+/// The [`Clock`] held by the components enforcing deadlines.
+pub(crate) struct ClockHandle(Box<dyn Clock>);
+
+impl ClockHandle {
+    pub(crate) fn new<C: Clock + 'static>(clock: C) -> ClockHandle {
+        ClockHandle(Box::new(clock))
+    }
+
+    pub(crate) fn now(&self) -> u64 {
+        self.0.now()
+    }
+}
+
+/// The default clock is the [`SystemClock`].
+#[cfg(feature = "std")]
+impl Default for ClockHandle {
+    fn default() -> ClockHandle {
+        ClockHandle::new(SystemClock)
+    }
+}
+
+/// Without the standard library, there is no system time to read, and the
+/// default clock stands still at the Unix epoch until another one is set.
+#[cfg(not(feature = "std"))]
+impl Default for ClockHandle {
+    fn default() -> ClockHandle {
+        ClockHandle::new(ManualClock::default())
+    }
+}
+
+impl fmt::Debug for ClockHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ClockHandle")
+    }
+}
@@ -0,0 +1,334 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Routing of distributed key generation messages by a coordinator.
+//!
+//! The coordinator is an untrusted party which collects the round one
+//! [`Participant`] packages and the round two [`EncryptedSecretShare`]s, and
+//! forwards them to their recipients.  Its routing state can be exported as a
+//! [`DkgCoordinatorState`] and handed over to another coordinating process,
+//! which can then [`DkgCoordinator::resume`] the protocol run.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+#[cfg(feature = "std")]
+use std::string::ToString;
+
+use core::convert::TryInto;
+
+use crate::keygen::EncryptedSecretShare;
+use crate::keygen::Error;
+use crate::keygen::Participant;
+use crate::parameters::Parameters;
+
+/// The routing state of a [`DkgCoordinator`], i.e. who sent what to whom.
+///
+/// This is distinct from a participant's own `DistributedKeyGeneration`
+/// state, and contains no secret material.
+#[derive(Clone, Debug)]
+pub struct DkgCoordinatorState {
+    /// The parameters for this instantiation of the distributed key generation.
+    pub parameters: Parameters,
+    /// The round one packages collected thus far, sorted by participant index.
+    pub participants: Vec<Participant>,
+    /// The round two encrypted secret shares collected thus far.
+    pub encrypted_shares: Vec<EncryptedSecretShare>,
+}
+
+impl DkgCoordinatorState {
+    /// Serialise this coordinator state to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+        res.extend_from_slice(&self.parameters.to_bytes());
+
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.participants.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for participant in self.participants.iter() {
+            let bytes = participant.to_bytes();
+            res.extend_from_slice(&TryInto::<u32>::try_into(bytes.len()).unwrap().to_le_bytes());
+            res.extend_from_slice(&bytes);
+        }
+
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.encrypted_shares.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for share in self.encrypted_shares.iter() {
+            let bytes = share.to_bytes();
+            res.extend_from_slice(&TryInto::<u32>::try_into(bytes.len()).unwrap().to_le_bytes());
+            res.extend_from_slice(&bytes);
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `DkgCoordinatorState`
+    pub fn from_bytes(bytes: &[u8]) -> Result<DkgCoordinatorState, Error> {
+        let parameters = Parameters::from_bytes(
+            bytes
+                .get(0..8)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let mut index_slice = 8usize;
+
+        let len = read_u32(bytes, &mut index_slice)?;
+        let mut participants: Vec<Participant> = Vec::new();
+        for _ in 0..len {
+            let participant_len = read_u32(bytes, &mut index_slice)? as usize;
+            let participant_bytes = bytes
+                .get(index_slice..index_slice + participant_len)
+                .ok_or(Error::SerialisationError)?;
+            participants.push(Participant::from_bytes(participant_bytes)?);
+            index_slice += participant_len;
+        }
+
+        let len = read_u32(bytes, &mut index_slice)?;
+        let mut encrypted_shares: Vec<EncryptedSecretShare> = Vec::new();
+        for _ in 0..len {
+            let share_len = read_u32(bytes, &mut index_slice)? as usize;
+            let share_bytes = bytes
+                .get(index_slice..index_slice + share_len)
+                .ok_or(Error::SerialisationError)?;
+            encrypted_shares.push(EncryptedSecretShare::from_bytes(share_bytes)?);
+            index_slice += share_len;
+        }
+
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(DkgCoordinatorState {
+            parameters,
+            participants,
+            encrypted_shares,
+        })
+    }
+}
+
+fn read_u32(bytes: &[u8], index_slice: &mut usize) -> Result<u32, Error> {
+    let value = u32::from_le_bytes(
+        bytes
+            .get(*index_slice..*index_slice + 4)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    );
+    *index_slice += 4;
+
+    Ok(value)
+}
+
+/// A coordinator routing messages between the participants of a distributed
+/// key generation.
+#[derive(Clone, Debug)]
+pub struct DkgCoordinator {
+    state: DkgCoordinatorState,
+}
+
+impl DkgCoordinator {
+    /// Construct a new coordinator for a distributed key generation run with
+    /// the given `parameters`.
+    pub fn new(parameters: Parameters) -> DkgCoordinator {
+        DkgCoordinator {
+            state: DkgCoordinatorState {
+                parameters,
+                participants: Vec::with_capacity(parameters.n as usize),
+                encrypted_shares: Vec::new(),
+            },
+        }
+    }
+
+    /// Resume coordinating a distributed key generation run from a `state`
+    /// handed over by a previous coordinator.
+    pub fn resume(state: DkgCoordinatorState) -> DkgCoordinator {
+        DkgCoordinator { state }
+    }
+
+    /// Get the current routing state of this coordinator, for handing it
+    /// over to another coordinating process.
+    pub fn state(&self) -> &DkgCoordinatorState {
+        &self.state
+    }
+
+    /// Collect the round one package of a [`Participant`].
+    ///
+    /// # Returns
+    ///
+    /// An `Error::DuplicateParticipant` if a package was already collected for
+    /// this participant index.
+    pub fn include_participant(&mut self, participant: Participant) -> Result<(), Error> {
+        if self
+            .state
+            .participants
+            .iter()
+            .any(|p| p.index == participant.index)
+        {
+            return Err(Error::DuplicateParticipant(participant.index));
+        }
+
+        self.state.participants.push(participant);
+        self.state.participants.sort_by_key(|p| p.index);
+
+        Ok(())
+    }
+
+    /// Get the round one packages collected thus far, to be forwarded to
+    /// every participant.
+    pub fn participants(&self) -> &[Participant] {
+        &self.state.participants
+    }
+
+    /// Whether a round one package was collected for all `n` participants.
+    pub fn is_round_one_complete(&self) -> bool {
+        self.state.participants.len() == self.state.parameters.n as usize
+    }
+
+    /// Collect the round two [`EncryptedSecretShare`]s sent by a participant.
+    ///
+    /// # Returns
+    ///
+    /// An error if a share from the same sender to the same receiver was
+    /// already collected, in which case none of the `shares` are collected.
+    pub fn include_encrypted_shares(
+        &mut self,
+        shares: &[EncryptedSecretShare],
+    ) -> Result<(), Error> {
+        for (i, share) in shares.iter().enumerate() {
+            let is_duplicate = |s: &EncryptedSecretShare| {
+                s.sender_index == share.sender_index && s.receiver_index == share.receiver_index
+            };
+            if self.state.encrypted_shares.iter().any(is_duplicate)
+                || shares[..i].iter().any(is_duplicate)
+            {
+                return Err(Error::Custom(
+                    "Duplicate encrypted secret share for participant".to_string(),
+                ));
+            }
+        }
+
+        self.state.encrypted_shares.extend_from_slice(shares);
+
+        Ok(())
+    }
+
+    /// Get the [`EncryptedSecretShare`]s addressed to the participant with
+    /// the given `receiver_index`, sorted by sender index.
+    pub fn encrypted_shares_for(&self, receiver_index: u32) -> Vec<EncryptedSecretShare> {
+        let mut shares: Vec<EncryptedSecretShare> = self
+            .state
+            .encrypted_shares
+            .iter()
+            .filter(|s| s.receiver_index == receiver_index)
+            .cloned()
+            .collect();
+        shares.sort_by_key(|s| s.sender_index);
+
+        shares
+    }
+
+    /// Whether every participant has received a share from every
+    /// participant.
+    pub fn is_round_two_complete(&self) -> bool {
+        let n = self.state.parameters.n as usize;
+
+        self.state.encrypted_shares.len() == n * n
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keygen::{DistributedKeyGeneration, RoundOne};
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn resume_after_round_one() {
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+
+        let mut coordinator = DkgCoordinator::new(params);
+
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, coeffs, dh_sk) = Participant::new_dealer(&params, i, "Φ", rng);
+            coordinator.include_participant(p.clone()).unwrap();
+            let index = p.index;
+            assert_eq!(
+                coordinator.include_participant(p),
+                Err(Error::DuplicateParticipant(index))
+            );
+            coefficients.push(coeffs);
+            dh_secret_keys.push(dh_sk);
+        }
+        assert!(coordinator.is_round_one_complete());
+
+        // The coordinating process changes after round one.
+        let bytes = coordinator.state().to_bytes();
+        drop(coordinator);
+
+        let state = DkgCoordinatorState::from_bytes(&bytes).unwrap();
+        assert_eq!(state.to_bytes(), bytes);
+        assert!(DkgCoordinatorState::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut coordinator = DkgCoordinator::resume(state);
+        assert!(coordinator.is_round_one_complete());
+        assert!(!coordinator.is_round_two_complete());
+
+        let participants = coordinator.participants().to_vec();
+        let mut states = Vec::new();
+        for i in 0..params.n as usize {
+            let (state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &dh_secret_keys[i],
+                &participants[i].index,
+                &coefficients[i],
+                &participants,
+                "Φ",
+                rng,
+            )
+            .unwrap();
+            let shares = state.their_encrypted_secret_shares().unwrap();
+            coordinator.include_encrypted_shares(shares).unwrap();
+            assert!(coordinator.include_encrypted_shares(&shares[..1]).is_err());
+            states.push(state);
+        }
+        assert!(coordinator.is_round_two_complete());
+
+        let mut group_keys = Vec::new();
+        for (i, state) in states.into_iter().enumerate() {
+            let my_encrypted_secret_shares =
+                coordinator.encrypted_shares_for(participants[i].index);
+            assert_eq!(my_encrypted_secret_shares.len(), params.n as usize);
+
+            let state = state.to_round_two(my_encrypted_secret_shares, rng).unwrap();
+            let (group_key, _secret_key) = state.finish().unwrap();
+            group_keys.push(group_key);
+        }
+
+        assert!(group_keys[0] == group_keys[1]);
+        assert!(group_keys[1] == group_keys[2]);
+    }
+}
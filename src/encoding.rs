@@ -0,0 +1,213 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Encoding and decoding of scalars and Ristretto points.
+//!
+//! Every serialiser of this crate goes through these functions, so that the
+//! canonicality rules are enforced in a single place.  Applications storing
+//! secret scalars, or exposing them over an FFI, should use them rather than
+//! the decoding functions of `curve25519-dalek` directly.
+//!
+//! The orders of the group and of the field it is built over are exposed as
+//! [`GROUP_ORDER`], [`COFACTOR`] and [`FIELD_MODULUS`], for protocols built
+//! on top of this crate.
+
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use subtle::ConstantTimeEq;
+
+use zeroize::Zeroize;
+
+use crate::keygen::Error;
+
+/// The order \\(\ell = 2^{252} + 27742317777372353535851937790883648493\\) of
+/// the Ristretto group, i.e. of its scalar field, in little-endian.
+pub const GROUP_ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// The cofactor of the Ristretto group, in little-endian.
+///
+/// Ristretto is a prime-order group, so this is one, although the
+/// underlying Curve25519 has a cofactor of eight.
+pub const COFACTOR: [u8; 32] = [
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// The modulus \\(p = 2^{255} - 19\\) of the field over which Curve25519 is
+/// defined, in little-endian.
+pub const FIELD_MODULUS: [u8; 32] = [
+    0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+];
+
+/// Encode a scalar as its 32-byte little-endian canonical representation.
+pub fn encode_scalar(scalar: &Scalar) -> [u8; 32] {
+    scalar.to_bytes()
+}
+
+/// Decode a scalar from its 32-byte canonical encoding.
+///
+/// The scalar is reduced and compared against its encoding in constant
+/// time, so that decoding a secret scalar only leaks whether the encoding
+/// was canonical.
+///
+/// # Returns
+///
+/// A `SerialisationError` if the bytes are not the canonical encoding of a
+/// scalar, i.e. if they encode an integer of at least the group order.
+pub fn decode_scalar_canonical(bytes: &[u8; 32]) -> Result<Scalar, Error> {
+    let mut scalar = Scalar::from_bytes_mod_order(*bytes);
+
+    match bool::from(scalar.as_bytes().ct_eq(bytes)) {
+        true => Ok(scalar),
+        false => {
+            scalar.zeroize();
+            Err(Error::SerialisationError)
+        }
+    }
+}
+
+/// Encode a point as its 32-byte compressed Ristretto encoding.
+pub fn encode_point(point: &RistrettoPoint) -> [u8; 32] {
+    point.compress().to_bytes()
+}
+
+/// Decode a point from its 32-byte compressed Ristretto encoding.
+///
+/// The identity is accepted, and callers for which it is not meaningful
+/// must reject it themselves.
+///
+/// # Returns
+///
+/// A `SerialisationError` if the bytes are not the canonical encoding of a
+/// point.
+pub fn decode_point_checked(bytes: &[u8; 32]) -> Result<RistrettoPoint, Error> {
+    let point = CompressedRistretto(*bytes)
+        .decompress()
+        .ok_or(Error::SerialisationError)?;
+    // Ristretto decompression only succeeds on canonical encodings, but we
+    // check the round trip explicitly.
+    if point.compress().as_bytes() != bytes {
+        return Err(Error::SerialisationError);
+    }
+
+    Ok(point)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use curve25519_dalek::constants::EIGHT_TORSION;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+    use curve25519_dalek::traits::Identity;
+    use rand::rngs::OsRng;
+
+    /// The group order \\(\ell = 2^{252} + 27742317777372353535851937790883648493\\).
+    const L: [u8; 32] = [
+        0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde,
+        0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x10,
+    ];
+
+    #[test]
+    fn group_and_field_orders() {
+        assert_eq!(GROUP_ORDER, L);
+        assert_eq!(Scalar::from_bytes_mod_order(GROUP_ORDER), Scalar::zero());
+        assert_eq!(
+            &RISTRETTO_BASEPOINT_TABLE * &Scalar::from_bits(GROUP_ORDER),
+            RistrettoPoint::identity()
+        );
+        assert_eq!(Scalar::from_bytes_mod_order(COFACTOR), Scalar::one());
+
+        // p + 19 = 2^255.
+        let mut carry = 19u16;
+        let mut p_plus_19 = FIELD_MODULUS;
+        for byte in p_plus_19.iter_mut() {
+            let sum = *byte as u16 + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+        let mut two_255 = [0u8; 32];
+        two_255[31] = 0x80;
+        assert_eq!(p_plus_19, two_255);
+    }
+
+    #[test]
+    fn scalar_edge_encodings() {
+        let scalar = Scalar::random(&mut OsRng);
+        assert_eq!(
+            decode_scalar_canonical(&encode_scalar(&scalar)).unwrap(),
+            scalar
+        );
+        assert_eq!(decode_scalar_canonical(&[0u8; 32]).unwrap(), Scalar::zero());
+
+        // l - 1 is the largest canonical scalar.
+        let mut l_minus_one = L;
+        l_minus_one[0] -= 1;
+        assert_eq!(
+            decode_scalar_canonical(&l_minus_one).unwrap(),
+            -Scalar::one()
+        );
+
+        // l, l + 1, 2^255 - 1 and 2^256 - 1 are not canonical.
+        let mut l_plus_one = L;
+        l_plus_one[0] += 1;
+        let mut max_255 = [0xffu8; 32];
+        max_255[31] = 0x7f;
+        for bytes in [L, l_plus_one, max_255, [0xffu8; 32]] {
+            assert_eq!(
+                decode_scalar_canonical(&bytes),
+                Err(Error::SerialisationError)
+            );
+            assert!(Scalar::from_canonical_bytes(bytes).is_none());
+        }
+    }
+
+    #[test]
+    fn point_edge_encodings() {
+        let point = &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng);
+        assert_eq!(decode_point_checked(&encode_point(&point)).unwrap(), point);
+
+        // The identity is accepted, and has a single encoding.
+        let identity = encode_point(&RistrettoPoint::identity());
+        assert_eq!(identity, [0u8; 32]);
+        assert_eq!(
+            decode_point_checked(&identity).unwrap(),
+            RistrettoPoint::identity()
+        );
+
+        // The Edwards encodings of the torsion points are either rejected,
+        // or decode to a point whose canonical encoding they are.
+        for torsion in EIGHT_TORSION.iter() {
+            let bytes = torsion.compress().to_bytes();
+            if let Ok(decoded) = decode_point_checked(&bytes) {
+                assert_eq!(encode_point(&decoded), bytes);
+            }
+        }
+
+        // Negative field elements and non-reduced encodings, such as the
+        // field order itself, are rejected.
+        let mut negative = [0u8; 32];
+        negative[0] = 1;
+        let mut non_reduced = [0xffu8; 32];
+        non_reduced[31] = 0x7f;
+        non_reduced[0] = 0xed;
+        for bytes in [negative, non_reduced, [0xffu8; 32]] {
+            assert_eq!(decode_point_checked(&bytes), Err(Error::SerialisationError));
+        }
+    }
+}
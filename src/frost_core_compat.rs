@@ -0,0 +1,240 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Conversions between the types of this crate and those of the
+//! `frost-core` ecosystem, over the `frost-ristretto255` ciphersuite.
+//!
+//! Keys and signatures are converted through their encodings, which line up
+//! with those of `frost-ristretto255`:
+//!
+//! * A [`GroupKey`] converts to and from a `VerifyingKey`, and an
+//!   [`IndividualPublicKey`] to and from an `Identifier` along with a
+//!   `VerifyingShare`.
+//! * A [`SecretKey`] converts to and from an `Identifier` along with a
+//!   `SigningShare`.  The index of a participant is the scalar of its
+//!   identifier, so that only identifiers below \\(2^{32}\\) convert back.
+//! * A [`ThresholdSignature`] converts to and from a `Signature`.
+//!
+//! The signatures themselves do not verify across the two crates: the
+//! challenge of this crate is computed over another domain separator, the
+//! signing parameters and a pre-hashed message.  A group migrating to
+//! `frost-core` keeps its keys, but must produce new signatures.
+//!
+//! `frost-core` rejects the encoding of the identity, hence a group key, a
+//! public share or a signature commitment equal to the identity fails to
+//! convert, with a `SerialisationError`.
+
+use core::convert::TryFrom;
+use core::convert::TryInto;
+
+use curve25519_dalek::scalar::Scalar;
+
+use frost_ristretto255::keys::SigningShare;
+use frost_ristretto255::keys::VerifyingShare;
+use frost_ristretto255::Identifier;
+use frost_ristretto255::Signature;
+use frost_ristretto255::VerifyingKey;
+
+use zeroize::Zeroize;
+
+use crate::encoding::decode_point_checked;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_point;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::IndividualPublicKey;
+use crate::keygen::SecretKey;
+use crate::signature::ThresholdSignature;
+
+/// The `frost-core` identifier of the participant of index `index`.
+fn identifier(index: u32) -> Result<Identifier, Error> {
+    Identifier::deserialize(&Scalar::from(index).to_bytes()).map_err(|_| Error::SerialisationError)
+}
+
+/// The index of the participant of `frost-core` identifier `identifier`.
+fn index(identifier: &Identifier) -> Result<u32, Error> {
+    let bytes = identifier.serialize();
+    if bytes[4..].iter().any(|byte| *byte != 0) {
+        return Err(Error::SerialisationError);
+    }
+
+    Ok(u32::from_le_bytes(
+        bytes[0..4]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    ))
+}
+
+impl TryFrom<&GroupKey> for VerifyingKey {
+    type Error = Error;
+
+    fn try_from(group_key: &GroupKey) -> Result<VerifyingKey, Error> {
+        VerifyingKey::deserialize(group_key.to_bytes()).map_err(|_| Error::SerialisationError)
+    }
+}
+
+impl TryFrom<&VerifyingKey> for GroupKey {
+    type Error = Error;
+
+    fn try_from(verifying_key: &VerifyingKey) -> Result<GroupKey, Error> {
+        GroupKey::from_bytes_strict(&verifying_key.serialize())
+    }
+}
+
+impl TryFrom<&IndividualPublicKey> for (Identifier, VerifyingShare) {
+    type Error = Error;
+
+    fn try_from(public_key: &IndividualPublicKey) -> Result<(Identifier, VerifyingShare), Error> {
+        let verifying_share = VerifyingShare::deserialize(encode_point(&public_key.share))
+            .map_err(|_| Error::SerialisationError)?;
+
+        Ok((identifier(public_key.index)?, verifying_share))
+    }
+}
+
+impl TryFrom<(Identifier, VerifyingShare)> for IndividualPublicKey {
+    type Error = Error;
+
+    fn try_from(
+        (identifier, verifying_share): (Identifier, VerifyingShare),
+    ) -> Result<IndividualPublicKey, Error> {
+        let share = decode_point_checked(&verifying_share.serialize())?;
+
+        Ok(IndividualPublicKey::new(index(&identifier)?, share))
+    }
+}
+
+impl TryFrom<&SecretKey> for (Identifier, SigningShare) {
+    type Error = Error;
+
+    fn try_from(secret_key: &SecretKey) -> Result<(Identifier, SigningShare), Error> {
+        let mut bytes = secret_key.key.to_bytes();
+        let signing_share = SigningShare::deserialize(bytes).map_err(|_| Error::SerialisationError);
+        bytes.zeroize();
+
+        Ok((identifier(secret_key.index)?, signing_share?))
+    }
+}
+
+impl TryFrom<(Identifier, SigningShare)> for SecretKey {
+    type Error = Error;
+
+    fn try_from(
+        (identifier, signing_share): (Identifier, SigningShare),
+    ) -> Result<SecretKey, Error> {
+        let mut bytes = signing_share.serialize();
+        let key = decode_scalar_canonical(&bytes);
+        bytes.zeroize();
+
+        Ok(SecretKey {
+            index: index(&identifier)?,
+            key: key?,
+        })
+    }
+}
+
+impl TryFrom<&ThresholdSignature> for Signature {
+    type Error = Error;
+
+    fn try_from(signature: &ThresholdSignature) -> Result<Signature, Error> {
+        Signature::deserialize(signature.to_bytes()).map_err(|_| Error::SerialisationError)
+    }
+}
+
+impl TryFrom<&Signature> for ThresholdSignature {
+    type Error = Error;
+
+    fn try_from(signature: &Signature) -> Result<ThresholdSignature, Error> {
+        ThresholdSignature::from_bytes(&signature.serialize())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::traits::Identity;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn group_key_round_trip() {
+        let group_key = GroupKey::new(RistrettoPoint::random(&mut OsRng));
+
+        let verifying_key = VerifyingKey::try_from(&group_key).unwrap();
+        assert_eq!(verifying_key.serialize(), group_key.to_bytes());
+        assert_eq!(GroupKey::try_from(&verifying_key).unwrap(), group_key);
+
+        let identity = GroupKey::new(RistrettoPoint::identity());
+        assert_eq!(
+            VerifyingKey::try_from(&identity).unwrap_err(),
+            Error::SerialisationError
+        );
+    }
+
+    #[test]
+    fn key_share_round_trip() {
+        let secret_key = SecretKey {
+            index: 7,
+            key: Scalar::random(&mut OsRng),
+        };
+        let public_key = secret_key.to_public();
+
+        let (identifier, signing_share) =
+            <(Identifier, SigningShare)>::try_from(&secret_key).unwrap();
+        assert_eq!(identifier, Identifier::try_from(7u16).unwrap());
+        assert_eq!(
+            SecretKey::try_from((identifier, signing_share)).unwrap(),
+            secret_key
+        );
+
+        // The converted public share is the one frost-core derives from the
+        // converted secret share.
+        let (public_identifier, verifying_share) =
+            <(Identifier, VerifyingShare)>::try_from(&public_key).unwrap();
+        assert_eq!(public_identifier, identifier);
+        assert_eq!(verifying_share, VerifyingShare::from(signing_share));
+        assert_eq!(
+            IndividualPublicKey::try_from((public_identifier, verifying_share)).unwrap(),
+            public_key
+        );
+
+        // Index zero has no identifier, and identifiers beyond the range of
+        // indices have no index.
+        let zero = SecretKey {
+            index: 0,
+            key: Scalar::random(&mut OsRng),
+        };
+        assert_eq!(
+            <(Identifier, SigningShare)>::try_from(&zero).unwrap_err(),
+            Error::SerialisationError
+        );
+        let large = Identifier::deserialize(&Scalar::from(1u64 << 32).to_bytes()).unwrap();
+        assert_eq!(
+            SecretKey::try_from((large, signing_share)).unwrap_err(),
+            Error::SerialisationError
+        );
+    }
+
+    #[test]
+    fn signature_round_trip() {
+        let signature = ThresholdSignature {
+            R: &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng),
+            z: Scalar::random(&mut OsRng),
+        };
+
+        let converted = Signature::try_from(&signature).unwrap();
+        assert_eq!(converted.serialize(), signature.to_bytes());
+        assert_eq!(ThresholdSignature::try_from(&converted).unwrap(), signature);
+    }
+}
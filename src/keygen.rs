@@ -99,15 +99,15 @@
 //! //
 //! // Bob and Carol verify Alice's zero-knowledge proof by doing:
 //!
-//! alice.proof_of_secret_key.as_ref().unwrap()
+//! alice.proof_of_secret_key
 //!     .verify(&alice.index, &alice.public_key().unwrap(), "Φ").or(Err(()))?;
 //!
 //! // Similarly, Alice and Carol verify Bob's proof:
-//! bob.proof_of_secret_key.as_ref().unwrap()
+//! bob.proof_of_secret_key
 //!     .verify(&bob.index, &bob.public_key().unwrap(), "Φ").or(Err(()))?;
 //!
 //! // And, again, Alice and Bob verify Carol's proof:
-//! carol.proof_of_secret_key.as_ref().unwrap()
+//! carol.proof_of_secret_key
 //!     .verify(&carol.index, &carol.public_key().unwrap(), "Φ").or(Err(()))?;
 //!
 //! // Alice enters round one of the distributed key generation protocol.
@@ -213,6 +213,7 @@
 //! use ice_frost::DistributedKeyGeneration;
 //! use ice_frost::Parameters;
 //! use ice_frost::Participant;
+//! use ice_frost::DkgSigner;
 //! use curve25519_dalek::ristretto::RistrettoPoint;
 //! use curve25519_dalek::traits::Identity;
 //! use curve25519_dalek::scalar::Scalar;
@@ -245,15 +246,15 @@
 //! //
 //! // Bob and Carol verify Alice's zero-knowledge proof by doing:
 //!
-//! alice.proof_of_secret_key.as_ref().unwrap()
+//! alice.proof_of_secret_key
 //!     .verify(&alice.index, &alice.public_key().unwrap(), "Φ").or(Err(()))?;
 //!
 //! // Similarly, Alice and Carol verify Bob's proof:
-//! bob.proof_of_secret_key.as_ref().unwrap()
+//! bob.proof_of_secret_key
 //!     .verify(&bob.index, &bob.public_key().unwrap(), "Φ").or(Err(()))?;
 //!
 //! // And, again, Alice and Bob verify Carol's proof:
-//! carol.proof_of_secret_key.as_ref().unwrap()
+//! carol.proof_of_secret_key
 //!     .verify(&carol.index, &carol.public_key().unwrap(), "Φ").or(Err(()))?;
 //!
 //! // Alice enters round one of the distributed key generation protocol.
@@ -346,10 +347,10 @@
 //!
 //! // Alexis, Barbara, Claire and David each generate their Diffie-Hellman
 //! // private key, as well as a zero-knowledge proof to it.
-//! let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, "Φ", &mut rng);
-//! let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, "Φ", &mut rng);
-//! let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, "Φ", &mut rng);
-//! let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, "Φ", &mut rng);
+//! let (alexis, alexis_dh_sk) = DkgSigner::new(&new_params, 1, "Φ", &mut rng);
+//! let (barbara, barbara_dh_sk) = DkgSigner::new(&new_params, 2, "Φ", &mut rng);
+//! let (claire, claire_dh_sk) = DkgSigner::new(&new_params, 3, "Φ", &mut rng);
+//! let (david, david_dh_sk) = DkgSigner::new(&new_params, 4, "Φ", &mut rng);
 //!
 //! // They send these values to each of the other and previous participants
 //! // (out of scope for this library), or otherwise publish them somewhere.
@@ -388,7 +389,7 @@
 //! // Alice, Bob and Carol compute new secret shares of their long-lived secret signing key,
 //! // encrypted for Alexis, Barbara, Claire and David respectively.
 //!
-//! let signers: Vec<Participant> =
+//! let signers: Vec<DkgSigner> =
 //!     vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //!     Participant::reshare(&new_params, alice_secret_key, &signers, "Φ", &mut rng).or(Err(()))?;
@@ -497,11 +498,15 @@
 #[cfg(feature = "std")]
 use std::boxed::Box;
 #[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
 use std::vec::Vec;
 
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 #[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
 #[cfg(feature = "alloc")]
@@ -520,6 +525,7 @@ use curve25519_dalek::ristretto::RistrettoPoint;
 use curve25519_dalek::scalar::Scalar;
 use curve25519_dalek::traits::Identity;
 
+use rand::rngs::OsRng;
 use rand::CryptoRng;
 use rand::RngCore;
 
@@ -528,11 +534,26 @@ use sha2::Sha512;
 
 use hkdf::Hkdf;
 
+use hmac::{Hmac, Mac, NewMac};
+
+use sha2::Sha256;
+
+use subtle::{Choice, ConditionallySelectable};
+
 use zeroize::Zeroize;
 
+use crate::attestation::DkgReceipt;
+use crate::attestation::GroupPublicInfo;
+use crate::attestation::RefreshProof;
+use crate::attestation::ResolutionRecord;
+use crate::encoding::decode_point_checked;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_point;
+use crate::encoding::encode_scalar;
 use crate::nizk::NizkOfSecretKey;
 use crate::parameters::Parameters;
 use crate::signature::calculate_lagrange_coefficients;
+use crate::signature::calculate_lagrange_coefficients_at;
 
 use aes::cipher::{generic_array::GenericArray, FromBlockCipher, NewBlockCipher, StreamCipher};
 use aes::{Aes256, Aes256Ctr};
@@ -562,6 +583,112 @@ pub enum Error {
     InvalidNumberOfParticipants(usize, u32),
     /// Too many invalid participants, with their indices
     TooManyInvalidParticipants(Vec<u32>),
+    /// Pre-generated dealer material does not match the instance parameters
+    InvalidDealerMaterial,
+    /// The options of a dealer under construction are inconsistent, for this
+    /// reason
+    InvalidDealerOptions(&'static str),
+    /// The signature does not verify under any allowed context
+    InvalidSignature,
+    /// The signature has already been accepted
+    ReplayedSignature,
+    /// The keygen attestation of the participant with this index is invalid
+    InvalidAttestation(u32),
+    /// The keygen attestation of the participant with this index is missing
+    MissingAttestation(u32),
+    /// The participant with this index is not in the roster
+    UnknownParticipant(u32),
+    /// The participant with this index is already in the roster, or already
+    /// sent its round one package
+    DuplicateParticipant(u32),
+    /// The point encoding is not canonical
+    NonCanonicalPoint,
+    /// The point is the identity
+    IdentityPoint,
+    /// The dealer with this index sent a share in a format version which the
+    /// recipient does not accept
+    UnsupportedShareVersion {
+        /// The index of the dealer.
+        dealer: u32,
+        /// The version of the share.
+        version: ShareVersion,
+    },
+    /// The dealer with this index published a number of commitments which
+    /// does not match the threshold
+    WrongCommitmentCount {
+        /// The index of the dealer.
+        participant: u32,
+        /// The number of commitments expected, i.e. the threshold.
+        expected: u32,
+        /// The number of commitments published.
+        got: usize,
+    },
+    /// The signer with this index uploaded a commitment share twice
+    DuplicateCommitmentShare {
+        /// The index of the signer.
+        signer: u32,
+        /// The position in the batch of the second upload.
+        position: u32,
+    },
+    /// The signer with this index uploaded a batch of commitment shares of
+    /// an unexpected length
+    CommitmentBatchLength {
+        /// The index of the signer.
+        signer: u32,
+        /// The number of commitment shares expected.
+        expected: u32,
+        /// The number of commitment shares uploaded.
+        got: u32,
+    },
+    /// There is no commitment at this position of the commitments of the
+    /// participant
+    CommitmentIndexOutOfRange {
+        /// The index of the participant.
+        participant: u32,
+        /// The position of the commitment.
+        position: usize,
+        /// The number of commitments.
+        len: usize,
+    },
+    /// The state store failed, with this message
+    StorageError(String),
+    /// The split record does not link the sub-groups to the parent group
+    InvalidSplitRecord,
+    /// The resolution record was not made for the given roster
+    InvalidResolutionRecord,
+    /// The refresh proof does not show that the group key was left unchanged
+    InvalidRefreshProof,
+    /// The dealer with this index encrypted shares to two recipients under
+    /// the same nonce
+    NonceReuseByDealer {
+        /// The index of the dealer.
+        dealer: u32,
+    },
+    /// The public transcript of a key generation is invalid, because of the
+    /// participant with this index
+    InvalidTranscript {
+        /// The index of the participant.
+        participant: u32,
+        /// What is wrong with the messages of the participant.
+        reason: &'static str,
+    },
+    /// Two participants published the same Diffie-Hellman public key, so
+    /// that each could decrypt the shares sent to the other
+    DuplicateDhPublicKey {
+        /// The lower index of the two participants.
+        first: u32,
+        /// The higher index of the two participants.
+        second: u32,
+    },
+    /// The participant with this index has already had its quota of
+    /// complaints adjudicated
+    ComplaintQuotaExceeded(u32),
+    /// The DH private key of this participant is held externally, and the
+    /// operation must be given its [`ExternalDhKey`]
+    ExternalDhKey,
+    /// The share of an enrolled participant does not match the public keys
+    /// of the group at its index
+    InvalidEnrollmentShare,
     /// Custom error
     Custom(String),
 }
@@ -616,6 +743,155 @@ impl fmt::Display for Error {
                     indices
                 )
             }
+            Error::InvalidDealerMaterial => {
+                write!(
+                    f,
+                    "The dealer material does not match the DKG instance parameters."
+                )
+            }
+            Error::InvalidDealerOptions(reason) => {
+                write!(f, "The dealer options are inconsistent: {}.", reason)
+            }
+            Error::InvalidSignature => {
+                write!(f, "The signature is not correct for any allowed context.")
+            }
+            Error::ReplayedSignature => {
+                write!(f, "The signature has already been accepted.")
+            }
+            Error::InvalidAttestation(index) => {
+                write!(
+                    f,
+                    "The keygen attestation of participant {} is not correct.",
+                    index
+                )
+            }
+            Error::MissingAttestation(index) => {
+                write!(
+                    f,
+                    "The keygen attestation of participant {} is missing.",
+                    index
+                )
+            }
+            Error::UnknownParticipant(index) => {
+                write!(f, "The participant {} is not in the roster.", index)
+            }
+            Error::DuplicateParticipant(index) => {
+                write!(f, "The participant {} is already in the roster.", index)
+            }
+            Error::NonCanonicalPoint => {
+                write!(f, "The point encoding is not canonical.")
+            }
+            Error::IdentityPoint => {
+                write!(f, "The point is the identity.")
+            }
+            Error::UnsupportedShareVersion { dealer, version } => {
+                write!(
+                    f,
+                    "Dealer {} sent a share in the unsupported format {:?}.",
+                    dealer, version
+                )
+            }
+            Error::WrongCommitmentCount {
+                participant,
+                expected,
+                got,
+            } => {
+                write!(
+                    f,
+                    "Dealer {} published {} commitments instead of {}.",
+                    participant, got, expected
+                )
+            }
+            Error::DuplicateCommitmentShare { signer, position } => {
+                write!(
+                    f,
+                    "Signer {} uploaded at position {} a commitment share it already uploaded.",
+                    signer, position
+                )
+            }
+            Error::CommitmentBatchLength {
+                signer,
+                expected,
+                got,
+            } => {
+                write!(
+                    f,
+                    "Signer {} uploaded {} commitment shares instead of {}.",
+                    signer, got, expected
+                )
+            }
+            Error::CommitmentIndexOutOfRange {
+                participant,
+                position,
+                len,
+            } => {
+                write!(
+                    f,
+                    "Participant {} has no commitment at position {} out of {}.",
+                    participant, position, len
+                )
+            }
+            Error::StorageError(message) => {
+                write!(f, "The state store failed: {}", message)
+            }
+            Error::InvalidSplitRecord => {
+                write!(
+                    f,
+                    "The split record does not link the sub-groups to the parent group."
+                )
+            }
+            Error::InvalidResolutionRecord => {
+                write!(
+                    f,
+                    "The resolution record was not made for the given roster."
+                )
+            }
+            Error::InvalidRefreshProof => {
+                write!(
+                    f,
+                    "The refresh proof does not show that the group key was left unchanged."
+                )
+            }
+            Error::NonceReuseByDealer { dealer } => {
+                write!(
+                    f,
+                    "Dealer {} encrypted shares to several recipients under the same nonce.",
+                    dealer
+                )
+            }
+            Error::InvalidTranscript {
+                participant,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "The transcript is invalid because of participant {}: {}.",
+                    participant, reason
+                )
+            }
+            Error::DuplicateDhPublicKey { first, second } => {
+                write!(
+                    f,
+                    "The participants {} and {} have the same DH public key.",
+                    first, second
+                )
+            }
+            Error::ComplaintQuotaExceeded(index) => {
+                write!(
+                    f,
+                    "The participant {} has exceeded its quota of complaints.",
+                    index
+                )
+            }
+            Error::ExternalDhKey => {
+                write!(f, "The DH private key is held externally.")
+            }
+            Error::InvalidEnrollmentShare => {
+                write!(
+                    f,
+                    "The enrolled share does not match the public keys of the group."
+                )
+            }
             Error::Custom(string) => {
                 write!(f, "{:?}", string)
             }
@@ -623,6 +899,280 @@ impl fmt::Display for Error {
     }
 }
 
+/// A coarse classification of errors, telling an orchestration layer whether
+/// to retry an operation or to abort it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// Some input is missing, and the operation may succeed once it arrives.
+    Retryable,
+    /// A participant misbehaved, or cryptographic material is invalid.  The
+    /// operation should be aborted and humans alerted.
+    Misbehaviour {
+        /// The index of the implicated participant, when it is known.
+        participant: Option<u32>,
+    },
+    /// The inputs are inconsistent with the configuration of the instance.
+    Configuration,
+    /// Some input could not be deserialised.
+    Serialization,
+}
+
+impl Error {
+    /// Classify this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::MissingShares
+            | Error::NoEncryptedShares
+            | Error::MissingAttestation(_)
+            | Error::StorageError(_) => ErrorKind::Retryable,
+            Error::DecryptionError
+            | Error::ShareVerificationError
+            | Error::ComplaintVerificationError
+            | Error::InvalidGroupKey
+            | Error::InvalidProofOfKnowledge
+            | Error::Complaint(_)
+            | Error::TooManyInvalidParticipants(_)
+            | Error::InvalidSignature
+            | Error::ReplayedSignature
+            | Error::InvalidAttestation(_)
+            | Error::InvalidSplitRecord
+            | Error::InvalidResolutionRecord
+            | Error::InvalidRefreshProof
+            | Error::NonceReuseByDealer { .. }
+            | Error::InvalidTranscript { .. }
+            | Error::DuplicateDhPublicKey { .. }
+            | Error::ComplaintQuotaExceeded(_)
+            | Error::InvalidEnrollmentShare
+            | Error::WrongCommitmentCount { .. }
+            | Error::DuplicateCommitmentShare { .. }
+            | Error::CommitmentBatchLength { .. } => ErrorKind::Misbehaviour {
+                participant: self.participant(),
+            },
+            Error::InvalidNumberOfParticipants(_, _)
+            | Error::InvalidDealerMaterial
+            | Error::InvalidDealerOptions(_)
+            | Error::UnknownParticipant(_)
+            | Error::DuplicateParticipant(_)
+            | Error::UnsupportedShareVersion { .. }
+            | Error::CommitmentIndexOutOfRange { .. }
+            | Error::ExternalDhKey
+            | Error::Custom(_) => ErrorKind::Configuration,
+            Error::SerialisationError | Error::NonCanonicalPoint | Error::IdentityPoint => {
+                ErrorKind::Serialization
+            }
+        }
+    }
+
+    /// The index of the participant implicated by this error, if any.
+    ///
+    /// For complaints and lists of invalid participants, this is the first
+    /// implicated participant, and for a duplicate DH public key, the lower
+    /// of the two indices.
+    pub fn participant(&self) -> Option<u32> {
+        match self {
+            Error::Complaint(complaints) => complaints.first().map(|c| c.accused_index),
+            Error::TooManyInvalidParticipants(indices) => indices.first().copied(),
+            Error::InvalidAttestation(index)
+            | Error::MissingAttestation(index)
+            | Error::UnknownParticipant(index)
+            | Error::DuplicateParticipant(index)
+            | Error::ComplaintQuotaExceeded(index) => Some(*index),
+            Error::UnsupportedShareVersion { dealer, .. }
+            | Error::NonceReuseByDealer { dealer } => Some(*dealer),
+            Error::WrongCommitmentCount { participant, .. }
+            | Error::CommitmentIndexOutOfRange { participant, .. }
+            | Error::InvalidTranscript { participant, .. } => Some(*participant),
+            Error::DuplicateCommitmentShare { signer, .. }
+            | Error::CommitmentBatchLength { signer, .. } => Some(*signer),
+            Error::DuplicateDhPublicKey { first, .. } => Some(*first),
+            Error::SerialisationError
+            | Error::DecryptionError
+            | Error::ShareVerificationError
+            | Error::ComplaintVerificationError
+            | Error::InvalidGroupKey
+            | Error::InvalidProofOfKnowledge
+            | Error::MissingShares
+            | Error::NoEncryptedShares
+            | Error::InvalidNumberOfParticipants(_, _)
+            | Error::InvalidDealerMaterial
+            | Error::InvalidDealerOptions(_)
+            | Error::InvalidSignature
+            | Error::ReplayedSignature
+            | Error::NonCanonicalPoint
+            | Error::IdentityPoint
+            | Error::StorageError(_)
+            | Error::InvalidSplitRecord
+            | Error::InvalidResolutionRecord
+            | Error::InvalidRefreshProof
+            | Error::ExternalDhKey
+            | Error::InvalidEnrollmentShare
+            | Error::Custom(_) => None,
+        }
+    }
+
+    /// A stable numeric code for this error, e.g. to report it over FFI.
+    ///
+    /// Codes are never reassigned: a new variant takes the next unused
+    /// code, and the code of a removed variant is not reused.  The code 0 is
+    /// not assigned, so that it can stand for success.
+    ///
+    /// | Code | Variant |
+    /// |-----:|---------|
+    /// |    1 | `SerialisationError` |
+    /// |    2 | `DecryptionError` |
+    /// |    3 | `ShareVerificationError` |
+    /// |    4 | `ComplaintVerificationError` |
+    /// |    5 | `InvalidGroupKey` |
+    /// |    6 | `InvalidProofOfKnowledge` |
+    /// |    7 | `MissingShares` |
+    /// |    8 | `NoEncryptedShares` |
+    /// |    9 | `Complaint` |
+    /// |   10 | `InvalidNumberOfParticipants` |
+    /// |   11 | `TooManyInvalidParticipants` |
+    /// |   12 | `InvalidDealerMaterial` |
+    /// |   13 | `InvalidDealerOptions` |
+    /// |   14 | `InvalidSignature` |
+    /// |   15 | `ReplayedSignature` |
+    /// |   16 | `InvalidAttestation` |
+    /// |   17 | `MissingAttestation` |
+    /// |   18 | `UnknownParticipant` |
+    /// |   19 | `DuplicateParticipant` |
+    /// |   20 | `NonCanonicalPoint` |
+    /// |   21 | `IdentityPoint` |
+    /// |   22 | `UnsupportedShareVersion` |
+    /// |   23 | `WrongCommitmentCount` |
+    /// |   24 | `DuplicateCommitmentShare` |
+    /// |   25 | `CommitmentBatchLength` |
+    /// |   26 | `CommitmentIndexOutOfRange` |
+    /// |   27 | `StorageError` |
+    /// |   28 | `InvalidSplitRecord` |
+    /// |   29 | `InvalidResolutionRecord` |
+    /// |   30 | `InvalidRefreshProof` |
+    /// |   31 | `NonceReuseByDealer` |
+    /// |   32 | `ComplaintQuotaExceeded` |
+    /// |   33 | `Custom` |
+    /// |   34 | `InvalidTranscript` |
+    /// |   35 | `DuplicateDhPublicKey` |
+    /// |   36 | `ExternalDhKey` |
+    /// |   37 | `InvalidEnrollmentShare` |
+    pub fn code(&self) -> u32 {
+        match self {
+            Error::SerialisationError => 1,
+            Error::DecryptionError => 2,
+            Error::ShareVerificationError => 3,
+            Error::ComplaintVerificationError => 4,
+            Error::InvalidGroupKey => 5,
+            Error::InvalidProofOfKnowledge => 6,
+            Error::MissingShares => 7,
+            Error::NoEncryptedShares => 8,
+            Error::Complaint(_) => 9,
+            Error::InvalidNumberOfParticipants(_, _) => 10,
+            Error::TooManyInvalidParticipants(_) => 11,
+            Error::InvalidDealerMaterial => 12,
+            Error::InvalidDealerOptions(_) => 13,
+            Error::InvalidSignature => 14,
+            Error::ReplayedSignature => 15,
+            Error::InvalidAttestation(_) => 16,
+            Error::MissingAttestation(_) => 17,
+            Error::UnknownParticipant(_) => 18,
+            Error::DuplicateParticipant(_) => 19,
+            Error::NonCanonicalPoint => 20,
+            Error::IdentityPoint => 21,
+            Error::UnsupportedShareVersion { .. } => 22,
+            Error::WrongCommitmentCount { .. } => 23,
+            Error::DuplicateCommitmentShare { .. } => 24,
+            Error::CommitmentBatchLength { .. } => 25,
+            Error::CommitmentIndexOutOfRange { .. } => 26,
+            Error::StorageError(_) => 27,
+            Error::InvalidSplitRecord => 28,
+            Error::InvalidResolutionRecord => 29,
+            Error::InvalidRefreshProof => 30,
+            Error::NonceReuseByDealer { .. } => 31,
+            Error::ComplaintQuotaExceeded(_) => 32,
+            Error::Custom(_) => 33,
+            Error::InvalidTranscript { .. } => 34,
+            Error::DuplicateDhPublicKey { .. } => 35,
+            Error::ExternalDhKey => 36,
+            Error::InvalidEnrollmentShare => 37,
+        }
+    }
+
+    /// Reconstruct an error from its [`Error::code`], with its data, e.g.
+    /// participant indices, set to zero or empty.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no variant has this code.
+    pub fn from_code(code: u32) -> Option<Error> {
+        let error = match code {
+            1 => Error::SerialisationError,
+            2 => Error::DecryptionError,
+            3 => Error::ShareVerificationError,
+            4 => Error::ComplaintVerificationError,
+            5 => Error::InvalidGroupKey,
+            6 => Error::InvalidProofOfKnowledge,
+            7 => Error::MissingShares,
+            8 => Error::NoEncryptedShares,
+            9 => Error::Complaint(Vec::new()),
+            10 => Error::InvalidNumberOfParticipants(0, 0),
+            11 => Error::TooManyInvalidParticipants(Vec::new()),
+            12 => Error::InvalidDealerMaterial,
+            13 => Error::InvalidDealerOptions(""),
+            14 => Error::InvalidSignature,
+            15 => Error::ReplayedSignature,
+            16 => Error::InvalidAttestation(0),
+            17 => Error::MissingAttestation(0),
+            18 => Error::UnknownParticipant(0),
+            19 => Error::DuplicateParticipant(0),
+            20 => Error::NonCanonicalPoint,
+            21 => Error::IdentityPoint,
+            22 => Error::UnsupportedShareVersion {
+                dealer: 0,
+                version: ShareVersion::DEFAULT,
+            },
+            23 => Error::WrongCommitmentCount {
+                participant: 0,
+                expected: 0,
+                got: 0,
+            },
+            24 => Error::DuplicateCommitmentShare {
+                signer: 0,
+                position: 0,
+            },
+            25 => Error::CommitmentBatchLength {
+                signer: 0,
+                expected: 0,
+                got: 0,
+            },
+            26 => Error::CommitmentIndexOutOfRange {
+                participant: 0,
+                position: 0,
+                len: 0,
+            },
+            27 => Error::StorageError(String::new()),
+            28 => Error::InvalidSplitRecord,
+            29 => Error::InvalidResolutionRecord,
+            30 => Error::InvalidRefreshProof,
+            31 => Error::NonceReuseByDealer { dealer: 0 },
+            32 => Error::ComplaintQuotaExceeded(0),
+            33 => Error::Custom(String::new()),
+            34 => Error::InvalidTranscript {
+                participant: 0,
+                reason: "",
+            },
+            35 => Error::DuplicateDhPublicKey {
+                first: 0,
+                second: 0,
+            },
+            36 => Error::ExternalDhKey,
+            37 => Error::InvalidEnrollmentShare,
+            _ => return None,
+        };
+
+        Some(error)
+    }
+}
+
 /// A struct for holding a shard of the shared secret, in order to ensure that
 /// the shard is overwritten with zeroes when it falls out of scope.
 #[derive(Zeroize)]
@@ -630,14 +1180,16 @@ impl fmt::Display for Error {
 pub struct Coefficients(pub(crate) Vec<Scalar>);
 
 impl Coefficients {
+    /// Create another copy of these coefficients, which is zeroed out when
+    /// dropped independently of this one.
+    pub fn duplicate(&self) -> Coefficients {
+        Coefficients(self.0.clone())
+    }
+
     /// Serialise these coefficients as a Vec of bytes
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut res: Vec<u8> = Vec::with_capacity(self.0.len() * 32 + 4);
-        let mut tmp = self
-            .0
-            .iter()
-            .map(|e| e.to_bytes())
-            .collect::<Vec<[u8; 32]>>();
+        let mut tmp = self.0.iter().map(encode_scalar).collect::<Vec<[u8; 32]>>();
         res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
         for elem in tmp.iter_mut() {
             res.extend_from_slice(elem);
@@ -649,21 +1201,34 @@ impl Coefficients {
     /// Deserialise this slice of bytes to a `Coefficients`
     pub fn from_bytes(bytes: &[u8]) -> Result<Coefficients, Error> {
         let len = u32::from_le_bytes(
-            bytes[0..4]
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
-        let mut points: Vec<Scalar> = Vec::with_capacity(len as usize);
+        // Never reserve more coefficients than the remaining bytes can hold.
+        let mut coefficients = Coefficients(Vec::with_capacity(core::cmp::min(
+            len as usize,
+            (bytes.len() - 4) / 32,
+        )));
         let mut index_slice = 4usize;
         let mut array = [0u8; 32];
 
         for _ in 0..len {
-            array.copy_from_slice(&bytes[index_slice..index_slice + 32]);
-            points.push(Scalar::from_canonical_bytes(array).ok_or(Error::SerialisationError)?);
+            let coefficient = bytes
+                .get(index_slice..index_slice + 32)
+                .ok_or(Error::SerialisationError)
+                .and_then(|coefficient_bytes| {
+                    array.copy_from_slice(coefficient_bytes);
+                    decode_scalar_canonical(&array)
+                });
+            array.zeroize();
+            coefficients.0.push(coefficient?);
             index_slice += 32;
         }
 
-        Ok(Coefficients(points))
+        Ok(coefficients)
     }
 }
 
@@ -674,10 +1239,23 @@ pub struct VerifiableSecretSharingCommitment {
     /// The index of this participant.
     pub index: u32,
     /// The commitments to the participant's secret coefficients.
-    pub points: Vec<RistrettoPoint>,
+    points: Vec<RistrettoPoint>,
 }
 
 impl VerifiableSecretSharingCommitment {
+    /// Check that these are `expected` commitments, i.e. to a polynomial of
+    /// degree `expected - 1`.
+    pub(crate) fn check_count(&self, expected: usize) -> Result<(), Error> {
+        match self.points.len() == expected {
+            true => Ok(()),
+            false => Err(Error::WrongCommitmentCount {
+                participant: self.index,
+                expected: TryInto::<u32>::try_into(expected).unwrap(),
+                got: self.points.len(),
+            }),
+        }
+    }
+
     /// Retrieve \\( \alpha_{i0} * B \\), where \\( B \\) is the Ristretto basepoint.
     pub fn public_key(&self) -> Option<&RistrettoPoint> {
         if !self.points.is_empty() {
@@ -686,6 +1264,38 @@ impl VerifiableSecretSharingCommitment {
         None
     }
 
+    /// The commitments to the participant's secret coefficients.
+    pub fn points(&self) -> &[RistrettoPoint] {
+        &self.points
+    }
+
+    /// Retrieve the commitment at `position`, i.e. \\( \alpha_{i,position} * B \\),
+    /// if there is one.
+    pub fn point(&self, position: usize) -> Option<&RistrettoPoint> {
+        self.points.get(position)
+    }
+
+    /// Replace the commitment at `position` with `point`.
+    ///
+    /// # Returns
+    ///
+    /// A `CommitmentIndexOutOfRange` error if there is no commitment at
+    /// `position`, in which case the commitments are left untouched.
+    pub fn set_point(&mut self, position: usize, point: RistrettoPoint) -> Result<(), Error> {
+        let len = self.points.len();
+        let commitment = self
+            .points
+            .get_mut(position)
+            .ok_or(Error::CommitmentIndexOutOfRange {
+                participant: self.index,
+                position,
+                len,
+            })?;
+        *commitment = point;
+
+        Ok(())
+    }
+
     /// Evaluate g^P(i) without knowing the secret coefficients of the polynomial
     pub fn evaluate_hiding(&self, term: &Scalar) -> RistrettoPoint {
         let mut sum = RistrettoPoint::identity();
@@ -710,7 +1320,7 @@ impl VerifiableSecretSharingCommitment {
         let mut tmp = self
             .points
             .iter()
-            .map(|e| e.compress().to_bytes())
+            .map(encode_point)
             .collect::<Vec<[u8; 32]>>();
         res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
         for elem in tmp.iter_mut() {
@@ -723,27 +1333,30 @@ impl VerifiableSecretSharingCommitment {
     /// Deserialise this slice of bytes to a `VerifiableSecretSharingCommitment`
     pub fn from_bytes(bytes: &[u8]) -> Result<VerifiableSecretSharingCommitment, Error> {
         let index = u32::from_le_bytes(
-            bytes[0..4]
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
         let len = u32::from_le_bytes(
-            bytes[4..8]
+            bytes
+                .get(4..8)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
+        // Check the length before allocating anything.
+        let points_bytes = bytes
+            .get(8..)
+            .and_then(|b| b.get(..(len as usize).checked_mul(32)?))
+            .ok_or(Error::SerialisationError)?;
         let mut points: Vec<RistrettoPoint> = Vec::with_capacity(len as usize);
-        let mut index_slice = 8usize;
         let mut array = [0u8; 32];
 
-        for _ in 0..len {
-            array.copy_from_slice(&bytes[index_slice..index_slice + 32]);
-            points.push(
-                CompressedRistretto(array)
-                    .decompress()
-                    .ok_or(Error::SerialisationError)?,
-            );
-            index_slice += 32;
+        for chunk in points_bytes.chunks_exact(32) {
+            array.copy_from_slice(chunk);
+            points.push(decode_point_checked(&array)?);
         }
 
         Ok(VerifiableSecretSharingCommitment { index, points })
@@ -751,19 +1364,25 @@ impl VerifiableSecretSharingCommitment {
 }
 
 /// A Diffie-Hellman private key wrapper type around a Scalar
-#[derive(Clone, Debug, Eq, PartialEq, Zeroize)]
+#[derive(Debug, Eq, PartialEq, Zeroize)]
 #[zeroize(drop)]
 pub struct DHPrivateKey(pub(crate) Scalar);
 
 impl DHPrivateKey {
+    /// Create another copy of this Diffie-Hellman private key, which is
+    /// zeroed out when dropped independently of this one.
+    pub fn duplicate(&self) -> DHPrivateKey {
+        DHPrivateKey(self.0)
+    }
+
     /// Serialise this Diffie-Hellman private key as an array of bytes
     pub fn to_bytes(&self) -> [u8; 32] {
-        self.0.to_bytes()
+        encode_scalar(&self.0)
     }
 
     /// Deserialise this slice of bytes to a `DHPrivateKey`
     pub fn from_bytes(bytes: &[u8; 32]) -> Result<DHPrivateKey, Error> {
-        let scalar = Scalar::from_canonical_bytes(*bytes).ok_or(Error::SerialisationError)?;
+        let scalar = decode_scalar_canonical(bytes)?;
 
         Ok(DHPrivateKey(scalar))
     }
@@ -784,19 +1403,23 @@ pub struct DHPublicKey(pub(crate) RistrettoPoint);
 impl DHPublicKey {
     /// Serialise this Diffie-Hellman public key as an array of bytes
     pub fn to_bytes(&self) -> [u8; 32] {
-        self.0.compress().to_bytes()
+        encode_point(&self.0)
     }
 
     /// Deserialise this slice of bytes to a `DHPublicKey`
     pub fn from_bytes(bytes: &[u8; 32]) -> Result<DHPublicKey, Error> {
-        let key = CompressedRistretto(*bytes)
-            .decompress()
-            .ok_or(Error::SerialisationError)?;
+        let key = decode_point_checked(bytes)?;
 
         Ok(DHPublicKey(key))
     }
 }
 
+impl From<&DHPrivateKey> for DHPublicKey {
+    fn from(source: &DHPrivateKey) -> DHPublicKey {
+        DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &source.0)
+    }
+}
+
 impl Deref for DHPublicKey {
     type Target = RistrettoPoint;
 
@@ -805,7 +1428,138 @@ impl Deref for DHPublicKey {
     }
 }
 
-/// A participant in a threshold signing.
+/// A long-term Diffie-Hellman identity key, reused by a participant across
+/// the key generations of several groups.
+///
+/// The identity public key stands in for a per-group [`DHPublicKey`], both in
+/// the [`Participant`] and in roster entries, while the keys encrypting the
+/// secret shares of each group are derived from the Diffie-Hellman output and
+/// the context string of the group, see
+/// [`DistributedKeyGeneration::new_initial_with_identity_key`].  Context
+/// strings must hence be unique per group.
+///
+/// A [`Complaint`] reveals the Diffie-Hellman output of its maker and of the
+/// accused dealer, from which the share keys of every group they share can be
+/// derived: both should rotate their identity keys after one is raised.
+#[derive(Debug, Eq, PartialEq, Zeroize)]
+#[zeroize(drop)]
+pub struct IdentityKey(pub(crate) DHPrivateKey);
+
+impl IdentityKey {
+    /// Generate a new identity key.
+    pub fn new(mut rng: impl RngCore + CryptoRng) -> IdentityKey {
+        IdentityKey(DHPrivateKey(Scalar::random(&mut rng)))
+    }
+
+    /// The public half of this identity key, to be referenced by roster
+    /// entries in every group.
+    pub fn public_key(&self) -> DHPublicKey {
+        DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &self.0 .0)
+    }
+
+    /// Create another copy of this identity key, which is zeroed out when
+    /// dropped independently of this one.
+    pub fn duplicate(&self) -> IdentityKey {
+        IdentityKey(self.0.duplicate())
+    }
+
+    /// Serialise this identity key as an array of bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Deserialise this slice of bytes to an `IdentityKey`
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<IdentityKey, Error> {
+        Ok(IdentityKey(DHPrivateKey::from_bytes(bytes)?))
+    }
+}
+
+/// The format of an [`EncryptedSecretShare`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ShareVersion {
+    /// The legacy format: the share is encrypted with AES-256 in CTR mode,
+    /// and is not authenticated.
+    ///
+    /// This version is not available with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
+    V0,
+    /// The share is encrypted with AES-256 in CTR mode, then authenticated
+    /// along with the indices of the dealer and recipient with HMAC-SHA256.
+    V1,
+}
+
+impl ShareVersion {
+    /// All the versions supported by this implementation, newest first.
+    #[cfg(not(feature = "strict"))]
+    pub const SUPPORTED: [ShareVersion; 2] = [ShareVersion::V1, ShareVersion::V0];
+    /// All the versions supported by this implementation, newest first.
+    #[cfg(feature = "strict")]
+    pub const SUPPORTED: [ShareVersion; 1] = [ShareVersion::V1];
+
+    /// The version of the participants which do not advertise any, i.e.
+    /// [`ShareVersion::V0`], or [`ShareVersion::V1`] with the `strict`
+    /// feature.
+    #[cfg(not(feature = "strict"))]
+    pub const DEFAULT: ShareVersion = ShareVersion::V0;
+    /// The version of the participants which do not advertise any, i.e.
+    /// [`ShareVersion::V0`], or [`ShareVersion::V1`] with the `strict`
+    /// feature.
+    #[cfg(feature = "strict")]
+    pub const DEFAULT: ShareVersion = ShareVersion::V1;
+
+    /// Serialise this version to a byte.
+    pub fn to_byte(&self) -> u8 {
+        match self {
+            #[cfg(not(feature = "strict"))]
+            ShareVersion::V0 => 0,
+            ShareVersion::V1 => 1,
+        }
+    }
+
+    /// Deserialise a byte to a `ShareVersion`.
+    pub fn from_byte(byte: u8) -> Result<ShareVersion, Error> {
+        match byte {
+            #[cfg(not(feature = "strict"))]
+            0 => Ok(ShareVersion::V0),
+            1 => Ok(ShareVersion::V1),
+            _ => Err(Error::SerialisationError),
+        }
+    }
+
+    /// Negotiate the version of the shares sent by a dealer supporting
+    /// `dealer_versions` to a recipient supporting `recipient_versions`,
+    /// i.e. the newest version they both support.
+    pub fn negotiate(
+        dealer_versions: &[ShareVersion],
+        recipient_versions: &[ShareVersion],
+    ) -> Option<ShareVersion> {
+        dealer_versions
+            .iter()
+            .filter(|v| recipient_versions.contains(v))
+            .max()
+            .copied()
+    }
+}
+
+/// A dealer's secret polynomial coefficients along with their commitments,
+/// generated ahead of a distributed key generation run with
+/// [`Participant::pregenerate_dealer_material`].
+///
+/// This type is deliberately not `Clone`, so that the same material cannot be
+/// consumed by more than one ceremony.  The coefficients are zeroed out when
+/// it falls out of scope.
+pub struct DealerMaterial {
+    /// The dealer's secret polynomial coefficients.
+    coefficients: Coefficients,
+    /// The commitments to the dealer's secret polynomial coefficients.
+    points: Vec<RistrettoPoint>,
+}
+
+/// A participant in a threshold signing, acting as a dealer.
+///
+/// A dealer always carries the commitments to its secret polynomial and the
+/// proof of knowledge of its constant term.  Participants which only receive
+/// shares, as in the resharing flow, are [`DkgSigner`]s instead.
 #[derive(Clone, Debug)]
 pub struct Participant {
     /// The index of this participant, to keep the participants in order.
@@ -815,20 +1569,63 @@ pub struct Participant {
     pub dh_public_key: DHPublicKey,
     /// A vector of Pedersen commitments to the coefficients of this
     /// participant's private polynomial.
-    pub commitments: Option<VerifiableSecretSharingCommitment>,
+    pub commitments: VerifiableSecretSharingCommitment,
     /// The zero-knowledge proof of knowledge of the secret key (a.k.a. the
     /// first coefficient in the private polynomial).  It is constructed as a
     /// Schnorr signature using \\( a_{i0} \\) as the signing key.
-    pub proof_of_secret_key: Option<NizkOfSecretKey>,
+    pub proof_of_secret_key: NizkOfSecretKey,
     /// The zero-knowledge proof of knowledge of the DH private key.
     /// It is computed similarly to the proof_of_secret_key.
     pub proof_of_dh_private_key: NizkOfSecretKey,
+    /// The versions of the [`EncryptedSecretShare`] format which this
+    /// participant accepts as a recipient and produces as a dealer.
+    ///
+    /// This defaults to all the [`ShareVersion::SUPPORTED`] versions, while
+    /// participants decoded from packages which predate share format versions
+    /// only have [`ShareVersion::DEFAULT`].  Dealers encrypt each share with
+    /// the newest version they have in common with its recipient, see
+    /// [`ShareVersion::negotiate`], and recipients only accept this version.
+    pub share_versions: Vec<ShareVersion>,
+    /// The weight of this participant, i.e. the number of shares it stands
+    /// for.  This defaults to 1.
+    pub weight: u32,
+    /// An optional identifier of the device holding this participant's
+    /// secrets, of at most [`MAX_DEVICE_ID_LENGTH`] bytes.
+    pub device_id: Option<Vec<u8>>,
 }
 
+/// The maximum length in bytes of a [`Participant::device_id`].
+pub const MAX_DEVICE_ID_LENGTH: usize = 255;
+
 impl Participant {
+    /// Start building a new dealer for the distributed key generation
+    /// protocol, with this participant's `index` and a `context_string` to
+    /// prevent replay attacks.
+    ///
+    /// See [`DealerBuilder`] for the optional capabilities which can be
+    /// attached to the dealer before calling [`DealerBuilder::build`].
+    pub fn dealer<'a>(
+        parameters: &'a Parameters,
+        index: u32,
+        context_string: &'a str,
+    ) -> DealerBuilder<'a, OsRng> {
+        DealerBuilder {
+            parameters,
+            index,
+            context_string,
+            rng: OsRng,
+            weight: None,
+            device_id: None,
+            material: None,
+        }
+    }
+
     /// Construct a new dealer for the distributed key generation protocol,
     /// who will generate shares for a group of signers (can be the group of dealers).
     ///
+    /// This is a shorthand for building a dealer with [`Participant::dealer`]
+    /// without any optional capability.
+    ///
     /// In case of resharing/refreshing of the secret participant shares once the
     /// DKG has completed, a dealer can call the `reshare` method to distribute
     /// shares of her secret key to a new set of participants.
@@ -858,140 +1655,217 @@ impl Participant {
         context_string: &str,
         mut rng: impl RngCore + CryptoRng,
     ) -> (Self, Coefficients, DHPrivateKey) {
-        let (dealer, coeff_option, dh_private_key) =
-            Self::new_internal(parameters, false, index, None, context_string, &mut rng);
-        (dealer, coeff_option.unwrap(), dh_private_key)
+        Self::dealer(parameters, index, context_string)
+            .with_rng(&mut rng)
+            .build()
+            .expect("a dealer without optional capabilities is always valid")
     }
 
-    /// Construct a new signer for the distributed key generation protocol.
-    ///
-    /// A signer only combines shares from a previous set of dealers and
-    /// computes a private signing key from it.
-    ///
-    /// # Inputs
-    ///
-    /// * The protocol instance [`Parameters`],
-    /// * This participant's `index`,
-    /// * A context string to prevent replay attacks.
-    ///
-    /// # Usage
-    ///
-    /// After a new participant is constructed, the `participant.index`
-    /// and `participant.proof_of_dh_private_key` should be sent to every
-    /// other participant in the protocol.
+    /// Construct a new dealer for the distributed key generation protocol, as
+    /// with [`Participant::new_dealer`], reusing an existing long-term
+    /// Diffie-Hellman private key, e.g. the one of this participant in
+    /// another group.
     ///
     /// # Returns
     ///
-    /// A distributed key generation protocol [`Participant`] along the
-    /// signers's Diffie-Hellman private key for secret shares encryption
-    /// which must be kept private,
-    pub fn new_signer(
+    /// A distributed key generation protocol [`Participant`] and that
+    /// dealer's secret polynomial `Coefficients`, which must be kept private.
+    pub fn new_dealer_with_dh_private_key(
         parameters: &Parameters,
         index: u32,
+        dh_private_key: &DHPrivateKey,
         context_string: &str,
         mut rng: impl RngCore + CryptoRng,
-    ) -> (Self, DHPrivateKey) {
-        let (signer, _coeff_option, dh_private_key) =
-            Self::new_internal(parameters, true, index, None, context_string, &mut rng);
-        (signer, dh_private_key)
+    ) -> (Self, Coefficients) {
+        let (dealer, coefficients, _dh_private_key) = Self::new_internal(
+            parameters,
+            index,
+            None,
+            Some(dh_private_key),
+            context_string,
+            &mut rng,
+        );
+        (dealer, coefficients)
     }
 
-    fn new_internal(
+    /// Construct a new dealer for the distributed key generation protocol of
+    /// one of several groups, as with [`Participant::new_dealer`], with a
+    /// long-term `identity_key` in place of a per-group Diffie-Hellman key.
+    ///
+    /// # Returns
+    ///
+    /// A distributed key generation protocol [`Participant`] and that
+    /// dealer's secret polynomial `Coefficients`, which must be kept private.
+    pub fn new_dealer_with_identity_key(
         parameters: &Parameters,
-        is_signer: bool,
         index: u32,
-        secret_key: Option<Scalar>,
+        identity_key: &IdentityKey,
         context_string: &str,
+        rng: impl RngCore + CryptoRng,
+    ) -> (Self, Coefficients) {
+        Self::new_dealer_with_dh_private_key(
+            parameters,
+            index,
+            &identity_key.0,
+            context_string,
+            rng,
+        )
+    }
+
+    /// Pre-generate `count` sets of secret polynomial coefficients and their
+    /// commitments, to be consumed by [`Participant::new_dealer_from_material`].
+    ///
+    /// Committing to the polynomial coefficients dominates the cost of
+    /// constructing a dealer, and does not depend on the ceremony it is used
+    /// in, hence it can be performed offline.
+    pub fn pregenerate_dealer_material(
+        parameters: &Parameters,
+        count: usize,
         mut rng: impl RngCore + CryptoRng,
-    ) -> (Self, Option<Coefficients>, DHPrivateKey) {
-        // Step 1: Every participant P_i samples t random values (a_{i0}, ..., a_{i(t-1)})
-        //         uniformly in ZZ_q, and uses these values as coefficients to define a
-        //         polynomial f_i(x) = \sum_{j=0}^{t-1} a_{ij} x^{j} of degree t-1 over
-        //         ZZ_q.
+    ) -> Vec<DealerMaterial> {
         let t: usize = parameters.t as usize;
 
-        // RICE-FROST: Every participant samples a random pair of keys (dh_private_key, dh_public_key)
-        // and generates a proof of knowledge of dh_private_key. This will be used for secret shares
-        // encryption and for complaint generation.
+        (0..count)
+            .map(|_| {
+                let coefficients = Coefficients((0..t).map(|_| Scalar::random(&mut rng)).collect());
+                let points = coefficients
+                    .0
+                    .iter()
+                    .map(|a| a * &RISTRETTO_BASEPOINT_TABLE)
+                    .collect();
+
+                DealerMaterial {
+                    coefficients,
+                    points,
+                }
+            })
+            .collect()
+    }
+
+    /// Construct a new dealer for the distributed key generation protocol
+    /// from some `material` obtained through
+    /// [`Participant::pregenerate_dealer_material`].
+    ///
+    /// The proof of knowledge of the secret key is computed here, so that it
+    /// is bound to this participant's `index` and this ceremony's
+    /// `context_string`.
+    ///
+    /// # Returns
+    ///
+    /// The same as [`Participant::new_dealer`], or an error if the `material`
+    /// was generated for a different threshold.
+    pub fn new_dealer_from_material(
+        parameters: &Parameters,
+        index: u32,
+        context_string: &str,
+        material: DealerMaterial,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, Coefficients, DHPrivateKey), Error> {
+        if material.points.len() != parameters.t as usize {
+            return Err(Error::InvalidDealerMaterial);
+        }
 
-        let dh_private_key = DHPrivateKey(Scalar::random(&mut rng));
-        let dh_public_key = DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &dh_private_key);
+        let (dh_private_key, dh_public_key, proof_of_dh_private_key) =
+            new_dh_key_pair(index, None, context_string, &mut rng);
 
-        // Compute a proof of knowledge of dh_secret_key
-        let proof_of_dh_private_key: NizkOfSecretKey = NizkOfSecretKey::prove(
+        let DealerMaterial {
+            coefficients,
+            points,
+        } = material;
+        let commitments = VerifiableSecretSharingCommitment { index, points };
+        let proof_of_secret_key: NizkOfSecretKey = NizkOfSecretKey::prove(
             &index,
-            &dh_private_key,
-            &dh_public_key,
+            &coefficients.0[0],
+            commitments.public_key().unwrap(),
             context_string,
-            &mut rng,
+            rng,
         );
 
-        if is_signer {
-            // Signers don't need coefficients, commitments or proofs of secret key.
-            (
-                Participant {
-                    index,
-                    dh_public_key,
-                    commitments: None,
-                    proof_of_secret_key: None,
-                    proof_of_dh_private_key,
-                },
-                None,
-                dh_private_key,
-            )
-        } else {
-            let mut coefficients: Vec<Scalar> = Vec::with_capacity(t);
-            let mut commitments = VerifiableSecretSharingCommitment {
-                index,
-                points: Vec::with_capacity(t),
-            };
+        let dealer = Participant {
+            index,
+            dh_public_key,
+            commitments,
+            proof_of_secret_key,
+            proof_of_dh_private_key,
+            share_versions: ShareVersion::SUPPORTED.to_vec(),
+            weight: 1,
+            device_id: None,
+        };
 
-            match secret_key {
-                Some(sk) => coefficients.push(sk),
-                None => coefficients.push(Scalar::random(&mut rng)),
-            }
+        Ok((dealer, coefficients, dh_private_key))
+    }
 
-            for _ in 1..t {
-                coefficients.push(Scalar::random(&mut rng));
-            }
+    fn new_internal(
+        parameters: &Parameters,
+        index: u32,
+        secret_key: Option<Scalar>,
+        dh_private_key: Option<&DHPrivateKey>,
+        context_string: &str,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> (Self, Coefficients, DHPrivateKey) {
+        // Step 1: Every participant P_i samples t random values (a_{i0}, ..., a_{i(t-1)})
+        //         uniformly in ZZ_q, and uses these values as coefficients to define a
+        //         polynomial f_i(x) = \sum_{j=0}^{t-1} a_{ij} x^{j} of degree t-1 over
+        //         ZZ_q.
+        let t: usize = parameters.t as usize;
 
-            let coefficients = Coefficients(coefficients);
+        let (dh_private_key, dh_public_key, proof_of_dh_private_key) =
+            new_dh_key_pair(index, dh_private_key, context_string, &mut rng);
 
-            // Step 3: Every dealer computes a public commitment
-            //         C_i = [\phi_{i0}, ..., \phi_{i(t-1)}], where \phi_{ij} = g^{a_{ij}},
-            //         0 ≤ j ≤ t-1.
-            for j in 0..t {
-                commitments
-                    .points
-                    .push(&coefficients.0[j] * &RISTRETTO_BASEPOINT_TABLE);
-            }
+        let mut coefficients: Vec<Scalar> = Vec::with_capacity(t);
+        let mut commitments = VerifiableSecretSharingCommitment {
+            index,
+            points: Vec::with_capacity(t),
+        };
 
-            // The steps are out of order, in order to save one scalar multiplication.
-
-            // Step 2: Every dealer computes a proof of knowledge to the corresponding secret
-            //         a_{i0} by calculating a Schnorr signature \alpha_i = (s, R).  (In
-            //         the FROST paper: \alpha_i = (\mu_i, c_i), but we stick with Schnorr's
-            //         original notation here.)
-            let proof_of_secret_key: NizkOfSecretKey = NizkOfSecretKey::prove(
-                &index,
-                &coefficients.0[0],
-                commitments.public_key().unwrap(),
-                context_string,
-                rng,
-            );
+        match secret_key {
+            Some(sk) => coefficients.push(sk),
+            None => coefficients.push(Scalar::random(&mut rng)),
+        }
 
-            (
-                Participant {
-                    index,
-                    dh_public_key,
-                    commitments: Some(commitments),
-                    proof_of_secret_key: Some(proof_of_secret_key),
-                    proof_of_dh_private_key,
-                },
-                Some(coefficients),
-                dh_private_key,
-            )
+        for _ in 1..t {
+            coefficients.push(Scalar::random(&mut rng));
+        }
+
+        let coefficients = Coefficients(coefficients);
+
+        // Step 3: Every dealer computes a public commitment
+        //         C_i = [\phi_{i0}, ..., \phi_{i(t-1)}], where \phi_{ij} = g^{a_{ij}},
+        //         0 ≤ j ≤ t-1.
+        for j in 0..t {
+            commitments
+                .points
+                .push(&coefficients.0[j] * &RISTRETTO_BASEPOINT_TABLE);
         }
+
+        // The steps are out of order, in order to save one scalar multiplication.
+
+        // Step 2: Every dealer computes a proof of knowledge to the corresponding secret
+        //         a_{i0} by calculating a Schnorr signature \alpha_i = (s, R).  (In
+        //         the FROST paper: \alpha_i = (\mu_i, c_i), but we stick with Schnorr's
+        //         original notation here.)
+        let proof_of_secret_key: NizkOfSecretKey = NizkOfSecretKey::prove(
+            &index,
+            &coefficients.0[0],
+            commitments.public_key().unwrap(),
+            context_string,
+            rng,
+        );
+
+        (
+            Participant {
+                index,
+                dh_public_key,
+                commitments,
+                proof_of_secret_key,
+                proof_of_dh_private_key,
+                share_versions: ShareVersion::SUPPORTED.to_vec(),
+                weight: 1,
+                device_id: None,
+            },
+            coefficients,
+            dh_private_key,
+        )
     }
 
     /// Reshare this dealer's secret key to a new set of participants.
@@ -1020,31 +1894,37 @@ impl Participant {
     pub fn reshare(
         parameters: &Parameters,
         secret_key: SecretKey,
-        signers: &[Participant],
+        signers: &[DkgSigner],
         context_string: &str,
         mut rng: impl RngCore + CryptoRng,
-    ) -> Result<(Self, Vec<EncryptedSecretShare>, DKGParticipantList), Error> {
-        let (dealer, coeff_option, dh_private_key) = Self::new_internal(
+    ) -> Result<
+        (
+            Self,
+            Vec<EncryptedSecretShare>,
+            DKGParticipantList<DkgSigner>,
+        ),
+        Error,
+    > {
+        let (dealer, coefficients, dh_private_key) = Self::new_internal(
             parameters,
-            false,
             secret_key.index,
             Some(secret_key.key),
+            None,
             context_string,
             &mut rng,
         );
 
-        // Unwrapping cannot panic here
-        let coefficients = coeff_option.unwrap();
-
         let (participant_state, participant_lists) = DistributedKeyGeneration::new_state_internal(
             parameters,
-            &dh_private_key,
+            Some(&dh_private_key),
+            DHPublicKey::from(&dh_private_key),
             &secret_key.index,
             Some(&coefficients),
             signers,
             context_string,
             true,
             false,
+            None,
             &mut rng,
         )?;
 
@@ -1061,11 +1941,7 @@ impl Participant {
     ///
     /// This is used to pass into the final call to `DistributedKeyGeneration::<RoundTwo>.finish()`.
     pub fn public_key(&self) -> Option<&RistrettoPoint> {
-        if self.commitments.is_some() {
-            return self.commitments.as_ref().unwrap().public_key();
-        }
-
-        None
+        self.commitments.public_key()
     }
 
     /// Serialise this participant to a Vec of bytes
@@ -1074,76 +1950,91 @@ impl Participant {
         res.extend_from_slice(&self.index.to_le_bytes());
         res.extend_from_slice(&self.dh_public_key.to_bytes());
 
-        match &self.commitments {
-            Some(v) => {
-                res.push(1u8);
-                res.extend_from_slice(&v.to_bytes());
-            }
-            None => res.push(0u8),
-        }
+        // The flags preceding the commitments and the proof are always set,
+        // and only kept for compatibility with the encoding of signers.
+        res.push(1u8);
+        res.extend_from_slice(&self.commitments.to_bytes());
+        res.push(1u8);
+        res.extend_from_slice(&self.proof_of_secret_key.to_bytes());
 
-        match &self.proof_of_secret_key {
-            Some(p) => {
-                res.push(1u8);
-                res.extend_from_slice(&p.to_bytes());
+        res.extend_from_slice(&self.proof_of_dh_private_key.to_bytes());
+        share_versions_to_bytes(&self.share_versions, &mut res);
+
+        // The weight and the device identifier are only appended when they
+        // differ from their defaults, so that the encoding of other dealers
+        // is unchanged.
+        if self.weight != 1 || self.device_id.is_some() {
+            res.extend_from_slice(&self.weight.to_le_bytes());
+            match &self.device_id {
+                Some(device_id) => {
+                    res.push(TryInto::<u8>::try_into(device_id.len()).unwrap());
+                    res.extend_from_slice(device_id);
+                }
+                None => res.push(0u8),
             }
-            None => res.push(0u8),
         }
 
-        res.extend_from_slice(&self.proof_of_dh_private_key.to_bytes());
-
         res
     }
 
     /// Deserialise this slice of bytes to a `Participant`
+    ///
+    /// Participants serialised without share versions are deserialised as
+    /// supporting [`ShareVersion::V0`] only, and rejected with the `strict`
+    /// feature.  Participants serialised without a weight have a weight of 1
+    /// and no device identifier.
+    ///
+    /// # Returns
+    ///
+    /// A `SerialisationError` if the bytes are truncated, or do not carry
+    /// non-empty commitments and a proof of knowledge of the secret key, as
+    /// serialised [`DkgSigner`]s do.
     pub fn from_bytes(bytes: &[u8]) -> Result<Participant, Error> {
-        let index = u32::from_le_bytes(
-            bytes[0..4]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        );
-
-        let mut array = [0u8; 32];
-        array.copy_from_slice(&bytes[4..36]);
-
-        let dh_public_key = DHPublicKey::from_bytes(&array)?;
+        let (index, dh_public_key) = participant_header_from_bytes(bytes)?;
 
         let mut index_slice = 36;
-        let commitments = match bytes[index_slice] {
-            1u8 => {
-                index_slice += 1;
-                let com = VerifiableSecretSharingCommitment::from_bytes(&bytes[index_slice..])?;
-                index_slice += 4 + 4 + com.points.len() * 32;
-                Some(com)
-            }
-            0u8 => {
-                index_slice += 1;
-                None
-            }
-            _ => return Err(Error::SerialisationError),
-        };
+        if bytes.get(index_slice) != Some(&1u8) {
+            return Err(Error::SerialisationError);
+        }
+        index_slice += 1;
+        let commitments = VerifiableSecretSharingCommitment::from_bytes(&bytes[index_slice..])?;
+        if commitments.points.is_empty() {
+            return Err(Error::SerialisationError);
+        }
+        index_slice += 4 + 4 + commitments.points.len() * 32;
 
-        let proof_of_secret_key = match bytes[index_slice] {
-            1u8 => {
-                index_slice += 1;
-                Some(NizkOfSecretKey::from_bytes(
-                    &bytes[index_slice..index_slice + 64]
-                        .try_into()
-                        .map_err(|_| Error::SerialisationError)?,
-                )?)
-            }
-            0u8 => {
-                index_slice += 1;
-                None
+        if bytes.get(index_slice) != Some(&1u8) {
+            return Err(Error::SerialisationError);
+        }
+        index_slice += 1;
+        let proof_of_secret_key = proof_from_bytes(bytes, index_slice)?;
+        index_slice += 64;
+
+        let proof_of_dh_private_key = proof_from_bytes(bytes, index_slice)?;
+        index_slice += 64;
+
+        let share_versions = share_versions_from_bytes(bytes, index_slice)?;
+        index_slice += 1 + share_versions.len();
+
+        let mut weight = 1;
+        let mut device_id = None;
+        if let Some(weight_bytes) = bytes.get(index_slice..index_slice + 4) {
+            weight = u32::from_le_bytes(weight_bytes.try_into().unwrap());
+            index_slice += 4;
+            let len = *bytes.get(index_slice).ok_or(Error::SerialisationError)? as usize;
+            index_slice += 1;
+            if len > 0 {
+                device_id = Some(
+                    bytes
+                        .get(index_slice..index_slice + len)
+                        .ok_or(Error::SerialisationError)?
+                        .to_vec(),
+                );
             }
-            _ => return Err(Error::SerialisationError),
-        };
-
-        let proof_of_dh_private_key = NizkOfSecretKey::from_bytes(
-            &bytes[index_slice..index_slice + 64]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        )?;
+        }
+        if weight == 0 {
+            return Err(Error::SerialisationError);
+        }
 
         Ok(Participant {
             index,
@@ -1151,8 +2042,29 @@ impl Participant {
             commitments,
             proof_of_secret_key,
             proof_of_dh_private_key,
+            share_versions,
+            weight,
+            device_id,
         })
     }
+
+    /// Deserialise this slice of bytes to a `Participant`, as with
+    /// [`Participant::from_bytes`], for a key generation with these
+    /// `parameters`.
+    ///
+    /// # Returns
+    ///
+    /// A `WrongCommitmentCount` error if the participant does not publish
+    /// exactly \\(t\\) commitments.
+    pub fn from_bytes_with_parameters(
+        bytes: &[u8],
+        parameters: &Parameters,
+    ) -> Result<Participant, Error> {
+        let participant = Participant::from_bytes(bytes)?;
+        participant.commitments.check_count(parameters.t as usize)?;
+
+        Ok(participant)
+    }
 }
 
 impl PartialOrd for Participant {
@@ -1171,1381 +2083,4496 @@ impl PartialEq for Participant {
     }
 }
 
-/// Module to implement trait sealing so that `DkgState` cannot be
-/// implemented for externally declared types.
-mod private {
-    pub trait Sealed {}
-
-    impl Sealed for super::RoundOne {}
-    impl Sealed for super::RoundTwo {}
+/// A builder for a dealer of the distributed key generation protocol,
+/// obtained with [`Participant::dealer`].
+///
+/// The options are only checked against each other and against the
+/// instance [`Parameters`] by [`DealerBuilder::build`].
+pub struct DealerBuilder<'a, R: RngCore + CryptoRng> {
+    parameters: &'a Parameters,
+    index: u32,
+    context_string: &'a str,
+    rng: R,
+    weight: Option<u32>,
+    device_id: Option<Vec<u8>>,
+    material: Option<DealerMaterial>,
 }
 
-/// State machine structures for holding intermediate values during a
-/// distributed key generation protocol run, to prevent misuse.
-#[allow(dead_code)]
-#[derive(Clone, Debug)]
-pub struct DistributedKeyGeneration<S: DkgState> {
-    state: Box<ActualState>,
-    data: S,
-}
+impl<'a, R: RngCore + CryptoRng> DealerBuilder<'a, R> {
+    /// Use this random number generator instead of the operating system's.
+    pub fn with_rng<S: RngCore + CryptoRng>(self, rng: S) -> DealerBuilder<'a, S> {
+        DealerBuilder {
+            parameters: self.parameters,
+            index: self.index,
+            context_string: self.context_string,
+            rng,
+            weight: self.weight,
+            device_id: self.device_id,
+            material: self.material,
+        }
+    }
 
-/// Shared state which occurs across all rounds of a threshold signing protocol run.
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct ActualState {
-    /// The parameters for this instantiation of a threshold signature.
-    parameters: Parameters,
-    /// The index of the participant.
-    index: u32,
-    /// The DH private key for deriving a symmetric key to encrypt and decrypt
-    /// secret shares.
-    dh_private_key: DHPrivateKey,
-    /// The DH public key for deriving a symmetric key to encrypt and decrypt
-    /// secret shares.
-    dh_public_key: DHPublicKey,
-    /// A vector of tuples containing the index of each participant and that
-    /// respective participant's commitments to their private polynomial
-    /// coefficients.
-    their_commitments: Option<Vec<VerifiableSecretSharingCommitment>>,
-    /// A vector of ECPoints containing the index of each participant and that
-    /// respective participant's DH public key.
-    their_dh_public_keys: Vec<(u32, DHPublicKey)>,
-    /// The encrypted secret shares this participant has calculated for all the other participants.
-    their_encrypted_secret_shares: Option<Vec<EncryptedSecretShare>>,
-    /// The secret shares this participant has received from all the other participants.
-    my_secret_shares: Option<Vec<SecretShare>>,
-}
+    /// Give the dealer this `weight`, which must be non-zero and cannot
+    /// exceed the number of participants.
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
 
-impl ActualState {
-    /// Serialise this state to a Vec of bytes
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut res: Vec<u8> = Vec::new();
-        res.extend_from_slice(&self.parameters.to_bytes());
-        res.extend_from_slice(&self.index.to_le_bytes());
-        res.extend_from_slice(&self.dh_private_key.to_bytes());
-        res.extend_from_slice(&self.dh_public_key.to_bytes());
+    /// Attach this identifier of the device holding the dealer's secrets,
+    /// which must be non-empty and at most [`MAX_DEVICE_ID_LENGTH`] bytes.
+    pub fn with_device_id(mut self, device_id: &[u8]) -> Self {
+        self.device_id = Some(device_id.to_vec());
+        self
+    }
 
-        match &self.their_commitments {
-            Some(v) => {
-                res.push(1u8);
-                let mut tmp = v.iter().map(|e| e.to_bytes()).collect::<Vec<Vec<u8>>>();
-                res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
-                for commitment in tmp.iter_mut() {
-                    res.extend_from_slice(commitment);
-                }
-            }
-            None => res.push(0u8),
-        }
+    /// Use some `material` obtained through
+    /// [`Participant::pregenerate_dealer_material`] instead of sampling a
+    /// new secret polynomial.
+    pub fn from_pregenerated(mut self, material: DealerMaterial) -> Self {
+        self.material = Some(material);
+        self
+    }
 
-        let mut tmp = self
-            .their_dh_public_keys
-            .iter()
-            .map(|e| (e.0.to_le_bytes(), e.1.to_bytes()))
-            .collect::<Vec<([u8; 4], [u8; 32])>>();
-        res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
-        for (index, keys) in tmp.iter_mut() {
-            res.extend_from_slice(index);
-            res.extend_from_slice(keys);
+    /// Build the dealer.
+    ///
+    /// # Returns
+    ///
+    /// The same as [`Participant::new_dealer`], or an
+    /// `InvalidDealerOptions` error if the options are inconsistent with each
+    /// other or with the instance parameters, and an `InvalidDealerMaterial`
+    /// error if the pre-generated material was generated for a different
+    /// threshold.
+    pub fn build(self) -> Result<(Participant, Coefficients, DHPrivateKey), Error> {
+        let weight = self.weight.unwrap_or(1);
+        if weight == 0 {
+            return Err(Error::InvalidDealerOptions("the weight must be non-zero"));
         }
-
-        match &self.their_encrypted_secret_shares {
-            Some(v) => {
-                res.push(1u8);
-                let mut tmp = v.iter().map(|e| e.to_bytes()).collect::<Vec<[u8; 56]>>();
-                res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
-                for elem in tmp.iter_mut() {
-                    res.extend_from_slice(elem);
-                }
+        if weight > self.parameters.n {
+            return Err(Error::InvalidDealerOptions(
+                "the weight cannot exceed the number of participants",
+            ));
+        }
+        if let Some(device_id) = &self.device_id {
+            if device_id.is_empty() || device_id.len() > MAX_DEVICE_ID_LENGTH {
+                return Err(Error::InvalidDealerOptions(
+                    "the device identifier must be between 1 and 255 bytes long",
+                ));
             }
-            None => res.push(0u8),
-        };
+        }
+        // A weighted dealer stands for several shares of the same secret,
+        // which pre-generated material committed to a single polynomial
+        // cannot account for.
+        if weight != 1 && self.material.is_some() {
+            return Err(Error::InvalidDealerOptions(
+                "pre-generated material cannot be used by a weighted dealer",
+            ));
+        }
 
-        match &self.my_secret_shares {
-            Some(v) => {
-                res.push(1u8);
-                let mut tmp = v.iter().map(|e| e.to_bytes()).collect::<Vec<[u8; 40]>>();
-                res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
-                for elem in tmp.iter_mut() {
-                    res.extend_from_slice(elem);
-                }
-            }
-            None => res.push(0u8),
+        let (mut dealer, coefficients, dh_private_key) = match self.material {
+            Some(material) => Participant::new_dealer_from_material(
+                self.parameters,
+                self.index,
+                self.context_string,
+                material,
+                self.rng,
+            )?,
+            None => Participant::new_internal(
+                self.parameters,
+                self.index,
+                None,
+                None,
+                self.context_string,
+                self.rng,
+            ),
         };
+        dealer.weight = weight;
+        dealer.device_id = self.device_id;
 
-        res
+        Ok((dealer, coefficients, dh_private_key))
     }
+}
 
-    /// Deserialise this slice of bytes to an `ActualState`
-    pub fn from_bytes(bytes: &[u8]) -> Result<ActualState, Error> {
-        let mut array = [0u8; 8];
-        array.copy_from_slice(&bytes[..8]);
-        let parameters = Parameters::from_bytes(&array)?;
-
-        let index = u32::from_le_bytes(
-            bytes[8..12]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        );
-
-        let mut array = [0u8; 32];
-        array.copy_from_slice(&bytes[12..44]);
-        let dh_private_key = DHPrivateKey::from_bytes(&array)?;
+/// A participant in a threshold signing which only receives shares from a
+/// set of dealers, as in the resharing flow, without dealing any.
+///
+/// Unlike a [`Participant`], a signer carries no commitments, hence cannot
+/// be passed as a dealer to
+/// [`DistributedKeyGeneration::new_initial`] or
+/// [`DistributedKeyGeneration::new`].
+#[derive(Clone, Debug)]
+pub struct DkgSigner {
+    /// The index of this signer, to keep the signers in order.
+    pub index: u32,
+    /// The public key used to derive symmetric keys for encrypting and
+    /// decrypting shares via DH.
+    pub dh_public_key: DHPublicKey,
+    /// The zero-knowledge proof of knowledge of the DH private key.
+    pub proof_of_dh_private_key: NizkOfSecretKey,
+    /// The versions of the [`EncryptedSecretShare`] format which this signer
+    /// accepts as a recipient.
+    pub share_versions: Vec<ShareVersion>,
+}
 
-        array.copy_from_slice(&bytes[44..76]);
-        let dh_public_key = DHPublicKey::from_bytes(&array)?;
-
-        let mut index_slice = 76usize;
+impl DkgSigner {
+    /// Construct a new signer for the distributed key generation protocol.
+    ///
+    /// A signer only combines shares from a previous set of dealers and
+    /// computes a private signing key from it.
+    ///
+    /// # Inputs
+    ///
+    /// * The protocol instance [`Parameters`],
+    /// * This participant's `index`,
+    /// * A context string to prevent replay attacks.
+    ///
+    /// # Usage
+    ///
+    /// After a new signer is constructed, the `signer.index`, the
+    /// `signer.dh_public_key` and the `signer.proof_of_dh_private_key` should
+    /// be sent to every dealer in the protocol.
+    ///
+    /// # Returns
+    ///
+    /// A distributed key generation protocol [`DkgSigner`] along the
+    /// signers's Diffie-Hellman private key for secret shares encryption
+    /// which must be kept private,
+    pub fn new(
+        _parameters: &Parameters,
+        index: u32,
+        context_string: &str,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> (Self, DHPrivateKey) {
+        let (dh_private_key, dh_public_key, proof_of_dh_private_key) =
+            new_dh_key_pair(index, None, context_string, &mut rng);
 
-        let their_commitments = match bytes[index_slice] {
-            1u8 => {
-                index_slice += 1;
-                let commit_len = u32::from_le_bytes(
-                    bytes[index_slice..index_slice + 4]
-                        .try_into()
-                        .map_err(|_| Error::SerialisationError)?,
-                );
-                let mut coms: Vec<VerifiableSecretSharingCommitment> =
-                    Vec::with_capacity(commit_len as usize);
+        // Signers don't need coefficients, commitments or proofs of secret key.
+        (
+            DkgSigner {
+                index,
+                dh_public_key,
+                proof_of_dh_private_key,
+                share_versions: ShareVersion::SUPPORTED.to_vec(),
+            },
+            dh_private_key,
+        )
+    }
 
-                index_slice += 4;
+    /// Construct a new signer from public data only, for a signer whose DH
+    /// key pair is held externally, e.g. inside a hardware security module,
+    /// and which answered the [`DhKeyChallenge`] of this ceremony with a
+    /// [`DhKeyHandshake`].
+    ///
+    /// The share dealt to this signer is encrypted to the DH public key of
+    /// the `handshake`, and decrypted by the signer with
+    /// [`DistributedKeyGeneration::new_with_external_dh_key`].
+    ///
+    /// # Returns
+    ///
+    /// A distributed key generation protocol [`DkgSigner`], or an
+    /// `InvalidProofOfKnowledge` error if the handshake does not prove
+    /// possession of the DH private key for the `context_string`.
+    pub fn new_from_public(
+        handshake: &DhKeyHandshake,
+        context_string: &str,
+    ) -> Result<DkgSigner, Error> {
+        handshake.verify(context_string)?;
+
+        Ok(DkgSigner {
+            index: handshake.index,
+            dh_public_key: handshake.dh_public_key.clone(),
+            proof_of_dh_private_key: handshake.signature.clone(),
+            share_versions: ShareVersion::SUPPORTED.to_vec(),
+        })
+    }
 
-                for _ in 0..commit_len {
-                    let com = VerifiableSecretSharingCommitment::from_bytes(&bytes[index_slice..])?;
-                    index_slice += 4 + 4 + com.points.len() * 32;
-                    coms.push(com);
-                }
+    /// Serialise this signer to a Vec of bytes.
+    ///
+    /// This is the encoding of a [`Participant`] without commitments nor
+    /// proof of knowledge of the secret key, as signers used to be.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+        res.extend_from_slice(&self.index.to_le_bytes());
+        res.extend_from_slice(&self.dh_public_key.to_bytes());
+        res.push(0u8);
+        res.push(0u8);
+        res.extend_from_slice(&self.proof_of_dh_private_key.to_bytes());
+        share_versions_to_bytes(&self.share_versions, &mut res);
 
-                Some(coms)
-            }
-            0u8 => {
-                index_slice += 1;
-                None
-            }
-            _ => return Err(Error::SerialisationError),
-        };
+        res
+    }
 
-        let dh_key_len = u32::from_le_bytes(
-            bytes[index_slice..index_slice + 4]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        );
-        let mut their_dh_public_keys: Vec<(u32, DHPublicKey)> =
-            Vec::with_capacity(dh_key_len as usize);
+    /// Deserialise this slice of bytes to a `DkgSigner`.
+    ///
+    /// # Returns
+    ///
+    /// A `SerialisationError` if the bytes are truncated, or carry the
+    /// commitments of a dealer.
+    pub fn from_bytes(bytes: &[u8]) -> Result<DkgSigner, Error> {
+        let (index, dh_public_key) = participant_header_from_bytes(bytes)?;
 
-        index_slice += 4;
-        for _ in 0..dh_key_len {
-            let index = u32::from_le_bytes(
-                bytes[index_slice..index_slice + 4]
-                    .try_into()
-                    .map_err(|_| Error::SerialisationError)?,
-            );
-            let key = DHPublicKey::from_bytes(
-                &bytes[index_slice + 4..index_slice + 36]
-                    .try_into()
-                    .map_err(|_| Error::SerialisationError)?,
-            )?;
-            their_dh_public_keys.push((index, key));
-            index_slice += 36;
+        if bytes.get(36..38) != Some(&[0u8, 0u8][..]) {
+            return Err(Error::SerialisationError);
         }
+        let proof_of_dh_private_key = proof_from_bytes(bytes, 38)?;
+        let share_versions = share_versions_from_bytes(bytes, 102)?;
 
-        let their_encrypted_secret_shares = match bytes[index_slice] {
-            1u8 => {
-                index_slice += 1;
-                let shares_len = u32::from_le_bytes(
-                    bytes[index_slice..index_slice + 4]
-                        .try_into()
-                        .map_err(|_| Error::SerialisationError)?,
-                );
-                let mut encrypted_shares: Vec<EncryptedSecretShare> =
-                    Vec::with_capacity(shares_len as usize);
-
-                index_slice += 4;
-                for _ in 0..shares_len {
-                    let share = EncryptedSecretShare::from_bytes(
-                        &bytes[index_slice..index_slice + 56]
-                            .try_into()
-                            .map_err(|_| Error::SerialisationError)?,
-                    )?;
-                    encrypted_shares.push(share);
-                    index_slice += 56;
-                }
-
-                Some(encrypted_shares)
-            }
-            0u8 => {
-                index_slice += 1;
-                None
-            }
-            _ => return Err(Error::SerialisationError),
-        };
-
-        let my_secret_shares = match bytes[index_slice] {
-            1u8 => {
-                index_slice += 1;
-                let shares_len = u32::from_le_bytes(
-                    bytes[index_slice..index_slice + 4]
-                        .try_into()
-                        .map_err(|_| Error::SerialisationError)?,
-                );
-                let mut shares: Vec<SecretShare> = Vec::with_capacity(shares_len as usize);
-
-                index_slice += 4;
-                for _ in 0..shares_len {
-                    let share = SecretShare::from_bytes(
-                        &bytes[index_slice..index_slice + 40]
-                            .try_into()
-                            .map_err(|_| Error::SerialisationError)?,
-                    )?;
-                    shares.push(share);
-                    index_slice += 40;
-                }
-
-                Some(shares)
-            }
-            0u8 => None,
-            _ => return Err(Error::SerialisationError),
-        };
-
-        Ok(ActualState {
-            parameters,
+        Ok(DkgSigner {
             index,
-            dh_private_key,
             dh_public_key,
-            their_commitments,
-            their_dh_public_keys,
-            their_encrypted_secret_shares,
-            my_secret_shares,
+            proof_of_dh_private_key,
+            share_versions,
         })
     }
 }
 
-/// Marker trait to designate valid rounds in the distributed key generation
-/// protocol's state machine.  It is implemented using the [sealed trait design
-/// pattern][sealed] pattern to prevent external types from implementing further
-/// valid states.
-///
-/// [sealed]: https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed
-pub trait DkgState: private::Sealed {}
+impl From<&Participant> for DkgSigner {
+    fn from(source: &Participant) -> DkgSigner {
+        DkgSigner {
+            index: source.index,
+            dh_public_key: source.dh_public_key.clone(),
+            proof_of_dh_private_key: source.proof_of_dh_private_key.clone(),
+            share_versions: source.share_versions.clone(),
+        }
+    }
+}
 
-impl DkgState for RoundOne {}
-impl DkgState for RoundTwo {}
+impl PartialOrd for DkgSigner {
+    fn partial_cmp(&self, other: &DkgSigner) -> Option<Ordering> {
+        match self.index.cmp(&other.index) {
+            Ordering::Less => Some(Ordering::Less),
+            Ordering::Equal => None, // Signers cannot have the same index.
+            Ordering::Greater => Some(Ordering::Greater),
+        }
+    }
+}
 
-/// Marker trait to designate valid variants of [`RoundOne`] in the distributed
-/// key generation protocol's state machine.  It is implemented using the
-/// [sealed trait design pattern][sealed] pattern to prevent external types from
-/// implementing further valid states.
-///
-/// [sealed]: https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed
-pub trait Round1: private::Sealed {}
+impl PartialEq for DkgSigner {
+    fn eq(&self, other: &DkgSigner) -> bool {
+        self.index == other.index
+    }
+}
 
-/// Marker trait to designate valid variants of [`RoundTwo`] in the distributed
-/// key generation protocol's state machine.  It is implemented using the
-/// [sealed trait design pattern][sealed] pattern to prevent external types from
-/// implementing further valid states.
+/// A Diffie-Hellman key pair held outside of this library, e.g. inside a
+/// hardware security module which cannot export its private key.
 ///
-/// [sealed]: https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed
-pub trait Round2: private::Sealed {}
+/// The library only ever asks the holder of the key for the results of
+/// operations with it.  A device built on this crate can compute them with
+/// [`DhKeyChallenge::sign`] and [`ComplaintProof::prove`].
+pub trait ExternalDhKey {
+    /// The DH public key of the pair.
+    fn dh_public_key(&self) -> DHPublicKey;
+
+    /// The Diffie-Hellman output of the private key with
+    /// `their_public_key`, as a compressed point.
+    fn diffie_hellman(&self, their_public_key: &DHPublicKey) -> [u8; 32];
+
+    /// Sign the `challenge` of a handshake with the private key, proving
+    /// possession of it.
+    fn sign_challenge(&self, challenge: &DhKeyChallenge) -> NizkOfSecretKey;
+
+    /// Prove that `dh_output` is the Diffie-Hellman output of the private
+    /// key with `their_public_key`, for a complaint about the share whose
+    /// ciphertext hashes to `ciphertext_hash`.
+    fn prove_complaint(
+        &self,
+        their_public_key: &DHPublicKey,
+        dh_output: &[u8; 32],
+        ciphertext_hash: &[u8; 32],
+    ) -> ComplaintProof;
+}
 
-impl Round1 for RoundOne {}
-impl Round2 for RoundTwo {}
+/// The DH private key with which a participant decrypts its shares.
+#[derive(Clone, Copy)]
+enum ShareDecryptionKey<'a> {
+    /// A key held by the library.
+    Local(&'a DHPrivateKey),
+    /// A key held externally.
+    External(&'a dyn ExternalDhKey),
+}
 
-fn encrypt_share(
-    share: &SecretShare,
-    aes_key: &[u8; 32],
-    mut rng: impl RngCore + CryptoRng,
-) -> EncryptedSecretShare {
-    let hkdf = Hkdf::<Sha512>::new(None, &aes_key[..]);
-    let mut final_aes_key = [0u8; 32];
-    hkdf.expand(&[], &mut final_aes_key)
-        .expect("KDF expansion failed unexpectedly");
+impl ShareDecryptionKey<'_> {
+    fn diffie_hellman(&self, their_public_key: &DHPublicKey) -> [u8; 32] {
+        match self {
+            ShareDecryptionKey::Local(key) => (their_public_key.0 * key.0).compress().to_bytes(),
+            ShareDecryptionKey::External(key) => key.diffie_hellman(their_public_key),
+        }
+    }
 
-    let mut nonce_array = [0u8; 16];
-    rng.fill_bytes(&mut nonce_array);
+    fn prove_complaint(
+        &self,
+        their_public_key: &DHPublicKey,
+        dh_output: &[u8; 32],
+        ciphertext_hash: &[u8; 32],
+        rng: impl RngCore + CryptoRng,
+    ) -> ComplaintProof {
+        match self {
+            ShareDecryptionKey::Local(key) => {
+                ComplaintProof::prove(key, their_public_key, dh_output, ciphertext_hash, rng)
+            }
+            ShareDecryptionKey::External(key) => {
+                key.prove_complaint(their_public_key, dh_output, ciphertext_hash)
+            }
+        }
+    }
+}
 
-    let final_aes_key = GenericArray::from_slice(&final_aes_key);
-    let mut share_bytes = share.polynomial_evaluation.to_bytes();
+/// The challenge signed by the holder of an [`ExternalDhKey`] to prove
+/// possession of it to the dealers of a ceremony.
+///
+/// The signature binds the DH public key to the `index` of the signer and to
+/// the `context_string` of the ceremony, hence cannot be replayed in another.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DhKeyChallenge {
+    /// The index of the signer holding the key.
+    pub index: u32,
+    /// The context string of the ceremony.
+    pub context_string: String,
+}
 
-    let nonce = GenericArray::from_slice(&nonce_array);
-    let cipher = Aes256::new(final_aes_key);
-    let mut cipher = Aes256Ctr::from_block_cipher(cipher, nonce);
+impl DhKeyChallenge {
+    /// Construct the challenge for the signer with this `index` in the
+    /// ceremony of this `context_string`.
+    pub fn new(index: u32, context_string: &str) -> DhKeyChallenge {
+        DhKeyChallenge {
+            index,
+            context_string: context_string.to_string(),
+        }
+    }
 
-    cipher.apply_keystream(&mut share_bytes);
+    /// Sign this challenge with `dh_private_key`, as the holder of an
+    /// [`ExternalDhKey`] does.
+    pub fn sign(
+        &self,
+        dh_private_key: &DHPrivateKey,
+        rng: impl RngCore + CryptoRng,
+    ) -> NizkOfSecretKey {
+        let dh_public_key = &RISTRETTO_BASEPOINT_TABLE * &dh_private_key.0;
 
-    EncryptedSecretShare {
-        sender_index: share.sender_index,
-        receiver_index: share.receiver_index,
-        nonce: nonce_array,
-        encrypted_polynomial_evaluation: share_bytes,
+        NizkOfSecretKey::prove(
+            &self.index,
+            dh_private_key,
+            &dh_public_key,
+            &self.context_string,
+            rng,
+        )
     }
 }
 
-fn decrypt_share(
-    encrypted_share: &EncryptedSecretShare,
-    aes_key: &[u8; 32],
-) -> Result<SecretShare, Error> {
-    let hkdf = Hkdf::<Sha512>::new(None, &aes_key[..]);
-    let mut final_aes_key = [0u8; 32];
-    hkdf.expand(&[], &mut final_aes_key)
-        .expect("KDF expansion failed unexpectedly");
-
-    let final_aes_key = GenericArray::from_slice(&final_aes_key);
-
-    let nonce = GenericArray::from_slice(&encrypted_share.nonce);
-    let cipher = Aes256::new(final_aes_key);
-    let mut cipher = Aes256Ctr::from_block_cipher(cipher, nonce);
+/// The answer of the holder of an [`ExternalDhKey`] to a [`DhKeyChallenge`],
+/// sent to the dealers in lieu of a [`DkgSigner`], which they construct from
+/// it with [`DkgSigner::new_from_public`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DhKeyHandshake {
+    /// The index of the signer holding the key.
+    pub index: u32,
+    /// The DH public key of the signer.
+    pub dh_public_key: DHPublicKey,
+    /// The signature of the challenge, made inside the holder of the key.
+    pub signature: NizkOfSecretKey,
+}
 
-    let mut bytes: [u8; 32] = encrypted_share.encrypted_polynomial_evaluation;
-    cipher.apply_keystream(&mut bytes);
+impl DhKeyHandshake {
+    /// Answer the `challenge` with the external `dh_key`.
+    pub fn new(challenge: &DhKeyChallenge, dh_key: &impl ExternalDhKey) -> DhKeyHandshake {
+        DhKeyHandshake {
+            index: challenge.index,
+            dh_public_key: dh_key.dh_public_key(),
+            signature: dh_key.sign_challenge(challenge),
+        }
+    }
 
-    let evaluation = Scalar::from_canonical_bytes(bytes);
-    if evaluation.is_none() {
-        return Err(Error::DecryptionError);
+    /// Verify that this handshake proves possession of the DH private key,
+    /// for the ceremony of this `context_string`.
+    pub fn verify(&self, context_string: &str) -> Result<(), Error> {
+        self.signature
+            .verify(&self.index, &self.dh_public_key, context_string)
     }
 
+    /// Serialise this handshake to an array of bytes.
+    pub fn to_bytes(&self) -> [u8; 100] {
+        let mut res = [0u8; 100];
+        res[0..4].copy_from_slice(&self.index.to_le_bytes());
+        res[4..36].copy_from_slice(&self.dh_public_key.to_bytes());
+        res[36..100].copy_from_slice(&self.signature.to_bytes());
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `DhKeyHandshake`.
+    pub fn from_bytes(bytes: &[u8; 100]) -> Result<DhKeyHandshake, Error> {
+        let (index, dh_public_key) = participant_header_from_bytes(bytes)?;
+        let signature = proof_from_bytes(bytes, 36)?;
+
+        Ok(DhKeyHandshake {
+            index,
+            dh_public_key,
+            signature,
+        })
+    }
+}
+
+/// The public information used by the distributed key generation protocol
+/// from both [`Participant`]s and [`DkgSigner`]s.
+trait DkgParticipant: Clone {
+    fn index(&self) -> u32;
+    fn dh_public_key(&self) -> &DHPublicKey;
+    fn proof_of_dh_private_key(&self) -> &NizkOfSecretKey;
+    fn share_versions(&self) -> &[ShareVersion];
+    /// The commitments and proof of knowledge of the secret key of this
+    /// participant, if it is a dealer.
+    fn dealer(&self) -> Option<&Participant>;
+}
+
+impl DkgParticipant for Participant {
+    fn index(&self) -> u32 {
+        self.index
+    }
+    fn dh_public_key(&self) -> &DHPublicKey {
+        &self.dh_public_key
+    }
+    fn proof_of_dh_private_key(&self) -> &NizkOfSecretKey {
+        &self.proof_of_dh_private_key
+    }
+    fn share_versions(&self) -> &[ShareVersion] {
+        &self.share_versions
+    }
+    fn dealer(&self) -> Option<&Participant> {
+        Some(self)
+    }
+}
+
+impl DkgParticipant for DkgSigner {
+    fn index(&self) -> u32 {
+        self.index
+    }
+    fn dh_public_key(&self) -> &DHPublicKey {
+        &self.dh_public_key
+    }
+    fn proof_of_dh_private_key(&self) -> &NizkOfSecretKey {
+        &self.proof_of_dh_private_key
+    }
+    fn share_versions(&self) -> &[ShareVersion] {
+        &self.share_versions
+    }
+    fn dealer(&self) -> Option<&Participant> {
+        None
+    }
+}
+
+/// Sample a new Diffie-Hellman key pair, unless a `dh_private_key` is given,
+/// along with a proof of knowledge of the private key.
+fn new_dh_key_pair(
+    index: u32,
+    dh_private_key: Option<&DHPrivateKey>,
+    context_string: &str,
+    mut rng: impl RngCore + CryptoRng,
+) -> (DHPrivateKey, DHPublicKey, NizkOfSecretKey) {
+    // RICE-FROST: Every participant samples a random pair of keys (dh_private_key, dh_public_key)
+    // and generates a proof of knowledge of dh_private_key. This will be used for secret shares
+    // encryption and for complaint generation.
+    let dh_private_key = match dh_private_key {
+        Some(key) => key.duplicate(),
+        None => DHPrivateKey(Scalar::random(&mut rng)),
+    };
+    let dh_public_key = DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &dh_private_key);
+
+    // Compute a proof of knowledge of dh_secret_key
+    let proof_of_dh_private_key: NizkOfSecretKey = NizkOfSecretKey::prove(
+        &index,
+        &dh_private_key,
+        &dh_public_key,
+        context_string,
+        &mut rng,
+    );
+
+    (dh_private_key, dh_public_key, proof_of_dh_private_key)
+}
+
+/// Check that no two `participants` have the same DH public key.
+fn check_unique_dh_public_keys<P: DkgParticipant>(participants: &[P]) -> Result<(), Error> {
+    let mut keys: Vec<(CompressedRistretto, u32)> = participants
+        .iter()
+        .map(|p| (p.dh_public_key().0.compress(), p.index()))
+        .collect();
+    keys.sort_unstable_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()).then(a.1.cmp(&b.1)));
+
+    match keys.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        Some(pair) => Err(Error::DuplicateDhPublicKey {
+            first: pair[0].1.min(pair[1].1),
+            second: pair[0].1.max(pair[1].1),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check that all the dealers of a resharing published the same number of
+/// commitments, designating one which departs from the most common count.
+fn check_commitment_counts_agree(
+    commitments: &[&VerifiableSecretSharingCommitment],
+) -> Result<(), Error> {
+    let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+    for c in commitments.iter() {
+        *counts.entry(c.points.len()).or_insert(0) += 1;
+    }
+    let expected = match counts.iter().max_by_key(|(_, occurrences)| **occurrences) {
+        Some((count, _)) => *count,
+        None => return Ok(()),
+    };
+
+    for c in commitments.iter() {
+        c.check_count(expected)?;
+    }
+
+    Ok(())
+}
+
+/// Deserialise the index and the DH public key heading the encoding of a
+/// [`Participant`] or a [`DkgSigner`].
+fn participant_header_from_bytes(bytes: &[u8]) -> Result<(u32, DHPublicKey), Error> {
+    let index = u32::from_le_bytes(
+        bytes
+            .get(0..4)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    );
+    let dh_public_key = DHPublicKey::from_bytes(
+        bytes
+            .get(4..36)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    )?;
+
+    Ok((index, dh_public_key))
+}
+
+/// Deserialise the proof of knowledge at `index_slice` in `bytes`.
+fn proof_from_bytes(bytes: &[u8], index_slice: usize) -> Result<NizkOfSecretKey, Error> {
+    NizkOfSecretKey::from_bytes(
+        bytes
+            .get(index_slice..index_slice + 64)
+            .ok_or(Error::SerialisationError)?
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    )
+}
+
+/// Serialise the share versions supported by a participant.
+///
+/// The share versions are appended last, so that participants serialised
+/// before their introduction can still be deserialised.
+fn share_versions_to_bytes(share_versions: &[ShareVersion], res: &mut Vec<u8>) {
+    res.push(TryInto::<u8>::try_into(share_versions.len()).unwrap());
+    for version in share_versions.iter() {
+        res.push(version.to_byte());
+    }
+}
+
+/// Deserialise the share versions at `index_slice` in `bytes`, defaulting to
+/// [`ShareVersion::V0`] only if there are none.
+fn share_versions_from_bytes(bytes: &[u8], index_slice: usize) -> Result<Vec<ShareVersion>, Error> {
+    match bytes.get(index_slice) {
+        #[cfg(not(feature = "strict"))]
+        None => Ok([ShareVersion::V0].to_vec()),
+        #[cfg(feature = "strict")]
+        None => Err(Error::SerialisationError),
+        Some(len) => {
+            let versions = bytes
+                .get(index_slice + 1..index_slice + 1 + *len as usize)
+                .ok_or(Error::SerialisationError)?;
+            versions
+                .iter()
+                .map(|v| ShareVersion::from_byte(*v))
+                .collect::<Result<Vec<ShareVersion>, Error>>()
+        }
+    }
+}
+
+/// Module to implement trait sealing so that `DkgState` cannot be
+/// implemented for externally declared types.
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::RoundOne {}
+    impl Sealed for super::RoundTwo {}
+}
+
+/// State machine structures for holding intermediate values during a
+/// distributed key generation protocol run, to prevent misuse.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct DistributedKeyGeneration<S: DkgState> {
+    state: Box<ActualState>,
+    data: S,
+}
+
+/// Shared state which occurs across all rounds of a threshold signing protocol run.
+#[derive(Debug, PartialEq, Eq)]
+struct ActualState {
+    /// The parameters for this instantiation of a threshold signature.
+    parameters: Parameters,
+    /// The index of the participant.
+    index: u32,
+    /// The DH private key for deriving a symmetric key to encrypt and decrypt
+    /// secret shares, or `None` if it is held externally.
+    dh_private_key: Option<DHPrivateKey>,
+    /// The DH public key for deriving a symmetric key to encrypt and decrypt
+    /// secret shares.
+    dh_public_key: DHPublicKey,
+    /// The versions of the encrypted secret shares accepted by this participant.
+    share_versions: Vec<ShareVersion>,
+    /// A vector of tuples containing the index of each participant and that
+    /// respective participant's commitments to their private polynomial
+    /// coefficients.
+    their_commitments: Option<Vec<VerifiableSecretSharingCommitment>>,
+    /// A vector of ECPoints containing the index of each participant and that
+    /// respective participant's DH public key.
+    their_dh_public_keys: Vec<(u32, DHPublicKey)>,
+    /// The index of each dealer with the share version negotiated with it,
+    /// which is the only one accepted from this dealer.
+    their_share_versions: Vec<(u32, ShareVersion)>,
+    /// The encrypted secret shares this participant has calculated for all the other participants.
+    their_encrypted_secret_shares: Option<Vec<EncryptedSecretShare>>,
+    /// The secret shares this participant has received from all the other participants.
+    my_secret_shares: Option<Vec<SecretShare>>,
+    /// The salt binding the share keys to this group, when the participants
+    /// use long-term [`IdentityKey`]s.
+    share_key_salt: Option<[u8; 32]>,
+}
+
+impl ActualState {
+    /// Create another copy of this state, whose secrets are zeroed out when
+    /// dropped independently of this one.
+    fn duplicate(&self) -> ActualState {
+        ActualState {
+            parameters: self.parameters,
+            index: self.index,
+            dh_private_key: self.dh_private_key.as_ref().map(DHPrivateKey::duplicate),
+            dh_public_key: self.dh_public_key.clone(),
+            share_versions: self.share_versions.clone(),
+            their_commitments: self.their_commitments.clone(),
+            their_dh_public_keys: self.their_dh_public_keys.clone(),
+            their_share_versions: self.their_share_versions.clone(),
+            their_encrypted_secret_shares: self.their_encrypted_secret_shares.clone(),
+            my_secret_shares: self
+                .my_secret_shares
+                .as_ref()
+                .map(|shares| shares.iter().map(SecretShare::duplicate).collect()),
+            share_key_salt: self.share_key_salt,
+        }
+    }
+
+    /// Overwrite the secrets of this state with zeroes.
+    fn wipe(&mut self) {
+        self.dh_private_key.zeroize();
+        self.their_encrypted_secret_shares.zeroize();
+        self.my_secret_shares.zeroize();
+    }
+
+    /// Serialise this state to a Vec of bytes
+    ///
+    /// A DH private key held externally is encoded as zero, as is a wiped one.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+        res.extend_from_slice(&self.parameters.to_bytes());
+        res.extend_from_slice(&self.index.to_le_bytes());
+        res.extend_from_slice(
+            &self
+                .dh_private_key
+                .as_ref()
+                .map_or([0u8; 32], DHPrivateKey::to_bytes),
+        );
+        res.extend_from_slice(&self.dh_public_key.to_bytes());
+        res.push(TryInto::<u8>::try_into(self.share_versions.len()).unwrap());
+        for version in self.share_versions.iter() {
+            res.push(version.to_byte());
+        }
+
+        match &self.their_commitments {
+            Some(v) => {
+                res.push(1u8);
+                let mut tmp = v.iter().map(|e| e.to_bytes()).collect::<Vec<Vec<u8>>>();
+                res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
+                for commitment in tmp.iter_mut() {
+                    res.extend_from_slice(commitment);
+                }
+            }
+            None => res.push(0u8),
+        }
+
+        let mut tmp = self
+            .their_dh_public_keys
+            .iter()
+            .map(|e| (e.0.to_le_bytes(), e.1.to_bytes()))
+            .collect::<Vec<([u8; 4], [u8; 32])>>();
+        res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
+        for (index, keys) in tmp.iter_mut() {
+            res.extend_from_slice(index);
+            res.extend_from_slice(keys);
+        }
+
+        match &self.their_encrypted_secret_shares {
+            Some(v) => {
+                res.push(1u8);
+                let mut tmp = v.iter().map(|e| e.to_bytes()).collect::<Vec<Vec<u8>>>();
+                res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
+                for elem in tmp.iter_mut() {
+                    res.push(TryInto::<u8>::try_into(elem.len()).unwrap());
+                    res.extend_from_slice(elem);
+                    elem.zeroize();
+                }
+            }
+            None => res.push(0u8),
+        };
+
+        match &self.my_secret_shares {
+            Some(v) => {
+                res.push(1u8);
+                let mut tmp = v.iter().map(|e| e.to_bytes()).collect::<Vec<[u8; 40]>>();
+                res.extend_from_slice(&TryInto::<u32>::try_into(tmp.len()).unwrap().to_le_bytes());
+                for elem in tmp.iter_mut() {
+                    res.extend_from_slice(elem);
+                }
+            }
+            None => res.push(0u8),
+        };
+
+        // The salt is only appended when present, so that the encoding of
+        // states with per-group DH keys is unchanged.
+        if let Some(salt) = &self.share_key_salt {
+            res.push(1u8);
+            res.extend_from_slice(salt);
+        }
+        // Likewise, the negotiated versions are only appended for states
+        // receiving shares.
+        if !self.their_share_versions.is_empty() {
+            res.push(2u8);
+            res.extend_from_slice(
+                &TryInto::<u32>::try_into(self.their_share_versions.len())
+                    .unwrap()
+                    .to_le_bytes(),
+            );
+            for (index, version) in self.their_share_versions.iter() {
+                res.extend_from_slice(&index.to_le_bytes());
+                res.push(version.to_byte());
+            }
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to an `ActualState`
+    pub fn from_bytes(bytes: &[u8]) -> Result<ActualState, Error> {
+        let mut array = [0u8; 8];
+        array.copy_from_slice(&bytes[..8]);
+        let parameters = Parameters::from_bytes(&array)?;
+
+        let index = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes[12..44]);
+        let dh_private_key = match array == [0u8; 32] {
+            true => None,
+            false => Some(DHPrivateKey::from_bytes(&array)?),
+        };
+
+        array.copy_from_slice(&bytes[44..76]);
+        let dh_public_key = DHPublicKey::from_bytes(&array)?;
+
+        let versions_len = *bytes.get(76).ok_or(Error::SerialisationError)? as usize;
+        let share_versions = bytes
+            .get(77..77 + versions_len)
+            .ok_or(Error::SerialisationError)?
+            .iter()
+            .map(|v| ShareVersion::from_byte(*v))
+            .collect::<Result<Vec<ShareVersion>, Error>>()?;
+
+        let mut index_slice = 77 + versions_len;
+
+        let their_commitments = match bytes[index_slice] {
+            1u8 => {
+                index_slice += 1;
+                let commit_len = u32::from_le_bytes(
+                    bytes[index_slice..index_slice + 4]
+                        .try_into()
+                        .map_err(|_| Error::SerialisationError)?,
+                );
+                let mut coms: Vec<VerifiableSecretSharingCommitment> =
+                    Vec::with_capacity(commit_len as usize);
+
+                index_slice += 4;
+
+                for _ in 0..commit_len {
+                    let com = VerifiableSecretSharingCommitment::from_bytes(&bytes[index_slice..])?;
+                    index_slice += 4 + 4 + com.points.len() * 32;
+                    coms.push(com);
+                }
+                check_commitment_counts_agree(&coms.iter().collect::<Vec<_>>())?;
+
+                Some(coms)
+            }
+            0u8 => {
+                index_slice += 1;
+                None
+            }
+            _ => return Err(Error::SerialisationError),
+        };
+
+        let dh_key_len = u32::from_le_bytes(
+            bytes[index_slice..index_slice + 4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let mut their_dh_public_keys: Vec<(u32, DHPublicKey)> =
+            Vec::with_capacity(dh_key_len as usize);
+
+        index_slice += 4;
+        for _ in 0..dh_key_len {
+            let index = u32::from_le_bytes(
+                bytes[index_slice..index_slice + 4]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            );
+            let key = DHPublicKey::from_bytes(
+                &bytes[index_slice + 4..index_slice + 36]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            )?;
+            their_dh_public_keys.push((index, key));
+            index_slice += 36;
+        }
+
+        let their_encrypted_secret_shares = match bytes[index_slice] {
+            1u8 => {
+                index_slice += 1;
+                let shares_len = u32::from_le_bytes(
+                    bytes[index_slice..index_slice + 4]
+                        .try_into()
+                        .map_err(|_| Error::SerialisationError)?,
+                );
+                let mut encrypted_shares: Vec<EncryptedSecretShare> =
+                    Vec::with_capacity(shares_len as usize);
+
+                index_slice += 4;
+                for _ in 0..shares_len {
+                    let share_len = *bytes.get(index_slice).ok_or(Error::SerialisationError)?;
+                    index_slice += 1;
+                    let share = EncryptedSecretShare::from_bytes(
+                        bytes
+                            .get(index_slice..index_slice + share_len as usize)
+                            .ok_or(Error::SerialisationError)?,
+                    )?;
+                    encrypted_shares.push(share);
+                    index_slice += share_len as usize;
+                }
+
+                Some(encrypted_shares)
+            }
+            0u8 => {
+                index_slice += 1;
+                None
+            }
+            _ => return Err(Error::SerialisationError),
+        };
+
+        let my_secret_shares = match bytes[index_slice] {
+            1u8 => {
+                index_slice += 1;
+                let shares_len = u32::from_le_bytes(
+                    bytes[index_slice..index_slice + 4]
+                        .try_into()
+                        .map_err(|_| Error::SerialisationError)?,
+                );
+                let mut shares: Vec<SecretShare> = Vec::with_capacity(shares_len as usize);
+
+                index_slice += 4;
+                for _ in 0..shares_len {
+                    let share = SecretShare::from_bytes(
+                        &bytes[index_slice..index_slice + 40]
+                            .try_into()
+                            .map_err(|_| Error::SerialisationError)?,
+                    )?;
+                    shares.push(share);
+                    index_slice += 40;
+                }
+
+                Some(shares)
+            }
+            0u8 => {
+                index_slice += 1;
+                None
+            }
+            _ => return Err(Error::SerialisationError),
+        };
+
+        // The encoding of the state is followed by the round of the protocol.
+        let remaining = |index_slice: usize| bytes.len().saturating_sub(index_slice);
+        let mut share_key_salt = None;
+        if remaining(index_slice) > 1 && bytes[index_slice] == 1u8 {
+            share_key_salt = Some(
+                bytes
+                    .get(index_slice + 1..index_slice + 33)
+                    .ok_or(Error::SerialisationError)?
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            );
+            index_slice += 33;
+        }
+        let mut their_share_versions = Vec::new();
+        if remaining(index_slice) > 1 && bytes[index_slice] == 2u8 {
+            let versions_len = u32::from_le_bytes(
+                bytes
+                    .get(index_slice + 1..index_slice + 5)
+                    .ok_or(Error::SerialisationError)?
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            );
+            index_slice += 5;
+            for _ in 0..versions_len {
+                let entry = bytes
+                    .get(index_slice..index_slice + 5)
+                    .ok_or(Error::SerialisationError)?;
+                let index = u32::from_le_bytes(
+                    entry[0..4]
+                        .try_into()
+                        .map_err(|_| Error::SerialisationError)?,
+                );
+                their_share_versions.push((index, ShareVersion::from_byte(entry[4])?));
+                index_slice += 5;
+            }
+        }
+        if remaining(index_slice) != 1 {
+            return Err(Error::SerialisationError);
+        }
+
+        Ok(ActualState {
+            parameters,
+            index,
+            dh_private_key,
+            dh_public_key,
+            share_versions,
+            their_commitments,
+            their_dh_public_keys,
+            their_share_versions,
+            their_encrypted_secret_shares,
+            my_secret_shares,
+            share_key_salt,
+        })
+    }
+}
+
+/// Marker trait to designate valid rounds in the distributed key generation
+/// protocol's state machine.  It is implemented using the [sealed trait design
+/// pattern][sealed] pattern to prevent external types from implementing further
+/// valid states.
+///
+/// [sealed]: https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed
+pub trait DkgState: private::Sealed {}
+
+impl DkgState for RoundOne {}
+impl DkgState for RoundTwo {}
+
+/// Marker trait to designate valid variants of [`RoundOne`] in the distributed
+/// key generation protocol's state machine.  It is implemented using the
+/// [sealed trait design pattern][sealed] pattern to prevent external types from
+/// implementing further valid states.
+///
+/// [sealed]: https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed
+pub trait Round1: private::Sealed {}
+
+/// Marker trait to designate valid variants of [`RoundTwo`] in the distributed
+/// key generation protocol's state machine.  It is implemented using the
+/// [sealed trait design pattern][sealed] pattern to prevent external types from
+/// implementing further valid states.
+///
+/// [sealed]: https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed
+pub trait Round2: private::Sealed {}
+
+impl Round1 for RoundOne {}
+impl Round2 for RoundTwo {}
+
+/// The salt binding the share keys of participants using long-term
+/// [`IdentityKey`]s to the group identified by `context_string`.
+fn share_key_salt(context_string: &str) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(b"ICE-FROST-GROUP-SHARE-KEY");
+    h.update(context_string.as_bytes());
+
+    h.finalize().into()
+}
+
+/// The key shared by a dealer and a recipient for encrypting secret shares,
+/// derived from their Diffie-Hellman output and, with long-term identity
+/// keys, from the salt of their group.
+fn share_key(dh_output: &[u8; 32], salt: Option<&[u8; 32]>) -> [u8; 32] {
+    match salt {
+        None => *dh_output,
+        Some(salt) => {
+            let hkdf = Hkdf::<Sha512>::new(Some(&salt[..]), &dh_output[..]);
+            let mut key = [0u8; 32];
+            hkdf.expand(b"ICE-FROST-IDENTITY-SHARE-KEY", &mut key)
+                .expect("KDF expansion failed unexpectedly");
+
+            key
+        }
+    }
+}
+
+/// Derive the AES key and, for [`ShareVersion::V1`], the MAC key of a share
+/// from the Diffie-Hellman key shared by its dealer and recipient.
+fn derive_share_keys(aes_key: &[u8; 32], version: ShareVersion) -> ([u8; 32], [u8; 32]) {
+    let hkdf = Hkdf::<Sha512>::new(None, &aes_key[..]);
+    let mut final_aes_key = [0u8; 32];
+    let mut mac_key = [0u8; 32];
+
+    match version {
+        #[cfg(not(feature = "strict"))]
+        ShareVersion::V0 => {
+            hkdf.expand(&[], &mut final_aes_key)
+                .expect("KDF expansion failed unexpectedly");
+        }
+        ShareVersion::V1 => {
+            let mut okm = [0u8; 64];
+            hkdf.expand(b"ICE-FROST-SHARE-V1", &mut okm)
+                .expect("KDF expansion failed unexpectedly");
+            final_aes_key.copy_from_slice(&okm[..32]);
+            mac_key.copy_from_slice(&okm[32..]);
+            okm.zeroize();
+        }
+    }
+
+    (final_aes_key, mac_key)
+}
+
+/// Compute the authentication tag of a [`ShareVersion::V1`] share.
+fn share_tag(mac_key: &[u8; 32], encrypted_share: &EncryptedSecretShare) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&mac_key[..]).expect("HMAC accepts any key size");
+    mac.update(&[encrypted_share.version.to_byte()]);
+    mac.update(&encrypted_share.sender_index.to_le_bytes());
+    mac.update(&encrypted_share.receiver_index.to_le_bytes());
+    mac.update(&encrypted_share.nonce);
+    mac.update(&encrypted_share.encrypted_polynomial_evaluation);
+
+    mac
+}
+
+fn encrypt_share(
+    share: &SecretShare,
+    aes_key: &[u8; 32],
+    version: ShareVersion,
+    mut rng: impl RngCore + CryptoRng,
+) -> EncryptedSecretShare {
+    let (mut final_aes_key, mut mac_key) = derive_share_keys(aes_key, version);
+
+    let mut nonce_array = [0u8; 16];
+    rng.fill_bytes(&mut nonce_array);
+
+    let mut share_bytes = share.polynomial_evaluation.to_bytes();
+
+    let nonce = GenericArray::from_slice(&nonce_array);
+    let cipher = Aes256::new(GenericArray::from_slice(&final_aes_key));
+    let mut cipher = Aes256Ctr::from_block_cipher(cipher, nonce);
+
+    cipher.apply_keystream(&mut share_bytes);
+
+    let mut encrypted_share = EncryptedSecretShare {
+        sender_index: share.sender_index,
+        receiver_index: share.receiver_index,
+        nonce: nonce_array,
+        encrypted_polynomial_evaluation: share_bytes,
+        version,
+        tag: [0u8; 32],
+    };
+    if version == ShareVersion::V1 {
+        let tag = share_tag(&mac_key, &encrypted_share)
+            .finalize()
+            .into_bytes();
+        encrypted_share.tag.copy_from_slice(&tag);
+    }
+    final_aes_key.zeroize();
+    mac_key.zeroize();
+
+    encrypted_share
+}
+
+fn decrypt_share(
+    encrypted_share: &EncryptedSecretShare,
+    aes_key: &[u8; 32],
+) -> Result<SecretShare, Error> {
+    let (mut final_aes_key, mut mac_key) = derive_share_keys(aes_key, encrypted_share.version);
+
+    let authentic = match encrypted_share.version {
+        #[cfg(not(feature = "strict"))]
+        ShareVersion::V0 => true,
+        ShareVersion::V1 => share_tag(&mac_key, encrypted_share)
+            .verify(&encrypted_share.tag)
+            .is_ok(),
+    };
+    mac_key.zeroize();
+    if !authentic {
+        final_aes_key.zeroize();
+        return Err(Error::DecryptionError);
+    }
+
+    let nonce = GenericArray::from_slice(&encrypted_share.nonce);
+    let cipher = Aes256::new(GenericArray::from_slice(&final_aes_key));
+    let mut cipher = Aes256Ctr::from_block_cipher(cipher, nonce);
+    final_aes_key.zeroize();
+
+    let mut bytes: [u8; 32] = encrypted_share.encrypted_polynomial_evaluation;
+    cipher.apply_keystream(&mut bytes);
+
+    let evaluation = decode_scalar_canonical(&bytes).map_err(|_| Error::DecryptionError);
+    bytes.zeroize();
+
     Ok(SecretShare {
         sender_index: encrypted_share.sender_index,
         receiver_index: encrypted_share.receiver_index,
-        polynomial_evaluation: evaluation.unwrap(),
+        polynomial_evaluation: evaluation?,
     })
 }
 
-/// Every participant in the distributed key generation has sent a vector of
-/// commitments and a zero-knowledge proof of a secret key to every other
-/// participant in the protocol.  During round one, each participant checks the
-/// zero-knowledge proofs of secret keys of all other participants.
-#[derive(Clone, Debug)]
-pub struct RoundOne {}
+/// Every participant in the distributed key generation has sent a vector of
+/// commitments and a zero-knowledge proof of a secret key to every other
+/// participant in the protocol.  During round one, each participant checks the
+/// zero-knowledge proofs of secret keys of all other participants.
+#[derive(Clone, Debug)]
+pub struct RoundOne {}
+
+/// The minimal secret state a dealer must retain during round one of a
+/// distributed key generation, in order to resume it after a crash: its
+/// parameters and index, its DH private key and its secret polynomial.
+///
+/// Everything else is re-derived from it by
+/// [`DistributedKeyGeneration::restore_round_one`], so that a checkpoint
+/// takes \(48 + 32t\) bytes whatever the number of participants, whereas
+/// the whole round one state holds an encrypted share for each of them.
+#[derive(Zeroize)]
+#[zeroize(drop)]
+pub struct DkgCheckpoint {
+    #[zeroize(skip)]
+    parameters: Parameters,
+    index: u32,
+    dh_private_key: DHPrivateKey,
+    coefficients: Coefficients,
+}
+
+impl DkgCheckpoint {
+    /// Serialise this checkpoint to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(48 + 32 * self.coefficients.0.len());
+        res.extend_from_slice(&self.parameters.to_bytes());
+        res.extend_from_slice(&self.index.to_le_bytes());
+        res.extend_from_slice(&self.dh_private_key.to_bytes());
+        res.extend_from_slice(&self.coefficients.to_bytes());
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `DkgCheckpoint`
+    pub fn from_bytes(bytes: &[u8]) -> Result<DkgCheckpoint, Error> {
+        if bytes.len() < 48 {
+            return Err(Error::SerialisationError);
+        }
+        let parameters = Parameters::from_bytes(
+            bytes[0..8]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let index = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let dh_private_key = DHPrivateKey::from_bytes(
+            bytes[12..44]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let len = u32::from_le_bytes(
+            bytes[44..48]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        if len != parameters.t || bytes.len() != 48 + 32 * len as usize {
+            return Err(Error::SerialisationError);
+        }
+        let coefficients = Coefficients::from_bytes(&bytes[44..])?;
+
+        Ok(DkgCheckpoint {
+            parameters,
+            index,
+            dh_private_key,
+            coefficients,
+        })
+    }
+
+    /// Abandon the key generation this checkpoint was taken from, wiping
+    /// its secrets, as with [`DistributedKeyGeneration::abandon`].
+    pub fn abandon(mut self, reason: &str) -> AbortRecord {
+        self.dh_private_key.zeroize();
+        self.coefficients.zeroize();
+
+        AbortRecord {
+            parameters: self.parameters,
+            index: self.index,
+            round: 1,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// The record of an abandoned distributed key generation, whose secrets
+/// have been wiped, for the ceremony log.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AbortRecord {
+    /// The parameters of the key generation.
+    pub parameters: Parameters,
+    /// The index of the participant which abandoned it.
+    pub index: u32,
+    /// The round of the protocol the participant was in, 1 or 2.
+    pub round: u8,
+    /// The reason of the abort.
+    pub reason: String,
+}
+
+impl AbortRecord {
+    fn new(state: &ActualState, round: u8, reason: &str) -> AbortRecord {
+        AbortRecord {
+            parameters: state.parameters,
+            index: state.index,
+            round,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Serialise this abort record to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(17 + self.reason.len());
+        res.extend_from_slice(&self.parameters.to_bytes());
+        res.extend_from_slice(&self.index.to_le_bytes());
+        res.push(self.round);
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.reason.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        res.extend_from_slice(self.reason.as_bytes());
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to an `AbortRecord`
+    pub fn from_bytes(bytes: &[u8]) -> Result<AbortRecord, Error> {
+        if bytes.len() < 17 {
+            return Err(Error::SerialisationError);
+        }
+        let parameters = Parameters::from_bytes(
+            bytes[0..8]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let index = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let round = bytes[12];
+        if round != 1 && round != 2 {
+            return Err(Error::SerialisationError);
+        }
+        let len = u32::from_le_bytes(
+            bytes[13..17]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        if bytes.len() != 17 + len as usize {
+            return Err(Error::SerialisationError);
+        }
+        let reason = core::str::from_utf8(&bytes[17..])
+            .map_err(|_| Error::SerialisationError)?
+            .to_string();
+
+        Ok(AbortRecord {
+            parameters,
+            index,
+            round,
+            reason,
+        })
+    }
+}
+
+/// Output of the first round of the Distributed Key Generation.
+///
+/// The participants are [`DkgSigner`]s for a dealer resharing its secret key
+/// with [`Participant::reshare`], and [`Participant`]s otherwise.
+#[derive(Clone, Debug)]
+pub struct DKGParticipantList<P = Participant> {
+    /// List of the valid participants to be used in RoundTwo
+    pub valid_participants: Vec<P>,
+    /// List of the invalid participants that have been removed
+    pub misbehaving_participants: Option<Vec<u32>>,
+}
+
+impl<S: DkgState + Clone> DistributedKeyGeneration<S> {
+    /// Create another copy of this state, whose secrets, i.e. the DH private
+    /// key and the secret shares, are zeroed out when dropped independently
+    /// of this one.
+    ///
+    /// # Warning
+    ///
+    /// Both copies hold the same secrets, and should not both be carried on
+    /// with, e.g. with different shares of the other participants.
+    pub fn duplicate(&self) -> DistributedKeyGeneration<S> {
+        DistributedKeyGeneration {
+            state: Box::new(self.state.duplicate()),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl DistributedKeyGeneration<RoundOne> {
+    /// Check the zero-knowledge proofs of knowledge of the constant term of
+    /// the secret polynomial of all the `participants`, e.g. to bail before
+    /// decrypting any share if one of them attempted a rogue-key attack.
+    ///
+    /// # Returns
+    ///
+    /// The sorted indices of the participants whose proof did not verify, or
+    /// who have empty commitments.
+    pub fn verify_all_proofs_of_knowledge(
+        participants: &[Participant],
+        context_string: &str,
+    ) -> Result<(), Vec<u32>> {
+        let mut failed: Vec<u32> = participants
+            .iter()
+            .filter(|p| match p.public_key() {
+                Some(public_key) => p
+                    .proof_of_secret_key
+                    .verify(&p.index, public_key, context_string)
+                    .is_err(),
+                None => true,
+            })
+            .map(|p| p.index)
+            .collect();
+
+        match failed.is_empty() {
+            true => Ok(()),
+            false => {
+                failed.sort_unstable();
+                Err(failed)
+            }
+        }
+    }
+
+    /// Check the zero-knowledge proofs of knowledge of secret keys of all the
+    /// other participants. When no group key has been computed by a group of
+    /// participants yet, this method should be called rather than
+    /// `DistributedKeyGeneration<RoundOne>::new()`.
+    ///
+    /// # Note
+    ///
+    /// The `participants` will be sorted by their indices.
+    ///
+    /// # Returns
+    ///
+    /// An updated state machine for the distributed key generation protocol if
+    /// all of the zero-knowledge proofs verified successfully, otherwise a
+    /// vector of participants whose zero-knowledge proofs were incorrect.
+    /// A `WrongCommitmentCount` error designates a dealer which did not
+    /// publish exactly \\(t\\) commitments, and a `DuplicateDhPublicKey`
+    /// error two participants publishing the same DH public key.
+    pub fn new_initial(
+        parameters: &Parameters,
+        dh_private_key: &DHPrivateKey,
+        my_index: &u32,
+        my_coefficients: &Coefficients,
+        participants: &[Participant],
+        context_string: &str,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, DKGParticipantList), Error> {
+        Self::new_state_internal(
+            parameters,
+            Some(dh_private_key),
+            DHPublicKey::from(dh_private_key),
+            my_index,
+            Some(my_coefficients),
+            participants,
+            context_string,
+            true,
+            true,
+            None,
+            &mut rng,
+        )
+    }
+
+    /// Check the zero-knowledge proofs of knowledge of secret keys of all the
+    /// other participants. When a group key already exists and dealers have
+    /// distributed secret shares to a new set, participants of this new set
+    /// should call this method.
+    ///
+    /// # Note
+    ///
+    /// The `participants` will be sorted by their indices.
+    ///
+    /// # Returns
+    ///
+    /// An updated state machine for the distributed key generation protocol if
+    /// all of the zero-knowledge proofs verified successfully, otherwise a
+    /// vector of participants whose zero-knowledge proofs were incorrect.
+    /// A `WrongCommitmentCount` error designates a dealer which published
+    /// another number of commitments than most of the others.
+    pub fn new(
+        parameters: &Parameters,
+        dh_private_key: &DHPrivateKey,
+        my_index: &u32,
+        dealers: &[Participant],
+        context_string: &str,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, DKGParticipantList), Error> {
+        Self::new_state_internal(
+            parameters,
+            Some(dh_private_key),
+            DHPublicKey::from(dh_private_key),
+            my_index,
+            None,
+            dealers,
+            context_string,
+            false,
+            true,
+            None,
+            &mut rng,
+        )
+    }
+
+    /// Check the zero-knowledge proofs of knowledge of secret keys of all the
+    /// other participants, as with [`DistributedKeyGeneration::new_initial`],
+    /// for participants using long-term [`IdentityKey`]s across several
+    /// groups.
+    ///
+    /// The keys encrypting the secret shares are derived from the
+    /// Diffie-Hellman output of the identity keys and from the
+    /// `context_string`, which must be unique to the group, so that shares
+    /// dealt in one group can neither be decrypted nor replayed in another.
+    /// All the participants of the group must use this constructor.
+    ///
+    /// A state created this way cannot be restored from a [`DkgCheckpoint`],
+    /// which restores states with per-group DH keys.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_initial_with_identity_key(
+        parameters: &Parameters,
+        identity_key: &IdentityKey,
+        my_index: &u32,
+        my_coefficients: &Coefficients,
+        participants: &[Participant],
+        context_string: &str,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, DKGParticipantList), Error> {
+        Self::new_state_internal(
+            parameters,
+            Some(&identity_key.0),
+            identity_key.public_key(),
+            my_index,
+            Some(my_coefficients),
+            participants,
+            context_string,
+            true,
+            true,
+            Some(share_key_salt(context_string)),
+            &mut rng,
+        )
+    }
+
+    /// Check the zero-knowledge proofs of knowledge of secret keys of the
+    /// `dealers` of a resharing, as with [`DistributedKeyGeneration::new`],
+    /// for a participant of the new set using a long-term [`IdentityKey`].
+    ///
+    /// See [`DistributedKeyGeneration::new_initial_with_identity_key`] for how
+    /// the share keys are derived.
+    pub fn new_with_identity_key(
+        parameters: &Parameters,
+        identity_key: &IdentityKey,
+        my_index: &u32,
+        dealers: &[Participant],
+        context_string: &str,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, DKGParticipantList), Error> {
+        Self::new_state_internal(
+            parameters,
+            Some(&identity_key.0),
+            identity_key.public_key(),
+            my_index,
+            None,
+            dealers,
+            context_string,
+            false,
+            true,
+            Some(share_key_salt(context_string)),
+            &mut rng,
+        )
+    }
+
+    /// Check the zero-knowledge proofs of knowledge of secret keys of the
+    /// `dealers` of a resharing, as with [`DistributedKeyGeneration::new`],
+    /// for a participant of the new set whose DH key pair is held
+    /// externally, e.g. inside a hardware security module.
+    ///
+    /// The DH private key never enters this state: the shares are decrypted
+    /// by the `dh_key` in
+    /// [`DistributedKeyGeneration::to_round_two_with_external_dh_key`].  The
+    /// dealers learn of the participant through a [`DhKeyHandshake`], see
+    /// [`DkgSigner::new_from_public`].
+    pub fn new_with_external_dh_key(
+        parameters: &Parameters,
+        dh_key: &impl ExternalDhKey,
+        my_index: &u32,
+        dealers: &[Participant],
+        context_string: &str,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, DKGParticipantList), Error> {
+        Self::new_state_internal(
+            parameters,
+            None,
+            dh_key.dh_public_key(),
+            my_index,
+            None,
+            dealers,
+            context_string,
+            false,
+            true,
+            None,
+            &mut rng,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_state_internal<P: DkgParticipant>(
+        parameters: &Parameters,
+        dh_private_key: Option<&DHPrivateKey>,
+        dh_public_key: DHPublicKey,
+        my_index: &u32,
+        my_coefficients: Option<&Coefficients>,
+        participants: &[P],
+        context_string: &str,
+        from_dealer: bool,
+        from_signer: bool,
+        share_key_salt: Option<[u8; 32]>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, DKGParticipantList<P>), Error> {
+        let mut their_commitments: Vec<VerifiableSecretSharingCommitment> =
+            Vec::with_capacity(parameters.t as usize);
+        let mut their_dh_public_keys: Vec<(u32, DHPublicKey)> =
+            Vec::with_capacity(parameters.t as usize);
+        let mut their_share_versions: Vec<(u32, ShareVersion)> =
+            Vec::with_capacity(parameters.t as usize);
+        let mut valid_participants: Vec<P> = Vec::with_capacity(parameters.n as usize);
+        let mut misbehaving_participants: Vec<u32> = Vec::new();
+
+        // The share versions we advertised, if we are among the participants.
+        let share_versions = participants
+            .iter()
+            .find(|p| p.index() == *my_index)
+            .map_or(ShareVersion::SUPPORTED.to_vec(), |p| {
+                p.share_versions().to_vec()
+            });
+
+        // Bail if we didn't get enough participants.
+        if participants.len() != parameters.n as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                participants.len(),
+                parameters.n,
+            ));
+        }
+
+        // Shares are encrypted under the DH keys of their recipients, hence
+        // two participants sharing one could decrypt each other's shares.
+        check_unique_dh_public_keys(participants)?;
+
+        // A dealer committing to a polynomial of another degree would break
+        // the threshold.  The dealers of a resharing commit to polynomials of
+        // the degree of the new group, which is not known here, hence they
+        // must only agree with one another.
+        let dealer_commitments: Vec<&VerifiableSecretSharingCommitment> = participants
+            .iter()
+            .filter_map(|p| p.dealer())
+            .map(|dealer| &dealer.commitments)
+            .collect();
+        match from_dealer {
+            true => {
+                for commitments in dealer_commitments.iter() {
+                    commitments.check_count(parameters.t as usize)?;
+                }
+            }
+            false => check_commitment_counts_agree(&dealer_commitments)?,
+        }
+
+        // Check the public keys and the DH keys of the participants.
+        for p in participants.iter() {
+            // Always check the DH keys of the participants
+            match p
+                .proof_of_dh_private_key()
+                .verify(&p.index(), p.dh_public_key(), context_string)
+            {
+                Ok(_) => {
+                    // Signers additionally check the public keys of the signers
+                    if from_signer {
+                        let (dealer, public_key) = match p
+                            .dealer()
+                            .and_then(|dealer| Some((dealer, dealer.public_key()?)))
+                        {
+                            Some(dealer) => dealer,
+                            None => {
+                                misbehaving_participants.push(p.index());
+                                continue;
+                            }
+                        };
+                        match dealer.proof_of_secret_key.verify(
+                            &p.index(),
+                            public_key,
+                            context_string,
+                        ) {
+                            Ok(_) => {
+                                valid_participants.push(p.clone());
+                                their_commitments.push(dealer.commitments.clone());
+                                their_dh_public_keys.push((p.index(), p.dh_public_key().clone()));
+                                // A dealer sharing no version with us has
+                                // none, and all its shares are rejected.
+                                if let Some(version) =
+                                    ShareVersion::negotiate(p.share_versions(), &share_versions)
+                                {
+                                    their_share_versions.push((p.index(), version));
+                                }
+                            }
+                            Err(_) => misbehaving_participants.push(p.index()),
+                        }
+                    } else {
+                        valid_participants.push(p.clone());
+                        their_dh_public_keys.push((p.index(), p.dh_public_key().clone()));
+                    }
+                }
+                Err(_) => misbehaving_participants.push(p.index()),
+            }
+        }
+
+        // [DIFFERENT_TO_PAPER] If too many participants were misbehaving, return an error along their indices.
+        if valid_participants.len() < parameters.t as usize {
+            return Err(Error::TooManyInvalidParticipants(misbehaving_participants));
+        }
+
+        if !from_dealer && from_signer {
+            let state = ActualState {
+                parameters: *parameters,
+                index: *my_index,
+                dh_private_key: dh_private_key.map(DHPrivateKey::duplicate),
+                dh_public_key,
+                share_versions,
+                their_commitments: Some(their_commitments),
+                their_dh_public_keys,
+                their_share_versions,
+                their_encrypted_secret_shares: None,
+                my_secret_shares: None,
+                share_key_salt,
+            };
+
+            return Ok((
+                DistributedKeyGeneration::<RoundOne> {
+                    state: Box::new(state),
+                    data: RoundOne {},
+                },
+                DKGParticipantList {
+                    valid_participants,
+                    misbehaving_participants: if misbehaving_participants.is_empty() {
+                        None
+                    } else {
+                        Some(misbehaving_participants)
+                    },
+                },
+            ));
+        }
+
+        // [DIFFERENT_TO_PAPER] We pre-calculate the secret shares from Round 2
+        // Step 1 here since it doesn't require additional online activity.
+        // RICE-FROST: We also encrypt them into their_encrypted_secret_shares.
+        //
+        // Round 2
+        // Step 1: Each P_i securely sends to each other participant P_l a secret share
+        //         (l, f_i(l)) and keeps (i, f_i(i)) for themselves.
+        let dh_private_key = dh_private_key.ok_or(Error::ExternalDhKey)?;
+        let mut their_encrypted_secret_shares: Vec<EncryptedSecretShare> =
+            Vec::with_capacity(parameters.n as usize - 1);
+
+        // XXX need a way to index their_encrypted_secret_shares
+        for p in participants.iter() {
+            let share =
+                SecretShare::evaluate_polynomial(my_index, &p.index(), my_coefficients.unwrap());
+
+            let mut dh_output = (p.dh_public_key().0 * dh_private_key.0)
+                .compress()
+                .to_bytes();
+            let dh_key = share_key(&dh_output, share_key_salt.as_ref());
+            dh_output.zeroize();
+
+            // If the recipient accepts none of our versions, we still send
+            // our newest one, for the recipient to fail cleanly with an
+            // `UnsupportedShareVersion` error designating us.
+            let version = ShareVersion::negotiate(&share_versions, p.share_versions())
+                .or_else(|| share_versions.iter().max().copied())
+                .unwrap_or(ShareVersion::DEFAULT);
+
+            their_encrypted_secret_shares.push(encrypt_share(&share, &dh_key, version, &mut rng));
+        }
+
+        // A faulty random number generator is caught before any share is
+        // sent, rather than by the recipients.
+        if has_reused_nonce(their_encrypted_secret_shares.iter().map(|s| &s.nonce)) {
+            return Err(Error::NonceReuseByDealer { dealer: *my_index });
+        }
+
+        let state = ActualState {
+            parameters: *parameters,
+            index: *my_index,
+            dh_private_key: Some(dh_private_key.duplicate()),
+            dh_public_key,
+            share_versions,
+            their_commitments: if !from_signer {
+                None
+            } else {
+                Some(their_commitments)
+            },
+            their_dh_public_keys,
+            their_share_versions,
+            their_encrypted_secret_shares: Some(their_encrypted_secret_shares),
+            my_secret_shares: None,
+            share_key_salt,
+        };
+
+        Ok((
+            DistributedKeyGeneration::<RoundOne> {
+                state: Box::new(state),
+                data: RoundOne {},
+            },
+            DKGParticipantList {
+                valid_participants,
+                misbehaving_participants: if misbehaving_participants.is_empty() {
+                    None
+                } else {
+                    Some(misbehaving_participants)
+                },
+            },
+        ))
+    }
+
+    /// Retrieve an encrypted secret share for each other participant, to be given to them
+    /// at the end of `DistributedKeyGeneration::<RoundOne>`.
+    pub fn their_encrypted_secret_shares(&self) -> Result<&Vec<EncryptedSecretShare>, Error> {
+        self.state
+            .their_encrypted_secret_shares
+            .as_ref()
+            .ok_or(Error::NoEncryptedShares)
+    }
+
+    /// Retrieve the nonces under which our secret shares were encrypted, to
+    /// be broadcast to all participants along with the shares, so that they
+    /// can check with [`DistributedKeyGeneration::check_share_nonces`] that
+    /// no nonce was reused.
+    pub fn share_nonces(&self) -> Result<ShareNonceList, Error> {
+        Ok(ShareNonceList {
+            sender_index: self.state.index,
+            nonces: self
+                .their_encrypted_secret_shares()?
+                .iter()
+                .map(|share| (share.receiver_index, share.nonce))
+                .collect(),
+        })
+    }
+
+    /// Whether `coefficients` are the secret polynomial this participant
+    /// committed to.
+    fn is_committed_polynomial(&self, coefficients: &Coefficients) -> bool {
+        let commitment = self
+            .state
+            .their_commitments
+            .iter()
+            .flatten()
+            .find(|c| c.index == self.state.index);
+
+        match commitment {
+            Some(commitment) => {
+                commitment.points.len() == coefficients.0.len()
+                    && commitment.points.iter().zip(coefficients.0.iter()).all(
+                        |(point, coefficient)| *point == &RISTRETTO_BASEPOINT_TABLE * coefficient,
+                    )
+            }
+            None => false,
+        }
+    }
+
+    /// Produce a checkpoint of the secret state of this dealer, from which
+    /// round one can be resumed with [`DistributedKeyGeneration::restore_round_one`].
+    ///
+    /// # Inputs
+    ///
+    /// * The `my_coefficients` this state was created from with
+    ///   [`DistributedKeyGeneration::new_initial`].
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidDealerMaterial` error if this participant is not a dealer
+    /// of an initial key generation or if the coefficients are not those it
+    /// committed to.
+    pub fn checkpoint(&self, my_coefficients: &Coefficients) -> Result<DkgCheckpoint, Error> {
+        if self.state.their_encrypted_secret_shares.is_none()
+            || !self.is_committed_polynomial(my_coefficients)
+        {
+            return Err(Error::InvalidDealerMaterial);
+        }
+
+        Ok(DkgCheckpoint {
+            parameters: self.state.parameters,
+            index: self.state.index,
+            dh_private_key: self
+                .state
+                .dh_private_key
+                .as_ref()
+                .ok_or(Error::InvalidDealerMaterial)?
+                .duplicate(),
+            coefficients: my_coefficients.duplicate(),
+        })
+    }
+
+    /// Resume round one of an initial distributed key generation from a
+    /// `checkpoint`, with the same `participants` and `context_string` as
+    /// [`DistributedKeyGeneration::new_initial`] was given.
+    ///
+    /// The restored state is that of `new_initial`, except for the
+    /// encryptions of the secret shares, which use fresh nonces.  The shares
+    /// themselves are the same, since the polynomial must not change: shares
+    /// sent before the crash remain valid, and the new encryptions may be
+    /// resent to participants who did not receive them.
+    ///
+    /// # Returns
+    ///
+    /// The restored state, or an `InvalidDealerMaterial` error if the
+    /// published commitments of this dealer among the `participants` are not
+    /// to the polynomial of the checkpoint.
+    pub fn restore_round_one(
+        checkpoint: &DkgCheckpoint,
+        participants: &[Participant],
+        context_string: &str,
+        rng: impl RngCore + CryptoRng,
+    ) -> Result<(Self, DKGParticipantList), Error> {
+        let (state, participant_lists) = Self::new_initial(
+            &checkpoint.parameters,
+            &checkpoint.dh_private_key,
+            &checkpoint.index,
+            &checkpoint.coefficients,
+            participants,
+            context_string,
+            rng,
+        )?;
+
+        if !state.is_committed_polynomial(&checkpoint.coefficients) {
+            return Err(Error::InvalidDealerMaterial);
+        }
+
+        Ok((state, participant_lists))
+    }
+
+    /// Decrypt and verify one of our `encrypted_share`s, following steps 2.1
+    /// and 2.2 of round two.
+    ///
+    /// # Returns
+    ///
+    /// The decrypted share, `None` if its dealer is unknown, or a `Complaint`
+    /// error against its dealer if it does not decrypt or verify.
+    fn decrypt_and_verify_share(
+        &self,
+        encrypted_share: &EncryptedSecretShare,
+        decryption_key: ShareDecryptionKey<'_>,
+        rng: impl RngCore + CryptoRng,
+    ) -> Result<Option<SecretShare>, Error> {
+        // Step 2.1: Each P_i decrypts their shares with
+        //           key k_il = pk_l^sk_i
+        let pk = match self
+            .state
+            .their_dh_public_keys
+            .iter()
+            .find(|pk| pk.0 == encrypted_share.sender_index)
+        {
+            Some(pk) => pk,
+            None => return Ok(None),
+        };
+
+        // Only the version negotiated with the dealer is accepted, so that a
+        // share cannot be downgraded to another version we also support.  A
+        // share in any other version is reported as such, rather than as a
+        // misleading decryption failure.
+        let negotiated = self
+            .state
+            .their_share_versions
+            .iter()
+            .find(|(index, _)| *index == encrypted_share.sender_index)
+            .map(|(_, version)| *version);
+        if negotiated != Some(encrypted_share.version) {
+            return Err(Error::UnsupportedShareVersion {
+                dealer: encrypted_share.sender_index,
+                version: encrypted_share.version,
+            });
+        }
+
+        let mut dh_output = decryption_key.diffie_hellman(&pk.1);
+        let dh_key = share_key(&dh_output, self.state.share_key_salt.as_ref());
+
+        // Step 2.2: Each share is verified by calculating:
+        //           g^{f_l(i)} ?= \Prod_{k=0}^{t-1} \phi_{lk}^{i^{k} mod q},
+        //           creating a complaint if the check fails.
+        let decrypted_share = decrypt_share(encrypted_share, &dh_key);
+        let commitment = self
+            .state
+            .their_commitments
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|commitment| commitment.index == encrypted_share.sender_index);
+
+        let is_valid = match (&decrypted_share, commitment) {
+            (Ok(share), Some(commitment)) => share.verify(commitment).is_ok(),
+            (Ok(_), None) => true,
+            (Err(_), Some(_)) => false,
+            (Err(_), None) => true,
+        };
+
+        // If the decrypted share is incorrect, P_i builds a complaint
+        let result = if is_valid {
+            Ok(decrypted_share.ok())
+        } else {
+            let ciphertext_hash = encrypted_share.hash();
+            let proof = decryption_key.prove_complaint(&pk.1, &dh_output, &ciphertext_hash, rng);
+
+            Err(Error::Complaint(Vec::from([Complaint {
+                maker_index: encrypted_share.receiver_index,
+                accused_index: pk.0,
+                // The complaint reveals the DH output, which its proof is
+                // about, rather than the share key derived from it.
+                dh_key: dh_output,
+                ciphertext_hash,
+                proof,
+            }])))
+        };
+        dh_output.zeroize();
+
+        result
+    }
+
+    /// Check, before progressing to round two, that no dealer reused a nonce
+    /// across the shares it encrypted, from the broadcast `nonce_lists` of
+    /// the dealers and `my_encrypted_secret_shares`.
+    ///
+    /// Each recipient only sees its own share, hence the nonce listed for us
+    /// is replaced by that of the share we actually received, so that a
+    /// dealer cannot hide a reuse by broadcasting another nonce.
+    ///
+    /// # Returns
+    ///
+    /// A `NonceReuseByDealer` error designating the first dealer which
+    /// reused a nonce.
+    pub fn check_share_nonces(
+        &self,
+        nonce_lists: &[ShareNonceList],
+        my_encrypted_secret_shares: &[EncryptedSecretShare],
+    ) -> Result<(), Error> {
+        for list in nonce_lists.iter() {
+            let received = my_encrypted_secret_shares
+                .iter()
+                .find(|share| share.sender_index == list.sender_index)
+                .map(|share| (share.receiver_index, &share.nonce));
+            let nonces = list
+                .nonces
+                .iter()
+                .filter(|(receiver_index, _)| {
+                    received.map_or(true, |(index, _)| *receiver_index != index)
+                })
+                .map(|(_, nonce)| nonce)
+                .chain(received.map(|(_, nonce)| nonce));
+
+            if has_reused_nonce(nonces) {
+                return Err(Error::NonceReuseByDealer {
+                    dealer: list.sender_index,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Progress to round two of the DKG protocol once we have sent each encrypted share
+    /// from `DistributedKeyGeneration::<RoundOne>.their_encrypted_secret_shares()` to its
+    /// respective other participant, and collected our shares from the other
+    /// participants in turn.
+    ///
+    /// # Returns
+    ///
+    /// An `ExternalDhKey` error if our DH private key is held externally, in
+    /// which case [`DistributedKeyGeneration::to_round_two_with_external_dh_key`]
+    /// must be used instead.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_round_two(
+        self,
+        my_encrypted_secret_shares: Vec<EncryptedSecretShare>,
+        rng: impl RngCore + CryptoRng,
+    ) -> Result<DistributedKeyGeneration<RoundTwo>, Error> {
+        let dh_private_key = self
+            .state
+            .dh_private_key
+            .as_ref()
+            .ok_or(Error::ExternalDhKey)?
+            .duplicate();
+
+        self.to_round_two_internal(
+            my_encrypted_secret_shares,
+            ShareDecryptionKey::Local(&dh_private_key),
+            rng,
+        )
+    }
+
+    /// Progress to round two of the DKG protocol as with
+    /// [`DistributedKeyGeneration::to_round_two`], for a state created with
+    /// [`DistributedKeyGeneration::new_with_external_dh_key`], decrypting our
+    /// shares with the `dh_key` held externally.
+    ///
+    /// # Returns
+    ///
+    /// An `ExternalDhKey` error if `dh_key` is not the key of this state.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_round_two_with_external_dh_key(
+        self,
+        my_encrypted_secret_shares: Vec<EncryptedSecretShare>,
+        dh_key: &impl ExternalDhKey,
+    ) -> Result<DistributedKeyGeneration<RoundTwo>, Error> {
+        if dh_key.dh_public_key() != self.state.dh_public_key {
+            return Err(Error::ExternalDhKey);
+        }
+
+        // The external key proves complaints with its own randomness.
+        self.to_round_two_internal(
+            my_encrypted_secret_shares,
+            ShareDecryptionKey::External(dh_key),
+            OsRng,
+        )
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn to_round_two_internal(
+        mut self,
+        my_encrypted_secret_shares: Vec<EncryptedSecretShare>,
+        decryption_key: ShareDecryptionKey<'_>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<DistributedKeyGeneration<RoundTwo>, Error> {
+        // Zero out the other participants encrypted secret shares from memory.
+        if self.state.their_encrypted_secret_shares.is_some() {
+            self.state.their_encrypted_secret_shares.unwrap().zeroize();
+            // XXX Does setting this to None always call drop()?
+            self.state.their_encrypted_secret_shares = None;
+        }
+
+        // RICE-FROST
+
+        let mut complaints: Vec<Complaint> = Vec::new();
+
+        if my_encrypted_secret_shares.len() != self.state.parameters.n as usize {
+            return Err(Error::MissingShares);
+        }
+
+        let mut my_secret_shares: Vec<SecretShare> = Vec::new();
+
+        for encrypted_share in my_encrypted_secret_shares.iter() {
+            match self.decrypt_and_verify_share(encrypted_share, decryption_key, &mut rng) {
+                Ok(Some(share)) => my_secret_shares.push(share),
+                Ok(None) => (),
+                Err(Error::Complaint(mut complaint)) => complaints.append(&mut complaint),
+                Err(error) => return Err(error),
+            }
+        }
+
+        if !complaints.is_empty() {
+            return Err(Error::Complaint(complaints));
+        }
+
+        self.state.my_secret_shares = Some(my_secret_shares);
+
+        Ok(DistributedKeyGeneration::<RoundTwo> {
+            state: self.state,
+            data: RoundTwo {},
+        })
+    }
+
+    /// Incorporate one of our encrypted secret shares, as an alternative to
+    /// giving them all at once to [`DistributedKeyGeneration::to_round_two`],
+    /// so that shares can be verified as they arrive.
+    ///
+    /// The decrypted shares are kept in this state, and survive its
+    /// serialisation.  Once a share has been processed from every dealer,
+    /// round two is reached with [`DistributedKeyGeneration::complete_round_two`].
+    ///
+    /// # Returns
+    ///
+    /// A `DuplicateParticipant` or `UnknownParticipant` error if a share of
+    /// the dealer was already processed or if the dealer is unknown.  A
+    /// share which does not decrypt or verify is not incorporated, and the
+    /// complaint against its dealer is returned at once in a `Complaint`
+    /// error, so that it can be broadcast while other shares still arrive.
+    /// An `ExternalDhKey` error is returned if our DH private key is held
+    /// externally.
+    pub fn process_share(
+        &mut self,
+        my_encrypted_secret_share: EncryptedSecretShare,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(), Error> {
+        let dh_private_key = self
+            .state
+            .dh_private_key
+            .as_ref()
+            .ok_or(Error::ExternalDhKey)?
+            .duplicate();
+
+        let sender_index = my_encrypted_secret_share.sender_index;
+        if self
+            .state
+            .my_secret_shares
+            .iter()
+            .flatten()
+            .any(|share| share.sender_index == sender_index)
+        {
+            return Err(Error::DuplicateParticipant(sender_index));
+        }
+
+        let share = self
+            .decrypt_and_verify_share(
+                &my_encrypted_secret_share,
+                ShareDecryptionKey::Local(&dh_private_key),
+                &mut rng,
+            )?
+            .ok_or(Error::UnknownParticipant(sender_index))?;
+        self.state
+            .my_secret_shares
+            .get_or_insert_with(Vec::new)
+            .push(share);
+
+        Ok(())
+    }
+
+    /// Progress to round two of the DKG protocol once a share from every
+    /// dealer has been incorporated with [`DistributedKeyGeneration::process_share`].
+    ///
+    /// # Returns
+    ///
+    /// A `MissingShares` error if a share is missing.
+    pub fn complete_round_two(mut self) -> Result<DistributedKeyGeneration<RoundTwo>, Error> {
+        let processed = self.state.my_secret_shares.as_ref().map_or(0, Vec::len);
+        if processed != self.state.parameters.n as usize {
+            return Err(Error::MissingShares);
+        }
+
+        // Zero out the other participants encrypted secret shares from memory.
+        if let Some(mut shares) = self.state.their_encrypted_secret_shares.take() {
+            shares.zeroize();
+        }
+
+        Ok(DistributedKeyGeneration::<RoundTwo> {
+            state: self.state,
+            data: RoundTwo {},
+        })
+    }
+
+    /// Abandon this key generation, e.g. because the ceremony was aborted,
+    /// wiping the secrets of this state.
+    ///
+    /// # Returns
+    ///
+    /// An [`AbortRecord`] stating the `reason` of the abort, for the ceremony
+    /// log.
+    pub fn abandon(mut self, reason: &str) -> AbortRecord {
+        self.state.wipe();
+
+        AbortRecord::new(&self.state, 1, reason)
+    }
+
+    /// Serialise this DKG to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = self.state.to_bytes();
+        res.push(1u8);
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `DistributedKeyGeneration::<RoundOne>`
+    pub fn from_bytes(bytes: &[u8]) -> Result<DistributedKeyGeneration<RoundOne>, Error> {
+        let state = ActualState::from_bytes(bytes)?;
+        let data = if bytes[bytes.len() - 1] == 1 {
+            RoundOne {}
+        } else {
+            return Err(Error::SerialisationError);
+        };
+
+        Ok(DistributedKeyGeneration::<RoundOne> {
+            state: Box::new(state),
+            data,
+        })
+    }
+}
+
+/// A secret share calculated by evaluating a polynomial with secret
+/// coefficients for some indeterminant.
+#[derive(Debug, Eq, PartialEq, Zeroize)]
+#[zeroize(drop)]
+pub struct SecretShare {
+    /// The index of the share maker.
+    pub sender_index: u32,
+    /// The participant index that this secret share was calculated for.
+    pub receiver_index: u32,
+    /// The final evaluation of the polynomial for the participant-respective
+    /// indeterminant.
+    pub(crate) polynomial_evaluation: Scalar,
+}
+
+impl SecretShare {
+    /// Create another copy of this secret share, which is zeroed out when
+    /// dropped independently of this one.
+    pub(crate) fn duplicate(&self) -> SecretShare {
+        SecretShare {
+            sender_index: self.sender_index,
+            receiver_index: self.receiver_index,
+            polynomial_evaluation: self.polynomial_evaluation,
+        }
+    }
+
+    /// Evaluate the polynomial, `f(x)` for the secret coefficients at the value of `x`.
+    //
+    // XXX [PAPER] [CFRG] The participant index CANNOT be 0, or the secret share ends up being Scalar::zero().
+    pub(crate) fn evaluate_polynomial(
+        sender_index: &u32,
+        receiver_index: &u32,
+        coefficients: &Coefficients,
+    ) -> SecretShare {
+        // The index is lifted into the scalar field before any arithmetic, so
+        // that its powers never overflow, whatever the threshold.
+        let term: Scalar = (*receiver_index).into();
+        let mut sum: Scalar = Scalar::zero();
+
+        // Evaluate using Horner's method.
+        for (receiver_index, coefficient) in coefficients.0.iter().rev().enumerate() {
+            // The secret is the constant term in the polynomial
+            sum += coefficient;
+
+            if receiver_index != (coefficients.0.len() - 1) {
+                sum *= term;
+            }
+        }
+        SecretShare {
+            sender_index: *sender_index,
+            receiver_index: *receiver_index,
+            polynomial_evaluation: sum,
+        }
+    }
+
+    /// Verify that this secret share was correctly computed w.r.t. some secret
+    /// polynomial coefficients attested to by some `commitment`.
+    pub(crate) fn verify(
+        &self,
+        commitment: &VerifiableSecretSharingCommitment,
+    ) -> Result<(), Error> {
+        let lhs = &RISTRETTO_BASEPOINT_TABLE * &self.polynomial_evaluation;
+        let term: Scalar = self.receiver_index.into();
+        let mut rhs: RistrettoPoint = RistrettoPoint::identity();
+
+        for (index, com) in commitment.points.iter().rev().enumerate() {
+            rhs += com;
+
+            if index != (commitment.points.len() - 1) {
+                rhs *= term;
+            }
+        }
+
+        match lhs.compress() == rhs.compress() {
+            true => Ok(()),
+            false => Err(Error::ShareVerificationError),
+        }
+    }
+
+    /// Serialise this secret share to an array of bytes
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let mut res = [0u8; 40];
+        res[0..4].copy_from_slice(&self.sender_index.to_le_bytes());
+        res[4..8].copy_from_slice(&self.receiver_index.to_le_bytes());
+        res[8..40].copy_from_slice(&encode_scalar(&self.polynomial_evaluation));
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `SecretShare`
+    pub fn from_bytes(bytes: &[u8; 40]) -> Result<SecretShare, Error> {
+        let sender_index = u32::from_le_bytes(
+            bytes[0..4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+
+        let receiver_index = u32::from_le_bytes(
+            bytes[4..8]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+
+        let polynomial_evaluation = decode_scalar_canonical(
+            &bytes[8..40]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        Ok(SecretShare {
+            sender_index,
+            receiver_index,
+            polynomial_evaluation,
+        })
+    }
+}
+
+/// A secret share encrypted with a participant's public key
+#[derive(Clone, Debug, Eq, PartialEq, Zeroize)]
+#[zeroize(drop)]
+pub struct EncryptedSecretShare {
+    /// The index of the share maker.
+    pub sender_index: u32,
+    /// The participant index that this secret share was calculated for.
+    pub receiver_index: u32,
+    /// The nonce to be used for decryption with AES-CTR mode.
+    pub nonce: [u8; 16],
+    /// The encrypted polynomial evaluation.
+    pub(crate) encrypted_polynomial_evaluation: [u8; 32],
+    /// The format of this encrypted share.
+    #[zeroize(skip)]
+    pub version: ShareVersion,
+    /// The authentication tag of a [`ShareVersion::V1`] share, zero otherwise.
+    pub(crate) tag: [u8; 32],
+}
+
+// The legacy format holds the two indices, the nonce and the encrypted
+// polynomial evaluation, and the versioned format adds the version and tag.
+const _: () = assert!(EncryptedSecretShare::LEGACY_SERIALIZED_LEN == 4 + 4 + 16 + 32);
+const _: () = assert!(
+    EncryptedSecretShare::SERIALIZED_LEN == 1 + EncryptedSecretShare::LEGACY_SERIALIZED_LEN + 32
+);
+
+impl EncryptedSecretShare {
+    /// The length of a serialised [`ShareVersion::V0`] share, in bytes.
+    ///
+    /// The layout is:
+    ///
+    /// | bytes    | content                                    |
+    /// |----------|--------------------------------------------|
+    /// | `0..4`   | the index of the dealer, little-endian     |
+    /// | `4..8`   | the index of the recipient, little-endian  |
+    /// | `8..24`  | the AES-CTR nonce                          |
+    /// | `24..56` | the encrypted polynomial evaluation        |
+    pub const LEGACY_SERIALIZED_LEN: usize = 56;
+
+    /// The length of a serialised share of any later version, in bytes.
+    ///
+    /// The layout is:
+    ///
+    /// | bytes    | content                                    |
+    /// |----------|--------------------------------------------|
+    /// | `0`      | the version                                |
+    /// | `1..57`  | the legacy layout                          |
+    /// | `57..89` | the HMAC-SHA256 authentication tag         |
+    ///
+    /// where the legacy layout is the one described in
+    /// [`EncryptedSecretShare::LEGACY_SERIALIZED_LEN`].
+    pub const SERIALIZED_LEN: usize = 89;
+
+    /// The length of this encrypted secret share once serialised with
+    /// [`EncryptedSecretShare::to_bytes`], in bytes, including its
+    /// authentication tag if it has one.
+    pub fn serialized_len(&self) -> usize {
+        if self.is_legacy() {
+            EncryptedSecretShare::LEGACY_SERIALIZED_LEN
+        } else {
+            EncryptedSecretShare::SERIALIZED_LEN
+        }
+    }
+
+    /// A hash of this encrypted secret share, by which a [`Complaint`]
+    /// refers to it.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+        h.update(b"ICE-FROST-ENCRYPTED-SHARE");
+        h.update(self.to_bytes());
+
+        let mut output = [0u8; 32];
+        output.copy_from_slice(h.finalize().as_slice());
+        output
+    }
+
+    /// Serialise this encrypted secret share to a Vec of bytes
+    ///
+    /// A [`ShareVersion::V0`] share is serialised to the 56 bytes of the
+    /// legacy format.  A [`ShareVersion::V1`] share is serialised to 89
+    /// bytes: its version, the 56 bytes of the legacy format and its tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(self.serialized_len());
+        if !self.is_legacy() {
+            res.push(self.version.to_byte());
+        }
+        res.extend_from_slice(&self.sender_index.to_le_bytes());
+        res.extend_from_slice(&self.receiver_index.to_le_bytes());
+        res.extend_from_slice(&self.nonce);
+        res.extend_from_slice(&self.encrypted_polynomial_evaluation);
+        if !self.is_legacy() {
+            res.extend_from_slice(&self.tag);
+        }
+
+        res
+    }
+
+    /// Whether this share is in the legacy format.
+    #[cfg(not(feature = "strict"))]
+    fn is_legacy(&self) -> bool {
+        self.version == ShareVersion::V0
+    }
+
+    /// Whether this share is in the legacy format.
+    #[cfg(feature = "strict")]
+    fn is_legacy(&self) -> bool {
+        false
+    }
+
+    /// Deserialise this slice of bytes to a `EncryptedSecretShare`
+    ///
+    /// The format is recognised from the length of the slice, so that shares
+    /// in the legacy format can still be decoded, unless with the `strict`
+    /// feature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<EncryptedSecretShare, Error> {
+        let (version, legacy, tag) = match bytes.len() {
+            #[cfg(not(feature = "strict"))]
+            EncryptedSecretShare::LEGACY_SERIALIZED_LEN => (ShareVersion::V0, bytes, [0u8; 32]),
+            EncryptedSecretShare::SERIALIZED_LEN => (
+                ShareVersion::from_byte(bytes[0])?,
+                &bytes[1..57],
+                bytes[57..89]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            ),
+            _ => return Err(Error::SerialisationError),
+        };
+        #[cfg(not(feature = "strict"))]
+        if version == ShareVersion::V0 && bytes.len() != EncryptedSecretShare::LEGACY_SERIALIZED_LEN
+        {
+            return Err(Error::SerialisationError);
+        }
+
+        let sender_index = u32::from_le_bytes(
+            legacy[0..4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let receiver_index = u32::from_le_bytes(
+            legacy[4..8]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let nonce = legacy[8..24]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let encrypted_polynomial_evaluation = legacy[24..56]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+
+        Ok(EncryptedSecretShare {
+            sender_index,
+            receiver_index,
+            nonce,
+            encrypted_polynomial_evaluation,
+            version,
+            tag,
+        })
+    }
+}
+
+/// The nonces under which a dealer encrypted its secret shares, which are
+/// not secret and are broadcast so that recipients can detect their reuse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareNonceList {
+    /// The index of the dealer.
+    pub sender_index: u32,
+    /// The index of each recipient, with the nonce of its share.
+    pub nonces: Vec<(u32, [u8; 16])>,
+}
+
+impl ShareNonceList {
+    /// Serialise this nonce list to a Vec of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(8 + 20 * self.nonces.len());
+        res.extend_from_slice(&self.sender_index.to_le_bytes());
+        res.extend_from_slice(&(self.nonces.len() as u32).to_le_bytes());
+        for (receiver_index, nonce) in self.nonces.iter() {
+            res.extend_from_slice(&receiver_index.to_le_bytes());
+            res.extend_from_slice(nonce);
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `ShareNonceList`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ShareNonceList, Error> {
+        if bytes.len() < 8 {
+            return Err(Error::SerialisationError);
+        }
+        let sender_index = u32::from_le_bytes(
+            bytes[0..4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let len = u32::from_le_bytes(
+            bytes[4..8]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        ) as usize;
+        if (bytes.len() - 8) / 20 != len || (bytes.len() - 8) % 20 != 0 {
+            return Err(Error::SerialisationError);
+        }
+
+        let nonces = bytes[8..]
+            .chunks_exact(20)
+            .map(|chunk| {
+                let receiver_index = u32::from_le_bytes(
+                    chunk[0..4]
+                        .try_into()
+                        .map_err(|_| Error::SerialisationError)?,
+                );
+                let nonce = chunk[4..20]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?;
+                Ok((receiver_index, nonce))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(ShareNonceList {
+            sender_index,
+            nonces,
+        })
+    }
+}
+
+/// Whether some nonce appears more than once among `nonces`.
+fn has_reused_nonce<'a>(nonces: impl Iterator<Item = &'a [u8; 16]>) -> bool {
+    let mut nonces: Vec<&[u8; 16]> = nonces.collect();
+    nonces.sort_unstable();
+    nonces.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+/// A proof that a generated complaint is valid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComplaintProof {
+    /// a1 = g^r.
+    pub a1: RistrettoPoint,
+    /// a2 = pk_l^r.
+    pub a2: RistrettoPoint,
+    /// z = r + H(pk_i, pk_l, k_il).sh_i
+    pub z: Scalar,
+}
+
+impl ComplaintProof {
+    /// Prove that `dh_output` is the Diffie-Hellman output of
+    /// `dh_private_key` with `their_public_key`, for a complaint about the
+    /// share whose ciphertext hashes to `ciphertext_hash`.
+    pub fn prove(
+        dh_private_key: &DHPrivateKey,
+        their_public_key: &DHPublicKey,
+        dh_output: &[u8; 32],
+        ciphertext_hash: &[u8; 32],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> ComplaintProof {
+        let r = Scalar::random(&mut rng);
+
+        let a1 = &RISTRETTO_BASEPOINT_TABLE * &r;
+        let a2 = their_public_key.0 * r;
+
+        let h = complaint_challenge(
+            &(&RISTRETTO_BASEPOINT_TABLE * &dh_private_key.0),
+            &their_public_key.0,
+            dh_output,
+            ciphertext_hash,
+            &a1,
+            &a2,
+        );
+
+        ComplaintProof {
+            a1,
+            a2,
+            z: r + h * dh_private_key.0,
+        }
+    }
+
+    /// Serialise this complaint proof to an array of bytes
+    pub fn to_bytes(&self) -> [u8; 96] {
+        let mut res = [0u8; 96];
+        res[0..32].copy_from_slice(&encode_point(&self.a1));
+        res[32..64].copy_from_slice(&encode_point(&self.a2));
+        res[64..96].copy_from_slice(&encode_scalar(&self.z));
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `ComplaintProof`
+    pub fn from_bytes(bytes: &[u8; 96]) -> Result<ComplaintProof, Error> {
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes[0..32]);
+        let a1 = decode_point_checked(&array)?;
+
+        array.copy_from_slice(&bytes[32..64]);
+        let a2 = decode_point_checked(&array)?;
+
+        array.copy_from_slice(&bytes[64..96]);
+        let z = decode_scalar_canonical(&array)?;
+
+        Ok(ComplaintProof { a1, a2, z })
+    }
+}
+
+/// A complaint generated when a participant receives a bad share.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Complaint {
+    /// The index of the complaint maker.
+    pub maker_index: u32,
+    /// The index of the alleged misbehaving participant.
+    pub accused_index: u32,
+    /// The shared DH key.
+    pub dh_key: [u8; 32],
+    /// The hash of the encrypted share the complaint is about, as computed
+    /// by [`EncryptedSecretShare::hash`].
+    pub ciphertext_hash: [u8; 32],
+    /// The complaint proof.
+    pub proof: ComplaintProof,
+}
+
+/// The challenge of the proof of a complaint, which binds the disclosed key
+/// to the encrypted share the complaint is about.
+fn complaint_challenge(
+    pk_i: &RistrettoPoint,
+    pk_l: &RistrettoPoint,
+    dh_key: &[u8; 32],
+    ciphertext_hash: &[u8; 32],
+    a1: &RistrettoPoint,
+    a2: &RistrettoPoint,
+) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(pk_i.compress().to_bytes());
+    h.update(pk_l.compress().to_bytes());
+    h.update(dh_key);
+    h.update(ciphertext_hash);
+    h.update(a1.compress().to_bytes());
+    h.update(a2.compress().to_bytes());
+
+    Scalar::from_hash(h)
+}
+
+impl Complaint {
+    /// A complaint is valid if:
+    /// --  a1 + h.pk_i = z.g
+    /// --  a2 + h.k_il = z.pk_l
+    ///
+    /// Malformed evidence, i.e. a complaint against its own maker or a
+    /// disclosed key which is not the encoding of a point other than the
+    /// identity, is rejected before any group operation, so that the cost of
+    /// verifying a complaint is bounded by that of the two equations above.
+    pub fn verify(&self, pk_i: &RistrettoPoint, pk_l: &RistrettoPoint) -> Result<(), Error> {
+        if self.maker_index == self.accused_index {
+            return Err(Error::ComplaintVerificationError);
+        }
+
+        let key_as_point =
+            decode_point_checked(&self.dh_key).map_err(|_| Error::ComplaintVerificationError)?;
+        if key_as_point == RistrettoPoint::identity() {
+            return Err(Error::ComplaintVerificationError);
+        }
+
+        let h = complaint_challenge(
+            pk_i,
+            pk_l,
+            &self.dh_key,
+            &self.ciphertext_hash,
+            &self.proof.a1,
+            &self.proof.a2,
+        );
+
+        if self.proof.a1 + pk_i * h != &RISTRETTO_BASEPOINT_TABLE * &self.proof.z {
+            return Err(Error::ComplaintVerificationError);
+        }
+
+        if self.proof.a2 + key_as_point * h != pk_l * self.proof.z {
+            return Err(Error::ComplaintVerificationError);
+        }
+
+        Ok(())
+    }
+
+    /// Serialise this complaint to an array of bytes
+    pub fn to_bytes(&self) -> [u8; 168] {
+        let mut res = [0u8; 168];
+        res[0..4].copy_from_slice(&self.maker_index.to_le_bytes());
+        res[4..8].copy_from_slice(&self.accused_index.to_le_bytes());
+        res[8..40].copy_from_slice(&self.dh_key);
+        res[40..72].copy_from_slice(&self.ciphertext_hash);
+        res[72..168].copy_from_slice(&self.proof.to_bytes());
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `Complaint`
+    pub fn from_bytes(bytes: &[u8; 168]) -> Result<Complaint, Error> {
+        let maker_index = u32::from_le_bytes(
+            bytes[0..4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let accused_index = u32::from_le_bytes(
+            bytes[4..8]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let dh_key = bytes[8..40]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let ciphertext_hash = bytes[40..72]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let proof = ComplaintProof::from_bytes(
+            &bytes[72..168]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        Ok(Complaint {
+            maker_index,
+            accused_index,
+            dh_key,
+            ciphertext_hash,
+            proof,
+        })
+    }
+}
 
-/// Output of the first round of the Distributed Key Generation.
+/// During round two each participant verifies their secret shares they received
+/// from each other participant.
 #[derive(Clone, Debug)]
-pub struct DKGParticipantList {
-    /// List of the valid participants to be used in RoundTwo
-    pub valid_participants: Vec<Participant>,
-    /// List of the invalid participants that have been removed
-    pub misbehaving_participants: Option<Vec<u32>>,
-}
+pub struct RoundTwo {}
 
-impl DistributedKeyGeneration<RoundOne> {
-    /// Check the zero-knowledge proofs of knowledge of secret keys of all the
-    /// other participants. When no group key has been computed by a group of
-    /// participants yet, this method should be called rather than
-    /// `DistributedKeyGeneration<RoundOne>::new()`.
+impl DistributedKeyGeneration<RoundTwo> {
+    /// Calculate this threshold signing protocol participant's long-lived
+    /// secret signing keyshare and the group's public verification key.
     ///
-    /// # Note
+    /// # Example
     ///
-    /// The `participants` will be sorted by their indices.
+    /// ```ignore
+    /// let (group_key, secret_key) = state.finish()?;
+    /// ```
+    pub fn finish(mut self) -> Result<(GroupKey, SecretKey), Error> {
+        let secret_key = self.calculate_signing_key()?;
+        let group_key = self.calculate_group_key()?;
+
+        self.state.my_secret_shares.zeroize();
+
+        Ok((group_key, secret_key))
+    }
+
+    /// Finish the key generation as with
+    /// [`DistributedKeyGeneration::finish`], and sign a [`DkgReceipt`] of the
+    /// group key and the roster of participants seen by this participant with
+    /// its long-term `dh_private_key`, for audit trails.
     ///
     /// # Returns
     ///
-    /// An updated state machine for the distributed key generation protocol if
-    /// all of the zero-knowledge proofs verified successfully, otherwise a
-    /// vector of participants whose zero-knowledge proofs were incorrect.
-    pub fn new_initial(
-        parameters: &Parameters,
+    /// The same as [`DistributedKeyGeneration::finish`] along with the
+    /// receipt, or an `InvalidAttestation` error if `dh_private_key` is not
+    /// the one this participant ran the key generation with.
+    pub fn finish_with_receipt(
+        self,
         dh_private_key: &DHPrivateKey,
-        my_index: &u32,
-        my_coefficients: &Coefficients,
-        participants: &[Participant],
-        context_string: &str,
         mut rng: impl RngCore + CryptoRng,
-    ) -> Result<(Self, DKGParticipantList), Error> {
-        Self::new_state_internal(
+    ) -> Result<(GroupKey, SecretKey, DkgReceipt), Error> {
+        let index = self.state.index;
+        if &RISTRETTO_BASEPOINT_TABLE * &dh_private_key.0 != self.state.dh_public_key.0 {
+            return Err(Error::InvalidAttestation(index));
+        }
+        let parameters = self.state.parameters;
+        let mut roster = self.state.their_dh_public_keys.clone();
+        roster.sort_by_key(|(index, _)| *index);
+
+        let (group_key, secret_key) = self.finish()?;
+        let info = GroupPublicInfo {
             parameters,
-            dh_private_key,
-            my_index,
-            Some(my_coefficients),
-            participants,
-            context_string,
-            true,
-            true,
-            &mut rng,
+            group_key,
+            roster,
+        };
+        let receipt = DkgReceipt::sign(info, index, dh_private_key, &mut rng)?;
+
+        Ok((group_key, secret_key, receipt))
+    }
+
+    /// Finish the key generation as with
+    /// [`DistributedKeyGeneration::finish`], along with a [`RefreshProof`]
+    /// that the dealers reshared the group key they were given, for the
+    /// audit trail of a refresh or a resharing.
+    ///
+    /// The proof only verifies if the dealers were the participants of a
+    /// previous group, resharing their secret keys with
+    /// [`Participant::reshare`].
+    pub fn finish_with_refresh_proof(self) -> Result<(GroupKey, SecretKey, RefreshProof), Error> {
+        let commitments = self
+            .state
+            .their_commitments
+            .as_ref()
+            .ok_or(Error::InvalidRefreshProof)?;
+        let proof = RefreshProof::new(commitments);
+
+        let (group_key, secret_key) = self.finish()?;
+
+        Ok((group_key, secret_key, proof))
+    }
+
+    /// Calculate this threshold signing participant's long-lived secret signing
+    /// key by interpolating all of the polynomial evaluations from the other
+    /// participants.
+    pub(crate) fn calculate_signing_key(&self) -> Result<SecretKey, Error> {
+        let my_secret_shares = self.state.my_secret_shares.as_ref().ok_or_else(|| {
+            Error::Custom("Could not retrieve participant's secret shares".to_string())
+        })?;
+
+        let mut index_vector: Vec<u32> = Vec::new();
+
+        for share in my_secret_shares.iter() {
+            index_vector.push(share.sender_index);
+        }
+
+        let mut key = Scalar::zero();
+
+        for share in my_secret_shares.iter() {
+            let coeff = match calculate_lagrange_coefficients(&share.sender_index, &index_vector) {
+                Ok(s) => s,
+                Err(error) => return Err(Error::Custom(error.to_string())),
+            };
+            key += share.polynomial_evaluation * coeff;
+        }
+
+        Ok(SecretKey {
+            index: self.state.index,
+            key,
+        })
+    }
+
+    /// Calculate the group public key used for verifying threshold signatures.
+    ///
+    /// # Returns
+    ///
+    /// A [`GroupKey`] for the set of participants.
+    ///
+    /// my_commitment is needed for now, but won't be when the distinction
+    /// dealers/signers is implemented.
+    pub(crate) fn calculate_group_key(&self) -> Result<GroupKey, Error> {
+        let mut index_vector: Vec<u32> = Vec::new();
+
+        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
+            index_vector.push(commitment.index);
+        }
+
+        let mut group_key = RistrettoPoint::identity();
+
+        // The group key is the interpolation at 0 of all index 0 of the dealers' commitments.
+        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
+            let coeff = match calculate_lagrange_coefficients(&commitment.index, &index_vector) {
+                Ok(s) => s,
+                Err(error) => return Err(Error::Custom(error.to_string())),
+            };
+
+            group_key += coeff * commitment.public_key().unwrap();
+        }
+
+        Ok(GroupKey::new(group_key))
+    }
+
+    /// Every participant can verify a complaint and determine who is the malicious
+    /// party. The relevant encrypted share is assumed to exist and publicly retrievable
+    /// by any participant, e.g. by the `ciphertext_hash` of the complaint.
+    ///
+    /// A complaint which does not refer to the given encrypted share blames
+    /// its maker.
+    pub fn blame(&self, encrypted_share: &EncryptedSecretShare, complaint: &Complaint) -> u32 {
+        if encrypted_share.sender_index != complaint.accused_index
+            || encrypted_share.receiver_index != complaint.maker_index
+            || encrypted_share.hash() != complaint.ciphertext_hash
+        {
+            return complaint.maker_index;
+        }
+
+        let mut pk_maker = RistrettoPoint::identity();
+        let mut pk_accused = RistrettoPoint::identity();
+        let mut commitment_accused = VerifiableSecretSharingCommitment {
+            index: 0,
+            points: Vec::new(),
+        };
+
+        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
+            if commitment.index == complaint.accused_index {
+                commitment_accused = commitment.clone();
+            }
+        }
+
+        if commitment_accused.points.is_empty() {
+            return complaint.maker_index;
+        }
+
+        for (index, pk) in self.state.their_dh_public_keys.iter() {
+            if index == &complaint.maker_index {
+                pk_maker = **pk;
+            } else if index == &complaint.accused_index {
+                pk_accused = **pk;
+            }
+        }
+
+        if pk_maker == RistrettoPoint::identity() || pk_accused == RistrettoPoint::identity() {
+            return complaint.maker_index;
+        }
+
+        if complaint.verify(&pk_maker, &pk_accused).is_err() {
+            return complaint.maker_index;
+        }
+
+        let dh_key = share_key(&complaint.dh_key, self.state.share_key_salt.as_ref());
+        let share = decrypt_share(encrypted_share, &dh_key);
+        if share.is_err() {
+            return complaint.accused_index;
+        }
+        match share.unwrap().verify(&commitment_accused) {
+            Ok(()) => complaint.maker_index,
+            Err(_) => complaint.accused_index,
+        }
+    }
+
+    /// Abandon this key generation, e.g. because the ceremony was aborted,
+    /// wiping the secrets of this state.
+    ///
+    /// # Returns
+    ///
+    /// An [`AbortRecord`] stating the `reason` of the abort, for the ceremony
+    /// log.
+    pub fn abandon(mut self, reason: &str) -> AbortRecord {
+        self.state.wipe();
+
+        AbortRecord::new(&self.state, 2, reason)
+    }
+
+    /// Serialise this DKG to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = self.state.to_bytes();
+        res.push(2u8);
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `DistributedKeyGeneration::<RoundTwo>`
+    pub fn from_bytes(bytes: &[u8]) -> Result<DistributedKeyGeneration<RoundTwo>, Error> {
+        let state = ActualState::from_bytes(bytes)?;
+        let data = if bytes[bytes.len() - 1] == 2 {
+            RoundTwo {}
+        } else {
+            return Err(Error::SerialisationError);
+        };
+
+        Ok(DistributedKeyGeneration::<RoundTwo> {
+            state: Box::new(state),
+            data,
+        })
+    }
+}
+
+/// Adjudicates the complaints raised during a distributed key generation,
+/// processing at most a quota of complaints from each maker, so that a
+/// malicious participant cannot slow down adjudication for everyone by
+/// flooding it with complaints.
+///
+/// Complaints are attributed to the `maker_index` they carry, so they should
+/// be received over channels authenticating their maker.
+#[derive(Clone, Debug)]
+pub struct ComplaintAdjudicator<'a> {
+    dkg: &'a DistributedKeyGeneration<RoundTwo>,
+    quota: u32,
+    processed: BTreeMap<u32, u32>,
+    blamed: Vec<u32>,
+}
+
+impl<'a> ComplaintAdjudicator<'a> {
+    /// Create an adjudicator for the complaints of the key generation `dkg`.
+    ///
+    /// An honest participant complains at most once about each other
+    /// participant, so the quota of complaints of each maker is \(n - 1\).
+    pub fn new(dkg: &'a DistributedKeyGeneration<RoundTwo>) -> Self {
+        ComplaintAdjudicator {
+            dkg,
+            quota: dkg.state.parameters.n.saturating_sub(1),
+            processed: BTreeMap::new(),
+            blamed: Vec::new(),
+        }
+    }
+
+    /// Set the number of complaints adjudicated for each maker.
+    pub fn with_quota(mut self, quota: u32) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    /// Adjudicate a `complaint` about an `encrypted_share`, returning the
+    /// index of the participant to blame as [`DistributedKeyGeneration::blame`]
+    /// does.
+    ///
+    /// # Returns
+    ///
+    /// A `ComplaintQuotaExceeded` error if the quota of complaints of the
+    /// maker of the complaint is exhausted, in which case the complaint is
+    /// not looked at.
+    pub fn adjudicate(
+        &mut self,
+        encrypted_share: &EncryptedSecretShare,
+        complaint: &Complaint,
+    ) -> Result<u32, Error> {
+        let processed = self.processed.entry(complaint.maker_index).or_insert(0);
+        if *processed >= self.quota {
+            return Err(Error::ComplaintQuotaExceeded(complaint.maker_index));
+        }
+        *processed += 1;
+
+        let blamed = self.dkg.blame(encrypted_share, complaint);
+        if !self.blamed.contains(&blamed) {
+            self.blamed.push(blamed);
+        }
+
+        Ok(blamed)
+    }
+
+    /// The [`ResolutionRecord`] disqualifying every participant blamed by the
+    /// complaints adjudicated thus far, for the remaining participants to
+    /// co-sign.
+    pub fn resolution(&self) -> Result<ResolutionRecord, Error> {
+        ResolutionRecord::new(
+            self.dkg.state.parameters,
+            &self.dkg.state.their_dh_public_keys,
+            &self.blamed,
         )
     }
+}
+
+/// A public verification share for a participant.
+///
+/// Any participant can recalculate the public verification share, which is the
+/// public half of a [`SecretKey`], of any other participant in the protocol.
+#[derive(Clone, Debug)]
+pub struct IndividualPublicKey {
+    /// The participant index to which this key belongs.
+    pub index: u32,
+    /// The public verification share.
+    pub share: RistrettoPoint,
+    /// The compressed encoding of the share, when it is still current.
+    pub(crate) compressed: CompressedPoint,
+}
+
+impl Eq for IndividualPublicKey {}
+
+impl PartialEq for IndividualPublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.compressed_share() == other.compressed_share()
+    }
+}
+
+impl IndividualPublicKey {
+    /// Create the public verification share `share` of the participant with
+    /// the given `index`.
+    pub fn new(index: u32, share: RistrettoPoint) -> IndividualPublicKey {
+        IndividualPublicKey {
+            index,
+            share,
+            compressed: CompressedPoint::new(&share),
+        }
+    }
 
-    /// Check the zero-knowledge proofs of knowledge of secret keys of all the
-    /// other participants. When a group key already exists and dealers have
-    /// distributed secret shares to a new set, participants of this new set
-    /// should call this method.
+    /// The compressed encoding of the public verification share.
+    pub(crate) fn compressed_share(&self) -> CompressedRistretto {
+        self.compressed.get(&self.share)
+    }
+
+    /// Any participant can compute the public verification share of any other participant.
     ///
-    /// # Note
+    /// This is done by re-computing each [`IndividualPublicKey`] as \\(Y\_i\\) s.t.:
     ///
-    /// The `participants` will be sorted by their indices.
+    /// \\[
+    /// Y\_i = \prod\_{j=1}^{n} \prod\_{k=0}^{t-1} \phi\_{jk}^{i^{k} \mod q}
+    /// \\]
+    ///
+    /// for each [`Participant`] index \\(i\\).
+    ///
+    /// # Inputs
+    ///
+    /// * A vector of `commitments` regarding the secret polynomial
+    ///   [`Coefficients`] that this [`IndividualPublicKey`] was generated with.
     ///
     /// # Returns
     ///
-    /// An updated state machine for the distributed key generation protocol if
-    /// all of the zero-knowledge proofs verified successfully, otherwise a
-    /// vector of participants whose zero-knowledge proofs were incorrect.
-    pub fn new(
-        parameters: &Parameters,
-        dh_private_key: &DHPrivateKey,
-        my_index: &u32,
-        dealers: &[Participant],
-        context_string: &str,
-        mut rng: impl RngCore + CryptoRng,
-    ) -> Result<(Self, DKGParticipantList), Error> {
-        Self::new_state_internal(
-            parameters,
-            dh_private_key,
-            my_index,
-            None,
-            dealers,
-            context_string,
-            false,
-            true,
-            &mut rng,
-        )
-    }
-
-    #[allow(clippy::too_many_arguments)]
-    fn new_state_internal(
-        parameters: &Parameters,
-        dh_private_key: &DHPrivateKey,
-        my_index: &u32,
-        my_coefficients: Option<&Coefficients>,
-        participants: &[Participant],
-        context_string: &str,
-        from_dealer: bool,
-        from_signer: bool,
-        mut rng: impl RngCore + CryptoRng,
-    ) -> Result<(Self, DKGParticipantList), Error> {
-        let mut their_commitments: Vec<VerifiableSecretSharingCommitment> =
-            Vec::with_capacity(parameters.t as usize);
-        let mut their_dh_public_keys: Vec<(u32, DHPublicKey)> =
-            Vec::with_capacity(parameters.t as usize);
-        let mut valid_participants: Vec<Participant> = Vec::with_capacity(parameters.n as usize);
-        let mut misbehaving_participants: Vec<u32> = Vec::new();
-
-        let dh_public_key = DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * dh_private_key);
+    /// A `Result` with either an empty `Ok` or `Err` value, depending on
+    /// whether or not the verification was successful.
+    pub fn verify(&self, commitments: &[VerifiableSecretSharingCommitment]) -> Result<(), Error> {
+        let mut rhs: RistrettoPoint = RistrettoPoint::identity();
+        let term: Scalar = self.index.into();
 
-        // Bail if we didn't get enough participants.
-        if participants.len() != parameters.n as usize {
-            return Err(Error::InvalidNumberOfParticipants(
-                participants.len(),
-                parameters.n,
-            ));
+        let mut index_vector: Vec<u32> = Vec::new();
+        for commitment in commitments.iter() {
+            index_vector.push(commitment.index);
         }
 
-        // Check the public keys and the DH keys of the participants.
-        for p in participants.iter() {
-            // Always check the DH keys of the participants
-            match p
-                .proof_of_dh_private_key
-                .verify(&p.index, &p.dh_public_key, context_string)
-            {
-                Ok(_) => {
-                    // Signers additionally check the public keys of the signers
-                    if from_signer {
-                        let public_key = match p.public_key() {
-                            Some(key) => key,
-                            None => {
-                                misbehaving_participants.push(p.index);
-                                continue;
-                            }
-                        };
-                        match p.proof_of_secret_key.as_ref().unwrap().verify(
-                            &p.index,
-                            public_key,
-                            context_string,
-                        ) {
-                            Ok(_) => {
-                                valid_participants.push(p.clone());
-                                their_commitments.push(p.commitments.as_ref().unwrap().clone());
-                                their_dh_public_keys.push((p.index, p.dh_public_key.clone()));
-                            }
-                            Err(_) => misbehaving_participants.push(p.index),
-                        }
-                    } else {
-                        valid_participants.push(p.clone());
-                        their_dh_public_keys.push((p.index, p.dh_public_key.clone()));
-                    }
+        for commitment in commitments.iter() {
+            let mut tmp: RistrettoPoint = RistrettoPoint::identity();
+            for (index, com) in commitment.points.iter().rev().enumerate() {
+                tmp += com;
+
+                if index != (commitment.points.len() - 1) {
+                    tmp *= term;
                 }
-                Err(_) => misbehaving_participants.push(p.index),
             }
-        }
-
-        // [DIFFERENT_TO_PAPER] If too many participants were misbehaving, return an error along their indices.
-        if valid_participants.len() < parameters.t as usize {
-            return Err(Error::TooManyInvalidParticipants(misbehaving_participants));
-        }
 
-        if !from_dealer && from_signer {
-            let state = ActualState {
-                parameters: *parameters,
-                index: *my_index,
-                dh_private_key: dh_private_key.clone(),
-                dh_public_key,
-                their_commitments: Some(their_commitments),
-                their_dh_public_keys,
-                their_encrypted_secret_shares: None,
-                my_secret_shares: None,
+            let coeff = match calculate_lagrange_coefficients(&commitment.index, &index_vector) {
+                Ok(s) => s,
+                Err(error) => return Err(Error::Custom(error.to_string())),
             };
 
-            return Ok((
-                DistributedKeyGeneration::<RoundOne> {
-                    state: Box::new(state),
-                    data: RoundOne {},
-                },
-                DKGParticipantList {
-                    valid_participants,
-                    misbehaving_participants: if misbehaving_participants.is_empty() {
-                        None
-                    } else {
-                        Some(misbehaving_participants)
-                    },
-                },
-            ));
+            rhs += tmp * coeff;
         }
 
-        // [DIFFERENT_TO_PAPER] We pre-calculate the secret shares from Round 2
-        // Step 1 here since it doesn't require additional online activity.
-        // RICE-FROST: We also encrypt them into their_encrypted_secret_shares.
-        //
-        // Round 2
-        // Step 1: Each P_i securely sends to each other participant P_l a secret share
-        //         (l, f_i(l)) and keeps (i, f_i(i)) for themselves.
-        let mut their_encrypted_secret_shares: Vec<EncryptedSecretShare> =
-            Vec::with_capacity(parameters.n as usize - 1);
-
-        // XXX need a way to index their_encrypted_secret_shares
-        for p in participants.iter() {
-            let share =
-                SecretShare::evaluate_polynomial(my_index, &p.index, my_coefficients.unwrap());
+        match self.compressed_share() == rhs.compress() {
+            true => Ok(()),
+            false => Err(Error::ShareVerificationError),
+        }
+    }
 
-            let dh_key = (p.dh_public_key.0 * dh_private_key.0).compress().to_bytes();
+    /// Any participant can compute the public verification share of any other participant.
+    ///
+    /// This is done by re-computing each [`IndividualPublicKey`] as \\(Y\_i\\) s.t.:
+    ///
+    /// \\[
+    /// Y\_i = \prod\_{j=1}^{n} \prod\_{k=0}^{t-1} \phi\_{jk}^{i^{k} \mod q}
+    /// \\]
+    ///
+    /// for each [`Participant`] index \\(i\\).
+    ///
+    /// # Inputs
+    ///
+    /// * A `participant_index` and
+    /// * A vector of `commitments` regarding the secret polynomial
+    ///   [`Coefficients`] that the [`IndividualPublicKey`] will be generated from.
+    ///
+    /// # Returns
+    ///
+    /// An `IndividualPublicKey`.
+    pub fn generate_from_commitments(
+        participant_index: u32,
+        commitments: &[VerifiableSecretSharingCommitment],
+    ) -> Self {
+        let mut share: RistrettoPoint = RistrettoPoint::identity();
+        let term: Scalar = participant_index.into();
 
-            their_encrypted_secret_shares.push(encrypt_share(&share, &dh_key, &mut rng));
+        let mut index_vector: Vec<u32> = Vec::new();
+        for commitment in commitments.iter() {
+            index_vector.push(commitment.index);
         }
 
-        let state = ActualState {
-            parameters: *parameters,
-            index: *my_index,
-            dh_private_key: dh_private_key.clone(),
-            dh_public_key,
-            their_commitments: if !from_signer {
-                None
-            } else {
-                Some(their_commitments)
-            },
-            their_dh_public_keys,
-            their_encrypted_secret_shares: Some(their_encrypted_secret_shares),
-            my_secret_shares: None,
-        };
+        for commitment in commitments.iter() {
+            let mut tmp: RistrettoPoint = RistrettoPoint::identity();
+            for (index, com) in commitment.points.iter().rev().enumerate() {
+                tmp += com;
 
-        Ok((
-            DistributedKeyGeneration::<RoundOne> {
-                state: Box::new(state),
-                data: RoundOne {},
-            },
-            DKGParticipantList {
-                valid_participants,
-                misbehaving_participants: if misbehaving_participants.is_empty() {
-                    None
-                } else {
-                    Some(misbehaving_participants)
-                },
-            },
-        ))
-    }
+                if index != (commitment.points.len() - 1) {
+                    tmp *= term;
+                }
+            }
 
-    /// Retrieve an encrypted secret share for each other participant, to be given to them
-    /// at the end of `DistributedKeyGeneration::<RoundOne>`.
-    pub fn their_encrypted_secret_shares(&self) -> Result<&Vec<EncryptedSecretShare>, Error> {
-        self.state
-            .their_encrypted_secret_shares
-            .as_ref()
-            .ok_or(Error::NoEncryptedShares)
+            let coeff = calculate_lagrange_coefficients(&commitment.index, &index_vector).unwrap();
+            share += tmp * coeff;
+        }
+
+        IndividualPublicKey::new(participant_index, share)
     }
 
-    /// Progress to round two of the DKG protocol once we have sent each encrypted share
-    /// from `DistributedKeyGeneration::<RoundOne>.their_encrypted_secret_shares()` to its
-    /// respective other participant, and collected our shares from the other
-    /// participants in turn.
-    #[allow(clippy::wrong_self_convention)]
-    pub fn to_round_two(
-        mut self,
-        my_encrypted_secret_shares: Vec<EncryptedSecretShare>,
-        mut rng: impl RngCore + CryptoRng,
-    ) -> Result<DistributedKeyGeneration<RoundTwo>, Error> {
-        // Zero out the other participants encrypted secret shares from memory.
-        if self.state.their_encrypted_secret_shares.is_some() {
-            self.state.their_encrypted_secret_shares.unwrap().zeroize();
-            // XXX Does setting this to None always call drop()?
-            self.state.their_encrypted_secret_shares = None;
+    /// Reconstruct the [`GroupKey`] from the [`IndividualPublicKey`]s of a set
+    /// of participants, by Lagrange interpolation at \\(x = 0\\):
+    ///
+    /// \\[
+    /// Y = \prod\_{i \in S} Y\_i^{\lambda\_i}
+    /// \\]
+    ///
+    /// This can be used to cross-check the output of a distributed key
+    /// generation, as any \\(t\\) or more valid public keys yield the
+    /// same group key.
+    ///
+    /// # Inputs
+    ///
+    /// * The protocol instance [`Parameters`],
+    /// * A list of participant indices along with their `IndividualPublicKey`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` with the interpolated [`GroupKey`], or an `Error` if fewer
+    /// than \\(t\\) public keys were provided, if an index does not match its
+    /// public key, or if an index appears twice.
+    pub fn aggregate(
+        parameters: &Parameters,
+        public_keys: &[(u32, IndividualPublicKey)],
+    ) -> Result<GroupKey, Error> {
+        if public_keys.len() < parameters.t as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                public_keys.len(),
+                parameters.t,
+            ));
         }
 
-        // RICE-FROST
-
-        let mut complaints: Vec<Complaint> = Vec::new();
-
-        if my_encrypted_secret_shares.len() != self.state.parameters.n as usize {
-            return Err(Error::MissingShares);
+        let index_vector: Vec<u32> = public_keys.iter().map(|(index, _)| *index).collect();
+        let mut sorted_indices = index_vector.clone();
+        sorted_indices.sort_unstable();
+        sorted_indices.dedup();
+        if sorted_indices.len() != index_vector.len() {
+            return Err(Error::InvalidGroupKey);
         }
 
-        let mut my_secret_shares: Vec<SecretShare> = Vec::new();
+        let mut group_key: RistrettoPoint = RistrettoPoint::identity();
 
-        // Step 2.1: Each P_i decrypts their shares with
-        //           key k_il = pk_l^sk_i
-        for encrypted_share in my_encrypted_secret_shares.iter() {
-            for pk in self.state.their_dh_public_keys.iter() {
-                if pk.0 == encrypted_share.sender_index {
-                    let dh_key = (*pk.1 * self.state.dh_private_key.0).compress().to_bytes();
-
-                    // Step 2.2: Each share is verified by calculating:
-                    //           g^{f_l(i)} ?= \Prod_{k=0}^{t-1} \phi_{lk}^{i^{k} mod q},
-                    //           creating a complaint if the check fails.
-                    let decrypted_share = decrypt_share(encrypted_share, &dh_key);
-                    let decrypted_share_ref = &decrypted_share;
-
-                    for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
-                        if commitment.index == encrypted_share.sender_index {
-                            // If the decrypted share is incorrect, P_i builds
-                            // a complaint
-
-                            if decrypted_share.is_err()
-                                || decrypted_share_ref
-                                    .as_ref()
-                                    .unwrap()
-                                    .verify(commitment)
-                                    .is_err()
-                            {
-                                let r = Scalar::random(&mut rng);
-
-                                let a1 = &RISTRETTO_BASEPOINT_TABLE * &r;
-                                let a2 = *pk.1 * r;
-
-                                let mut h = Sha512::new();
-                                h.update(self.state.dh_public_key.compress().to_bytes());
-                                h.update(pk.1.compress().to_bytes());
-                                h.update(dh_key);
-                                h.update(a1.compress().to_bytes());
-                                h.update(a2.compress().to_bytes());
-
-                                let h = Scalar::from_hash(h);
-
-                                complaints.push(Complaint {
-                                    maker_index: encrypted_share.receiver_index,
-                                    accused_index: pk.0,
-                                    dh_key,
-                                    proof: ComplaintProof {
-                                        a1,
-                                        a2,
-                                        z: r + h * self.state.dh_private_key.0,
-                                    },
-                                });
-                                break;
-                            }
-                        }
-                    }
-                    if let Ok(share) = decrypted_share {
-                        my_secret_shares.push(share);
-                    }
-                }
+        for (index, public_key) in public_keys.iter() {
+            if *index != public_key.index {
+                return Err(Error::InvalidGroupKey);
             }
-        }
 
-        if !complaints.is_empty() {
-            return Err(Error::Complaint(complaints));
+            let coeff = calculate_lagrange_coefficients(index, &index_vector)
+                .map_err(|_| Error::InvalidGroupKey)?;
+            group_key += public_key.share * coeff;
         }
 
-        self.state.my_secret_shares = Some(my_secret_shares);
-
-        Ok(DistributedKeyGeneration::<RoundTwo> {
-            state: self.state,
-            data: RoundTwo {},
-        })
+        Ok(GroupKey::new(group_key))
     }
 
-    /// Serialise this DKG to a Vec of bytes
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut res = self.state.to_bytes();
-        res.push(1u8);
+    /// Serialise this individual public key to an array of bytes.
+    pub fn to_bytes(&self) -> [u8; 36] {
+        let mut res = [0u8; 36];
+        res[0..4].copy_from_slice(&self.index.to_le_bytes());
+        res[4..36].copy_from_slice(self.compressed_share().as_bytes());
 
         res
     }
 
-    /// Deserialise this slice of bytes to a `DistributedKeyGeneration::<RoundOne>`
-    pub fn from_bytes(bytes: &[u8]) -> Result<DistributedKeyGeneration<RoundOne>, Error> {
-        let state = ActualState::from_bytes(bytes)?;
-        let data = if bytes[bytes.len() - 1] == 1 {
-            RoundOne {}
-        } else {
-            return Err(Error::SerialisationError);
-        };
+    /// Deserialise this individual public key from an array of bytes.
+    pub fn from_bytes(bytes: &[u8; 36]) -> Result<IndividualPublicKey, Error> {
+        let index = u32::from_le_bytes(
+            bytes[0..4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
 
-        Ok(DistributedKeyGeneration::<RoundOne> {
-            state: Box::new(state),
-            data,
+        let compressed = CompressedRistretto(
+            bytes[4..36]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let share = decode_point_checked(compressed.as_bytes())?;
+
+        Ok(IndividualPublicKey {
+            index,
+            share,
+            compressed: CompressedPoint::from_parts(share, compressed),
         })
     }
 }
 
-/// A secret share calculated by evaluating a polynomial with secret
-/// coefficients for some indeterminant.
-#[derive(Clone, Debug, Eq, PartialEq, Zeroize)]
+/// A secret key, used by one participant in a threshold signature scheme, to sign a message.
+#[derive(Debug, Eq, PartialEq, Zeroize)]
 #[zeroize(drop)]
-pub struct SecretShare {
-    /// The index of the share maker.
-    pub sender_index: u32,
-    /// The participant index that this secret share was calculated for.
-    pub receiver_index: u32,
-    /// The final evaluation of the polynomial for the participant-respective
-    /// indeterminant.
-    pub(crate) polynomial_evaluation: Scalar,
+pub struct SecretKey {
+    /// The participant index to which this key belongs.
+    pub(crate) index: u32,
+    /// The participant's long-lived secret share of the group signing key.
+    pub(crate) key: Scalar,
 }
 
-impl SecretShare {
-    /// Evaluate the polynomial, `f(x)` for the secret coefficients at the value of `x`.
-    //
-    // XXX [PAPER] [CFRG] The participant index CANNOT be 0, or the secret share ends up being Scalar::zero().
-    pub(crate) fn evaluate_polynomial(
-        sender_index: &u32,
-        receiver_index: &u32,
-        coefficients: &Coefficients,
-    ) -> SecretShare {
-        let term: Scalar = (*receiver_index).into();
-        let mut sum: Scalar = Scalar::zero();
-
-        // Evaluate using Horner's method.
-        for (receiver_index, coefficient) in coefficients.0.iter().rev().enumerate() {
-            // The secret is the constant term in the polynomial
-            sum += coefficient;
-
-            if receiver_index != (coefficients.0.len() - 1) {
-                sum *= term;
-            }
-        }
-        SecretShare {
-            sender_index: *sender_index,
-            receiver_index: *receiver_index,
-            polynomial_evaluation: sum,
+impl SecretKey {
+    /// Create another copy of this secret key, which is zeroed out when
+    /// dropped independently of this one.
+    pub fn duplicate(&self) -> SecretKey {
+        SecretKey {
+            index: self.index,
+            key: self.key,
         }
     }
 
-    /// Verify that this secret share was correctly computed w.r.t. some secret
-    /// polynomial coefficients attested to by some `commitment`.
-    pub(crate) fn verify(
-        &self,
-        commitment: &VerifiableSecretSharingCommitment,
-    ) -> Result<(), Error> {
-        let lhs = &RISTRETTO_BASEPOINT_TABLE * &self.polynomial_evaluation;
-        let term: Scalar = self.receiver_index.into();
-        let mut rhs: RistrettoPoint = RistrettoPoint::identity();
-
-        for (index, com) in commitment.points.iter().rev().enumerate() {
-            rhs += com;
-
-            if index != (commitment.points.len() - 1) {
-                rhs *= term;
-            }
-        }
+    /// Derive the corresponding public key for this secret key.
+    pub fn to_public(&self) -> IndividualPublicKey {
+        let share = &RISTRETTO_BASEPOINT_TABLE * &self.key;
 
-        match lhs.compress() == rhs.compress() {
-            true => Ok(()),
-            false => Err(Error::ShareVerificationError),
-        }
+        IndividualPublicKey::new(self.index, share)
     }
 
-    /// Serialise this secret share to an array of bytes
-    pub fn to_bytes(&self) -> [u8; 40] {
-        let mut res = [0u8; 40];
-        res[0..4].copy_from_slice(&self.sender_index.to_le_bytes());
-        res[4..8].copy_from_slice(&self.receiver_index.to_le_bytes());
-        res[8..40].copy_from_slice(&self.polynomial_evaluation.to_bytes());
+    /// Serialise this secret key to an array of bytes.
+    pub fn to_bytes(&self) -> [u8; 36] {
+        let mut res = [0u8; 36];
+        res[0..4].copy_from_slice(&self.index.to_le_bytes());
+        res[4..36].copy_from_slice(&encode_scalar(&self.key));
 
         res
     }
 
-    /// Deserialise this slice of bytes to a `SecretShare`
-    pub fn from_bytes(bytes: &[u8; 40]) -> Result<SecretShare, Error> {
-        let sender_index = u32::from_le_bytes(
+    /// Deserialise this secret key from an array of bytes.
+    pub fn from_bytes(bytes: &[u8; 36]) -> Result<SecretKey, Error> {
+        let index = u32::from_le_bytes(
             bytes[0..4]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
 
-        let receiver_index = u32::from_le_bytes(
-            bytes[4..8]
+        let key = decode_scalar_canonical(
+            &bytes[4..36]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        );
+        )?;
 
-        let polynomial_evaluation = Scalar::from_canonical_bytes(
-            bytes[8..40]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        )
-        .ok_or(Error::SerialisationError)?;
+        Ok(SecretKey { index, key })
+    }
 
-        Ok(SecretShare {
-            sender_index,
-            receiver_index,
-            polynomial_evaluation,
+    /// Select `a` if `choice` is 0 and `b` if `choice` is 1, in constant
+    /// time, so that which key is selected does not leak through timing.
+    pub fn conditional_select(a: &SecretKey, b: &SecretKey, choice: Choice) -> SecretKey {
+        SecretKey {
+            index: u32::conditional_select(&a.index, &b.index, choice),
+            key: Scalar::conditional_select(&a.key, &b.key, choice),
+        }
+    }
+
+    /// Derive the secret key of a participant newly enrolled with
+    /// [`enroll_participant`].
+    ///
+    /// The derived share is checked against the public keys of at least
+    /// \\(t\\) existing signers, interpolated at the newcomer's index, so that
+    /// a helper sending a corrupted contribution, or a missing contribution,
+    /// is detected before the share is used.
+    ///
+    /// # Inputs
+    ///
+    /// * The protocol instance [`Parameters`] of the existing group,
+    /// * The newcomer's `index`,
+    /// * The newcomer's DH private key,
+    /// * The [`EnrollmentContribution`]s of all the helpers, and
+    /// * The [`IndividualPublicKey`]s of at least \\(t\\) existing signers.
+    ///
+    /// # Returns
+    ///
+    /// The newcomer's [`SecretKey`], or an `Error` if a contribution is not
+    /// addressed to this newcomer, could not be decrypted, or was given twice,
+    /// if fewer than \\(t\\) distinct public keys were given, or an
+    /// `InvalidEnrollmentShare` error if the derived share does not match the
+    /// public keys.
+    pub fn from_enrollment(
+        parameters: &Parameters,
+        index: u32,
+        dh_private_key: &DHPrivateKey,
+        contributions: &[EnrollmentContribution],
+        public_keys: &[IndividualPublicKey],
+    ) -> Result<SecretKey, Error> {
+        if contributions.is_empty() {
+            return Err(Error::MissingShares);
+        }
+        if public_keys.len() < parameters.t as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                public_keys.len(),
+                parameters.t,
+            ));
+        }
+        let index_vector: Vec<u32> = public_keys.iter().map(|pk| pk.index).collect();
+        for (i, public_key) in public_keys.iter().enumerate() {
+            if public_key.index == index || index_vector[..i].contains(&public_key.index) {
+                return Err(Error::DuplicateParticipant(public_key.index));
+            }
+        }
+
+        let mut key = Scalar::zero();
+        for (i, contribution) in contributions.iter().enumerate() {
+            let encrypted_share = &contribution.encrypted_share;
+            if encrypted_share.receiver_index != index {
+                return Err(Error::DecryptionError);
+            }
+            if contributions[..i]
+                .iter()
+                .any(|c| c.encrypted_share.sender_index == encrypted_share.sender_index)
+            {
+                return Err(Error::DuplicateParticipant(encrypted_share.sender_index));
+            }
+            if encrypted_share.version != ShareVersion::V1 {
+                return Err(Error::UnsupportedShareVersion {
+                    dealer: encrypted_share.sender_index,
+                    version: encrypted_share.version,
+                });
+            }
+
+            let mut dh_key = (contribution.ephemeral_dh_public_key.0 * dh_private_key.0)
+                .compress()
+                .to_bytes();
+            let share = decrypt_share(encrypted_share, &dh_key);
+            dh_key.zeroize();
+            key += share?.polynomial_evaluation;
+        }
+
+        let mut expected = RistrettoPoint::identity();
+        for public_key in public_keys.iter() {
+            let lambda = calculate_lagrange_coefficients_at(
+                &Scalar::from(index),
+                &public_key.index,
+                &index_vector,
+            )
+            .map_err(|e| Error::Custom(e.to_string()))?;
+            expected += public_key.share * lambda;
+        }
+
+        let secret_key = SecretKey { index, key };
+        if secret_key.to_public().share != expected {
+            return Err(Error::InvalidEnrollmentShare);
+        }
+
+        Ok(secret_key)
+    }
+
+    /// Refresh this secret key after the ejection of a participant, with the
+    /// [`EjectionContribution`]s of all the remaining signers from
+    /// [`eject_participant`].
+    ///
+    /// # Inputs
+    ///
+    /// * The protocol instance [`Parameters`],
+    /// * The DH private key of this signer,
+    /// * The indices and DH public keys of all the `remaining_signers`, as
+    ///   given to [`eject_participant`],
+    /// * The `contributions` of all the remaining signers, this one included.
+    ///
+    /// # Returns
+    ///
+    /// The refreshed [`SecretKey`], which must replace this one, or an `Error`
+    /// if a contribution is missing, given twice, made by an unknown signer,
+    /// or if its share could not be decrypted or does not match its
+    /// commitment to a polynomial of degree \\(t - 1\\) with a zero constant
+    /// term.
+    pub fn refresh_after_ejection(
+        &self,
+        parameters: &Parameters,
+        dh_private_key: &DHPrivateKey,
+        remaining_signers: &[(u32, DHPublicKey)],
+        contributions: &[EjectionContribution],
+    ) -> Result<SecretKey, Error> {
+        roster_dh_public_key(remaining_signers, self.index)?;
+        if let Some(unknown) = contributions.iter().find(|c| {
+            !remaining_signers
+                .iter()
+                .any(|(index, _)| *index == c.sender_index)
+        }) {
+            return Err(Error::UnknownParticipant(unknown.sender_index));
+        }
+
+        let mut key = self.key;
+        for (index, dh_public_key) in remaining_signers.iter() {
+            let mut matching = contributions.iter().filter(|c| c.sender_index == *index);
+            let contribution = matching.next().ok_or(Error::MissingShares)?;
+            if matching.next().is_some() {
+                return Err(Error::DuplicateParticipant(*index));
+            }
+
+            let commitment = &contribution.commitment;
+            commitment.check_count(parameters.t as usize)?;
+            if commitment.index != *index
+                || commitment.public_key() != Some(&RistrettoPoint::identity())
+            {
+                return Err(Error::ShareVerificationError);
+            }
+
+            let encrypted_share = contribution
+                .encrypted_shares
+                .iter()
+                .find(|s| s.receiver_index == self.index)
+                .ok_or(Error::MissingShares)?;
+            if encrypted_share.sender_index != *index {
+                return Err(Error::ShareVerificationError);
+            }
+            if encrypted_share.version != ShareVersion::V1 {
+                return Err(Error::UnsupportedShareVersion {
+                    dealer: *index,
+                    version: encrypted_share.version,
+                });
+            }
+
+            let mut dh_key = (dh_public_key.0 * dh_private_key.0).compress().to_bytes();
+            let share = decrypt_share(encrypted_share, &dh_key);
+            dh_key.zeroize();
+            let share = share?;
+            share.verify(commitment)?;
+            key += share.polynomial_evaluation;
+        }
+        Ok(SecretKey {
+            index: self.index,
+            key,
         })
     }
 }
 
-/// A secret share encrypted with a participant's public key
-#[derive(Clone, Debug, Eq, PartialEq, Zeroize)]
-#[zeroize(drop)]
-pub struct EncryptedSecretShare {
-    /// The index of the share maker.
-    pub sender_index: u32,
-    /// The participant index that this secret share was calculated for.
-    pub receiver_index: u32,
-    /// The nonce to be used for decryption with AES-CTR mode.
-    pub nonce: [u8; 16],
-    /// The encrypted polynomial evaluation.
-    pub(crate) encrypted_polynomial_evaluation: [u8; 32],
+/// Find the DH public key of the participant with the given `index` in a
+/// `roster` of participants taking part in an enrollment or an ejection,
+/// checking that the roster lists every participant once.
+fn roster_dh_public_key(roster: &[(u32, DHPublicKey)], index: u32) -> Result<&DHPublicKey, Error> {
+    for (i, (first, _)) in roster.iter().enumerate() {
+        if roster[..i].iter().any(|(other, _)| other == first) {
+            return Err(Error::DuplicateParticipant(*first));
+        }
+    }
+
+    roster
+        .iter()
+        .find(|(other, _)| *other == index)
+        .map(|(_, key)| key)
+        .ok_or(Error::UnknownParticipant(index))
 }
 
-impl EncryptedSecretShare {
-    /// Serialise this encrypted secret share to an array of bytes
-    pub fn to_bytes(&self) -> [u8; 56] {
-        let mut res = [0u8; 56];
-        res[0..4].copy_from_slice(&self.sender_index.to_le_bytes());
-        res[4..8].copy_from_slice(&self.receiver_index.to_le_bytes());
-        res[8..24].copy_from_slice(&self.nonce.clone());
-        res[24..56].copy_from_slice(&self.encrypted_polynomial_evaluation.clone());
+/// The pairwise mask shared by the helpers with indices `first` and `second`
+/// of the enrollment of a newcomer, derived from their Diffie-Hellman `dh_key`.
+fn enrollment_mask(
+    new_index: u32,
+    new_dh_public_key: &DHPublicKey,
+    first: u32,
+    second: u32,
+    dh_key: &[u8; 32],
+) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(b"ICE-FROST-ENROLLMENT-MASK");
+    h.update(new_index.to_le_bytes());
+    h.update(new_dh_public_key.to_bytes());
+    h.update(first.min(second).to_le_bytes());
+    h.update(first.max(second).to_le_bytes());
+    h.update(dh_key);
+
+    Scalar::from_hash(h)
+}
+
+/// The contribution of one existing signer to the enrollment of a newcomer,
+/// from [`enroll_participant`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnrollmentContribution {
+    /// The ephemeral DH public key under which the share is encrypted.
+    pub ephemeral_dh_public_key: DHPublicKey,
+    /// The masked contribution of the signer, encrypted to the newcomer.
+    pub encrypted_share: EncryptedSecretShare,
+}
+
+impl EnrollmentContribution {
+    /// Serialise this contribution to a Vec of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(32 + self.encrypted_share.serialized_len());
+        res.extend_from_slice(&self.ephemeral_dh_public_key.to_bytes());
+        res.extend_from_slice(&self.encrypted_share.to_bytes());
 
         res
     }
-
-    /// Deserialise this slice of bytes to a `EncryptedSecretShare`
-    pub fn from_bytes(bytes: &[u8; 56]) -> Result<EncryptedSecretShare, Error> {
-        let sender_index = u32::from_le_bytes(
-            bytes[0..4]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        );
-        let receiver_index = u32::from_le_bytes(
-            bytes[4..8]
+
+    /// Deserialise this slice of bytes to an `EnrollmentContribution`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<EnrollmentContribution, Error> {
+        let ephemeral_dh_public_key = DHPublicKey::from_bytes(
+            bytes
+                .get(0..32)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        );
-        let nonce = bytes[8..24]
-            .try_into()
-            .map_err(|_| Error::SerialisationError)?;
-        let encrypted_polynomial_evaluation = bytes[24..56]
-            .try_into()
-            .map_err(|_| Error::SerialisationError)?;
+        )?;
+        let encrypted_share = EncryptedSecretShare::from_bytes(&bytes[32..])?;
 
-        Ok(EncryptedSecretShare {
-            sender_index,
-            receiver_index,
-            nonce,
-            encrypted_polynomial_evaluation,
+        Ok(EnrollmentContribution {
+            ephemeral_dh_public_key,
+            encrypted_share,
         })
     }
 }
 
-/// A proof that a generated complaint is valid.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct ComplaintProof {
-    /// a1 = g^r.
-    pub a1: RistrettoPoint,
-    /// a2 = pk_l^r.
-    pub a2: RistrettoPoint,
-    /// z = r + H(pk_i, pk_l, k_il).sh_i
-    pub z: Scalar,
+/// Contribute to the enrollment of a new participant into an existing group
+/// of signers, without changing the group key.
+///
+/// At least \\(t\\) existing signers, the `helpers`, each evaluate their part
+/// of the group's secret polynomial at the newcomer's index by Lagrange
+/// interpolation, so that
+///
+/// \\[
+/// s\_k = \sum\_{i \in S} \lambda\_{i}(k) s\_i
+/// \\]
+///
+/// Each contribution is additionally blinded by a mask derived from the
+/// Diffie-Hellman keys the helper shares with every other helper, the masks
+/// of all the helpers summing to zero, so that the newcomer only learns
+/// \\(s\_k\\) and not the individual existing shares.  Each helper only uses
+/// its own share, and the newcomer combines the contributions with
+/// [`SecretKey::from_enrollment`], which checks the result against the
+/// public keys of the group.
+///
+/// The other signers keep their shares, and the threshold is unchanged.
+///
+/// # Inputs
+///
+/// * The protocol instance [`Parameters`] of the existing group,
+/// * The `new_index` of the newcomer,
+/// * The [`SecretKey`] and DH private key of this helper,
+/// * The indices and DH public keys of all the `helpers`, this one included,
+///   each of which must contribute,
+/// * The newcomer's DH public key.
+///
+/// # Returns
+///
+/// The [`EnrollmentContribution`] of this helper, to be sent to the newcomer.
+pub fn enroll_participant(
+    parameters: &Parameters,
+    new_index: u32,
+    secret_key: &SecretKey,
+    dh_private_key: &DHPrivateKey,
+    helpers: &[(u32, DHPublicKey)],
+    new_dh_public_key: &DHPublicKey,
+    mut rng: impl RngCore + CryptoRng,
+) -> Result<EnrollmentContribution, Error> {
+    if helpers.len() < parameters.t as usize {
+        return Err(Error::InvalidNumberOfParticipants(
+            helpers.len(),
+            parameters.t,
+        ));
+    }
+    roster_dh_public_key(helpers, secret_key.index)?;
+    if new_index == 0 || roster_dh_public_key(helpers, new_index).is_ok() {
+        return Err(Error::DuplicateParticipant(new_index));
+    }
+
+    let index_vector: Vec<u32> = helpers.iter().map(|(index, _)| *index).collect();
+    let lambda = calculate_lagrange_coefficients_at(
+        &Scalar::from(new_index),
+        &secret_key.index,
+        &index_vector,
+    )
+    .map_err(|e| Error::Custom(e.to_string()))?;
+
+    let mut mask = Scalar::zero();
+    for (index, dh_public_key) in helpers.iter() {
+        if *index == secret_key.index {
+            continue;
+        }
+        let mut dh_key = (dh_public_key.0 * dh_private_key.0).compress().to_bytes();
+        let pairwise_mask = enrollment_mask(
+            new_index,
+            new_dh_public_key,
+            secret_key.index,
+            *index,
+            &dh_key,
+        );
+        dh_key.zeroize();
+        match secret_key.index < *index {
+            true => mask += pairwise_mask,
+            false => mask -= pairwise_mask,
+        }
+    }
+
+    let ephemeral_dh_private_key = DHPrivateKey(Scalar::random(&mut rng));
+    let ephemeral_dh_public_key = DHPublicKey::from(&ephemeral_dh_private_key);
+    let mut dh_key = (new_dh_public_key.0 * ephemeral_dh_private_key.0)
+        .compress()
+        .to_bytes();
+
+    let share = SecretShare {
+        sender_index: secret_key.index,
+        receiver_index: new_index,
+        polynomial_evaluation: lambda * secret_key.key + mask,
+    };
+    let encrypted_share = encrypt_share(&share, &dh_key, ShareVersion::V1, &mut rng);
+    dh_key.zeroize();
+    mask.zeroize();
+
+    Ok(EnrollmentContribution {
+        ephemeral_dh_public_key,
+        encrypted_share,
+    })
 }
 
-impl ComplaintProof {
-    /// Serialise this complaint proof to an array of bytes
-    pub fn to_bytes(&self) -> [u8; 96] {
-        let mut res = [0u8; 96];
-        res[0..32].copy_from_slice(&self.a1.compress().to_bytes());
-        res[32..64].copy_from_slice(&self.a2.compress().to_bytes());
-        res[64..96].copy_from_slice(&self.z.to_bytes());
+/// The contribution of one remaining signer to the ejection of a
+/// participant, from [`eject_participant`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EjectionContribution {
+    /// The index of the signer making this contribution.
+    pub sender_index: u32,
+    /// The commitment to the refresh polynomial of the signer, whose
+    /// constant term is zero.
+    pub commitment: VerifiableSecretSharingCommitment,
+    /// The evaluations of the refresh polynomial, encrypted to each of the
+    /// remaining signers.
+    pub encrypted_shares: Vec<EncryptedSecretShare>,
+}
+
+impl EjectionContribution {
+    /// Serialise this contribution to a Vec of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::new();
+        res.extend_from_slice(&self.sender_index.to_le_bytes());
+        let commitment = self.commitment.to_bytes();
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(commitment.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        res.extend_from_slice(&commitment);
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.encrypted_shares.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for encrypted_share in self.encrypted_shares.iter() {
+            let bytes = encrypted_share.to_bytes();
+            res.push(TryInto::<u8>::try_into(bytes.len()).unwrap());
+            res.extend_from_slice(&bytes);
+        }
 
         res
     }
 
-    /// Deserialise this slice of bytes to a `ComplaintProof`
-    pub fn from_bytes(bytes: &[u8; 96]) -> Result<ComplaintProof, Error> {
-        let mut array = [0u8; 32];
-        array.copy_from_slice(&bytes[0..32]);
-        let a1 = CompressedRistretto(array)
-            .decompress()
-            .ok_or(Error::SerialisationError)?;
+    /// Deserialise this slice of bytes to an `EjectionContribution`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<EjectionContribution, Error> {
+        let read_u32 = |index_slice: usize| -> Result<u32, Error> {
+            Ok(u32::from_le_bytes(
+                bytes
+                    .get(index_slice..index_slice + 4)
+                    .ok_or(Error::SerialisationError)?
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            ))
+        };
 
-        array.copy_from_slice(&bytes[32..64]);
-        let a2 = CompressedRistretto(array)
-            .decompress()
-            .ok_or(Error::SerialisationError)?;
+        let sender_index = read_u32(0)?;
+        let commitment_len = read_u32(4)? as usize;
+        let mut index_slice = 8usize;
+        let commitment = VerifiableSecretSharingCommitment::from_bytes(
+            bytes
+                .get(index_slice..)
+                .and_then(|b| b.get(..commitment_len))
+                .ok_or(Error::SerialisationError)?,
+        )?;
+        index_slice += commitment_len;
 
-        array.copy_from_slice(&bytes[64..96]);
-        let z = Scalar::from_canonical_bytes(array).ok_or(Error::SerialisationError)?;
+        let len = read_u32(index_slice)?;
+        index_slice += 4;
+        // Each share takes at least its length and the legacy layout.
+        let remaining = bytes.len() - index_slice;
+        let mut encrypted_shares = Vec::with_capacity(
+            (len as usize).min(remaining / (1 + EncryptedSecretShare::LEGACY_SERIALIZED_LEN)),
+        );
+        for _ in 0..len {
+            let share_len = *bytes.get(index_slice).ok_or(Error::SerialisationError)? as usize;
+            index_slice += 1;
+            encrypted_shares.push(EncryptedSecretShare::from_bytes(
+                bytes
+                    .get(index_slice..index_slice + share_len)
+                    .ok_or(Error::SerialisationError)?,
+            )?);
+            index_slice += share_len;
+        }
+        if index_slice != bytes.len() {
+            return Err(Error::SerialisationError);
+        }
 
-        Ok(ComplaintProof { a1, a2, z })
+        Ok(EjectionContribution {
+            sender_index,
+            commitment,
+            encrypted_shares,
+        })
     }
 }
 
-/// A complaint generated when a participant receives a bad share.
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Complaint {
-    /// The index of the complaint maker.
-    pub maker_index: u32,
-    /// The index of the alleged misbehaving participant.
-    pub accused_index: u32,
-    /// The shared DH key.
-    pub dh_key: [u8; 32],
-    /// The complaint proof.
-    pub proof: ComplaintProof,
-}
+/// Contribute to the ejection of a participant from a group of signers,
+/// without changing the group key.
+///
+/// Each remaining signer deals the evaluations of a random polynomial of
+/// degree \\(t - 1\\) with a zero constant term to all the remaining signers,
+/// encrypted to their DH public keys, along with a commitment to its
+/// coefficients.  This does not involve the share of the signer at all.
+/// Each remaining signer then adds the evaluations it received from all the
+/// others to its share with [`SecretKey::refresh_after_ejection`].  The
+/// refreshed shares still interpolate to the same group secret key, but the
+/// ejected participant's old share is no longer on the same polynomial, and
+/// hence cannot be combined with any refreshed share anymore.
+///
+/// # Inputs
+///
+/// * The protocol instance [`Parameters`],
+/// * The `ejected_index` of the participant to eject,
+/// * The index and DH private key of this signer,
+/// * The indices and DH public keys of all the `remaining_signers`, at least
+///   \\(t\\) of them, this one included.
+///
+/// # Returns
+///
+/// The [`EjectionContribution`] of this signer, to be sent to every
+/// remaining signer.
+pub fn eject_participant(
+    parameters: &Parameters,
+    ejected_index: u32,
+    my_index: u32,
+    dh_private_key: &DHPrivateKey,
+    remaining_signers: &[(u32, DHPublicKey)],
+    mut rng: impl RngCore + CryptoRng,
+) -> Result<EjectionContribution, Error> {
+    if remaining_signers.len() < parameters.t as usize {
+        return Err(Error::InvalidNumberOfParticipants(
+            remaining_signers.len(),
+            parameters.t,
+        ));
+    }
+    roster_dh_public_key(remaining_signers, my_index)?;
+    if roster_dh_public_key(remaining_signers, ejected_index).is_ok() {
+        return Err(Error::Custom(
+            "The ejected participant is among the remaining signers".to_string(),
+        ));
+    }
 
-impl Complaint {
-    /// A complaint is valid if:
-    /// --  a1 + h.pk_i = z.g
-    /// --  a2 + h.k_il = z.pk_l
-    pub fn verify(&self, pk_i: &RistrettoPoint, pk_l: &RistrettoPoint) -> Result<(), Error> {
-        let mut h = Sha512::new();
-        h.update(pk_i.compress().to_bytes());
-        h.update(pk_l.compress().to_bytes());
-        h.update(self.dh_key);
-        h.update(self.proof.a1.compress().to_bytes());
-        h.update(self.proof.a2.compress().to_bytes());
+    let mut coefficients: Vec<Scalar> = Vec::with_capacity(parameters.t as usize);
+    coefficients.push(Scalar::zero());
+    for _ in 1..parameters.t {
+        coefficients.push(Scalar::random(&mut rng));
+    }
+    let coefficients = Coefficients(coefficients);
 
-        let h = Scalar::from_hash(h);
+    let commitment = VerifiableSecretSharingCommitment {
+        index: my_index,
+        points: coefficients
+            .0
+            .iter()
+            .map(|coefficient| &RISTRETTO_BASEPOINT_TABLE * coefficient)
+            .collect(),
+    };
+
+    let encrypted_shares = remaining_signers
+        .iter()
+        .map(|(index, dh_public_key)| {
+            let share = SecretShare::evaluate_polynomial(&my_index, index, &coefficients);
+            let mut dh_key = (dh_public_key.0 * dh_private_key.0).compress().to_bytes();
+            let encrypted_share = encrypt_share(&share, &dh_key, ShareVersion::V1, &mut rng);
+            dh_key.zeroize();
+            encrypted_share
+        })
+        .collect();
 
-        if self.proof.a1 + pk_i * h != &RISTRETTO_BASEPOINT_TABLE * &self.proof.z {
-            return Err(Error::ComplaintVerificationError);
-        }
+    Ok(EjectionContribution {
+        sender_index: my_index,
+        commitment,
+        encrypted_shares,
+    })
+}
 
-        if let Some(key_as_point) = CompressedRistretto::from_slice(&self.dh_key).decompress() {
-            if self.proof.a2 + key_as_point * h != pk_l * self.proof.z {
-                return Err(Error::ComplaintVerificationError);
-            }
-        } else {
-            return Err(Error::ComplaintVerificationError);
-        }
+/// The state of one of the two signers of a 2-of-2 group during a
+/// [`refresh_two_party`] of their shares.
+pub struct TwoPartyRefresh {
+    group_key: GroupKey,
+    secret_key: SecretKey,
+    delta: Scalar,
+    delta_commitment: RistrettoPoint,
+    peer_index: u32,
+    dh_key: [u8; 32],
+}
 
-        Ok(())
+impl Drop for TwoPartyRefresh {
+    fn drop(&mut self) {
+        self.delta.zeroize();
+        self.dh_key.zeroize();
     }
+}
 
-    /// Serialise this complaint to an array of bytes
-    pub fn to_bytes(&self) -> [u8; 136] {
-        let mut res = [0u8; 136];
-        res[0..4].copy_from_slice(&self.maker_index.to_le_bytes());
-        res[4..8].copy_from_slice(&self.accused_index.to_le_bytes());
-        res[8..40].copy_from_slice(&self.dh_key.clone());
-        res[40..136].copy_from_slice(&self.proof.to_bytes());
+/// The single message sent by each of the two signers of a 2-of-2 group to
+/// the other one during a [`refresh_two_party`] of their shares.
+///
+/// This message contains the random contribution of its sender to the
+/// refresh, encrypted to the DH public key of the other signer in the same
+/// way as the shares of a distributed key generation, along with a public
+/// commitment to it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TwoPartyRefreshMessage {
+    /// The index of the signer sending this message.
+    pub index: u32,
+    /// The random contribution of the sender to the refresh, encrypted to
+    /// the other signer.
+    pub(crate) encrypted_delta: EncryptedSecretShare,
+    /// The commitment \\(\delta \cdot G\\) to the contribution of the sender.
+    pub(crate) delta_commitment: RistrettoPoint,
+    /// A proof of knowledge of the current share of the sender, bound to its
+    /// encrypted contribution and to its commitment.
+    pub(crate) proof: NizkOfSecretKey,
+}
+
+impl TwoPartyRefreshMessage {
+    /// The length of a serialised [`TwoPartyRefreshMessage`], in bytes.
+    ///
+    /// The layout is:
+    ///
+    /// | bytes     | content                                    |
+    /// |-----------|--------------------------------------------|
+    /// | `0..4`     | the index of the sender, little-endian     |
+    /// | `4..93`    | the encrypted contribution of the sender   |
+    /// | `93..125`  | the commitment to the contribution         |
+    /// | `125..189` | the proof of knowledge of the sender       |
+    pub const SERIALIZED_LEN: usize = 4 + EncryptedSecretShare::SERIALIZED_LEN + 32 + 64;
+
+    /// Serialise this message to an array of bytes.
+    pub fn to_bytes(&self) -> [u8; TwoPartyRefreshMessage::SERIALIZED_LEN] {
+        let mut res = [0u8; TwoPartyRefreshMessage::SERIALIZED_LEN];
+        res[0..4].copy_from_slice(&self.index.to_le_bytes());
+        res[4..93].copy_from_slice(&self.encrypted_delta.to_bytes());
+        res[93..125].copy_from_slice(&encode_point(&self.delta_commitment));
+        res[125..189].copy_from_slice(&self.proof.to_bytes());
 
         res
     }
 
-    /// Deserialise this slice of bytes to a `Complaint`
-    pub fn from_bytes(bytes: &[u8; 136]) -> Result<Complaint, Error> {
-        let maker_index = u32::from_le_bytes(
+    /// Deserialise this slice of bytes to a [`TwoPartyRefreshMessage`].
+    ///
+    /// The contribution must be encrypted with [`ShareVersion::V1`], and
+    /// sent by the signer of index `index`.
+    pub fn from_bytes(
+        bytes: &[u8; TwoPartyRefreshMessage::SERIALIZED_LEN],
+    ) -> Result<TwoPartyRefreshMessage, Error> {
+        let index = u32::from_le_bytes(
             bytes[0..4]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
-        let accused_index = u32::from_le_bytes(
-            bytes[4..8]
+        let encrypted_delta = EncryptedSecretShare::from_bytes(&bytes[4..93])?;
+        if encrypted_delta.version != ShareVersion::V1 || encrypted_delta.sender_index != index {
+            return Err(Error::SerialisationError);
+        }
+        let delta_commitment = decode_point_checked(
+            bytes[93..125]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        );
-        let dh_key = bytes[8..40]
-            .try_into()
-            .map_err(|_| Error::SerialisationError)?;
-        let proof = ComplaintProof::from_bytes(
-            &bytes[40..136]
+        )?;
+        let proof = NizkOfSecretKey::from_bytes(
+            bytes[125..189]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         )?;
 
-        Ok(Complaint {
-            maker_index,
-            accused_index,
-            dh_key,
+        Ok(TwoPartyRefreshMessage {
+            index,
+            encrypted_delta,
+            delta_commitment,
             proof,
         })
     }
 }
 
-/// During round two each participant verifies their secret shares they received
-/// from each other participant.
-#[derive(Clone, Debug)]
-pub struct RoundTwo {}
-
-impl DistributedKeyGeneration<RoundTwo> {
-    /// Calculate this threshold signing protocol participant's long-lived
-    /// secret signing keyshare and the group's public verification key.
-    ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// let (group_key, secret_key) = state.finish()?;
-    /// ```
-    pub fn finish(mut self) -> Result<(GroupKey, SecretKey), Error> {
-        let secret_key = self.calculate_signing_key()?;
-        let group_key = self.calculate_group_key()?;
-
-        self.state.my_secret_shares.zeroize();
-
-        Ok((group_key, secret_key))
-    }
-
-    /// Calculate this threshold signing participant's long-lived secret signing
-    /// key by interpolating all of the polynomial evaluations from the other
-    /// participants.
-    pub(crate) fn calculate_signing_key(&self) -> Result<SecretKey, Error> {
-        let my_secret_shares = self.state.my_secret_shares.as_ref().ok_or_else(|| {
-            Error::Custom("Could not retrieve participant's secret shares".to_string())
-        })?;
-
-        let mut index_vector: Vec<u32> = Vec::new();
-
-        for share in my_secret_shares.iter() {
-            index_vector.push(share.sender_index);
-        }
-
-        let mut key = Scalar::zero();
-
-        for share in my_secret_shares.iter() {
-            let coeff = match calculate_lagrange_coefficients(&share.sender_index, &index_vector) {
-                Ok(s) => s,
-                Err(error) => return Err(Error::Custom(error.to_string())),
-            };
-            key += share.polynomial_evaluation * coeff;
-        }
-
-        Ok(SecretKey {
-            index: self.state.index,
-            key,
-        })
+/// The context string of the proofs of knowledge of a two-party refresh,
+/// binding them to the encrypted contribution of their prover and to its
+/// commitment.
+fn two_party_refresh_context(
+    encrypted_delta: &EncryptedSecretShare,
+    delta_commitment: &RistrettoPoint,
+) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let mut context_string = String::with_capacity(155);
+    context_string.push_str("ICE-FROST-TWO-PARTY-REFRESH");
+    for byte in encrypted_delta
+        .hash()
+        .iter()
+        .chain(encode_point(delta_commitment).iter())
+    {
+        context_string.push(HEX[(byte >> 4) as usize] as char);
+        context_string.push(HEX[(byte & 0xf) as usize] as char);
     }
+    context_string
+}
 
-    /// Calculate the group public key used for verifying threshold signatures.
+impl TwoPartyRefresh {
+    /// Start refreshing the `secret_key` of one of the two signers of a
+    /// 2-of-2 group, without changing its `group_key`.
     ///
-    /// # Returns
+    /// The random contribution of this signer is encrypted to the
+    /// `peer_dh_public_key` of the other signer, of index `peer_index`,
+    /// with a key derived from this signer's `dh_private_key`.
     ///
-    /// A [`GroupKey`] for the set of participants.
+    /// # Returns
     ///
-    /// my_commitment is needed for now, but won't be when the distinction
-    /// dealers/signers is implemented.
-    pub(crate) fn calculate_group_key(&self) -> Result<GroupKey, Error> {
-        let mut index_vector: Vec<u32> = Vec::new();
-
-        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
-            index_vector.push(commitment.index);
-        }
-
-        let mut group_key = RistrettoPoint::identity();
-
-        // The group key is the interpolation at 0 of all index 0 of the dealers' commitments.
-        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
-            let coeff = match calculate_lagrange_coefficients(&commitment.index, &index_vector) {
-                Ok(s) => s,
-                Err(error) => return Err(Error::Custom(error.to_string())),
-            };
-
-            group_key += coeff * commitment.public_key().unwrap();
-        }
-
-        Ok(GroupKey(group_key))
-    }
-
-    /// Every participant can verify a complaint and determine who is the malicious
-    /// party. The relevant encrypted share is assumed to exist and publicly retrievable
-    /// by any participant.
-    pub fn blame(&self, encrypted_share: &EncryptedSecretShare, complaint: &Complaint) -> u32 {
-        let mut pk_maker = RistrettoPoint::identity();
-        let mut pk_accused = RistrettoPoint::identity();
-        let mut commitment_accused = VerifiableSecretSharingCommitment {
-            index: 0,
-            points: Vec::new(),
-        };
-
-        for commitment in self.state.their_commitments.as_ref().unwrap().iter() {
-            if commitment.index == complaint.accused_index {
-                commitment_accused = commitment.clone();
-            }
-        }
-
-        if commitment_accused.points.is_empty() {
-            return complaint.maker_index;
-        }
-
-        for (index, pk) in self.state.their_dh_public_keys.iter() {
-            if index == &complaint.maker_index {
-                pk_maker = **pk;
-            } else if index == &complaint.accused_index {
-                pk_accused = **pk;
-            }
+    /// The state of this signer, to be given to [`refresh_two_party`], and
+    /// the [`TwoPartyRefreshMessage`] to send to the other signer.
+    pub fn new(
+        parameters: &Parameters,
+        group_key: &GroupKey,
+        secret_key: &SecretKey,
+        dh_private_key: &DHPrivateKey,
+        peer_index: u32,
+        peer_dh_public_key: &DHPublicKey,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(TwoPartyRefresh, TwoPartyRefreshMessage), Error> {
+        if parameters.n != 2 || parameters.t != 2 {
+            return Err(Error::Custom(
+                "The two-party refresh requires a 2-of-2 group".to_string(),
+            ));
         }
-
-        if pk_maker == RistrettoPoint::identity() || pk_accused == RistrettoPoint::identity() {
-            return complaint.maker_index;
+        if peer_index == secret_key.index {
+            return Err(Error::DuplicateParticipant(peer_index));
         }
 
-        if complaint.verify(&pk_maker, &pk_accused).is_err() {
-            return complaint.maker_index;
-        }
+        let delta = Scalar::random(&mut rng);
+        let dh_key = (peer_dh_public_key.0 * dh_private_key.0)
+            .compress()
+            .to_bytes();
+        let share = SecretShare {
+            sender_index: secret_key.index,
+            receiver_index: peer_index,
+            polynomial_evaluation: delta,
+        };
+        let encrypted_delta = encrypt_share(&share, &dh_key, ShareVersion::V1, &mut rng);
+        let delta_commitment = &RISTRETTO_BASEPOINT_TABLE * &delta;
 
-        let share = decrypt_share(encrypted_share, &complaint.dh_key);
-        if share.is_err() {
-            return complaint.accused_index;
-        }
-        match share.unwrap().verify(&commitment_accused) {
-            Ok(()) => complaint.maker_index,
-            Err(_) => complaint.accused_index,
-        }
+        let proof = NizkOfSecretKey::prove(
+            &secret_key.index,
+            &secret_key.key,
+            &secret_key.to_public().share,
+            &two_party_refresh_context(&encrypted_delta, &delta_commitment),
+            &mut rng,
+        );
+
+        let message = TwoPartyRefreshMessage {
+            index: secret_key.index,
+            encrypted_delta,
+            delta_commitment,
+            proof,
+        };
+        let state = TwoPartyRefresh {
+            group_key: *group_key,
+            secret_key: secret_key.duplicate(),
+            delta,
+            delta_commitment,
+            peer_index,
+            dh_key,
+        };
+
+        Ok((state, message))
     }
+}
 
-    /// Serialise this DKG to a Vec of bytes
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut res = self.state.to_bytes();
-        res.push(2u8);
+/// Refresh the share of one of the two signers of a 2-of-2 group in a single
+/// round, without changing the group key.
+///
+/// This is the [`eject_participant`] refresh specialised to two signers: the
+/// shares are refreshed by adding the evaluations of the polynomial
+/// \\(f(x) = (\delta\_1 + \delta\_2) \cdot x\\), where each \\(\delta\_i\\) is
+/// the random contribution of one signer, so that each signer only needs the
+/// single [`TwoPartyRefreshMessage`] of the other one.
+///
+/// Each signer verifies the other one by recomputing its current public
+/// share from the group key and its own share, and checking the proof of
+/// knowledge of the corresponding secret share.  This ensures that both
+/// signers apply the refresh to the two halves of the same group key.
+///
+/// Each message also commits publicly to the contribution of its sender,
+/// and the decrypted contribution is checked against that commitment, so
+/// that a signer cannot encrypt another contribution than the one it
+/// commits to.  Both refreshed public shares are then derived from the two
+/// commitments alone, so that the refreshed public share returned for the
+/// other signer is the one it obtains if it follows its own commitment, and
+/// the refreshed share of this signer is checked to match its public share.
+///
+/// # Inputs
+///
+/// * The `state` of this signer, from [`TwoPartyRefresh::new`],
+/// * The [`TwoPartyRefreshMessage`] of the other signer.
+///
+/// # Returns
+///
+/// The refreshed [`SecretKey`] of this signer, which must replace the old
+/// one, along with the refreshed [`IndividualPublicKey`] of the other signer.
+pub fn refresh_two_party(
+    state: TwoPartyRefresh,
+    peer_message: &TwoPartyRefreshMessage,
+) -> Result<(SecretKey, IndividualPublicKey), Error> {
+    let index = state.secret_key.index;
+    if peer_message.index == index {
+        return Err(Error::DuplicateParticipant(index));
+    }
+    if peer_message.index != state.peer_index {
+        return Err(Error::UnknownParticipant(peer_message.index));
+    }
+    if peer_message.encrypted_delta.sender_index != peer_message.index
+        || peer_message.encrypted_delta.receiver_index != index
+        || peer_message.encrypted_delta.version != ShareVersion::V1
+    {
+        return Err(Error::DecryptionError);
+    }
 
-        res
+    let indices = [index, peer_message.index];
+    let lambda = calculate_lagrange_coefficients(&index, &indices)
+        .map_err(|error| Error::Custom(error.to_string()))?;
+    let peer_lambda = calculate_lagrange_coefficients(&peer_message.index, &indices)
+        .map_err(|error| Error::Custom(error.to_string()))?;
+
+    // The group key is the Lagrange interpolation at zero of the two public
+    // shares, from which we recover the current public share of the peer.
+    let public_share = state.secret_key.to_public().share;
+    let peer_public_share = (state.group_key.0 - public_share * lambda) * peer_lambda.invert();
+
+    peer_message.proof.verify(
+        &peer_message.index,
+        &peer_public_share,
+        &two_party_refresh_context(
+            &peer_message.encrypted_delta,
+            &peer_message.delta_commitment,
+        ),
+    )?;
+
+    let peer_delta = decrypt_share(&peer_message.encrypted_delta, &state.dh_key)?;
+    if &RISTRETTO_BASEPOINT_TABLE * &peer_delta.polynomial_evaluation
+        != peer_message.delta_commitment
+    {
+        return Err(Error::ShareVerificationError);
+    }
+    let mut slope = state.delta + peer_delta.polynomial_evaluation;
+
+    let refreshed = SecretKey {
+        index,
+        key: state.secret_key.key + slope * Scalar::from(index),
+    };
+    slope.zeroize();
+
+    // The refreshed public shares, from the commitments of both signers.
+    let slope_commitment = state.delta_commitment + peer_message.delta_commitment;
+    let peer_refreshed = IndividualPublicKey::new(
+        peer_message.index,
+        peer_public_share + slope_commitment * Scalar::from(peer_message.index),
+    );
+    if refreshed.to_public().share != public_share + slope_commitment * Scalar::from(index) {
+        return Err(Error::InvalidRefreshProof);
     }
 
-    /// Deserialise this slice of bytes to a `DistributedKeyGeneration::<RoundTwo>`
-    pub fn from_bytes(bytes: &[u8]) -> Result<DistributedKeyGeneration<RoundTwo>, Error> {
-        let state = ActualState::from_bytes(bytes)?;
-        let data = if bytes[bytes.len() - 1] == 2 {
-            RoundTwo {}
-        } else {
-            return Err(Error::SerialisationError);
-        };
+    Ok((refreshed, peer_refreshed))
+}
 
-        Ok(DistributedKeyGeneration::<RoundTwo> {
-            state: Box::new(state),
-            data,
-        })
+impl From<&SecretKey> for IndividualPublicKey {
+    fn from(source: &SecretKey) -> IndividualPublicKey {
+        source.to_public()
     }
 }
 
-/// A public verification share for a participant.
+/// A point along with its compressed encoding, so that serialising or
+/// comparing the same point repeatedly only compresses it once.
 ///
-/// Any participant can recalculate the public verification share, which is the
-/// public half of a [`SecretKey`], of any other participant in the protocol.
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct IndividualPublicKey {
-    /// The participant index to which this key belongs.
-    pub index: u32,
-    /// The public verification share.
-    pub share: RistrettoPoint,
+/// The encoding is only served for the point it was computed from.  Checking
+/// that is much cheaper than a compression, and keeps the encoding correct for
+/// points which are modified after construction, e.g. through the public
+/// fields of an [`IndividualPublicKey`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CompressedPoint {
+    point: RistrettoPoint,
+    compressed: CompressedRistretto,
 }
 
-impl IndividualPublicKey {
-    /// Any participant can compute the public verification share of any other participant.
-    ///
-    /// This is done by re-computing each [`IndividualPublicKey`] as \\(Y\_i\\) s.t.:
-    ///
-    /// \\[
-    /// Y\_i = \prod\_{j=1}^{n} \prod\_{k=0}^{t-1} \phi\_{jk}^{i^{k} \mod q}
-    /// \\]
-    ///
-    /// for each [`Participant`] index \\(i\\).
-    ///
-    /// # Inputs
-    ///
-    /// * A vector of `commitments` regarding the secret polynomial
-    ///   [`Coefficients`] that this [`IndividualPublicKey`] was generated with.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` with either an empty `Ok` or `Err` value, depending on
-    /// whether or not the verification was successful.
-    pub fn verify(&self, commitments: &[VerifiableSecretSharingCommitment]) -> Result<(), Error> {
-        let mut rhs: RistrettoPoint = RistrettoPoint::identity();
-        let term: Scalar = self.index.into();
-
-        let mut index_vector: Vec<u32> = Vec::new();
-        for commitment in commitments.iter() {
-            index_vector.push(commitment.index);
+impl CompressedPoint {
+    /// Compress `point` and remember its encoding.
+    pub(crate) fn new(point: &RistrettoPoint) -> CompressedPoint {
+        CompressedPoint {
+            point: *point,
+            compressed: point.compress(),
         }
+    }
 
-        for commitment in commitments.iter() {
-            let mut tmp: RistrettoPoint = RistrettoPoint::identity();
-            for (index, com) in commitment.points.iter().rev().enumerate() {
-                tmp += com;
+    /// Remember an already known `compressed` encoding of `point`, e.g. the
+    /// bytes it was just decompressed from.
+    pub(crate) fn from_parts(
+        point: RistrettoPoint,
+        compressed: CompressedRistretto,
+    ) -> CompressedPoint {
+        CompressedPoint { point, compressed }
+    }
 
-                if index != (commitment.points.len() - 1) {
-                    tmp *= term;
-                }
-            }
+    /// The compressed encoding of `point`, recomputed only if `point` is not
+    /// the point this encoding was computed from.
+    pub(crate) fn get(&self, point: &RistrettoPoint) -> CompressedRistretto {
+        match self.point == *point {
+            true => self.compressed,
+            false => point.compress(),
+        }
+    }
+}
 
-            let coeff = match calculate_lagrange_coefficients(&commitment.index, &index_vector) {
-                Ok(s) => s,
-                Err(error) => return Err(Error::Custom(error.to_string())),
-            };
+/// A public key, used to verify a signature made by a threshold of a group of participants.
+#[derive(Clone, Copy, Debug, Eq)]
+pub struct GroupKey(pub(crate) RistrettoPoint, CompressedRistretto);
 
-            rhs += tmp * coeff;
-        }
+impl PartialEq for GroupKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.1 == other.1
+    }
+}
 
-        match self.share.compress() == rhs.compress() {
-            true => Ok(()),
-            false => Err(Error::ShareVerificationError),
-        }
+impl GroupKey {
+    /// Create a group key from its point, compressing it once.
+    pub(crate) fn new(point: RistrettoPoint) -> GroupKey {
+        GroupKey(point, point.compress())
     }
 
-    /// Any participant can compute the public verification share of any other participant.
-    ///
-    /// This is done by re-computing each [`IndividualPublicKey`] as \\(Y\_i\\) s.t.:
-    ///
-    /// \\[
-    /// Y\_i = \prod\_{j=1}^{n} \prod\_{k=0}^{t-1} \phi\_{jk}^{i^{k} \mod q}
-    /// \\]
-    ///
-    /// for each [`Participant`] index \\(i\\).
-    ///
-    /// # Inputs
+    /// Serialise this group public key to an array of bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.1.to_bytes()
+    }
+
+    /// Deserialise this group public key from an array of bytes.
     ///
-    /// * A `participant_index` and
-    /// * A vector of `commitments` regarding the secret polynomial
-    ///   [`Coefficients`] that the [`IndividualPublicKey`] will be generated from.
+    /// This accepts the encoding of the identity, for which no signature
+    /// can be meaningful.  Group keys received from untrusted parties should
+    /// be deserialised with [`GroupKey::from_bytes_strict`] instead.
+    #[cfg(not(feature = "strict"))]
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<GroupKey, Error> {
+        GroupKey::from_bytes_with_policy(bytes, DecompressionPolicy::Lenient)
+    }
+
+    /// Deserialise this group public key from an array of bytes, as with
+    /// [`GroupKey::from_bytes_strict`] under the `strict` feature.
+    #[cfg(feature = "strict")]
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<GroupKey, Error> {
+        GroupKey::from_bytes_strict(bytes)
+    }
+
+    /// Deserialise this group public key from an array of bytes, rejecting
+    /// the identity.
     ///
     /// # Returns
     ///
-    /// An `IndividualPublicKey`.
-    pub fn generate_from_commitments(
-        participant_index: u32,
-        commitments: &[VerifiableSecretSharingCommitment],
-    ) -> Self {
-        let mut share: RistrettoPoint = RistrettoPoint::identity();
-        let term: Scalar = participant_index.into();
+    /// A `NonCanonicalPoint` error if the bytes are not the canonical
+    /// encoding of a point, and an `IdentityPoint` error if they encode the
+    /// identity.
+    pub fn from_bytes_strict(bytes: &[u8; 32]) -> Result<GroupKey, Error> {
+        GroupKey::from_bytes_with_policy(bytes, DecompressionPolicy::Strict)
+    }
 
-        let mut index_vector: Vec<u32> = Vec::new();
-        for commitment in commitments.iter() {
-            index_vector.push(commitment.index);
+    /// Deserialise this group public key from an array of bytes, with the
+    /// given decompression `policy`.
+    pub fn from_bytes_with_policy(
+        bytes: &[u8; 32],
+        policy: DecompressionPolicy,
+    ) -> Result<GroupKey, Error> {
+        match policy {
+            #[cfg(not(feature = "strict"))]
+            DecompressionPolicy::Lenient => {
+                let point = decode_point_checked(bytes)?;
+
+                Ok(GroupKey(point, CompressedRistretto(*bytes)))
+            }
+            DecompressionPolicy::Strict => {
+                let point = decode_point_checked(bytes).map_err(|_| Error::NonCanonicalPoint)?;
+                if point == RistrettoPoint::identity() {
+                    return Err(Error::IdentityPoint);
+                }
+
+                Ok(GroupKey(point, CompressedRistretto(*bytes)))
+            }
         }
+    }
+}
 
-        for commitment in commitments.iter() {
-            let mut tmp: RistrettoPoint = RistrettoPoint::identity();
-            for (index, com) in commitment.points.iter().rev().enumerate() {
-                tmp += com;
+/// The public outcome of a distributed key generation: the group key and the
+/// public verification shares of all the participants.
+///
+/// Light clients may only store its [`PublicKeyPackage::hash`], and check a
+/// claimed group key against it with
+/// [`ThresholdSignature::verify_with_package_hash`].
+///
+/// [`ThresholdSignature::verify_with_package_hash`]: crate::signature::ThresholdSignature::verify_with_package_hash
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicKeyPackage {
+    /// The parameters of the group.
+    pub parameters: Parameters,
+    /// The group public key.
+    pub group_key: GroupKey,
+    /// The public verification shares of the participants, sorted by index.
+    pub public_keys: Vec<IndividualPublicKey>,
+}
 
-                if index != (commitment.points.len() - 1) {
-                    tmp *= term;
-                }
+impl PublicKeyPackage {
+    /// Construct the public key package of a group from the public
+    /// verification shares of all of its participants, in any order.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidNumberOfParticipants` error if there is not exactly one
+    /// share per participant, an `UnknownParticipant` error if a share has an
+    /// index outside of the group, or a `DuplicateParticipant` error if a
+    /// participant has two shares.
+    pub fn new(
+        parameters: Parameters,
+        group_key: GroupKey,
+        mut public_keys: Vec<IndividualPublicKey>,
+    ) -> Result<PublicKeyPackage, Error> {
+        if public_keys.len() != parameters.n as usize {
+            return Err(Error::InvalidNumberOfParticipants(
+                public_keys.len(),
+                parameters.n,
+            ));
+        }
+        public_keys.sort_by_key(|public_key| public_key.index);
+        for public_key in public_keys.iter() {
+            if public_key.index == 0 || public_key.index > parameters.n {
+                return Err(Error::UnknownParticipant(public_key.index));
             }
-
-            let coeff = calculate_lagrange_coefficients(&commitment.index, &index_vector).unwrap();
-            share += tmp * coeff;
         }
-
-        IndividualPublicKey {
-            index: participant_index,
-            share,
+        for pair in public_keys.windows(2) {
+            if pair[0].index == pair[1].index {
+                return Err(Error::DuplicateParticipant(pair[0].index));
+            }
         }
-    }
-
-    /// Serialise this individual public key to an array of bytes.
-    pub fn to_bytes(&self) -> [u8; 36] {
-        let mut res = [0u8; 36];
-        res[0..4].copy_from_slice(&self.index.to_le_bytes());
-        res[4..36].copy_from_slice(&self.share.compress().to_bytes());
 
-        res
+        Ok(PublicKeyPackage {
+            parameters,
+            group_key,
+            public_keys,
+        })
     }
 
-    /// Deserialise this individual public key from an array of bytes.
-    pub fn from_bytes(bytes: &[u8; 36]) -> Result<IndividualPublicKey, Error> {
-        let index = u32::from_le_bytes(
-            bytes[0..4]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        );
+    /// The hash committing to this whole package.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
 
-        let share = CompressedRistretto(
-            bytes[4..36]
-                .try_into()
-                .map_err(|_| Error::SerialisationError)?,
-        )
-        .decompress()
-        .ok_or(Error::SerialisationError)?;
+        h.update(b"ICE-FROST-PUBLIC-KEY-PACKAGE");
+        h.update(self.to_bytes());
 
-        Ok(IndividualPublicKey { index, share })
+        h.finalize().into()
     }
-}
-
-/// A secret key, used by one participant in a threshold signature scheme, to sign a message.
-#[derive(Clone, Debug, Eq, PartialEq, Zeroize)]
-#[zeroize(drop)]
-pub struct SecretKey {
-    /// The participant index to which this key belongs.
-    pub(crate) index: u32,
-    /// The participant's long-lived secret share of the group signing key.
-    pub(crate) key: Scalar,
-}
-
-impl SecretKey {
-    /// Derive the corresponding public key for this secret key.
-    pub fn to_public(&self) -> IndividualPublicKey {
-        let share = &RISTRETTO_BASEPOINT_TABLE * &self.key;
 
-        IndividualPublicKey {
-            index: self.index,
-            share,
+    /// Serialise this public key package to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(44 + 36 * self.public_keys.len());
+        res.extend_from_slice(&self.parameters.to_bytes());
+        res.extend_from_slice(&self.group_key.to_bytes());
+        res.extend_from_slice(&(self.public_keys.len() as u32).to_le_bytes());
+        for public_key in self.public_keys.iter() {
+            res.extend_from_slice(&public_key.to_bytes());
         }
-    }
-
-    /// Serialise this secret key to an array of bytes.
-    pub fn to_bytes(&self) -> [u8; 36] {
-        let mut res = [0u8; 36];
-        res[0..4].copy_from_slice(&self.index.to_le_bytes());
-        res[4..36].copy_from_slice(&self.key.to_bytes());
 
         res
     }
 
-    /// Deserialise this secret key from an array of bytes.
-    pub fn from_bytes(bytes: &[u8; 36]) -> Result<SecretKey, Error> {
-        let index = u32::from_le_bytes(
-            bytes[0..4]
+    /// Deserialise this slice of bytes to a `PublicKeyPackage`
+    ///
+    /// The group key is decoded with [`GroupKey::from_bytes_strict`], and the
+    /// shares are validated as in [`PublicKeyPackage::new`], but must already
+    /// be sorted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKeyPackage, Error> {
+        let parameters = Parameters::from_bytes(
+            bytes
+                .get(0..8)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        );
-
-        let key = Scalar::from_canonical_bytes(
-            bytes[4..36]
+        )?;
+        let group_key = GroupKey::from_bytes_strict(
+            bytes
+                .get(8..40)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .ok_or(Error::SerialisationError)?;
+        )?;
+        let len = u32::from_le_bytes(
+            bytes
+                .get(40..44)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        if (bytes.len() - 44) as u64 != 36 * len as u64 {
+            return Err(Error::SerialisationError);
+        }
 
-        Ok(SecretKey { index, key })
-    }
-}
+        let mut public_keys: Vec<IndividualPublicKey> = Vec::with_capacity(len as usize);
+        for chunk in bytes[44..].chunks(36) {
+            let public_key = IndividualPublicKey::from_bytes(
+                chunk.try_into().map_err(|_| Error::SerialisationError)?,
+            )?;
+            if let Some(previous) = public_keys.last() {
+                if previous.index >= public_key.index {
+                    return Err(Error::SerialisationError);
+                }
+            }
+            public_keys.push(public_key);
+        }
 
-impl From<&SecretKey> for IndividualPublicKey {
-    fn from(source: &SecretKey) -> IndividualPublicKey {
-        source.to_public()
+        PublicKeyPackage::new(parameters, group_key, public_keys)
     }
 }
 
-/// A public key, used to verify a signature made by a threshold of a group of participants.
-#[derive(Clone, Copy, Debug, Eq)]
-pub struct GroupKey(pub(crate) RistrettoPoint);
-
-impl PartialEq for GroupKey {
-    fn eq(&self, other: &Self) -> bool {
-        self.0.compress() == other.0.compress()
-    }
+/// How to validate points decompressed from untrusted bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecompressionPolicy {
+    /// Reject non-canonical encodings with a `SerialisationError`, and
+    /// accept the identity.  This is the behaviour of `from_bytes`.
+    ///
+    /// This policy is not available with the `strict` feature.
+    #[cfg(not(feature = "strict"))]
+    Lenient,
+    /// Reject non-canonical encodings with a `NonCanonicalPoint` error, and
+    /// the identity with an `IdentityPoint` error.
+    Strict,
 }
 
-impl GroupKey {
-    /// Serialise this group public key to an array of bytes.
-    pub fn to_bytes(&self) -> [u8; 32] {
-        self.0.compress().to_bytes()
-    }
+/// The lenient behaviours compiled into this build of the crate, as
+/// reported by [`strictness`].
+///
+/// Each of them is kept for compatibility, and compiled out by the `strict`
+/// feature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Strictness {
+    /// Whether the crate was built with the `strict` feature.
+    pub strict: bool,
+    /// Whether shares in the legacy, unauthenticated [`ShareVersion::V0`]
+    /// format can be produced and decoded.
+    pub legacy_shares: bool,
+    /// Whether round one packages which do not advertise their share
+    /// versions are accepted.
+    pub unversioned_packages: bool,
+    /// Whether [`GroupKey::from_bytes`] accepts the identity.
+    pub lenient_group_keys: bool,
+}
 
-    /// Deserialise this group public key from an array of bytes.
-    pub fn from_bytes(bytes: &[u8; 32]) -> Result<GroupKey, Error> {
-        let point = CompressedRistretto(*bytes)
-            .decompress()
-            .ok_or(Error::SerialisationError)?;
+/// Report the lenient behaviours compiled into this build, e.g. for audit
+/// logs.
+pub fn strictness() -> Strictness {
+    let strict = cfg!(feature = "strict");
 
-        Ok(GroupKey(point))
+    Strictness {
+        strict,
+        legacy_shares: !strict,
+        unversioned_packages: !strict,
+        lenient_group_keys: !strict,
     }
 }
 
 #[cfg(test)]
+// The tests pass `&mut rng` to the generic constructors for readability.
+#[allow(clippy::needless_borrows_for_generic_args)]
 mod test {
     use super::*;
+    use crate::test_utils::{run_dkg, run_full_dkg, Dkg};
     use rand::rngs::OsRng;
     use rand::Rng;
 
@@ -2555,11 +6582,9 @@ mod test {
         let mut rng = OsRng;
 
         let (p, _, _) = Participant::new_dealer(&params, 0, "Φ", &mut rng);
-        let result =
-            p.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&p.index, p.public_key().unwrap(), "Φ");
+        let result = p
+            .proof_of_secret_key
+            .verify(&p.index, p.public_key().unwrap(), "Φ");
 
         assert!(result.is_ok());
     }
@@ -2591,6 +6616,49 @@ mod test {
         assert!(share.verify(&commitments).is_ok());
     }
 
+    #[test]
+    fn secret_share_at_large_index() {
+        // With t = 500, index^k is far beyond any integer type: every step of
+        // the evaluation must happen in the scalar field.
+        let coefficients = Coefficients((0..500).map(|_| Scalar::random(&mut OsRng)).collect());
+        let commitments = VerifiableSecretSharingCommitment {
+            index: 1,
+            points: coefficients
+                .0
+                .iter()
+                .map(|c| &RISTRETTO_BASEPOINT_TABLE * c)
+                .collect(),
+        };
+
+        for index in [1000u32, u32::MAX] {
+            let term = Scalar::from(index);
+            let mut power = Scalar::one();
+            let mut expected = Scalar::zero();
+            for coefficient in coefficients.0.iter() {
+                expected += coefficient * power;
+                power *= term;
+            }
+
+            let share = SecretShare::evaluate_polynomial(&1, &index, &coefficients);
+            assert_eq!(share.polynomial_evaluation, expected);
+            assert!(share.verify(&commitments).is_ok());
+            assert_eq!(
+                commitments.evaluate_hiding(&term),
+                &RISTRETTO_BASEPOINT_TABLE * &expected
+            );
+        }
+
+        // Any 500 of the shares of a 1000 participants interpolate the secret.
+        let indices: Vec<u32> = (501..=1000).collect();
+        let mut secret = Scalar::zero();
+        for index in indices.iter() {
+            let share = SecretShare::evaluate_polynomial(&1, index, &coefficients);
+            let lambda = calculate_lagrange_coefficients(index, &indices).unwrap();
+            secret += lambda * share.polynomial_evaluation;
+        }
+        assert_eq!(secret, coefficients.0[0]);
+    }
+
     #[test]
     fn secret_share_participant_index_zero() {
         let mut coeffs: Vec<Scalar> = Vec::new();
@@ -2626,8 +6694,6 @@ mod test {
         let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
 
         p1.proof_of_secret_key
-            .as_ref()
-            .unwrap()
             .verify(&p1.index, p1.public_key().unwrap(), "Φ")
             .unwrap();
 
@@ -2671,28 +6737,18 @@ mod test {
         let (p5, p5coeffs, p5_dh_sk) = Participant::new_dealer(&params, 5, "Φ", &mut rng);
 
         p1.proof_of_secret_key
-            .as_ref()
-            .unwrap()
             .verify(&p1.index, p1.public_key().unwrap(), "Φ")
             .unwrap();
         p2.proof_of_secret_key
-            .as_ref()
-            .unwrap()
             .verify(&p2.index, p2.public_key().unwrap(), "Φ")
             .unwrap();
         p3.proof_of_secret_key
-            .as_ref()
-            .unwrap()
             .verify(&p3.index, p3.public_key().unwrap(), "Φ")
             .unwrap();
         p4.proof_of_secret_key
-            .as_ref()
-            .unwrap()
             .verify(&p4.index, p4.public_key().unwrap(), "Φ")
             .unwrap();
         p5.proof_of_secret_key
-            .as_ref()
-            .unwrap()
             .verify(&p5.index, p5.public_key().unwrap(), "Φ")
             .unwrap();
 
@@ -2855,18 +6911,12 @@ mod test {
             let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 
             p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p1.index, p1.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p2.index, p2.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p3.index, p3.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
 
@@ -2963,20 +7013,14 @@ mod test {
 
             dealer1
                 .proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&dealer1.index, dealer1.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             dealer2
                 .proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&dealer2.index, dealer2.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             dealer3
                 .proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&dealer3.index, dealer3.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
 
@@ -3056,12 +7100,12 @@ mod test {
             assert!(dealer1_group_key.0.compress() == dealer2_group_key.0.compress());
             assert!(dealer2_group_key.0.compress() == dealer3_group_key.0.compress());
 
-            let (signer1, signer1_dh_sk) = Participant::new_signer(&params, 1, "Φ", &mut rng);
-            let (signer2, signer2_dh_sk) = Participant::new_signer(&params, 2, "Φ", &mut rng);
+            let (signer1, signer1_dh_sk) = DkgSigner::new(&params, 1, "Φ", rng);
+            let (signer2, signer2_dh_sk) = DkgSigner::new(&params, 2, "Φ", rng);
             // Dealer 3 is also a participant of the next set of signers
-            let (signer3, signer3_dh_sk) = (dealer3.clone(), dealer3_dh_sk);
+            let (signer3, signer3_dh_sk) = (DkgSigner::from(&dealer3), dealer3_dh_sk);
 
-            let signers: Vec<Participant> = vec![signer1.clone(), signer2.clone(), signer3.clone()];
+            let signers: Vec<DkgSigner> = vec![signer1.clone(), signer2.clone(), signer3.clone()];
 
             let (dealer1_for_signers, dealer1_encrypted_shares_for_signers, _participant_lists) =
                 Participant::reshare(&params, dealer1_secret_key, &signers, "Φ", &mut rng)
@@ -3163,20 +7207,14 @@ mod test {
 
             dealer1
                 .proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&dealer1.index, dealer1.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             dealer2
                 .proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&dealer2.index, dealer2.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             dealer3
                 .proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&dealer3.index, dealer3.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
 
@@ -3257,18 +7295,13 @@ mod test {
             assert!(dealer2_group_key.0.compress() == dealer3_group_key.0.compress());
 
             let params_signers = Parameters { n: 5, t: 3 };
-            let (signer1, signer1_dh_sk) =
-                Participant::new_signer(&params_signers, 1, "Φ", &mut rng);
-            let (signer2, signer2_dh_sk) =
-                Participant::new_signer(&params_signers, 2, "Φ", &mut rng);
-            let (signer3, signer3_dh_sk) =
-                Participant::new_signer(&params_signers, 3, "Φ", &mut rng);
-            let (signer4, signer4_dh_sk) =
-                Participant::new_signer(&params_signers, 4, "Φ", &mut rng);
-            let (signer5, signer5_dh_sk) =
-                Participant::new_signer(&params_signers, 5, "Φ", &mut rng);
-
-            let signers: Vec<Participant> = vec![
+            let (signer1, signer1_dh_sk) = DkgSigner::new(&params_signers, 1, "Φ", rng);
+            let (signer2, signer2_dh_sk) = DkgSigner::new(&params_signers, 2, "Φ", rng);
+            let (signer3, signer3_dh_sk) = DkgSigner::new(&params_signers, 3, "Φ", rng);
+            let (signer4, signer4_dh_sk) = DkgSigner::new(&params_signers, 4, "Φ", rng);
+            let (signer5, signer5_dh_sk) = DkgSigner::new(&params_signers, 5, "Φ", rng);
+
+            let signers: Vec<DkgSigner> = vec![
                 signer1.clone(),
                 signer2.clone(),
                 signer3.clone(),
@@ -3414,13 +7447,16 @@ mod test {
         let mut key = [0u8; 32];
         rng.fill(&mut key);
 
-        let encrypted_share = encrypt_share(&original_share, &key, &mut rng);
-        let decrypted_share = decrypt_share(&encrypted_share, &key);
+        for version in ShareVersion::SUPPORTED {
+            let encrypted_share = encrypt_share(&original_share, &key, version, rng);
+            let decrypted_share = decrypt_share(&encrypted_share, &key);
 
-        assert!(decrypted_share.is_ok());
-        assert!(
-            original_share.polynomial_evaluation == decrypted_share.unwrap().polynomial_evaluation
-        );
+            assert!(decrypted_share.is_ok());
+            assert!(
+                original_share.polynomial_evaluation
+                    == decrypted_share.unwrap().polynomial_evaluation
+            );
+        }
     }
 
     #[test]
@@ -3434,18 +7470,12 @@ mod test {
             let (p3, p3coeffs, dh_sk3) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 
             p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p1.index, p1.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p2.index, p2.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p3.index, p3.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
 
@@ -3505,26 +7535,638 @@ mod test {
                 p3_their_encrypted_secret_shares[2].clone(),
             ];
 
-            let p1_state = p1_state
-                .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let p2_state = p2_state
-                .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let p3_state = p3_state
-                .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
+            let p1_state = p1_state
+                .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let p2_state = p2_state
+                .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let p3_state = p3_state
+                .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+
+            let (p1_group_key, _p1_secret_key) = p1_state.finish().or(Err(()))?;
+            let (p2_group_key, _p2_secret_key) = p2_state.finish().or(Err(()))?;
+            let (p3_group_key, _p3_secret_key) = p3_state.finish().or(Err(()))?;
+
+            assert!(p1_group_key.0.compress() == p2_group_key.0.compress());
+            assert!(p2_group_key.0.compress() == p3_group_key.0.compress());
+
+            Ok(())
+        }
+        assert!(do_test().is_ok());
+    }
+
+    /// Run the first round of a 2-out-of-3 key generation in which each
+    /// participant advertises the given share versions, and return the
+    /// resulting states and the encrypted shares addressed to each of them.
+    #[allow(clippy::type_complexity)]
+    fn mixed_version_round_one(
+        versions: [Vec<ShareVersion>; 3],
+    ) -> (
+        Vec<DistributedKeyGeneration<RoundOne>>,
+        Vec<Vec<EncryptedSecretShare>>,
+    ) {
+        let params = Parameters { n: 3, t: 2 };
+        let rng: OsRng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut secrets = Vec::new();
+        for (i, versions) in versions.iter().enumerate() {
+            let (mut p, coeffs, dh_sk) = Participant::new_dealer(&params, i as u32 + 1, "Φ", rng);
+            p.share_versions = versions.clone();
+            // The advertised versions must survive the round one package.
+            let p = Participant::from_bytes(&p.to_bytes()).unwrap();
+            assert_eq!(&p.share_versions, versions);
+
+            participants.push(p);
+            secrets.push((coeffs, dh_sk));
+        }
+
+        let mut states = Vec::new();
+        let mut shares = vec![Vec::new(), Vec::new(), Vec::new()];
+        for (p, (coeffs, dh_sk)) in participants.iter().zip(secrets.iter()) {
+            let (state, _) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                dh_sk,
+                &p.index,
+                coeffs,
+                &participants,
+                "Φ",
+                rng,
+            )
+            .unwrap();
+            for share in state.their_encrypted_secret_shares().unwrap().iter() {
+                shares[share.receiver_index as usize - 1].push(share.clone());
+            }
+            states.push(state);
+        }
+
+        (states, shares)
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn share_version_negotiation() {
+        assert_eq!(
+            ShareVersion::negotiate(&ShareVersion::SUPPORTED, &[ShareVersion::V0]),
+            Some(ShareVersion::V0)
+        );
+        assert_eq!(
+            ShareVersion::negotiate(&ShareVersion::SUPPORTED, &ShareVersion::SUPPORTED),
+            Some(ShareVersion::V1)
+        );
+        assert_eq!(
+            ShareVersion::negotiate(&[ShareVersion::V1], &[ShareVersion::V0]),
+            None
+        );
+        assert!(ShareVersion::from_byte(0xff).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn keygen_mixed_share_versions() {
+        let rng: OsRng = OsRng;
+
+        // Participant 2 only knows the legacy format, the others both.
+        let (states, shares) = mixed_version_round_one([
+            ShareVersion::SUPPORTED.to_vec(),
+            vec![ShareVersion::V0],
+            ShareVersion::SUPPORTED.to_vec(),
+        ]);
+
+        for (receiver, shares) in shares.iter().enumerate() {
+            for share in shares.iter() {
+                let expected = if share.sender_index != 2 && receiver != 1 {
+                    ShareVersion::V1
+                } else {
+                    ShareVersion::V0
+                };
+                assert_eq!(share.version, expected);
+                assert_eq!(
+                    &EncryptedSecretShare::from_bytes(&share.to_bytes()).unwrap(),
+                    share
+                );
+            }
+        }
+
+        let mut group_keys = Vec::new();
+        for (state, shares) in states.into_iter().zip(shares.into_iter()) {
+            let state = state.to_round_two(shares, rng).unwrap();
+            group_keys.push(state.finish().unwrap().0);
+        }
+        assert_eq!(group_keys[0], group_keys[1]);
+        assert_eq!(group_keys[1], group_keys[2]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn keygen_incompatible_share_versions() {
+        let rng: OsRng = OsRng;
+
+        // Participant 3 refuses the legacy format, which is all that
+        // participant 2 can produce.
+        let (mut states, mut shares) = mixed_version_round_one([
+            ShareVersion::SUPPORTED.to_vec(),
+            vec![ShareVersion::V0],
+            vec![ShareVersion::V1],
+        ]);
+
+        let p3_state = states.pop().unwrap();
+        let p3_shares = shares.pop().unwrap();
+        assert_eq!(
+            p3_state.to_round_two(p3_shares, rng).unwrap_err(),
+            Error::UnsupportedShareVersion {
+                dealer: 2,
+                version: ShareVersion::V0,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn keygen_downgraded_share_version() {
+        let rng: OsRng = OsRng;
+
+        let (mut states, mut shares) = mixed_version_round_one([
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+        ]);
+
+        // Participant 3 also accepts the legacy format, but not from a
+        // dealer with which it negotiated the authenticated one.
+        let mut p3_shares = shares.pop().unwrap();
+        let downgraded = p3_shares.iter_mut().find(|s| s.sender_index == 1).unwrap();
+        assert_eq!(downgraded.version, ShareVersion::V1);
+        downgraded.version = ShareVersion::V0;
+        downgraded.tag = [0u8; 32];
+
+        let p3_state = states.pop().unwrap();
+        assert_eq!(
+            p3_state.to_round_two(p3_shares, rng).unwrap_err(),
+            Error::UnsupportedShareVersion {
+                dealer: 1,
+                version: ShareVersion::V0,
+            }
+        );
+    }
+
+    #[test]
+    fn keygen_tampered_v1_share() {
+        let rng: OsRng = OsRng;
+
+        let (mut states, mut shares) = mixed_version_round_one([
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+        ]);
+
+        // Flipping a bit of an authenticated share is caught by its tag,
+        // and makes its recipient complain about the dealer.
+        let mut p3_shares = shares.pop().unwrap();
+        let tampered = p3_shares.iter_mut().find(|s| s.sender_index == 1).unwrap();
+        assert_eq!(tampered.version, ShareVersion::V1);
+        tampered.encrypted_polynomial_evaluation[0] ^= 1;
+
+        let p3_state = states.pop().unwrap();
+        match p3_state.to_round_two(p3_shares, rng) {
+            Err(Error::Complaint(complaints)) => {
+                assert_eq!(complaints.len(), 1);
+                assert_eq!(complaints[0].accused_index, 1);
+            }
+            _ => panic!("the tampered share was not detected"),
+        }
+    }
+
+    /// A hardware security module holding a DH key pair, which it never
+    /// exports to the library.
+    struct Hsm(DHPrivateKey);
+
+    impl ExternalDhKey for Hsm {
+        fn dh_public_key(&self) -> DHPublicKey {
+            DHPublicKey::from(&self.0)
+        }
+
+        fn diffie_hellman(&self, their_public_key: &DHPublicKey) -> [u8; 32] {
+            (their_public_key.0 * self.0 .0).compress().to_bytes()
+        }
+
+        fn sign_challenge(&self, challenge: &DhKeyChallenge) -> NizkOfSecretKey {
+            challenge.sign(&self.0, OsRng)
+        }
+
+        fn prove_complaint(
+            &self,
+            their_public_key: &DHPublicKey,
+            dh_output: &[u8; 32],
+            ciphertext_hash: &[u8; 32],
+        ) -> ComplaintProof {
+            ComplaintProof::prove(&self.0, their_public_key, dh_output, ciphertext_hash, OsRng)
+        }
+    }
+
+    #[test]
+    fn keygen_reshare_to_external_dh_key() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng: OsRng = OsRng;
+
+        let (states, shares) = mixed_version_round_one([
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+        ]);
+        let mut group_key = None;
+        let mut secret_keys = Vec::new();
+        for (state, shares) in states.into_iter().zip(shares.into_iter()) {
+            let (key, secret_key) = state.to_round_two(shares, rng).unwrap().finish().unwrap();
+            group_key = Some(key);
+            secret_keys.push(secret_key);
+        }
+        let group_key = group_key.unwrap();
+
+        // The third new signer only publishes its answer to the challenge of
+        // the ceremony, signed inside its device.
+        let (signer1, signer1_dh_sk) = DkgSigner::new(&params, 1, "Φ", rng);
+        let (signer2, signer2_dh_sk) = DkgSigner::new(&params, 2, "Φ", rng);
+        let hsm = Hsm(DHPrivateKey(Scalar::random(&mut rng)));
+        let handshake = DhKeyHandshake::new(&DhKeyChallenge::new(3, "Φ"), &hsm);
+        let handshake = DhKeyHandshake::from_bytes(&handshake.to_bytes()).unwrap();
+        assert_eq!(
+            DkgSigner::new_from_public(&handshake, "Ψ").unwrap_err(),
+            Error::InvalidProofOfKnowledge
+        );
+        let signer3 = DkgSigner::new_from_public(&handshake, "Φ").unwrap();
+        assert_eq!(signer3.dh_public_key, hsm.dh_public_key());
+
+        let signers = vec![signer1.clone(), signer2.clone(), signer3.clone()];
+        let mut dealers = Vec::new();
+        let mut reshared = [Vec::new(), Vec::new(), Vec::new()];
+        for secret_key in secret_keys.into_iter() {
+            let (dealer, encrypted_shares, _) =
+                Participant::reshare(&params, secret_key, &signers, "Φ", rng).unwrap();
+            for share in encrypted_shares.into_iter() {
+                reshared[share.receiver_index as usize - 1].push(share);
+            }
+            dealers.push(dealer);
+        }
+
+        let mut new_group_keys = Vec::new();
+        for (dh_sk, signer) in [(signer1_dh_sk, &signer1), (signer2_dh_sk, &signer2)] {
+            let (state, _) = DistributedKeyGeneration::<RoundOne>::new(
+                &params,
+                &dh_sk,
+                &signer.index,
+                &dealers,
+                "Φ",
+                rng,
+            )
+            .unwrap();
+            let (key, _) = state
+                .to_round_two(reshared[signer.index as usize - 1].clone(), rng)
+                .unwrap()
+                .finish()
+                .unwrap();
+            new_group_keys.push(key);
+        }
+
+        // The state of the device signer holds no DH private key, neither in
+        // memory nor once serialised, and can only decrypt through the device.
+        let (state, _) = DistributedKeyGeneration::<RoundOne>::new_with_external_dh_key(
+            &params,
+            &hsm,
+            &signer3.index,
+            &dealers,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        assert!(state.state.dh_private_key.is_none());
+        let state = DistributedKeyGeneration::<RoundOne>::from_bytes(&state.to_bytes()).unwrap();
+        assert!(state.state.dh_private_key.is_none());
+        let my_shares = reshared[2].clone();
+        assert_eq!(
+            state
+                .duplicate()
+                .to_round_two(my_shares.clone(), rng)
+                .unwrap_err(),
+            Error::ExternalDhKey
+        );
+        assert_eq!(
+            state
+                .duplicate()
+                .process_share(my_shares[0].clone(), rng)
+                .unwrap_err(),
+            Error::ExternalDhKey
+        );
+        let other = Hsm(DHPrivateKey(Scalar::random(&mut rng)));
+        assert_eq!(
+            state
+                .duplicate()
+                .to_round_two_with_external_dh_key(my_shares.clone(), &other)
+                .unwrap_err(),
+            Error::ExternalDhKey
+        );
+
+        // Complaints are proven inside the device as well.
+        let mut tampered = my_shares.clone();
+        tampered[1].encrypted_polynomial_evaluation[0] ^= 1;
+        match state
+            .duplicate()
+            .to_round_two_with_external_dh_key(tampered, &hsm)
+        {
+            Err(Error::Complaint(complaints)) => {
+                assert_eq!(complaints.len(), 1);
+                assert_eq!(complaints[0].accused_index, 2);
+                assert!(complaints[0]
+                    .verify(&hsm.dh_public_key(), &dealers[1].dh_public_key)
+                    .is_ok());
+            }
+            _ => panic!("the tampered share was not detected"),
+        }
+
+        let (key, _) = state
+            .to_round_two_with_external_dh_key(my_shares, &hsm)
+            .unwrap()
+            .finish()
+            .unwrap();
+        new_group_keys.push(key);
+
+        for key in new_group_keys.iter() {
+            assert!(*key == group_key);
+        }
+    }
+
+    #[test]
+    fn keygen_streamed_shares() {
+        let rng: OsRng = OsRng;
+
+        let (mut states, mut shares) = mixed_version_round_one([
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+            ShareVersion::SUPPORTED.to_vec(),
+        ]);
+        let p3_shares = shares.pop().unwrap();
+        let p3_state = states.pop().unwrap();
+
+        let batch = p3_state
+            .duplicate()
+            .to_round_two(p3_shares.clone(), rng)
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        // Shares are incorporated one at a time, in any order, and the
+        // progress survives serialisation.
+        let mut streamed = p3_state.duplicate();
+        streamed.process_share(p3_shares[2].clone(), rng).unwrap();
+        assert_eq!(
+            streamed.process_share(p3_shares[2].clone(), rng),
+            Err(Error::DuplicateParticipant(3))
+        );
+        let mut unknown = p3_shares[0].clone();
+        unknown.sender_index = 4;
+        assert_eq!(
+            streamed.process_share(unknown, rng),
+            Err(Error::UnknownParticipant(4))
+        );
+        streamed.process_share(p3_shares[0].clone(), rng).unwrap();
+        assert_eq!(
+            streamed.duplicate().complete_round_two().unwrap_err(),
+            Error::MissingShares
+        );
+
+        let mut streamed =
+            DistributedKeyGeneration::<RoundOne>::from_bytes(&streamed.to_bytes()).unwrap();
+        let mut tampered = p3_shares[1].clone();
+        tampered.encrypted_polynomial_evaluation[0] ^= 1;
+        match streamed.process_share(tampered, rng) {
+            Err(Error::Complaint(complaints)) => {
+                assert_eq!(complaints.len(), 1);
+                assert_eq!(complaints[0].accused_index, 2);
+            }
+            _ => panic!("the tampered share was not detected"),
+        }
+        streamed.process_share(p3_shares[1].clone(), rng).unwrap();
+
+        let streamed = streamed.complete_round_two().unwrap().finish().unwrap();
+        assert_eq!(streamed, batch);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn encrypted_share_legacy_serialisation() {
+        let mut rng: OsRng = OsRng;
+
+        let share = SecretShare {
+            sender_index: 1,
+            receiver_index: 2,
+            polynomial_evaluation: Scalar::random(&mut rng),
+        };
+        let key = [7u8; 32];
+
+        let legacy = encrypt_share(&share, &key, ShareVersion::V0, rng);
+        let bytes = legacy.to_bytes();
+        assert_eq!(bytes.len(), 56);
+        assert_eq!(bytes.len(), legacy.serialized_len());
+        let decoded = EncryptedSecretShare::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, legacy);
+        assert_eq!(
+            decrypt_share(&decoded, &key).unwrap().polynomial_evaluation,
+            share.polynomial_evaluation
+        );
+
+        let current = encrypt_share(&share, &key, ShareVersion::V1, rng);
+        let bytes = current.to_bytes();
+        assert_eq!(bytes.len(), 89);
+        assert_eq!(bytes.len(), current.serialized_len());
+        assert_eq!(EncryptedSecretShare::from_bytes(&bytes).unwrap(), current);
+
+        // A legacy share claiming the current format is rejected.
+        let mut bytes = [0u8; 89];
+        bytes[1..57].copy_from_slice(&legacy.to_bytes());
+        assert!(EncryptedSecretShare::from_bytes(&bytes).is_err());
+        assert!(EncryptedSecretShare::from_bytes(&bytes[..88]).is_err());
+
+        // A round one package without advertised versions is a legacy one.
+        let params = Parameters { n: 3, t: 2 };
+        let (mut p, _, _) = Participant::new_dealer(&params, 1, "Φ", rng);
+        p.share_versions = ShareVersion::SUPPORTED.to_vec();
+        let bytes = p.to_bytes();
+        let legacy_bytes = &bytes[..bytes.len() - 1 - ShareVersion::SUPPORTED.len()];
+        assert_eq!(
+            Participant::from_bytes(legacy_bytes)
+                .unwrap()
+                .share_versions,
+            vec![ShareVersion::V0]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    fn strict_rejects_legacy_formats() {
+        let mut rng: OsRng = OsRng;
+
+        assert_eq!(
+            strictness(),
+            Strictness {
+                strict: true,
+                legacy_shares: false,
+                unversioned_packages: false,
+                lenient_group_keys: false,
+            }
+        );
+        assert_eq!(ShareVersion::SUPPORTED, [ShareVersion::V1]);
+        assert!(ShareVersion::from_byte(0).is_err());
+
+        let share = SecretShare {
+            sender_index: 1,
+            receiver_index: 2,
+            polynomial_evaluation: Scalar::random(&mut rng),
+        };
+        let current = encrypt_share(&share, &[7u8; 32], ShareVersion::V1, rng);
+        let bytes = current.to_bytes();
+        assert_eq!(bytes.len(), current.serialized_len());
+        assert_eq!(EncryptedSecretShare::from_bytes(&bytes).unwrap(), current);
+        // The legacy format is not decoded.
+        assert!(EncryptedSecretShare::from_bytes(&bytes[1..57]).is_err());
+
+        // Round one packages must advertise their versions.
+        let params = Parameters { n: 3, t: 2 };
+        let (p, _, _) = Participant::new_dealer(&params, 1, "Φ", rng);
+        assert_eq!(p.share_versions, vec![ShareVersion::V1]);
+        let bytes = p.to_bytes();
+        assert!(Participant::from_bytes(&bytes).is_ok());
+        assert!(Participant::from_bytes(&bytes[..bytes.len() - 2]).is_err());
+
+        assert_eq!(
+            GroupKey::from_bytes(&RistrettoPoint::identity().compress().to_bytes()),
+            Err(Error::IdentityPoint)
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn lenient_strictness() {
+        assert_eq!(
+            strictness(),
+            Strictness {
+                strict: false,
+                legacy_shares: true,
+                unversioned_packages: true,
+                lenient_group_keys: true,
+            }
+        );
+    }
+
+    #[test]
+    fn verify_all_proofs_of_knowledge() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng: OsRng = OsRng;
+
+        let (p1, _, _) = Participant::new_dealer(&params, 1, "Φ", rng);
+        let (p2, _, _) = Participant::new_dealer(&params, 2, "Φ", rng);
+        let (mut p3, _, _) = Participant::new_dealer(&params, 3, "Φ", rng);
+
+        let participants = vec![p1.clone(), p2.clone(), p3.clone()];
+        assert!(
+            DistributedKeyGeneration::<RoundOne>::verify_all_proofs_of_knowledge(
+                &participants,
+                "Φ"
+            )
+            .is_ok()
+        );
+        assert_eq!(
+            DistributedKeyGeneration::<RoundOne>::verify_all_proofs_of_knowledge(
+                &participants,
+                "Ψ"
+            ),
+            Err(vec![1, 2, 3])
+        );
+
+        // Participant 3 swaps its constant term commitment for another key.
+        p3.commitments.points[0] = &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut rng);
+        // Participant 4 has no commitments at all.
+        let (mut p4, _, _) = Participant::new_dealer(&params, 4, "Φ", rng);
+        p4.commitments.points.clear();
+        assert_eq!(
+            DistributedKeyGeneration::<RoundOne>::verify_all_proofs_of_knowledge(
+                &[p3, p2, p1, p4],
+                "Φ"
+            ),
+            Err(vec![3, 4])
+        );
+    }
+
+    #[test]
+    fn dealer_and_signer_serialisation() {
+        let params = Parameters { n: 3, t: 2 };
+        let rng: OsRng = OsRng;
+
+        let (dealer, coefficients, _) = Participant::new_dealer(&params, 1, "Φ", rng);
+        let (signer, _) = DkgSigner::new(&params, 2, "Φ", rng);
+
+        let dealer_bytes = dealer.to_bytes();
+        let signer_bytes = signer.to_bytes();
+        let deserialised = Participant::from_bytes(&dealer_bytes).unwrap();
+        assert_eq!(deserialised.commitments, dealer.commitments);
+        assert_eq!(deserialised.proof_of_secret_key, dealer.proof_of_secret_key);
+        let deserialised = DkgSigner::from_bytes(&signer_bytes).unwrap();
+        assert_eq!(deserialised.dh_public_key, signer.dh_public_key);
+        assert_eq!(deserialised.share_versions, signer.share_versions);
+
+        // A dealer always carries commitments, and a signer never does.
+        assert_eq!(
+            Participant::from_bytes(&signer_bytes).unwrap_err(),
+            Error::SerialisationError
+        );
+        assert_eq!(
+            DkgSigner::from_bytes(&dealer_bytes).unwrap_err(),
+            Error::SerialisationError
+        );
 
-            let (p1_group_key, _p1_secret_key) = p1_state.finish().or(Err(()))?;
-            let (p2_group_key, _p2_secret_key) = p2_state.finish().or(Err(()))?;
-            let (p3_group_key, _p3_secret_key) = p3_state.finish().or(Err(()))?;
+        // A dealer cannot carry empty commitments either.
+        let mut empty = dealer.clone();
+        empty.commitments.points.clear();
+        assert_eq!(
+            Participant::from_bytes(&empty.to_bytes()).unwrap_err(),
+            Error::SerialisationError
+        );
 
-            assert!(p1_group_key.0.compress() == p2_group_key.0.compress());
-            assert!(p2_group_key.0.compress() == p3_group_key.0.compress());
+        // Truncated encodings are rejected rather than panicking.  Only the
+        // share versions are optional, for compatibility with older encodings.
+        let dealer_len = dealer_bytes.len() - 1 - dealer.share_versions.len();
+        for len in 0..dealer_len {
+            assert!(Participant::from_bytes(&dealer_bytes[..len]).is_err());
+        }
+        let signer_len = signer_bytes.len() - 1 - signer.share_versions.len();
+        for len in 0..signer_len {
+            assert!(DkgSigner::from_bytes(&signer_bytes[..len]).is_err());
+        }
 
-            Ok(())
+        // A commitment claiming more points than it carries is rejected
+        // before allocating them.
+        let mut bytes = dealer.commitments.to_bytes();
+        bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            VerifiableSecretSharingCommitment::from_bytes(&bytes).unwrap_err(),
+            Error::SerialisationError
+        );
+
+        // The same holds for the coefficients of a dealer.
+        let coefficients_bytes = coefficients.to_bytes();
+        for len in 0..coefficients_bytes.len() {
+            assert_eq!(
+                Coefficients::from_bytes(&coefficients_bytes[..len]).err(),
+                Some(Error::SerialisationError)
+            );
         }
-        assert!(do_test().is_ok());
+        let mut bytes = coefficients_bytes;
+        bytes[0..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            Coefficients::from_bytes(&bytes).err(),
+            Some(Error::SerialisationError)
+        );
     }
 
     #[test]
@@ -3538,18 +8180,12 @@ mod test {
             let (p3, p3coeffs, dh_sk3) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 
             p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p1.index, p1.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p2.index, p2.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p3.index, p3.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
 
@@ -3617,16 +8253,16 @@ mod test {
                 ];
 
                 let p1_state = p1_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
                 let p3_state = p3_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
 
                 let complaints = p2_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p2_my_encrypted_secret_shares, &mut rng);
                 assert!(complaints.is_err());
                 let complaints = complaints.unwrap_err();
@@ -3671,16 +8307,16 @@ mod test {
                 ];
 
                 let p1_state = p1_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
                 let p3_state = p3_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
 
                 let complaints = p2_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p2_my_encrypted_secret_shares, &mut rng);
                 assert!(complaints.is_err());
                 let complaints = complaints.unwrap_err();
@@ -3709,6 +8345,7 @@ mod test {
                         polynomial_evaluation: Scalar::from(42u32),
                     },
                     &dh_key,
+                    ShareVersion::V1,
                     &mut rng,
                 );
                 let p1_my_encrypted_secret_shares = vec![
@@ -3729,16 +8366,16 @@ mod test {
                 ];
 
                 let p1_state = p1_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
                 let p3_state = p3_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
 
                 let complaints = p2_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p2_my_encrypted_secret_shares, &mut rng);
                 assert!(complaints.is_err());
                 let complaints = complaints.unwrap_err();
@@ -3775,18 +8412,918 @@ mod test {
                     p3_their_encrypted_secret_shares[2].clone(),
                 ];
 
-                let p3_state = p3_state
-                    .clone()
-                    .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
-                    .or(Err(()))?;
+                let p3_state = p3_state
+                    .duplicate()
+                    .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
+                    .or(Err(()))?;
+
+                let bad_index = p3_state.blame(&p1_their_encrypted_secret_shares[0], &complaint);
+                assert!(bad_index == 2);
+            }
+
+            Ok(())
+        }
+        assert!(do_test().is_ok());
+    }
+
+    #[test]
+    fn detect_share_nonce_reuse() {
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+
+        let dealers: Vec<(Participant, Coefficients, DHPrivateKey)> = (1..=3)
+            .map(|i| Participant::new_dealer(&params, i, "Φ", rng))
+            .collect();
+        let participants: Vec<Participant> = dealers.iter().map(|d| d.0.clone()).collect();
+        let states: Vec<DistributedKeyGeneration<RoundOne>> = dealers
+            .iter()
+            .map(|(p, coefficients, dh_private_key)| {
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    dh_private_key,
+                    &p.index,
+                    coefficients,
+                    &participants,
+                    "Φ",
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+
+        let mut nonce_lists: Vec<ShareNonceList> =
+            states.iter().map(|s| s.share_nonces().unwrap()).collect();
+        let bytes = nonce_lists[0].to_bytes();
+        assert_eq!(bytes.len(), 8 + 20 * 3);
+        assert_eq!(ShareNonceList::from_bytes(&bytes).unwrap(), nonce_lists[0]);
+        assert!(ShareNonceList::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // Participant 2 collects its shares.
+        let mut my_shares: Vec<EncryptedSecretShare> = states
+            .iter()
+            .map(|s| s.their_encrypted_secret_shares().unwrap()[1].clone())
+            .collect();
+        assert!(states[1]
+            .check_share_nonces(&nonce_lists, &my_shares)
+            .is_ok());
+
+        // Dealer 1 broadcasts a package reusing the nonce of participant 3
+        // for participant 1.
+        let reused = nonce_lists[0].nonces[2].1;
+        nonce_lists[0].nonces[0].1 = reused;
+        assert_eq!(
+            states[1].check_share_nonces(&nonce_lists, &my_shares),
+            Err(Error::NonceReuseByDealer { dealer: 1 })
+        );
+
+        // Broadcasting another nonce than the one reused does not hide it.
+        nonce_lists[0] = states[0].share_nonces().unwrap();
+        my_shares[0].nonce = reused;
+        assert_eq!(
+            states[1].check_share_nonces(&nonce_lists, &my_shares),
+            Err(Error::NonceReuseByDealer { dealer: 1 })
+        );
+    }
+
+    #[test]
+    fn restore_round_one_from_checkpoint() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        let dealers: Vec<(Participant, Coefficients, DHPrivateKey)> = (1..=3)
+            .map(|i| Participant::new_dealer(&params, i, "Φ", rng))
+            .collect();
+        let participants: Vec<Participant> = dealers.iter().map(|d| d.0.clone()).collect();
+        let mut states: Vec<DistributedKeyGeneration<RoundOne>> = dealers
+            .iter()
+            .map(|(p, coefficients, dh_private_key)| {
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    dh_private_key,
+                    &p.index,
+                    coefficients,
+                    &participants,
+                    "Φ",
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+
+        let finish = |states: &[DistributedKeyGeneration<RoundOne>], rng: &mut OsRng| {
+            states
+                .iter()
+                .enumerate()
+                .map(|(i, state)| {
+                    let my_shares = states
+                        .iter()
+                        .map(|s| s.their_encrypted_secret_shares().unwrap()[i].clone())
+                        .collect();
+                    state
+                        .duplicate()
+                        .to_round_two(my_shares, *rng)
+                        .unwrap()
+                        .finish()
+                        .unwrap()
+                })
+                .collect::<Vec<(GroupKey, SecretKey)>>()
+        };
+        let original = finish(&states, &mut rng);
+
+        // Only the coefficients the dealer committed to can be checkpointed.
+        assert_eq!(
+            states[0].checkpoint(&dealers[1].1).err(),
+            Some(Error::InvalidDealerMaterial)
+        );
+        let checkpoint = states[0].checkpoint(&dealers[0].1).unwrap();
+        let bytes = checkpoint.to_bytes();
+        assert_eq!(bytes.len(), 48 + 32 * params.t as usize);
+        assert!(bytes.len() < states[0].to_bytes().len());
+        assert!(DkgCheckpoint::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let checkpoint = DkgCheckpoint::from_bytes(&bytes).unwrap();
+
+        // The restored dealer encrypts the same shares with fresh nonces.
+        let (restored, _) = DistributedKeyGeneration::<RoundOne>::restore_round_one(
+            &checkpoint,
+            &participants,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        let before = states[0].their_encrypted_secret_shares().unwrap();
+        let after = restored.their_encrypted_secret_shares().unwrap();
+        for (before, after) in before.iter().zip(after.iter()) {
+            assert_ne!(before.nonce, after.nonce);
+            assert_eq!(before.receiver_index, after.receiver_index);
+        }
+        states[0] = restored;
+
+        let resumed = finish(&states, &mut rng);
+        for ((group_key, secret_key), (resumed_group_key, resumed_secret_key)) in
+            original.iter().zip(resumed.iter())
+        {
+            assert_eq!(group_key, resumed_group_key);
+            assert_eq!(secret_key, resumed_secret_key);
+        }
+
+        // A checkpoint does not restore a dealer with other commitments.
+        let mut other_participants = participants.clone();
+        other_participants[0] = dealers[1].0.clone();
+        other_participants[0].index = 1;
+        assert!(DistributedKeyGeneration::<RoundOne>::restore_round_one(
+            &checkpoint,
+            &other_participants,
+            "Φ",
+            rng
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn abandon_key_generation() {
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, coeffs, dh_sk) = Participant::new_dealer(&params, i, "Φ", rng);
+            participants.push(p);
+            coefficients.push(coeffs);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        let mut states = Vec::new();
+        let mut their_encrypted_secret_shares = Vec::new();
+        for i in 0..params.n as usize {
+            let (state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &dh_secret_keys[i],
+                &participants[i].index,
+                &coefficients[i],
+                &participants,
+                "Φ",
+                rng,
+            )
+            .unwrap();
+            their_encrypted_secret_shares
+                .push(state.their_encrypted_secret_shares().unwrap().clone());
+            states.push(state);
+        }
+
+        // A checkpoint can be abandoned on its own, without affecting the
+        // state it was taken from.
+        let checkpoint = states[0].checkpoint(&coefficients[0]).unwrap();
+        let record = checkpoint.abandon("operator cancelled");
+        assert_eq!(
+            record,
+            AbortRecord {
+                parameters: params,
+                index: 1,
+                round: 1,
+                reason: "operator cancelled".to_string(),
+            }
+        );
+        assert_eq!(AbortRecord::from_bytes(&record.to_bytes()).unwrap(), record);
+
+        let mut round_two_states = Vec::new();
+        for (i, state) in states.into_iter().enumerate() {
+            let my_encrypted_secret_shares = their_encrypted_secret_shares
+                .iter()
+                .map(|shares| shares[i].clone())
+                .collect();
+            round_two_states.push(state.to_round_two(my_encrypted_secret_shares, rng).unwrap());
+        }
+
+        // The states which are not abandoned finish normally.
+        let abandoned = round_two_states.pop().unwrap();
+        let record = abandoned.abandon("participant 3 timed out");
+        assert_eq!(record.index, 3);
+        assert_eq!(record.round, 2);
+        assert_eq!(AbortRecord::from_bytes(&record.to_bytes()).unwrap(), record);
+
+        let mut group_keys = Vec::new();
+        for state in round_two_states.into_iter() {
+            let (group_key, _secret_key) = state.finish().unwrap();
+            group_keys.push(group_key);
+        }
+        assert!(group_keys[0] == group_keys[1]);
+
+        // A round one state records its own round, and wipes its secrets.
+        let (mut state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &dh_secret_keys[1],
+            &participants[1].index,
+            &coefficients[1],
+            &participants,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        state.state.wipe();
+        assert!(state.state.dh_private_key.is_none());
+        assert!(state.state.their_encrypted_secret_shares.is_none());
+        let record = state.abandon("");
+        assert_eq!((record.index, record.round), (2, 1));
+        assert_eq!(AbortRecord::from_bytes(&record.to_bytes()).unwrap(), record);
+
+        // Malformed records are rejected.
+        let mut bytes = record.to_bytes();
+        bytes[12] = 3;
+        assert!(AbortRecord::from_bytes(&bytes).is_err());
+        let mut bytes = AbortRecord {
+            reason: "é".to_string(),
+            ..record
+        }
+        .to_bytes();
+        assert!(AbortRecord::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        bytes[13] = 1;
+        assert!(AbortRecord::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn duplicate_dh_public_key() {
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, coeffs, dh_sk) = Participant::new_dealer(&params, i, "Φ", rng);
+            participants.push(p);
+            coefficients.push(coeffs);
+            dh_secret_keys.push(dh_sk);
+        }
+
+        // The third participant reuses the DH key of the second one, with a
+        // valid proof of knowledge of its private key.
+        participants[2].dh_public_key = participants[1].dh_public_key.clone();
+        participants[2].proof_of_dh_private_key = crate::nizk::NizkOfSecretKey::prove(
+            &3,
+            &dh_secret_keys[1].0,
+            &participants[1].dh_public_key,
+            "Φ",
+            rng,
+        );
+
+        for i in 0..params.n as usize {
+            let result = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &dh_secret_keys[i],
+                &participants[i].index,
+                &coefficients[i],
+                &participants,
+                "Φ",
+                rng,
+            );
+            assert_eq!(
+                result.err(),
+                Some(Error::DuplicateDhPublicKey {
+                    first: 2,
+                    second: 3
+                })
+            );
+        }
+
+        // A participant of a resharing rejects them as well.
+        let (_, _, dh_sk) = Participant::new_dealer(&params, 4, "Φ", rng);
+        let result =
+            DistributedKeyGeneration::<RoundOne>::new(&params, &dh_sk, &4, &participants, "Φ", rng);
+        assert_eq!(
+            result.err(),
+            Some(Error::DuplicateDhPublicKey {
+                first: 2,
+                second: 3
+            })
+        );
+    }
+
+    #[test]
+    fn wrong_commitment_count() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        for t in [1, 5] {
+            let mut participants = Vec::new();
+            let mut coefficients = Vec::new();
+            let mut dh_secret_keys = Vec::new();
+            for i in 1..=params.n {
+                // The third dealer uses a polynomial of another degree.
+                let dealer_params = match i {
+                    3 => Parameters { n: 3, t },
+                    _ => params,
+                };
+                let (p, coeffs, dh_sk) = Participant::new_dealer(&dealer_params, i, "Φ", rng);
+                participants.push(p);
+                coefficients.push(coeffs);
+                dh_secret_keys.push(dh_sk);
+            }
+            let expected = || Error::WrongCommitmentCount {
+                participant: 3,
+                expected: 2,
+                got: t as usize,
+            };
+
+            let result = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &dh_secret_keys[0],
+                &participants[0].index,
+                &coefficients[0],
+                &participants,
+                "Φ",
+                rng,
+            );
+            assert_eq!(result.err(), Some(expected()));
+
+            // A participant of a resharing rejects the dealer as well.
+            let (_, _, dh_sk) = Participant::new_dealer(&params, 4, "Φ", rng);
+            let result = DistributedKeyGeneration::<RoundOne>::new(
+                &params,
+                &dh_sk,
+                &4,
+                &participants,
+                "Φ",
+                rng,
+            );
+            assert_eq!(result.err(), Some(expected()));
+
+            // So does the deserialisation of the dealer for these parameters.
+            let bytes = participants[2].to_bytes();
+            assert!(Participant::from_bytes(&bytes).is_ok());
+            assert_eq!(
+                Participant::from_bytes_with_parameters(&bytes, &params).err(),
+                Some(expected())
+            );
+            for p in participants[..2].iter() {
+                assert!(Participant::from_bytes_with_parameters(&p.to_bytes(), &params).is_ok());
+            }
+            assert_eq!(expected().participant(), Some(3));
+        }
+
+        // The commitments of a deserialised state are checked too.
+        let mut participants = Vec::new();
+        let mut coefficients = Vec::new();
+        let mut dh_secret_keys = Vec::new();
+        for i in 1..=params.n {
+            let (p, coeffs, dh_sk) = Participant::new_dealer(&params, i, "Φ", rng);
+            participants.push(p);
+            coefficients.push(coeffs);
+            dh_secret_keys.push(dh_sk);
+        }
+        let (mut state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &dh_secret_keys[0],
+            &participants[0].index,
+            &coefficients[0],
+            &participants,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        state.state.their_commitments.as_mut().unwrap()[1]
+            .points
+            .push(RistrettoPoint::random(&mut rng));
+        assert_eq!(
+            DistributedKeyGeneration::<RoundOne>::from_bytes(&state.to_bytes()).err(),
+            Some(Error::WrongCommitmentCount {
+                participant: 2,
+                expected: 2,
+                got: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn complaint_flood() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        let dealers: Vec<(Participant, Coefficients, DHPrivateKey)> = (1..=3)
+            .map(|i| Participant::new_dealer(&params, i, "Φ", rng))
+            .collect();
+        let participants: Vec<Participant> = dealers.iter().map(|d| d.0.clone()).collect();
+        let states: Vec<DistributedKeyGeneration<RoundOne>> = dealers
+            .iter()
+            .map(|(p, coefficients, dh_private_key)| {
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    dh_private_key,
+                    &p.index,
+                    coefficients,
+                    &participants,
+                    "Φ",
+                    rng,
+                )
+                .unwrap()
+                .0
+            })
+            .collect();
+        let mut shares: Vec<Vec<EncryptedSecretShare>> = states
+            .iter()
+            .map(|state| state.their_encrypted_secret_shares().unwrap().clone())
+            .collect();
+        let my_shares = |shares: &Vec<Vec<EncryptedSecretShare>>, index: usize| {
+            shares
+                .iter()
+                .map(|s| s[index - 1].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Participant 1 sends a bad share to participant 2.
+        shares[0][1].encrypted_polynomial_evaluation = [42; 32];
+        let bad_share = shares[0][1].clone();
+        let genuine = match states[1]
+            .duplicate()
+            .to_round_two(my_shares(&shares, 2), rng)
+        {
+            Err(Error::Complaint(complaints)) => complaints[0].clone(),
+            _ => panic!("expected a complaint"),
+        };
+        assert_eq!(genuine.ciphertext_hash, bad_share.hash());
+        let p3_state = states[2]
+            .duplicate()
+            .to_round_two(my_shares(&shares, 3), rng)
+            .unwrap();
+
+        // Participants 1 and 3 flood the adjudicator with garbage complaints,
+        // with or without a valid point as disclosed key.
+        let garbage = |maker_index: u32, rng: &mut OsRng| Complaint {
+            maker_index,
+            accused_index: 2,
+            dh_key: match rng.gen::<bool>() {
+                true => RistrettoPoint::random(&mut *rng).compress().to_bytes(),
+                false => rng.gen(),
+            },
+            ciphertext_hash: rng.gen(),
+            proof: ComplaintProof {
+                a1: RistrettoPoint::random(&mut *rng),
+                a2: RistrettoPoint::random(&mut *rng),
+                z: Scalar::random(&mut *rng),
+            },
+        };
+        assert!(garbage(1, &mut rng)
+            .verify(
+                &RistrettoPoint::random(&mut rng),
+                &RistrettoPoint::random(&mut rng)
+            )
+            .is_err());
+
+        let mut adjudicator = ComplaintAdjudicator::new(&p3_state);
+        let mut adjudicated = 0;
+        let mut rejected = 0;
+        for i in 0..1000 {
+            if i == 500 {
+                // The genuine complaint among them blames the accused.
+                assert_eq!(adjudicator.adjudicate(&bad_share, &genuine), Ok(1));
+            }
+            for maker_index in [1, 3] {
+                match adjudicator.adjudicate(
+                    &shares[1][maker_index as usize - 1],
+                    &garbage(maker_index, &mut rng),
+                ) {
+                    Ok(blamed) => {
+                        assert_eq!(blamed, maker_index);
+                        adjudicated += 1;
+                    }
+                    Err(error) => {
+                        assert_eq!(error, Error::ComplaintQuotaExceeded(maker_index));
+                        rejected += 1;
+                    }
+                }
+            }
+        }
+        // At most n - 1 complaints of each maker were looked at.
+        assert_eq!(adjudicated, 4);
+        assert_eq!(rejected, 1996);
+
+        // Malformed evidence is rejected before any other check.
+        let mut self_accusation = genuine.clone();
+        self_accusation.accused_index = self_accusation.maker_index;
+        assert!(self_accusation
+            .verify(
+                &participants[1].dh_public_key,
+                &participants[1].dh_public_key
+            )
+            .is_err());
+        let mut identity_key = genuine.clone();
+        identity_key.dh_key = RistrettoPoint::identity().compress().to_bytes();
+        assert!(identity_key
+            .verify(
+                &participants[1].dh_public_key,
+                &participants[0].dh_public_key
+            )
+            .is_err());
+        assert!(genuine
+            .verify(
+                &participants[1].dh_public_key,
+                &participants[0].dh_public_key
+            )
+            .is_ok());
+
+        // A complaint about another ciphertext blames its maker.
+        let mut other_ciphertext = genuine.clone();
+        other_ciphertext.ciphertext_hash = shares[1][1].hash();
+        assert_eq!(p3_state.blame(&bad_share, &other_ciphertext), 2);
+        assert_eq!(Complaint::from_bytes(&genuine.to_bytes()).unwrap(), genuine);
+    }
+
+    #[test]
+    fn secret_key_conditional_select() {
+        let mut rng = OsRng;
+        let a = SecretKey {
+            index: 1,
+            key: Scalar::random(&mut rng),
+        };
+        let b = SecretKey {
+            index: 2,
+            key: Scalar::random(&mut rng),
+        };
+
+        for choice in [0u8, 1] {
+            let expected = match choice {
+                0 => &a,
+                _ => &b,
+            };
+            let selected = SecretKey::conditional_select(&a, &b, Choice::from(choice));
+            assert_eq!(&selected, expected);
+            assert_eq!(selected.to_public(), expected.to_public());
+        }
+    }
+
+    #[test]
+    fn error_kinds() {
+        let complaint = Complaint {
+            maker_index: 1,
+            accused_index: 2,
+            dh_key: [0u8; 32],
+            ciphertext_hash: [0u8; 32],
+            proof: ComplaintProof {
+                a1: RistrettoPoint::identity(),
+                a2: RistrettoPoint::identity(),
+                z: Scalar::zero(),
+            },
+        };
+        let errors = vec![
+            Error::SerialisationError,
+            Error::DecryptionError,
+            Error::ShareVerificationError,
+            Error::ComplaintVerificationError,
+            Error::InvalidGroupKey,
+            Error::InvalidProofOfKnowledge,
+            Error::MissingShares,
+            Error::NoEncryptedShares,
+            Error::Complaint(vec![complaint]),
+            Error::InvalidNumberOfParticipants(2, 3),
+            Error::TooManyInvalidParticipants(vec![3, 1]),
+            Error::InvalidDealerMaterial,
+            Error::InvalidDealerOptions("weight"),
+            Error::InvalidSignature,
+            Error::ReplayedSignature,
+            Error::InvalidAttestation(4),
+            Error::MissingAttestation(5),
+            Error::UnknownParticipant(6),
+            Error::DuplicateParticipant(7),
+            Error::NonCanonicalPoint,
+            Error::IdentityPoint,
+            Error::UnsupportedShareVersion {
+                dealer: 8,
+                version: ShareVersion::DEFAULT,
+            },
+            Error::WrongCommitmentCount {
+                participant: 10,
+                expected: 2,
+                got: 5,
+            },
+            Error::DuplicateCommitmentShare {
+                signer: 11,
+                position: 3,
+            },
+            Error::CommitmentBatchLength {
+                signer: 12,
+                expected: 4,
+                got: 2,
+            },
+            Error::CommitmentIndexOutOfRange {
+                participant: 13,
+                position: 3,
+                len: 2,
+            },
+            Error::StorageError("storage".to_string()),
+            Error::InvalidSplitRecord,
+            Error::InvalidResolutionRecord,
+            Error::InvalidRefreshProof,
+            Error::NonceReuseByDealer { dealer: 14 },
+            Error::InvalidTranscript {
+                participant: 15,
+                reason: "reason",
+            },
+            Error::DuplicateDhPublicKey {
+                first: 16,
+                second: 17,
+            },
+            Error::ComplaintQuotaExceeded(9),
+            Error::ExternalDhKey,
+            Error::InvalidEnrollmentShare,
+            Error::Custom("custom".to_string()),
+        ];
+
+        for error in errors.iter() {
+            // This match has no wildcard, so that new variants must be
+            // classified here as well.
+            let (kind, participant) = match error {
+                Error::SerialisationError => (ErrorKind::Serialization, None),
+                Error::DecryptionError => (ErrorKind::Misbehaviour { participant: None }, None),
+                Error::ShareVerificationError => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                Error::ComplaintVerificationError => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                Error::InvalidGroupKey => (ErrorKind::Misbehaviour { participant: None }, None),
+                Error::InvalidProofOfKnowledge => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                Error::MissingShares => (ErrorKind::Retryable, None),
+                Error::NoEncryptedShares => (ErrorKind::Retryable, None),
+                Error::Complaint(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(2),
+                    },
+                    Some(2),
+                ),
+                Error::InvalidNumberOfParticipants(_, _) => (ErrorKind::Configuration, None),
+                Error::TooManyInvalidParticipants(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(3),
+                    },
+                    Some(3),
+                ),
+                Error::InvalidDealerMaterial => (ErrorKind::Configuration, None),
+                Error::InvalidDealerOptions(_) => (ErrorKind::Configuration, None),
+                Error::InvalidSignature => (ErrorKind::Misbehaviour { participant: None }, None),
+                Error::ReplayedSignature => (ErrorKind::Misbehaviour { participant: None }, None),
+                Error::InvalidAttestation(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(4),
+                    },
+                    Some(4),
+                ),
+                Error::MissingAttestation(_) => (ErrorKind::Retryable, Some(5)),
+                Error::UnknownParticipant(_) => (ErrorKind::Configuration, Some(6)),
+                Error::DuplicateParticipant(_) => (ErrorKind::Configuration, Some(7)),
+                Error::NonCanonicalPoint => (ErrorKind::Serialization, None),
+                Error::IdentityPoint => (ErrorKind::Serialization, None),
+                Error::UnsupportedShareVersion { .. } => (ErrorKind::Configuration, Some(8)),
+                Error::WrongCommitmentCount { .. } => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(10),
+                    },
+                    Some(10),
+                ),
+                Error::DuplicateCommitmentShare { .. } => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(11),
+                    },
+                    Some(11),
+                ),
+                Error::CommitmentBatchLength { .. } => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(12),
+                    },
+                    Some(12),
+                ),
+                Error::CommitmentIndexOutOfRange { .. } => (ErrorKind::Configuration, Some(13)),
+                Error::StorageError(_) => (ErrorKind::Retryable, None),
+                Error::InvalidSplitRecord => (ErrorKind::Misbehaviour { participant: None }, None),
+                Error::InvalidResolutionRecord => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                Error::InvalidRefreshProof => (ErrorKind::Misbehaviour { participant: None }, None),
+                Error::NonceReuseByDealer { .. } => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(14),
+                    },
+                    Some(14),
+                ),
+                Error::InvalidTranscript { .. } => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(15),
+                    },
+                    Some(15),
+                ),
+                Error::DuplicateDhPublicKey { .. } => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(16),
+                    },
+                    Some(16),
+                ),
+                Error::ComplaintQuotaExceeded(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(9),
+                    },
+                    Some(9),
+                ),
+                Error::ExternalDhKey => (ErrorKind::Configuration, None),
+                Error::InvalidEnrollmentShare => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                Error::Custom(_) => (ErrorKind::Configuration, None),
+            };
+            assert_eq!(error.kind(), kind, "{:?}", error);
+            assert_eq!(error.participant(), participant, "{:?}", error);
+
+            let generic = Error::from_code(error.code()).unwrap();
+            assert_eq!(
+                core::mem::discriminant(&generic),
+                core::mem::discriminant(error)
+            );
+        }
+
+        let mut codes: Vec<u32> = errors.iter().map(Error::code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+    }
+
+    #[test]
+    fn error_codes() {
+        // These codes are part of the FFI and must never change.
+        assert_eq!(Error::SerialisationError.code(), 1);
+        assert_eq!(Error::Complaint(Vec::new()).code(), 9);
+        assert_eq!(Error::UnknownParticipant(3).code(), 18);
+        assert_eq!(Error::InvalidRefreshProof.code(), 30);
+        assert_eq!(Error::NonceReuseByDealer { dealer: 2 }.code(), 31);
+        assert_eq!(Error::Custom("custom".to_string()).code(), 33);
+
+        assert_eq!(Error::from_code(18), Some(Error::UnknownParticipant(0)));
+        assert_eq!(Error::from_code(0), None);
+        assert_eq!(Error::from_code(38), None);
+        for code in 1..=37 {
+            assert_eq!(Error::from_code(code).unwrap().code(), code);
+        }
+    }
+
+    #[test]
+    fn duplicate_secrets() {
+        use crate::precomputation::generate_commitment_share_lists;
+        use crate::signature::{compute_message_hash, SignatureAggregator};
+
+        let params = Parameters { n: 3, t: 2 };
+        let (_, coefficients, dh_private_key) = Participant::new_dealer(&params, 1, "Φ", OsRng);
+
+        let copy = coefficients.duplicate();
+        assert_eq!(copy.to_bytes(), coefficients.to_bytes());
+        drop(coefficients);
+        assert_eq!(copy.0.len(), 2);
+
+        let copy = dh_private_key.duplicate();
+        assert_eq!(copy, dh_private_key);
+        drop(dh_private_key);
+        assert_ne!(copy.0, Scalar::zero());
+
+        let identity_key = IdentityKey::new(OsRng);
+        assert_eq!(
+            identity_key.duplicate().public_key(),
+            identity_key.public_key()
+        );
+
+        // A duplicated secret key still produces valid partial signatures.
+        let key = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &key);
+        let secret_key = SecretKey { index: 1, key };
+        let copy = secret_key.duplicate();
+        drop(secret_key);
+
+        let (public_comshares, secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let mut secret_comshares = secret_comshares.duplicate();
+        let message_hash = compute_message_hash(b"CONTEXT", b"MESSAGE");
+        let mut aggregator =
+            SignatureAggregator::new(Parameters { n: 1, t: 1 }, group_key, b"CONTEXT", b"MESSAGE");
+        aggregator
+            .include_signer(1, public_comshares.commitments[0], copy.to_public())
+            .unwrap();
+        let signers = aggregator.get_signers().clone();
+        let partial = copy
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+    }
+
+    #[test]
+    fn commitment_point_access() {
+        let params = Parameters { n: 3, t: 2 };
+        let (p1, _, _) = Participant::new_dealer(&params, 1, "Φ", OsRng);
+        let mut commitments = p1.commitments;
+        assert_eq!(commitments.points().len(), 2);
+        assert_eq!(commitments.point(0), commitments.public_key());
+        assert!(commitments.point(2).is_none());
+
+        let point = &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng);
+        commitments.set_point(1, point).unwrap();
+        assert_eq!(commitments.point(1), Some(&point));
+
+        let before = commitments.clone();
+        assert_eq!(
+            commitments.set_point(2, point),
+            Err(Error::CommitmentIndexOutOfRange {
+                participant: 1,
+                position: 2,
+                len: 2,
+            })
+        );
+        assert_eq!(commitments, before);
+    }
 
-                let bad_index = p3_state.blame(&p1_their_encrypted_secret_shares[0], &complaint);
-                assert!(bad_index == 2);
-            }
+    #[test]
+    fn group_key_strict_deserialisation() {
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng));
+        let bytes = group_key.to_bytes();
+        assert_eq!(GroupKey::from_bytes_strict(&bytes).unwrap(), group_key);
+
+        // The identity is accepted by `from_bytes` only, unless with the
+        // `strict` feature.
+        let identity = RistrettoPoint::identity().compress().to_bytes();
+        #[cfg(not(feature = "strict"))]
+        assert!(GroupKey::from_bytes(&identity).is_ok());
+        assert_eq!(
+            GroupKey::from_bytes_strict(&identity),
+            Err(Error::IdentityPoint)
+        );
 
-            Ok(())
+        // The field modulus 2^255 - 19 is not a canonical field element.
+        let mut modulus = [0xffu8; 32];
+        modulus[0] = 0xed;
+        modulus[31] = 0x7f;
+        // A negative field element is not a canonical encoding.
+        let mut negative = bytes;
+        negative[0] |= 1;
+        let mut high_bit = bytes;
+        high_bit[31] |= 0x80;
+
+        for encoding in [modulus, negative, high_bit] {
+            #[cfg(not(feature = "strict"))]
+            assert_eq!(
+                GroupKey::from_bytes(&encoding),
+                Err(Error::SerialisationError)
+            );
+            assert_eq!(
+                GroupKey::from_bytes_strict(&encoding),
+                Err(Error::NonCanonicalPoint)
+            );
         }
-        assert!(do_test().is_ok());
     }
 
     #[test]
@@ -3800,18 +9337,12 @@ mod test {
             let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 
             p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p1.index, p1.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p2.index, p2.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p3.index, p3.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
 
@@ -3887,9 +9418,9 @@ mod test {
                 let bytes = p1_dh_sk.to_bytes();
                 assert_eq!(p1_dh_sk, DHPrivateKey::from_bytes(&bytes).unwrap());
 
-                let bytes = p1.proof_of_secret_key.as_ref().unwrap().to_bytes();
+                let bytes = p1.proof_of_secret_key.to_bytes();
                 assert_eq!(
-                    p1.proof_of_secret_key.unwrap(),
+                    p1.proof_of_secret_key,
                     NizkOfSecretKey::from_bytes(&bytes).unwrap()
                 );
 
@@ -3910,19 +9441,19 @@ mod test {
                 // Continue KeyGen
 
                 let p1_state = p1_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
                 let p2_state = p2_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
                 let p3_state = p3_state
-                    .clone()
+                    .duplicate()
                     .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
                     .or(Err(()))?;
 
-                let (p1_group_key, _p1_secret_key) = p1_state.clone().finish().or(Err(()))?;
+                let (p1_group_key, _p1_secret_key) = p1_state.duplicate().finish().or(Err(()))?;
                 let (p2_group_key, _p2_secret_key) = p2_state.finish().or(Err(()))?;
                 let (p3_group_key, _p3_secret_key) = p3_state.finish().or(Err(()))?;
 
@@ -3948,6 +9479,8 @@ mod test {
                     receiver_index: 2,
                     nonce: [0; 16],
                     encrypted_polynomial_evaluation: [0; 32],
+                    version: ShareVersion::V1,
+                    tag: [0; 32],
                 };
 
                 let p1_my_encrypted_secret_shares = vec![
@@ -4020,18 +9553,12 @@ mod test {
             let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 
             p1.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p1.index, p1.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p2.index, p2.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
             p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
                 .verify(&p3.index, p3.public_key().unwrap(), "Φ")
                 .or(Err(()))?;
 
@@ -4115,11 +9642,7 @@ mod test {
             let p3_public_key = p3_secret_key.to_public();
 
             // The order does not matter
-            let commitments = [
-                p2.commitments.unwrap(),
-                p3.commitments.unwrap(),
-                p1.commitments.unwrap(),
-            ];
+            let commitments = [p2.commitments, p3.commitments, p1.commitments];
 
             assert!(p1_public_key.verify(&commitments).is_ok());
             assert!(p2_public_key.verify(&commitments).is_ok());
@@ -4143,4 +9666,1042 @@ mod test {
         }
         assert!(do_test().is_ok());
     }
+
+    #[test]
+    fn keygen_from_pregenerated_dealer_material() {
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+
+        let mut pool = Participant::pregenerate_dealer_material(&params, 2, rng);
+        assert_eq!(pool.len(), 2);
+
+        let mut first_commitments = None;
+        for context in ["ceremony 1", "ceremony 2"] {
+            let material = pool.pop().unwrap();
+            let (p1, p1coeffs, p1_dh_sk) =
+                Participant::new_dealer_from_material(&params, 1, context, material, rng).unwrap();
+            let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, context, rng);
+            let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, context, rng);
+
+            // The proofs are bound to the ceremony the material was consumed in.
+            let proof = &p1.proof_of_secret_key;
+            assert!(proof.verify(&1, p1.public_key().unwrap(), context).is_ok());
+            assert!(proof.verify(&1, p1.public_key().unwrap(), "Φ").is_err());
+            assert!(proof.verify(&2, p1.public_key().unwrap(), context).is_err());
+            assert!(p1
+                .proof_of_dh_private_key
+                .verify(&1, &p1.dh_public_key, context)
+                .is_ok());
+
+            match &first_commitments {
+                None => first_commitments = Some(p1.commitments.clone()),
+                Some(first) => assert!(first.points != p1.commitments.points),
+            }
+
+            let participants: Vec<Participant> = vec![p1.clone(), p2.clone(), p3.clone()];
+            let dealers = [
+                (&p1, &p1coeffs, &p1_dh_sk),
+                (&p2, &p2coeffs, &p2_dh_sk),
+                (&p3, &p3coeffs, &p3_dh_sk),
+            ];
+
+            let mut states = Vec::new();
+            let mut their_encrypted_secret_shares = Vec::new();
+            for (p, coeffs, dh_sk) in dealers.iter() {
+                let (state, participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    dh_sk,
+                    &p.index,
+                    coeffs,
+                    &participants,
+                    context,
+                    rng,
+                )
+                .unwrap();
+                assert!(participant_lists.misbehaving_participants.is_none());
+                their_encrypted_secret_shares
+                    .push(state.their_encrypted_secret_shares().unwrap().clone());
+                states.push(state);
+            }
+
+            let mut group_keys = Vec::new();
+            let mut public_keys = Vec::new();
+            for (i, state) in states.into_iter().enumerate() {
+                let my_encrypted_secret_shares = their_encrypted_secret_shares
+                    .iter()
+                    .map(|shares| shares[i].clone())
+                    .collect();
+                let state = state.to_round_two(my_encrypted_secret_shares, rng).unwrap();
+                let (group_key, secret_key) = state.finish().unwrap();
+                group_keys.push(group_key);
+                public_keys.push(secret_key.to_public());
+            }
+
+            assert!(group_keys[0] == group_keys[1]);
+            assert!(group_keys[1] == group_keys[2]);
+
+            let commitments = [p1.commitments, p2.commitments, p3.commitments];
+            for public_key in public_keys.iter() {
+                assert!(public_key.verify(&commitments).is_ok());
+            }
+        }
+
+        // Every pre-generated tuple has been consumed.
+        assert!(pool.pop().is_none());
+
+        // Material must match the threshold of the ceremony it is used in.
+        let material = Participant::pregenerate_dealer_material(&params, 1, rng)
+            .pop()
+            .unwrap();
+        let result = Participant::new_dealer_from_material(
+            &Parameters { n: 5, t: 3 },
+            1,
+            "Φ",
+            material,
+            rng,
+        );
+        assert_eq!(result.err(), Some(Error::InvalidDealerMaterial));
+    }
+
+    #[test]
+    fn dealer_builder() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        // Without options, the dealer is the same as with `new_dealer`.
+        let (p1, p1coeffs, p1_dh_sk) = Participant::dealer(&params, 1, "Φ")
+            .with_rng(&mut rng)
+            .build()
+            .unwrap();
+        assert_eq!(p1.weight, 1);
+        assert!(p1.device_id.is_none());
+        assert!(p1
+            .proof_of_secret_key
+            .verify(&1, p1.public_key().unwrap(), "Φ")
+            .is_ok());
+        assert!(p1
+            .proof_of_dh_private_key
+            .verify(&1, &p1.dh_public_key, "Φ")
+            .is_ok());
+        assert_eq!(p1.dh_public_key.0, &RISTRETTO_BASEPOINT_TABLE * &p1_dh_sk.0);
+        assert_eq!(p1coeffs.0.len(), 2);
+        // Nor is its encoding extended.
+        let (p, _, _) = Participant::new_dealer(&params, 1, "Φ", rng);
+        assert_eq!(p1.to_bytes().len(), p.to_bytes().len());
+
+        // The default random number generator is the operating system's.
+        let (p2, _, _) = Participant::dealer(&params, 2, "Φ").build().unwrap();
+        assert!(p2
+            .proof_of_secret_key
+            .verify(&2, p2.public_key().unwrap(), "Φ")
+            .is_ok());
+
+        // The weight and the device identifier survive serialisation.
+        let (p3, _, _) = Participant::dealer(&params, 3, "Φ")
+            .with_rng(&mut rng)
+            .with_weight(2)
+            .with_device_id(b"hsm-3")
+            .build()
+            .unwrap();
+        assert_eq!(p3.weight, 2);
+        assert_eq!(p3.device_id.as_deref(), Some(&b"hsm-3"[..]));
+        let decoded = Participant::from_bytes(&p3.to_bytes()).unwrap();
+        assert_eq!(decoded.weight, 2);
+        assert_eq!(decoded.device_id, p3.device_id);
+        assert_eq!(decoded.share_versions, p3.share_versions);
+
+        let (p1, _, _) = Participant::dealer(&params, 1, "Φ")
+            .with_device_id(b"laptop")
+            .build()
+            .unwrap();
+        let decoded = Participant::from_bytes(&p1.to_bytes()).unwrap();
+        assert_eq!(decoded.weight, 1);
+        assert_eq!(decoded.device_id.as_deref(), Some(&b"laptop"[..]));
+
+        // Pre-generated material is consumed by the builder.
+        let material = Participant::pregenerate_dealer_material(&params, 1, rng)
+            .pop()
+            .unwrap();
+        let points = material.points.clone();
+        let (p1, _, _) = Participant::dealer(&params, 1, "Φ")
+            .from_pregenerated(material)
+            .with_device_id(b"laptop")
+            .build()
+            .unwrap();
+        assert_eq!(p1.commitments.points, points);
+        assert!(p1
+            .proof_of_secret_key
+            .verify(&1, p1.public_key().unwrap(), "Φ")
+            .is_ok());
+
+        // Each inconsistent option is rejected.
+        let result = Participant::dealer(&params, 1, "Φ").with_weight(0).build();
+        assert!(matches!(result, Err(Error::InvalidDealerOptions(_))));
+
+        let result = Participant::dealer(&params, 1, "Φ").with_weight(4).build();
+        assert!(matches!(result, Err(Error::InvalidDealerOptions(_))));
+
+        let result = Participant::dealer(&params, 1, "Φ")
+            .with_device_id(b"")
+            .build();
+        assert!(matches!(result, Err(Error::InvalidDealerOptions(_))));
+
+        let result = Participant::dealer(&params, 1, "Φ")
+            .with_device_id(&[0u8; MAX_DEVICE_ID_LENGTH + 1])
+            .build();
+        assert!(matches!(result, Err(Error::InvalidDealerOptions(_))));
+
+        let material = Participant::pregenerate_dealer_material(&params, 1, rng)
+            .pop()
+            .unwrap();
+        let result = Participant::dealer(&params, 1, "Φ")
+            .from_pregenerated(material)
+            .with_weight(2)
+            .build();
+        assert!(matches!(result, Err(Error::InvalidDealerOptions(_))));
+
+        let material = Participant::pregenerate_dealer_material(&params, 1, rng)
+            .pop()
+            .unwrap();
+        let result = Participant::dealer(&Parameters { n: 5, t: 3 }, 1, "Φ")
+            .from_pregenerated(material)
+            .build();
+        assert_eq!(result.err(), Some(Error::InvalidDealerMaterial));
+    }
+
+    #[test]
+    fn keygen_with_shared_identity_keys() {
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+
+        let identity_keys: Vec<IdentityKey> = (0..3).map(|_| IdentityKey::new(rng)).collect();
+        assert_eq!(
+            IdentityKey::from_bytes(&identity_keys[0].to_bytes()).unwrap(),
+            identity_keys[0]
+        );
+
+        // Run the first round of two groups concurrently, with the same
+        // identity keys.
+        let mut groups = Vec::new();
+        for context in ["group A", "group B"] {
+            let mut participants = Vec::new();
+            let mut coefficients = Vec::new();
+            for (i, identity_key) in identity_keys.iter().enumerate() {
+                let (p, coeffs) = Participant::new_dealer_with_identity_key(
+                    &params,
+                    i as u32 + 1,
+                    identity_key,
+                    context,
+                    rng,
+                );
+                assert_eq!(p.dh_public_key, identity_key.public_key());
+                participants.push(p);
+                coefficients.push(coeffs);
+            }
+
+            let mut states = Vec::new();
+            let mut their_encrypted_secret_shares = Vec::new();
+            for (i, identity_key) in identity_keys.iter().enumerate() {
+                let (state, _participant_lists) =
+                    DistributedKeyGeneration::<RoundOne>::new_initial_with_identity_key(
+                        &params,
+                        identity_key,
+                        &participants[i].index,
+                        &coefficients[i],
+                        &participants,
+                        context,
+                        rng,
+                    )
+                    .unwrap();
+                their_encrypted_secret_shares
+                    .push(state.their_encrypted_secret_shares().unwrap().clone());
+
+                // The salt survives serialisation.
+                let bytes = state.to_bytes();
+                assert_eq!(
+                    DistributedKeyGeneration::<RoundOne>::from_bytes(&bytes)
+                        .unwrap()
+                        .state,
+                    state.state
+                );
+                states.push(state);
+            }
+            groups.push((participants, states, their_encrypted_secret_shares));
+        }
+
+        // Shares dealt in one group cannot be decrypted in the other, whose
+        // share keys differ although the DH keys are the same.
+        let dh_output = (identity_keys[1].public_key().0 * identity_keys[0].0 .0)
+            .compress()
+            .to_bytes();
+        let key_a = share_key(&dh_output, Some(&share_key_salt("group A")));
+        let key_b = share_key(&dh_output, Some(&share_key_salt("group B")));
+        assert!(key_a != key_b);
+        assert!(key_a != dh_output);
+
+        let share_from_a = &groups[0].2[1][0];
+        assert!(decrypt_share(share_from_a, &key_a).is_ok());
+        assert!(
+            decrypt_share(share_from_a, &dh_output).map_or(true, |share| share
+                .verify(&groups[0].0[1].commitments)
+                .is_err())
+        );
+
+        // Replaying the shares of group A into group B raises complaints
+        // against their dealers.
+        let state_b = groups[1].1[0].duplicate();
+        let replayed: Vec<EncryptedSecretShare> =
+            groups[0].2.iter().map(|shares| shares[0].clone()).collect();
+        match state_b.to_round_two(replayed, rng) {
+            Err(Error::Complaint(complaints)) => assert_eq!(complaints.len(), 3),
+            _ => panic!("replayed shares must be rejected"),
+        }
+
+        // Each group finishes with its own shares.
+        let mut group_keys = Vec::new();
+        for (participants, states, their_encrypted_secret_shares) in groups.into_iter() {
+            let mut group_key = None;
+            for (i, state) in states.into_iter().enumerate() {
+                let my_encrypted_secret_shares = their_encrypted_secret_shares
+                    .iter()
+                    .map(|shares| shares[i].clone())
+                    .collect();
+                let state = state.to_round_two(my_encrypted_secret_shares, rng).unwrap();
+                let (gk, sk) = state.finish().unwrap();
+                let commitments: Vec<VerifiableSecretSharingCommitment> =
+                    participants.iter().map(|p| p.commitments.clone()).collect();
+                assert!(sk.to_public().verify(&commitments).is_ok());
+                group_key = Some(gk);
+            }
+            group_keys.push(group_key.unwrap());
+        }
+        assert!(group_keys[0] != group_keys[1]);
+    }
+
+    #[test]
+    fn individual_public_key_aggregate() {
+        let params = Parameters { n: 5, t: 3 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let public_keys: Vec<(u32, IndividualPublicKey)> = secret_keys
+            .iter()
+            .map(|sk| (sk.index, sk.to_public()))
+            .collect();
+
+        // All n public keys.
+        assert!(IndividualPublicKey::aggregate(&params, &public_keys).unwrap() == group_key);
+
+        // Any valid t-subset, in any order.
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4], [3, 4, 2]] {
+            let keys: Vec<(u32, IndividualPublicKey)> =
+                subset.iter().map(|i| public_keys[*i].clone()).collect();
+            assert!(IndividualPublicKey::aggregate(&params, &keys).unwrap() == group_key);
+        }
+
+        // Too few public keys.
+        assert_eq!(
+            IndividualPublicKey::aggregate(&params, &public_keys[..2]),
+            Err(Error::InvalidNumberOfParticipants(2, 3))
+        );
+
+        // Duplicate indices.
+        let keys = vec![
+            public_keys[0].clone(),
+            public_keys[1].clone(),
+            public_keys[1].clone(),
+        ];
+        assert_eq!(
+            IndividualPublicKey::aggregate(&params, &keys),
+            Err(Error::InvalidGroupKey)
+        );
+
+        // Mismatched indices.
+        let keys = vec![
+            public_keys[0].clone(),
+            public_keys[1].clone(),
+            (4, public_keys[2].1.clone()),
+        ];
+        assert_eq!(
+            IndividualPublicKey::aggregate(&params, &keys),
+            Err(Error::InvalidGroupKey)
+        );
+    }
+
+    #[test]
+    fn enroll_participant_2_out_of_3_into_2_out_of_4() {
+        use crate::precomputation::generate_commitment_share_lists;
+        use crate::signature::{compute_message_hash, SignatureAggregator};
+
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+
+        let Dkg {
+            group_key,
+            participants,
+            dh_secret_keys,
+            secret_keys,
+            ..
+        } = run_full_dkg(&params);
+
+        // Signers 1 and 3 enroll a newcomer with index 4, each with its own
+        // share only.
+        let (newcomer, newcomer_dh_sk) = DkgSigner::new(&params, 4, "Φ", rng);
+        let helpers = [
+            (1, participants[0].dh_public_key.clone()),
+            (3, participants[2].dh_public_key.clone()),
+        ];
+        let contribute = |i: usize, helpers: &[(u32, DHPublicKey)], new_index: u32| {
+            enroll_participant(
+                &params,
+                new_index,
+                &secret_keys[i],
+                &dh_secret_keys[i],
+                helpers,
+                &newcomer.dh_public_key,
+                rng,
+            )
+        };
+
+        assert_eq!(
+            contribute(0, &helpers[..1], 4),
+            Err(Error::InvalidNumberOfParticipants(1, 2))
+        );
+        assert_eq!(
+            contribute(0, &helpers, 3),
+            Err(Error::DuplicateParticipant(3))
+        );
+        assert_eq!(
+            contribute(1, &helpers, 4),
+            Err(Error::UnknownParticipant(2))
+        );
+
+        let contributions = [
+            contribute(0, &helpers, 4).unwrap(),
+            contribute(2, &helpers, 4).unwrap(),
+        ];
+        let contributions: Vec<EnrollmentContribution> = contributions
+            .iter()
+            .map(|c| EnrollmentContribution::from_bytes(&c.to_bytes()).unwrap())
+            .collect();
+        assert!(EnrollmentContribution::from_bytes(&[0u8; 31]).is_err());
+
+        let public_keys: Vec<IndividualPublicKey> =
+            secret_keys.iter().map(SecretKey::to_public).collect();
+        let enroll = |index: u32,
+                      dh_sk: &DHPrivateKey,
+                      contributions: &[EnrollmentContribution],
+                      public_keys: &[IndividualPublicKey]| {
+            SecretKey::from_enrollment(&params, index, dh_sk, contributions, public_keys)
+        };
+
+        // A single contribution does not reveal the share of its helper.
+        let contribution = &contributions[0];
+        let mut dh_key = (contribution.ephemeral_dh_public_key.0 * newcomer_dh_sk.0)
+            .compress()
+            .to_bytes();
+        let masked = decrypt_share(&contribution.encrypted_share, &dh_key).unwrap();
+        dh_key.zeroize();
+        let lambda = calculate_lagrange_coefficients_at(&Scalar::from(4u32), &1, &[1, 3]).unwrap();
+        assert!(masked.polynomial_evaluation != lambda * secret_keys[0].key);
+        assert_eq!(
+            enroll(4, &newcomer_dh_sk, &contributions[..1], &public_keys),
+            Err(Error::InvalidEnrollmentShare)
+        );
+
+        // Another participant cannot derive the newcomer's key.
+        assert!(enroll(3, &dh_secret_keys[2], &contributions, &public_keys).is_err());
+        assert_eq!(
+            enroll(
+                4,
+                &newcomer_dh_sk,
+                &[contributions[0].clone(), contributions[0].clone()],
+                &public_keys
+            ),
+            Err(Error::DuplicateParticipant(1))
+        );
+        assert_eq!(
+            enroll(4, &newcomer_dh_sk, &contributions, &public_keys[..1]),
+            Err(Error::InvalidNumberOfParticipants(1, 2))
+        );
+        assert_eq!(
+            enroll(
+                4,
+                &newcomer_dh_sk,
+                &contributions,
+                &[public_keys[0].clone(), public_keys[0].clone()]
+            ),
+            Err(Error::DuplicateParticipant(1))
+        );
+
+        // A helper contributing with a corrupted share is detected.
+        let corrupted_key = SecretKey {
+            index: 1,
+            key: Scalar::random(&mut OsRng),
+        };
+        let corrupted = enroll_participant(
+            &params,
+            4,
+            &corrupted_key,
+            &dh_secret_keys[0],
+            &helpers,
+            &newcomer.dh_public_key,
+            rng,
+        )
+        .unwrap();
+        assert_eq!(
+            enroll(
+                4,
+                &newcomer_dh_sk,
+                &[corrupted, contributions[1].clone()],
+                &public_keys
+            ),
+            Err(Error::InvalidEnrollmentShare)
+        );
+
+        let p4_sk = enroll(4, &newcomer_dh_sk, &contributions, &public_keys).unwrap();
+        assert_eq!(
+            enroll(4, &newcomer_dh_sk, &contributions, &public_keys[1..]).unwrap(),
+            p4_sk
+        );
+
+        // The newcomer's share lies on the same polynomial.
+        let new_params = Parameters { n: 4, t: 2 };
+        for other in secret_keys.iter() {
+            let keys = [(other.index, other.to_public()), (4, p4_sk.to_public())];
+            assert!(IndividualPublicKey::aggregate(&new_params, &keys).unwrap() == group_key);
+        }
+
+        // The newcomer signs along with signer 2, who did not take part in the enrollment.
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+        let (p4_public_comshares, mut p4_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 4, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(new_params, group_key, &context[..], &message[..]);
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers().clone();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let p2_partial = secret_keys[1]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p2_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        let p4_partial = p4_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p4_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+        aggregator.include_partial_signature(p4_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+
+    #[test]
+    fn eject_participant_2_out_of_3() {
+        use crate::precomputation::generate_commitment_share_lists;
+        use crate::signature::{compute_message_hash, SignatureAggregator};
+
+        let params = Parameters { n: 3, t: 2 };
+        let rng = OsRng;
+        let (group_key, secret_keys) = run_dkg(&params);
+
+        let dh_secret_keys: Vec<DHPrivateKey> = (0..3)
+            .map(|_| DHPrivateKey(Scalar::random(&mut OsRng)))
+            .collect();
+        let roster: Vec<(u32, DHPublicKey)> = dh_secret_keys
+            .iter()
+            .enumerate()
+            .map(|(i, dh_sk)| (i as u32 + 1, DHPublicKey::from(dh_sk)))
+            .collect();
+        let remaining = &roster[..2];
+
+        assert_eq!(
+            eject_participant(&params, 3, 1, &dh_secret_keys[0], &remaining[..1], rng),
+            Err(Error::InvalidNumberOfParticipants(1, 2))
+        );
+        assert!(eject_participant(&params, 3, 1, &dh_secret_keys[0], &roster, rng).is_err());
+        assert_eq!(
+            eject_participant(&params, 3, 3, &dh_secret_keys[2], remaining, rng),
+            Err(Error::UnknownParticipant(3))
+        );
+
+        // Each remaining signer contributes without using its share.
+        let contributions: Vec<EjectionContribution> = (0..2)
+            .map(|i| {
+                let contribution =
+                    eject_participant(&params, 3, i as u32 + 1, &dh_secret_keys[i], remaining, rng)
+                        .unwrap();
+                EjectionContribution::from_bytes(&contribution.to_bytes()).unwrap()
+            })
+            .collect();
+        let bytes = contributions[0].to_bytes();
+        assert!(EjectionContribution::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let refresh = |i: usize, contributions: &[EjectionContribution]| {
+            secret_keys[i].refresh_after_ejection(
+                &params,
+                &dh_secret_keys[i],
+                remaining,
+                contributions,
+            )
+        };
+
+        // Every remaining signer must contribute, once.
+        assert_eq!(refresh(0, &contributions[..1]), Err(Error::MissingShares));
+        assert_eq!(
+            refresh(0, &[contributions[0].clone(), contributions[0].clone()]),
+            Err(Error::DuplicateParticipant(1))
+        );
+
+        // A contribution which would change the group key is rejected.
+        let mut tampered = contributions.clone();
+        tampered[1]
+            .commitment
+            .set_point(0, RISTRETTO_BASEPOINT_TABLE.basepoint())
+            .unwrap();
+        assert_eq!(refresh(0, &tampered), Err(Error::ShareVerificationError));
+
+        let refreshed = [
+            refresh(0, &contributions).unwrap(),
+            refresh(1, &contributions).unwrap(),
+        ];
+        assert_eq!(refreshed[0].index, 1);
+        assert_eq!(refreshed[1].index, 2);
+        assert!(refreshed[0].key != secret_keys[0].key);
+        assert!(refreshed[1].key != secret_keys[1].key);
+
+        let keys: Vec<(u32, IndividualPublicKey)> = refreshed
+            .iter()
+            .map(|sk| (sk.index, sk.to_public()))
+            .collect();
+        assert!(IndividualPublicKey::aggregate(&params, &keys).unwrap() == group_key);
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let sign = |first: &SecretKey, second: &SecretKey| {
+            let (first_public_comshares, mut first_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, first.index, 1);
+            let (second_public_comshares, mut second_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, second.index, 1);
+
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+            aggregator
+                .include_signer(
+                    first.index,
+                    first_public_comshares.commitments[0],
+                    first.into(),
+                )
+                .unwrap();
+            aggregator
+                .include_signer(
+                    second.index,
+                    second_public_comshares.commitments[0],
+                    second.into(),
+                )
+                .unwrap();
+
+            let signers = aggregator.get_signers().clone();
+            let first_partial = first
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut first_secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            let second_partial = second
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut second_secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(first_partial).unwrap();
+            aggregator
+                .include_partial_signature(second_partial)
+                .unwrap();
+
+            aggregator.finalize().unwrap().aggregate()
+        };
+
+        // The remaining signers can still sign with their refreshed shares.
+        let threshold_signature = sign(&refreshed[0], &refreshed[1]).unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // The ejected signer's old share cannot be combined with a refreshed one.
+        assert!(sign(&refreshed[0], &secret_keys[2]).is_err());
+        assert!(sign(&refreshed[1], &secret_keys[2]).is_err());
+    }
+
+    #[test]
+    fn refresh_two_party_2_out_of_2() {
+        use crate::precomputation::generate_commitment_share_lists;
+        use crate::signature::{compute_message_hash, SignatureAggregator};
+
+        let params = Parameters { n: 2, t: 2 };
+        let rng = OsRng;
+        let (group_key, secret_keys) = run_dkg(&params);
+
+        let dh_private_keys = [
+            DHPrivateKey(Scalar::random(&mut OsRng)),
+            DHPrivateKey(Scalar::random(&mut OsRng)),
+        ];
+        let dh_public_keys = [
+            DHPublicKey::from(&dh_private_keys[0]),
+            DHPublicKey::from(&dh_private_keys[1]),
+        ];
+        let start = |signer: usize, secret_key: &SecretKey| {
+            TwoPartyRefresh::new(
+                &params,
+                &group_key,
+                secret_key,
+                &dh_private_keys[signer],
+                secret_keys[1 - signer].index,
+                &dh_public_keys[1 - signer],
+                rng,
+            )
+        };
+
+        assert!(TwoPartyRefresh::new(
+            &Parameters { n: 3, t: 2 },
+            &group_key,
+            &secret_keys[0],
+            &dh_private_keys[0],
+            secret_keys[1].index,
+            &dh_public_keys[1],
+            rng
+        )
+        .is_err());
+        assert_eq!(
+            TwoPartyRefresh::new(
+                &params,
+                &group_key,
+                &secret_keys[0],
+                &dh_private_keys[0],
+                secret_keys[0].index,
+                &dh_public_keys[1],
+                rng
+            )
+            .err()
+            .unwrap(),
+            Error::DuplicateParticipant(secret_keys[0].index)
+        );
+
+        let (first_state, first_message) = start(0, &secret_keys[0]).unwrap();
+        let (second_state, second_message) = start(1, &secret_keys[1]).unwrap();
+
+        // The contributions are only sent encrypted.
+        assert_eq!(first_message.encrypted_delta.version, ShareVersion::V1);
+        assert!(
+            first_message
+                .encrypted_delta
+                .encrypted_polynomial_evaluation
+                != first_state.delta.to_bytes()
+        );
+
+        let first_message = TwoPartyRefreshMessage::from_bytes(&first_message.to_bytes()).unwrap();
+        let (first_refreshed, second_public) =
+            refresh_two_party(first_state, &second_message).unwrap();
+        let (second_refreshed, first_public) =
+            refresh_two_party(second_state, &first_message).unwrap();
+
+        // Each signer computes the refreshed public share of the other one.
+        assert_eq!(first_public, first_refreshed.to_public());
+        assert_eq!(second_public, second_refreshed.to_public());
+        assert!(first_refreshed.key != secret_keys[0].key);
+        assert!(second_refreshed.key != secret_keys[1].key);
+
+        // As with the general refresh, the shares are shifted by a polynomial
+        // of degree 1 with a zero constant term, and still interpolate to the
+        // group key.
+        let first_delta = first_refreshed.key - secret_keys[0].key;
+        let second_delta = second_refreshed.key - secret_keys[1].key;
+        assert_eq!(
+            first_delta * Scalar::from(second_refreshed.index),
+            second_delta * Scalar::from(first_refreshed.index)
+        );
+        let keys = [
+            (first_refreshed.index, first_public),
+            (second_refreshed.index, second_public),
+        ];
+        assert!(IndividualPublicKey::aggregate(&params, &keys).unwrap() == group_key);
+
+        // A message carrying a tampered contribution, or coming from a
+        // signer of another group, is rejected.
+        let (state, _) = start(0, &secret_keys[0]).unwrap();
+        let (_, mut message) = start(1, &secret_keys[1]).unwrap();
+        message.encrypted_delta.encrypted_polynomial_evaluation[0] ^= 1;
+        assert_eq!(
+            refresh_two_party(state, &message).unwrap_err(),
+            Error::InvalidProofOfKnowledge
+        );
+
+        // A signer encrypting another contribution than the one it commits
+        // to, with a valid proof over both, is rejected.
+        let (state, _) = start(0, &secret_keys[0]).unwrap();
+        let lie = SecretShare {
+            sender_index: secret_keys[1].index,
+            receiver_index: secret_keys[0].index,
+            polynomial_evaluation: Scalar::random(&mut OsRng),
+        };
+        let dh_key = (dh_public_keys[0].0 * dh_private_keys[1].0)
+            .compress()
+            .to_bytes();
+        let encrypted_delta = encrypt_share(&lie, &dh_key, ShareVersion::V1, &mut OsRng);
+        let delta_commitment = &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng);
+        let proof = NizkOfSecretKey::prove(
+            &secret_keys[1].index,
+            &secret_keys[1].key,
+            &secret_keys[1].to_public().share,
+            &two_party_refresh_context(&encrypted_delta, &delta_commitment),
+            &mut OsRng,
+        );
+        let message = TwoPartyRefreshMessage {
+            index: secret_keys[1].index,
+            encrypted_delta,
+            delta_commitment,
+            proof,
+        };
+        assert_eq!(
+            refresh_two_party(state, &message).unwrap_err(),
+            Error::ShareVerificationError
+        );
+
+        // The commitment cannot be swapped either.
+        let (state, _) = start(0, &secret_keys[0]).unwrap();
+        let (_, mut message) = start(1, &secret_keys[1]).unwrap();
+        message.delta_commitment = &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng);
+        assert_eq!(
+            refresh_two_party(state, &message).unwrap_err(),
+            Error::InvalidProofOfKnowledge
+        );
+
+        let (_, other_secret_keys) = run_dkg(&params);
+        let (state, _) = start(0, &secret_keys[0]).unwrap();
+        let (_, message) = start(1, &other_secret_keys[1]).unwrap();
+        assert_eq!(
+            refresh_two_party(state, &message).unwrap_err(),
+            Error::InvalidProofOfKnowledge
+        );
+
+        // A contribution encrypted to another DH key cannot be decrypted.
+        let (state, _) = start(0, &secret_keys[0]).unwrap();
+        let (_, message) = TwoPartyRefresh::new(
+            &params,
+            &group_key,
+            &secret_keys[1],
+            &dh_private_keys[1],
+            secret_keys[0].index,
+            &DHPublicKey::from(&DHPrivateKey(Scalar::random(&mut OsRng))),
+            rng,
+        )
+        .unwrap();
+        assert_eq!(
+            refresh_two_party(state, &message).unwrap_err(),
+            Error::DecryptionError
+        );
+
+        let (state, message) = start(0, &secret_keys[0]).unwrap();
+        assert_eq!(
+            refresh_two_party(state, &message).unwrap_err(),
+            Error::DuplicateParticipant(secret_keys[0].index)
+        );
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let sign = |first: &SecretKey, second: &SecretKey| {
+            let (first_public_comshares, mut first_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, first.index, 1);
+            let (second_public_comshares, mut second_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, second.index, 1);
+
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+            aggregator
+                .include_signer(
+                    first.index,
+                    first_public_comshares.commitments[0],
+                    first.into(),
+                )
+                .unwrap();
+            aggregator
+                .include_signer(
+                    second.index,
+                    second_public_comshares.commitments[0],
+                    second.into(),
+                )
+                .unwrap();
+
+            let signers = aggregator.get_signers().clone();
+            let first_partial = first
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut first_secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            let second_partial = second
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut second_secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(first_partial).unwrap();
+            aggregator
+                .include_partial_signature(second_partial)
+                .unwrap();
+
+            aggregator.finalize().unwrap().aggregate()
+        };
+
+        let threshold_signature = sign(&first_refreshed, &second_refreshed).unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // Old and refreshed shares cannot be mixed.
+        assert!(sign(&first_refreshed, &secret_keys[1]).is_err());
+        assert!(sign(&secret_keys[0], &second_refreshed).is_err());
+    }
+
+    #[test]
+    fn compressed_point_cache() {
+        let mut rng = OsRng;
+        let secret_key = SecretKey {
+            index: 1,
+            key: Scalar::random(&mut rng),
+        };
+        let tweak = &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut rng);
+
+        // Tweaking the share through its public field must not serve the
+        // encoding of the former share.
+        let mut public_key = secret_key.to_public();
+        let original = public_key.clone();
+        public_key.share += tweak;
+        assert_eq!(
+            public_key.to_bytes()[4..],
+            public_key.share.compress().to_bytes()
+        );
+        assert_eq!(
+            public_key,
+            IndividualPublicKey::new(1, original.share + tweak)
+        );
+        assert_ne!(public_key, original);
+        assert!(public_key.verify(&[]).is_err());
+
+        // Same for a key whose encoding was taken from its serialisation.
+        let mut decoded = IndividualPublicKey::from_bytes(&original.to_bytes()).unwrap();
+        assert_eq!(decoded, original);
+        decoded.share -= tweak;
+        assert_eq!(
+            decoded.to_bytes()[4..],
+            (original.share - tweak).compress().to_bytes()
+        );
+        decoded.share += tweak;
+        assert_eq!(decoded.to_bytes(), original.to_bytes());
+
+        // Group keys compare and serialise through their encoding.
+        let group_key = GroupKey::new(original.share);
+        assert_eq!(group_key.to_bytes(), original.share.compress().to_bytes());
+        assert_eq!(
+            GroupKey::from_bytes(&group_key.to_bytes()).unwrap(),
+            group_key
+        );
+        assert_eq!(
+            GroupKey::from_bytes_strict(&group_key.to_bytes()).unwrap(),
+            group_key
+        );
+        assert_ne!(GroupKey::new(original.share + tweak), group_key);
+    }
+
+    #[test]
+    fn public_key_package_validation() {
+        let params = Parameters { n: 3, t: 2 };
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng));
+        let public_keys: Vec<IndividualPublicKey> = (1..=3)
+            .map(|i| IndividualPublicKey::new(i, &RISTRETTO_BASEPOINT_TABLE * &Scalar::from(i)))
+            .collect();
+
+        let package = PublicKeyPackage::new(params, group_key, public_keys.clone()).unwrap();
+        let bytes = package.to_bytes();
+        assert_eq!(bytes.len(), 44 + 36 * 3);
+        assert_eq!(PublicKeyPackage::from_bytes(&bytes).unwrap(), package);
+        assert!(PublicKeyPackage::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // The hash commits to every part of the package.
+        let mut reversed = public_keys.clone();
+        reversed.reverse();
+        let sorted = PublicKeyPackage::new(params, group_key, reversed).unwrap();
+        assert_eq!(sorted.hash(), package.hash());
+        let mut other = package.clone();
+        other.public_keys[2] = IndividualPublicKey::new(3, group_key.0);
+        assert_ne!(other.hash(), package.hash());
+
+        assert_eq!(
+            PublicKeyPackage::new(params, group_key, public_keys[..2].to_vec()),
+            Err(Error::InvalidNumberOfParticipants(2, 3))
+        );
+        let mut duplicate = public_keys.clone();
+        duplicate[2] = IndividualPublicKey::new(2, group_key.0);
+        assert_eq!(
+            PublicKeyPackage::new(params, group_key, duplicate),
+            Err(Error::DuplicateParticipant(2))
+        );
+        let mut stranger = public_keys;
+        stranger[0] = IndividualPublicKey::new(4, group_key.0);
+        assert_eq!(
+            PublicKeyPackage::new(params, group_key, stranger),
+            Err(Error::UnknownParticipant(4))
+        );
+
+        // Unsorted shares are not accepted from bytes.
+        let mut unsorted = bytes;
+        unsorted[44..80].copy_from_slice(&other.public_keys[1].to_bytes());
+        unsorted[80..116].copy_from_slice(&package.public_keys[0].to_bytes());
+        assert_eq!(
+            PublicKeyPackage::from_bytes(&unsorted),
+            Err(Error::SerialisationError)
+        );
+    }
 }
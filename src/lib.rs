@@ -95,7 +95,7 @@
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 //! #
-//! alice.proof_of_secret_key.as_ref().unwrap()
+//! alice.proof_of_secret_key
 //!     .verify(&alice.index, &alice.public_key().unwrap(), "Φ").or(Err(()))?;
 //! # Ok(()) } fn main() { assert!(do_test().is_ok()); }
 //! ```
@@ -117,7 +117,7 @@
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 //! #
-//! bob.proof_of_secret_key.as_ref().unwrap()
+//! bob.proof_of_secret_key
 //!     .verify(&bob.index, &bob.public_key().unwrap(), "Φ").or(Err(()))?;
 //! # Ok(()) } fn main() { assert!(do_test().is_ok()); }
 //! ```
@@ -139,7 +139,7 @@
 //! # let (bob, bob_coefficients, bob_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
 //! # let (carol, carol_coefficients, carol_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
 //! #
-//! carol.proof_of_secret_key.as_ref().unwrap()
+//! carol.proof_of_secret_key
 //!     .verify(&carol.index, &carol.public_key().unwrap(), "Φ").or(Err(()))?;
 //! # Ok(()) } fn main() { assert!(do_test().is_ok()); }
 //! ```
@@ -468,6 +468,7 @@
 //! # use ice_frost::DistributedKeyGeneration;
 //! # use ice_frost::Parameters;
 //! # use ice_frost::Participant;
+//! # use ice_frost::DkgSigner;
 //! # use curve25519_dalek::ristretto::RistrettoPoint;
 //! # use curve25519_dalek::traits::Identity;
 //! # use curve25519_dalek::scalar::Scalar;
@@ -518,12 +519,12 @@
 //! // Instantiate new configuration parameters and create a new set of signers
 //! let new_params = Parameters { t: 3, n: 4 };
 //!
-//! let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, "Φ", &mut rng);
-//! let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, "Φ", &mut rng);
-//! let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, "Φ", &mut rng);
-//! let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, "Φ", &mut rng);
+//! let (alexis, alexis_dh_sk) = DkgSigner::new(&new_params, 1, "Φ", &mut rng);
+//! let (barbara, barbara_dh_sk) = DkgSigner::new(&new_params, 2, "Φ", &mut rng);
+//! let (claire, claire_dh_sk) = DkgSigner::new(&new_params, 3, "Φ", &mut rng);
+//! let (david, david_dh_sk) = DkgSigner::new(&new_params, 4, "Φ", &mut rng);
 //!
-//! let signers: Vec<Participant> =
+//! let signers: Vec<DkgSigner> =
 //!     vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //!
 //! let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
@@ -544,6 +545,7 @@
 //! # use ice_frost::DistributedKeyGeneration;
 //! # use ice_frost::Parameters;
 //! # use ice_frost::Participant;
+//! # use ice_frost::DkgSigner;
 //! # use curve25519_dalek::ristretto::RistrettoPoint;
 //! # use curve25519_dalek::traits::Identity;
 //! # use curve25519_dalek::scalar::Scalar;
@@ -593,12 +595,12 @@
 //! # // Instantiate new configuration parameters and create a set of signers
 //! # let new_params = Parameters { t: 3, n: 4 };
 //! #
-//! # let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, "Φ", &mut rng);
-//! # let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, "Φ", &mut rng);
-//! # let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, "Φ", &mut rng);
-//! # let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, "Φ", &mut rng);
+//! # let (alexis, alexis_dh_sk) = DkgSigner::new(&new_params, 1, "Φ", &mut rng);
+//! # let (barbara, barbara_dh_sk) = DkgSigner::new(&new_params, 2, "Φ", &mut rng);
+//! # let (claire, claire_dh_sk) = DkgSigner::new(&new_params, 3, "Φ", &mut rng);
+//! # let (david, david_dh_sk) = DkgSigner::new(&new_params, 4, "Φ", &mut rng);
 //! #
-//! # let signers: Vec<Participant> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
+//! # let signers: Vec<DkgSigner> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! # let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, alice_secret_key, &signers, "Φ", &mut rng).or(Err(()))?;
 //! # let (bob_as_dealer, bob_encrypted_shares, participant_lists) =
@@ -664,6 +666,7 @@
 //! # use ice_frost::DistributedKeyGeneration;
 //! # use ice_frost::Parameters;
 //! # use ice_frost::Participant;
+//! # use ice_frost::DkgSigner;
 //! # use curve25519_dalek::ristretto::RistrettoPoint;
 //! # use curve25519_dalek::traits::Identity;
 //! # use curve25519_dalek::scalar::Scalar;
@@ -713,12 +716,12 @@
 //! # // Instantiate new configuration parameters and create a set of signers
 //! # let new_params = Parameters { t: 3, n: 4 };
 //! #
-//! # let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, "Φ", &mut rng);
-//! # let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, "Φ", &mut rng);
-//! # let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, "Φ", &mut rng);
-//! # let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, "Φ", &mut rng);
+//! # let (alexis, alexis_dh_sk) = DkgSigner::new(&new_params, 1, "Φ", &mut rng);
+//! # let (barbara, barbara_dh_sk) = DkgSigner::new(&new_params, 2, "Φ", &mut rng);
+//! # let (claire, claire_dh_sk) = DkgSigner::new(&new_params, 3, "Φ", &mut rng);
+//! # let (david, david_dh_sk) = DkgSigner::new(&new_params, 4, "Φ", &mut rng);
 //! #
-//! # let signers: Vec<Participant> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
+//! # let signers: Vec<DkgSigner> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! # let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, alice_secret_key, &signers, "Φ", &mut rng).or(Err(()))?;
 //! # let (bob_as_dealer, bob_encrypted_shares, participant_lists) =
@@ -768,6 +771,7 @@
 //! # use ice_frost::DistributedKeyGeneration;
 //! # use ice_frost::Parameters;
 //! # use ice_frost::Participant;
+//! # use ice_frost::DkgSigner;
 //! # use curve25519_dalek::ristretto::RistrettoPoint;
 //! # use curve25519_dalek::traits::Identity;
 //! # use curve25519_dalek::scalar::Scalar;
@@ -816,12 +820,12 @@
 //! #
 //! # let new_params = Parameters { t: 3, n: 4 };
 //! #
-//! # let (alexis, alexis_dh_sk) = Participant::new_signer(&new_params, 1, "Φ", &mut rng);
-//! # let (barbara, barbara_dh_sk) = Participant::new_signer(&new_params, 2, "Φ", &mut rng);
-//! # let (claire, claire_dh_sk) = Participant::new_signer(&new_params, 3, "Φ", &mut rng);
-//! # let (david, david_dh_sk) = Participant::new_signer(&new_params, 4, "Φ", &mut rng);
+//! # let (alexis, alexis_dh_sk) = DkgSigner::new(&new_params, 1, "Φ", &mut rng);
+//! # let (barbara, barbara_dh_sk) = DkgSigner::new(&new_params, 2, "Φ", &mut rng);
+//! # let (claire, claire_dh_sk) = DkgSigner::new(&new_params, 3, "Φ", &mut rng);
+//! # let (david, david_dh_sk) = DkgSigner::new(&new_params, 4, "Φ", &mut rng);
 //! #
-//! # let signers: Vec<Participant> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
+//! # let signers: Vec<DkgSigner> = vec!(alexis.clone(), barbara.clone(), claire.clone(), david.clone());
 //! # let (alice_as_dealer, alice_encrypted_shares, participant_lists) =
 //! #     Participant::reshare(&new_params, alice_secret_key, &signers, "Φ", &mut rng).or(Err(()))?;
 //! # let (bob_as_dealer, bob_encrypted_shares, participant_lists) =
@@ -1034,8 +1038,8 @@
 //! #
 //! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &context[..], &message[..]);
 //! #
-//! aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key);
-//! aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key);
+//! aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key).or(Err(()))?;
+//! aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key).or(Err(()))?;
 //! # Ok(()) }
 //! # fn main() { assert!(do_test().is_ok()); }
 //! ```
@@ -1109,8 +1113,8 @@
 //! #
 //! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &context[..], &message[..]);
 //! #
-//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key);
-//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key);
+//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], alice_public_key).or(Err(()))?;
+//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], carol_public_key).or(Err(()))?;
 //! let signers = aggregator.get_signers();
 //! # Ok(()) }
 //! # fn main() { assert!(do_test().is_ok()); }
@@ -1184,8 +1188,8 @@
 //! #
 //! # let mut aggregator = SignatureAggregator::new(params, bob_group_key.clone(), &context[..], &message[..]);
 //! #
-//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], (&alice_secret_key).into());
-//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], (&carol_secret_key).into());
+//! # aggregator.include_signer(1, alice_public_comshares.commitments[0], (&alice_secret_key).into()).or(Err(""))?;
+//! # aggregator.include_signer(3, carol_public_comshares.commitments[0], (&carol_secret_key).into()).or(Err(""))?;
 //! #
 //! # let signers = aggregator.get_signers();
 //!
@@ -1194,8 +1198,8 @@
 //! let carol_partial = carol_secret_key.sign(&message_hash, &carol_group_key,
 //!                                           &mut carol_secret_comshares, 0, signers).or(Err(""))?;
 //!
-//! aggregator.include_partial_signature(alice_partial);
-//! aggregator.include_partial_signature(carol_partial);
+//! aggregator.include_partial_signature(alice_partial).or(Err(""))?;
+//! aggregator.include_partial_signature(carol_partial).or(Err(""))?;
 //! # Ok(()) }
 //! # fn main() { assert!(do_test().is_ok()); }
 //! ```
@@ -1232,6 +1236,27 @@
 //! ```rust,ignore
 //! let verified = threshold_signature.verify(&alice_group_key, &message_hash)?;
 //! ```
+//!
+//! ## Interoperability with `frost-core`
+//!
+//! With the `frost-core-compat` feature, the `frost_core_compat` module
+//! converts keys and signatures to and from the types of
+//! `frost-ristretto255`.  The encodings line up, but the signatures do not:
+//!
+//! * A [`GroupKey`] and an [`IndividualPublicKey`] are encoded as 32-byte
+//!   compressed Ristretto points, as the `VerifyingKey` and
+//!   `VerifyingShare` of `frost-ristretto255` are.  They have no counterpart
+//!   in `frost-ed25519`, which works over another group.
+//! * A [`ThresholdSignature`](signature::ThresholdSignature) is encoded as
+//!   \(R \| z\), as a `frost-ristretto255` `Signature` is, but its
+//!   challenge is computed over another domain separator, the signing
+//!   parameters and a pre-hashed message, hence it does not verify under
+//!   `frost-core`, and conversely.
+//! * An [`IndividualSecretKey`] is encoded with its index in front of the
+//!   scalar, whereas `frost-core` identifiers are scalars on their own.
+//!
+//! Migrating a group to `frost-core` therefore requires converting the keys,
+//! and producing new signatures.
 
 #![no_std]
 #![warn(future_incompatible)]
@@ -1249,21 +1274,57 @@ extern crate std;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+pub mod attestation;
+pub mod bundle;
+pub mod ciphersuite;
+pub mod clock;
+pub mod coordinator;
+pub mod encoding;
+#[cfg(feature = "frost-core-compat")]
+pub mod frost_core_compat;
 pub mod keygen;
+pub mod liveness;
+#[cfg(feature = "memlock")]
+pub mod memlock;
+pub mod metrics;
 pub mod nizk;
 pub mod parameters;
 pub mod precomputation;
+#[cfg(feature = "dangerous-recovery")]
+pub mod recovery;
+pub mod roster;
+#[cfg(feature = "scenario")]
+pub mod scenario;
+#[cfg(feature = "serde")]
+mod serde_impls;
 pub mod signature;
+#[cfg(feature = "simple")]
+pub mod simple;
+pub mod split;
+pub mod store;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod verifier;
 
 pub use keygen::Error;
+pub use keygen::ErrorKind;
 
+pub use keygen::DealerBuilder;
+pub use keygen::DhKeyChallenge;
+pub use keygen::DhKeyHandshake;
 pub use keygen::DistributedKeyGeneration;
+pub use keygen::DkgSigner;
+pub use keygen::ExternalDhKey;
 pub use keygen::GroupKey;
+pub use keygen::IdentityKey;
 pub use keygen::IndividualPublicKey;
 pub use keygen::Participant;
+pub use keygen::PublicKeyPackage;
 pub use keygen::SecretKey as IndividualSecretKey;
+pub use keygen::{strictness, Strictness};
 pub use parameters::Parameters;
 pub use precomputation::generate_commitment_share_lists;
 
 pub use signature::compute_message_hash;
+pub use signature::Signable;
 pub use signature::SignatureAggregator;
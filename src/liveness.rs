@@ -0,0 +1,401 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Proofs that a participant still holds its share, on demand.
+//!
+//! A [`LivenessCoordinator`] issues a [`LivenessChallenge`] to a participant,
+//! which answers with a [`LivenessResponse`]: a Schnorr proof of knowledge of
+//! its secret share, bound to the challenge.  The proof is domain separated,
+//! so that it cannot be used as a signature of any message, and each
+//! challenge can only be answered once.
+//!
+//! Once the shares are refreshed, a response made with an old share no
+//! longer verifies against the refreshed individual public key of its
+//! participant.
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use core::convert::TryInto;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use rand::CryptoRng;
+use rand::RngCore;
+
+use sha2::Digest;
+use sha2::{Sha256, Sha512};
+
+use crate::encoding::decode_point_checked;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_point;
+use crate::encoding::encode_scalar;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::IndividualPublicKey;
+use crate::keygen::SecretKey;
+
+const PROOF_TAG: &[u8] = b"ICE-FROST-LIVENESS-PROOF";
+const FINGERPRINT_TAG: &[u8] = b"ICE-FROST-GROUP-KEY-FINGERPRINT";
+
+/// The fingerprint of a group key, by which a challenge designates its group.
+pub fn group_key_fingerprint(group_key: &GroupKey) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(FINGERPRINT_TAG);
+    h.update(group_key.to_bytes());
+
+    h.finalize().into()
+}
+
+/// A challenge for a participant to prove that it still holds its share.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LivenessChallenge {
+    /// The random nonce identifying this challenge.
+    pub nonce: [u8; 32],
+    /// The epoch of the shares, e.g. the number of refreshes thus far.
+    pub epoch: u64,
+    /// The [`group_key_fingerprint`] of the group of the participant.
+    pub group_key_fingerprint: [u8; 32],
+}
+
+impl LivenessChallenge {
+    /// Answer this challenge with the `secret_key` of a participant of the
+    /// group with the given `group_key`.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidGroupKey` error if this challenge is for another group.
+    pub fn respond(
+        &self,
+        secret_key: &SecretKey,
+        group_key: &GroupKey,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<LivenessResponse, Error> {
+        if self.group_key_fingerprint != group_key_fingerprint(group_key) {
+            return Err(Error::InvalidGroupKey);
+        }
+
+        let public_key = secret_key.to_public();
+        let k = Scalar::random(&mut rng);
+        let R = &RISTRETTO_BASEPOINT_TABLE * &k;
+        let c = self.proof_challenge(secret_key.index, &public_key.share, &R);
+
+        Ok(LivenessResponse {
+            index: secret_key.index,
+            nonce: self.nonce,
+            R,
+            z: k + c * secret_key.key,
+        })
+    }
+
+    /// Serialise this challenge to an array of 72 bytes.
+    pub fn to_bytes(&self) -> [u8; 72] {
+        let mut bytes = [0u8; 72];
+
+        bytes[..32].copy_from_slice(&self.nonce);
+        bytes[32..40].copy_from_slice(&self.epoch.to_le_bytes());
+        bytes[40..].copy_from_slice(&self.group_key_fingerprint);
+
+        bytes
+    }
+
+    /// Deserialise a challenge from an array of 72 bytes.
+    pub fn from_bytes(bytes: &[u8; 72]) -> Result<LivenessChallenge, Error> {
+        Ok(LivenessChallenge {
+            nonce: bytes[..32]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+            epoch: u64::from_le_bytes(
+                bytes[32..40]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            ),
+            group_key_fingerprint: bytes[40..]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        })
+    }
+
+    /// The challenge of the proof of knowledge of the participant with this
+    /// `index` and `public_key`, with the commitment `R`.
+    fn proof_challenge(
+        &self,
+        index: u32,
+        public_key: &RistrettoPoint,
+        R: &RistrettoPoint,
+    ) -> Scalar {
+        let mut h = Sha512::new();
+
+        h.update(PROOF_TAG);
+        h.update(self.to_bytes());
+        h.update(index.to_le_bytes());
+        h.update(public_key.compress().as_bytes());
+        h.update(R.compress().as_bytes());
+
+        Scalar::from_hash(h)
+    }
+}
+
+/// A participant's answer to a [`LivenessChallenge`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LivenessResponse {
+    /// The index of the responding participant.
+    pub index: u32,
+    /// The nonce of the challenge answered.
+    pub nonce: [u8; 32],
+    R: RistrettoPoint,
+    z: Scalar,
+}
+
+impl LivenessResponse {
+    /// Serialise this response to an array of 100 bytes.
+    pub fn to_bytes(&self) -> [u8; 100] {
+        let mut bytes = [0u8; 100];
+
+        bytes[..4].copy_from_slice(&self.index.to_le_bytes());
+        bytes[4..36].copy_from_slice(&self.nonce);
+        bytes[36..68].copy_from_slice(&encode_point(&self.R));
+        bytes[68..].copy_from_slice(&encode_scalar(&self.z));
+
+        bytes
+    }
+
+    /// Deserialise a response from an array of 100 bytes.
+    pub fn from_bytes(bytes: &[u8; 100]) -> Result<LivenessResponse, Error> {
+        let index = u32::from_le_bytes(
+            bytes[..4]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        let nonce = bytes[4..36]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let R = decode_point_checked(
+            &bytes[36..68]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let z = decode_scalar_canonical(
+            &bytes[68..]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        Ok(LivenessResponse { index, nonce, R, z })
+    }
+}
+
+/// The issuer of [`LivenessChallenge`]s to the participants of a group, and
+/// verifier of their responses.
+#[derive(Debug)]
+pub struct LivenessCoordinator {
+    /// The group key of the participants.
+    group_key: GroupKey,
+    /// The epoch of the shares of the participants.
+    epoch: u64,
+    /// The challenges not answered yet, by nonce, along with the index of
+    /// the participant they were issued to.
+    outstanding: BTreeMap<[u8; 32], (LivenessChallenge, u32)>,
+}
+
+impl LivenessCoordinator {
+    /// Construct a coordinator challenging the participants of the group with
+    /// the given `group_key`, for the shares of the given `epoch`.
+    pub fn new(group_key: GroupKey, epoch: u64) -> LivenessCoordinator {
+        LivenessCoordinator {
+            group_key,
+            epoch,
+            outstanding: BTreeMap::new(),
+        }
+    }
+
+    /// Issue a new challenge to the participant with the given `index`.
+    pub fn challenge(
+        &mut self,
+        index: u32,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> LivenessChallenge {
+        let mut nonce = [0u8; 32];
+        rng.fill_bytes(&mut nonce);
+
+        let challenge = LivenessChallenge {
+            nonce,
+            epoch: self.epoch,
+            group_key_fingerprint: group_key_fingerprint(&self.group_key),
+        };
+        self.outstanding.insert(nonce, (challenge, index));
+
+        challenge
+    }
+
+    /// The number of challenges issued which have not been answered yet.
+    pub fn outstanding(&self) -> usize {
+        self.outstanding.len()
+    }
+
+    /// Check a `response` to a challenge issued by this coordinator, against
+    /// the current individual `public_key` of the participant.
+    ///
+    /// The challenge is answered once the response verifies, and any later
+    /// response to it is rejected.
+    ///
+    /// # Returns
+    ///
+    /// A `ReplayedSignature` error if the challenge was not issued by this
+    /// coordinator or was already answered, an `UnknownParticipant` error if
+    /// the response is from another participant than the one challenged, or
+    /// an `InvalidProofOfKnowledge` error if the proof does not verify, e.g.
+    /// because it was made with a share which has since been refreshed.
+    pub fn verify_response(
+        &mut self,
+        response: &LivenessResponse,
+        public_key: &IndividualPublicKey,
+    ) -> Result<(), Error> {
+        let (challenge, index) = self
+            .outstanding
+            .get(&response.nonce)
+            .ok_or(Error::ReplayedSignature)?;
+        if response.index != *index || public_key.index != *index {
+            return Err(Error::UnknownParticipant(response.index));
+        }
+
+        let c = challenge.proof_challenge(response.index, &public_key.share, &response.R);
+        if &RISTRETTO_BASEPOINT_TABLE * &response.z != response.R + c * public_key.share {
+            return Err(Error::InvalidProofOfKnowledge);
+        }
+
+        self.outstanding.remove(&response.nonce);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keygen::{Coefficients, SecretShare};
+    use crate::parameters::Parameters;
+
+    use std::vec::Vec;
+
+    use rand::rngs::OsRng;
+
+    fn trusted_dealer(parameters: &Parameters) -> (GroupKey, Vec<SecretKey>) {
+        let coefficients = Coefficients(
+            (0..parameters.t)
+                .map(|_| Scalar::random(&mut OsRng))
+                .collect(),
+        );
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &coefficients.0[0]);
+        let shares = (1..=parameters.n)
+            .map(|index| SecretKey {
+                index,
+                key: SecretShare::evaluate_polynomial(&0, &index, &coefficients)
+                    .polynomial_evaluation,
+            })
+            .collect();
+
+        (group_key, shares)
+    }
+
+    #[test]
+    fn liveness_challenge() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, shares) = trusted_dealer(&params);
+        let mut coordinator = LivenessCoordinator::new(group_key, 0);
+
+        let challenge = coordinator.challenge(2, OsRng);
+        assert_eq!(
+            LivenessChallenge::from_bytes(&challenge.to_bytes()).unwrap(),
+            challenge
+        );
+        let response = challenge.respond(&shares[1], &group_key, OsRng).unwrap();
+        let response = LivenessResponse::from_bytes(&response.to_bytes()).unwrap();
+
+        // A response from another participant, or for another participant's
+        // public key, is rejected, without answering the challenge.
+        let other = challenge.respond(&shares[0], &group_key, OsRng).unwrap();
+        assert_eq!(
+            coordinator.verify_response(&other, &shares[0].to_public()),
+            Err(Error::UnknownParticipant(1))
+        );
+        assert_eq!(
+            coordinator.verify_response(&response, &shares[0].to_public()),
+            Err(Error::UnknownParticipant(2))
+        );
+        assert_eq!(coordinator.outstanding(), 1);
+
+        assert!(coordinator
+            .verify_response(&response, &shares[1].to_public())
+            .is_ok());
+        assert_eq!(coordinator.outstanding(), 0);
+
+        // The response cannot be replayed, nor can a fresh response to the
+        // same challenge be accepted.
+        assert_eq!(
+            coordinator.verify_response(&response, &shares[1].to_public()),
+            Err(Error::ReplayedSignature)
+        );
+        let fresh = challenge.respond(&shares[1], &group_key, OsRng).unwrap();
+        assert_eq!(
+            coordinator.verify_response(&fresh, &shares[1].to_public()),
+            Err(Error::ReplayedSignature)
+        );
+
+        // A participant refuses a challenge for another group.
+        let (other_group_key, _) = trusted_dealer(&params);
+        assert_eq!(
+            challenge.respond(&shares[1], &other_group_key, OsRng),
+            Err(Error::InvalidGroupKey)
+        );
+    }
+
+    #[test]
+    fn liveness_challenge_stale_share() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, shares) = trusted_dealer(&params);
+
+        // The shares of participants 1 and 2 are refreshed with a polynomial
+        // with a zero constant term, as when ejecting participant 3.
+        let refresh = Coefficients(Vec::from([Scalar::zero(), Scalar::random(&mut OsRng)]));
+        let refreshed: Vec<SecretKey> = shares[..2]
+            .iter()
+            .map(|share| SecretKey {
+                index: share.index,
+                key: share.key
+                    + SecretShare::evaluate_polynomial(&0, &share.index, &refresh)
+                        .polynomial_evaluation,
+            })
+            .collect();
+        let mut coordinator = LivenessCoordinator::new(group_key, 1);
+
+        let challenge = coordinator.challenge(1, OsRng);
+        assert_eq!(challenge.epoch, 1);
+        let stale = challenge.respond(&shares[0], &group_key, OsRng).unwrap();
+        assert_eq!(
+            coordinator.verify_response(&stale, &refreshed[0].to_public()),
+            Err(Error::InvalidProofOfKnowledge)
+        );
+
+        // The challenge is still outstanding, and can be answered with the
+        // refreshed share.
+        let response = challenge.respond(&refreshed[0], &group_key, OsRng).unwrap();
+        assert!(coordinator
+            .verify_response(&response, &refreshed[0].to_public())
+            .is_ok());
+    }
+}
@@ -0,0 +1,195 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Memory locking of secret values, so that they are not swapped to disk.
+//!
+//! Secret values are held inline and moved around by value throughout this
+//! crate, so their addresses cannot be locked once and for all.  Instead, a
+//! [`Locked`] wrapper moves a secret value to the heap and locks the pages
+//! holding it, along with the heap buffer of the coefficients of a secret
+//! polynomial.  When the wrapper is dropped, the value is zeroed out before
+//! its pages are unlocked.
+//!
+//! Locking is best-effort: it may be refused by the operating system, e.g.
+//! when the process exceeds its `RLIMIT_MEMLOCK`, which
+//! [`Locked::is_locked`] reports.  Pages are locked and unlocked as a whole,
+//! and locks are not counted, so that dropping a [`Locked`] value unlocks
+//! the other values sharing its pages.
+
+use std::boxed::Box;
+use std::vec::Vec;
+
+use core::fmt;
+use core::mem;
+use core::ops::Deref;
+
+use curve25519_dalek::scalar::Scalar;
+
+use zeroize::Zeroize;
+
+use crate::keygen::{Coefficients, DHPrivateKey, IdentityKey, SecretKey};
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for crate::keygen::SecretKey {}
+    impl Sealed for crate::keygen::Coefficients {}
+    impl Sealed for crate::keygen::DHPrivateKey {}
+    impl Sealed for crate::keygen::IdentityKey {}
+}
+
+/// A secret value which can be held in locked memory by a [`Locked`]
+/// wrapper.
+pub trait Lockable: Zeroize + private::Sealed {
+    /// The heap buffer holding secrets outside of the value itself, as its
+    /// address and size in bytes.
+    #[doc(hidden)]
+    fn heap_buffer(&self) -> Option<(*const u8, usize)> {
+        None
+    }
+}
+
+impl Lockable for SecretKey {}
+
+impl Lockable for DHPrivateKey {}
+
+impl Lockable for IdentityKey {}
+
+impl Lockable for Coefficients {
+    fn heap_buffer(&self) -> Option<(*const u8, usize)> {
+        match self.0.capacity() {
+            0 => None,
+            capacity => Some((
+                self.0.as_ptr() as *const u8,
+                capacity * mem::size_of::<Scalar>(),
+            )),
+        }
+    }
+}
+
+/// A secret value held in locked memory.
+///
+/// The value can only be borrowed immutably, so that the buffers which were
+/// locked are not reallocated.
+pub struct Locked<T: Lockable> {
+    value: Box<T>,
+    /// The regions which were successfully locked.
+    regions: Vec<(*const u8, usize)>,
+}
+
+impl<T: Lockable> Locked<T> {
+    /// Move a secret `value` to locked memory.
+    ///
+    /// The memory the value is moved out of is not zeroed out, hence secret
+    /// values should be locked as soon as they are created.
+    pub fn new(value: T) -> Locked<T> {
+        let value = Box::new(value);
+
+        let mut regions = Vec::new();
+        let inline = (&*value as *const T as *const u8, mem::size_of::<T>());
+        for (address, size) in core::iter::once(inline).chain(value.heap_buffer()) {
+            if lock(address, size) {
+                regions.push((address, size));
+            }
+        }
+
+        Locked { value, regions }
+    }
+
+    /// Whether all the memory holding the secret value is locked.
+    pub fn is_locked(&self) -> bool {
+        self.regions.len() == 1 + self.value.heap_buffer().iter().count()
+    }
+}
+
+impl<T: Lockable> Deref for Locked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Lockable> Drop for Locked<T> {
+    fn drop(&mut self) {
+        self.value.zeroize();
+        for (address, size) in self.regions.drain(..) {
+            // Unlocking only fails for regions which were not locked.
+            let _ = region::unlock(address, size);
+        }
+    }
+}
+
+impl<T: Lockable> fmt::Debug for Locked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Locked")
+            .field("locked", &self.is_locked())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+static LOCK_CALLS: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+/// Lock the region of `size` bytes at `address`, and report whether it is.
+fn lock(address: *const u8, size: usize) -> bool {
+    #[cfg(test)]
+    LOCK_CALLS.fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+
+    match region::lock(address, size) {
+        Ok(guard) => {
+            // The region is unlocked once the value is zeroed out, when the
+            // `Locked` wrapper is dropped.
+            mem::forget(guard);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use core::sync::atomic::Ordering;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn lock_secret_values() {
+        let secret_key = SecretKey {
+            index: 1,
+            key: Scalar::random(&mut OsRng),
+        };
+        let coefficients = Coefficients((0..3).map(|_| Scalar::random(&mut OsRng)).collect());
+        let expected = coefficients.0.clone();
+
+        let calls = LOCK_CALLS.load(Ordering::SeqCst);
+        let locked_key = Locked::new(secret_key.duplicate());
+        let locked_coefficients = Locked::new(coefficients);
+        let locked_dh_key = Locked::new(DHPrivateKey(Scalar::random(&mut OsRng)));
+
+        // The secret key and the DH key take one call each, and the
+        // coefficients one more for their heap buffer.
+        assert!(LOCK_CALLS.load(Ordering::SeqCst) >= calls + 4);
+
+        assert_eq!(*locked_key, secret_key);
+        assert_eq!(locked_coefficients.0, expected);
+        assert_eq!(locked_coefficients.heap_buffer().unwrap().1, 3 * 32);
+
+        // Locking needs privileges the test may not run with.
+        if !locked_key.is_locked() || !locked_coefficients.is_locked() {
+            return;
+        }
+        assert!(locked_dh_key.is_locked());
+        assert_eq!(format!("{:?}", locked_key), "Locked { locked: true, .. }");
+    }
+}
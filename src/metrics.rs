@@ -0,0 +1,157 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Metrics hooks for signing sessions.
+//!
+//! A [`Metrics`] implementation given to a
+//! [`SignatureAggregator`](crate::signature::SignatureAggregator), a
+//! [`CommitmentPool`](crate::store::CommitmentPool) or a
+//! [`SignerSessionManager`](crate::store::SignerSessionManager) is called at
+//! well-defined points of a session, with one of the metric names below and a
+//! fixed set of labels, so that it can be forwarded to any metrics backend.
+//! Without one, nothing is recorded.
+//!
+//! | Metric                | Kind    | Labels               |
+//! |-----------------------|---------|----------------------|
+//! | [`SESSION_CREATED`]   | counter | `role`               |
+//! | [`PARTIAL_ACCEPTED`]  | counter | `signer`             |
+//! | [`PARTIAL_REJECTED`]  | counter | `signer`, `reason`   |
+//! | [`PARTIAL_SUSPICIOUS`]| counter | `signer`, `reason`   |
+//! | [`PARTIAL_MISSING`]   | counter | `signer`             |
+//! | [`SESSION_COMPLETED`] | counter | `role`               |
+//! | [`SESSION_ABORTED`]   | counter | `role`, `reason`     |
+//! | [`POOL_DEPLETED`]     | counter | `signer`             |
+//! | [`POOL_REMAINING`]    | gauge   | `signer`             |
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::boxed::Box;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use core::fmt;
+
+use crate::signature::SignatureError;
+
+/// A session was created, by an aggregator or a signer.
+pub const SESSION_CREATED: &str = "frost_session_created";
+/// A partial signature was accepted by an aggregator.
+pub const PARTIAL_ACCEPTED: &str = "frost_partial_accepted";
+/// A partial signature was rejected by an aggregator.
+pub const PARTIAL_REJECTED: &str = "frost_partial_rejected";
+/// A partial signature was flagged as degenerate by an aggregator, whether
+/// or not it was accepted.
+pub const PARTIAL_SUSPICIOUS: &str = "frost_partial_suspicious";
+/// A signer did not send its partial signature before the session was
+/// finalized.
+pub const PARTIAL_MISSING: &str = "frost_partial_missing";
+/// A session produced a signature, or a signer released its partial
+/// signature.
+pub const SESSION_COMPLETED: &str = "frost_session_completed";
+/// A session was abandoned without producing a signature.
+pub const SESSION_ABORTED: &str = "frost_session_aborted";
+/// A signer could not sign because it ran out of commitment shares.
+pub const POOL_DEPLETED: &str = "frost_pool_depleted";
+/// The number of unused commitment shares left in a signer's pool.
+pub const POOL_REMAINING: &str = "frost_pool_remaining";
+
+/// The `role` label of the metrics emitted by an aggregator.
+pub const ROLE_AGGREGATOR: &str = "aggregator";
+/// The `role` label of the metrics emitted by a signer.
+pub const ROLE_SIGNER: &str = "signer";
+
+/// A sink for the counters and gauges of signing sessions.
+///
+/// Both methods do nothing by default.  Label names are static, and label
+/// values are either participant indices in decimal or static reasons.
+pub trait Metrics: Send + Sync {
+    /// Increment the counter `name` with the given `labels` by one.
+    fn increment_counter(&self, _name: &'static str, _labels: &[(&'static str, &str)]) {}
+
+    /// Set the gauge `name` with the given `labels` to `value`.
+    fn set_gauge(&self, _name: &'static str, _value: u64, _labels: &[(&'static str, &str)]) {}
+}
+
+/// A [`Metrics`] implementation which records nothing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {}
+
+impl<M: Metrics + ?Sized> Metrics for Arc<M> {
+    fn increment_counter(&self, name: &'static str, labels: &[(&'static str, &str)]) {
+        (**self).increment_counter(name, labels)
+    }
+
+    fn set_gauge(&self, name: &'static str, value: u64, labels: &[(&'static str, &str)]) {
+        (**self).set_gauge(name, value, labels)
+    }
+}
+
+/// The [`Metrics`] implementation held by the types emitting metrics.
+pub(crate) struct MetricsHandle(Box<dyn Metrics>);
+
+impl MetricsHandle {
+    pub(crate) fn new<M: Metrics + 'static>(metrics: M) -> MetricsHandle {
+        MetricsHandle(Box::new(metrics))
+    }
+
+    pub(crate) fn increment_counter(&self, name: &'static str, labels: &[(&'static str, &str)]) {
+        self.0.increment_counter(name, labels)
+    }
+
+    pub(crate) fn set_gauge(
+        &self,
+        name: &'static str,
+        value: u64,
+        labels: &[(&'static str, &str)],
+    ) {
+        self.0.set_gauge(name, value, labels)
+    }
+}
+
+impl Default for MetricsHandle {
+    fn default() -> MetricsHandle {
+        MetricsHandle::new(NoopMetrics)
+    }
+}
+
+impl fmt::Debug for MetricsHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("MetricsHandle")
+    }
+}
+
+/// The `reason` label for a rejection or an abort caused by `error`.
+pub(crate) fn error_reason(error: &SignatureError) -> &'static str {
+    match error {
+        SignatureError::InvalidPartialSignature(_) => "invalid_partial",
+        SignatureError::PartialBudgetExceeded(_) => "budget_exceeded",
+        SignatureError::DuplicatePartial(_) => "duplicate_partial",
+        SignatureError::UnknownSigner(_) => "unknown_signer",
+        SignatureError::UnsignedCommitment(_) => "unsigned_commitment",
+        SignatureError::SessionMismatch(_) => "session_mismatch",
+        SignatureError::MissingCommitmentShares => "missing_commitment_shares",
+        SignatureError::CommitmentPoolLow(_) => "commitment_pool_low",
+        SignatureError::ConcurrentUpdate => "concurrent_update",
+        SignatureError::InterruptedSession => "interrupted_session",
+        SignatureError::UnauthorizedSession => "unauthorized_session",
+        SignatureError::ConflictingIntent { .. } => "conflicting_intent",
+        SignatureError::SessionExpired { .. } => "session_expired",
+        SignatureError::SequenceMismatch { .. } => "sequence_mismatch",
+        SignatureError::SequenceFork(_) => "sequence_fork",
+        SignatureError::StorageError(_) => "storage_error",
+        _ => "other",
+    }
+}
@@ -11,6 +11,10 @@
 
 //! Zero-knowledge proofs.
 
+use crate::ciphersuite::Ciphersuite;
+use crate::ciphersuite::Ristretto25519;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_scalar;
 use crate::keygen::Error;
 
 use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
@@ -21,7 +25,24 @@ use rand::CryptoRng;
 use rand::Rng;
 
 use sha2::Digest;
-use sha2::Sha512;
+
+/// The challenge of a proof of knowledge of the secret key of `public_key`,
+/// for the participant with this `index`, with the commitment `M`.
+fn challenge<C: Ciphersuite>(
+    index: &u32,
+    context_string: &str,
+    public_key: &C::Element,
+    M: &C::Element,
+) -> C::Scalar {
+    let mut hram = C::Hash::default();
+
+    hram.update(index.to_be_bytes());
+    hram.update(context_string);
+    hram.update(C::encode_element(public_key));
+    hram.update(C::encode_element(M));
+
+    C::hash_to_scalar(hram)
+}
 
 /// A proof of knowledge of a secret key, created by making a Schnorr signature
 /// with the secret key.
@@ -57,14 +78,7 @@ impl NizkOfSecretKey {
         let k: Scalar = Scalar::random(&mut csprng);
         let M: RistrettoPoint = &k * &RISTRETTO_BASEPOINT_TABLE;
 
-        let mut hram = Sha512::new();
-
-        hram.update(index.to_be_bytes());
-        hram.update(context_string);
-        hram.update(public_key.compress().as_bytes());
-        hram.update(M.compress().as_bytes());
-
-        let s = Scalar::from_hash(hram);
+        let s = challenge::<Ristretto25519>(index, context_string, public_key, &M);
         let r = k + (secret_key * s);
 
         NizkOfSecretKey { s, r }
@@ -80,14 +94,7 @@ impl NizkOfSecretKey {
         let M_prime: RistrettoPoint =
             (&RISTRETTO_BASEPOINT_TABLE * &self.r) + (public_key * -&self.s);
 
-        let mut hram = Sha512::new();
-
-        hram.update(index.to_be_bytes());
-        hram.update(context_string);
-        hram.update(public_key.compress().as_bytes());
-        hram.update(M_prime.compress().as_bytes());
-
-        let s_prime = Scalar::from_hash(hram);
+        let s_prime = challenge::<Ristretto25519>(index, context_string, public_key, &M_prime);
 
         if self.s == s_prime {
             return Ok(());
@@ -99,27 +106,25 @@ impl NizkOfSecretKey {
     /// Serialise this proof to an array of bytes
     pub fn to_bytes(&self) -> [u8; 64] {
         let mut res = [0u8; 64];
-        res[0..32].copy_from_slice(&self.s.to_bytes());
-        res[32..64].copy_from_slice(&self.r.to_bytes());
+        res[0..32].copy_from_slice(&encode_scalar(&self.s));
+        res[32..64].copy_from_slice(&encode_scalar(&self.r));
 
         res
     }
 
     /// Deserialise this slice of bytes to a NiZK proof
     pub fn from_bytes(bytes: &[u8; 64]) -> Result<NizkOfSecretKey, Error> {
-        let s = Scalar::from_canonical_bytes(
-            bytes[0..32]
+        let s = decode_scalar_canonical(
+            &bytes[0..32]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .ok_or(Error::SerialisationError)?;
+        )?;
 
-        let r = Scalar::from_canonical_bytes(
-            bytes[32..64]
+        let r = decode_scalar_canonical(
+            &bytes[32..64]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .ok_or(Error::SerialisationError)?;
+        )?;
 
         Ok(NizkOfSecretKey { s, r })
     }
@@ -11,7 +11,14 @@
 
 //! Precomputation for one-round signing.
 
+use crate::ciphersuite::Ciphersuite;
+use crate::encoding::decode_point_checked;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_point;
+use crate::encoding::encode_scalar;
+use crate::keygen::CompressedPoint;
 use crate::keygen::Error;
+use crate::signature::SignatureError;
 
 #[cfg(feature = "std")]
 use std::vec::Vec;
@@ -51,31 +58,38 @@ impl From<NoncePair> for CommitmentShare {
         let y = &RISTRETTO_BASEPOINT_TABLE * &other.1;
 
         CommitmentShare {
-            hiding: Commitment {
-                nonce: other.0,
-                sealed: x,
-            },
-            binding: Commitment {
-                nonce: other.1,
-                sealed: y,
-            },
+            hiding: Commitment::new(other.0, x),
+            binding: Commitment::new(other.1, y),
         }
     }
 }
 
 /// A pair of a nonce and a commitment to it.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// This is not `Clone`, so that copies of the nonce are explicit.
+#[derive(Debug)]
 pub(crate) struct Commitment {
     /// The nonce.
     pub(crate) nonce: Scalar,
     /// The commitment.
     pub(crate) sealed: RistrettoPoint,
+    /// The compressed encoding of the commitment.
+    pub(crate) compressed: CompressedPoint,
+}
+
+impl Eq for Commitment {}
+
+impl PartialEq for Commitment {
+    fn eq(&self, other: &Commitment) -> bool {
+        self.nonce == other.nonce && self.compressed_sealed() == other.compressed_sealed()
+    }
 }
 
 impl Zeroize for Commitment {
     fn zeroize(&mut self) {
         self.nonce.zeroize();
         self.sealed = RistrettoPoint::identity();
+        self.compressed = CompressedPoint::new(&self.sealed);
     }
 }
 
@@ -88,43 +102,72 @@ impl Drop for Commitment {
 /// Test equality in constant-time.
 impl ConstantTimeEq for Commitment {
     fn ct_eq(&self, other: &Commitment) -> Choice {
-        self.nonce.ct_eq(&other.nonce) & self.sealed.compress().ct_eq(&other.sealed.compress())
+        self.nonce.ct_eq(&other.nonce) & self.compressed_sealed().ct_eq(&other.compressed_sealed())
     }
 }
 
 impl Commitment {
+    /// Create a commitment `sealed` to a `nonce`.
+    pub(crate) fn new(nonce: Scalar, sealed: RistrettoPoint) -> Commitment {
+        Commitment {
+            nonce,
+            sealed,
+            compressed: CompressedPoint::new(&sealed),
+        }
+    }
+
+    /// Create another copy of this commitment, whose nonce is zeroed out
+    /// when dropped independently of this one.
+    pub(crate) fn duplicate(&self) -> Commitment {
+        Commitment {
+            nonce: self.nonce,
+            sealed: self.sealed,
+            compressed: self.compressed,
+        }
+    }
+
+    /// The compressed encoding of the commitment.
+    pub(crate) fn compressed_sealed(&self) -> CompressedRistretto {
+        self.compressed.get(&self.sealed)
+    }
+
     /// Serialise this commitment to an array of bytes
     pub fn to_bytes(&self) -> [u8; 64] {
         let mut res = [0u8; 64];
-        res[0..32].copy_from_slice(&self.nonce.to_bytes());
-        res[32..64].copy_from_slice(&self.sealed.compress().to_bytes());
+        res[0..32].copy_from_slice(&encode_scalar(&self.nonce));
+        res[32..64].copy_from_slice(self.compressed_sealed().as_bytes());
 
         res
     }
 
     /// Deserialise this array of bytes to a `Commitment`
     pub fn from_bytes(bytes: &[u8; 64]) -> Result<Commitment, Error> {
-        let nonce = Scalar::from_canonical_bytes(
-            bytes[0..32]
+        let nonce = decode_scalar_canonical(
+            &bytes[0..32]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .ok_or(Error::SerialisationError)?;
+        )?;
 
-        let sealed = CompressedRistretto(
+        let compressed = CompressedRistretto(
             bytes[32..64]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .decompress()
-        .ok_or(Error::SerialisationError)?;
+        );
+        let sealed = decode_point_checked(compressed.as_bytes())?;
 
-        Ok(Commitment { nonce, sealed })
+        Ok(Commitment {
+            nonce,
+            sealed,
+            compressed: CompressedPoint::from_parts(sealed, compressed),
+        })
     }
 }
 
 /// A precomputed commitment share.
-#[derive(Clone, Debug, Eq, PartialEq, Zeroize)]
+///
+/// As in standard FROST, each commitment share holds
+/// [`CommitmentShare::NONCE_COUNT`] nonces: a hiding and a binding one.
+#[derive(Debug, Eq, PartialEq, Zeroize)]
 #[zeroize(drop)]
 pub struct CommitmentShare {
     /// The hiding commitment.
@@ -145,6 +188,33 @@ impl ConstantTimeEq for CommitmentShare {
 }
 
 impl CommitmentShare {
+    /// The number of nonces in a commitment share, i.e. a hiding and a
+    /// binding nonce as in standard FROST.
+    ///
+    /// Only ciphersuites whose [`Ciphersuite::NONCE_COUNT`] is this count
+    /// can be used with commitment shares.
+    pub const NONCE_COUNT: usize = 2;
+
+    /// The nonces of this commitment share, in the order in which they are
+    /// weighted by the powers of the binding factor.
+    pub(crate) fn nonces(&self) -> [&Scalar; CommitmentShare::NONCE_COUNT] {
+        [&self.hiding.nonce, &self.binding.nonce]
+    }
+
+    /// Create another copy of this commitment share, whose nonces are zeroed
+    /// out when dropped independently of this one.
+    ///
+    /// # Warning
+    ///
+    /// Signing with both copies reuses the nonces, which reveals the secret
+    /// key.
+    pub fn duplicate(&self) -> CommitmentShare {
+        CommitmentShare {
+            hiding: self.hiding.duplicate(),
+            binding: self.binding.duplicate(),
+        }
+    }
+
     /// Publish the public commitments in this [`CommitmentShare`].
     pub fn publish(&self) -> (RistrettoPoint, RistrettoPoint) {
         (self.hiding.sealed, self.binding.sealed)
@@ -179,43 +249,207 @@ impl CommitmentShare {
 
 /// A secret commitment share list, containing the revealed nonces for the
 /// hiding and binding commitments.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq)]
 pub struct SecretCommitmentShareList {
-    /// The secret commitment shares.
+    /// The secret commitment shares which have not been used yet.
     pub commitments: Vec<CommitmentShare>,
+    /// The sorted indices, in the published [`PublicCommitmentShareList`], of
+    /// the commitment shares which were already used.
+    pub(crate) consumed: Vec<u32>,
+    /// The number of unused commitment shares below which signing is
+    /// refused, if any.
+    pub(crate) low_watermark: Option<usize>,
+    /// The index of the participant who generated this list, if known.
+    /// Lists serialised by earlier versions of this crate do not record it.
+    pub(crate) participant_index: Option<u32>,
 }
 
 impl SecretCommitmentShareList {
+    /// Create another copy of this secret commitment share list, whose
+    /// nonces are zeroed out when dropped independently of this one.
+    ///
+    /// # Warning
+    ///
+    /// Signing with the same commitment share from both copies reuses its
+    /// nonces, which reveals the secret key.  The copy should only serve as
+    /// a backup of the list.
+    pub fn duplicate(&self) -> SecretCommitmentShareList {
+        SecretCommitmentShareList {
+            commitments: self
+                .commitments
+                .iter()
+                .map(CommitmentShare::duplicate)
+                .collect(),
+            consumed: self.consumed.clone(),
+            low_watermark: self.low_watermark,
+            participant_index: self.participant_index,
+        }
+    }
+
     /// Serialise this secret commitment share list to a Vec of bytes
+    ///
+    /// The used commitment shares are recorded, so that the remaining ones
+    /// keep their published index after a reload, followed by the index of
+    /// the participant owning the list, if known.
+    ///
+    /// # Warning
+    ///
+    /// The result contains the secret nonces, and should be encrypted before
+    /// being persisted, and wiped from memory after use.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut res = Vec::with_capacity(8 + 128 * self.commitments.len());
+        let mut res =
+            Vec::with_capacity(8 + 128 * self.commitments.len() + 4 * self.consumed.len());
 
         let len = self.commitments.len();
         res.extend_from_slice(&TryInto::<u32>::try_into(len).unwrap().to_le_bytes());
         for i in 0..len {
-            res.extend_from_slice(&self.commitments[i].to_bytes());
+            let mut bytes = self.commitments[i].to_bytes();
+            res.extend_from_slice(&bytes);
+            bytes.zeroize();
+        }
+
+        let len = self.consumed.len();
+        res.extend_from_slice(&TryInto::<u32>::try_into(len).unwrap().to_le_bytes());
+        for index in self.consumed.iter() {
+            res.extend_from_slice(&index.to_le_bytes());
+        }
+
+        if let Some(participant_index) = self.participant_index {
+            res.extend_from_slice(&participant_index.to_le_bytes());
         }
 
         res
     }
 
-    /// Deserialise this slice of bytes to a `PublicCommitmentShareList`
+    /// Deserialise this slice of bytes to a `SecretCommitmentShareList`
     pub fn from_bytes(bytes: &[u8]) -> Result<SecretCommitmentShareList, Error> {
         let len = u32::from_le_bytes(
-            bytes[0..4]
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
-        let mut commitments: Vec<CommitmentShare> = Vec::with_capacity(len as usize);
+        let mut commitments: Vec<CommitmentShare> = Vec::new();
         let mut index_slice = 4;
         let mut array = [0u8; 128];
 
         for _ in 0..len {
-            array.copy_from_slice(&bytes[index_slice..index_slice + 128]);
-            commitments.push(CommitmentShare::from_bytes(&array)?);
+            let share_bytes = bytes
+                .get(index_slice..index_slice + 128)
+                .ok_or(Error::SerialisationError);
+            let share = share_bytes.and_then(|share_bytes| {
+                array.copy_from_slice(share_bytes);
+                CommitmentShare::from_bytes(&array)
+            });
+            array.zeroize();
+            commitments.push(share?);
             index_slice += 128;
         }
-        Ok(SecretCommitmentShareList { commitments })
+
+        let len = u32::from_le_bytes(
+            bytes
+                .get(index_slice..index_slice + 4)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        index_slice += 4;
+        let mut consumed: Vec<u32> = Vec::new();
+        for _ in 0..len {
+            let index = u32::from_le_bytes(
+                bytes
+                    .get(index_slice..index_slice + 4)
+                    .ok_or(Error::SerialisationError)?
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            );
+            if consumed.last().map_or(false, |last| *last >= index) {
+                return Err(Error::SerialisationError);
+            }
+            consumed.push(index);
+            index_slice += 4;
+        }
+
+        // Lists serialised before they recorded their owner end here.
+        let participant_index = match bytes.len() - index_slice {
+            0 => None,
+            4 => Some(u32::from_le_bytes(
+                bytes[index_slice..]
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            )),
+            _ => return Err(Error::SerialisationError),
+        };
+
+        Ok(SecretCommitmentShareList {
+            commitments,
+            consumed,
+            low_watermark: None,
+            participant_index,
+        })
+    }
+
+    /// Get the position in [`SecretCommitmentShareList::commitments`] of the
+    /// commitment share published at `published_index` in the
+    /// [`PublicCommitmentShareList`], to be used for signing.
+    ///
+    /// # Returns
+    ///
+    /// `None` if this commitment share was already used, or does not exist.
+    pub fn position(&self, published_index: u32) -> Option<usize> {
+        if self.consumed.binary_search(&published_index).is_ok() {
+            return None;
+        }
+        let used_before = self
+            .consumed
+            .iter()
+            .filter(|i| **i < published_index)
+            .count();
+        let position = published_index as usize - used_before;
+
+        match position < self.commitments.len() {
+            true => Some(position),
+            false => None,
+        }
+    }
+
+    /// The index of the participant who generated this list, or `None` for a
+    /// list deserialised from the encoding of earlier versions of this crate.
+    pub fn participant_index(&self) -> Option<u32> {
+        self.participant_index
+    }
+
+    /// Whether the commitment share published at `published_index` in the
+    /// [`PublicCommitmentShareList`] was already used.
+    pub fn is_consumed(&self, published_index: u32) -> bool {
+        self.consumed.binary_search(&published_index).is_ok()
+    }
+
+    /// Refuse to sign when doing so would leave fewer than `n` unused
+    /// commitment shares, so that the pool can be refilled before it is
+    /// exhausted.  Signing then fails with a `CommitmentPoolLow` error.
+    ///
+    /// The watermark is a runtime policy: it is not serialised, and must be
+    /// set again after reloading the list.
+    pub fn set_low_watermark(&mut self, n: usize) {
+        self.low_watermark = Some(n);
+    }
+
+    /// Remove the low watermark set with
+    /// [`SecretCommitmentShareList::set_low_watermark`], allowing the
+    /// remaining commitment shares to be used.
+    pub fn clear_low_watermark(&mut self) {
+        self.low_watermark = None;
+    }
+
+    /// Whether signing once more would bring the number of unused commitment
+    /// shares below the low watermark.
+    pub fn is_low(&self) -> bool {
+        match self.low_watermark {
+            Some(n) => self.commitments.len() <= n,
+            None => false,
+        }
     }
 }
 
@@ -241,8 +475,8 @@ impl PublicCommitmentShareList {
         let len = self.commitments.len();
         res.extend_from_slice(&TryInto::<u32>::try_into(len).unwrap().to_le_bytes());
         for i in 0..len {
-            res.extend_from_slice(&self.commitments[i].0.compress().to_bytes());
-            res.extend_from_slice(&self.commitments[i].1.compress().to_bytes());
+            res.extend_from_slice(&encode_point(&self.commitments[i].0));
+            res.extend_from_slice(&encode_point(&self.commitments[i].1));
         }
 
         res
@@ -251,33 +485,39 @@ impl PublicCommitmentShareList {
     /// Deserialise this slice of bytes to a `PublicCommitmentShareList`
     pub fn from_bytes(bytes: &[u8]) -> Result<PublicCommitmentShareList, Error> {
         let participant_index = u32::from_le_bytes(
-            bytes[0..4]
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
         let len = u32::from_le_bytes(
-            bytes[4..8]
+            bytes
+                .get(4..8)
+                .ok_or(Error::SerialisationError)?
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
         );
+        // Never reserve more pairs than the remaining bytes can hold.
         let mut commitments: Vec<(RistrettoPoint, RistrettoPoint)> =
-            Vec::with_capacity(len as usize);
+            Vec::with_capacity(core::cmp::min(len as usize, (bytes.len() - 8) / 64));
         let mut index_slice = 8;
         let mut array = [0u8; 32];
 
         for _ in 0..len {
-            array.copy_from_slice(&bytes[index_slice..index_slice + 32]);
-            let point1 = CompressedRistretto(array)
-                .decompress()
-                .ok_or(Error::SerialisationError)?;
-            array.copy_from_slice(&bytes[index_slice + 32..index_slice + 64]);
-
-            commitments.push((
-                point1,
-                CompressedRistretto(array)
-                    .decompress()
+            array.copy_from_slice(
+                bytes
+                    .get(index_slice..index_slice + 32)
                     .ok_or(Error::SerialisationError)?,
-            ));
+            );
+            let point1 = decode_point_checked(&array)?;
+            array.copy_from_slice(
+                bytes
+                    .get(index_slice + 32..index_slice + 64)
+                    .ok_or(Error::SerialisationError)?,
+            );
+
+            commitments.push((point1, decode_point_checked(&array)?));
             index_slice += 64;
         }
         Ok(PublicCommitmentShareList {
@@ -320,10 +560,40 @@ pub fn generate_commitment_share_lists(
             participant_index,
             commitments: published,
         },
-        SecretCommitmentShareList { commitments },
+        SecretCommitmentShareList {
+            commitments,
+            consumed: Vec::new(),
+            low_watermark: None,
+            participant_index: Some(participant_index),
+        },
     )
 }
 
+/// Pre-compute a list of [`CommitmentShare`]s as with
+/// [`generate_commitment_share_lists`], for signing with the given
+/// [`Ciphersuite`].
+///
+/// # Returns
+///
+/// A tuple of ([`PublicCommitmentShareList`], [`SecretCommitmentShareList`]),
+/// or an `UnsupportedNonceCount` error if the ciphersuite asks for another
+/// number of nonces than [`CommitmentShare::NONCE_COUNT`].
+pub fn generate_commitment_share_lists_with<C: Ciphersuite>(
+    csprng: impl CryptoRng + Rng,
+    participant_index: u32,
+    number_of_shares: usize,
+) -> Result<(PublicCommitmentShareList, SecretCommitmentShareList), SignatureError> {
+    if C::NONCE_COUNT != CommitmentShare::NONCE_COUNT {
+        return Err(SignatureError::UnsupportedNonceCount(C::NONCE_COUNT));
+    }
+
+    Ok(generate_commitment_share_lists(
+        csprng,
+        participant_index,
+        number_of_shares,
+    ))
+}
+
 // XXX TODO This should maybe be a field on SecretKey with some sort of
 // regeneration API for generating new share, or warning that there are no
 // ununsed shares.
@@ -343,10 +613,31 @@ impl SecretCommitmentShareList {
             }
         }
         if index >= 0 {
-            drop(self.commitments.remove(index as usize));
+            self.drop_share_at(index as usize);
         }
         drop(share);
     }
+
+    /// Drop the [`CommitmentShare`] at position `index` of our secret
+    /// commitment share list, as with [`SecretCommitmentShareList::drop_share`],
+    /// without copying it.
+    pub(crate) fn drop_share_at(&mut self, index: usize) {
+        drop(self.commitments.remove(index));
+
+        // Record the published index of the removed share, i.e. the
+        // index-th one which was not consumed yet.
+        let mut published_index = index as u32;
+        for consumed in self.consumed.iter() {
+            if *consumed <= published_index {
+                published_index += 1;
+            }
+        }
+        let position = self
+            .consumed
+            .binary_search(&published_index)
+            .unwrap_or_else(|p| p);
+        self.consumed.insert(position, published_index);
+    }
 }
 
 #[cfg(test)]
@@ -371,7 +662,7 @@ mod test {
         for _ in 0..100 {
             let nonce = Scalar::random(&mut rng);
             let sealed = &nonce * &curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
-            let commitment = Commitment { nonce, sealed };
+            let commitment = Commitment::new(nonce, sealed);
 
             let bytes = commitment.to_bytes();
             assert!(Commitment::from_bytes(&bytes).is_ok());
@@ -381,8 +672,8 @@ mod test {
         for _ in 0..100 {
             let nonce = Scalar::random(&mut rng);
             let sealed = &nonce * &curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
-            let binding = Commitment { nonce, sealed };
-            let hiding = binding.clone();
+            let binding = Commitment::new(nonce, sealed);
+            let hiding = binding.duplicate();
             let commitment_share = CommitmentShare { binding, hiding };
 
             let bytes = commitment_share.to_bytes();
@@ -413,6 +704,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn public_commitment_share_list_serialisation() {
+        let (public_share_list, _) = generate_commitment_share_lists(&mut OsRng, 2, 3);
+
+        let bytes = public_share_list.to_bytes();
+        assert_eq!(
+            PublicCommitmentShareList::from_bytes(&bytes).unwrap(),
+            public_share_list
+        );
+
+        // Truncated encodings are rejected rather than panicking.
+        for len in 0..bytes.len() {
+            assert_eq!(
+                PublicCommitmentShareList::from_bytes(&bytes[..len]).unwrap_err(),
+                Error::SerialisationError
+            );
+        }
+
+        // A list claiming more commitments than it carries is rejected
+        // without reserving room for them.
+        let mut bytes = bytes;
+        bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            PublicCommitmentShareList::from_bytes(&bytes).unwrap_err(),
+            Error::SerialisationError
+        );
+    }
+
     #[test]
     fn drop_used_commitment_shares() {
         let (_public_share_list, mut secret_share_list) =
@@ -420,10 +739,66 @@ mod test {
 
         assert!(secret_share_list.commitments.len() == 8);
 
-        let used_share = secret_share_list.commitments[0].clone();
+        let used_share = secret_share_list.commitments[0].duplicate();
 
         secret_share_list.drop_share(used_share);
 
         assert!(secret_share_list.commitments.len() == 7);
     }
+
+    #[test]
+    fn consumed_commitment_shares_survive_serialisation() {
+        let (public_share_list, mut secret_share_list) =
+            generate_commitment_share_lists(&mut OsRng, 1, 4);
+
+        let used_share = secret_share_list.commitments[0].duplicate();
+        secret_share_list.drop_share(used_share);
+        let used_share = secret_share_list.commitments[1].duplicate();
+        secret_share_list.drop_share(used_share);
+
+        let bytes = secret_share_list.to_bytes();
+        let reloaded = SecretCommitmentShareList::from_bytes(&bytes).unwrap();
+        assert_eq!(reloaded, secret_share_list);
+        assert_eq!(reloaded.participant_index(), Some(1));
+        assert!(SecretCommitmentShareList::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // Lists serialised without their owner are still accepted.
+        let legacy = SecretCommitmentShareList::from_bytes(&bytes[..bytes.len() - 4]).unwrap();
+        assert_eq!(legacy.participant_index(), None);
+        assert_eq!(legacy.commitments, reloaded.commitments);
+
+        // Published shares 0 and 2 were used, and 1 and 3 remain.
+        assert!(reloaded.is_consumed(0));
+        assert!(reloaded.is_consumed(2));
+        assert_eq!(reloaded.position(0), None);
+        assert_eq!(reloaded.position(2), None);
+        assert_eq!(reloaded.position(4), None);
+        for published_index in [1, 3] {
+            let position = reloaded.position(published_index).unwrap();
+            assert_eq!(
+                reloaded.commitments[position].publish(),
+                public_share_list.commitments[published_index as usize]
+            );
+        }
+    }
+
+    #[test]
+    fn zeroized_commitment_encoding() {
+        let nonce = Scalar::random(&mut OsRng);
+        let mut commitment = Commitment::new(nonce, &nonce * &RISTRETTO_BASEPOINT_TABLE);
+        let decoded = Commitment::from_bytes(&commitment.to_bytes()).unwrap();
+        assert_eq!(decoded, commitment);
+
+        commitment.zeroize();
+        assert_eq!(
+            commitment.to_bytes(),
+            [
+                Scalar::zero().to_bytes(),
+                RistrettoPoint::identity().compress().to_bytes()
+            ]
+            .concat()[..]
+        );
+        assert_ne!(decoded, commitment);
+        assert!(bool::from(!decoded.ct_eq(&commitment)));
+    }
 }
@@ -0,0 +1,232 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Reconstruction of the group secret key from \\(t\\) shares.
+//!
+//! **This defeats the purpose of threshold signatures.**  Once reconstructed,
+//! the group secret key signs on its own, and whoever holds it holds the
+//! whole group.  It is only meant for disaster recovery, e.g. to migrate away
+//! from this scheme in an air-gapped ceremony, and is gated behind the
+//! `dangerous-recovery` feature so that it cannot be reached by accident.
+//!
+//! The reconstructed key is wrapped in a [`ReconstructedSecret`], which
+//! cannot be cloned, is redacted from debug output, and is zeroed out when
+//! dropped.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::scalar::Scalar;
+
+use zeroize::Zeroize;
+
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::SecretKey;
+use crate::parameters::Parameters;
+use crate::signature::calculate_lagrange_coefficients;
+
+/// The group secret key, reconstructed from the shares of participants.
+///
+/// It is zeroed out when dropped, and redacted from its `Debug` output.
+#[derive(Zeroize)]
+#[zeroize(drop)]
+pub struct ReconstructedSecret(Scalar);
+
+impl ReconstructedSecret {
+    /// The group secret key, whose public key is the group key.
+    pub fn expose_secret(&self) -> &Scalar {
+        &self.0
+    }
+
+    /// Serialise the group secret key to bytes, which the caller is
+    /// responsible for zeroing out.
+    pub fn expose_secret_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+}
+
+impl fmt::Debug for ReconstructedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ReconstructedSecret(REDACTED)")
+    }
+}
+
+/// Reconstruct the secret key of the `group_key` from the `shares` of at
+/// least \\(t\\) distinct participants of a group with these `parameters`.
+///
+/// All the given shares are interpolated, and the result is checked against
+/// the group key, so that a share inconsistent with the others, e.g. from
+/// another group, is detected however many shares are given.
+///
+/// # Returns
+///
+/// The group secret key, or:
+///
+/// * a `MissingShares` error if fewer than \\(t\\) shares are given,
+/// * an `UnknownParticipant` error for a share whose index is not within
+///   \\(1\\) and \\(n\\),
+/// * a `DuplicateParticipant` error for two shares of the same index, or
+/// * a `ShareVerificationError` if the shares do not reconstruct the secret
+///   key of the `group_key`.
+pub fn reconstruct_group_secret(
+    shares: &[SecretKey],
+    parameters: &Parameters,
+    group_key: &GroupKey,
+) -> Result<ReconstructedSecret, Error> {
+    if shares.len() < parameters.t as usize {
+        return Err(Error::MissingShares);
+    }
+
+    let mut indices: Vec<u32> = Vec::with_capacity(shares.len());
+    for share in shares.iter() {
+        if share.index == 0 || share.index > parameters.n {
+            return Err(Error::UnknownParticipant(share.index));
+        }
+        if indices.contains(&share.index) {
+            return Err(Error::DuplicateParticipant(share.index));
+        }
+        indices.push(share.index);
+    }
+
+    let mut secret = ReconstructedSecret(Scalar::zero());
+    for share in shares.iter() {
+        // The indices were checked to be distinct and non-zero.
+        let coefficient = calculate_lagrange_coefficients(&share.index, &indices)
+            .map_err(|_| Error::ShareVerificationError)?;
+        let mut term = coefficient * share.key;
+        secret.0 += term;
+        term.zeroize();
+    }
+
+    if &RISTRETTO_BASEPOINT_TABLE * &secret.0 != group_key.0 {
+        return Err(Error::ShareVerificationError);
+    }
+
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keygen::{Coefficients, SecretShare};
+
+    use rand::rngs::OsRng;
+
+    fn trusted_dealer(parameters: &Parameters) -> (Scalar, GroupKey, Vec<SecretKey>) {
+        let coefficients = Coefficients(
+            (0..parameters.t)
+                .map(|_| Scalar::random(&mut OsRng))
+                .collect(),
+        );
+        let secret = coefficients.0[0];
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &secret);
+        let shares = (1..=parameters.n)
+            .map(|index| SecretKey {
+                index,
+                key: SecretShare::evaluate_polynomial(&0, &index, &coefficients)
+                    .polynomial_evaluation,
+            })
+            .collect();
+
+        (secret, group_key, shares)
+    }
+
+    #[test]
+    fn reconstruct_from_threshold_shares() {
+        let params = Parameters { n: 5, t: 3 };
+        let (secret, group_key, shares) = trusted_dealer(&params);
+
+        let reconstructed = reconstruct_group_secret(&shares[1..4], &params, &group_key).unwrap();
+        assert_eq!(*reconstructed.expose_secret(), secret);
+        assert_eq!(reconstructed.expose_secret_bytes(), secret.to_bytes());
+        assert_eq!(
+            format!("{:?}", reconstructed),
+            "ReconstructedSecret(REDACTED)"
+        );
+
+        let reconstructed = reconstruct_group_secret(&shares[..4], &params, &group_key).unwrap();
+        assert_eq!(*reconstructed.expose_secret(), secret);
+        let reconstructed = reconstruct_group_secret(&shares, &params, &group_key).unwrap();
+        assert_eq!(*reconstructed.expose_secret(), secret);
+    }
+
+    #[test]
+    fn reconstruct_invalid_shares() {
+        let params = Parameters { n: 5, t: 3 };
+        let (_secret, group_key, shares) = trusted_dealer(&params);
+
+        assert_eq!(
+            reconstruct_group_secret(&shares[..2], &params, &group_key).unwrap_err(),
+            Error::MissingShares
+        );
+
+        let duplicated = [
+            shares[0].duplicate(),
+            shares[1].duplicate(),
+            shares[1].duplicate(),
+        ];
+        assert_eq!(
+            reconstruct_group_secret(&duplicated, &params, &group_key).unwrap_err(),
+            Error::DuplicateParticipant(2)
+        );
+
+        let out_of_range = [
+            shares[0].duplicate(),
+            shares[1].duplicate(),
+            SecretKey {
+                index: 6,
+                key: shares[2].key,
+            },
+        ];
+        assert_eq!(
+            reconstruct_group_secret(&out_of_range, &params, &group_key).unwrap_err(),
+            Error::UnknownParticipant(6)
+        );
+
+        // A share of another group is detected with exactly t shares as well
+        // as with more.
+        let (_other_secret, _other_group_key, other_shares) = trusted_dealer(&params);
+        let mixed = [
+            shares[0].duplicate(),
+            shares[1].duplicate(),
+            other_shares[2].duplicate(),
+        ];
+        assert_eq!(
+            reconstruct_group_secret(&mixed, &params, &group_key).unwrap_err(),
+            Error::ShareVerificationError
+        );
+        let mixed = [
+            shares[0].duplicate(),
+            shares[1].duplicate(),
+            shares[2].duplicate(),
+            other_shares[3].duplicate(),
+        ];
+        assert_eq!(
+            reconstruct_group_secret(&mixed, &params, &group_key).unwrap_err(),
+            Error::ShareVerificationError
+        );
+
+        // Shares of the right group do not reconstruct another group's key.
+        let (_other_secret, other_group_key, _other_shares) = trusted_dealer(&params);
+        assert_eq!(
+            reconstruct_group_secret(&shares[..3], &params, &other_group_key).unwrap_err(),
+            Error::ShareVerificationError
+        );
+    }
+}
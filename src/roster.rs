@@ -0,0 +1,532 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! A Merkle tree over the roster of participants of a group.
+//!
+//! Each leaf commits to the index and long-term Diffie-Hellman public key of
+//! a participant.  Enrolling, revoking or rotating the key of a single
+//! participant only rehashes the path from its leaf to the root, which keeps
+//! the roster hash cheap to maintain for groups with thousands of
+//! participants.  A [`RosterProof`] lets a light verifier check that a
+//! participant belongs to the roster with only the root at hand, while the
+//! [`RosterTree::canonical_hash`] lets two parties compare rosters enrolled
+//! in different orders.
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::convert::TryInto;
+
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::keygen::DHPublicKey;
+use crate::keygen::Error;
+
+const LEAF_TAG: u8 = 0;
+const NODE_TAG: u8 = 1;
+const ROOT_TAG: u8 = 2;
+
+fn hash_leaf(index: u32, dh_public_key: &DHPublicKey) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-ROSTER");
+    h.update([LEAF_TAG]);
+    h.update(index.to_le_bytes());
+    h.update(dh_public_key.to_bytes());
+
+    h.finalize().into()
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-ROSTER");
+    h.update([NODE_TAG]);
+    h.update(left);
+    h.update(right);
+
+    h.finalize().into()
+}
+
+fn hash_root(leaf_count: u32, top: &[u8; 32]) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-ROSTER");
+    h.update([ROOT_TAG]);
+    h.update(leaf_count.to_le_bytes());
+    h.update(top);
+
+    h.finalize().into()
+}
+
+/// A Merkle tree over the participants of a roster.
+///
+/// The leaves are kept in insertion order: removing a participant moves the
+/// last leaf into its position.  The root only depends on the sequence of
+/// leaves, so that two trees with the same [`RosterTree::leaves`] have the
+/// same root, however they were built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RosterTree {
+    /// The position of the leaf of each participant, by index.
+    positions: BTreeMap<u32, usize>,
+    /// The participant index of each leaf.
+    indices: Vec<u32>,
+    /// The levels of the tree, from the leaves up to the top node.  A node
+    /// without a sibling is promoted unchanged to the next level.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl RosterTree {
+    /// Build a roster tree from scratch over the given participants, in this
+    /// order.
+    pub fn new(roster: &[(u32, DHPublicKey)]) -> Result<RosterTree, Error> {
+        let mut positions = BTreeMap::new();
+        let mut indices = Vec::with_capacity(roster.len());
+        let mut leaves = Vec::with_capacity(roster.len());
+        for (position, (index, dh_public_key)) in roster.iter().enumerate() {
+            if positions.insert(*index, position).is_some() {
+                return Err(Error::DuplicateParticipant(*index));
+            }
+            indices.push(*index);
+            leaves.push(hash_leaf(*index, dh_public_key));
+        }
+
+        let mut levels = Vec::with_capacity(1);
+        levels.push(leaves);
+        while levels[levels.len() - 1].len() > 1 {
+            let level = &levels[levels.len() - 1];
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(RosterTree {
+            positions,
+            indices,
+            levels,
+        })
+    }
+
+    /// The number of participants in the roster.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Whether the roster is empty.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// The participant indices of the leaves, in order.
+    pub fn leaves(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// The root of the tree, which commits to the whole roster.
+    pub fn root(&self) -> [u8; 32] {
+        let top = match self.levels.last() {
+            Some(level) if !level.is_empty() => level[0],
+            _ => [0u8; 32],
+        };
+
+        hash_root(self.len() as u32, &top)
+    }
+
+    /// The hash of the leaves of the tree sorted by participant index, which
+    /// unlike [`RosterTree::root`] does not depend on the order in which the
+    /// participants were enrolled.
+    pub fn canonical_hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+
+        h.update(b"ICE-FROST-ROSTER-CANONICAL");
+        h.update((self.len() as u32).to_le_bytes());
+        for position in self.positions.values() {
+            h.update(self.levels[0][*position]);
+        }
+
+        h.finalize().into()
+    }
+
+    /// Enrol a new participant at the end of the roster.
+    ///
+    /// # Returns
+    ///
+    /// A `DuplicateParticipant` error if the index is already in the roster.
+    pub fn add_leaf(&mut self, index: u32, dh_public_key: &DHPublicKey) -> Result<(), Error> {
+        if self.positions.contains_key(&index) {
+            return Err(Error::DuplicateParticipant(index));
+        }
+
+        let position = self.indices.len();
+        self.positions.insert(index, position);
+        self.indices.push(index);
+        self.levels[0].push(hash_leaf(index, dh_public_key));
+        self.rehash(position);
+
+        Ok(())
+    }
+
+    /// Replace the long-term Diffie-Hellman public key of a participant.
+    ///
+    /// # Returns
+    ///
+    /// An `UnknownParticipant` error if the index is not in the roster.
+    pub fn update_leaf(&mut self, index: u32, dh_public_key: &DHPublicKey) -> Result<(), Error> {
+        let position = *self
+            .positions
+            .get(&index)
+            .ok_or(Error::UnknownParticipant(index))?;
+
+        self.levels[0][position] = hash_leaf(index, dh_public_key);
+        self.rehash(position);
+
+        Ok(())
+    }
+
+    /// Revoke a participant, moving the last leaf into its position.
+    ///
+    /// # Returns
+    ///
+    /// An `UnknownParticipant` error if the index is not in the roster.
+    pub fn remove_leaf(&mut self, index: u32) -> Result<(), Error> {
+        let position = self
+            .positions
+            .remove(&index)
+            .ok_or(Error::UnknownParticipant(index))?;
+
+        self.indices.swap_remove(position);
+        self.levels[0].swap_remove(position);
+
+        let len = self.indices.len();
+        if position < len {
+            self.positions.insert(self.indices[position], position);
+            self.rehash(position);
+        }
+        if len > 0 {
+            self.rehash(len - 1);
+        } else {
+            self.levels.truncate(1);
+        }
+
+        Ok(())
+    }
+
+    /// Prove that the participant with this index is in the roster.
+    pub fn prove(&self, index: u32) -> Option<RosterProof> {
+        let mut position = *self.positions.get(&index)?;
+
+        let mut siblings = Vec::new();
+        for level in self.levels.iter().take(self.levels.len() - 1) {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            position /= 2;
+        }
+
+        Some(RosterProof {
+            position: self.positions[&index] as u32,
+            leaf_count: self.len() as u32,
+            siblings,
+        })
+    }
+
+    /// Recompute the nodes on the path from the leaf at `position` to the
+    /// top, resizing the levels to the current number of leaves.
+    fn rehash(&mut self, mut position: usize) {
+        let mut k = 0;
+        loop {
+            let len = self.levels[k].len();
+            if len <= 1 {
+                self.levels.truncate(k + 1);
+                return;
+            }
+            if self.levels.len() == k + 1 {
+                self.levels.push(Vec::new());
+            }
+            self.levels[k + 1].resize((len + 1) / 2, [0u8; 32]);
+
+            let parent = position / 2;
+            let level = &self.levels[k];
+            let node = match level.get(2 * parent + 1) {
+                Some(right) => hash_node(&level[2 * parent], right),
+                None => level[2 * parent],
+            };
+            self.levels[k + 1][parent] = node;
+
+            position = parent;
+            k += 1;
+        }
+    }
+}
+
+/// A proof of membership of a participant in a [`RosterTree`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RosterProof {
+    /// The position of the participant's leaf.
+    pub position: u32,
+    /// The number of leaves of the tree.
+    pub leaf_count: u32,
+    /// The siblings on the path from the leaf to the top, bottom first.
+    siblings: Vec<[u8; 32]>,
+}
+
+impl RosterProof {
+    /// Check that the participant with this `index` and `dh_public_key` is
+    /// in the roster committed to by `root`.
+    ///
+    /// # Returns
+    ///
+    /// An `UnknownParticipant` error if the proof does not hold.
+    pub fn verify(
+        &self,
+        root: &[u8; 32],
+        index: u32,
+        dh_public_key: &DHPublicKey,
+    ) -> Result<(), Error> {
+        if self.position >= self.leaf_count {
+            return Err(Error::UnknownParticipant(index));
+        }
+
+        let mut node = hash_leaf(index, dh_public_key);
+        let mut position = self.position as usize;
+        let mut len = self.leaf_count as usize;
+        let mut siblings = self.siblings.iter();
+        while len > 1 {
+            if position ^ 1 < len {
+                let sibling = siblings.next().ok_or(Error::UnknownParticipant(index))?;
+                node = match position % 2 {
+                    0 => hash_node(&node, sibling),
+                    _ => hash_node(sibling, &node),
+                };
+            }
+            position /= 2;
+            len = (len + 1) / 2;
+        }
+
+        match siblings.next().is_none() && hash_root(self.leaf_count, &node) == *root {
+            true => Ok(()),
+            false => Err(Error::UnknownParticipant(index)),
+        }
+    }
+
+    /// Serialise this roster proof to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::with_capacity(12 + 32 * self.siblings.len());
+        res.extend_from_slice(&self.position.to_le_bytes());
+        res.extend_from_slice(&self.leaf_count.to_le_bytes());
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.siblings.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for sibling in self.siblings.iter() {
+            res.extend_from_slice(sibling);
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `RosterProof`
+    pub fn from_bytes(bytes: &[u8]) -> Result<RosterProof, Error> {
+        let read_u32 = |from: usize| -> Result<u32, Error> {
+            Ok(u32::from_le_bytes(
+                bytes
+                    .get(from..from + 4)
+                    .ok_or(Error::SerialisationError)?
+                    .try_into()
+                    .map_err(|_| Error::SerialisationError)?,
+            ))
+        };
+        let position = read_u32(0)?;
+        let leaf_count = read_u32(4)?;
+        let len = read_u32(8)? as usize;
+
+        // A tree with at most 2^32 leaves has at most 32 levels above them.
+        if len > 32 || bytes.len() != 12 + 32 * len {
+            return Err(Error::SerialisationError);
+        }
+        let siblings = bytes[12..]
+            .chunks(32)
+            .map(|chunk| chunk.try_into().map_err(|_| Error::SerialisationError))
+            .collect::<Result<Vec<[u8; 32]>, Error>>()?;
+
+        Ok(RosterProof {
+            position,
+            leaf_count,
+            siblings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+    use curve25519_dalek::scalar::Scalar;
+
+    use rand::rngs::OsRng;
+    use rand::seq::SliceRandom;
+
+    fn random_roster(n: u32) -> Vec<(u32, DHPublicKey)> {
+        (1..=n)
+            .map(|i| {
+                let key = &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng);
+                (i, DHPublicKey(key))
+            })
+            .collect()
+    }
+
+    fn random_key() -> DHPublicKey {
+        DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng))
+    }
+
+    fn from_scratch(tree: &RosterTree, roster: &[(u32, DHPublicKey)]) -> RosterTree {
+        let ordered: Vec<(u32, DHPublicKey)> = tree
+            .leaves()
+            .iter()
+            .map(|i| roster.iter().find(|(j, _)| j == i).unwrap().clone())
+            .collect();
+
+        RosterTree::new(&ordered).unwrap()
+    }
+
+    #[test]
+    fn roster_membership_proofs() {
+        for n in 1..=9 {
+            let roster = random_roster(n);
+            let tree = RosterTree::new(&roster).unwrap();
+            let root = tree.root();
+
+            for (index, key) in roster.iter() {
+                let proof = tree.prove(*index).unwrap();
+                assert!(proof.verify(&root, *index, key).is_ok());
+
+                let proof = RosterProof::from_bytes(&proof.to_bytes()).unwrap();
+                assert!(proof.verify(&root, *index, key).is_ok());
+
+                assert_eq!(
+                    proof.verify(&root, *index, &random_key()),
+                    Err(Error::UnknownParticipant(*index))
+                );
+                assert!(proof.verify(&root, index + 1, key).is_err());
+            }
+            assert!(tree.prove(n + 1).is_none());
+        }
+
+        let roster = random_roster(5);
+        assert_eq!(
+            RosterTree::new(&[roster[0].clone(), roster[0].clone()]),
+            Err(Error::DuplicateParticipant(1))
+        );
+    }
+
+    #[test]
+    fn roster_canonical_hash() {
+        let roster = random_roster(9);
+        let tree = RosterTree::new(&roster).unwrap();
+
+        let mut shuffled = roster.clone();
+        for _ in 0..16 {
+            shuffled.shuffle(&mut OsRng);
+            let other = RosterTree::new(&shuffled).unwrap();
+            assert_eq!(other.canonical_hash(), tree.canonical_hash());
+
+            // Enrolling in another order, or after a revocation.
+            let mut incremental = RosterTree::new(&[]).unwrap();
+            for (index, key) in shuffled.iter().rev() {
+                incremental.add_leaf(*index, key).unwrap();
+            }
+            incremental.remove_leaf(shuffled[0].0).unwrap();
+            incremental.add_leaf(shuffled[0].0, &shuffled[0].1).unwrap();
+            assert_eq!(incremental.canonical_hash(), tree.canonical_hash());
+        }
+
+        let mut other = tree.clone();
+        other.update_leaf(1, &random_key()).unwrap();
+        assert_ne!(other.canonical_hash(), tree.canonical_hash());
+        other.remove_leaf(1).unwrap();
+        assert_ne!(other.canonical_hash(), tree.canonical_hash());
+    }
+
+    #[test]
+    fn roster_incremental_updates() {
+        let mut roster = random_roster(13);
+        let mut tree = RosterTree::new(&[]).unwrap();
+        for (index, key) in roster.iter() {
+            tree.add_leaf(*index, key).unwrap();
+            assert_eq!(tree.root(), from_scratch(&tree, &roster).root());
+        }
+        assert_eq!(tree, RosterTree::new(&roster).unwrap());
+        assert_eq!(
+            tree.add_leaf(3, &roster[2].1),
+            Err(Error::DuplicateParticipant(3))
+        );
+
+        for i in [4usize, 0, 12] {
+            roster[i].1 = random_key();
+            tree.update_leaf(roster[i].0, &roster[i].1).unwrap();
+            assert_eq!(tree.root(), from_scratch(&tree, &roster).root());
+        }
+        assert_eq!(
+            tree.update_leaf(14, &random_key()),
+            Err(Error::UnknownParticipant(14))
+        );
+
+        for index in [1u32, 13, 7, 2, 8, 3, 4, 5, 6, 9, 10, 11, 12] {
+            let previous_root = tree.root();
+            tree.remove_leaf(index).unwrap();
+            assert_ne!(tree.root(), previous_root);
+            assert_eq!(tree, from_scratch(&tree, &roster));
+
+            let root = tree.root();
+            for leaf in tree.leaves() {
+                let key = &roster.iter().find(|(j, _)| j == leaf).unwrap().1;
+                assert!(tree.prove(*leaf).unwrap().verify(&root, *leaf, key).is_ok());
+            }
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), RosterTree::new(&[]).unwrap().root());
+        assert_eq!(tree.remove_leaf(1), Err(Error::UnknownParticipant(1)));
+    }
+
+    #[test]
+    fn roster_incremental_updates_at_scale() {
+        let mut roster = random_roster(4096);
+        let mut tree = RosterTree::new(&roster).unwrap();
+
+        // The timing comparison with a recomputation from scratch is in the
+        // roster benchmarks.
+        for i in 0..64 {
+            let position = (i * 61) % roster.len();
+            roster[position].1 = random_key();
+
+            tree.update_leaf(roster[position].0, &roster[position].1)
+                .unwrap();
+            assert_eq!(tree.root(), RosterTree::new(&roster).unwrap().root());
+        }
+    }
+}
@@ -0,0 +1,718 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Scripted end-to-end scenarios, for quality assurance.
+//!
+//! A [`Scenario`] describes a group, the participants misbehaving in it, an
+//! optional refresh of the shares, and the signers of a message.  Running it
+//! executes the key generation and the signing with the production state
+//! machines, routing the messages in memory through a
+//! [`DkgCoordinator`] and a [`SignatureAggregator`], and returns a
+//! [`ScenarioReport`] of every step taken along with the final artifacts.
+//!
+//! Both types can be (de)serialised with serde, so that scenarios can be
+//! loaded from fixtures.  This module is gated behind the `scenario` feature.
+//!
+//! ```rust
+//! # use ice_frost::scenario::{run, Outcome, Scenario};
+//! let scenario: Scenario = serde_json::from_str(
+//!     r#"{ "name": "honest", "n": 3, "t": 2, "signers": [1, 3] }"#,
+//! )
+//! .unwrap();
+//!
+//! let report = run(scenario);
+//! assert_eq!(report.outcome, Outcome::Signed);
+//! ```
+
+use std::collections::BTreeMap;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::scalar::Scalar;
+
+use rand::rngs::OsRng;
+use rand::CryptoRng;
+use rand::Rng;
+
+use serde::{Deserialize, Serialize};
+
+use crate::coordinator::DkgCoordinator;
+use crate::keygen::eject_participant;
+use crate::keygen::Coefficients;
+use crate::keygen::DHPrivateKey;
+use crate::keygen::DHPublicKey;
+use crate::keygen::DistributedKeyGeneration;
+use crate::keygen::EjectionContribution;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::IndividualPublicKey;
+use crate::keygen::Participant;
+use crate::keygen::RoundOne;
+use crate::keygen::SecretKey;
+use crate::keygen::VerifiableSecretSharingCommitment;
+use crate::parameters::Parameters;
+use crate::precomputation::generate_commitment_share_lists;
+use crate::precomputation::SecretCommitmentShareList;
+use crate::signature::compute_message_hash;
+use crate::signature::SignatureAggregator;
+use crate::signature::ThresholdSignature;
+
+/// The context string of the key generations and signatures of scenarios.
+pub const CONTEXT: &str = "ICE-FROST-SCENARIO";
+
+/// A way for a participant to deviate from the protocol.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Misbehaviour {
+    /// The participant publishes a commitment to another constant term than
+    /// the one of its proof of knowledge.
+    InvalidProof {
+        /// The index of the participant.
+        participant: u32,
+    },
+    /// The dealer sends a corrupted encrypted share to the recipient.
+    CorruptShare {
+        /// The index of the dealer.
+        dealer: u32,
+        /// The index of the recipient.
+        recipient: u32,
+    },
+    /// The dealer sends the victim the commitments of another polynomial,
+    /// with the same constant term, and a share consistent with them, so that
+    /// the share of the victim is inconsistent with everyone else's.
+    RogueKey {
+        /// The index of the dealer.
+        dealer: u32,
+        /// The index of the victim.
+        victim: u32,
+    },
+    /// The signer sends a partial signature which does not verify.
+    InvalidPartialSignature {
+        /// The index of the signer.
+        signer: u32,
+    },
+    /// The signer commits to a nonce, but never sends its partial signature.
+    WithholdPartialSignature {
+        /// The index of the signer.
+        signer: u32,
+    },
+}
+
+fn default_message() -> String {
+    "This is a test of the tsunami alert system. This is only a test.".to_string()
+}
+
+/// The description of a scenario.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    /// The name of the scenario, copied to its report.
+    pub name: String,
+    /// The number of participants.
+    pub n: u32,
+    /// The threshold of the group.
+    pub t: u32,
+    /// The participants deviating from the protocol, and how.
+    #[serde(default)]
+    pub misbehaviours: Vec<Misbehaviour>,
+    /// The participant ejected by a refresh of the shares of the others
+    /// between the key generation and the signing, if any.
+    #[serde(default)]
+    pub eject: Option<u32>,
+    /// The indices of the signers of the message.
+    pub signers: Vec<u32>,
+    /// The message to sign.
+    #[serde(default = "default_message")]
+    pub message: String,
+}
+
+/// A step of a scenario.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// The round one packages and the encrypted shares are sent.
+    RoundOne,
+    /// The encrypted shares are decrypted and verified.
+    RoundTwo,
+    /// The complaints about shares are made and adjudicated.
+    Complaint,
+    /// The group key and the secret keys are derived.
+    Finish,
+    /// The shares are refreshed.
+    Refresh,
+    /// The signers commit to nonces and sign.
+    Signing,
+    /// The partial signatures are aggregated.
+    Aggregation,
+}
+
+/// A state transition in a scenario.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    /// The step of the scenario.
+    pub phase: Phase,
+    /// The participant making the transition, or `None` for the coordinator.
+    pub participant: Option<u32>,
+    /// What happened.
+    pub event: String,
+}
+
+/// How a scenario ended.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Outcome {
+    /// A valid signature was produced.
+    Signed,
+    /// The key generation was aborted, blaming these participants.
+    KeygenAborted {
+        /// The sorted indices of the blamed participants.
+        blamed: Vec<u32>,
+    },
+    /// The signing was aborted, blaming these participants.
+    SigningAborted {
+        /// The sorted indices of the blamed participants.
+        blamed: Vec<u32>,
+    },
+    /// The scenario cannot be run.
+    InvalidScenario {
+        /// Why the scenario cannot be run.
+        reason: String,
+    },
+}
+
+/// The report of a scenario run.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScenarioReport {
+    /// The name of the scenario.
+    pub name: String,
+    /// Every state transition, in order.
+    pub transitions: Vec<Transition>,
+    /// The dealers excluded from the key generation for an invalid proof of
+    /// knowledge.
+    pub excluded_dealers: Vec<u32>,
+    /// The group key, if the key generation finished.
+    pub group_key: Option<GroupKey>,
+    /// The signature, if one was produced.
+    pub signature: Option<ThresholdSignature>,
+    /// How the scenario ended.
+    pub outcome: Outcome,
+}
+
+impl ScenarioReport {
+    fn record(&mut self, phase: Phase, participant: Option<u32>, event: impl Into<String>) {
+        self.transitions.push(Transition {
+            phase,
+            participant,
+            event: event.into(),
+        });
+    }
+}
+
+/// The key generation of a scenario.
+struct Keygen {
+    group_key: GroupKey,
+    secret_keys: BTreeMap<u32, SecretKey>,
+    public_keys: BTreeMap<u32, IndividualPublicKey>,
+}
+
+/// Run a `scenario` with the operating system's random number generator.
+pub fn run(scenario: Scenario) -> ScenarioReport {
+    run_with_rng(scenario, OsRng)
+}
+
+/// Run a `scenario` with the given random number generator.
+pub fn run_with_rng(scenario: Scenario, mut rng: impl Rng + CryptoRng) -> ScenarioReport {
+    let mut report = ScenarioReport {
+        name: scenario.name.clone(),
+        transitions: Vec::new(),
+        excluded_dealers: Vec::new(),
+        group_key: None,
+        signature: None,
+        outcome: Outcome::Signed,
+    };
+
+    if let Err(reason) = validate(&scenario) {
+        report.outcome = Outcome::InvalidScenario { reason };
+        return report;
+    }
+
+    let keygen = match run_keygen(&scenario, &mut report, &mut rng) {
+        Ok(keygen) => keygen,
+        Err(outcome) => {
+            report.outcome = outcome;
+            return report;
+        }
+    };
+    report.group_key = Some(keygen.group_key);
+
+    let keygen = match scenario.eject {
+        Some(ejected) => match refresh(&scenario, keygen, ejected, &mut report, &mut rng) {
+            Ok(keygen) => keygen,
+            Err(outcome) => {
+                report.outcome = outcome;
+                return report;
+            }
+        },
+        None => keygen,
+    };
+
+    report.outcome = match run_signing(&scenario, &keygen, &mut report, &mut rng) {
+        Ok(signature) => {
+            report.signature = Some(signature);
+            Outcome::Signed
+        }
+        Err(outcome) => outcome,
+    };
+
+    report
+}
+
+fn validate(scenario: &Scenario) -> Result<(), String> {
+    let in_group = |index: u32| index >= 1 && index <= scenario.n;
+
+    if scenario.t == 0 || scenario.t > scenario.n {
+        return Err("The threshold must be between 1 and the number of participants".to_string());
+    }
+    let mut signers = scenario.signers.clone();
+    signers.sort_unstable();
+    signers.dedup();
+    if signers.len() != scenario.signers.len() || !signers.iter().all(|s| in_group(*s)) {
+        return Err("The signers must be distinct participants".to_string());
+    }
+    if scenario.eject.map_or(false, |e| !in_group(e)) {
+        return Err("The ejected participant must be a participant".to_string());
+    }
+    for misbehaviour in scenario.misbehaviours.iter() {
+        let valid = match *misbehaviour {
+            Misbehaviour::InvalidProof { participant } => in_group(participant),
+            Misbehaviour::CorruptShare { dealer, recipient } => {
+                in_group(dealer) && in_group(recipient) && dealer != recipient
+            }
+            Misbehaviour::RogueKey { dealer, victim } => {
+                in_group(dealer) && in_group(victim) && dealer != victim && scenario.t > 1
+            }
+            Misbehaviour::InvalidPartialSignature { signer }
+            | Misbehaviour::WithholdPartialSignature { signer } => {
+                scenario.signers.contains(&signer)
+            }
+        };
+        if !valid {
+            return Err(std::format!("Invalid misbehaviour {:?}", misbehaviour));
+        }
+    }
+
+    Ok(())
+}
+
+/// The sorted and deduplicated `indices`, without the aggregator's 0.
+fn blamed(mut indices: Vec<u32>) -> Vec<u32> {
+    indices.retain(|index| *index != 0);
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+fn run_keygen(
+    scenario: &Scenario,
+    report: &mut ScenarioReport,
+    mut rng: impl Rng + CryptoRng,
+) -> Result<Keygen, Outcome> {
+    let parameters = Parameters {
+        n: scenario.n,
+        t: scenario.t,
+    };
+    let misbehaves = |m: Misbehaviour| scenario.misbehaviours.contains(&m);
+    let keygen_aborted = |error: Error| Outcome::KeygenAborted {
+        blamed: error.participant().into_iter().collect(),
+    };
+
+    let mut coordinator = DkgCoordinator::new(parameters);
+    let mut coefficients = Vec::new();
+    let mut dh_private_keys = Vec::new();
+    for i in 1..=scenario.n {
+        let (mut participant, coeffs, dh_private_key) =
+            Participant::new_dealer(&parameters, i, CONTEXT, &mut rng);
+        if misbehaves(Misbehaviour::InvalidProof { participant: i }) {
+            participant
+                .commitments
+                .set_point(0, &RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut rng))
+                .map_err(keygen_aborted)?;
+            report.record(
+                Phase::RoundOne,
+                Some(i),
+                "published a commitment not matching its proof of knowledge",
+            );
+        }
+        coordinator
+            .include_participant(participant)
+            .map_err(keygen_aborted)?;
+        report.record(Phase::RoundOne, Some(i), "published its round one package");
+        coefficients.push(coeffs);
+        dh_private_keys.push(dh_private_key);
+    }
+    let participants = coordinator.participants().to_vec();
+
+    // The package a rogue dealer sends to its victim, along with the
+    // coefficients it deals the victim's share with.
+    let rogue = scenario.misbehaviours.iter().find_map(|m| match *m {
+        Misbehaviour::RogueKey { dealer, victim } => Some((dealer, victim)),
+        _ => None,
+    });
+    let rogue_view = rogue.map(|(dealer, _)| {
+        let position = dealer as usize - 1;
+        let mut tampered = participants.clone();
+        let shifted = tampered[position].commitments.point(1).unwrap() + RISTRETTO_BASEPOINT_POINT;
+        tampered[position]
+            .commitments
+            .set_point(1, shifted)
+            .unwrap();
+
+        let mut tampered_coefficients = coefficients[position].0.clone();
+        tampered_coefficients[1] += Scalar::one();
+
+        (tampered, Coefficients(tampered_coefficients))
+    });
+
+    let mut states = Vec::new();
+    for i in 1..=scenario.n {
+        let position = i as usize - 1;
+        let view = match (rogue, rogue_view.as_ref()) {
+            (Some((_, victim)), Some((tampered, _))) if victim == i => tampered,
+            _ => &participants,
+        };
+        let (state, lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &parameters,
+            &dh_private_keys[position],
+            &i,
+            &coefficients[position],
+            view,
+            CONTEXT,
+            &mut rng,
+        )
+        .map_err(keygen_aborted)?;
+        for excluded in lists.misbehaving_participants.unwrap_or_default() {
+            if !report.excluded_dealers.contains(&excluded) {
+                report.excluded_dealers.push(excluded);
+                report.record(
+                    Phase::RoundOne,
+                    None,
+                    std::format!("excluded dealer {} for an invalid proof", excluded),
+                );
+            }
+        }
+
+        let mut shares = state.their_encrypted_secret_shares().unwrap().clone();
+        if let (Some((dealer, victim)), Some((tampered, tampered_coefficients))) =
+            (rogue, rogue_view.as_ref())
+        {
+            if dealer == i {
+                let (rogue_state, _) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &parameters,
+                    &dh_private_keys[position],
+                    &i,
+                    tampered_coefficients,
+                    tampered,
+                    CONTEXT,
+                    &mut rng,
+                )
+                .map_err(keygen_aborted)?;
+                let rogue_shares = rogue_state.their_encrypted_secret_shares().unwrap();
+                for share in shares.iter_mut().filter(|s| s.receiver_index == victim) {
+                    *share = rogue_shares
+                        .iter()
+                        .find(|s| s.receiver_index == victim)
+                        .unwrap()
+                        .clone();
+                }
+                report.record(
+                    Phase::RoundOne,
+                    Some(i),
+                    std::format!("sent rogue commitments and share to {}", victim),
+                );
+            }
+        }
+        for share in shares.iter_mut() {
+            if misbehaves(Misbehaviour::CorruptShare {
+                dealer: i,
+                recipient: share.receiver_index,
+            }) {
+                share.encrypted_polynomial_evaluation[0] ^= 1;
+                report.record(
+                    Phase::RoundOne,
+                    Some(i),
+                    std::format!("sent a corrupted share to {}", share.receiver_index),
+                );
+            }
+        }
+        coordinator
+            .include_encrypted_shares(&shares)
+            .map_err(keygen_aborted)?;
+        report.record(Phase::RoundOne, Some(i), "sent its encrypted shares");
+        states.push((i, state));
+    }
+
+    let mut round_two = Vec::new();
+    let mut complaints = Vec::new();
+    for (i, state) in states.into_iter() {
+        match state.to_round_two(coordinator.encrypted_shares_for(i), &mut rng) {
+            Ok(state) => {
+                report.record(Phase::RoundTwo, Some(i), "verified its shares");
+                round_two.push((i, state));
+            }
+            Err(Error::Complaint(mut made)) => {
+                for complaint in made.iter() {
+                    report.record(
+                        Phase::Complaint,
+                        Some(i),
+                        std::format!("complained about dealer {}", complaint.accused_index),
+                    );
+                }
+                complaints.append(&mut made);
+            }
+            Err(error) => return Err(keygen_aborted(error)),
+        }
+    }
+
+    if !complaints.is_empty() {
+        let mut culprits = Vec::new();
+        for complaint in complaints.iter() {
+            let share = coordinator.state().encrypted_shares.iter().find(|s| {
+                s.sender_index == complaint.accused_index
+                    && s.receiver_index == complaint.maker_index
+            });
+            let culprit = match (round_two.first(), share) {
+                (Some((adjudicator, state)), Some(share)) => {
+                    let culprit = state.blame(share, complaint);
+                    report.record(
+                        Phase::Complaint,
+                        Some(*adjudicator),
+                        std::format!("blamed participant {}", culprit),
+                    );
+                    culprit
+                }
+                _ => complaint.accused_index,
+            };
+            culprits.push(culprit);
+        }
+
+        return Err(Outcome::KeygenAborted {
+            blamed: blamed(culprits),
+        });
+    }
+
+    let mut group_key = None;
+    let mut secret_keys = BTreeMap::new();
+    for (i, state) in round_two.into_iter() {
+        let (key, secret_key) = state.finish().map_err(keygen_aborted)?;
+        if group_key.map_or(false, |group_key| group_key != key) {
+            report.record(Phase::Finish, Some(i), "derived another group key");
+            return Err(Outcome::KeygenAborted { blamed: Vec::new() });
+        }
+        report.record(Phase::Finish, Some(i), "derived the group key");
+        group_key = Some(key);
+        secret_keys.insert(i, secret_key);
+    }
+
+    // The public keys are derived from the broadcast commitments of the
+    // dealers who were not excluded, as any observer would.
+    let commitments: Vec<VerifiableSecretSharingCommitment> = participants
+        .iter()
+        .filter(|p| !report.excluded_dealers.contains(&p.index))
+        .map(|p| p.commitments.clone())
+        .collect();
+    let public_keys = (1..=scenario.n)
+        .map(|i| {
+            (
+                i,
+                IndividualPublicKey::generate_from_commitments(i, &commitments),
+            )
+        })
+        .collect();
+
+    Ok(Keygen {
+        group_key: group_key.unwrap(),
+        secret_keys,
+        public_keys,
+    })
+}
+
+fn refresh(
+    scenario: &Scenario,
+    keygen: Keygen,
+    ejected: u32,
+    report: &mut ScenarioReport,
+    mut rng: impl Rng + CryptoRng,
+) -> Result<Keygen, Outcome> {
+    let parameters = Parameters {
+        n: scenario.n,
+        t: scenario.t,
+    };
+    let remaining: Vec<SecretKey> = keygen
+        .secret_keys
+        .values()
+        .filter(|s| s.index != ejected)
+        .map(|s| s.duplicate())
+        .collect();
+
+    let invalid = |e: Error| Outcome::InvalidScenario {
+        reason: e.to_string(),
+    };
+
+    // Each remaining signer contributes to the refresh under a fresh DH key,
+    // and refreshes its own share with the contributions of all of them.
+    let dh_private_keys: Vec<DHPrivateKey> = remaining
+        .iter()
+        .map(|_| DHPrivateKey(Scalar::random(&mut rng)))
+        .collect();
+    let roster: Vec<(u32, DHPublicKey)> = remaining
+        .iter()
+        .zip(dh_private_keys.iter())
+        .map(|(s, dh_private_key)| (s.index, DHPublicKey::from(dh_private_key)))
+        .collect();
+    let contributions = remaining
+        .iter()
+        .zip(dh_private_keys.iter())
+        .map(|(s, dh_private_key)| {
+            eject_participant(
+                &parameters,
+                ejected,
+                s.index,
+                dh_private_key,
+                &roster,
+                &mut rng,
+            )
+        })
+        .collect::<Result<Vec<EjectionContribution>, Error>>()
+        .map_err(invalid)?;
+    let refreshed = remaining
+        .iter()
+        .zip(dh_private_keys.iter())
+        .map(|(s, dh_private_key)| {
+            s.refresh_after_ejection(&parameters, dh_private_key, &roster, &contributions)
+        })
+        .collect::<Result<Vec<SecretKey>, Error>>()
+        .map_err(invalid)?;
+    report.record(
+        Phase::Refresh,
+        None,
+        std::format!("ejected participant {}", ejected),
+    );
+
+    let mut secret_keys = keygen.secret_keys;
+    let mut public_keys = BTreeMap::new();
+    for secret_key in refreshed.into_iter() {
+        report.record(
+            Phase::Refresh,
+            Some(secret_key.index),
+            "refreshed its share",
+        );
+        public_keys.insert(secret_key.index, secret_key.to_public());
+        secret_keys.insert(secret_key.index, secret_key);
+    }
+
+    Ok(Keygen {
+        group_key: keygen.group_key,
+        secret_keys,
+        public_keys,
+    })
+}
+
+fn run_signing(
+    scenario: &Scenario,
+    keygen: &Keygen,
+    report: &mut ScenarioReport,
+    mut rng: impl Rng + CryptoRng,
+) -> Result<ThresholdSignature, Outcome> {
+    let parameters = Parameters {
+        n: scenario.n,
+        t: scenario.t,
+    };
+    let misbehaves = |m: Misbehaviour| scenario.misbehaviours.contains(&m);
+    let signing_aborted = |indices: Vec<u32>| Outcome::SigningAborted {
+        blamed: blamed(indices),
+    };
+
+    let mut aggregator = SignatureAggregator::new(
+        parameters,
+        keygen.group_key,
+        CONTEXT.as_bytes(),
+        scenario.message.as_bytes(),
+    );
+    let mut secret_commitment_shares: BTreeMap<u32, SecretCommitmentShareList> = BTreeMap::new();
+    for signer in scenario.signers.iter().copied() {
+        let public_key = match keygen.public_keys.get(&signer) {
+            Some(public_key) => public_key.clone(),
+            None => {
+                report.record(Phase::Signing, Some(signer), "is no longer a participant");
+                return Err(signing_aborted(std::vec![signer]));
+            }
+        };
+        let (public_comshares, secret_comshares) =
+            generate_commitment_share_lists(&mut rng, signer, 1);
+        aggregator
+            .include_signer(signer, public_comshares.commitments[0], public_key)
+            .map_err(|_| signing_aborted(std::vec![signer]))?;
+        secret_commitment_shares.insert(signer, secret_comshares);
+        report.record(Phase::Signing, Some(signer), "committed to its nonces");
+    }
+
+    let signers = aggregator.get_signers().clone();
+    let message_hash = compute_message_hash(CONTEXT.as_bytes(), scenario.message.as_bytes());
+    for signer in scenario.signers.iter().copied() {
+        if misbehaves(Misbehaviour::WithholdPartialSignature { signer }) {
+            report.record(
+                Phase::Signing,
+                Some(signer),
+                "withheld its partial signature",
+            );
+            continue;
+        }
+
+        let mut partial = keygen.secret_keys[&signer]
+            .sign(
+                &message_hash,
+                &keygen.group_key,
+                secret_commitment_shares.get_mut(&signer).unwrap(),
+                0,
+                &signers,
+            )
+            .map_err(|_| signing_aborted(std::vec![signer]))?;
+        if misbehaves(Misbehaviour::InvalidPartialSignature { signer }) {
+            partial.z += Scalar::one();
+            report.record(
+                Phase::Signing,
+                Some(signer),
+                "sent an invalid partial signature",
+            );
+        }
+        aggregator
+            .include_partial_signature(partial)
+            .map_err(|_| signing_aborted(std::vec![signer]))?;
+        report.record(Phase::Signing, Some(signer), "sent its partial signature");
+    }
+
+    let aggregator = aggregator.finalize().map_err(|misbehaving| {
+        report.record(Phase::Aggregation, None, "could not finalize the session");
+        signing_aborted(misbehaving.into_keys().collect())
+    })?;
+    let signature = aggregator.aggregate().map_err(|misbehaving| {
+        report.record(
+            Phase::Aggregation,
+            None,
+            "could not aggregate a valid signature",
+        );
+        signing_aborted(misbehaving.into_keys().collect())
+    })?;
+    report.record(Phase::Aggregation, None, "aggregated a valid signature");
+
+    Ok(signature)
+}
@@ -0,0 +1,665 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Serde support for the types which cross the wire.
+//!
+//! Following the convention of other cryptographic crates, the encoding of a
+//! type is serialised as a string of lowercase hexadecimal digits in
+//! human-readable formats such as JSON, and as raw bytes in binary formats.
+//! Deserialisation goes through the `from_bytes` method of the type, so that
+//! it validates its input just as much.
+//!
+//! Secret types, such as a [`SecretKey`] or the [`Coefficients`] of a dealer,
+//! are only (de)serialisable with the additional `serde-secrets` feature, as
+//! their serialised copies are not zeroed out.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::convert::TryInto;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::attestation::AttestationSignature;
+use crate::attestation::DkgReceipt;
+use crate::attestation::GroupPublicInfo;
+use crate::attestation::KeygenAttestation;
+use crate::attestation::RefreshProof;
+use crate::attestation::ResolutionRecord;
+use crate::bundle::MultiSigningResponseBundle;
+use crate::bundle::SessionTranscript;
+use crate::bundle::SigningRequestBundle;
+use crate::bundle::SigningResponseBundle;
+use crate::keygen::AbortRecord;
+use crate::keygen::Complaint;
+use crate::keygen::ComplaintProof;
+use crate::keygen::DHPublicKey;
+use crate::keygen::DhKeyHandshake;
+use crate::keygen::EncryptedSecretShare;
+use crate::keygen::GroupKey;
+use crate::keygen::IndividualPublicKey;
+use crate::keygen::Participant;
+use crate::keygen::PublicKeyPackage;
+use crate::keygen::ShareNonceList;
+use crate::keygen::VerifiableSecretSharingCommitment;
+use crate::liveness::LivenessChallenge;
+use crate::liveness::LivenessResponse;
+use crate::nizk::NizkOfSecretKey;
+use crate::parameters::Parameters;
+use crate::precomputation::PublicCommitmentShareList;
+use crate::roster::RosterProof;
+use crate::signature::AuthorizerPublicKey;
+use crate::signature::HalfAggregate;
+use crate::signature::PartialSignatureBatch;
+use crate::signature::PartialThresholdSignature;
+use crate::signature::SessionAuthorization;
+use crate::signature::ThresholdSignature;
+use crate::split::SplitRecord;
+
+#[cfg(feature = "serde-secrets")]
+use crate::keygen::Coefficients;
+#[cfg(feature = "serde-secrets")]
+use crate::keygen::DHPrivateKey;
+#[cfg(feature = "serde-secrets")]
+use crate::keygen::IdentityKey;
+#[cfg(feature = "serde-secrets")]
+use crate::keygen::SecretKey;
+#[cfg(feature = "serde-secrets")]
+use crate::keygen::SecretShare;
+#[cfg(feature = "serde-secrets")]
+use crate::keygen::TwoPartyRefreshMessage;
+#[cfg(feature = "serde-secrets")]
+use crate::precomputation::CommitmentShare;
+#[cfg(feature = "serde-secrets")]
+use crate::precomputation::SecretCommitmentShareList;
+
+/// A byte slice displayed as lowercase hexadecimal digits.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Serialise `bytes` as hexadecimal digits or as raw bytes, depending on
+/// whether the format is human-readable.
+pub(crate) fn serialize_bytes<S: Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match serializer.is_human_readable() {
+        true => serializer.collect_str(&Hex(bytes)),
+        false => serializer.serialize_bytes(bytes),
+    }
+}
+
+/// Deserialise an array of `N` bytes serialised with [`serialize_bytes`].
+pub(crate) fn deserialize_array<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    match deserializer.is_human_readable() {
+        true => deserializer.deserialize_str(ArrayVisitor::<N>(PhantomData)),
+        false => deserializer.deserialize_bytes(ArrayVisitor::<N>(PhantomData)),
+    }
+}
+
+/// Deserialise a vector of bytes serialised with [`serialize_bytes`].
+pub(crate) fn deserialize_vec<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<u8>, D::Error> {
+    match deserializer.is_human_readable() {
+        true => deserializer.deserialize_str(VecVisitor),
+        false => deserializer.deserialize_byte_buf(VecVisitor),
+    }
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+struct ArrayVisitor<const N: usize>(PhantomData<[u8; N]>);
+
+impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes, or a string of {} hexadecimal digits",
+            N,
+            2 * N
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<[u8; N], E> {
+        if v.len() != 2 * N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut res = [0u8; N];
+        for (byte, digits) in res.iter_mut().zip(v.as_bytes().chunks(2)) {
+            match (hex_digit(digits[0]), hex_digit(digits[1])) {
+                (Some(high), Some(low)) => *byte = (high << 4) | low,
+                _ => return Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<[u8; N], E> {
+        v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[u8; N], A::Error> {
+        let mut res = [0u8; N];
+        for (i, byte) in res.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+
+        Ok(res)
+    }
+}
+
+struct VecVisitor;
+
+impl<'de> Visitor<'de> for VecVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("bytes, or a string of hexadecimal digits")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+        if v.len() % 2 != 0 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        v.as_bytes()
+            .chunks(2)
+            .map(
+                |digits| match (hex_digit(digits[0]), hex_digit(digits[1])) {
+                    (Some(high), Some(low)) => Ok((high << 4) | low),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+                },
+            )
+            .collect()
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut res = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            res.push(byte);
+        }
+
+        Ok(res)
+    }
+}
+
+/// Implement `Serialize` and `Deserialize` for a type through its
+/// `to_bytes` and `from_bytes` methods, over an array of `$len` bytes.
+macro_rules! impl_serde_with_bytes {
+    ($type:ty, $len:expr) => {
+        impl serde::Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&self.to_bytes(), serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = deserialize_array::<D, $len>(deserializer)?;
+
+                <$type>::from_bytes(&bytes).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+/// Implement `Serialize` and `Deserialize` for a type through its
+/// `to_bytes` and `from_bytes` methods, over a vector of bytes.
+macro_rules! impl_serde_with_vec {
+    ($type:ty) => {
+        impl serde::Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&self.to_bytes(), serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = deserialize_vec(deserializer)?;
+
+                <$type>::from_bytes(&bytes).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+impl_serde_with_bytes!(Parameters, 8);
+impl_serde_with_bytes!(GroupKey, 32);
+impl_serde_with_bytes!(ThresholdSignature, 64);
+impl_serde_with_bytes!(DHPublicKey, 32);
+impl_serde_with_bytes!(IndividualPublicKey, 36);
+impl_serde_with_bytes!(NizkOfSecretKey, 64);
+impl_serde_with_bytes!(DhKeyHandshake, 100);
+impl_serde_with_bytes!(ComplaintProof, 96);
+impl_serde_with_bytes!(Complaint, 168);
+impl_serde_with_bytes!(PartialThresholdSignature, 36);
+impl_serde_with_bytes!(AuthorizerPublicKey, 32);
+impl_serde_with_bytes!(SessionAuthorization, 64);
+impl_serde_with_bytes!(SigningResponseBundle, 68);
+impl_serde_with_bytes!(AttestationSignature, 68);
+impl_serde_with_bytes!(LivenessChallenge, 72);
+impl_serde_with_bytes!(LivenessResponse, 100);
+impl_serde_with_bytes!(SplitRecord, 160);
+
+impl_serde_with_vec!(Participant);
+impl_serde_with_vec!(VerifiableSecretSharingCommitment);
+impl_serde_with_vec!(EncryptedSecretShare);
+impl_serde_with_vec!(ShareNonceList);
+impl_serde_with_vec!(AbortRecord);
+impl_serde_with_vec!(PublicKeyPackage);
+impl_serde_with_vec!(PublicCommitmentShareList);
+impl_serde_with_vec!(PartialSignatureBatch);
+impl_serde_with_vec!(HalfAggregate);
+impl_serde_with_vec!(SigningRequestBundle);
+impl_serde_with_vec!(MultiSigningResponseBundle);
+impl_serde_with_vec!(SessionTranscript);
+impl_serde_with_vec!(GroupPublicInfo);
+impl_serde_with_vec!(DkgReceipt);
+impl_serde_with_vec!(KeygenAttestation);
+impl_serde_with_vec!(ResolutionRecord);
+impl_serde_with_vec!(RefreshProof);
+impl_serde_with_vec!(RosterProof);
+
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_bytes!(SecretKey, 36);
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_bytes!(DHPrivateKey, 32);
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_bytes!(IdentityKey, 32);
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_bytes!(SecretShare, 40);
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_bytes!(CommitmentShare, 128);
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_bytes!(TwoPartyRefreshMessage, 189);
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_vec!(Coefficients);
+#[cfg(feature = "serde-secrets")]
+impl_serde_with_vec!(SecretCommitmentShareList);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+    use curve25519_dalek::scalar::Scalar;
+    use rand::rngs::OsRng;
+    use std::string::String;
+
+    use crate::generate_commitment_share_lists;
+    use crate::keygen::Error;
+    use crate::liveness::LivenessCoordinator;
+    use crate::signature::compute_message_hash;
+    use crate::signature::AuthorizerKey;
+    use crate::{DistributedKeyGeneration, SignatureAggregator};
+
+    fn threshold_signature() -> (GroupKey, ThresholdSignature) {
+        let params = Parameters { n: 1, t: 1 };
+        let mut rng = OsRng;
+
+        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", rng);
+        let participants = [p1.clone()];
+        let (p1_state, _) = DistributedKeyGeneration::new_initial(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1coeffs,
+            &participants,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        let p1_my_encrypted_secret_shares =
+            p1_state.their_encrypted_secret_shares().unwrap().clone();
+        let p1_state = p1_state
+            .to_round_two(p1_my_encrypted_secret_shares, rng)
+            .unwrap();
+        let (group_key, p1_sk) = p1_state.finish().unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut rng, 1, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        let signers = aggregator.get_signers().clone();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+        let partial = p1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+
+        (
+            group_key,
+            aggregator.finalize().unwrap().aggregate().unwrap(),
+        )
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        format!("\"{}\"", Hex(bytes))
+    }
+
+    #[test]
+    fn human_readable_round_trip() {
+        let params = Parameters { n: 5, t: 3 };
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(json, "\"0500000003000000\"");
+        assert_eq!(serde_json::from_str::<Parameters>(&json).unwrap(), params);
+
+        let (group_key, signature) = threshold_signature();
+        let json = serde_json::to_string(&group_key).unwrap();
+        assert_eq!(json, hex(&group_key.to_bytes()));
+        assert_eq!(serde_json::from_str::<GroupKey>(&json).unwrap(), group_key);
+
+        let json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(json, hex(&signature.to_bytes()));
+        assert_eq!(
+            serde_json::from_str::<ThresholdSignature>(&json).unwrap(),
+            signature
+        );
+
+        // Upper case digits are accepted, but not a wrong length, non-digits
+        // or an invalid encoding.
+        let json = serde_json::to_string(&group_key).unwrap().to_uppercase();
+        assert_eq!(serde_json::from_str::<GroupKey>(&json).unwrap(), group_key);
+        assert!(serde_json::from_str::<GroupKey>(&json[..json.len() - 3]).is_err());
+        assert!(serde_json::from_str::<GroupKey>(&format!("\"{}\"", "zz".repeat(32))).is_err());
+        assert!(serde_json::from_str::<GroupKey>(&hex(&[0xff; 32])).is_err());
+        assert!(serde_json::from_str::<GroupKey>("[0, 1]").is_err());
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let params = Parameters { n: 5, t: 3 };
+        let cbor = serde_cbor::to_vec(&params).unwrap();
+        // A byte string of 8 bytes, then the encoding.
+        assert_eq!(cbor[0], 0x48);
+        assert_eq!(cbor[1..], params.to_bytes());
+        assert_eq!(serde_cbor::from_slice::<Parameters>(&cbor).unwrap(), params);
+
+        let (group_key, signature) = threshold_signature();
+        let cbor = serde_cbor::to_vec(&group_key).unwrap();
+        assert_eq!(cbor[..2], [0x58, 32]);
+        assert_eq!(cbor[2..], group_key.to_bytes());
+        assert_eq!(
+            serde_cbor::from_slice::<GroupKey>(&cbor).unwrap(),
+            group_key
+        );
+
+        let cbor = serde_cbor::to_vec(&signature).unwrap();
+        assert_eq!(cbor[..2], [0x58, 64]);
+        assert_eq!(cbor[2..], signature.to_bytes());
+        assert_eq!(
+            serde_cbor::from_slice::<ThresholdSignature>(&cbor).unwrap(),
+            signature
+        );
+
+        // Wrong lengths and invalid encodings are rejected.
+        assert!(serde_cbor::from_slice::<GroupKey>(&cbor).is_err());
+        let mut cbor = serde_cbor::to_vec(&group_key).unwrap();
+        cbor[2..].copy_from_slice(&[0xff; 32]);
+        assert!(serde_cbor::from_slice::<GroupKey>(&cbor).is_err());
+
+        let key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng));
+        let cbor = serde_cbor::to_vec(&key).unwrap();
+        assert_eq!(serde_cbor::from_slice::<GroupKey>(&cbor).unwrap(), key);
+    }
+
+    /// Check that `$value` of type `$type` survives a round trip through
+    /// both a human-readable and a binary format.
+    macro_rules! assert_round_trip {
+        ($type:ty, $value:expr) => {{
+            let value: &$type = $value;
+
+            let json = serde_json::to_string(value).unwrap();
+            assert_eq!(json, hex(&value.to_bytes()), stringify!($type));
+            let decoded = serde_json::from_str::<$type>(&json).unwrap();
+            assert_eq!(decoded.to_bytes(), value.to_bytes(), stringify!($type));
+
+            let cbor = serde_cbor::to_vec(value).unwrap();
+            let decoded = serde_cbor::from_slice::<$type>(&cbor).unwrap();
+            assert_eq!(decoded.to_bytes(), value.to_bytes(), stringify!($type));
+        }};
+    }
+
+    #[test]
+    fn protocol_types_round_trip() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        let mut participants = Vec::new();
+        let mut dealers = Vec::new();
+        for i in 1..=params.n {
+            let (p, coeffs, dh_sk) = Participant::new_dealer(&params, i, "Φ", rng);
+            participants.push(p);
+            dealers.push((coeffs, dh_sk));
+        }
+
+        let mut states = Vec::new();
+        for (p, (coeffs, dh_sk)) in participants.iter().zip(dealers.iter()) {
+            let (state, _) = DistributedKeyGeneration::new_initial(
+                &params,
+                dh_sk,
+                &p.index,
+                coeffs,
+                &participants,
+                "Φ",
+                rng,
+            )
+            .unwrap();
+            states.push(state);
+        }
+        let encrypted_shares: Vec<EncryptedSecretShare> = states
+            .iter()
+            .flat_map(|state| state.their_encrypted_secret_shares().unwrap().clone())
+            .collect();
+        let shares_for = |index: u32| {
+            encrypted_shares
+                .iter()
+                .filter(|share| share.receiver_index == index)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        let participant = &participants[0];
+        assert_round_trip!(Participant, participant);
+        assert_round_trip!(VerifiableSecretSharingCommitment, &participant.commitments);
+        assert_round_trip!(DHPublicKey, &participant.dh_public_key);
+        assert_round_trip!(NizkOfSecretKey, &participant.proof_of_secret_key);
+        assert_round_trip!(
+            DhKeyHandshake,
+            &DhKeyHandshake {
+                index: participant.index,
+                dh_public_key: participant.dh_public_key.clone(),
+                signature: participant.proof_of_dh_private_key.clone(),
+            }
+        );
+        assert_round_trip!(EncryptedSecretShare, &shares_for(1)[0]);
+        assert_round_trip!(ShareNonceList, &states[0].share_nonces().unwrap());
+        assert_round_trip!(AbortRecord, &states[0].duplicate().abandon("test"));
+
+        // A corrupted share leads to a complaint.
+        let mut corrupted = shares_for(1);
+        corrupted[1].encrypted_polynomial_evaluation[0] ^= 1;
+        let complaint = match states[0].duplicate().to_round_two(corrupted, rng) {
+            Err(Error::Complaint(complaints)) => complaints[0].clone(),
+            _ => panic!("a corrupted share should lead to a complaint"),
+        };
+        assert_round_trip!(Complaint, &complaint);
+        assert_round_trip!(ComplaintProof, &complaint.proof);
+
+        let mut secret_keys = Vec::new();
+        let mut group_key = None;
+        for (i, state) in states.into_iter().enumerate() {
+            let state = state.to_round_two(shares_for(i as u32 + 1), rng).unwrap();
+            let (key, secret_key) = state.finish().unwrap();
+            group_key = Some(key);
+            secret_keys.push(secret_key);
+        }
+        let group_key = group_key.unwrap();
+        let public_keys: Vec<IndividualPublicKey> =
+            secret_keys.iter().map(|sk| sk.to_public()).collect();
+        assert_round_trip!(IndividualPublicKey, &public_keys[0]);
+        assert_round_trip!(
+            PublicKeyPackage,
+            &PublicKeyPackage::new(params, group_key, public_keys.clone()).unwrap()
+        );
+        assert_round_trip!(
+            GroupPublicInfo,
+            &GroupPublicInfo::new(params, group_key, &participants).unwrap()
+        );
+
+        // Signing by the first two participants.
+        let mut aggregator = SignatureAggregator::new(params, group_key, b"CONTEXT", b"MESSAGE");
+        let mut secret_comshares = Vec::new();
+        for sk in secret_keys[..2].iter() {
+            let (public_comshares, secret) = generate_commitment_share_lists(&mut rng, sk.index, 1);
+            assert_round_trip!(PublicCommitmentShareList, &public_comshares);
+            aggregator
+                .include_signer(sk.index, public_comshares.commitments[0], sk.to_public())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+        let session = aggregator.session();
+        let authorization = AuthorizerKey::new(rng).authorize(&session, rng);
+        assert_round_trip!(SessionAuthorization, &authorization);
+
+        let mut partials = Vec::new();
+        for (sk, secret) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial = sk.sign_session(&session, &group_key, secret, 0).unwrap();
+            assert_round_trip!(PartialThresholdSignature, &partial);
+            aggregator.include_partial_signature(partial).unwrap();
+            partials.push(partial);
+        }
+        assert_round_trip!(
+            PartialSignatureBatch,
+            &PartialSignatureBatch::new(partials).unwrap()
+        );
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert_round_trip!(ThresholdSignature, &signature);
+
+        // Liveness challenges.
+        let mut coordinator = LivenessCoordinator::new(group_key, 1);
+        let challenge = coordinator.challenge(1, rng);
+        assert_round_trip!(LivenessChallenge, &challenge);
+        assert_round_trip!(
+            LivenessResponse,
+            &challenge.respond(&secret_keys[0], &group_key, rng).unwrap()
+        );
+    }
+
+    #[test]
+    fn malformed_protocol_types() {
+        let params = Parameters { n: 3, t: 2 };
+        let (participant, _, _) = Participant::new_dealer(&params, 1, "Φ", OsRng);
+
+        // A commitment which is not a valid point is rejected, as by
+        // `from_bytes`.
+        let mut bytes = participant.to_bytes();
+        let position = bytes
+            .windows(32)
+            .position(|w| {
+                w == participant
+                    .commitments
+                    .point(0)
+                    .unwrap()
+                    .compress()
+                    .as_bytes()
+            })
+            .unwrap();
+        bytes[position..position + 32].copy_from_slice(&[0xff; 32]);
+        assert!(Participant::from_bytes(&bytes).is_err());
+        assert!(serde_json::from_str::<Participant>(&hex(&bytes)).is_err());
+        let cbor = serde_cbor::to_vec(&serde_cbor::Value::Bytes(bytes)).unwrap();
+        assert!(serde_cbor::from_slice::<Participant>(&cbor).is_err());
+
+        // Odd numbers of digits and non-digits are rejected.
+        let json = serde_json::to_string(&participant).unwrap();
+        assert!(
+            serde_json::from_str::<Participant>(&format!("{}0\"", &json[..json.len() - 1]))
+                .is_err()
+        );
+        assert!(serde_json::from_str::<Participant>(&format!("\"z{}", &json[2..])).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde-secrets")]
+    fn secret_types_round_trip() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        let (_, coefficients, dh_private_key) = Participant::new_dealer(&params, 1, "Φ", rng);
+        assert_round_trip!(Coefficients, &coefficients);
+        assert_round_trip!(DHPrivateKey, &dh_private_key);
+
+        let secret_key = SecretKey {
+            index: 1,
+            key: Scalar::random(&mut rng),
+        };
+        assert_round_trip!(SecretKey, &secret_key);
+
+        let (_, secret_comshares) = generate_commitment_share_lists(&mut rng, 1, 2);
+        assert_round_trip!(SecretCommitmentShareList, &secret_comshares);
+        assert_round_trip!(CommitmentShare, &secret_comshares.commitments[0]);
+    }
+}
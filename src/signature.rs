@@ -16,6 +16,11 @@ use alloc::boxed::Box;
 #[cfg(feature = "std")]
 use std::boxed::Box;
 
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
 use core::cmp::Ordering;
 use core::convert::TryInto;
 use core::fmt;
@@ -23,11 +28,11 @@ use core::fmt;
 #[cfg(feature = "alloc")]
 use alloc::collections::btree_map::Values;
 #[cfg(feature = "alloc")]
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 #[cfg(feature = "std")]
 use std::collections::btree_map::Values;
 #[cfg(feature = "std")]
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[cfg(feature = "alloc")]
 use alloc::string::{String, ToString};
@@ -43,14 +48,40 @@ use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
 use curve25519_dalek::ristretto::CompressedRistretto;
 use curve25519_dalek::ristretto::RistrettoPoint;
 use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use curve25519_dalek::traits::VartimeMultiscalarMul;
+
+use rand::CryptoRng;
+use rand::RngCore;
 
 use sha2::Digest;
 use sha2::{Sha256, Sha512};
 
+use zeroize::Zeroize;
+
+use crate::attestation::verify_commitment_share;
+use crate::attestation::AttestationSignature;
+use crate::ciphersuite::Ciphersuite;
+use crate::ciphersuite::Ristretto25519;
+use crate::ciphersuite::RistrettoCiphersuite;
+use crate::ciphersuite::RistrettoHash;
+use crate::ciphersuite::UniformRistretto25519;
+use crate::encoding::decode_point_checked;
+use crate::encoding::decode_scalar_canonical;
+use crate::encoding::encode_point;
+use crate::encoding::encode_scalar;
+use crate::keygen::CompressedPoint;
+use crate::keygen::DHPublicKey;
 use crate::keygen::Error;
+use crate::keygen::ErrorKind;
 use crate::keygen::GroupKey;
 use crate::keygen::IndividualPublicKey;
+use crate::keygen::PublicKeyPackage;
+use crate::metrics;
+use crate::metrics::Metrics;
+use crate::metrics::MetricsHandle;
 use crate::parameters::Parameters;
+use crate::precomputation::CommitmentShare;
 use crate::precomputation::SecretCommitmentShareList;
 
 pub use crate::keygen::SecretKey;
@@ -64,6 +95,111 @@ pub enum SignatureError {
     InvalidBindingFactor,
     /// Invalid signature
     InvalidSignature,
+    /// A partial signature from the participant with this index was invalid
+    InvalidPartialSignature(u32),
+    /// The participant with this index has exhausted its partial signature budget
+    PartialBudgetExceeded(u32),
+    /// Two distinct signers published an identical commitment point
+    DuplicateCommitment {
+        /// The indices of the two signers.
+        signers: (u32, u32),
+    },
+    /// The signer with this index published the identity or the basepoint as commitment
+    DegenerateCommitment(u32),
+    /// The commitment share of the signer with this index does not carry a
+    /// valid signature by its long-term Diffie-Hellman key
+    UnsignedCommitment(u32),
+    /// The same metadata key was given more than once
+    DuplicateMetadataKey(String),
+    /// The participant with this index is not a signer in this session
+    UnknownSigner(u32),
+    /// The participant with this index signed a different session
+    SessionMismatch(u32),
+    /// Signing would bring the commitment share pool, with this many unused
+    /// shares left, below its low watermark
+    CommitmentPoolLow(usize),
+    /// A partial signature from the participant with this index was already
+    /// included
+    DuplicatePartial(u32),
+    /// The state store failed, with this message
+    StorageError(String),
+    /// The persisted state was modified concurrently
+    ConcurrentUpdate,
+    /// The commitment share of an opened session was consumed, but its
+    /// partial signature was lost
+    InterruptedSession,
+    /// The session does not carry a valid authorization from the configured
+    /// authorizer
+    UnauthorizedSession,
+    /// The ciphersuite asks for this many nonces per commitment share, while
+    /// commitment shares hold two
+    UnsupportedNonceCount(usize),
+    /// The signatures at these positions of a batch are invalid
+    InvalidBatchItems(Vec<usize>),
+    /// The revealed message does not match the message commitment
+    MessageCommitmentMismatch,
+    /// A different message was already signed for the same intent
+    ConflictingIntent {
+        /// The message hash signed for the intent.
+        recorded: [u8; 32],
+        /// The message hash requested to be signed.
+        requested: [u8; 32],
+    },
+    /// A commitment share of a signer was already reserved
+    OverlappingReservation {
+        /// The index of the signer.
+        signer: u32,
+        /// The position of the commitment share reserved twice.
+        commitment_share_index: u32,
+    },
+    /// The group key does not match the committed public key package
+    PackageMismatch,
+    /// A signer tried to sign with the commitment shares of another
+    CommitmentShareSignerMismatch {
+        /// The index of the signer.
+        signer: u32,
+        /// The index of the participant who generated the commitment shares.
+        owner: u32,
+    },
+    /// The commitment share listed for the signer in the session is not the
+    /// one at the given index of its secret commitment share list, e.g. since
+    /// the aggregator used an outdated upload
+    StaleCommitment {
+        /// The compressed hiding and binding commitments of the secret
+        /// commitment share.
+        expected: Box<(CompressedRistretto, CompressedRistretto)>,
+        /// The compressed hiding and binding commitments listed in the session.
+        listed: Box<(CompressedRistretto, CompressedRistretto)>,
+    },
+    /// The verification descriptor asks for this unsupported feature
+    UnsupportedDescriptor(&'static str),
+    /// The message to be owned by an aggregator is longer than allowed
+    MessageTooLarge {
+        /// The length of the message in bytes.
+        length: usize,
+        /// The maximum length in bytes.
+        max: usize,
+    },
+    /// The session outlived its lifetime, or was opened later than the
+    /// current time
+    SessionExpired {
+        /// The time at which the session was opened, in milliseconds since
+        /// the Unix epoch.
+        opened_at: u64,
+        /// The current time, in milliseconds since the Unix epoch.
+        now: u64,
+    },
+    /// The signature does not carry the expected sequence number, e.g. since
+    /// it skips, replays or lacks one
+    SequenceMismatch {
+        /// The sequence number expected next.
+        expected: u64,
+        /// The sequence number of the signature, if it carries one.
+        found: Option<u64>,
+    },
+    /// The signature with this sequence number does not link to the
+    /// previous signature of the group, i.e. the sequence was forked
+    SequenceFork(u64),
     /// Custom error
     Custom(String),
 }
@@ -83,6 +219,175 @@ impl fmt::Display for SignatureError {
             SignatureError::InvalidSignature => {
                 write!(f, "The threshold signature is not correct.")
             }
+            SignatureError::InvalidPartialSignature(index) => {
+                write!(
+                    f,
+                    "The partial signature of participant {} is not correct.",
+                    index
+                )
+            }
+            SignatureError::PartialBudgetExceeded(index) => {
+                write!(
+                    f,
+                    "Participant {} has exceeded its partial signature budget.",
+                    index
+                )
+            }
+            SignatureError::DuplicateCommitment { signers } => {
+                write!(
+                    f,
+                    "Participants {} and {} published an identical commitment.",
+                    signers.0, signers.1
+                )
+            }
+            SignatureError::DegenerateCommitment(index) => {
+                write!(
+                    f,
+                    "Participant {} published a degenerate commitment.",
+                    index
+                )
+            }
+            SignatureError::UnsignedCommitment(index) => {
+                write!(f, "Participant {} published an unsigned commitment.", index)
+            }
+            SignatureError::DuplicateMetadataKey(key) => {
+                write!(f, "The metadata key {:?} was given more than once.", key)
+            }
+            SignatureError::UnknownSigner(index) => {
+                write!(f, "Participant {} is not a signer in this session.", index)
+            }
+            SignatureError::SessionMismatch(index) => {
+                write!(f, "Participant {} signed a different session.", index)
+            }
+            SignatureError::CommitmentPoolLow(remaining) => {
+                write!(
+                    f,
+                    "Only {} commitment shares are left, which is below the low watermark.",
+                    remaining
+                )
+            }
+            SignatureError::DuplicatePartial(index) => {
+                write!(
+                    f,
+                    "A partial signature of participant {} was already included.",
+                    index
+                )
+            }
+            SignatureError::StorageError(message) => {
+                write!(f, "The state store failed: {}", message)
+            }
+            SignatureError::ConcurrentUpdate => {
+                write!(f, "The persisted state was modified concurrently.")
+            }
+            SignatureError::InterruptedSession => {
+                write!(
+                    f,
+                    "The partial signature of this session was lost, and the session must be renewed."
+                )
+            }
+            SignatureError::UnauthorizedSession => {
+                write!(f, "The session is not authorized.")
+            }
+            SignatureError::UnsupportedNonceCount(count) => {
+                write!(
+                    f,
+                    "Commitment shares with {} nonces are not supported.",
+                    count
+                )
+            }
+            SignatureError::InvalidBatchItems(positions) => {
+                write!(
+                    f,
+                    "The signatures at positions {:?} of the batch are not correct.",
+                    positions
+                )
+            }
+            SignatureError::MessageCommitmentMismatch => {
+                write!(f, "The revealed message does not match its commitment.")
+            }
+            SignatureError::ConflictingIntent {
+                recorded,
+                requested,
+            } => {
+                write!(
+                    f,
+                    "The message hash {:02x?} conflicts with {:02x?}, already signed for the same intent.",
+                    requested, recorded
+                )
+            }
+            SignatureError::OverlappingReservation {
+                signer,
+                commitment_share_index,
+            } => {
+                write!(
+                    f,
+                    "The commitment share {} of signer {} is already reserved.",
+                    commitment_share_index, signer
+                )
+            }
+            SignatureError::PackageMismatch => {
+                write!(
+                    f,
+                    "The group key does not match the committed public key package."
+                )
+            }
+            SignatureError::CommitmentShareSignerMismatch { signer, owner } => {
+                write!(
+                    f,
+                    "Signer {} tried to sign with the commitment shares of participant {}.",
+                    signer, owner
+                )
+            }
+            SignatureError::StaleCommitment { expected, listed } => {
+                write!(
+                    f,
+                    "The session lists the commitments ({:?}, {:?}) instead of ({:?}, {:?}).",
+                    listed.0.as_bytes(),
+                    listed.1.as_bytes(),
+                    expected.0.as_bytes(),
+                    expected.1.as_bytes()
+                )
+            }
+            SignatureError::UnsupportedDescriptor(feature) => {
+                write!(
+                    f,
+                    "The verification descriptor asks for an unsupported {}.",
+                    feature
+                )
+            }
+            SignatureError::MessageTooLarge { length, max } => {
+                write!(
+                    f,
+                    "The message of {} bytes is longer than the maximum of {} bytes.",
+                    length, max
+                )
+            }
+            SignatureError::SessionExpired { opened_at, now } => {
+                write!(
+                    f,
+                    "The session opened at {} has expired at {}.",
+                    opened_at, now
+                )
+            }
+            SignatureError::SequenceMismatch { expected, found } => match found {
+                Some(found) => write!(
+                    f,
+                    "The signature has sequence number {}, while {} was expected.",
+                    found, expected
+                ),
+                None => write!(
+                    f,
+                    "The signature has no sequence number, while {} was expected.",
+                    expected
+                ),
+            },
+            SignatureError::SequenceFork(seq) => {
+                write!(
+                    f,
+                    "The signature with sequence number {} does not link to the previous one.",
+                    seq
+                )
+            }
             SignatureError::Custom(string) => {
                 write!(f, "{:?}", string)
             }
@@ -90,17 +395,128 @@ impl fmt::Display for SignatureError {
     }
 }
 
+impl SignatureError {
+    /// Classify this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            SignatureError::SessionMismatch(_)
+            | SignatureError::StorageError(_)
+            | SignatureError::ConcurrentUpdate => ErrorKind::Retryable,
+            SignatureError::InvalidSignature
+            | SignatureError::InvalidPartialSignature(_)
+            | SignatureError::PartialBudgetExceeded(_)
+            | SignatureError::DuplicateCommitment { .. }
+            | SignatureError::DegenerateCommitment(_)
+            | SignatureError::UnsignedCommitment(_)
+            | SignatureError::DuplicatePartial(_)
+            | SignatureError::UnauthorizedSession
+            | SignatureError::InvalidBatchItems(_)
+            | SignatureError::MessageCommitmentMismatch
+            | SignatureError::ConflictingIntent { .. }
+            | SignatureError::PackageMismatch
+            | SignatureError::SequenceMismatch { .. }
+            | SignatureError::SequenceFork(_) => ErrorKind::Misbehaviour {
+                participant: self.participant(),
+            },
+            SignatureError::MissingCommitmentShares
+            | SignatureError::InvalidBindingFactor
+            | SignatureError::DuplicateMetadataKey(_)
+            | SignatureError::UnknownSigner(_)
+            | SignatureError::CommitmentPoolLow(_)
+            | SignatureError::InterruptedSession
+            | SignatureError::UnsupportedNonceCount(_)
+            | SignatureError::OverlappingReservation { .. }
+            | SignatureError::CommitmentShareSignerMismatch { .. }
+            | SignatureError::StaleCommitment { .. }
+            | SignatureError::UnsupportedDescriptor(_)
+            | SignatureError::MessageTooLarge { .. }
+            | SignatureError::SessionExpired { .. }
+            | SignatureError::Custom(_) => ErrorKind::Configuration,
+        }
+    }
+
+    /// The index of the participant implicated by this error, if any.
+    ///
+    /// For duplicate commitments, this is the second of the two signers.
+    pub fn participant(&self) -> Option<u32> {
+        match self {
+            SignatureError::InvalidPartialSignature(index)
+            | SignatureError::PartialBudgetExceeded(index)
+            | SignatureError::DegenerateCommitment(index)
+            | SignatureError::UnsignedCommitment(index)
+            | SignatureError::DuplicatePartial(index)
+            | SignatureError::UnknownSigner(index)
+            | SignatureError::SessionMismatch(index) => Some(*index),
+            SignatureError::DuplicateCommitment { signers } => Some(signers.1),
+            SignatureError::OverlappingReservation { signer, .. }
+            | SignatureError::CommitmentShareSignerMismatch { signer, .. } => Some(*signer),
+            SignatureError::MissingCommitmentShares
+            | SignatureError::InvalidBindingFactor
+            | SignatureError::InvalidSignature
+            | SignatureError::DuplicateMetadataKey(_)
+            | SignatureError::CommitmentPoolLow(_)
+            | SignatureError::StorageError(_)
+            | SignatureError::ConcurrentUpdate
+            | SignatureError::InterruptedSession
+            | SignatureError::UnauthorizedSession
+            | SignatureError::UnsupportedNonceCount(_)
+            | SignatureError::InvalidBatchItems(_)
+            | SignatureError::MessageCommitmentMismatch
+            | SignatureError::ConflictingIntent { .. }
+            | SignatureError::PackageMismatch
+            | SignatureError::StaleCommitment { .. }
+            | SignatureError::UnsupportedDescriptor(_)
+            | SignatureError::MessageTooLarge { .. }
+            | SignatureError::SessionExpired { .. }
+            | SignatureError::SequenceMismatch { .. }
+            | SignatureError::SequenceFork(_)
+            | SignatureError::Custom(_) => None,
+        }
+    }
+}
+
 // XXX Nonce reuse is catastrophic and results in obtaining an individual
 //     signer's long-term secret key; it must be prevented at all costs.
 
 /// An individual signer in the threshold signature scheme.
-#[derive(Clone, Copy, Debug, Eq)]
+#[derive(Clone, Copy, Debug)]
 pub struct Signer {
     /// The participant index of this signer.
     pub participant_index: u32,
     /// One of the commitments that were published by each signing participant
     /// in the pre-computation phase.
     pub published_commitment_share: (RistrettoPoint, RistrettoPoint),
+    /// The compressed encodings of the published commitments.
+    pub(crate) compressed_commitment_share: (CompressedPoint, CompressedPoint),
+}
+
+impl Signer {
+    /// Create a signer with index `participant_index`, who published the
+    /// commitments `published_commitment_share`.
+    pub fn new(
+        participant_index: u32,
+        published_commitment_share: (RistrettoPoint, RistrettoPoint),
+    ) -> Signer {
+        Signer {
+            participant_index,
+            published_commitment_share,
+            compressed_commitment_share: (
+                CompressedPoint::new(&published_commitment_share.0),
+                CompressedPoint::new(&published_commitment_share.1),
+            ),
+        }
+    }
+
+    /// The compressed encodings of the hiding and binding commitments of
+    /// this signer.
+    pub(crate) fn compressed_commitment_share(&self) -> (CompressedRistretto, CompressedRistretto) {
+        let (hiding, binding) = self.published_commitment_share;
+
+        (
+            self.compressed_commitment_share.0.get(&hiding),
+            self.compressed_commitment_share.1.get(&binding),
+        )
+    }
 }
 
 impl Ord for Signer {
@@ -109,6 +525,7 @@ impl Ord for Signer {
     }
 }
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Signer {
     fn partial_cmp(&self, other: &Signer) -> Option<Ordering> {
         match self.participant_index.cmp(&other.participant_index) {
@@ -120,6 +537,8 @@ impl PartialOrd for Signer {
     }
 }
 
+impl Eq for Signer {}
+
 impl PartialEq for Signer {
     fn eq(&self, other: &Signer) -> bool {
         self.participant_index == other.participant_index
@@ -128,19 +547,36 @@ impl PartialEq for Signer {
 
 /// A partially-constructed threshold signature, made by each participant in the
 /// signing protocol during the first phase of a signature creation.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct PartialThresholdSignature {
     pub(crate) index: u32,
     pub(crate) z: Scalar,
 }
 
 impl PartialThresholdSignature {
+    /// Combine the partial signatures made by a weighted signer with each of
+    /// its indices into the single one it submits, under its lowest index, to
+    /// an aggregator which included it with
+    /// [`SignatureAggregator::include_weighted_signer`].
+    ///
+    /// # Returns
+    ///
+    /// The combined partial signature, or `None` if `partials` is empty.
+    pub fn combine(partials: &[PartialThresholdSignature]) -> Option<PartialThresholdSignature> {
+        let index = partials.iter().map(|partial| partial.index).min()?;
+
+        Some(PartialThresholdSignature {
+            index,
+            z: partials.iter().map(|partial| partial.z).sum(),
+        })
+    }
+
     /// Serialize this partial threshold signature to an array of 36 bytes.
     pub fn to_bytes(&self) -> [u8; 36] {
         let mut bytes = [0u8; 36];
 
         bytes[..4].copy_from_slice(&self.index.to_le_bytes());
-        bytes[4..].copy_from_slice(self.z.as_bytes());
+        bytes[4..].copy_from_slice(&encode_scalar(&self.z));
 
         bytes
     }
@@ -153,17 +589,120 @@ impl PartialThresholdSignature {
                 .map_err(|_| Error::SerialisationError)?,
         );
 
-        let z = Scalar::from_canonical_bytes(
-            bytes[4..36]
+        let z = decode_scalar_canonical(
+            &bytes[4..36]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .ok_or(Error::SerialisationError)?;
+        )?;
 
         Ok(PartialThresholdSignature { index, z })
     }
 }
 
+/// The partial signatures of several signers, e.g. behind the same gateway,
+/// bundled in a single message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartialSignatureBatch {
+    partials: Vec<PartialThresholdSignature>,
+}
+
+impl PartialSignatureBatch {
+    /// Bundle the given `partials`, which must come from distinct signers.
+    ///
+    /// # Returns
+    ///
+    /// A [`SignatureError::DuplicatePartial`] with the index of the first
+    /// signer with several partial signatures in the batch.
+    pub fn new(
+        partials: Vec<PartialThresholdSignature>,
+    ) -> Result<PartialSignatureBatch, SignatureError> {
+        let mut seen = BTreeSet::new();
+        for partial in partials.iter() {
+            if !seen.insert(partial.index) {
+                return Err(SignatureError::DuplicatePartial(partial.index));
+            }
+        }
+
+        Ok(PartialSignatureBatch { partials })
+    }
+
+    /// The partial signatures in this batch.
+    pub fn partials(&self) -> &[PartialThresholdSignature] {
+        &self.partials
+    }
+
+    /// Serialise this batch to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(4 + 36 * self.partials.len());
+        res.extend_from_slice(
+            &TryInto::<u32>::try_into(self.partials.len())
+                .unwrap()
+                .to_le_bytes(),
+        );
+        for partial in self.partials.iter() {
+            res.extend_from_slice(&partial.to_bytes());
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `PartialSignatureBatch`
+    ///
+    /// A batch with several partial signatures from the same signer is
+    /// rejected with a `SerialisationError`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PartialSignatureBatch, Error> {
+        let len = u32::from_le_bytes(
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        ) as usize;
+        if (bytes.len() - 4) as u64 != 36 * len as u64 {
+            return Err(Error::SerialisationError);
+        }
+
+        let mut partials = Vec::with_capacity(len);
+        for chunk in bytes[4..].chunks(36) {
+            partials.push(PartialThresholdSignature::from_bytes(
+                chunk.try_into().map_err(|_| Error::SerialisationError)?,
+            )?);
+        }
+
+        PartialSignatureBatch::new(partials).map_err(|_| Error::SerialisationError)
+    }
+
+    /// Deserialise this slice of bytes to a `PartialSignatureBatch`, as with
+    /// [`PartialSignatureBatch::from_bytes`], for an aggregator with these
+    /// `parameters`.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidNumberOfParticipants` error, before any partial signature
+    /// is decoded, if the batch claims more partial signatures than the
+    /// \\(n\\) signers of the group.
+    pub fn from_bytes_with_parameters(
+        bytes: &[u8],
+        parameters: &Parameters,
+    ) -> Result<PartialSignatureBatch, Error> {
+        let len = u32::from_le_bytes(
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        if len > parameters.n {
+            return Err(Error::InvalidNumberOfParticipants(
+                len as usize,
+                parameters.n,
+            ));
+        }
+
+        PartialSignatureBatch::from_bytes(bytes)
+    }
+}
+
 /// A complete, aggregated threshold signature.
 #[derive(Debug, Eq, PartialEq)]
 pub struct ThresholdSignature {
@@ -171,32 +710,185 @@ pub struct ThresholdSignature {
     pub(crate) z: Scalar,
 }
 
+// A threshold signature is serialised to a compressed point and a scalar.
+const _: () = assert!(ThresholdSignature::SERIALIZED_LEN == 32 + 32);
+
 impl ThresholdSignature {
+    /// The length of a serialised threshold signature, in bytes.
+    ///
+    /// The layout is:
+    ///
+    /// | bytes    | content                                             |
+    /// |----------|-----------------------------------------------------|
+    /// | `0..32`  | the compressed group commitment \\(R\\)             |
+    /// | `32..64` | the canonical encoding of the response \\(z\\)      |
+    pub const SERIALIZED_LEN: usize = 64;
+
+    /// The length of a serialised threshold signature, in bytes, e.g. to
+    /// preallocate buffers.  See [`ThresholdSignature::SERIALIZED_LEN`] for
+    /// the layout.
+    pub const fn serialized_len() -> usize {
+        ThresholdSignature::SERIALIZED_LEN
+    }
+
+    /// A hash of this threshold signature, by which the next signature of a
+    /// sequence refers to it, see [`SequenceBinding`].
+    pub fn hash(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+        h.update(b"ICE-FROST-SIGNATURE");
+        h.update(self.to_bytes());
+
+        let mut output = [0u8; 32];
+        output.copy_from_slice(h.finalize().as_slice());
+        output
+    }
+
     /// Serialize this threshold signature to an array of 64 bytes.
-    pub fn to_bytes(&self) -> [u8; 64] {
-        let mut bytes = [0u8; 64];
+    pub fn to_bytes(&self) -> [u8; ThresholdSignature::SERIALIZED_LEN] {
+        let mut bytes = [0u8; ThresholdSignature::SERIALIZED_LEN];
 
-        bytes[..32].copy_from_slice(&self.R.compress().as_bytes()[..]);
-        bytes[32..].copy_from_slice(&self.z.as_bytes()[..]);
+        bytes[..32].copy_from_slice(&encode_point(&self.R));
+        bytes[32..].copy_from_slice(&encode_scalar(&self.z));
         bytes
     }
 
     /// Attempt to deserialize a threshold signature from an array of 64 bytes.
-    pub fn from_bytes(bytes: &[u8; 64]) -> Result<ThresholdSignature, Error> {
-        let R = CompressedRistretto(
-            bytes[0..32]
+    pub fn from_bytes(
+        bytes: &[u8; ThresholdSignature::SERIALIZED_LEN],
+    ) -> Result<ThresholdSignature, Error> {
+        let R = decode_point_checked(
+            &bytes[0..32]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .decompress()
-        .ok_or(Error::SerialisationError)?;
+        )?;
 
-        let z = Scalar::from_canonical_bytes(
-            bytes[32..64]
+        let z = decode_scalar_canonical(
+            &bytes[32..64]
                 .try_into()
                 .map_err(|_| Error::SerialisationError)?,
-        )
-        .ok_or(Error::SerialisationError)?;
+        )?;
+
+        Ok(ThresholdSignature { R, z })
+    }
+
+    /// Aggregate some `partials` into a threshold signature with the given
+    /// group commitment `R`, instead of the one derived from the commitment
+    /// shares of the signers, e.g. to reproduce published test vectors.
+    ///
+    /// # Warning
+    ///
+    /// Neither the group commitment nor the partial signatures are checked,
+    /// so that the result is only a valid signature if the partial signatures
+    /// were made under this group commitment.  This is gated behind the
+    /// `test-vectors` feature, and must not be used in production.
+    #[cfg(feature = "test-vectors")]
+    pub fn aggregate_with_nonce(
+        R: RistrettoPoint,
+        partials: &[PartialThresholdSignature],
+    ) -> ThresholdSignature {
+        ThresholdSignature {
+            R,
+            z: partials.iter().map(|partial| partial.z).sum(),
+        }
+    }
+
+    /// Serialize this threshold signature to a DER encoding, for systems which
+    /// expect DER-encoded signatures.
+    ///
+    /// The encoding is the ASN.1 structure
+    ///
+    /// ```text
+    /// ThresholdSignature ::= SEQUENCE {
+    ///     R OCTET STRING (SIZE(32)),
+    ///     z INTEGER
+    /// }
+    /// ```
+    ///
+    /// where `R` is the compressed group commitment and `z` is the big-endian,
+    /// minimally-encoded, non-negative response scalar.
+    ///
+    /// # Note
+    ///
+    /// The raw 64 bytes encoding from [`ThresholdSignature::to_bytes`] remains
+    /// the primary format of this crate.
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut z_bytes = encode_scalar(&self.z);
+        z_bytes.reverse();
+
+        let leading_zeros = z_bytes.iter().take(31).take_while(|b| **b == 0).count();
+        let z_bytes = &z_bytes[leading_zeros..];
+        let z_padding = (z_bytes[0] & 0x80 != 0) as usize;
+        let z_len = z_bytes.len() + z_padding;
+
+        let mut res = Vec::with_capacity(2 + 34 + 2 + z_len);
+        res.push(0x30);
+        res.push((34 + 2 + z_len) as u8);
+
+        res.push(0x04);
+        res.push(0x20);
+        res.extend_from_slice(&encode_point(&self.R));
+
+        res.push(0x02);
+        res.push(z_len as u8);
+        if z_padding == 1 {
+            res.push(0x00);
+        }
+        res.extend_from_slice(z_bytes);
+
+        res
+    }
+
+    /// Attempt to deserialize a threshold signature from its DER encoding.
+    ///
+    /// Decoding is strict: only the exact structure produced by
+    /// [`ThresholdSignature::to_der`] is accepted, and any non-minimal length or
+    /// integer encoding, negative integer, or trailing data is rejected.
+    pub fn from_der(bytes: &[u8]) -> Result<ThresholdSignature, Error> {
+        // The outer SEQUENCE always fits a short-form length.
+        if bytes.len() < 2 || bytes[0] != 0x30 || bytes[1] as usize != bytes.len() - 2 {
+            return Err(Error::SerialisationError);
+        }
+
+        let content = &bytes[2..];
+        if content.len() < 37 || content[0] != 0x04 || content[1] != 0x20 {
+            return Err(Error::SerialisationError);
+        }
+
+        let R = decode_point_checked(
+            &content[2..34]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        let integer = &content[34..];
+        let z_len = integer[1] as usize;
+        if integer[0] != 0x02 || z_len == 0 || z_len > 33 || integer.len() != 2 + z_len {
+            return Err(Error::SerialisationError);
+        }
+
+        let z_bytes = &integer[2..];
+        // Negative integers are not valid scalars.
+        if z_bytes[0] & 0x80 != 0 {
+            return Err(Error::SerialisationError);
+        }
+        // A leading zero byte is only allowed when the next byte has its high bit set.
+        if z_len > 1 && z_bytes[0] == 0x00 && z_bytes[1] & 0x80 == 0 {
+            return Err(Error::SerialisationError);
+        }
+        let z_bytes = if z_len == 33 {
+            if z_bytes[0] != 0x00 {
+                return Err(Error::SerialisationError);
+            }
+            &z_bytes[1..]
+        } else {
+            z_bytes
+        };
+
+        let mut array = [0u8; 32];
+        array[32 - z_bytes.len()..].copy_from_slice(z_bytes);
+        array.reverse();
+
+        let z = decode_scalar_canonical(&array)?;
 
         Ok(ThresholdSignature { R, z })
     }
@@ -220,6 +912,11 @@ macro_rules! impl_indexed_hashmap {
                 self.0.get(&index.to_be_bytes())
             }
 
+            #[allow(unused)]
+            pub(crate) fn remove(&mut self, index: &u32) -> Option<$item> {
+                self.0.remove(&index.to_be_bytes())
+            }
+
             #[allow(unused)]
             pub(crate) fn sorted(&self) -> Vec<(u32, $item)> {
                 let mut sorted: Vec<(u32, $item)> = Vec::with_capacity(self.0.len());
@@ -262,83 +959,508 @@ pub(crate) struct IndividualPublicKeys(pub(crate) BTreeMap<[u8; 4], RistrettoPoi
 
 impl_indexed_hashmap!(Type = IndividualPublicKeys, Item = RistrettoPoint);
 
-/// Compute a Sha-256 hash of a `context_string` and a `message`.
+/// Compute the message hash of the [`Ristretto25519`] ciphersuite, i.e. a
+/// Sha-256 hash, of a `context_string` and a `message`.
 pub fn compute_message_hash(context_string: &[u8], message: &[u8]) -> [u8; 32] {
-    let mut h = Sha256::new();
-
-    h.update(context_string);
-    h.update(message);
+    Ristretto25519::message_hash(context_string, message)
+}
 
-    let mut output = [0u8; 32];
+/// The maximum length in bytes of a message owned by a [`SignatureAggregator`],
+/// as constructed by [`SignatureAggregator::from_signable`].
+///
+/// Larger messages should be hashed by the caller, and signed with
+/// [`SignatureAggregator::new_prehashed`].
+pub const MAX_OWNED_MESSAGE_LENGTH: usize = 64 * 1024 * 1024;
 
-    output.copy_from_slice(h.finalize().as_slice());
-    output
+/// A domain type which can be signed, as a context string and a message.
+///
+/// Implementing this trait once for a type, rather than serialising it at
+/// every call site, ensures that the bytes signed with
+/// [`SignatureAggregator::from_signable`] are the ones verified with
+/// [`ThresholdSignature::verify_signable`].
+pub trait Signable {
+    /// The context string and the message to be signed, in this order.
+    fn to_signing_bytes(&self) -> (Vec<u8>, Vec<u8>);
 }
 
-fn compute_binding_factors_and_group_commitment(
-    message_hash: &[u8; 32],
-    signers: &[Signer],
-) -> (BTreeMap<u32, Scalar>, SignerRs) {
-    let mut binding_factors: BTreeMap<u32, Scalar> = BTreeMap::new();
-    let mut Rs: SignerRs = SignerRs::new();
-
-    // [CFRG] Should the hash function be hardcoded in the RFC or should
-    // we instead specify the output/block size?
-    let mut h = Sha512::new();
+/// Compute the hash of a `context_string` and a `message` with the given
+/// [`Ciphersuite`].
+pub fn compute_message_hash_with<C: Ciphersuite>(
+    context_string: &[u8],
+    message: &[u8],
+) -> [u8; 32] {
+    C::message_hash(context_string, message)
+}
 
-    // [DIFFERENT_TO_PAPER] We use a context string for computing the binding
-    // factor. The message is then hashed first, which does not match the order
-    // in the paper.
-    h.update(b"FROST-SHA512");
-    h.update(&message_hash[..]);
+/// The binding factors and individual group commitments of the signers.
+type BindingFactorsAndCommitments = (BTreeMap<u32, Scalar>, SignerRs);
 
-    // [DIFFERENT_TO_PAPER] We add the set of participants (in the paper
-    // B = <(i, D_{ij}, E_(ij))> i \E S) here to avoid rehashing them over and
-    // over again.
-    for signer in signers.iter() {
-        let hiding = signer.published_commitment_share.0;
-        let binding = signer.published_commitment_share.1;
+/// The hash functions of a [`Ciphersuite`], as used by a [`SignatureAggregator`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HashFunctions {
+    message_hash: fn(&[u8], &[u8]) -> [u8; 32],
+    binding_factors: fn(&[u8; 32], &GroupKey, &[Signer]) -> BindingFactorsAndCommitments,
+    challenge: fn(&[u8; 32], &GroupKey, &RistrettoPoint, Option<&Parameters>) -> Scalar,
+}
 
-        h.update(signer.participant_index.to_be_bytes());
-        h.update(hiding.compress().as_bytes());
-        h.update(binding.compress().as_bytes());
+impl HashFunctions {
+    fn new<C: RistrettoCiphersuite>() -> HashFunctions {
+        HashFunctions {
+            message_hash: C::message_hash,
+            binding_factors: compute_binding_factors_and_group_commitment::<C>,
+            challenge: compute_challenge::<C>,
+        }
     }
+}
 
-    for signer in signers.iter() {
-        let hiding = signer.published_commitment_share.0;
-        let binding = signer.published_commitment_share.1;
+/// Sort some key-value `metadata` by key, rejecting duplicate keys.
+pub(crate) fn canonicalise_metadata(
+    metadata: &[(&str, &[u8])],
+) -> Result<Vec<(String, Vec<u8>)>, SignatureError> {
+    let mut canonical: Vec<(String, Vec<u8>)> = metadata
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_vec()))
+        .collect();
+    canonical.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for pair in canonical.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            return Err(SignatureError::DuplicateMetadataKey(pair[0].0.clone()));
+        }
+    }
 
-        let mut h1 = h.clone();
+    Ok(canonical)
+}
 
-        // [DIFFERENT_TO_PAPER] The participant index is added last
-        // to finish their unique calculation of rho.
-        h1.update(signer.participant_index.to_be_bytes());
-        h1.update(hiding.compress().as_bytes());
-        h1.update(binding.compress().as_bytes());
+/// Bind canonicalised `metadata` to a `message_hash`.
+pub(crate) fn bind_canonical_metadata(
+    message_hash: &[u8; 32],
+    metadata: &[(String, Vec<u8>)],
+) -> [u8; 32] {
+    if metadata.is_empty() {
+        return *message_hash;
+    }
 
-        let binding_factor = Scalar::from_hash(h1); // This is rho in the paper.
+    let mut h = Sha256::new();
 
-        // THIS IS THE MAGIC STUFF ↓↓↓
-        Rs.insert(
-            &signer.participant_index,
-            hiding + (binding_factor * binding),
-        );
-        binding_factors.insert(signer.participant_index, binding_factor);
+    h.update(b"ICE-FROST-METADATA");
+    h.update(&message_hash[..]);
+    h.update((metadata.len() as u32).to_le_bytes());
+    for (key, value) in metadata.iter() {
+        h.update((key.len() as u32).to_le_bytes());
+        h.update(key.as_bytes());
+        h.update((value.len() as u32).to_le_bytes());
+        h.update(value);
     }
-    (binding_factors, Rs)
-}
 
-fn compute_challenge(message_hash: &[u8; 32], group_key: &GroupKey, R: &RistrettoPoint) -> Scalar {
-    let mut h2 = Sha512::new();
+    let mut output = [0u8; 32];
+
+    output.copy_from_slice(h.finalize().as_slice());
+    output
+}
+
+/// Bind some key-value `metadata` (e.g. a chain identifier or a policy
+/// version) to a `message_hash`, as computed by [`compute_message_hash`].
+///
+/// The metadata is sorted by key and serialised with length prefixes before
+/// being hashed together with the `message_hash`, so that the result does not
+/// depend on the order of the pairs.  As the resulting hash is what gets
+/// signed, a signature over it cannot be transplanted to another message,
+/// context, or metadata.  Empty `metadata` leaves the `message_hash` unchanged.
+///
+/// # Returns
+///
+/// The bound message hash, or a [`SignatureError::DuplicateMetadataKey`] if
+/// a key appears more than once.
+pub fn bind_metadata(
+    message_hash: &[u8; 32],
+    metadata: &[(&str, &[u8])],
+) -> Result<[u8; 32], SignatureError> {
+    let canonical = canonicalise_metadata(metadata)?;
+
+    Ok(bind_canonical_metadata(message_hash, &canonical))
+}
+
+/// Bind the identity of the coordinator orchestrating a signing session to a
+/// `message_hash`, as computed by [`compute_message_hash`] and possibly
+/// bound to metadata with [`bind_metadata`].
+///
+/// As the challenge is computed over the resulting hash, a signature made
+/// under one coordinator does not verify under another one.
+pub fn bind_coordinator_id(message_hash: &[u8; 32], coordinator_id: &[u8]) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-COORDINATOR");
+    h.update((coordinator_id.len() as u32).to_le_bytes());
+    h.update(coordinator_id);
+    h.update(&message_hash[..]);
+
+    let mut output = [0u8; 32];
+
+    output.copy_from_slice(h.finalize().as_slice());
+    output
+}
+
+/// The position of a signature in the totally ordered sequence of signatures
+/// of a group, as bound into it with [`SignatureAggregator::bind_sequence`].
+///
+/// Each signature commits to its sequence number and to the
+/// [`ThresholdSignature::hash`] of the previous signature of the group, so
+/// that the signatures form a hash chain in which gaps, replays and forks can
+/// be detected with [`verify_sequence`].  The binding is carried in the
+/// metadata of the signature, under the reserved keys
+/// [`SequenceBinding::SEQ_KEY`] and [`SequenceBinding::PREV_KEY`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SequenceBinding {
+    /// The sequence number of the signature, starting at 0.
+    pub seq: u64,
+    /// The hash of the previous signature, or zero for the first one.
+    pub prev: [u8; 32],
+}
+
+impl SequenceBinding {
+    /// The metadata key of the sequence number, as 8 little-endian bytes.
+    pub const SEQ_KEY: &'static str = "ice-frost-seq";
+    /// The metadata key of the hash of the previous signature.
+    pub const PREV_KEY: &'static str = "ice-frost-prev";
+
+    /// The binding of the first signature of a group.
+    pub fn genesis() -> SequenceBinding {
+        SequenceBinding {
+            seq: 0,
+            prev: [0u8; 32],
+        }
+    }
+
+    /// The binding of the signature following the `signature` made with
+    /// this binding.
+    pub fn next(&self, signature: &ThresholdSignature) -> SequenceBinding {
+        SequenceBinding {
+            seq: self.seq + 1,
+            prev: signature.hash(),
+        }
+    }
+
+    /// Read the binding from canonicalised `metadata`, if it holds both of
+    /// its keys with well-formed values.
+    pub(crate) fn from_metadata(metadata: &[(String, Vec<u8>)]) -> Option<SequenceBinding> {
+        let get = |key: &str| {
+            metadata
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_slice())
+        };
+
+        Some(SequenceBinding {
+            seq: u64::from_le_bytes(get(SequenceBinding::SEQ_KEY)?.try_into().ok()?),
+            prev: get(SequenceBinding::PREV_KEY)?.try_into().ok()?,
+        })
+    }
+}
+
+/// Check that the signature described by `descriptor` directly follows the
+/// `previous` signature, described by `previous_descriptor`, in the sequence
+/// of signatures of a group.
+///
+/// Only the linkage is checked here: each signature must also be verified
+/// with [`ThresholdSignature::verify_with_descriptor`], which ensures that
+/// the sequence binding of its descriptor is the one it was signed with.
+///
+/// # Returns
+///
+/// A `SequenceMismatch` error if either descriptor carries no sequence
+/// binding or if the sequence number of the signature does not follow the
+/// one of `previous`, and a `SequenceFork` error if the signature does not
+/// commit to the hash of `previous`.
+pub fn verify_sequence(
+    previous: &ThresholdSignature,
+    previous_descriptor: &VerificationDescriptor,
+    descriptor: &VerificationDescriptor,
+) -> Result<(), SignatureError> {
+    let previous_binding =
+        previous_descriptor
+            .sequence
+            .ok_or(SignatureError::SequenceMismatch {
+                expected: 0,
+                found: None,
+            })?;
+    let expected = previous_binding.next(previous);
+    let binding = descriptor
+        .sequence
+        .ok_or(SignatureError::SequenceMismatch {
+            expected: expected.seq,
+            found: None,
+        })?;
+
+    if binding.seq != expected.seq {
+        return Err(SignatureError::SequenceMismatch {
+            expected: expected.seq,
+            found: Some(binding.seq),
+        });
+    }
+    if binding.prev != expected.prev {
+        return Err(SignatureError::SequenceFork(binding.seq));
+    }
+
+    Ok(())
+}
+
+/// Compute the digest of canonicalised `metadata`, as committed to by a
+/// [`VerificationDescriptor`].
+pub(crate) fn metadata_digest(metadata: &[(String, Vec<u8>)]) -> Option<[u8; 32]> {
+    if metadata.is_empty() {
+        return None;
+    }
+
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-METADATA-DIGEST");
+    h.update((metadata.len() as u32).to_le_bytes());
+    for (key, value) in metadata.iter() {
+        h.update((key.len() as u32).to_le_bytes());
+        h.update(key.as_bytes());
+        h.update((value.len() as u32).to_le_bytes());
+        h.update(value);
+    }
+
+    let mut output = [0u8; 32];
+
+    output.copy_from_slice(h.finalize().as_slice());
+    Some(output)
+}
+
+/// How a consumer of a [`ThresholdSignature`] is expected to verify it, as
+/// checked by [`ThresholdSignature::verify_with_descriptor`].
+///
+/// The ciphersuite and the key encoding are plain identifiers, so that a
+/// descriptor produced by a newer version of this crate can still be parsed,
+/// and then fails to verify rather than to deserialise.  Only Ristretto
+/// keys without tweak are currently supported, under either the
+/// [`Ristretto25519`] ciphersuite or the [`UniformRistretto25519`] one over
+/// Sha-512.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationDescriptor {
+    /// The identifier of the ciphersuite the signature was produced with.
+    pub suite_id: u8,
+    /// The identifier of the encoding of the group key.
+    pub key_encoding: u8,
+    /// The tweak added to the group key, if any.
+    pub tweak: Option<[u8; 32]>,
+    /// The digest of the metadata bound into the signature, if any.
+    pub metadata_digest: Option<[u8; 32]>,
+    /// The parameters bound into the challenge, if any.
+    pub parameters: Option<Parameters>,
+    /// The identity of the coordinator bound into the challenge, if any.
+    pub coordinator_id: Option<Vec<u8>>,
+    /// The position of the signature in the sequence of signatures of its
+    /// group, if any, as also carried in its metadata.
+    pub sequence: Option<SequenceBinding>,
+    /// The context string of the message.
+    pub context: Vec<u8>,
+}
+
+impl VerificationDescriptor {
+    /// The identifier of the [`Ristretto25519`] ciphersuite.
+    pub const DEFAULT_SUITE: u8 = 0;
+    /// The identifier of the [`UniformRistretto25519`] ciphersuite over Sha-512.
+    pub const UNIFORM_SHA512_SUITE: u8 = 1;
+
+    /// The identifier of compressed Ristretto group keys.
+    pub const RISTRETTO_KEY: u8 = 0;
+    /// The identifier of Ed25519-compatible group keys.
+    pub const ED25519_KEY: u8 = 1;
+    /// The identifier of BIP-340 x-only group keys.
+    pub const X_ONLY_KEY: u8 = 2;
+
+    /// The descriptor of the signatures aggregated in a `session` under the
+    /// [`Ristretto25519`] ciphersuite.
+    pub fn for_session(session: &SigningSession) -> VerificationDescriptor {
+        VerificationDescriptor {
+            suite_id: VerificationDescriptor::DEFAULT_SUITE,
+            key_encoding: VerificationDescriptor::RISTRETTO_KEY,
+            tweak: None,
+            metadata_digest: metadata_digest(&session.metadata),
+            parameters: session.parameters,
+            coordinator_id: session.coordinator_id.clone(),
+            sequence: session.sequence(),
+            context: session.context.clone(),
+        }
+    }
+}
+
+fn compute_binding_factors_and_group_commitment<C: RistrettoCiphersuite>(
+    message_hash: &[u8; 32],
+    group_key: &GroupKey,
+    signers: &[Signer],
+) -> (BTreeMap<u32, Scalar>, SignerRs) {
+    let binding_factors = match C::RFC9591_CONTEXT_STRING {
+        Some(context_string) => {
+            compute_rfc9591_binding_factors::<C>(context_string, message_hash, group_key, signers)
+        }
+        None => compute_binding_factors::<C>(message_hash, signers),
+    };
+    let mut Rs: SignerRs = SignerRs::new();
+
+    for signer in signers.iter() {
+        let hiding = signer.published_commitment_share.0;
+        let binding = signer.published_commitment_share.1;
+        let binding_factor = binding_factors[&signer.participant_index]; // This is rho in the paper.
+
+        // THIS IS THE MAGIC STUFF ↓↓↓
+        //
+        // The j-th nonce commitment is weighted by rho^j, see
+        // `Ciphersuite::NONCE_COUNT`, i.e. D + rho * E for standard FROST.
+        let commitments = [hiding, binding];
+        let mut R_i = commitments[0];
+        let mut weight = Scalar::one();
+        for commitment in commitments[1..].iter() {
+            weight *= binding_factor;
+            R_i += commitment * weight;
+        }
+        Rs.insert(&signer.participant_index, R_i);
+    }
+    (binding_factors, Rs)
+}
+
+fn compute_binding_factors<C: RistrettoCiphersuite>(
+    message_hash: &[u8; 32],
+    signers: &[Signer],
+) -> BTreeMap<u32, Scalar> {
+    let mut binding_factors: BTreeMap<u32, Scalar> = BTreeMap::new();
+
+    // [CFRG] Should the hash function be hardcoded in the RFC or should
+    // we instead specify the output/block size?
+    let mut h = C::Hash::default();
+
+    // [DIFFERENT_TO_PAPER] We use a context string for computing the binding
+    // factor. The message is then hashed first, which does not match the order
+    // in the paper.
+    h.update(b"FROST-SHA512");
+    h.update(&message_hash[..]);
+
+    // [DIFFERENT_TO_PAPER] We add the set of participants (in the paper
+    // B = <(i, D_{ij}, E_(ij))> i \E S) here to avoid rehashing them over and
+    // over again.
+    for signer in signers.iter() {
+        let (hiding, binding) = signer.compressed_commitment_share();
+
+        h.update(signer.participant_index.to_be_bytes());
+        h.update(hiding.as_bytes());
+        h.update(binding.as_bytes());
+    }
+
+    for signer in signers.iter() {
+        let (compressed_hiding, compressed_binding) = signer.compressed_commitment_share();
+
+        let mut h1 = h.clone();
+
+        // [DIFFERENT_TO_PAPER] The participant index is added last
+        // to finish their unique calculation of rho.
+        h1.update(signer.participant_index.to_be_bytes());
+        h1.update(compressed_hiding.as_bytes());
+        h1.update(compressed_binding.as_bytes());
+
+        binding_factors.insert(signer.participant_index, C::hash_to_scalar(h1));
+    }
+    binding_factors
+}
+
+/// Derive the binding factors of the `signers` as in RFC 9591, under the
+/// `context_string` of its ciphersuite, the 32-byte `message_hash` being the
+/// message of the RFC.
+fn compute_rfc9591_binding_factors<C: RistrettoCiphersuite>(
+    context_string: &str,
+    message_hash: &[u8; 32],
+    group_key: &GroupKey,
+    signers: &[Signer],
+) -> BTreeMap<u32, Scalar> {
+    // The commitment list of the RFC is sorted by identifier, which is the
+    // participant index encoded as a scalar.
+    let mut sorted: Vec<&Signer> = signers.iter().collect();
+    sorted.sort_by_key(|signer| signer.participant_index);
+
+    let mut h4 = C::Hash::default();
+    h4.update(context_string.as_bytes());
+    h4.update(b"msg");
+    h4.update(&message_hash[..]);
+
+    let mut h5 = C::Hash::default();
+    h5.update(context_string.as_bytes());
+    h5.update(b"com");
+    for signer in sorted.iter() {
+        let (hiding, binding) = signer.compressed_commitment_share();
+
+        h5.update(Scalar::from(signer.participant_index).as_bytes());
+        h5.update(hiding.as_bytes());
+        h5.update(binding.as_bytes());
+    }
+
+    let mut h = C::Hash::default();
+    h.update(context_string.as_bytes());
+    h.update(b"rho");
+    h.update(group_key.to_bytes());
+    h.update(h4.finalize());
+    h.update(h5.finalize());
+
+    sorted
+        .iter()
+        .map(|signer| {
+            let mut h1 = h.clone();
+            h1.update(Scalar::from(signer.participant_index).as_bytes());
+
+            (signer.participant_index, C::hash_to_scalar(h1))
+        })
+        .collect()
+}
+
+/// Compute the group commitment \\(R\\) and the challenge of a session with
+/// the [`Ristretto25519`] ciphersuite, from its public data only.
+pub(crate) fn compute_group_commitment_and_challenge(
+    message_hash: &[u8; 32],
+    group_key: &GroupKey,
+    signers: &[Signer],
+    parameters: Option<&Parameters>,
+) -> (RistrettoPoint, Scalar) {
+    let (_, Rs) = compute_binding_factors_and_group_commitment::<Ristretto25519>(
+        message_hash,
+        group_key,
+        signers,
+    );
+    let R: RistrettoPoint = Rs.values().sum();
+    let challenge = compute_challenge::<Ristretto25519>(message_hash, group_key, &R, parameters);
+
+    (R, challenge)
+}
 
-    // XXX [PAPER] Decide if we want a context string for the challenge.  This
-    // would break compatibility with standard ed25519 libraries for verification.
-    h2.update(b"FROST-SHA512");
+pub(crate) fn compute_challenge<C: RistrettoCiphersuite>(
+    message_hash: &[u8; 32],
+    group_key: &GroupKey,
+    R: &RistrettoPoint,
+    parameters: Option<&Parameters>,
+) -> Scalar {
+    let mut h2 = C::Hash::default();
+
+    match C::RFC9591_CONTEXT_STRING {
+        Some(context_string) => {
+            h2.update(context_string.as_bytes());
+            h2.update(b"chal");
+        }
+        None => {
+            // XXX [PAPER] Decide if we want a context string for the challenge.  This
+            // would break compatibility with standard ed25519 libraries for verification.
+            h2.update(b"FROST-SHA512");
+            // Binding the parameters prevents a signature from being reinterpreted
+            // under another threshold or number of participants.
+            if let Some(parameters) = parameters {
+                h2.update(parameters.to_bytes());
+            }
+        }
+    }
     h2.update(R.compress().as_bytes());
     h2.update(group_key.to_bytes());
     h2.update(&message_hash[..]);
 
-    Scalar::from_hash(h2)
+    C::hash_to_scalar(h2)
 }
 
 /// Calculate using Lagrange's method the interpolation of a polynomial.
@@ -372,6 +1494,62 @@ pub(crate) fn calculate_lagrange_coefficients(
     Ok(num * den.invert())
 }
 
+/// Calculate the Lagrange coefficient of the signer with this `index`, for the
+/// interpolation at zero of a polynomial from the shares of all `signers`.
+///
+/// This is the coefficient applied to the share of each signer when signing
+/// and aggregating, so that the sum of the weighted secret shares of a valid
+/// set of signers is the group secret key.
+///
+/// # Returns
+///
+/// An `UnknownSigner` error if `index` is not one of the `signers`, and a
+/// `Custom` error if an index appears more than once in `signers`.
+pub fn lagrange_coefficient(index: u32, signers: &[u32]) -> Result<Scalar, SignatureError> {
+    if !signers.contains(&index) {
+        return Err(SignatureError::UnknownSigner(index));
+    }
+    let mut sorted = signers.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() != signers.len() {
+        return Err(SignatureError::Custom(
+            "Duplicate shares provided".to_string(),
+        ));
+    }
+
+    calculate_lagrange_coefficients(&index, signers)
+        .map_err(|e| SignatureError::Custom(e.to_string()))
+}
+
+/// Calculate the Lagrange coefficient of `participant_index` for the
+/// interpolation of a polynomial at `x`, rather than at zero.
+pub(crate) fn calculate_lagrange_coefficients_at(
+    x: &Scalar,
+    participant_index: &u32,
+    all_participant_indices: &[u32],
+) -> Result<Scalar, &'static str> {
+    let mut num = Scalar::one();
+    let mut den = Scalar::one();
+
+    let mine = Scalar::from(*participant_index);
+
+    for j in all_participant_indices.iter() {
+        if j == participant_index {
+            continue;
+        }
+        let s = Scalar::from(*j);
+
+        num *= x - s;
+        den *= mine - s;
+    }
+
+    if den == Scalar::zero() {
+        return Err("Duplicate shares provided");
+    }
+    Ok(num * den.invert())
+}
+
 impl SecretKey {
     /// Compute an individual signer's [`PartialThresholdSignature`] contribution to
     /// a [`ThresholdSignature`] on a `message`.
@@ -379,7 +1557,7 @@ impl SecretKey {
     /// # Inputs
     ///
     /// * The `message_hash` to be signed by every individual signer, this should be
-    ///   the `Sha512` digest of the message, optionally along with some application-specific
+    ///   the hash of the message by the [`Ciphersuite`], optionally along with some application-specific
     ///   context string, and can be calculated with the helper function
     ///   [`compute_message_hash`].
     /// * The public [`GroupKey`] for this group of signing participants,
@@ -391,7 +1569,9 @@ impl SecretKey {
     ///
     /// The secret share `index` here **must** be the same secret share
     /// corresponding to its public commitment which is passed to
-    /// `SignatureAggregrator.include_signer()`.
+    /// `SignatureAggregrator.include_signer()`.  Otherwise, signing fails
+    /// with a [`SignatureError::StaleCommitment`] error, without consuming
+    /// the commitment share.
     ///
     /// # Returns
     ///
@@ -408,14 +1588,89 @@ impl SecretKey {
         my_commitment_share_index: usize,
         signers: &[Signer],
     ) -> Result<PartialThresholdSignature, SignatureError> {
+        self.sign_with::<Ristretto25519>(
+            message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+        )
+    }
+
+    /// Compute an individual signer's [`PartialThresholdSignature`] as with
+    /// [`SecretKey::sign`], using the hashes of the given [`Ciphersuite`].
+    ///
+    /// The `message_hash` should be computed with
+    /// [`compute_message_hash_with`] for the same ciphersuite.
+    pub fn sign_with<C: RistrettoCiphersuite>(
+        &self,
+        message_hash: &[u8; 32],
+        group_key: &GroupKey,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList,
+        my_commitment_share_index: usize,
+        signers: &[Signer],
+    ) -> Result<PartialThresholdSignature, SignatureError> {
+        self.sign_internal::<C>(
+            message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            signers,
+            None,
+        )
+    }
+
+    fn sign_internal<C: RistrettoCiphersuite>(
+        &self,
+        message_hash: &[u8; 32],
+        group_key: &GroupKey,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList,
+        my_commitment_share_index: usize,
+        signers: &[Signer],
+        parameters: Option<&Parameters>,
+    ) -> Result<PartialThresholdSignature, SignatureError> {
+        if C::NONCE_COUNT != CommitmentShare::NONCE_COUNT {
+            return Err(SignatureError::UnsupportedNonceCount(C::NONCE_COUNT));
+        }
+        if let Some(owner) = my_secret_commitment_share_list.participant_index {
+            if owner != self.index {
+                return Err(SignatureError::CommitmentShareSignerMismatch {
+                    signer: self.index,
+                    owner,
+                });
+            }
+        }
         if my_commitment_share_index + 1 > my_secret_commitment_share_list.commitments.len() {
             return Err(SignatureError::MissingCommitmentShares);
         }
+        if my_secret_commitment_share_list.is_low() {
+            return Err(SignatureError::CommitmentPoolLow(
+                my_secret_commitment_share_list.commitments.len(),
+            ));
+        }
+        // A session listing another commitment share for us than the one we
+        // are about to use would yield a partial signature which does not
+        // aggregate, so we refuse to sign it before touching our nonces.
+        if let Some(me) = signers.iter().find(|s| s.participant_index == self.index) {
+            let my_commitment_share =
+                &my_secret_commitment_share_list.commitments[my_commitment_share_index];
+            let expected = (
+                my_commitment_share.hiding.compressed_sealed(),
+                my_commitment_share.binding.compressed_sealed(),
+            );
+            let listed = me.compressed_commitment_share();
+            if listed != expected {
+                return Err(SignatureError::StaleCommitment {
+                    expected: Box::new(expected),
+                    listed: Box::new(listed),
+                });
+            }
+        }
 
         let (binding_factors, Rs) =
-            compute_binding_factors_and_group_commitment(message_hash, signers);
+            compute_binding_factors_and_group_commitment::<C>(message_hash, group_key, signers);
         let R: RistrettoPoint = Rs.values().sum();
-        let challenge = compute_challenge(message_hash, group_key, &R);
+        let challenge = compute_challenge::<C>(message_hash, group_key, &R, parameters);
         let my_binding_factor = binding_factors
             .get(&self.index)
             .ok_or(SignatureError::InvalidBindingFactor)?;
@@ -424,10 +1679,15 @@ impl SecretKey {
         let lambda: Scalar = calculate_lagrange_coefficients(&self.index, &all_participant_indices)
             .map_err(|e| SignatureError::Custom(e.to_string()))?;
         let my_commitment_share =
-            my_secret_commitment_share_list.commitments[my_commitment_share_index].clone();
-        let z = my_commitment_share.hiding.nonce
-            + (my_commitment_share.binding.nonce * my_binding_factor)
-            + (lambda * self.key * challenge);
+            &my_secret_commitment_share_list.commitments[my_commitment_share_index];
+        // The nonces are weighted by the powers of our binding factor, as
+        // their commitments in the group commitment.
+        let mut z = lambda * self.key * challenge;
+        let mut weight = Scalar::one();
+        for nonce in my_commitment_share.nonces().iter() {
+            z += *nonce * weight;
+            weight *= my_binding_factor;
+        }
 
         // [DIFFERENT_TO_PAPER] We need to instead pass in the commitment
         // share list and zero-out the used commitment share, which means the
@@ -440,62 +1700,603 @@ impl SecretKey {
         // commitment shares go out of sync.
 
         // Zero out our secrets from memory to prevent nonce reuse.
-        my_secret_commitment_share_list.drop_share(my_commitment_share);
+        my_secret_commitment_share_list.drop_share_at(my_commitment_share_index);
 
         Ok(PartialThresholdSignature {
             index: self.index,
             z,
         })
     }
-}
 
-/// A signature aggregator, in any of various states.
-pub trait Aggregator {}
+    /// Compute an individual signer's [`PartialThresholdSignature`] for a
+    /// [`SigningSession`] obtained from the [`SignatureAggregator`].
+    ///
+    /// The signer should inspect the session's context, message and metadata
+    /// before deciding whether or not to sign.  The other inputs are as for
+    /// [`SecretKey::sign`].
+    pub fn sign_session(
+        &self,
+        session: &SigningSession,
+        group_key: &GroupKey,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList,
+        my_commitment_share_index: usize,
+    ) -> Result<PartialThresholdSignature, SignatureError> {
+        self.sign_internal::<Ristretto25519>(
+            &session.message_hash,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+            &session.signers,
+            session.parameters.as_ref(),
+        )
+    }
 
-/// The internal state of a signature aggregator.
-#[allow(dead_code)]
-#[derive(Debug)]
-pub(crate) struct AggregatorState {
-    /// The protocol instance parameters.
-    pub(crate) parameters: Parameters,
-    /// The set of signing participants for this round.
-    pub(crate) signers: Vec<Signer>,
-    /// The signer's public keys for verifying their [`PartialThresholdSignature`].
-    pub(crate) public_keys: IndividualPublicKeys,
-    /// The partial signatures from individual participants which have been
-    /// collected thus far.
-    pub(crate) partial_signatures: PartialThresholdSignatures,
-    /// The group public key for all the participants.
-    pub(crate) group_key: GroupKey,
-}
+    /// Compute an individual signer's [`PartialThresholdSignature`] for a
+    /// [`SigningSession`] as with [`SecretKey::sign_session`], for a signer
+    /// configured to only sign the sessions authorized by the `authorizer`.
+    ///
+    /// # Returns
+    ///
+    /// An `UnauthorizedSession` error if the session does not carry a valid
+    /// authorization from the `authorizer`, in which case no commitment share
+    /// was consumed.
+    pub fn sign_authorized_session(
+        &self,
+        session: &SigningSession,
+        authorizer: &AuthorizerPublicKey,
+        group_key: &GroupKey,
+        my_secret_commitment_share_list: &mut SecretCommitmentShareList,
+        my_commitment_share_index: usize,
+    ) -> Result<PartialThresholdSignature, SignatureError> {
+        authorizer.verify(session)?;
 
-/// A signature aggregator is an untrusted party who coalesces all of the
-/// participating signers' published commitment shares and their
-/// [`PartialThresholdSignature`] and creates the final [`ThresholdSignature`].
-/// The signature aggregator may even be one of the \\(t\\) participants in this
-/// signing operation.
-#[derive(Debug)]
-pub struct SignatureAggregator<A: Aggregator> {
-    /// The aggregator's actual state, shared across types.
-    pub(crate) state: Box<AggregatorState>,
-    /// The aggregator's additional state.
-    pub(crate) aggregator: A,
+        self.sign_session(
+            session,
+            group_key,
+            my_secret_commitment_share_list,
+            my_commitment_share_index,
+        )
+    }
 }
 
-/// The initial state for a [`SignatureAggregator`], which may include invalid
-/// or non-sensical data.
-#[derive(Debug)]
-pub struct Initial<'sa> {
-    /// An optional context string for computing the message hash.
-    pub(crate) context: &'sa [u8],
+/// Everything a signer needs to know about a signing session in order to
+/// decide whether or not to take part in it, as exposed by
+/// [`SignatureAggregator::session`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SigningSession {
+    /// The context string of the message.
+    pub(crate) context: Vec<u8>,
     /// The message to be signed.
-    pub(crate) message: &'sa [u8],
+    pub(crate) message: Vec<u8>,
+    /// The metadata bound into the signature, sorted by key.
+    pub(crate) metadata: Vec<(String, Vec<u8>)>,
+    /// The participating signers, sorted by index.
+    pub(crate) signers: Vec<Signer>,
+    /// The hashed context, message and metadata which will be signed.
+    pub(crate) message_hash: [u8; 32],
+    /// The parameters bound into the challenge, if any.
+    pub(crate) parameters: Option<Parameters>,
+    /// The identity of the coordinator bound into the challenge, if any.
+    pub(crate) coordinator_id: Option<Vec<u8>>,
+    /// The nonce distinguishing this session from others over the same inputs.
+    pub(crate) nonce: [u8; 32],
+    /// The authorizer's signature over this session, if any.
+    pub(crate) authorized_by: Option<SessionAuthorization>,
 }
 
-impl Aggregator for Initial<'_> {}
+impl SigningSession {
+    /// The context string of the message to be signed.
+    pub fn context(&self) -> &[u8] {
+        &self.context
+    }
 
-/// The finalized state for a [`SignatureAggregator`], which has thoroughly
-/// validated its data.
+    /// The message to be signed.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// All key-value metadata bound into the signature, sorted by key.
+    pub fn metadata(&self) -> &[(String, Vec<u8>)] {
+        &self.metadata
+    }
+
+    /// The metadata value for the given `key`, if any.
+    pub fn get_metadata(&self, key: &str) -> Option<&[u8]> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    /// The position of the signature in the sequence of signatures of its
+    /// group, if bound with [`SignatureAggregator::bind_sequence`].
+    pub fn sequence(&self) -> Option<SequenceBinding> {
+        SequenceBinding::from_metadata(&self.metadata)
+    }
+
+    /// The participating signers.
+    pub fn signers(&self) -> &[Signer] {
+        &self.signers
+    }
+
+    /// The hash of the context, message and metadata which will be signed.
+    pub fn message_hash(&self) -> &[u8; 32] {
+        &self.message_hash
+    }
+
+    /// The parameters bound into the signature, if any.
+    pub fn parameters(&self) -> Option<&Parameters> {
+        self.parameters.as_ref()
+    }
+
+    /// The identity of the coordinator bound into the signature, if any.
+    pub fn coordinator_id(&self) -> Option<&[u8]> {
+        self.coordinator_id.as_deref()
+    }
+
+    /// The nonce distinguishing this session from others over the same inputs.
+    pub fn nonce(&self) -> &[u8; 32] {
+        &self.nonce
+    }
+
+    /// The authorizer's signature over this session, if any.
+    pub fn authorized_by(&self) -> Option<&SessionAuthorization> {
+        self.authorized_by.as_ref()
+    }
+
+    /// Attach the authorizer's signature over this session, obtained with
+    /// [`AuthorizerKey::authorize`].
+    pub fn set_authorization(&mut self, authorization: SessionAuthorization) {
+        self.authorized_by = Some(authorization);
+    }
+
+    /// An identifier of this session, committing to its nonce, to the hash of
+    /// its context, message and metadata, and to the signers' commitments.
+    ///
+    /// Signing the same message several times is legitimate, so caches and
+    /// nonce managers should key their state by this identifier rather than
+    /// by the message hash alone.
+    pub fn id(&self) -> [u8; 32] {
+        let mut h = Sha256::new();
+
+        h.update(b"ICE-FROST-SESSION");
+        h.update(self.nonce);
+        h.update(self.message_hash);
+        for signer in self.signers.iter() {
+            let (hiding, binding) = signer.compressed_commitment_share();
+            h.update(signer.participant_index.to_le_bytes());
+            h.update(hiding.as_bytes());
+            h.update(binding.as_bytes());
+        }
+
+        let mut output = [0u8; 32];
+
+        output.copy_from_slice(h.finalize().as_slice());
+        output
+    }
+
+    /// Create a fresh session over the same context, message, metadata,
+    /// parameter and coordinator binding, with a new random nonce, e.g. to produce a second,
+    /// independent signature over an already signed message.
+    ///
+    /// The renewed session has no signers nor authorization: it should be
+    /// handed to [`SignatureAggregator::from_session`], which collects fresh
+    /// commitment shares.
+    pub fn renew(&self, mut rng: impl RngCore + CryptoRng) -> SigningSession {
+        let mut nonce = [0u8; 32];
+        rng.fill_bytes(&mut nonce);
+
+        SigningSession {
+            context: self.context.clone(),
+            message: self.message.clone(),
+            metadata: self.metadata.clone(),
+            signers: Vec::new(),
+            message_hash: self.message_hash,
+            parameters: self.parameters,
+            coordinator_id: self.coordinator_id.clone(),
+            nonce,
+            authorized_by: None,
+        }
+    }
+}
+
+/// The secret key of an authorizer, whose signature over a
+/// [`SigningSession`] signers may require before taking part in it.
+#[derive(Debug, Zeroize)]
+#[zeroize(drop)]
+pub struct AuthorizerKey(pub(crate) Scalar);
+
+impl AuthorizerKey {
+    /// Generate a new authorizer key.
+    pub fn new(mut rng: impl RngCore + CryptoRng) -> AuthorizerKey {
+        AuthorizerKey(Scalar::random(&mut rng))
+    }
+
+    /// The public key signers should be configured with.
+    pub fn public_key(&self) -> AuthorizerPublicKey {
+        AuthorizerPublicKey(&RISTRETTO_BASEPOINT_TABLE * &self.0)
+    }
+
+    /// Sign a `session`, once all of its signers have been included.
+    ///
+    /// The resulting [`SessionAuthorization`] should be attached with
+    /// [`SigningSession::set_authorization`].
+    pub fn authorize(
+        &self,
+        session: &SigningSession,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> SessionAuthorization {
+        let k = Scalar::random(&mut rng);
+        let R = &RISTRETTO_BASEPOINT_TABLE * &k;
+        let c = authorization_challenge(&self.public_key(), &R, session);
+
+        SessionAuthorization {
+            R,
+            z: k + c * self.0,
+        }
+    }
+}
+
+/// The public key of an authorizer of [`SigningSession`]s.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuthorizerPublicKey(pub(crate) RistrettoPoint);
+
+impl AuthorizerPublicKey {
+    /// Check that a `session` carries a valid authorization under this key.
+    ///
+    /// # Returns
+    ///
+    /// An `UnauthorizedSession` error if the authorization is missing or
+    /// invalid.
+    pub fn verify(&self, session: &SigningSession) -> Result<(), SignatureError> {
+        let authorization = session
+            .authorized_by
+            .as_ref()
+            .ok_or(SignatureError::UnauthorizedSession)?;
+        let c = authorization_challenge(self, &authorization.R, session);
+
+        match &RISTRETTO_BASEPOINT_TABLE * &authorization.z == authorization.R + c * self.0 {
+            true => Ok(()),
+            false => Err(SignatureError::UnauthorizedSession),
+        }
+    }
+
+    /// Serialise this public key to an array of bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        encode_point(&self.0)
+    }
+
+    /// Deserialise this array of bytes to an `AuthorizerPublicKey`
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<AuthorizerPublicKey, Error> {
+        let point = decode_point_checked(bytes)?;
+
+        Ok(AuthorizerPublicKey(point))
+    }
+}
+
+/// An authorizer's Schnorr signature over a [`SigningSession`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SessionAuthorization {
+    pub(crate) R: RistrettoPoint,
+    pub(crate) z: Scalar,
+}
+
+impl SessionAuthorization {
+    /// Serialise this authorization to an array of bytes
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut res = [0u8; 64];
+        res[..32].copy_from_slice(&encode_point(&self.R));
+        res[32..].copy_from_slice(&encode_scalar(&self.z));
+
+        res
+    }
+
+    /// Deserialise this array of bytes to a `SessionAuthorization`
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<SessionAuthorization, Error> {
+        let R = decode_point_checked(
+            &bytes[..32]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+        let z = decode_scalar_canonical(
+            &bytes[32..]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        Ok(SessionAuthorization { R, z })
+    }
+}
+
+/// The challenge of an authorization, binding the session identifier, which
+/// commits to its nonce, message hash and signers, and its parameter binding.
+fn authorization_challenge(
+    public_key: &AuthorizerPublicKey,
+    R: &RistrettoPoint,
+    session: &SigningSession,
+) -> Scalar {
+    let mut h = RistrettoHash::new();
+
+    h.update(b"ICE-FROST-SESSION-AUTHORIZATION");
+    h.update(public_key.0.compress().as_bytes());
+    h.update(R.compress().as_bytes());
+    h.update(session.id());
+    match session.parameters {
+        Some(parameters) => {
+            h.update([1u8]);
+            h.update(parameters.to_bytes());
+        }
+        None => h.update([0u8]),
+    }
+
+    Ristretto25519::hash_to_scalar(h)
+}
+
+/// A signature aggregator, in any of various states.
+pub trait Aggregator {}
+
+/// The internal state of a signature aggregator.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub(crate) struct AggregatorState {
+    /// The protocol instance parameters.
+    pub(crate) parameters: Parameters,
+    /// The set of signing participants for this round.
+    pub(crate) signers: Vec<Signer>,
+    /// The signer's public keys for verifying their [`PartialThresholdSignature`].
+    pub(crate) public_keys: IndividualPublicKeys,
+    /// The partial signatures from individual participants which have been
+    /// collected thus far.
+    pub(crate) partial_signatures: PartialThresholdSignatures,
+    /// The sorted indices held by each weighted signer, whose combined
+    /// partial signature is submitted under the first of them.
+    pub(crate) weighted_signers: Vec<Vec<u32>>,
+    /// The group public key for all the participants.
+    pub(crate) group_key: GroupKey,
+    /// The maximum number of partial signatures verified per signer and
+    /// source, if any.
+    pub(crate) partial_budget: Option<u32>,
+    /// Counters of the partial signatures submitted thus far, by signer and
+    /// source tag.
+    pub(crate) partial_counters: BTreeMap<(u32, String), PartialSignatureCounters>,
+    /// The hash functions used for signing.
+    pub(crate) hash_functions: HashFunctions,
+    /// Whether the parameters are bound into the challenge.
+    pub(crate) bind_parameters: bool,
+    /// The long-term Diffie-Hellman keys which must have signed the
+    /// commitment shares of the signers, if required.
+    pub(crate) commitment_roster: Option<Vec<(u32, DHPublicKey)>>,
+    /// The sink of the metrics of this session.
+    pub(crate) metrics: MetricsHandle,
+}
+
+impl AggregatorState {
+    /// The parameters bound into the challenge, if any.
+    fn bound_parameters(&self) -> Option<&Parameters> {
+        match self.bind_parameters {
+            true => Some(&self.parameters),
+            false => None,
+        }
+    }
+
+    /// The indices whose partial signatures are combined into the one
+    /// submitted under `index`: those of the weighted signer it is the first
+    /// index of, or `index` alone.
+    fn combined_indices(&self, index: u32) -> Vec<u32> {
+        self.weighted_signers
+            .iter()
+            .find(|indices| indices[0] == index)
+            .cloned()
+            .unwrap_or_else(|| Vec::from([index]))
+    }
+
+    /// Whether `index` is held by a weighted signer, but is not the one its
+    /// partial signature is submitted under.
+    fn is_combined_index(&self, index: u32) -> bool {
+        self.weighted_signers
+            .iter()
+            .any(|indices| indices[1..].contains(&index))
+    }
+
+    /// Check the partial signature `z` submitted under `index` against the
+    /// public keys and the commitments `Rs` of the indices it combines,
+    /// among `all_participant_indices`, for the challenge `c`.
+    fn check_partial_signature(
+        &self,
+        index: u32,
+        z: &Scalar,
+        Rs: &SignerRs,
+        c: &Scalar,
+        all_participant_indices: &[u32],
+    ) -> bool {
+        let mut expected = RistrettoPoint::identity();
+        for index in self.combined_indices(index) {
+            let lambda = match calculate_lagrange_coefficients(&index, all_participant_indices) {
+                Ok(lambda) => lambda,
+                Err(_) => return false,
+            };
+            match (self.public_keys.get(&index), Rs.get(&index)) {
+                (Some(Y_i), Some(R_i)) => expected += R_i + (Y_i * (c * lambda)),
+                _ => return false,
+            }
+        }
+
+        &RISTRETTO_BASEPOINT_TABLE * z == expected
+    }
+
+    /// Combine the partial signatures of the finalized `signers` over the
+    /// `message_hash`, and verify the result.
+    ///
+    /// # Returns
+    ///
+    /// The [`ThresholdSignature`], or the signers whose partial signatures
+    /// are incorrect.
+    fn combine_partial_signatures(
+        &self,
+        signers: &[Signer],
+        message_hash: &[u8; 32],
+    ) -> Result<ThresholdSignature, AggregationReport> {
+        let mut misbehaving_participants: BTreeMap<u32, &'static str> = BTreeMap::new();
+
+        let hash_functions = self.hash_functions;
+        let (_, Rs) = (hash_functions.binding_factors)(message_hash, &self.group_key, signers);
+        let R: RistrettoPoint = Rs.values().sum();
+        let c =
+            (hash_functions.challenge)(message_hash, &self.group_key, &R, self.bound_parameters());
+        let all_participant_indices: Vec<u32> =
+            signers.iter().map(|x| x.participant_index).collect();
+        let mut z = Scalar::zero();
+
+        // We first combine all partial signatures together, to remove the need for individual
+        // signature verification in case the final group signature is valid.
+        for signer in signers.iter() {
+            // This unwrap() cannot fail, because SignatureAggregator<Initial>.finalize()
+            // checks that we have partial signature for every expected signer.
+            let partial_sig = self
+                .partial_signatures
+                .get(&signer.participant_index)
+                .unwrap();
+
+            z += partial_sig;
+        }
+
+        let signature = ThresholdSignature { z, R };
+
+        // Verify the obtained signature, listing malicious participants
+        // if the verification failed.
+        match signature.verify_challenge(&self.group_key, &c) {
+            Ok(()) => Ok(signature),
+            Err(_) => {
+                for signer in signers.iter() {
+                    // The partial signature of a weighted signer is checked
+                    // under its first index, for all of its indices.
+                    if self.is_combined_index(signer.participant_index) {
+                        continue;
+                    }
+
+                    // This cannot fail, and has already been performed previously.
+                    let partial_sig = self
+                        .partial_signatures
+                        .get(&signer.participant_index)
+                        .unwrap();
+
+                    // The Lagrange coefficients, public keys and commitments
+                    // are all available, because of the checks in finalize().
+                    if !self.check_partial_signature(
+                        signer.participant_index,
+                        partial_sig,
+                        &Rs,
+                        &c,
+                        &all_participant_indices,
+                    ) {
+                        // XXX We don't really need the error string anymore, since there's only one failure mode.
+                        misbehaving_participants
+                            .insert(signer.participant_index, "Incorrect partial signature");
+                    }
+                }
+                Err(misbehaving_participants)
+            }
+        }
+    }
+}
+
+/// Counters of the [`PartialThresholdSignature`]s submitted by a single signer,
+/// from a single source or from all of them, to a [`SignatureAggregator`],
+/// suitable for exporting as metrics.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PartialSignatureCounters {
+    /// The number of partial signatures received from this signer.
+    pub received: u32,
+    /// The number of partial signatures from this signer which were verified.
+    pub verified: u32,
+    /// The number of partial signatures from this signer which failed verification.
+    pub invalid: u32,
+    /// The number of partial signatures from this signer which were rejected,
+    /// without verification, because its budget was exhausted.
+    pub throttled: u32,
+    /// The number of partial signatures from this signer which were flagged
+    /// as degenerate, i.e. with a zero response.
+    pub suspicious: u32,
+}
+
+impl PartialSignatureCounters {
+    fn add(self, other: &PartialSignatureCounters) -> PartialSignatureCounters {
+        PartialSignatureCounters {
+            received: self.received + other.received,
+            verified: self.verified + other.verified,
+            invalid: self.invalid + other.invalid,
+            throttled: self.throttled + other.throttled,
+            suspicious: self.suspicious + other.suspicious,
+        }
+    }
+}
+
+/// How a [`SignatureAggregator`] selects the signers of a session when more
+/// than \\(t\\) of them could take part.
+///
+/// As the binding factors commit to the whole set of signers, partial
+/// signatures cannot be recombined across sets, and the set must be settled
+/// before any signer signs.  Applying the same policy, redundant aggregators
+/// which included the same signers in a different order settle on the same
+/// set, and thus emit byte-identical signatures from the same partials.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubsetPolicy {
+    /// Select the \\(t\\) signers with the smallest indices.
+    SmallestIndices,
+    /// Select the first \\(t\\) signers in this priority order of
+    /// participant indices, followed by any unlisted signer by increasing
+    /// index.
+    Priority(Vec<u32>),
+}
+
+/// The participant indices of the misbehaving signers preventing an
+/// aggregation, with a description of their misbehaviour, where index `0`
+/// stands for the aggregator.
+pub type AggregationReport = BTreeMap<u32, &'static str>;
+
+/// A signature aggregator is an untrusted party who coalesces all of the
+/// participating signers' published commitment shares and their
+/// [`PartialThresholdSignature`] and creates the final [`ThresholdSignature`].
+/// The signature aggregator may even be one of the \\(t\\) participants in this
+/// signing operation.
+#[derive(Debug)]
+pub struct SignatureAggregator<A: Aggregator> {
+    /// The aggregator's actual state, shared across types.
+    pub(crate) state: Box<AggregatorState>,
+    /// The aggregator's additional state.
+    pub(crate) aggregator: A,
+}
+
+/// The initial state for a [`SignatureAggregator`], which may include invalid
+/// or non-sensical data.
+#[derive(Debug)]
+pub struct Initial<'sa> {
+    /// An optional context string for computing the message hash.
+    pub(crate) context: Cow<'sa, [u8]>,
+    /// The message to be signed, only kept to be handed to the signers, and
+    /// empty if it was hashed by the caller.
+    pub(crate) message: Cow<'sa, [u8]>,
+    /// The hash of the context and the message, computed once.
+    pub(crate) message_hash: [u8; 32],
+    /// Whether the message hash was given by the caller, rather than computed
+    /// from the context and the message.
+    pub(crate) prehashed: bool,
+    /// The key-value metadata bound into the signature, sorted by key.
+    pub(crate) metadata: Vec<(String, Vec<u8>)>,
+    /// The nonce distinguishing this session from others over the same inputs.
+    pub(crate) session_nonce: [u8; 32],
+    /// The commitment to a message which is yet to be revealed, if any.
+    pub(crate) message_commitment: Option<[u8; 32]>,
+    /// Whether the committed message was revealed.
+    pub(crate) message_revealed: bool,
+    /// The identity of the coordinator bound into the challenge, if any.
+    pub(crate) coordinator_id: Option<Vec<u8>>,
+}
+
+impl Aggregator for Initial<'_> {}
+
+/// The finalized state for a [`SignatureAggregator`], which has thoroughly
+/// validated its data.
 ///
 /// # Guarantees
 ///
@@ -550,835 +2351,1796 @@ impl SignatureAggregator<Initial<'_>> {
             signers,
             public_keys,
             partial_signatures,
+            weighted_signers: Vec::new(),
             group_key,
+            partial_budget: None,
+            partial_counters: BTreeMap::new(),
+            hash_functions: HashFunctions::new::<Ristretto25519>(),
+            bind_parameters: false,
+            commitment_roster: None,
+            metrics: MetricsHandle::default(),
         };
 
         SignatureAggregator {
             state: Box::new(state),
-            aggregator: Initial { context, message },
+            aggregator: Initial {
+                context: Cow::Borrowed(context),
+                message: Cow::Borrowed(message),
+                message_hash: compute_message_hash(context, message),
+                prehashed: false,
+                metadata: Vec::new(),
+                session_nonce: [0u8; 32],
+                message_commitment: None,
+                message_revealed: false,
+                coordinator_id: None,
+            },
         }
     }
 
-    /// Include a signer in the protocol.
-    ///
-    /// # Warning
+    /// Construct a new signature aggregator as with
+    /// [`SignatureAggregator::new`], over the context string and message of
+    /// a `signable` value.
     ///
-    /// If this method is called for a specific participant, then that
-    /// participant MUST provide a partial signature to give to
-    /// [`SignatureAggregator.include_partial_signature`], otherwise the signing
-    /// procedure will fail.
+    /// The signature can then be verified with
+    /// [`ThresholdSignature::verify_signable`] given the same value.
     ///
-    /// # Panics
+    /// # Returns
     ///
-    /// If the `signer.participant_index` doesn't match the `public_key.index`.
-    pub fn include_signer(
-        &mut self,
-        participant_index: u32,
-        published_commitment_share: (RistrettoPoint, RistrettoPoint),
-        public_key: IndividualPublicKey,
-    ) {
-        assert_eq!(participant_index, public_key.index,
-                   "Tried to add signer with participant index {}, but public key is for participant with index {}",
-                   participant_index, public_key.index);
-
-        self.state.signers.push(Signer {
-            participant_index,
-            published_commitment_share,
-        });
-        self.state
-            .public_keys
-            .insert(&public_key.index, public_key.share);
+    /// A new [`SignatureAggregator`], which owns the bytes to be signed, or
+    /// a [`SignatureError::MessageTooLarge`] if the message is longer than
+    /// [`MAX_OWNED_MESSAGE_LENGTH`].
+    pub fn from_signable<'sa>(
+        parameters: Parameters,
+        group_key: GroupKey,
+        signable: &impl Signable,
+    ) -> Result<SignatureAggregator<Initial<'sa>>, SignatureError> {
+        SignatureAggregator::from_signable_with_limit(
+            parameters,
+            group_key,
+            signable,
+            MAX_OWNED_MESSAGE_LENGTH,
+        )
     }
 
-    /// Get the list of partipating signers.
+    /// Construct a new signature aggregator as with
+    /// [`SignatureAggregator::from_signable`], with messages of up to
+    /// `max_message_length` bytes rather than [`MAX_OWNED_MESSAGE_LENGTH`].
     ///
     /// # Returns
     ///
-    /// A `&Vec<Signer>` of the participating signers in this round.
-    pub fn get_signers(&'_ mut self) -> &'_ Vec<Signer> {
-        // .sort() must be called before .dedup() because the latter only
-        // removes consecutive repeated elements.
-        self.state.signers.sort();
-        self.state.signers.dedup();
+    /// A new [`SignatureAggregator`], or a [`SignatureError::MessageTooLarge`]
+    /// if the message is longer than `max_message_length`.
+    pub fn from_signable_with_limit<'sa>(
+        parameters: Parameters,
+        group_key: GroupKey,
+        signable: &impl Signable,
+        max_message_length: usize,
+    ) -> Result<SignatureAggregator<Initial<'sa>>, SignatureError> {
+        let (context, message) = signable.to_signing_bytes();
+        if message.len() > max_message_length {
+            return Err(SignatureError::MessageTooLarge {
+                length: message.len(),
+                max: max_message_length,
+            });
+        }
+        let mut aggregator = SignatureAggregator::new(parameters, group_key, &[], &[]);
+        aggregator.aggregator.context = Cow::Owned(context);
+        aggregator.aggregator.message = Cow::Owned(message);
+        aggregator.rehash_message();
 
-        &self.state.signers
+        Ok(aggregator)
     }
 
-    /// Helper function to get the remaining signers who were expected to sign,
-    /// but have not yet contributed their [`PartialThresholdSignature`]s.
+    /// Construct a new signature aggregator as with
+    /// [`SignatureAggregator::new`], over the `message_hash` of the `context`
+    /// and a message hashed by the caller, e.g. by streaming a large message
+    /// through a [`Sha256`] hasher rather than holding it in memory.
     ///
-    /// This can be used by an honest aggregator who wishes to ensure that the
-    /// aggregation procedure is ready to be run, or who wishes to be able to
-    /// remind/poll individual signers for their [`PartialThresholdSignature`]
-    /// contribution.
+    /// The `message_hash` must be computed as by [`compute_message_hash`], or
+    /// by the message hash of the [`Ciphersuite`] set with
+    /// [`SignatureAggregator::set_ciphersuite`].  Only the hash is kept, and
+    /// the signers get an empty message in the [`SigningSession`].
     ///
     /// # Returns
     ///
-    /// A sorted `Vec` of unique [`Signer`]s who have yet to contribute their
-    /// partial signatures.
-    pub fn get_remaining_signers(&self) -> Vec<Signer> {
-        let mut remaining_signers: Vec<Signer> = Vec::new();
+    /// A new [`SignatureAggregator`].
+    pub fn new_prehashed(
+        parameters: Parameters,
+        group_key: GroupKey,
+        context: &[u8],
+        message_hash: [u8; 32],
+    ) -> SignatureAggregator<Initial<'_>> {
+        let mut aggregator = SignatureAggregator::new(parameters, group_key, context, &[]);
+        aggregator.aggregator.message_hash = message_hash;
+        aggregator.aggregator.prehashed = true;
+
+        aggregator
+    }
 
-        for signer in self.state.signers.iter() {
-            if self
-                .state
-                .partial_signatures
-                .get(&signer.participant_index)
-                .is_none()
-            {
-                remaining_signers.push(*signer);
-            }
-        }
-        remaining_signers.sort();
-        remaining_signers.dedup();
-        remaining_signers
+    /// Construct a new signature aggregator as with
+    /// [`SignatureAggregator::new`], over a `message_commitment` rather than
+    /// the message itself.
+    ///
+    /// The `message_commitment` is the message hash, as computed by
+    /// [`compute_message_hash`] from the `context` and the message, which the
+    /// signers sign without learning the message.  The message must be
+    /// revealed with [`SignatureAggregator::reveal_message`] before the
+    /// aggregator can be finalized.
+    ///
+    /// # Returns
+    ///
+    /// A new [`SignatureAggregator`].
+    pub fn new_committed_message(
+        parameters: Parameters,
+        group_key: GroupKey,
+        context: &[u8],
+        message_commitment: [u8; 32],
+    ) -> SignatureAggregator<Initial<'_>> {
+        let mut aggregator =
+            SignatureAggregator::new_prehashed(parameters, group_key, context, message_commitment);
+        aggregator.aggregator.message_commitment = Some(message_commitment);
+
+        aggregator
     }
 
-    /// Add a [`PartialThresholdSignature`] to be included in the aggregation.
-    pub fn include_partial_signature(&mut self, partial_signature: PartialThresholdSignature) {
-        self.state
-            .partial_signatures
-            .insert(&partial_signature.index, partial_signature.z);
+    /// Construct a new signature aggregator as with
+    /// [`SignatureAggregator::new`], additionally binding some key-value
+    /// `metadata` into the signature.
+    ///
+    /// The metadata is exposed to the signers in the [`SigningSession`] so
+    /// that they can enforce their own policy on it, and the resulting
+    /// signature only verifies with [`ThresholdSignature::verify_with_metadata`]
+    /// given the same metadata.
+    ///
+    /// # Returns
+    ///
+    /// A new [`SignatureAggregator`], or a
+    /// [`SignatureError::DuplicateMetadataKey`] if a key appears more than once.
+    pub fn new_with_metadata<'sa>(
+        parameters: Parameters,
+        group_key: GroupKey,
+        context: &'sa [u8],
+        message: &'sa [u8],
+        metadata: &[(&str, &[u8])],
+    ) -> Result<SignatureAggregator<Initial<'sa>>, SignatureError> {
+        let metadata = canonicalise_metadata(metadata)?;
+        let mut aggregator = SignatureAggregator::new(parameters, group_key, context, message);
+        aggregator.aggregator.metadata = metadata;
+
+        Ok(aggregator)
     }
 
-    /// Ensure that this signature aggregator is in a proper state to run the aggregation protocol.
+    /// Use the hashes of the given [`Ciphersuite`] rather than those of
+    /// [`Ristretto25519`].
+    ///
+    /// The signers must then sign with [`SecretKey::sign_with`] for the same
+    /// ciphersuite, and the signature be verified with
+    /// [`ThresholdSignature::verify_with`].
     ///
     /// # Returns
     ///
-    /// A Result whose Ok() value is a finalized aggregator, otherwise a
-    /// `BTreeMap<u32, &'static str>` containing the participant indices of the misbehaving
-    /// signers and a description of their misbehaviour.
+    /// An `UnsupportedNonceCount` error if the ciphersuite asks for another
+    /// number of nonces than [`CommitmentShare::NONCE_COUNT`], in which case
+    /// the ciphersuite of this aggregator is left unchanged.
+    pub fn set_ciphersuite<C: RistrettoCiphersuite>(&mut self) -> Result<(), SignatureError> {
+        if C::NONCE_COUNT != CommitmentShare::NONCE_COUNT {
+            return Err(SignatureError::UnsupportedNonceCount(C::NONCE_COUNT));
+        }
+        self.state.hash_functions = HashFunctions::new::<C>();
+        self.rehash_message();
+
+        Ok(())
+    }
+
+    /// Recompute the hash of the context and the message, unless it was
+    /// given by the caller.
+    fn rehash_message(&mut self) {
+        if !self.aggregator.prehashed {
+            self.aggregator.message_hash = (self.state.hash_functions.message_hash)(
+                &self.aggregator.context,
+                &self.aggregator.message,
+            );
+        }
+    }
+
+    /// Bind the [`Parameters`] of this aggregator into the challenge, so that
+    /// the signature only verifies with
+    /// [`ThresholdSignature::verify_with_parameters`] under these exact
+    /// parameters.
     ///
-    /// If the `BTreeMap` contains a key for `0`, this indicates that
-    /// the aggregator did not have \(( t' \)) partial signers
-    /// s.t. \(( t \le t' \le n \)).
-    pub fn finalize(
-        mut self,
-    ) -> Result<SignatureAggregator<Finalized>, BTreeMap<u32, &'static str>> {
-        let mut misbehaving_participants: BTreeMap<u32, &'static str> = BTreeMap::new();
-        let remaining_signers = self.get_remaining_signers();
+    /// The signers must sign with [`SecretKey::sign_session`].
+    pub fn bind_parameters(&mut self) {
+        self.state.bind_parameters = true;
+    }
 
-        // [DIFFERENT_TO_PAPER] We're reporting missing partial signatures which
-        // could possibly be the fault of the aggregator, but here we explicitly
-        // make it the aggregator's fault and problem.
-        if !remaining_signers.is_empty() {
-            // We call the aggregator "participant 0" for the sake of error messages.
-            misbehaving_participants.insert(0, "Missing remaining signer(s)");
+    /// Require the commitment share of every signer to be signed by its
+    /// long-term Diffie-Hellman key in the `roster`, with
+    /// [`sign_commitment_share`](crate::attestation::sign_commitment_share),
+    /// so that a coordinator relaying the commitment shares cannot choose or
+    /// substitute them.
+    ///
+    /// Signers must then be included with
+    /// [`SignatureAggregator::include_signed_signer`], and
+    /// [`SignatureAggregator::include_signer`] rejects all of them with a
+    /// [`SignatureError::UnsignedCommitment`].
+    pub fn require_signed_commitments(&mut self, roster: &[(u32, DHPublicKey)]) {
+        self.state.commitment_roster = Some(roster.to_vec());
+    }
 
-            for signer in remaining_signers.iter() {
-                misbehaving_participants
-                    .insert(signer.participant_index, "Missing partial signature");
+    /// Bind the identity of the coordinator orchestrating this session into
+    /// the challenge, with [`bind_coordinator_id`], so that the signature
+    /// reveals which coordinator orchestrated it, and only verifies with
+    /// [`ThresholdSignature::verify_with_coordinator`] for this exact
+    /// `coordinator_id`.
+    ///
+    /// The signers see the identity in the [`SigningSession`].
+    pub fn bind_coordinator(&mut self, coordinator_id: &[u8]) {
+        self.aggregator.coordinator_id = Some(coordinator_id.to_vec());
+    }
+
+    /// Bind the position of the signature in the sequence of signatures of
+    /// its group into the challenge, as metadata, so that successive
+    /// signatures form a hash chain checked by [`verify_sequence`].
+    ///
+    /// The `binding` should be obtained from a
+    /// [`SequenceTracker`](crate::store::SequenceTracker), which the
+    /// signature must be recorded to once aggregated.  The signers see the
+    /// binding in the [`SigningSession`].
+    ///
+    /// # Returns
+    ///
+    /// A [`SignatureError::DuplicateMetadataKey`] if the metadata of this
+    /// aggregator already holds one of the keys of the binding.
+    pub fn bind_sequence(&mut self, binding: SequenceBinding) -> Result<(), SignatureError> {
+        let metadata = &mut self.aggregator.metadata;
+        for key in [SequenceBinding::SEQ_KEY, SequenceBinding::PREV_KEY] {
+            if metadata.iter().any(|(k, _)| k == key) {
+                return Err(SignatureError::DuplicateMetadataKey(key.to_string()));
             }
         }
 
-        // Ensure that our new state is ordered and deduplicated.
-        self.state.signers = self.get_signers().clone();
+        metadata.push((
+            SequenceBinding::SEQ_KEY.to_string(),
+            binding.seq.to_le_bytes().to_vec(),
+        ));
+        metadata.push((SequenceBinding::PREV_KEY.to_string(), binding.prev.to_vec()));
+        metadata.sort_by(|a, b| a.0.cmp(&b.0));
 
-        for signer in self.state.signers.iter() {
-            if self
-                .state
-                .public_keys
-                .get(&signer.participant_index)
-                .is_none()
-            {
-                // XXX These should be Vec<&'static str> for full error reporting
-                misbehaving_participants.insert(signer.participant_index, "Missing public key");
-            }
+        Ok(())
+    }
+
+    /// The hash of the context, message and metadata to be signed.
+    fn message_hash(&self) -> [u8; 32] {
+        let message_hash =
+            bind_canonical_metadata(&self.aggregator.message_hash, &self.aggregator.metadata);
+
+        match self.aggregator.coordinator_id.as_ref() {
+            Some(coordinator_id) => bind_coordinator_id(&message_hash, coordinator_id),
+            None => message_hash,
         }
+    }
 
-        if !misbehaving_participants.is_empty() {
-            return Err(misbehaving_participants);
+    /// Get the [`SigningSession`] to be handed to the participating signers.
+    ///
+    /// This should be called once all signers have been included.
+    pub fn session(&mut self) -> SigningSession {
+        SigningSession {
+            context: self.aggregator.context.to_vec(),
+            message: self.aggregator.message.to_vec(),
+            metadata: self.aggregator.metadata.clone(),
+            signers: self.get_signers().clone(),
+            message_hash: self.message_hash(),
+            parameters: self.state.bound_parameters().copied(),
+            coordinator_id: self.aggregator.coordinator_id.clone(),
+            nonce: self.aggregator.session_nonce,
+            authorized_by: None,
         }
+    }
 
-        let message_hash = compute_message_hash(self.aggregator.context, self.aggregator.message);
+    /// Construct a new signature aggregator for a `session`, typically a
+    /// [`SigningSession::renew`]ed one, over its context, message, metadata
+    /// and parameter and coordinator binding.
+    ///
+    /// The signers of the `session` are not included: they must publish
+    /// fresh commitment shares for this aggregator.
+    pub fn from_session(
+        parameters: Parameters,
+        group_key: GroupKey,
+        session: &SigningSession,
+    ) -> SignatureAggregator<Initial<'_>> {
+        let mut aggregator =
+            SignatureAggregator::new(parameters, group_key, &session.context, &session.message);
+        aggregator.aggregator.metadata = session.metadata.clone();
+        aggregator.aggregator.session_nonce = session.nonce;
+        aggregator.state.bind_parameters = session.parameters.is_some();
+        aggregator.aggregator.coordinator_id = session.coordinator_id.clone();
 
-        Ok(SignatureAggregator {
-            state: self.state,
-            aggregator: Finalized { message_hash },
-        })
+        aggregator
     }
-}
 
-impl SignatureAggregator<Finalized> {
-    /// Aggregate a set of previously-collected partial signatures.
+    /// Include a signer in the protocol.
+    ///
+    /// # Warning
+    ///
+    /// If this method is called for a specific participant, then that
+    /// participant MUST provide a partial signature to give to
+    /// [`SignatureAggregator.include_partial_signature`], otherwise the signing
+    /// procedure will fail.
     ///
     /// # Returns
     ///
-    /// A Result whose Ok() value is a [`ThresholdSignature`], otherwise a
-    /// `BTreeMap<u32, &'static str>` containing the participant indices of the misbehaving
-    /// signers and a description of their misbehaviour.
-    pub fn aggregate(&self) -> Result<ThresholdSignature, BTreeMap<u32, &'static str>> {
-        let mut misbehaving_participants: BTreeMap<u32, &'static str> = BTreeMap::new();
-
-        let (_, Rs) = compute_binding_factors_and_group_commitment(
-            &self.aggregator.message_hash,
-            &self.state.signers,
-        );
-        let R: RistrettoPoint = Rs.values().sum();
-        let c = compute_challenge(&self.aggregator.message_hash, &self.state.group_key, &R);
-        let all_participant_indices: Vec<u32> = self
-            .state
-            .signers
-            .iter()
-            .map(|x| x.participant_index)
-            .collect();
-        let mut z = Scalar::zero();
-
-        // We first combine all partial signatures together, to remove the need for individual
-        // signature verification in case the final group signature is valid.
-        for signer in self.state.signers.iter() {
-            // This unwrap() cannot fail, because SignatureAggregator<Initial>.finalize()
-            // checks that we have partial signature for every expected signer.
-            let partial_sig = self
-                .state
-                .partial_signatures
-                .get(&signer.participant_index)
-                .unwrap();
-
-            z += partial_sig;
+    /// A [`SignatureError::DegenerateCommitment`] if either point of the
+    /// `published_commitment_share` is the identity or the basepoint, and a
+    /// [`SignatureError::DuplicateCommitment`] if either point was already
+    /// published by another signer.  In both cases the signer is not included.
+    ///
+    /// If signed commitment shares are required with
+    /// [`SignatureAggregator::require_signed_commitments`], this method
+    /// always returns a [`SignatureError::UnsignedCommitment`].
+    ///
+    /// # Panics
+    ///
+    /// If the `signer.participant_index` doesn't match the `public_key.index`.
+    pub fn include_signer(
+        &mut self,
+        participant_index: u32,
+        published_commitment_share: (RistrettoPoint, RistrettoPoint),
+        public_key: IndividualPublicKey,
+    ) -> Result<(), SignatureError> {
+        if self.state.commitment_roster.is_some() {
+            return Err(SignatureError::UnsignedCommitment(participant_index));
         }
 
-        let signature = ThresholdSignature { z, R };
+        self.include_signer_unchecked(participant_index, published_commitment_share, public_key)
+    }
 
-        // Verify the obtained signature, listing malicious participants
-        // if the verification failed.
-        match signature.verify(&self.state.group_key, &self.aggregator.message_hash) {
-            Ok(()) => Ok(signature),
-            Err(_) => {
-                for signer in self.state.signers.iter() {
-                    // This unwrap() cannot fail, since the attempted division by zero in
-                    // the calculation of the Lagrange interpolation cannot happen,
-                    // because we use the typestate pattern,
-                    // i.e. SignatureAggregator<Initial>.finalize(), to ensure that
-                    // there are no duplicate signers, which is the only thing that
-                    // would cause a denominator of zero.
-                    let lambda = calculate_lagrange_coefficients(
-                        &signer.participant_index,
-                        &all_participant_indices,
-                    )
-                    .unwrap();
+    /// Include a signer whose `published_commitment_share` was signed with
+    /// [`sign_commitment_share`](crate::attestation::sign_commitment_share)
+    /// by its long-term Diffie-Hellman key, as required by
+    /// [`SignatureAggregator::require_signed_commitments`].
+    ///
+    /// # Returns
+    ///
+    /// A [`SignatureError::UnsignedCommitment`] if signed commitment shares
+    /// are not required, or if the `signature` was not made by this signer
+    /// over this exact commitment share, and otherwise the errors of
+    /// [`SignatureAggregator::include_signer`].
+    ///
+    /// # Panics
+    ///
+    /// If the `signer.participant_index` doesn't match the `public_key.index`.
+    pub fn include_signed_signer(
+        &mut self,
+        participant_index: u32,
+        published_commitment_share: (RistrettoPoint, RistrettoPoint),
+        signature: &AttestationSignature,
+        public_key: IndividualPublicKey,
+    ) -> Result<(), SignatureError> {
+        match &self.state.commitment_roster {
+            Some(roster)
+                if verify_commitment_share(
+                    participant_index,
+                    &published_commitment_share,
+                    signature,
+                    roster,
+                )
+                .is_ok() => {}
+            _ => return Err(SignatureError::UnsignedCommitment(participant_index)),
+        }
 
-                    // This cannot fail, and has already been performed previously.
-                    let partial_sig = self
-                        .state
-                        .partial_signatures
-                        .get(&signer.participant_index)
-                        .unwrap();
+        self.include_signer_unchecked(participant_index, published_commitment_share, public_key)
+    }
 
-                    // Again, this unwrap() cannot fail, because of the checks in finalize().
-                    let Y_i = self
-                        .state
-                        .public_keys
-                        .get(&signer.participant_index)
-                        .unwrap();
+    fn include_signer_unchecked(
+        &mut self,
+        participant_index: u32,
+        published_commitment_share: (RistrettoPoint, RistrettoPoint),
+        public_key: IndividualPublicKey,
+    ) -> Result<(), SignatureError> {
+        assert_eq!(participant_index, public_key.index,
+                   "Tried to add signer with participant index {}, but public key is for participant with index {}",
+                   participant_index, public_key.index);
 
-                    let check = &RISTRETTO_BASEPOINT_TABLE * partial_sig;
+        let (hiding, binding) = published_commitment_share;
+        let basepoint = RISTRETTO_BASEPOINT_TABLE.basepoint();
 
-                    // Again, this unwrap() cannot fail, because we check the
-                    // participant indexes against the expected ones in finalize().
-                    let R_i = Rs.get(&signer.participant_index).unwrap();
+        for point in [hiding, binding] {
+            if point == RistrettoPoint::identity() || point == basepoint {
+                return Err(SignatureError::DegenerateCommitment(participant_index));
+            }
+        }
 
-                    if check != R_i + (Y_i * (c * lambda)) {
-                        // XXX We don't really need the error string anymore, since there's only one failure mode.
-                        misbehaving_participants
-                            .insert(signer.participant_index, "Incorrect partial signature");
-                    }
-                }
-                Err(misbehaving_participants)
+        // Identical nonce commitments across signers are almost certainly the
+        // result of a copy-paste error, and would lead to correlated nonces.
+        for signer in self.state.signers.iter() {
+            if signer.participant_index == participant_index {
+                continue;
+            }
+            let (other_hiding, other_binding) = signer.published_commitment_share;
+            if [other_hiding, other_binding]
+                .iter()
+                .any(|p| *p == hiding || *p == binding)
+            {
+                return Err(SignatureError::DuplicateCommitment {
+                    signers: (signer.participant_index, participant_index),
+                });
             }
         }
+
+        self.state
+            .signers
+            .push(Signer::new(participant_index, published_commitment_share));
+        self.state
+            .public_keys
+            .insert(&public_key.index, public_key.share);
+
+        Ok(())
     }
-}
 
-impl ThresholdSignature {
-    /// Verify this [`ThresholdSignature`].
+    /// Add a weighted signer, holding several participant `indices`, with
+    /// a published commitment share and a public key for each of them, as
+    /// with [`SignatureAggregator::include_signer`].
+    ///
+    /// The signer submits a single partial signature, combining those made
+    /// with each of its indices with [`PartialThresholdSignature::combine`],
+    /// under its lowest index.  It is verified against the sum of the
+    /// contributions of all its indices, weighted by their respective
+    /// Lagrange coefficients.
     ///
     /// # Returns
     ///
-    /// A `Result` whose `Ok` value is an empty tuple if the threshold signature
-    /// was successfully verified, otherwise a vector of the participant indices
-    /// of any misbehaving participants.
-    pub fn verify(
-        &self,
-        group_key: &GroupKey,
-        message_hash: &[u8; 32],
+    /// A [`SignatureError::Custom`] error if the numbers of indices,
+    /// commitment shares and public keys differ, if there are no indices, or
+    /// if an index is given twice, and otherwise the error of the first
+    /// index which could not be included, in which case the ones before it
+    /// were included as independent signers.
+    pub fn include_weighted_signer(
+        &mut self,
+        indices: &[u32],
+        published_commitment_shares: &[(RistrettoPoint, RistrettoPoint)],
+        public_keys: &[IndividualPublicKey],
     ) -> Result<(), SignatureError> {
-        let c_prime = compute_challenge(message_hash, group_key, &self.R);
-        let R_prime =
-            RistrettoPoint::vartime_double_scalar_mul_basepoint(&c_prime, &-group_key.0, &self.z);
-
-        match self.R.compress() == R_prime.compress() {
-            true => Ok(()),
-            false => Err(SignatureError::InvalidSignature),
+        if indices.is_empty()
+            || indices.len() != published_commitment_shares.len()
+            || indices.len() != public_keys.len()
+        {
+            return Err(SignatureError::Custom(
+                "A weighted signer needs a commitment share and a public key for each index"
+                    .to_string(),
+            ));
+        }
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        if sorted.len() != indices.len() {
+            return Err(SignatureError::Custom(
+                "A weighted signer cannot hold the same index twice".to_string(),
+            ));
         }
-    }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        for ((index, commitment_share), public_key) in indices
+            .iter()
+            .zip(published_commitment_shares.iter())
+            .zip(public_keys.iter())
+        {
+            self.include_signer(*index, *commitment_share, public_key.clone())?;
+        }
+        if sorted.len() > 1 {
+            self.state.weighted_signers.push(sorted);
+        }
 
-    use crate::keygen::Participant;
-    use crate::keygen::{DistributedKeyGeneration, RoundOne};
-    use crate::precomputation::{generate_commitment_share_lists, PublicCommitmentShareList};
+        Ok(())
+    }
 
-    use curve25519_dalek::traits::Identity;
+    /// Get the list of partipating signers.
+    ///
+    /// # Returns
+    ///
+    /// A `&Vec<Signer>` of the participating signers in this round.
+    pub fn get_signers(&'_ mut self) -> &'_ Vec<Signer> {
+        // .sort() must be called before .dedup() because the latter only
+        // removes consecutive repeated elements.
+        self.state.signers.sort();
+        self.state.signers.dedup();
 
-    use rand::rngs::OsRng;
+        &self.state.signers
+    }
 
-    #[test]
-    fn signing_and_verification_single_party() {
-        let params = Parameters { n: 1, t: 1 };
-        let mut rng = OsRng;
-
-        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+    /// Helper function to get the remaining signers who were expected to sign,
+    /// but have not yet contributed their [`PartialThresholdSignature`]s.
+    ///
+    /// This can be used by an honest aggregator who wishes to ensure that the
+    /// aggregation procedure is ready to be run, or who wishes to be able to
+    /// remind/poll individual signers for their [`PartialThresholdSignature`]
+    /// contribution.
+    ///
+    /// # Returns
+    ///
+    /// A sorted `Vec` of unique [`Signer`]s who have yet to contribute their
+    /// partial signatures.
+    pub fn get_remaining_signers(&self) -> Vec<Signer> {
+        let mut remaining_signers: Vec<Signer> = Vec::new();
 
-        p1.proof_of_secret_key
-            .as_ref()
-            .unwrap()
-            .verify(&p1.index, p1.public_key().unwrap(), "Φ")
-            .unwrap();
+        for signer in self.state.signers.iter() {
+            if self
+                .state
+                .partial_signatures
+                .get(&signer.participant_index)
+                .is_none()
+            {
+                remaining_signers.push(*signer);
+            }
+        }
+        remaining_signers.sort();
+        remaining_signers.dedup();
+        remaining_signers
+    }
 
-        let participants: Vec<Participant> = vec![p1.clone()];
-        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p1_dh_sk,
-            &p1.index,
-            &p1coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p1_my_encrypted_secret_shares =
-            p1_state.their_encrypted_secret_shares().unwrap().clone();
-        let p1_state = p1_state
-            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
-        let result = p1_state.finish();
+    /// Deterministically narrow down the included signers to exactly \\(t\\)
+    /// of them, according to a [`SubsetPolicy`].
+    ///
+    /// This should be called once all candidate signers have been included,
+    /// and before the [`SigningSession`] is handed out.  It does nothing if at
+    /// most \\(t\\) signers were included.
+    ///
+    /// # Returns
+    ///
+    /// The signers which were excluded, sorted by index, who must not sign.
+    pub fn select_signers(&mut self, policy: &SubsetPolicy) -> Vec<Signer> {
+        let t = self.state.parameters.t as usize;
+        let mut signers = self.get_signers().clone();
+        if signers.len() <= t {
+            return Vec::new();
+        }
 
-        assert!(result.is_ok());
+        if let SubsetPolicy::Priority(priorities) = policy {
+            signers.sort_by_key(|s| {
+                let rank = priorities
+                    .iter()
+                    .position(|i| *i == s.participant_index)
+                    .unwrap_or(priorities.len());
+                (rank, s.participant_index)
+            });
+        }
 
-        let (group_key, p1_sk) = result.unwrap();
+        let mut excluded = signers.split_off(t);
+        excluded.sort();
+        for signer in excluded.iter() {
+            self.state.public_keys.remove(&signer.participant_index);
+            self.state
+                .partial_signatures
+                .remove(&signer.participant_index);
+        }
+        signers.sort();
+        self.state.signers = signers;
 
-        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        excluded
+    }
 
-        let mut aggregator =
-            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    /// Limit the number of [`PartialThresholdSignature`]s which will be
+    /// verified for any single signer to `per_signer`, for each source tag
+    /// given to [`SignatureAggregator::include_partial_signature_from`].
+    ///
+    /// Once a budget is set, every partial signature is verified as soon as it
+    /// is included, and invalid ones are not kept for aggregation.  This
+    /// requires all signers to have been included beforehand.  Once a signer
+    /// has used up its budget for a source, any further partial signatures
+    /// from it through that source are rejected without verification, while
+    /// those through other sources are still verified.
+    pub fn set_partial_budget(&mut self, per_signer: u32) {
+        self.state.partial_budget = Some(per_signer);
+    }
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
+    /// Report the events of this session to `metrics`, as listed in the
+    /// [`metrics`](crate::metrics) module.
+    ///
+    /// As the session was created along with this aggregator, a
+    /// [`metrics::SESSION_CREATED`] event is reported right away.
+    pub fn set_metrics<M: Metrics + 'static>(&mut self, metrics: M) {
+        self.state.metrics = MetricsHandle::new(metrics);
+        self.state.metrics.increment_counter(
+            metrics::SESSION_CREATED,
+            &[("role", metrics::ROLE_AGGREGATOR)],
+        );
+    }
 
-        let signers = aggregator.get_signers();
-        let message_hash = compute_message_hash(&context[..], &message[..]);
+    /// Get the counters of partial signatures submitted by the signer with
+    /// the given `participant_index` from all sources, if any were submitted.
+    pub fn partial_signature_counters(
+        &self,
+        participant_index: u32,
+    ) -> Option<PartialSignatureCounters> {
+        self.state
+            .partial_counters
+            .range((participant_index, String::new())..)
+            .take_while(|((index, _), _)| *index == participant_index)
+            .map(|(_, counters)| counters)
+            .fold(None, |total, counters| {
+                Some(total.unwrap_or_default().add(counters))
+            })
+    }
 
-        let p1_partial = p1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut p1_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
+    /// Get the counters of partial signatures submitted by the signer with
+    /// the given `participant_index` from the given `source`, if any were
+    /// submitted.
+    pub fn partial_signature_counters_from(
+        &self,
+        participant_index: u32,
+        source: &str,
+    ) -> Option<PartialSignatureCounters> {
+        self.state
+            .partial_counters
+            .get(&(participant_index, source.to_string()))
+            .copied()
+    }
 
-        aggregator.include_partial_signature(p1_partial);
+    /// Add a [`PartialThresholdSignature`] to be included in the aggregation,
+    /// as with [`SignatureAggregator::include_partial_signature_from`] from
+    /// the empty source tag.
+    pub fn include_partial_signature(
+        &mut self,
+        partial_signature: PartialThresholdSignature,
+    ) -> Result<(), SignatureError> {
+        self.include_partial_signature_from(partial_signature, "")
+    }
 
-        let aggregator = aggregator.finalize().unwrap();
-        let signing_result = aggregator.aggregate();
+    /// Add a [`PartialThresholdSignature`], received from the given `source`,
+    /// e.g. the address of the peer which relayed it, to be included in the
+    /// aggregation.
+    ///
+    /// # Returns
+    ///
+    /// A [`SignatureError::DuplicatePartial`] if a partial signature from the
+    /// same signer was already included.
+    ///
+    /// If no budget was set with [`SignatureAggregator::set_partial_budget`],
+    /// this otherwise succeeds and the partial signature is only checked
+    /// during aggregation.  Otherwise, a [`SignatureError::PartialBudgetExceeded`] is
+    /// returned if the signer has exhausted its budget for this `source`, and a
+    /// [`SignatureError::InvalidPartialSignature`] if the partial signature
+    /// failed verification.
+    ///
+    /// A partial signature with a zero response, which an honest signer only
+    /// produces with negligible probability, is flagged in the
+    /// [`PartialSignatureCounters`] of its signer and reported as
+    /// [`metrics::PARTIAL_SUSPICIOUS`], but otherwise handled as any other,
+    /// as it is not invalid in itself.
+    pub fn include_partial_signature_from(
+        &mut self,
+        partial_signature: PartialThresholdSignature,
+        source: &str,
+    ) -> Result<(), SignatureError> {
+        let index = partial_signature.index;
+        let key = (index, source.to_string());
+        let mut counters = self
+            .state
+            .partial_counters
+            .get(&key)
+            .copied()
+            .unwrap_or_default();
+        counters.received += 1;
+        if partial_signature.z == Scalar::zero() {
+            counters.suspicious += 1;
+            self.state.metrics.increment_counter(
+                metrics::PARTIAL_SUSPICIOUS,
+                &[("signer", &index.to_string()), ("reason", "zero_response")],
+            );
+        }
 
-        assert!(signing_result.is_ok());
+        let result = match self.state.partial_budget {
+            _ if self.state.is_combined_index(index) => Err(SignatureError::UnknownSigner(index)),
+            _ if self.state.partial_signatures.get(&index).is_some() => {
+                Err(SignatureError::DuplicatePartial(index))
+            }
+            None => Ok(()),
+            Some(budget) if counters.verified >= budget => {
+                counters.throttled += 1;
+                Err(SignatureError::PartialBudgetExceeded(index))
+            }
+            Some(_) => {
+                counters.verified += 1;
+                if self.verify_partial_signature(&partial_signature) {
+                    Ok(())
+                } else {
+                    counters.invalid += 1;
+                    Err(SignatureError::InvalidPartialSignature(index))
+                }
+            }
+        };
+        self.state.partial_counters.insert(key, counters);
+
+        let signer = index.to_string();
+        match result.as_ref() {
+            Ok(()) => {
+                // The other indices of a weighted signer contribute nothing
+                // beyond its combined partial signature.
+                for other in self.state.combined_indices(index).iter().skip(1) {
+                    self.state.partial_signatures.insert(other, Scalar::zero());
+                }
+                self.state
+                    .partial_signatures
+                    .insert(&index, partial_signature.z);
+                self.state
+                    .metrics
+                    .increment_counter(metrics::PARTIAL_ACCEPTED, &[("signer", &signer)]);
+            }
+            Err(error) => self.state.metrics.increment_counter(
+                metrics::PARTIAL_REJECTED,
+                &[
+                    ("signer", &signer),
+                    ("reason", metrics::error_reason(error)),
+                ],
+            ),
+        }
+        result
+    }
 
-        let threshold_signature = signing_result.unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+    /// Add all the partial signatures of a [`PartialSignatureBatch`] to be
+    /// included in the aggregation, as with
+    /// [`SignatureAggregator::include_partial_signature`].
+    ///
+    /// # Returns
+    ///
+    /// The error of the first partial signature which could not be included,
+    /// in which case the ones before it in the batch were included.
+    pub fn include_partial_batch(
+        &mut self,
+        batch: PartialSignatureBatch,
+    ) -> Result<(), SignatureError> {
+        for partial in batch.partials {
+            self.include_partial_signature(partial)?;
+        }
 
-        println!("{:?}", verification_result);
+        Ok(())
+    }
 
-        assert!(verification_result.is_ok());
+    /// Verify a single [`PartialThresholdSignature`] against the public key
+    /// and published commitment share of its signer, without including it.
+    ///
+    /// # Returns
+    ///
+    /// A [`SignatureError::InvalidPartialSignature`] if the partial signature
+    /// does not verify, or its signer was not included.
+    pub fn verify_partial(
+        &self,
+        partial_signature: &PartialThresholdSignature,
+    ) -> Result<(), SignatureError> {
+        match self.verify_partial_signature(partial_signature) {
+            true => Ok(()),
+            false => Err(SignatureError::InvalidPartialSignature(
+                partial_signature.index,
+            )),
+        }
     }
 
-    #[test]
-    fn signing_and_verification_1_out_of_1() {
-        let params = Parameters { n: 1, t: 1 };
-        let mut rng = OsRng;
+    /// Check a single [`PartialThresholdSignature`] against the signer's
+    /// public key and published commitment share.
+    fn verify_partial_signature(&self, partial_signature: &PartialThresholdSignature) -> bool {
+        let mut signers = self.state.signers.clone();
+        signers.sort();
+        signers.dedup();
 
-        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+        if self.state.is_combined_index(partial_signature.index) {
+            return false;
+        }
+        let all_participant_indices: Vec<u32> =
+            signers.iter().map(|x| x.participant_index).collect();
 
-        let participants: Vec<Participant> = vec![p1.clone()];
-        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p1_dh_sk,
-            &p1.index,
-            &p1coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
+        let message_hash = self.message_hash();
+        let (_, Rs) = (self.state.hash_functions.binding_factors)(
+            &message_hash,
+            &self.state.group_key,
+            &signers,
+        );
+        let R: RistrettoPoint = Rs.values().sum();
+        let c = (self.state.hash_functions.challenge)(
+            &message_hash,
+            &self.state.group_key,
+            &R,
+            self.state.bound_parameters(),
+        );
+
+        self.state.check_partial_signature(
+            partial_signature.index,
+            &partial_signature.z,
+            &Rs,
+            &c,
+            &all_participant_indices,
         )
-        .unwrap();
-        let p1_my_encrypted_secret_shares =
-            p1_state.their_encrypted_secret_shares().unwrap().clone();
-        let p1_state = p1_state
-            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
+    }
 
-        let (group_key, p1_sk) = p1_state.finish().unwrap();
+    /// The misbehaving participants preventing the aggregation of the partial
+    /// signatures of the sorted and deduplicated `signers`, of whom the
+    /// `remaining_signers` have not contributed yet.
+    fn misbehaving_participants(
+        &self,
+        signers: &[Signer],
+        remaining_signers: &[Signer],
+    ) -> AggregationReport {
+        let mut misbehaving_participants: BTreeMap<u32, &'static str> = BTreeMap::new();
 
-        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        // [DIFFERENT_TO_PAPER] We're reporting missing partial signatures which
+        // could possibly be the fault of the aggregator, but here we explicitly
+        // make it the aggregator's fault and problem.
+        if !remaining_signers.is_empty() {
+            // We call the aggregator "participant 0" for the sake of error messages.
+            misbehaving_participants.insert(0, "Missing remaining signer(s)");
 
-        let mut aggregator =
-            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+            for signer in remaining_signers.iter() {
+                misbehaving_participants
+                    .insert(signer.participant_index, "Missing partial signature");
+            }
+        }
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
+        // A signature can only be produced by t' signers s.t. t <= t' <= n.
+        // In particular, when t = n every participant must have signed.
+        if signers.len() < self.state.parameters.t as usize {
+            misbehaving_participants.insert(0, "Missing partial signatures");
+        } else if signers.len() > self.state.parameters.n as usize {
+            misbehaving_participants.insert(0, "Too many signers");
+        }
 
-        let signers = aggregator.get_signers();
-        let message_hash = compute_message_hash(&context[..], &message[..]);
+        for signer in signers.iter() {
+            if self
+                .state
+                .public_keys
+                .get(&signer.participant_index)
+                .is_none()
+            {
+                // XXX These should be Vec<&'static str> for full error reporting
+                misbehaving_participants.insert(signer.participant_index, "Missing public key");
+            }
+        }
 
-        let p1_partial = p1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut p1_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
+        if self.aggregator.message_commitment.is_some() && !self.aggregator.message_revealed {
+            misbehaving_participants
+                .entry(0)
+                .or_insert("Committed message not revealed");
+        }
 
-        aggregator.include_partial_signature(p1_partial);
+        misbehaving_participants
+    }
 
-        let aggregator = aggregator.finalize().unwrap();
-        let threshold_signature = aggregator.aggregate().unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+    /// Compute, verify and report the signature [`SignatureAggregator::finalize`]
+    /// and [`SignatureAggregator::aggregate`] would produce, without consuming
+    /// this aggregator, e.g. to decide whether to broadcast it.
+    ///
+    /// # Returns
+    ///
+    /// The would-be [`ThresholdSignature`], or an [`AggregationReport`] of the
+    /// misbehaving participants, as either of these calls would return.
+    pub fn dry_run(&self) -> Result<ThresholdSignature, AggregationReport> {
+        let mut signers = self.state.signers.clone();
+        signers.sort();
+        signers.dedup();
+
+        let misbehaving_participants =
+            self.misbehaving_participants(&signers, &self.get_remaining_signers());
+        if !misbehaving_participants.is_empty() {
+            return Err(misbehaving_participants);
+        }
 
-        assert!(verification_result.is_ok());
+        self.state
+            .combine_partial_signatures(&signers, &self.message_hash())
     }
 
-    #[test]
-    fn signing_and_verification_1_out_of_2() {
-        let params = Parameters { n: 2, t: 1 };
-        let mut rng = OsRng;
+    /// Ensure that this signature aggregator is in a proper state to run the aggregation protocol.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok() value is a finalized aggregator, otherwise a
+    /// `BTreeMap<u32, &'static str>` containing the participant indices of the misbehaving
+    /// signers and a description of their misbehaviour.
+    ///
+    /// If the `BTreeMap` contains a key for `0`, this indicates that
+    /// the aggregator did not have \(( t' \)) partial signers
+    /// s.t. \(( t \le t' \le n \)).
+    ///
+    /// # Notes
+    ///
+    /// Finalizing consumes the aggregator, whether it succeeds or not, so
+    /// that it cannot be finalized twice:
+    ///
+    /// ```compile_fail
+    /// # use curve25519_dalek::constants::RISTRETTO_BASEPOINT_COMPRESSED;
+    /// # use ice_frost::{GroupKey, Parameters, SignatureAggregator};
+    /// # let params = Parameters { n: 3, t: 2 };
+    /// # let group_key = GroupKey::from_bytes(&RISTRETTO_BASEPOINT_COMPRESSED.to_bytes()).unwrap();
+    /// let aggregator = SignatureAggregator::new(params, group_key, b"context", b"message");
+    /// let _ = aggregator.finalize();
+    /// let _ = aggregator.finalize();
+    /// ```
+    ///
+    /// and a finalized aggregator cannot be finalized again either:
+    ///
+    /// ```compile_fail
+    /// # use curve25519_dalek::constants::RISTRETTO_BASEPOINT_COMPRESSED;
+    /// # use ice_frost::{GroupKey, Parameters, SignatureAggregator};
+    /// # let params = Parameters { n: 3, t: 2 };
+    /// # let group_key = GroupKey::from_bytes(&RISTRETTO_BASEPOINT_COMPRESSED.to_bytes()).unwrap();
+    /// let aggregator = SignatureAggregator::new(params, group_key, b"context", b"message");
+    /// let _ = aggregator.finalize().unwrap().finalize();
+    /// ```
+    pub fn finalize(
+        mut self,
+    ) -> Result<SignatureAggregator<Finalized>, BTreeMap<u32, &'static str>> {
+        let remaining_signers = self.get_remaining_signers();
 
-        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
-        let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
+        // Ensure that our new state is ordered and deduplicated.
+        self.state.signers = self.get_signers().clone();
 
-        let participants: Vec<Participant> = vec![p1.clone(), p2.clone()];
-        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p1_dh_sk,
-            &p1.index,
-            &p1coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p1_their_encrypted_secret_shares = p1_state.their_encrypted_secret_shares().unwrap();
-
-        let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p2_dh_sk,
-            &p2.index,
-            &p2coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p2_their_encrypted_secret_shares = p2_state.their_encrypted_secret_shares().unwrap();
+        let misbehaving_participants =
+            self.misbehaving_participants(&self.state.signers, &remaining_signers);
 
-        let p1_my_encrypted_secret_shares = vec![
-            p1_their_encrypted_secret_shares[0].clone(),
-            p2_their_encrypted_secret_shares[0].clone(),
-        ];
-        let p2_my_encrypted_secret_shares = vec![
-            p1_their_encrypted_secret_shares[1].clone(),
-            p2_their_encrypted_secret_shares[1].clone(),
-        ];
+        if !misbehaving_participants.is_empty() {
+            for signer in remaining_signers.iter() {
+                self.state.metrics.increment_counter(
+                    metrics::PARTIAL_MISSING,
+                    &[("signer", &signer.participant_index.to_string())],
+                );
+            }
+            self.state.metrics.increment_counter(
+                metrics::SESSION_ABORTED,
+                &[("role", metrics::ROLE_AGGREGATOR), ("reason", "incomplete")],
+            );
+            return Err(misbehaving_participants);
+        }
 
-        let p1_state = p1_state
-            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
-        let p2_state = p2_state
-            .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
+        let message_hash = self.message_hash();
 
-        let (group_key, p1_sk) = p1_state.finish().unwrap();
-        let (_, _p2_sk) = p2_state.finish().unwrap();
+        Ok(SignatureAggregator {
+            state: self.state,
+            aggregator: Finalized { message_hash },
+        })
+    }
+}
 
-        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
+impl<'sa> SignatureAggregator<Initial<'sa>> {
+    /// Reveal the `message` committed to in
+    /// [`SignatureAggregator::new_committed_message`].
+    ///
+    /// # Returns
+    ///
+    /// A [`SignatureError::MessageCommitmentMismatch`] if the message does not
+    /// hash to the commitment, or if this aggregator was not constructed over
+    /// a commitment, in which case the message is not revealed.
+    pub fn reveal_message(&mut self, message: &'sa [u8]) -> Result<(), SignatureError> {
+        let message_commitment = self
+            .aggregator
+            .message_commitment
+            .ok_or(SignatureError::MessageCommitmentMismatch)?;
+        let message_hash =
+            (self.state.hash_functions.message_hash)(&self.aggregator.context, message);
+
+        if message_hash != message_commitment {
+            return Err(SignatureError::MessageCommitmentMismatch);
+        }
 
-        let mut aggregator =
-            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        self.aggregator.message = Cow::Borrowed(message);
+        self.aggregator.message_revealed = true;
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
+        Ok(())
+    }
+}
 
-        let signers = aggregator.get_signers();
-        let message_hash = compute_message_hash(&context[..], &message[..]);
+impl SignatureAggregator<Finalized> {
+    /// Aggregate a set of previously-collected partial signatures.
+    ///
+    /// # Returns
+    ///
+    /// A Result whose Ok() value is a [`ThresholdSignature`], otherwise a
+    /// `BTreeMap<u32, &'static str>` containing the participant indices of the misbehaving
+    /// signers and a description of their misbehaviour.
+    pub fn aggregate(&self) -> Result<ThresholdSignature, BTreeMap<u32, &'static str>> {
+        let result = self
+            .state
+            .combine_partial_signatures(&self.state.signers, &self.aggregator.message_hash);
+
+        match &result {
+            Ok(_) => {
+                self.state.metrics.increment_counter(
+                    metrics::SESSION_COMPLETED,
+                    &[("role", metrics::ROLE_AGGREGATOR)],
+                );
+            }
+            Err(misbehaving_participants) => {
+                for participant_index in misbehaving_participants.keys() {
+                    self.state.metrics.increment_counter(
+                        metrics::PARTIAL_REJECTED,
+                        &[
+                            ("signer", &participant_index.to_string()),
+                            ("reason", "invalid_partial"),
+                        ],
+                    );
+                }
+                self.state.metrics.increment_counter(
+                    metrics::SESSION_ABORTED,
+                    &[
+                        ("role", metrics::ROLE_AGGREGATOR),
+                        ("reason", "invalid_signature"),
+                    ],
+                );
+            }
+        }
 
-        let p1_partial = p1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut p1_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
+        result
+    }
 
-        aggregator.include_partial_signature(p1_partial);
+    /// The hiding and binding nonce commitments published by every signer,
+    /// sorted by index, which make up the group commitment \(R\) of the
+    /// aggregated signature once weighted by the binding factors.
+    ///
+    /// These are meant for forensic logs, so that a dispute over a signature
+    /// can reconstruct what each signer committed to.
+    pub fn nonce_contributions(&self) -> Vec<(u32, (RistrettoPoint, RistrettoPoint))> {
+        self.state
+            .signers
+            .iter()
+            .map(|signer| (signer.participant_index, signer.published_commitment_share))
+            .collect()
+    }
 
-        let aggregator = aggregator.finalize().unwrap();
-        let threshold_signature = aggregator.aggregate().unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+    /// The binding factor of every signer, sorted by index, as derived from
+    /// the message and the commitment shares of the signers.
+    ///
+    /// This is gated behind the `test-vectors` feature, for comparing the
+    /// intermediate values of a signature with those of other
+    /// implementations.
+    #[cfg(feature = "test-vectors")]
+    pub fn binding_factors(&self) -> BTreeMap<u32, Scalar> {
+        let (binding_factors, _) = (self.state.hash_functions.binding_factors)(
+            &self.aggregator.message_hash,
+            &self.state.group_key,
+            &self.state.signers,
+        );
 
-        assert!(verification_result.is_ok());
+        binding_factors
     }
+}
 
-    #[test]
-    fn signing_and_verification_3_out_of_5() {
-        let params = Parameters { n: 5, t: 3 };
-        let mut rng = OsRng;
+impl ThresholdSignature {
+    /// Verify this [`ThresholdSignature`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose `Ok` value is an empty tuple if the threshold signature
+    /// was successfully verified, otherwise a vector of the participant indices
+    /// of any misbehaving participants.
+    ///
+    /// # Note
+    ///
+    /// Unlike Ed25519 points, Ristretto elements form a group of prime order,
+    /// and their encodings never decode to a point of small order.  Hence
+    /// neither the group commitment \\(R\\) nor the group key need a subgroup
+    /// check, and verification involves no cofactor.
+    pub fn verify(
+        &self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+    ) -> Result<(), SignatureError> {
+        self.verify_with::<Ristretto25519>(group_key, message_hash)
+    }
 
-        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
-        let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
-        let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
-        let (p4, p4coeffs, p4_dh_sk) = Participant::new_dealer(&params, 4, "Φ", &mut rng);
-        let (p5, p5coeffs, p5_dh_sk) = Participant::new_dealer(&params, 5, "Φ", &mut rng);
+    /// Verify this [`ThresholdSignature`] as with [`ThresholdSignature::verify`],
+    /// hashing the `(context_string, message)` pair with
+    /// [`compute_message_hash`] first.
+    pub fn verify_unhashed(
+        &self,
+        group_key: &GroupKey,
+        (context_string, message): (&[u8], &[u8]),
+    ) -> Result<(), SignatureError> {
+        let message_hash = compute_message_hash_with::<Ristretto25519>(context_string, message);
 
-        let participants: Vec<Participant> =
-            vec![p1.clone(), p2.clone(), p3.clone(), p4.clone(), p5.clone()];
-        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p1_dh_sk,
-            &p1.index,
-            &p1coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p1_their_encrypted_secret_shares = p1_state.their_encrypted_secret_shares().unwrap();
+        self.verify(group_key, &message_hash)
+    }
 
-        let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p2_dh_sk,
-            &p2.index,
-            &p2coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p2_their_encrypted_secret_shares = p2_state.their_encrypted_secret_shares().unwrap();
+    /// Verify this [`ThresholdSignature`] as with
+    /// [`ThresholdSignature::verify_unhashed`], over the context string and
+    /// message of a `signable` value.
+    pub fn verify_signable(
+        &self,
+        group_key: &GroupKey,
+        signable: &impl Signable,
+    ) -> Result<(), SignatureError> {
+        let (context_string, message) = signable.to_signing_bytes();
 
-        let (p3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p3_dh_sk,
-            &p3.index,
-            &p3coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p3_their_encrypted_secret_shares = p3_state.their_encrypted_secret_shares().unwrap();
+        self.verify_unhashed(group_key, (&context_string, &message))
+    }
 
-        let (p4_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p4_dh_sk,
-            &p4.index,
-            &p4coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p4_their_encrypted_secret_shares = p4_state.their_encrypted_secret_shares().unwrap();
+    /// Verify this [`ThresholdSignature`] as with [`ThresholdSignature::verify`],
+    /// using the hashes of the given [`Ciphersuite`].
+    pub fn verify_with<C: RistrettoCiphersuite>(
+        &self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+    ) -> Result<(), SignatureError> {
+        let c_prime = compute_challenge::<C>(message_hash, group_key, &self.R, None);
 
-        let (p5_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-            &params,
-            &p5_dh_sk,
-            &p5.index,
-            &p5coeffs,
-            &participants,
-            "Φ",
-            &mut rng,
-        )
-        .unwrap();
-        let p5_their_encrypted_secret_shares = p5_state.their_encrypted_secret_shares().unwrap();
+        self.verify_challenge(group_key, &c_prime)
+    }
 
-        let p1_my_encrypted_secret_shares = vec![
-            p1_their_encrypted_secret_shares[0].clone(),
-            p2_their_encrypted_secret_shares[0].clone(),
-            p3_their_encrypted_secret_shares[0].clone(),
-            p4_their_encrypted_secret_shares[0].clone(),
-            p5_their_encrypted_secret_shares[0].clone(),
-        ];
+    /// Verify this [`ThresholdSignature`], produced by a [`SignatureAggregator`]
+    /// which bound its `parameters` with
+    /// [`SignatureAggregator::bind_parameters`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose `Ok` value is an empty tuple if the threshold signature
+    /// was successfully verified under exactly these `parameters`.
+    pub fn verify_with_parameters(
+        &self,
+        group_key: &GroupKey,
+        parameters: &Parameters,
+        message_hash: &[u8; 32],
+    ) -> Result<(), SignatureError> {
+        let c_prime =
+            compute_challenge::<Ristretto25519>(message_hash, group_key, &self.R, Some(parameters));
 
-        let p2_my_encrypted_secret_shares = vec![
-            p1_their_encrypted_secret_shares[1].clone(),
-            p2_their_encrypted_secret_shares[1].clone(),
-            p3_their_encrypted_secret_shares[1].clone(),
-            p4_their_encrypted_secret_shares[1].clone(),
-            p5_their_encrypted_secret_shares[1].clone(),
-        ];
+        self.verify_challenge(group_key, &c_prime)
+    }
 
-        let p3_my_encrypted_secret_shares = vec![
-            p1_their_encrypted_secret_shares[2].clone(),
-            p2_their_encrypted_secret_shares[2].clone(),
-            p3_their_encrypted_secret_shares[2].clone(),
-            p4_their_encrypted_secret_shares[2].clone(),
-            p5_their_encrypted_secret_shares[2].clone(),
-        ];
+    /// Verify this [`ThresholdSignature`] as with [`ThresholdSignature::verify`],
+    /// given only the 32-byte encoding of the group key.
+    ///
+    /// The Ristretto group has no notion of x-only keys with an implicit
+    /// even y-coordinate, as used in BIP-340: the compressed encoding of a
+    /// group key is already a canonical 32-byte string.  It is decoded with
+    /// [`GroupKey::from_bytes_strict`], so that non-canonical encodings and
+    /// the identity are rejected.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidSignature` error if the key bytes are rejected, or if the
+    /// signature does not verify under the key they encode.
+    pub fn verify_with_key_bytes(
+        &self,
+        group_key: &[u8; 32],
+        message_hash: &[u8; 32],
+    ) -> Result<(), SignatureError> {
+        let group_key =
+            GroupKey::from_bytes_strict(group_key).map_err(|_| SignatureError::InvalidSignature)?;
 
-        let p4_my_encrypted_secret_shares = vec![
-            p1_their_encrypted_secret_shares[3].clone(),
-            p2_their_encrypted_secret_shares[3].clone(),
-            p3_their_encrypted_secret_shares[3].clone(),
-            p4_their_encrypted_secret_shares[3].clone(),
-            p5_their_encrypted_secret_shares[3].clone(),
-        ];
+        self.verify(&group_key, message_hash)
+    }
 
-        let p5_my_encrypted_secret_shares = vec![
-            p1_their_encrypted_secret_shares[4].clone(),
-            p2_their_encrypted_secret_shares[4].clone(),
-            p3_their_encrypted_secret_shares[4].clone(),
-            p4_their_encrypted_secret_shares[4].clone(),
-            p5_their_encrypted_secret_shares[4].clone(),
-        ];
+    /// Verify this [`ThresholdSignature`] over a `message` and its `metadata`,
+    /// along the verification path given by a `descriptor`.
+    ///
+    /// # Returns
+    ///
+    /// An `UnsupportedDescriptor` error if the descriptor asks for a hash
+    /// suite, a key encoding or a tweak this crate cannot verify, and an
+    /// `InvalidSignature` error if the `metadata` does not match the digest of
+    /// the descriptor, or if the signature does not verify along its path.
+    pub fn verify_with_descriptor(
+        &self,
+        descriptor: &VerificationDescriptor,
+        group_key: &GroupKey,
+        message: &[u8],
+        metadata: &[(&str, &[u8])],
+    ) -> Result<(), SignatureError> {
+        if descriptor.key_encoding != VerificationDescriptor::RISTRETTO_KEY {
+            return Err(SignatureError::UnsupportedDescriptor("key encoding"));
+        }
+        if descriptor.tweak.is_some() {
+            return Err(SignatureError::UnsupportedDescriptor("tweaked key"));
+        }
+        let message_hash = match descriptor.suite_id {
+            VerificationDescriptor::DEFAULT_SUITE => {
+                compute_message_hash_with::<Ristretto25519>(&descriptor.context, message)
+            }
+            VerificationDescriptor::UNIFORM_SHA512_SUITE => compute_message_hash_with::<
+                UniformRistretto25519<Sha512>,
+            >(
+                &descriptor.context, message
+            ),
+            _ => return Err(SignatureError::UnsupportedDescriptor("ciphersuite")),
+        };
 
-        let p1_state = p1_state
-            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
-        let p2_state = p2_state
-            .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
-        let p3_state = p3_state
-            .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
-        let p4_state = p4_state
-            .to_round_two(p4_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
-        let p5_state = p5_state
-            .to_round_two(p5_my_encrypted_secret_shares, &mut rng)
-            .unwrap();
-
-        let (group_key, p1_sk) = p1_state.finish().unwrap();
-        let (_, _) = p2_state.finish().unwrap();
-        let (_, p3_sk) = p3_state.finish().unwrap();
-        let (_, p4_sk) = p4_state.finish().unwrap();
-        let (_, _) = p5_state.finish().unwrap();
-
-        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
-        let (p3_public_comshares, mut p3_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 3, 1);
-        let (p4_public_comshares, mut p4_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 4, 1);
-
-        let mut aggregator =
-            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
-
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into());
-        aggregator.include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into());
+        let canonical = canonicalise_metadata(metadata)?;
+        if metadata_digest(&canonical) != descriptor.metadata_digest {
+            return Err(SignatureError::InvalidSignature);
+        }
+        if SequenceBinding::from_metadata(&canonical) != descriptor.sequence {
+            return Err(SignatureError::InvalidSignature);
+        }
+        let mut message_hash = bind_canonical_metadata(&message_hash, &canonical);
+        if let Some(coordinator_id) = descriptor.coordinator_id.as_ref() {
+            message_hash = bind_coordinator_id(&message_hash, coordinator_id);
+        }
 
-        let signers = aggregator.get_signers();
-        let message_hash = compute_message_hash(&context[..], &message[..]);
+        match descriptor.parameters {
+            Some(parameters) => self.verify_with_parameters(group_key, &parameters, &message_hash),
+            None => self.verify(group_key, &message_hash),
+        }
+    }
 
-        let p1_partial = p1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut p1_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
-        let p3_partial = p3_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut p3_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
-        let p4_partial = p4_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut p4_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
+    /// Verify this [`ThresholdSignature`] for an already computed challenge.
+    fn verify_challenge(
+        &self,
+        group_key: &GroupKey,
+        c_prime: &Scalar,
+    ) -> Result<(), SignatureError> {
+        let R_prime =
+            RistrettoPoint::vartime_double_scalar_mul_basepoint(c_prime, &-group_key.0, &self.z);
 
-        aggregator.include_partial_signature(p1_partial);
-        aggregator.include_partial_signature(p3_partial);
-        aggregator.include_partial_signature(p4_partial);
+        match self.R.compress() == R_prime.compress() {
+            true => Ok(()),
+            false => Err(SignatureError::InvalidSignature),
+        }
+    }
 
-        let aggregator = aggregator.finalize().unwrap();
-        let threshold_signature = aggregator.aggregate().unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+    /// Verify this [`ThresholdSignature`] over a `message_hash` to which some
+    /// key-value `metadata` was bound with [`bind_metadata`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose `Ok` value is an empty tuple if the threshold signature
+    /// was successfully verified with exactly this metadata.
+    pub fn verify_with_metadata(
+        &self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+        metadata: &[(&str, &[u8])],
+    ) -> Result<(), SignatureError> {
+        let message_hash = bind_metadata(message_hash, metadata)?;
 
-        assert!(verification_result.is_ok());
+        self.verify(group_key, &message_hash)
     }
 
-    #[test]
-    fn signing_and_verification_2_out_of_3() {
-        fn do_keygen() -> Result<(Parameters, SecretKey, SecretKey, SecretKey, GroupKey), ()> {
-            let params = Parameters { n: 3, t: 2 };
-            let mut rng = OsRng;
+    /// Verify this [`ThresholdSignature`] over a `message_hash`, for a session
+    /// orchestrated by the coordinator with identity `coordinator_id`, as
+    /// bound with [`SignatureAggregator::bind_coordinator`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` whose `Ok` value is an empty tuple if the threshold signature
+    /// was successfully verified for exactly this coordinator.
+    pub fn verify_with_coordinator(
+        &self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+        coordinator_id: &[u8],
+    ) -> Result<(), SignatureError> {
+        self.verify(
+            group_key,
+            &bind_coordinator_id(message_hash, coordinator_id),
+        )
+    }
 
-            let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
-            let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
-            let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
+    /// Verify this [`ThresholdSignature`] as with [`ThresholdSignature::verify`],
+    /// under a `group_key` claimed to be the one of the [`PublicKeyPackage`]
+    /// whose hash is `package_hash`, as stored by a light client.
+    ///
+    /// # Returns
+    ///
+    /// A `PackageMismatch` error if the `package` does not hash to
+    /// `package_hash`, or does not hold the `group_key`.
+    pub fn verify_with_package_hash(
+        &self,
+        group_key: &GroupKey,
+        package: &PublicKeyPackage,
+        package_hash: &[u8; 32],
+        message_hash: &[u8; 32],
+    ) -> Result<(), SignatureError> {
+        if package.hash() != *package_hash || package.group_key != *group_key {
+            return Err(SignatureError::PackageMismatch);
+        }
 
-            p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&p2.index, p2.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
-            p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&p3.index, p3.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
+        self.verify(group_key, message_hash)
+    }
 
-            let participants: Vec<Participant> = vec![p1.clone(), p2.clone(), p3.clone()];
-            let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-                &params,
-                &p1_dh_sk,
-                &p1.index,
-                &p1coeffs,
-                &participants,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
-            let p1_their_encrypted_secret_shares =
-                p1_state.their_encrypted_secret_shares().or(Err(()))?;
+    /// Verify this [`ThresholdSignature`] as with [`ThresholdSignature::verify`],
+    /// and report the values the verification bound together.
+    ///
+    /// This is meant for verifiers which derive the `message_hash` from
+    /// external data, and want to cross-check the challenge against their own
+    /// computation, for instance with [`VerifyReport::binds`].
+    ///
+    /// # Returns
+    ///
+    /// A [`VerifyReport`] holding the challenge and the group commitment
+    /// \(R\) if the threshold signature was successfully verified.
+    pub fn verify_and_report(
+        &self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+    ) -> Result<VerifyReport, SignatureError> {
+        let challenge = compute_challenge::<Ristretto25519>(message_hash, group_key, &self.R, None);
 
-            let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-                &params,
-                &p2_dh_sk,
-                &p2.index,
-                &p2coeffs,
-                &participants,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
-            let p2_their_encrypted_secret_shares =
-                p2_state.their_encrypted_secret_shares().or(Err(()))?;
+        self.verify_challenge(group_key, &challenge)?;
 
-            let (p3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-                &params,
-                &p3_dh_sk,
-                &p3.index,
-                &p3coeffs,
-                &participants,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
-            let p3_their_encrypted_secret_shares =
-                p3_state.their_encrypted_secret_shares().or(Err(()))?;
+        Ok(VerifyReport {
+            message_hash: *message_hash,
+            challenge,
+            R: self.R,
+        })
+    }
+}
 
-            let p1_my_encrypted_secret_shares = vec![
-                p1_their_encrypted_secret_shares[0].clone(),
-                p2_their_encrypted_secret_shares[0].clone(),
-                p3_their_encrypted_secret_shares[0].clone(),
-            ];
-            let p2_my_encrypted_secret_shares = vec![
-                p1_their_encrypted_secret_shares[1].clone(),
-                p2_their_encrypted_secret_shares[1].clone(),
-                p3_their_encrypted_secret_shares[1].clone(),
-            ];
-            let p3_my_encrypted_secret_shares = vec![
-                p1_their_encrypted_secret_shares[2].clone(),
-                p2_their_encrypted_secret_shares[2].clone(),
-                p3_their_encrypted_secret_shares[2].clone(),
-            ];
+/// The values bound together by a successful verification of a
+/// [`ThresholdSignature`], as returned by
+/// [`ThresholdSignature::verify_and_report`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VerifyReport {
+    /// The message hash the signature was verified over.
+    pub message_hash: [u8; 32],
+    /// The challenge \(c\) binding \(R\), the group key and the message hash.
+    pub challenge: Scalar,
+    /// The group commitment \(R\) recovered from the signature.
+    pub R: RistrettoPoint,
+}
 
-            let p1_state = p1_state
-                .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let p2_state = p2_state
-                .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let p3_state = p3_state
-                .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
+impl VerifyReport {
+    /// Check that the challenge of this report binds the given `group_key`
+    /// and an independently computed `message_hash`.
+    pub fn binds(&self, group_key: &GroupKey, message_hash: &[u8; 32]) -> bool {
+        self.message_hash == *message_hash
+            && compute_challenge::<Ristretto25519>(message_hash, group_key, &self.R, None)
+                == self.challenge
+    }
+}
 
-            let (p1_group_key, p1_secret_key) = p1_state.finish().or(Err(()))?;
-            let (p2_group_key, p2_secret_key) = p2_state.finish().or(Err(()))?;
-            let (p3_group_key, p3_secret_key) = p3_state.finish().or(Err(()))?;
+/// The components of the verification equation of a Schnorr signature,
+///
+/// \\[
+/// z \cdot B = R + c \cdot Y
+/// \\]
+///
+/// where \\(B\\) is the Ristretto basepoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VerificationEquation {
+    /// The signature commitment \\(R\\).
+    pub R: RistrettoPoint,
+    /// The signature response \\(z\\).
+    pub z: Scalar,
+    /// The challenge \\(c\\) binding \\(R\\), the public key and the message.
+    pub challenge: Scalar,
+    /// The public key \\(Y\\) the signature verifies under.
+    pub public_key: RistrettoPoint,
+}
 
-            assert!(p1_group_key.0.compress() == p2_group_key.0.compress());
-            assert!(p2_group_key.0.compress() == p3_group_key.0.compress());
+/// A signature whose verification equation can be combined with others by an
+/// external batch verifier, e.g. in a single multiscalar multiplication
+///
+/// \\[
+/// (\sum\_i a\_i z\_i) \cdot B - \sum\_i a\_i \cdot R\_i - \sum\_i (a\_i c\_i) \cdot Y\_i = 0
+/// \\]
+///
+/// for random weights \\(a\_i\\).
+pub trait BatchVerifiable {
+    /// Get the components of the verification equation of this signature for
+    /// a `message_hash` under a `group_key`.
+    fn verification_equation(
+        &self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+    ) -> VerificationEquation;
+}
 
-            Ok((
-                params,
-                p1_secret_key,
-                p2_secret_key,
-                p3_secret_key,
-                p1_group_key,
-            ))
+impl BatchVerifiable for ThresholdSignature {
+    fn verification_equation(
+        &self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+    ) -> VerificationEquation {
+        VerificationEquation {
+            R: self.R,
+            z: self.z,
+            challenge: compute_challenge::<Ristretto25519>(message_hash, group_key, &self.R, None),
+            public_key: group_key.0,
         }
-        let keygen_protocol = do_keygen();
+    }
+}
 
-        assert!(keygen_protocol.is_ok());
+/// A verifier of arbitrarily large batches of [`BatchVerifiable`] signatures,
+/// using constant memory.
+///
+/// Each added signature is folded into a running random linear combination
+///
+/// \\[
+/// \sum\_i a\_i \cdot R\_i + \sum\_i (a\_i c\_i) \cdot Y\_i = (\sum\_i a\_i z\_i) \cdot B
+/// \\]
+///
+/// whose weights \\(a\_i\\) are derived from a secret random seed and the
+/// signature itself, and which is only checked by [`BatchVerifier::verify`].
+/// A failed batch does not tell which signature was invalid.
+pub struct BatchVerifier {
+    /// The secret seed the weights are derived from.
+    seed: [u8; 32],
+    /// The number of signatures added thus far.
+    count: u64,
+    /// The running sum of the weighted commitments and public keys.
+    points: RistrettoPoint,
+    /// The running sum of the weighted responses.
+    z: Scalar,
+}
 
-        let (params, p1_sk, p2_sk, _p3_sk, group_key) = keygen_protocol.unwrap();
+impl fmt::Debug for BatchVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BatchVerifier")
+            .field("count", &self.count)
+            .finish()
+    }
+}
 
-        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
-        let (p2_public_comshares, mut p2_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 2, 1);
+impl BatchVerifier {
+    /// Construct a new, empty, batch verifier.
+    pub fn new(mut rng: impl RngCore + CryptoRng) -> BatchVerifier {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+
+        BatchVerifier {
+            seed,
+            count: 0,
+            points: RistrettoPoint::identity(),
+            z: Scalar::zero(),
+        }
+    }
 
-        let mut aggregator =
-            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    /// Add a `signature` over a `message_hash` under a `group_key` to the batch.
+    pub fn add<S: BatchVerifiable>(
+        &mut self,
+        group_key: &GroupKey,
+        message_hash: &[u8; 32],
+        signature: &S,
+    ) {
+        let equation = signature.verification_equation(group_key, message_hash);
+
+        let mut h = RistrettoHash::new();
+        h.update(b"ICE-FROST-BATCH");
+        h.update(self.seed);
+        h.update(self.count.to_le_bytes());
+        h.update(equation.R.compress().as_bytes());
+        h.update(equation.z.as_bytes());
+        h.update(equation.challenge.as_bytes());
+        h.update(equation.public_key.compress().as_bytes());
+        let a = Ristretto25519::hash_to_scalar(h);
+
+        self.points += RistrettoPoint::vartime_multiscalar_mul(
+            &[a, a * equation.challenge],
+            &[equation.R, equation.public_key],
+        );
+        self.z += a * equation.z;
+        self.count += 1;
+    }
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
+    /// The number of signatures added to the batch thus far.
+    pub fn len(&self) -> u64 {
+        self.count
+    }
 
-        let signers = aggregator.get_signers();
-        let message_hash = compute_message_hash(&context[..], &message[..]);
+    /// Whether no signature was added to the batch.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
 
-        let p1_partial = p1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut p1_secret_comshares,
-                0,
-                signers,
-            )
+    /// Verify all signatures of the batch at once.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every signature of the batch is valid, which includes the
+    /// empty batch, and a [`SignatureError::InvalidSignature`] otherwise.
+    pub fn verify(self) -> Result<(), SignatureError> {
+        match (&RISTRETTO_BASEPOINT_TABLE * &self.z).compress() == self.points.compress() {
+            true => Ok(()),
+            false => Err(SignatureError::InvalidSignature),
+        }
+    }
+}
+
+/// Check a random linear combination of the verification `equations` in a
+/// single multiscalar multiplication.
+fn verify_equations(equations: &[VerificationEquation], mut rng: impl RngCore + CryptoRng) -> bool {
+    let mut scalars: Vec<Scalar> = Vec::with_capacity(2 * equations.len() + 1);
+    let mut points: Vec<RistrettoPoint> = Vec::with_capacity(2 * equations.len() + 1);
+
+    let mut z = Scalar::zero();
+    for equation in equations.iter() {
+        let a = Scalar::random(&mut rng);
+
+        z += a * equation.z;
+        scalars.push(-a);
+        points.push(equation.R);
+        scalars.push(-(a * equation.challenge));
+        points.push(equation.public_key);
+    }
+    scalars.push(z);
+    points.push(RISTRETTO_BASEPOINT_TABLE.basepoint());
+
+    RistrettoPoint::vartime_multiscalar_mul(scalars, points) == RistrettoPoint::identity()
+}
+
+/// Verify a batch of threshold signatures, each over its own message hash and
+/// under its own group key, e.g. from many different groups.
+///
+/// A random linear combination of all verification equations is checked in
+/// a single multiscalar multiplication, which is sound for Schnorr signatures
+/// even under distinct keys.  Should it fail, the batch is bisected, checking
+/// each half the same way, to identify the invalid signatures.
+///
+/// # Returns
+///
+/// `Ok(())` if every signature of the batch is valid, which includes the
+/// empty batch, and an `InvalidBatchItems` error with the sorted positions of
+/// the invalid signatures otherwise.
+pub fn verify_heterogeneous_batch(
+    items: &[(GroupKey, [u8; 32], ThresholdSignature)],
+    mut rng: impl RngCore + CryptoRng,
+) -> Result<(), SignatureError> {
+    let equations: Vec<VerificationEquation> = items
+        .iter()
+        .map(|(group_key, message_hash, signature)| {
+            signature.verification_equation(group_key, message_hash)
+        })
+        .collect();
+
+    let mut invalid: Vec<usize> = Vec::new();
+    let mut ranges: Vec<(usize, usize)> = [(0, equations.len())].to_vec();
+    while let Some((start, end)) = ranges.pop() {
+        if start == end || verify_equations(&equations[start..end], &mut rng) {
+            continue;
+        }
+        match end - start {
+            1 => invalid.push(start),
+            len => {
+                ranges.push((start, start + len / 2));
+                ranges.push((start + len / 2, end));
+            }
+        }
+    }
+
+    match invalid.is_empty() {
+        true => Ok(()),
+        false => {
+            invalid.sort_unstable();
+            Err(SignatureError::InvalidBatchItems(invalid))
+        }
+    }
+}
+
+/// The half-aggregate of several [`ThresholdSignature`]s over the same
+/// message under different group keys, as produced by
+/// [`ThresholdSignature::batch_aggregate`].
+///
+/// It keeps the commitment \\(R\\) of every signature but a single response
+///
+/// \\[
+/// z = \sum\_i a\_i z\_i
+/// \\]
+///
+/// for coefficients \\(a\_i\\) derived from the message hash, the group keys
+/// and all the commitments, taking \\(32 (n + 1)\\) bytes instead of
+/// \\(64 n\\) for \\(n\\) signatures.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HalfAggregate {
+    /// The commitments of the aggregated signatures, in order.
+    pub(crate) Rs: Vec<RistrettoPoint>,
+    /// The aggregated response.
+    pub(crate) z: Scalar,
+}
+
+/// Derive the coefficient of each signature of a [`HalfAggregate`].
+fn half_aggregation_coefficients(
+    message_hash: &[u8; 32],
+    group_keys: &[&GroupKey],
+    Rs: &[RistrettoPoint],
+) -> Vec<Scalar> {
+    let mut h = RistrettoHash::new();
+    h.update(b"ICE-FROST-HALF-AGGREGATE");
+    h.update(message_hash);
+    h.update((Rs.len() as u32).to_le_bytes());
+    for (group_key, R) in group_keys.iter().zip(Rs.iter()) {
+        h.update(group_key.to_bytes());
+        h.update(R.compress().as_bytes());
+    }
+
+    (0..Rs.len() as u32)
+        .map(|i| {
+            let mut h = h.clone();
+            h.update(i.to_le_bytes());
+            Ristretto25519::hash_to_scalar(h)
+        })
+        .collect()
+}
+
+impl ThresholdSignature {
+    /// Half-aggregate threshold signatures over the same `message_hash`, each
+    /// under its own group key.
+    ///
+    /// The signatures are not verified: an aggregate including an invalid
+    /// signature does not verify.
+    pub fn batch_aggregate(
+        message_hash: &[u8; 32],
+        signatures: &[(&GroupKey, &ThresholdSignature)],
+    ) -> HalfAggregate {
+        let group_keys: Vec<&GroupKey> = signatures.iter().map(|(key, _)| *key).collect();
+        let Rs: Vec<RistrettoPoint> = signatures.iter().map(|(_, sig)| sig.R).collect();
+        let coefficients = half_aggregation_coefficients(message_hash, &group_keys, &Rs);
+        let z = signatures
+            .iter()
+            .zip(coefficients.iter())
+            .map(|((_, signature), a)| a * signature.z)
+            .sum();
+
+        HalfAggregate { Rs, z }
+    }
+}
+
+impl HalfAggregate {
+    /// The number of aggregated signatures.
+    pub fn len(&self) -> usize {
+        self.Rs.len()
+    }
+
+    /// Whether no signature was aggregated.
+    pub fn is_empty(&self) -> bool {
+        self.Rs.is_empty()
+    }
+
+    /// Verify this half-aggregate over a `message_hash`, under the
+    /// `group_keys` of the aggregated signatures, in the same order.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every aggregated signature was valid, and an
+    /// `InvalidSignature` error otherwise, including for an empty aggregate
+    /// or a wrong number of group keys.
+    pub fn verify(
+        &self,
+        message_hash: &[u8; 32],
+        group_keys: &[&GroupKey],
+    ) -> Result<(), SignatureError> {
+        if self.is_empty() || group_keys.len() != self.Rs.len() {
+            return Err(SignatureError::InvalidSignature);
+        }
+
+        let coefficients = half_aggregation_coefficients(message_hash, group_keys, &self.Rs);
+        let mut scalars: Vec<Scalar> = Vec::with_capacity(2 * self.Rs.len() + 1);
+        let mut points: Vec<RistrettoPoint> = Vec::with_capacity(2 * self.Rs.len() + 1);
+        for ((group_key, R), a) in group_keys.iter().zip(self.Rs.iter()).zip(coefficients) {
+            let challenge = compute_challenge::<Ristretto25519>(message_hash, group_key, R, None);
+            scalars.push(-a);
+            points.push(*R);
+            scalars.push(-(a * challenge));
+            points.push(group_key.0);
+        }
+        scalars.push(self.z);
+        points.push(RISTRETTO_BASEPOINT_TABLE.basepoint());
+
+        match RistrettoPoint::vartime_multiscalar_mul(scalars, points) == RistrettoPoint::identity()
+        {
+            true => Ok(()),
+            false => Err(SignatureError::InvalidSignature),
+        }
+    }
+
+    /// Serialise this half-aggregate to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(4 + 32 * (self.Rs.len() + 1));
+        res.extend_from_slice(&(self.Rs.len() as u32).to_le_bytes());
+        for R in self.Rs.iter() {
+            res.extend_from_slice(&encode_point(R));
+        }
+        res.extend_from_slice(&encode_scalar(&self.z));
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `HalfAggregate`
+    pub fn from_bytes(bytes: &[u8]) -> Result<HalfAggregate, Error> {
+        let len = u32::from_le_bytes(
+            bytes
+                .get(0..4)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        if (bytes.len() - 4) as u64 != 32 * (len as u64 + 1) {
+            return Err(Error::SerialisationError);
+        }
+
+        let mut chunks = bytes[4..].chunks_exact(32);
+        let mut Rs: Vec<RistrettoPoint> = Vec::with_capacity(len as usize);
+        for chunk in chunks.by_ref().take(len as usize) {
+            Rs.push(decode_point_checked(
+                &chunk.try_into().map_err(|_| Error::SerialisationError)?,
+            )?);
+        }
+        let z = decode_scalar_canonical(
+            &chunks
+                .next()
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        )?;
+
+        Ok(HalfAggregate { Rs, z })
+    }
+}
+
+#[cfg(test)]
+// The tests pass `&mut rng` to the generic constructors for readability.
+#[allow(clippy::needless_borrows_for_generic_args)]
+mod test {
+    use super::*;
+
+    use crate::attestation::sign_commitment_share;
+    use crate::ciphersuite::Rfc9591Ristretto255;
+    use crate::keygen::DHPrivateKey;
+    use crate::keygen::DkgSigner;
+    use crate::keygen::Participant;
+    use crate::keygen::{DistributedKeyGeneration, RoundOne};
+    use crate::precomputation::{
+        generate_commitment_share_lists, generate_commitment_share_lists_with,
+        PublicCommitmentShareList, SecretCommitmentShareList,
+    };
+    use crate::test_utils::run_dkg;
+
+    use curve25519_dalek::constants::EIGHT_TORSION;
+    use curve25519_dalek::traits::Identity;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn signing_and_verification_single_party() {
+        let params = Parameters { n: 1, t: 1 };
+        let mut rng = OsRng;
+
+        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+
+        p1.proof_of_secret_key
+            .verify(&p1.index, p1.public_key().unwrap(), "Φ")
             .unwrap();
-        let p2_partial = p2_sk
+
+        let participants: Vec<Participant> = vec![p1.clone()];
+        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p1_my_encrypted_secret_shares =
+            p1_state.their_encrypted_secret_shares().unwrap().clone();
+        let p1_state = p1_state
+            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+        let result = p1_state.finish();
+
+        assert!(result.is_ok());
+
+        let (group_key, p1_sk) = result.unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let p1_partial = p1_sk
             .sign(
                 &message_hash,
                 &group_key,
-                &mut p2_secret_comshares,
+                &mut p1_secret_comshares,
                 0,
                 signers,
             )
             .unwrap();
 
-        aggregator.include_partial_signature(p1_partial);
-        aggregator.include_partial_signature(p2_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let signing_result = aggregator.aggregate();
@@ -1394,988 +4156,4368 @@ mod test {
     }
 
     #[test]
-    fn signing_and_verification_static_2_out_of_3() {
-        #[allow(clippy::type_complexity)]
-        fn do_keygen() -> Result<
-            (
-                Parameters,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                GroupKey,
-            ),
-            (),
-        > {
-            let params = Parameters { n: 3, t: 2 };
-            let mut rng = OsRng;
+    fn signing_and_verification_1_out_of_1() {
+        let params = Parameters { n: 1, t: 1 };
+        let mut rng = OsRng;
 
-            let (dealer1, dealer1coeffs, dealer1_dh_sk) =
-                Participant::new_dealer(&params, 1, "Φ", &mut rng);
-            let (dealer2, dealer2coeffs, dealer2_dh_sk) =
-                Participant::new_dealer(&params, 2, "Φ", &mut rng);
-            let (dealer3, dealer3coeffs, dealer3_dh_sk) =
-                Participant::new_dealer(&params, 3, "Φ", &mut rng);
+        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
 
-            dealer1
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&dealer1.index, dealer1.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
-            dealer2
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&dealer2.index, dealer2.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
-            dealer3
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&dealer3.index, dealer3.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
+        let participants: Vec<Participant> = vec![p1.clone()];
+        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p1_my_encrypted_secret_shares =
+            p1_state.their_encrypted_secret_shares().unwrap().clone();
+        let p1_state = p1_state
+            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
 
-            let dealers: Vec<Participant> = vec![dealer1.clone(), dealer2.clone(), dealer3.clone()];
-            let (dealer1_state, _participant_lists) =
-                DistributedKeyGeneration::<RoundOne>::new_initial(
-                    &params,
-                    &dealer1_dh_sk,
-                    &dealer1.index,
-                    &dealer1coeffs,
-                    &dealers,
-                    "Φ",
-                    &mut rng,
-                )
-                .or(Err(()))?;
-            let dealer1_their_encrypted_secret_shares =
-                dealer1_state.their_encrypted_secret_shares().or(Err(()))?;
+        let (group_key, p1_sk) = p1_state.finish().unwrap();
 
-            let (dealer2_state, _participant_lists) =
-                DistributedKeyGeneration::<RoundOne>::new_initial(
-                    &params,
-                    &dealer2_dh_sk,
-                    &dealer2.index,
-                    &dealer2coeffs,
-                    &dealers,
-                    "Φ",
-                    &mut rng,
-                )
-                .or(Err(()))?;
-            let dealer2_their_encrypted_secret_shares =
-                dealer2_state.their_encrypted_secret_shares().or(Err(()))?;
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
 
-            let (dealer3_state, _participant_lists) =
-                DistributedKeyGeneration::<RoundOne>::new_initial(
-                    &params,
-                    &dealer3_dh_sk,
-                    &dealer3.index,
-                    &dealer3coeffs,
-                    &dealers,
-                    "Φ",
-                    &mut rng,
-                )
-                .or(Err(()))?;
-            let dealer3_their_encrypted_secret_shares =
-                dealer3_state.their_encrypted_secret_shares().or(Err(()))?;
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
 
-            let dealer1_my_encrypted_secret_shares = vec![
-                dealer1_their_encrypted_secret_shares[0].clone(),
-                dealer2_their_encrypted_secret_shares[0].clone(),
-                dealer3_their_encrypted_secret_shares[0].clone(),
-            ];
-            let dealer2_my_encrypted_secret_shares = vec![
-                dealer1_their_encrypted_secret_shares[1].clone(),
-                dealer2_their_encrypted_secret_shares[1].clone(),
-                dealer3_their_encrypted_secret_shares[1].clone(),
-            ];
-            let dealer3_my_encrypted_secret_shares = vec![
-                dealer1_their_encrypted_secret_shares[2].clone(),
-                dealer2_their_encrypted_secret_shares[2].clone(),
-                dealer3_their_encrypted_secret_shares[2].clone(),
-            ];
-
-            let dealer1_state = dealer1_state
-                .to_round_two(dealer1_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let dealer2_state = dealer2_state
-                .to_round_two(dealer2_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let dealer3_state = dealer3_state
-                .to_round_two(dealer3_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-
-            let (dealer1_group_key, dealer1_secret_key) = dealer1_state.finish().or(Err(()))?;
-            let (dealer2_group_key, dealer2_secret_key) = dealer2_state.finish().or(Err(()))?;
-            let (dealer3_group_key, dealer3_secret_key) = dealer3_state.finish().or(Err(()))?;
-
-            assert!(dealer1_group_key.0.compress() == dealer2_group_key.0.compress());
-            assert!(dealer2_group_key.0.compress() == dealer3_group_key.0.compress());
-
-            let (signer1, signer1_dh_sk) = Participant::new_signer(&params, 1, "Φ", &mut rng);
-            let (signer2, signer2_dh_sk) = Participant::new_signer(&params, 2, "Φ", &mut rng);
-            let (signer3, signer3_dh_sk) = Participant::new_signer(&params, 3, "Φ", &mut rng);
-
-            let signers: Vec<Participant> = vec![signer1.clone(), signer2.clone(), signer3.clone()];
-
-            let (dealer1_for_signers, dealer1_encrypted_shares_for_signers, _participant_lists) =
-                Participant::reshare(&params, dealer1_secret_key.clone(), &signers, "Φ", &mut rng)
-                    .map_err(|_| ())?;
-            let (dealer2_for_signers, dealer2_encrypted_shares_for_signers, _participant_lists) =
-                Participant::reshare(&params, dealer2_secret_key.clone(), &signers, "Φ", &mut rng)
-                    .map_err(|_| ())?;
-            let (dealer3_for_signers, dealer3_encrypted_shares_for_signers, _participant_lists) =
-                Participant::reshare(&params, dealer3_secret_key.clone(), &signers, "Φ", &mut rng)
-                    .map_err(|_| ())?;
-
-            let dealers: Vec<Participant> = vec![
-                dealer1_for_signers,
-                dealer2_for_signers,
-                dealer3_for_signers,
-            ];
-            let (signer1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params,
-                &signer1_dh_sk,
-                &signer1.index,
-                &dealers,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
-
-            let (signer2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params,
-                &signer2_dh_sk,
-                &signer2.index,
-                &dealers,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
-
-            let (signer3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params,
-                &signer3_dh_sk,
-                &signer3.index,
-                &dealers,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
-
-            let signer1_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[0].clone(),
-                dealer2_encrypted_shares_for_signers[0].clone(),
-                dealer3_encrypted_shares_for_signers[0].clone(),
-            ];
-            let signer2_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[1].clone(),
-                dealer2_encrypted_shares_for_signers[1].clone(),
-                dealer3_encrypted_shares_for_signers[1].clone(),
-            ];
-            let signer3_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[2].clone(),
-                dealer2_encrypted_shares_for_signers[2].clone(),
-                dealer3_encrypted_shares_for_signers[2].clone(),
-            ];
-
-            let signer1_state = signer1_state
-                .to_round_two(signer1_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let signer2_state = signer2_state
-                .to_round_two(signer2_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let signer3_state = signer3_state
-                .to_round_two(signer3_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-
-            let (signer1_group_key, signer1_secret_key) = signer1_state.finish().or(Err(()))?;
-            let (signer2_group_key, signer2_secret_key) = signer2_state.finish().or(Err(()))?;
-            let (signer3_group_key, signer3_secret_key) = signer3_state.finish().or(Err(()))?;
-
-            assert!(signer1_group_key.0.compress() == signer2_group_key.0.compress());
-            assert!(signer2_group_key.0.compress() == signer3_group_key.0.compress());
-
-            assert!(signer1_group_key.0.compress() == dealer1_group_key.0.compress());
-
-            Ok((
-                params,
-                dealer1_secret_key,
-                dealer2_secret_key,
-                dealer3_secret_key,
-                signer1_secret_key,
-                signer2_secret_key,
-                signer3_secret_key,
-                dealer1_group_key,
-            ))
-        }
-        let keygen_protocol = do_keygen();
-
-        assert!(keygen_protocol.is_ok());
-
-        let (params, d1_sk, d2_sk, _d3_sk, s1_sk, s2_sk, _s3_sk, group_key) =
-            keygen_protocol.unwrap();
-
-        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (d1_public_comshares, mut d1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
-        let (d2_public_comshares, mut d2_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 2, 1);
-
-        let mut aggregator =
-            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
-
-        aggregator.include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into());
-        aggregator.include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = compute_message_hash(&context[..], &message[..]);
 
-        let d1_partial = d1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut d1_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
-        let d2_partial = d2_sk
+        let p1_partial = p1_sk
             .sign(
                 &message_hash,
                 &group_key,
-                &mut d2_secret_comshares,
+                &mut p1_secret_comshares,
                 0,
                 signers,
             )
             .unwrap();
 
-        aggregator.include_partial_signature(d1_partial);
-        aggregator.include_partial_signature(d2_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
-        let signing_result = aggregator.aggregate();
+        let threshold_signature = aggregator.aggregate().unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
 
-        assert!(signing_result.is_ok());
+        assert!(verification_result.is_ok());
+    }
 
-        let threshold_signature = signing_result.unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+    #[test]
+    fn signing_and_verification_1_out_of_2() {
+        let params = Parameters { n: 2, t: 1 };
+        let mut rng = OsRng;
 
-        println!("Dealer's signing session: {:?}", verification_result);
+        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+        let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
+
+        let participants: Vec<Participant> = vec![p1.clone(), p2.clone()];
+        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p1_their_encrypted_secret_shares = p1_state.their_encrypted_secret_shares().unwrap();
+
+        let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p2_dh_sk,
+            &p2.index,
+            &p2coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p2_their_encrypted_secret_shares = p2_state.their_encrypted_secret_shares().unwrap();
+
+        let p1_my_encrypted_secret_shares = vec![
+            p1_their_encrypted_secret_shares[0].clone(),
+            p2_their_encrypted_secret_shares[0].clone(),
+        ];
+        let p2_my_encrypted_secret_shares = vec![
+            p1_their_encrypted_secret_shares[1].clone(),
+            p2_their_encrypted_secret_shares[1].clone(),
+        ];
+
+        let p1_state = p1_state
+            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+        let p2_state = p2_state
+            .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+
+        let (group_key, p1_sk) = p1_state.finish().unwrap();
+        let (_, _p2_sk) = p2_state.finish().unwrap();
 
         let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
         let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (s1_public_comshares, mut s1_secret_comshares) =
+        let (p1_public_comshares, mut p1_secret_comshares) =
             generate_commitment_share_lists(&mut OsRng, 1, 1);
-        let (s2_public_comshares, mut s2_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 2, 1);
 
         let mut aggregator =
             SignatureAggregator::new(params, group_key, &context[..], &message[..]);
 
-        aggregator.include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into());
-        aggregator.include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into());
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = compute_message_hash(&context[..], &message[..]);
 
-        let s1_partial = s1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut s1_secret_comshares,
-                0,
-                signers,
-            )
-            .unwrap();
-        let s2_partial = s2_sk
+        let p1_partial = p1_sk
             .sign(
                 &message_hash,
                 &group_key,
-                &mut s2_secret_comshares,
+                &mut p1_secret_comshares,
                 0,
                 signers,
             )
             .unwrap();
 
-        aggregator.include_partial_signature(s1_partial);
-        aggregator.include_partial_signature(s2_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
-        let signing_result = aggregator.aggregate();
-
-        assert!(signing_result.is_ok());
-
-        let threshold_signature = signing_result.unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
         let verification_result = threshold_signature.verify(&group_key, &message_hash);
 
-        println!("Signers's signing session: {:?}", verification_result);
-
         assert!(verification_result.is_ok());
     }
 
     #[test]
-    fn signing_and_verification_static_2_out_of_3_into_3_out_of_5() {
-        #[allow(clippy::type_complexity)]
-        fn do_keygen() -> Result<
-            (
-                Parameters,
-                Parameters,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                SecretKey,
-                GroupKey,
-            ),
-            (),
+    fn signing_and_verification_3_out_of_5() {
+        let params = Parameters { n: 5, t: 3 };
+        let mut rng = OsRng;
+
+        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+        let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
+        let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
+        let (p4, p4coeffs, p4_dh_sk) = Participant::new_dealer(&params, 4, "Φ", &mut rng);
+        let (p5, p5coeffs, p5_dh_sk) = Participant::new_dealer(&params, 5, "Φ", &mut rng);
+
+        let participants: Vec<Participant> =
+            vec![p1.clone(), p2.clone(), p3.clone(), p4.clone(), p5.clone()];
+        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p1_their_encrypted_secret_shares = p1_state.their_encrypted_secret_shares().unwrap();
+
+        let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p2_dh_sk,
+            &p2.index,
+            &p2coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p2_their_encrypted_secret_shares = p2_state.their_encrypted_secret_shares().unwrap();
+
+        let (p3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p3_dh_sk,
+            &p3.index,
+            &p3coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p3_their_encrypted_secret_shares = p3_state.their_encrypted_secret_shares().unwrap();
+
+        let (p4_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p4_dh_sk,
+            &p4.index,
+            &p4coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p4_their_encrypted_secret_shares = p4_state.their_encrypted_secret_shares().unwrap();
+
+        let (p5_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p5_dh_sk,
+            &p5.index,
+            &p5coeffs,
+            &participants,
+            "Φ",
+            &mut rng,
+        )
+        .unwrap();
+        let p5_their_encrypted_secret_shares = p5_state.their_encrypted_secret_shares().unwrap();
+
+        let p1_my_encrypted_secret_shares = vec![
+            p1_their_encrypted_secret_shares[0].clone(),
+            p2_their_encrypted_secret_shares[0].clone(),
+            p3_their_encrypted_secret_shares[0].clone(),
+            p4_their_encrypted_secret_shares[0].clone(),
+            p5_their_encrypted_secret_shares[0].clone(),
+        ];
+
+        let p2_my_encrypted_secret_shares = vec![
+            p1_their_encrypted_secret_shares[1].clone(),
+            p2_their_encrypted_secret_shares[1].clone(),
+            p3_their_encrypted_secret_shares[1].clone(),
+            p4_their_encrypted_secret_shares[1].clone(),
+            p5_their_encrypted_secret_shares[1].clone(),
+        ];
+
+        let p3_my_encrypted_secret_shares = vec![
+            p1_their_encrypted_secret_shares[2].clone(),
+            p2_their_encrypted_secret_shares[2].clone(),
+            p3_their_encrypted_secret_shares[2].clone(),
+            p4_their_encrypted_secret_shares[2].clone(),
+            p5_their_encrypted_secret_shares[2].clone(),
+        ];
+
+        let p4_my_encrypted_secret_shares = vec![
+            p1_their_encrypted_secret_shares[3].clone(),
+            p2_their_encrypted_secret_shares[3].clone(),
+            p3_their_encrypted_secret_shares[3].clone(),
+            p4_their_encrypted_secret_shares[3].clone(),
+            p5_their_encrypted_secret_shares[3].clone(),
+        ];
+
+        let p5_my_encrypted_secret_shares = vec![
+            p1_their_encrypted_secret_shares[4].clone(),
+            p2_their_encrypted_secret_shares[4].clone(),
+            p3_their_encrypted_secret_shares[4].clone(),
+            p4_their_encrypted_secret_shares[4].clone(),
+            p5_their_encrypted_secret_shares[4].clone(),
+        ];
+
+        let p1_state = p1_state
+            .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+        let p2_state = p2_state
+            .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+        let p3_state = p3_state
+            .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+        let p4_state = p4_state
+            .to_round_two(p4_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+        let p5_state = p5_state
+            .to_round_two(p5_my_encrypted_secret_shares, &mut rng)
+            .unwrap();
+
+        let (group_key, p1_sk) = p1_state.finish().unwrap();
+        let (_, _) = p2_state.finish().unwrap();
+        let (_, p3_sk) = p3_state.finish().unwrap();
+        let (_, p4_sk) = p4_state.finish().unwrap();
+        let (_, _) = p5_state.finish().unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p3_public_comshares, mut p3_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 3, 1);
+        let (p4_public_comshares, mut p4_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 4, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let p1_partial = p1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let p3_partial = p3_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p3_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let p4_partial = p4_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p4_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p3_partial).unwrap();
+        aggregator.include_partial_signature(p4_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+
+        assert!(verification_result.is_ok());
+    }
+
+    #[test]
+    fn signing_and_verification_2_out_of_3() {
+        fn do_keygen() -> Result<(Parameters, SecretKey, SecretKey, SecretKey, GroupKey), ()> {
+            let params = Parameters { n: 3, t: 2 };
+            let mut rng = OsRng;
+
+            let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+            let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
+            let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
+
+            p2.proof_of_secret_key
+                .verify(&p2.index, p2.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+            p3.proof_of_secret_key
+                .verify(&p3.index, p3.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+
+            let participants: Vec<Participant> = vec![p1.clone(), p2.clone(), p3.clone()];
+            let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &p1_dh_sk,
+                &p1.index,
+                &p1coeffs,
+                &participants,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+            let p1_their_encrypted_secret_shares =
+                p1_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &p2_dh_sk,
+                &p2.index,
+                &p2coeffs,
+                &participants,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+            let p2_their_encrypted_secret_shares =
+                p2_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (p3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &p3_dh_sk,
+                &p3.index,
+                &p3coeffs,
+                &participants,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+            let p3_their_encrypted_secret_shares =
+                p3_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let p1_my_encrypted_secret_shares = vec![
+                p1_their_encrypted_secret_shares[0].clone(),
+                p2_their_encrypted_secret_shares[0].clone(),
+                p3_their_encrypted_secret_shares[0].clone(),
+            ];
+            let p2_my_encrypted_secret_shares = vec![
+                p1_their_encrypted_secret_shares[1].clone(),
+                p2_their_encrypted_secret_shares[1].clone(),
+                p3_their_encrypted_secret_shares[1].clone(),
+            ];
+            let p3_my_encrypted_secret_shares = vec![
+                p1_their_encrypted_secret_shares[2].clone(),
+                p2_their_encrypted_secret_shares[2].clone(),
+                p3_their_encrypted_secret_shares[2].clone(),
+            ];
+
+            let p1_state = p1_state
+                .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let p2_state = p2_state
+                .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let p3_state = p3_state
+                .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+
+            let (p1_group_key, p1_secret_key) = p1_state.finish().or(Err(()))?;
+            let (p2_group_key, p2_secret_key) = p2_state.finish().or(Err(()))?;
+            let (p3_group_key, p3_secret_key) = p3_state.finish().or(Err(()))?;
+
+            assert!(p1_group_key.0.compress() == p2_group_key.0.compress());
+            assert!(p2_group_key.0.compress() == p3_group_key.0.compress());
+
+            Ok((
+                params,
+                p1_secret_key,
+                p2_secret_key,
+                p3_secret_key,
+                p1_group_key,
+            ))
+        }
+        let keygen_protocol = do_keygen();
+
+        assert!(keygen_protocol.is_ok());
+
+        let (params, p1_sk, p2_sk, _p3_sk, group_key) = keygen_protocol.unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let p1_partial = p1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let p2_partial = p2_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p2_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let signing_result = aggregator.aggregate();
+
+        assert!(signing_result.is_ok());
+
+        let threshold_signature = signing_result.unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+
+        println!("{:?}", verification_result);
+
+        assert!(verification_result.is_ok());
+    }
+
+    #[test]
+    fn signing_and_verification_static_2_out_of_3() {
+        #[allow(clippy::type_complexity)]
+        fn do_keygen() -> Result<
+            (
+                Parameters,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                GroupKey,
+            ),
+            (),
+        > {
+            let params = Parameters { n: 3, t: 2 };
+            let mut rng = OsRng;
+
+            let (dealer1, dealer1coeffs, dealer1_dh_sk) =
+                Participant::new_dealer(&params, 1, "Φ", &mut rng);
+            let (dealer2, dealer2coeffs, dealer2_dh_sk) =
+                Participant::new_dealer(&params, 2, "Φ", &mut rng);
+            let (dealer3, dealer3coeffs, dealer3_dh_sk) =
+                Participant::new_dealer(&params, 3, "Φ", &mut rng);
+
+            dealer1
+                .proof_of_secret_key
+                .verify(&dealer1.index, dealer1.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+            dealer2
+                .proof_of_secret_key
+                .verify(&dealer2.index, dealer2.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+            dealer3
+                .proof_of_secret_key
+                .verify(&dealer3.index, dealer3.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+
+            let dealers: Vec<Participant> = vec![dealer1.clone(), dealer2.clone(), dealer3.clone()];
+            let (dealer1_state, _participant_lists) =
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    &dealer1_dh_sk,
+                    &dealer1.index,
+                    &dealer1coeffs,
+                    &dealers,
+                    "Φ",
+                    &mut rng,
+                )
+                .or(Err(()))?;
+            let dealer1_their_encrypted_secret_shares =
+                dealer1_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (dealer2_state, _participant_lists) =
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    &dealer2_dh_sk,
+                    &dealer2.index,
+                    &dealer2coeffs,
+                    &dealers,
+                    "Φ",
+                    &mut rng,
+                )
+                .or(Err(()))?;
+            let dealer2_their_encrypted_secret_shares =
+                dealer2_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (dealer3_state, _participant_lists) =
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params,
+                    &dealer3_dh_sk,
+                    &dealer3.index,
+                    &dealer3coeffs,
+                    &dealers,
+                    "Φ",
+                    &mut rng,
+                )
+                .or(Err(()))?;
+            let dealer3_their_encrypted_secret_shares =
+                dealer3_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let dealer1_my_encrypted_secret_shares = vec![
+                dealer1_their_encrypted_secret_shares[0].clone(),
+                dealer2_their_encrypted_secret_shares[0].clone(),
+                dealer3_their_encrypted_secret_shares[0].clone(),
+            ];
+            let dealer2_my_encrypted_secret_shares = vec![
+                dealer1_their_encrypted_secret_shares[1].clone(),
+                dealer2_their_encrypted_secret_shares[1].clone(),
+                dealer3_their_encrypted_secret_shares[1].clone(),
+            ];
+            let dealer3_my_encrypted_secret_shares = vec![
+                dealer1_their_encrypted_secret_shares[2].clone(),
+                dealer2_their_encrypted_secret_shares[2].clone(),
+                dealer3_their_encrypted_secret_shares[2].clone(),
+            ];
+
+            let dealer1_state = dealer1_state
+                .to_round_two(dealer1_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let dealer2_state = dealer2_state
+                .to_round_two(dealer2_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let dealer3_state = dealer3_state
+                .to_round_two(dealer3_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+
+            let (dealer1_group_key, dealer1_secret_key) = dealer1_state.finish().or(Err(()))?;
+            let (dealer2_group_key, dealer2_secret_key) = dealer2_state.finish().or(Err(()))?;
+            let (dealer3_group_key, dealer3_secret_key) = dealer3_state.finish().or(Err(()))?;
+
+            assert!(dealer1_group_key.0.compress() == dealer2_group_key.0.compress());
+            assert!(dealer2_group_key.0.compress() == dealer3_group_key.0.compress());
+
+            let (signer1, signer1_dh_sk) = DkgSigner::new(&params, 1, "Φ", rng);
+            let (signer2, signer2_dh_sk) = DkgSigner::new(&params, 2, "Φ", rng);
+            let (signer3, signer3_dh_sk) = DkgSigner::new(&params, 3, "Φ", rng);
+
+            let signers: Vec<DkgSigner> = vec![signer1.clone(), signer2.clone(), signer3.clone()];
+
+            let (dealer1_for_signers, dealer1_encrypted_shares_for_signers, _participant_lists) =
+                Participant::reshare(&params, dealer1_secret_key.duplicate(), &signers, "Φ", rng)
+                    .map_err(|_| ())?;
+            let (dealer2_for_signers, dealer2_encrypted_shares_for_signers, _participant_lists) =
+                Participant::reshare(&params, dealer2_secret_key.duplicate(), &signers, "Φ", rng)
+                    .map_err(|_| ())?;
+            let (dealer3_for_signers, dealer3_encrypted_shares_for_signers, _participant_lists) =
+                Participant::reshare(&params, dealer3_secret_key.duplicate(), &signers, "Φ", rng)
+                    .map_err(|_| ())?;
+
+            let dealers: Vec<Participant> = vec![
+                dealer1_for_signers,
+                dealer2_for_signers,
+                dealer3_for_signers,
+            ];
+            let (signer1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params,
+                &signer1_dh_sk,
+                &signer1.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let (signer2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params,
+                &signer2_dh_sk,
+                &signer2.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let (signer3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params,
+                &signer3_dh_sk,
+                &signer3.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let signer1_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[0].clone(),
+                dealer2_encrypted_shares_for_signers[0].clone(),
+                dealer3_encrypted_shares_for_signers[0].clone(),
+            ];
+            let signer2_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[1].clone(),
+                dealer2_encrypted_shares_for_signers[1].clone(),
+                dealer3_encrypted_shares_for_signers[1].clone(),
+            ];
+            let signer3_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[2].clone(),
+                dealer2_encrypted_shares_for_signers[2].clone(),
+                dealer3_encrypted_shares_for_signers[2].clone(),
+            ];
+
+            let signer1_state = signer1_state
+                .to_round_two(signer1_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let signer2_state = signer2_state
+                .to_round_two(signer2_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let signer3_state = signer3_state
+                .to_round_two(signer3_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+
+            let (signer1_group_key, signer1_secret_key) = signer1_state.finish().or(Err(()))?;
+            let (signer2_group_key, signer2_secret_key) = signer2_state.finish().or(Err(()))?;
+            let (signer3_group_key, signer3_secret_key) = signer3_state.finish().or(Err(()))?;
+
+            assert!(signer1_group_key.0.compress() == signer2_group_key.0.compress());
+            assert!(signer2_group_key.0.compress() == signer3_group_key.0.compress());
+
+            assert!(signer1_group_key.0.compress() == dealer1_group_key.0.compress());
+
+            Ok((
+                params,
+                dealer1_secret_key,
+                dealer2_secret_key,
+                dealer3_secret_key,
+                signer1_secret_key,
+                signer2_secret_key,
+                signer3_secret_key,
+                dealer1_group_key,
+            ))
+        }
+        let keygen_protocol = do_keygen();
+
+        assert!(keygen_protocol.is_ok());
+
+        let (params, d1_sk, d2_sk, _d3_sk, s1_sk, s2_sk, _s3_sk, group_key) =
+            keygen_protocol.unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (d1_public_comshares, mut d1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (d2_public_comshares, mut d2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let d1_partial = d1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut d1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let d2_partial = d2_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut d2_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+
+        aggregator.include_partial_signature(d1_partial).unwrap();
+        aggregator.include_partial_signature(d2_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let signing_result = aggregator.aggregate();
+
+        assert!(signing_result.is_ok());
+
+        let threshold_signature = signing_result.unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+
+        println!("Dealer's signing session: {:?}", verification_result);
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (s1_public_comshares, mut s1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (s2_public_comshares, mut s2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let s1_partial = s1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut s1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let s2_partial = s2_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut s2_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+
+        aggregator.include_partial_signature(s1_partial).unwrap();
+        aggregator.include_partial_signature(s2_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let signing_result = aggregator.aggregate();
+
+        assert!(signing_result.is_ok());
+
+        let threshold_signature = signing_result.unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+
+        println!("Signers's signing session: {:?}", verification_result);
+
+        assert!(verification_result.is_ok());
+    }
+
+    #[test]
+    fn signing_and_verification_static_2_out_of_3_into_3_out_of_5() {
+        #[allow(clippy::type_complexity)]
+        fn do_keygen() -> Result<
+            (
+                Parameters,
+                Parameters,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                SecretKey,
+                GroupKey,
+            ),
+            (),
         > {
             let params_dealers = Parameters { n: 3, t: 2 };
             let mut rng = OsRng;
 
-            let (dealer1, dealer1coeffs, dealer1_dh_sk) =
-                Participant::new_dealer(&params_dealers, 1, "Φ", &mut rng);
-            let (dealer2, dealer2coeffs, dealer2_dh_sk) =
-                Participant::new_dealer(&params_dealers, 2, "Φ", &mut rng);
-            let (dealer3, dealer3coeffs, dealer3_dh_sk) =
-                Participant::new_dealer(&params_dealers, 3, "Φ", &mut rng);
+            let (dealer1, dealer1coeffs, dealer1_dh_sk) =
+                Participant::new_dealer(&params_dealers, 1, "Φ", &mut rng);
+            let (dealer2, dealer2coeffs, dealer2_dh_sk) =
+                Participant::new_dealer(&params_dealers, 2, "Φ", &mut rng);
+            let (dealer3, dealer3coeffs, dealer3_dh_sk) =
+                Participant::new_dealer(&params_dealers, 3, "Φ", &mut rng);
+
+            dealer1
+                .proof_of_secret_key
+                .verify(&dealer1.index, dealer1.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+            dealer2
+                .proof_of_secret_key
+                .verify(&dealer2.index, dealer2.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+            dealer3
+                .proof_of_secret_key
+                .verify(&dealer3.index, dealer3.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+
+            let dealers: Vec<Participant> = vec![dealer1.clone(), dealer2.clone(), dealer3.clone()];
+            let (dealer1_state, _participant_lists) =
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params_dealers,
+                    &dealer1_dh_sk,
+                    &dealer1.index,
+                    &dealer1coeffs,
+                    &dealers,
+                    "Φ",
+                    &mut rng,
+                )
+                .or(Err(()))?;
+            let dealer1_their_encrypted_secret_shares =
+                dealer1_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (dealer2_state, _participant_lists) =
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params_dealers,
+                    &dealer2_dh_sk,
+                    &dealer2.index,
+                    &dealer2coeffs,
+                    &dealers,
+                    "Φ",
+                    &mut rng,
+                )
+                .or(Err(()))?;
+            let dealer2_their_encrypted_secret_shares =
+                dealer2_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (dealer3_state, _participant_lists) =
+                DistributedKeyGeneration::<RoundOne>::new_initial(
+                    &params_dealers,
+                    &dealer3_dh_sk,
+                    &dealer3.index,
+                    &dealer3coeffs,
+                    &dealers,
+                    "Φ",
+                    &mut rng,
+                )
+                .or(Err(()))?;
+            let dealer3_their_encrypted_secret_shares =
+                dealer3_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let dealer1_my_encrypted_secret_shares = vec![
+                dealer1_their_encrypted_secret_shares[0].clone(),
+                dealer2_their_encrypted_secret_shares[0].clone(),
+                dealer3_their_encrypted_secret_shares[0].clone(),
+            ];
+            let dealer2_my_encrypted_secret_shares = vec![
+                dealer1_their_encrypted_secret_shares[1].clone(),
+                dealer2_their_encrypted_secret_shares[1].clone(),
+                dealer3_their_encrypted_secret_shares[1].clone(),
+            ];
+            let dealer3_my_encrypted_secret_shares = vec![
+                dealer1_their_encrypted_secret_shares[2].clone(),
+                dealer2_their_encrypted_secret_shares[2].clone(),
+                dealer3_their_encrypted_secret_shares[2].clone(),
+            ];
+
+            let dealer1_state = dealer1_state
+                .to_round_two(dealer1_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let dealer2_state = dealer2_state
+                .to_round_two(dealer2_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let dealer3_state = dealer3_state
+                .to_round_two(dealer3_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+
+            let (dealer1_group_key, dealer1_secret_key) = dealer1_state.finish().or(Err(()))?;
+            let (dealer2_group_key, dealer2_secret_key) = dealer2_state.finish().or(Err(()))?;
+            let (dealer3_group_key, dealer3_secret_key) = dealer3_state.finish().or(Err(()))?;
+
+            assert!(dealer1_group_key.0.compress() == dealer2_group_key.0.compress());
+            assert!(dealer2_group_key.0.compress() == dealer3_group_key.0.compress());
+
+            let params_signers = Parameters { n: 5, t: 3 };
+            let (signer1, signer1_dh_sk) = DkgSigner::new(&params_signers, 1, "Φ", rng);
+            let (signer2, signer2_dh_sk) = DkgSigner::new(&params_signers, 2, "Φ", rng);
+            let (signer3, signer3_dh_sk) = DkgSigner::new(&params_signers, 3, "Φ", rng);
+            let (signer4, signer4_dh_sk) = DkgSigner::new(&params_signers, 4, "Φ", rng);
+            let (signer5, signer5_dh_sk) = DkgSigner::new(&params_signers, 5, "Φ", rng);
+
+            let signers: Vec<DkgSigner> = vec![
+                signer1.clone(),
+                signer2.clone(),
+                signer3.clone(),
+                signer4.clone(),
+                signer5.clone(),
+            ];
+
+            let (dealer1_for_signers, dealer1_encrypted_shares_for_signers, _participant_lists) =
+                Participant::reshare(
+                    &params_signers,
+                    dealer1_secret_key.duplicate(),
+                    &signers,
+                    "Φ",
+                    &mut rng,
+                )
+                .map_err(|_| ())?;
+            let (dealer2_for_signers, dealer2_encrypted_shares_for_signers, _participant_lists) =
+                Participant::reshare(
+                    &params_signers,
+                    dealer2_secret_key.duplicate(),
+                    &signers,
+                    "Φ",
+                    &mut rng,
+                )
+                .map_err(|_| ())?;
+            let (dealer3_for_signers, dealer3_encrypted_shares_for_signers, _participant_lists) =
+                Participant::reshare(
+                    &params_signers,
+                    dealer3_secret_key.duplicate(),
+                    &signers,
+                    "Φ",
+                    &mut rng,
+                )
+                .map_err(|_| ())?;
+
+            let dealers: Vec<Participant> = vec![
+                dealer1_for_signers,
+                dealer2_for_signers,
+                dealer3_for_signers,
+            ];
+            let (signer1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params_dealers,
+                &signer1_dh_sk,
+                &signer1.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let (signer2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params_dealers,
+                &signer2_dh_sk,
+                &signer2.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let (signer3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params_dealers,
+                &signer3_dh_sk,
+                &signer3.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let (signer4_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params_dealers,
+                &signer4_dh_sk,
+                &signer4.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let (signer5_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
+                &params_dealers,
+                &signer5_dh_sk,
+                &signer5.index,
+                &dealers,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+
+            let signer1_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[0].clone(),
+                dealer2_encrypted_shares_for_signers[0].clone(),
+                dealer3_encrypted_shares_for_signers[0].clone(),
+            ];
+            let signer2_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[1].clone(),
+                dealer2_encrypted_shares_for_signers[1].clone(),
+                dealer3_encrypted_shares_for_signers[1].clone(),
+            ];
+            let signer3_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[2].clone(),
+                dealer2_encrypted_shares_for_signers[2].clone(),
+                dealer3_encrypted_shares_for_signers[2].clone(),
+            ];
+            let signer4_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[3].clone(),
+                dealer2_encrypted_shares_for_signers[3].clone(),
+                dealer3_encrypted_shares_for_signers[3].clone(),
+            ];
+            let signer5_my_encrypted_secret_shares = vec![
+                dealer1_encrypted_shares_for_signers[4].clone(),
+                dealer2_encrypted_shares_for_signers[4].clone(),
+                dealer3_encrypted_shares_for_signers[4].clone(),
+            ];
+
+            let signer1_state = signer1_state
+                .to_round_two(signer1_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let signer2_state = signer2_state
+                .to_round_two(signer2_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let signer3_state = signer3_state
+                .to_round_two(signer3_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let signer4_state = signer4_state
+                .to_round_two(signer4_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let signer5_state = signer5_state
+                .to_round_two(signer5_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+
+            let (signer1_group_key, signer1_secret_key) = signer1_state.finish().or(Err(()))?;
+            let (signer2_group_key, signer2_secret_key) = signer2_state.finish().or(Err(()))?;
+            let (signer3_group_key, signer3_secret_key) = signer3_state.finish().or(Err(()))?;
+            let (signer4_group_key, signer4_secret_key) = signer4_state.finish().or(Err(()))?;
+            let (signer5_group_key, signer5_secret_key) = signer5_state.finish().or(Err(()))?;
+
+            assert!(signer1_group_key.0.compress() == signer2_group_key.0.compress());
+            assert!(signer2_group_key.0.compress() == signer3_group_key.0.compress());
+            assert!(signer3_group_key.0.compress() == signer4_group_key.0.compress());
+            assert!(signer4_group_key.0.compress() == signer5_group_key.0.compress());
+
+            assert!(signer1_group_key.0.compress() == dealer1_group_key.0.compress());
+
+            Ok((
+                params_dealers,
+                params_signers,
+                dealer1_secret_key,
+                dealer2_secret_key,
+                dealer3_secret_key,
+                signer1_secret_key,
+                signer2_secret_key,
+                signer3_secret_key,
+                signer4_secret_key,
+                signer5_secret_key,
+                dealer1_group_key,
+            ))
+        }
+        let keygen_protocol = do_keygen();
+
+        assert!(keygen_protocol.is_ok());
+
+        let (
+            d_params,
+            s_params,
+            d1_sk,
+            d2_sk,
+            _d3_sk,
+            s1_sk,
+            s2_sk,
+            s3_sk,
+            _s4_sk,
+            _s5_sk,
+            group_key,
+        ) = keygen_protocol.unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (d1_public_comshares, mut d1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (d2_public_comshares, mut d2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(d_params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let d1_partial = d1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut d1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let d2_partial = d2_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut d2_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+
+        aggregator.include_partial_signature(d1_partial).unwrap();
+        aggregator.include_partial_signature(d2_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let signing_result = aggregator.aggregate();
+
+        assert!(signing_result.is_ok());
+
+        let threshold_signature = signing_result.unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+
+        println!("Dealer's signing session: {:?}", verification_result);
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (s1_public_comshares, mut s1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (s2_public_comshares, mut s2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+        let (s3_public_comshares, mut s3_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 3, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(s_params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(3, s3_public_comshares.commitments[0], (&s3_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let s1_partial = s1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut s1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let s2_partial = s2_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut s2_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let s3_partial = s3_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut s3_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+
+        aggregator.include_partial_signature(s1_partial).unwrap();
+        aggregator.include_partial_signature(s2_partial).unwrap();
+        aggregator.include_partial_signature(s3_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let signing_result = aggregator.aggregate();
+
+        assert!(signing_result.is_ok());
+
+        let threshold_signature = signing_result.unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+
+        println!("Signers's signing session: {:?}", verification_result);
+
+        assert!(verification_result.is_ok());
+    }
+
+    #[test]
+    fn aggregator_get_signers() {
+        let params = Parameters { n: 3, t: 2 };
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+
+        let (p1_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator = SignatureAggregator::new(
+            params,
+            GroupKey::new(RistrettoPoint::identity()),
+            &context[..],
+            &message[..],
+        );
+
+        let p1_sk = SecretKey {
+            index: 1,
+            key: Scalar::random(&mut OsRng),
+        };
+        let p2_sk = SecretKey {
+            index: 2,
+            key: Scalar::random(&mut OsRng),
+        };
+
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+
+        // The signers should be deduplicated.
+        assert!(signers.len() == 2);
+
+        // The indices should match and be in sorted order.
+        assert!(signers[0].participant_index == 1);
+        assert!(signers[1].participant_index == 2);
+
+        // Participant 1 should have the correct precomputed shares.
+        assert!(signers[0].published_commitment_share.0 == p1_public_comshares.commitments[0].0);
+        assert!(signers[0].published_commitment_share.1 == p1_public_comshares.commitments[0].1);
+
+        // Same for participant 2.
+        assert!(signers[1].published_commitment_share.0 == p2_public_comshares.commitments[0].0);
+        assert!(signers[1].published_commitment_share.1 == p2_public_comshares.commitments[0].1);
+    }
+
+    #[test]
+    fn serialisation() {
+        fn do_keygen() -> Result<(Parameters, SecretKey, SecretKey, SecretKey, GroupKey), ()> {
+            let params = Parameters { n: 3, t: 2 };
+            let mut rng = OsRng;
+
+            let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+            let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
+            let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
+
+            p2.proof_of_secret_key
+                .verify(&p2.index, p2.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+            p3.proof_of_secret_key
+                .verify(&p3.index, p3.public_key().unwrap(), "Φ")
+                .or(Err(()))?;
+
+            let participants: Vec<Participant> = vec![p1.clone(), p2.clone(), p3.clone()];
+            let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &p1_dh_sk,
+                &p1.index,
+                &p1coeffs,
+                &participants,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+            let p1_their_encrypted_secret_shares =
+                p1_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &p2_dh_sk,
+                &p2.index,
+                &p2coeffs,
+                &participants,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+            let p2_their_encrypted_secret_shares =
+                p2_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let (p3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &params,
+                &p3_dh_sk,
+                &p3.index,
+                &p3coeffs,
+                &participants,
+                "Φ",
+                &mut rng,
+            )
+            .or(Err(()))?;
+            let p3_their_encrypted_secret_shares =
+                p3_state.their_encrypted_secret_shares().or(Err(()))?;
+
+            let p1_my_encrypted_secret_shares = vec![
+                p1_their_encrypted_secret_shares[0].clone(),
+                p2_their_encrypted_secret_shares[0].clone(),
+                p3_their_encrypted_secret_shares[0].clone(),
+            ];
+            let p2_my_encrypted_secret_shares = vec![
+                p1_their_encrypted_secret_shares[1].clone(),
+                p2_their_encrypted_secret_shares[1].clone(),
+                p3_their_encrypted_secret_shares[1].clone(),
+            ];
+            let p3_my_encrypted_secret_shares = vec![
+                p1_their_encrypted_secret_shares[2].clone(),
+                p2_their_encrypted_secret_shares[2].clone(),
+                p3_their_encrypted_secret_shares[2].clone(),
+            ];
+
+            let p1_state = p1_state
+                .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let p2_state = p2_state
+                .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+            let p3_state = p3_state
+                .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
+                .or(Err(()))?;
+
+            let (p1_group_key, p1_secret_key) = p1_state.finish().or(Err(()))?;
+            let (p2_group_key, p2_secret_key) = p2_state.finish().or(Err(()))?;
+            let (p3_group_key, p3_secret_key) = p3_state.finish().or(Err(()))?;
+
+            assert!(p1_group_key.0.compress() == p2_group_key.0.compress());
+            assert!(p2_group_key.0.compress() == p3_group_key.0.compress());
+
+            Ok((
+                params,
+                p1_secret_key,
+                p2_secret_key,
+                p3_secret_key,
+                p1_group_key,
+            ))
+        }
+        let keygen_protocol = do_keygen();
+
+        assert!(keygen_protocol.is_ok());
+
+        let (params, p1_sk, p2_sk, _p3_sk, group_key) = keygen_protocol.unwrap();
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+        aggregator
+            .include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let p1_partial = p1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let p2_partial = p2_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p2_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+
+        // Check serialisation
+
+        let bytes = p1_secret_comshares.to_bytes();
+        assert_eq!(
+            p1_secret_comshares,
+            SecretCommitmentShareList::from_bytes(&bytes).unwrap()
+        );
+
+        let bytes = p1_public_comshares.to_bytes();
+        assert_eq!(
+            p1_public_comshares,
+            PublicCommitmentShareList::from_bytes(&bytes).unwrap()
+        );
+
+        let bytes = p1_partial.to_bytes();
+        assert_eq!(
+            p1_partial,
+            PartialThresholdSignature::from_bytes(&bytes).unwrap()
+        );
+
+        // Continue signature
+
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let signing_result = aggregator.aggregate();
+
+        assert!(signing_result.is_ok());
+
+        let threshold_signature = signing_result.unwrap();
+        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+
+        println!("{:?}", verification_result);
+
+        assert!(verification_result.is_ok());
+
+        // Check serialisation
+
+        let bytes = threshold_signature.to_bytes();
+        assert_eq!(bytes.len(), ThresholdSignature::serialized_len());
+        assert_eq!(
+            threshold_signature,
+            ThresholdSignature::from_bytes(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn der_serialisation() {
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let signature = ThresholdSignature {
+                R: &Scalar::random(&mut rng) * &RISTRETTO_BASEPOINT_TABLE,
+                z: Scalar::random(&mut rng),
+            };
+
+            let der = signature.to_der();
+            assert_eq!(der[0], 0x30);
+            assert_eq!(der[1] as usize, der.len() - 2);
+            assert_eq!(signature, ThresholdSignature::from_der(&der).unwrap());
+        }
+
+        // Small and high-bit scalars exercise the minimal integer encoding.
+        for z in [
+            Scalar::zero(),
+            Scalar::one(),
+            Scalar::from(0x80u8),
+            -Scalar::one(),
+        ] {
+            let signature = ThresholdSignature {
+                R: RISTRETTO_BASEPOINT_TABLE.basepoint(),
+                z,
+            };
+
+            let der = signature.to_der();
+            assert_eq!(signature, ThresholdSignature::from_der(&der).unwrap());
+        }
+
+        let signature = ThresholdSignature {
+            R: RISTRETTO_BASEPOINT_TABLE.basepoint(),
+            z: Scalar::from(0x80u8),
+        };
+        let der = signature.to_der();
+        assert_eq!(&der[36..], &[0x02, 0x02, 0x00, 0x80]);
+
+        // invalid encodings
+        assert!(ThresholdSignature::from_der(&[]).is_err());
+        assert!(ThresholdSignature::from_der(&der[..der.len() - 1]).is_err());
+
+        let mut trailing = der.clone();
+        trailing.push(0x00);
+        assert!(ThresholdSignature::from_der(&trailing).is_err());
+
+        let mut wrong_tag = der.clone();
+        wrong_tag[0] = 0x31;
+        assert!(ThresholdSignature::from_der(&wrong_tag).is_err());
+
+        let mut wrong_point = der.clone();
+        wrong_point[4..36].copy_from_slice(&[255u8; 32]);
+        assert!(ThresholdSignature::from_der(&wrong_point).is_err());
+
+        // Negative integer.
+        let mut negative = der.clone();
+        negative[38..].copy_from_slice(&[0x80, 0x80]);
+        assert!(ThresholdSignature::from_der(&negative).is_err());
+
+        // Non-minimal integer.
+        let non_minimal = [&der[..37], &[0x03, 0x00, 0x00, 0x80]].concat();
+        let mut non_minimal = non_minimal;
+        non_minimal[1] += 1;
+        assert!(ThresholdSignature::from_der(&non_minimal).is_err());
+
+        // Non-canonical scalar.
+        let mut non_canonical = vec![0x30, 34 + 2 + 33, 0x04, 0x20];
+        non_canonical
+            .extend_from_slice(RISTRETTO_BASEPOINT_TABLE.basepoint().compress().as_bytes());
+        non_canonical.extend_from_slice(&[0x02, 33, 0x00]);
+        non_canonical.extend_from_slice(&[0xff; 32]);
+        assert!(ThresholdSignature::from_der(&non_canonical).is_err());
+    }
+
+    #[test]
+    fn partial_budget_limits_verification() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator.set_partial_budget(3);
+
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+
+        let signers = aggregator.get_signers().clone();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        // Hammer participant 1 with bogus partial signatures.
+        for i in 0..10u32 {
+            let bogus = PartialThresholdSignature {
+                index: 1,
+                z: Scalar::from(i),
+            };
+            let expected = if i < 3 {
+                SignatureError::InvalidPartialSignature(1)
+            } else {
+                SignatureError::PartialBudgetExceeded(1)
+            };
+            assert_eq!(aggregator.include_partial_signature(bogus), Err(expected));
+        }
+
+        let counters = aggregator.partial_signature_counters(1).unwrap();
+        assert_eq!(counters.received, 10);
+        assert_eq!(counters.verified, 3);
+        assert_eq!(counters.invalid, 3);
+        assert_eq!(counters.throttled, 7);
+
+        // Even a valid partial signature is now rejected for participant 1.
+        let p1_partial = secret_keys[0]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        assert_eq!(
+            aggregator.include_partial_signature(p1_partial),
+            Err(SignatureError::PartialBudgetExceeded(1))
+        );
+        assert_eq!(
+            aggregator.partial_signature_counters(1).unwrap().verified,
+            3
+        );
+
+        // Participant 2 is unaffected.
+        let p2_partial = secret_keys[1]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p2_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        assert!(aggregator.include_partial_signature(p2_partial).is_ok());
+
+        let counters = aggregator.partial_signature_counters(2).unwrap();
+        assert_eq!(counters.received, 1);
+        assert_eq!(counters.verified, 1);
+        assert_eq!(counters.invalid, 0);
+        assert_eq!(counters.throttled, 0);
+        assert!(aggregator.partial_signature_counters(3).is_none());
+
+        // Participant 1 never got a partial signature in.
+        assert_eq!(aggregator.get_remaining_signers().len(), 1);
+        assert!(aggregator.finalize().is_err());
+    }
+
+    #[test]
+    fn partial_budget_is_per_source() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+
+        let context = b"CONTEXT";
+        let message = b"MESSAGE";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator.set_partial_budget(2);
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+
+        // A spamming relay exhausts the budget of participant 1 through it.
+        for i in 0..5u32 {
+            let bogus = PartialThresholdSignature {
+                index: 1,
+                z: Scalar::from(i),
+            };
+            let expected = if i < 2 {
+                SignatureError::InvalidPartialSignature(1)
+            } else {
+                SignatureError::PartialBudgetExceeded(1)
+            };
+            assert_eq!(
+                aggregator.include_partial_signature_from(bogus, "spammer"),
+                Err(expected)
+            );
+        }
+
+        // The partial signature of participant 1 is still verified through
+        // another source.
+        let signers = aggregator.get_signers().clone();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+        let p1_partial = secret_keys[0]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        assert!(aggregator
+            .include_partial_signature_from(p1_partial, "honest")
+            .is_ok());
+
+        let spammer = aggregator
+            .partial_signature_counters_from(1, "spammer")
+            .unwrap();
+        assert_eq!(spammer.verified, 2);
+        assert_eq!(spammer.throttled, 3);
+        let honest = aggregator
+            .partial_signature_counters_from(1, "honest")
+            .unwrap();
+        assert_eq!(honest.received, 1);
+        assert_eq!(honest.verified, 1);
+        assert_eq!(honest.throttled, 0);
+
+        let total = aggregator.partial_signature_counters(1).unwrap();
+        assert_eq!(total.received, 6);
+        assert_eq!(total.verified, 3);
+        assert!(aggregator.partial_signature_counters(2).is_none());
+    }
+
+    #[test]
+    fn aggregator_rejects_duplicate_and_degenerate_commitments() {
+        let params = Parameters { n: 3, t: 2 };
+        let mut rng = OsRng;
+
+        let public_key =
+            |index: u32| IndividualPublicKey::new(index, RistrettoPoint::random(&mut OsRng));
+        let group_key = GroupKey::new(RistrettoPoint::random(&mut rng));
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+
+        let (p1_public_comshares, _) = generate_commitment_share_lists(&mut rng, 1, 1);
+        let (p2_public_comshares, _) = generate_commitment_share_lists(&mut rng, 2, 1);
+        let (p1_hiding, p1_binding) = p1_public_comshares.commitments[0];
+        let (p2_hiding, p2_binding) = p2_public_comshares.commitments[0];
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator
+            .include_signer(1, (p1_hiding, p1_binding), public_key(1))
+            .unwrap();
+
+        // Re-registering the same signer is harmless.
+        aggregator
+            .include_signer(1, (p1_hiding, p1_binding), public_key(1))
+            .unwrap();
+
+        // The same commitment share registered for another signer.
+        assert_eq!(
+            aggregator.include_signer(2, (p1_hiding, p1_binding), public_key(2)),
+            Err(SignatureError::DuplicateCommitment { signers: (1, 2) })
+        );
+        // A single shared point is enough to be rejected, in any position.
+        assert_eq!(
+            aggregator.include_signer(3, (p2_hiding, p1_hiding), public_key(3)),
+            Err(SignatureError::DuplicateCommitment { signers: (1, 3) })
+        );
+        assert_eq!(
+            aggregator.include_signer(3, (p1_binding, p2_binding), public_key(3)),
+            Err(SignatureError::DuplicateCommitment { signers: (1, 3) })
+        );
+
+        // Degenerate commitments.
+        let identity = RistrettoPoint::identity();
+        let basepoint = RISTRETTO_BASEPOINT_TABLE.basepoint();
+        assert_eq!(
+            aggregator.include_signer(2, (identity, p2_binding), public_key(2)),
+            Err(SignatureError::DegenerateCommitment(2))
+        );
+        assert_eq!(
+            aggregator.include_signer(2, (p2_hiding, basepoint), public_key(2)),
+            Err(SignatureError::DegenerateCommitment(2))
+        );
+
+        // None of the rejected signers were included.
+        assert_eq!(aggregator.get_signers().len(), 1);
+
+        aggregator
+            .include_signer(2, (p2_hiding, p2_binding), public_key(2))
+            .unwrap();
+        assert_eq!(aggregator.get_signers().len(), 2);
+    }
+
+    #[test]
+    fn batch_verification_equations() {
+        use curve25519_dalek::traits::VartimeMultiscalarMul;
+
+        fn verify_batch(equations: &[VerificationEquation]) -> bool {
+            let mut rng = OsRng;
+            let weights: Vec<Scalar> = equations.iter().map(|_| Scalar::random(&mut rng)).collect();
+
+            let mut scalars: Vec<Scalar> = Vec::with_capacity(2 * equations.len() + 1);
+            let mut points: Vec<RistrettoPoint> = Vec::with_capacity(2 * equations.len() + 1);
+
+            let mut z = Scalar::zero();
+            for (a, equation) in weights.iter().zip(equations.iter()) {
+                z += a * equation.z;
+                scalars.push(-a);
+                points.push(equation.R);
+                scalars.push(-(a * equation.challenge));
+                points.push(equation.public_key);
+            }
+            scalars.push(z);
+            points.push(RISTRETTO_BASEPOINT_TABLE.basepoint());
+
+            RistrettoPoint::vartime_multiscalar_mul(scalars, points) == RistrettoPoint::identity()
+        }
+
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+
+        let mut signatures = Vec::new();
+        for i in 0..4u8 {
+            let message = [i; 32];
+            let (p1_public_comshares, mut p1_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, 1, 1);
+            let (p2_public_comshares, mut p2_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+            aggregator
+                .include_signer(
+                    1,
+                    p1_public_comshares.commitments[0],
+                    (&secret_keys[0]).into(),
+                )
+                .unwrap();
+            aggregator
+                .include_signer(
+                    2,
+                    p2_public_comshares.commitments[0],
+                    (&secret_keys[1]).into(),
+                )
+                .unwrap();
+
+            let signers = aggregator.get_signers().clone();
+            let message_hash = compute_message_hash(&context[..], &message[..]);
+            let p1_partial = secret_keys[0]
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut p1_secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            let p2_partial = secret_keys[1]
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut p2_secret_comshares,
+                    0,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(p1_partial).unwrap();
+            aggregator.include_partial_signature(p2_partial).unwrap();
+
+            let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            signatures.push((message_hash, signature));
+        }
+
+        let mut equations: Vec<VerificationEquation> = signatures
+            .iter()
+            .map(|(message_hash, signature)| {
+                assert!(signature.verify(&group_key, message_hash).is_ok());
+                signature.verification_equation(&group_key, message_hash)
+            })
+            .collect();
+        assert!(verify_batch(&equations));
+
+        // A signature over the wrong message fails individually and in the batch.
+        let wrong_hash = compute_message_hash(&context[..], b"wrong message");
+        assert!(signatures[0].1.verify(&group_key, &wrong_hash).is_err());
+        equations[0] = signatures[0]
+            .1
+            .verification_equation(&group_key, &wrong_hash);
+        assert!(!verify_batch(&equations));
+    }
+
+    #[test]
+    fn signing_with_metadata() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let metadata: [(&str, &[u8]); 3] = [
+            ("request_id", b"42"),
+            ("chain_id", b"mainnet"),
+            ("policy_version", b"3"),
+        ];
+
+        let sign_with = |metadata: &[(&str, &[u8])],
+                         policy: &dyn Fn(&SigningSession) -> bool|
+         -> Result<ThresholdSignature, SignatureError> {
+            let (p1_public_comshares, mut p1_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, 1, 1);
+            let (p2_public_comshares, mut p2_secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+            let mut aggregator = SignatureAggregator::new_with_metadata(
+                params,
+                group_key,
+                &context[..],
+                &message[..],
+                metadata,
+            )?;
+            aggregator.include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )?;
+            aggregator.include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )?;
+
+            let session = aggregator.session();
+            if !policy(&session) {
+                return Err(SignatureError::Custom("Rejected by policy".to_string()));
+            }
+            let p1_partial =
+                secret_keys[0].sign_session(&session, &group_key, &mut p1_secret_comshares, 0)?;
+            let p2_partial =
+                secret_keys[1].sign_session(&session, &group_key, &mut p2_secret_comshares, 0)?;
+            aggregator.include_partial_signature(p1_partial)?;
+            aggregator.include_partial_signature(p2_partial)?;
+
+            aggregator
+                .finalize()
+                .map_err(|_| SignatureError::InvalidSignature)?
+                .aggregate()
+                .map_err(|_| SignatureError::InvalidSignature)
+        };
+
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+        let signature = sign_with(&metadata, &|session: &SigningSession| {
+            session.get_metadata("chain_id") == Some(&b"mainnet"[..])
+        })
+        .unwrap();
+
+        // The order of the metadata is irrelevant.
+        let mut reordered = metadata;
+        reordered.reverse();
+        assert!(signature
+            .verify_with_metadata(&group_key, &message_hash, &reordered)
+            .is_ok());
+
+        // Altering, renaming, removing or adding any pair breaks verification.
+        for i in 0..metadata.len() {
+            let mut altered = metadata;
+            altered[i].1 = b"altered";
+            assert_eq!(
+                signature.verify_with_metadata(&group_key, &message_hash, &altered),
+                Err(SignatureError::InvalidSignature)
+            );
+
+            let mut renamed = metadata;
+            renamed[i].0 = "renamed";
+            assert!(signature
+                .verify_with_metadata(&group_key, &message_hash, &renamed)
+                .is_err());
+
+            let mut removed = metadata.to_vec();
+            removed.remove(i);
+            assert!(signature
+                .verify_with_metadata(&group_key, &message_hash, &removed)
+                .is_err());
+        }
+        let mut added = metadata.to_vec();
+        added.push(("extra", b""));
+        assert!(signature
+            .verify_with_metadata(&group_key, &message_hash, &added)
+            .is_err());
+        assert!(signature.verify(&group_key, &message_hash).is_err());
+
+        // Without metadata, signatures verify as before.
+        let signature = sign_with(&[], &|session: &SigningSession| {
+            session.metadata().is_empty()
+        })
+        .unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+        assert!(signature
+            .verify_with_metadata(&group_key, &message_hash, &[])
+            .is_ok());
+
+        // Signers can reject sessions with unexpected metadata.
+        let testnet: [(&str, &[u8]); 1] = [("chain_id", b"testnet")];
+        assert_eq!(
+            sign_with(&testnet, &|session: &SigningSession| {
+                session.get_metadata("chain_id") == Some(&b"mainnet"[..])
+            }),
+            Err(SignatureError::Custom("Rejected by policy".to_string()))
+        );
+
+        let duplicated: [(&str, &[u8]); 2] = [("chain_id", b"mainnet"), ("chain_id", b"testnet")];
+        assert_eq!(
+            sign_with(&duplicated, &|_: &SigningSession| true),
+            Err(SignatureError::DuplicateMetadataKey("chain_id".to_string()))
+        );
+    }
+
+    #[test]
+    fn signing_with_ciphersuite() {
+        type Uniform = UniformRistretto25519<Sha512>;
+
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+
+        let default_hash = compute_message_hash(&context[..], &message[..]);
+        let message_hash = compute_message_hash_with::<Uniform>(&context[..], &message[..]);
+        assert_eq!(
+            compute_message_hash_with::<Ristretto25519>(&context[..], &message[..]),
+            default_hash
+        );
+        assert_ne!(message_hash, default_hash);
+        assert_eq!(
+            message_hash[..],
+            Sha512::new()
+                .chain(&context[..])
+                .chain(&message[..])
+                .finalize()[..32]
+        );
+
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator.set_ciphersuite::<Uniform>().unwrap();
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+
+        let session = aggregator.session();
+        assert_eq!(session.message_hash(), &message_hash);
+
+        let signers = session.signers();
+        let p1_partial = secret_keys[0]
+            .sign_with::<Uniform>(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        let p2_partial = secret_keys[1]
+            .sign_with::<Uniform>(
+                &message_hash,
+                &group_key,
+                &mut p2_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        aggregator.set_partial_budget(1);
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        assert!(threshold_signature
+            .verify_with::<Uniform>(&group_key, &message_hash)
+            .is_ok());
+        assert!(threshold_signature
+            .verify_with::<Uniform>(&group_key, &default_hash)
+            .is_err());
+        // Both suites derive the challenge with Sha-512, so only the message
+        // hash differs.
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+        assert!(threshold_signature
+            .verify(&group_key, &default_hash)
+            .is_err());
+    }
+
+    #[test]
+    fn signing_with_rfc9591_ciphersuite() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p3_public_comshares, mut p3_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 3, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator.set_ciphersuite::<Rfc9591Ristretto255>().unwrap();
+        // The commitment list of the RFC is sorted, whatever the order in
+        // which the signers are included.
+        aggregator
+            .include_signer(
+                3,
+                p3_public_comshares.commitments[0],
+                (&secret_keys[2]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+
+        let signers = aggregator.get_signers().clone();
+        let p1_partial = secret_keys[0]
+            .sign_with::<Rfc9591Ristretto255>(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        let p3_partial = secret_keys[2]
+            .sign_with::<Rfc9591Ristretto255>(
+                &message_hash,
+                &group_key,
+                &mut p3_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p3_partial).unwrap();
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        assert!(threshold_signature
+            .verify_with::<Rfc9591Ristretto255>(&group_key, &message_hash)
+            .is_ok());
+        // The RFC derives the challenge over another domain separator.
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_err());
+    }
+
+    #[test]
+    fn nonce_count() {
+        /// A hypothetical variant committing to three nonces per signature.
+        #[derive(Clone, Copy, Debug)]
+        struct ThreeNonceSuite;
+
+        impl Ciphersuite for ThreeNonceSuite {
+            const ID: &'static str = "ristretto255-three-nonces";
+            const NONCE_COUNT: usize = 3;
+
+            type Scalar = Scalar;
+            type Element = RistrettoPoint;
+            type Hash = Sha512;
+
+            fn generator() -> RistrettoPoint {
+                Ristretto25519::generator()
+            }
+
+            fn random_scalar(rng: impl RngCore + CryptoRng) -> Scalar {
+                Ristretto25519::random_scalar(rng)
+            }
+
+            fn hash_to_scalar(hash: Sha512) -> Scalar {
+                Ristretto25519::hash_to_scalar(hash)
+            }
+
+            fn message_hash(context_string: &[u8], message: &[u8]) -> [u8; 32] {
+                Ristretto25519::message_hash(context_string, message)
+            }
+
+            fn encode_element(element: &RistrettoPoint) -> Vec<u8> {
+                Ristretto25519::encode_element(element)
+            }
+
+            fn decode_element(bytes: &[u8]) -> Result<RistrettoPoint, Error> {
+                Ristretto25519::decode_element(bytes)
+            }
+
+            fn encode_scalar(scalar: &Scalar) -> Vec<u8> {
+                Ristretto25519::encode_scalar(scalar)
+            }
+
+            fn decode_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+                Ristretto25519::decode_scalar(bytes)
+            }
+        }
+
+        assert_eq!(Ristretto25519::NONCE_COUNT, 2);
+        assert_eq!(UniformRistretto25519::<Sha512>::NONCE_COUNT, 2);
+        assert_eq!(CommitmentShare::NONCE_COUNT, 2);
+
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        // Commitment shares, aggregators and signatures with another nonce
+        // count are refused.
+        assert_eq!(
+            generate_commitment_share_lists_with::<ThreeNonceSuite>(&mut OsRng, 1, 1).err(),
+            Some(SignatureError::UnsupportedNonceCount(3))
+        );
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists_with::<Ristretto25519>(&mut OsRng, 1, 1).unwrap();
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists_with::<Ristretto25519>(&mut OsRng, 2, 1).unwrap();
+        assert_eq!(
+            p1_secret_comshares.commitments[0].to_bytes().len(),
+            64 * CommitmentShare::NONCE_COUNT
+        );
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        assert_eq!(
+            aggregator.set_ciphersuite::<ThreeNonceSuite>(),
+            Err(SignatureError::UnsupportedNonceCount(3))
+        );
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+        let signers = aggregator.get_signers().clone();
+
+        assert_eq!(
+            secret_keys[0].sign_with::<ThreeNonceSuite>(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                &signers,
+            ),
+            Err(SignatureError::UnsupportedNonceCount(3))
+        );
+        assert_eq!(p1_secret_comshares.commitments.len(), 1);
+
+        // The default two nonces produce a standard signature, whose group
+        // commitment is the sum of the D + rho * E of the signers.
+        let p1_partial = secret_keys[0]
+            .sign_with::<Ristretto25519>(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        let p2_partial = secret_keys[1]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p2_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        let (binding_factors, _) = compute_binding_factors_and_group_commitment::<Ristretto25519>(
+            &message_hash,
+            &group_key,
+            &signers,
+        );
+        let R: RistrettoPoint = signers
+            .iter()
+            .map(|signer| {
+                let (hiding, binding) = signer.published_commitment_share;
+                hiding + binding * binding_factors[&signer.participant_index]
+            })
+            .sum();
+        assert_eq!(threshold_signature.R, R);
+    }
 
-            dealer1
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&dealer1.index, dealer1.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
-            dealer2
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&dealer2.index, dealer2.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
-            dealer3
-                .proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&dealer3.index, dealer3.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
+    #[test]
+    fn torsion_group_commitment() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
 
-            let dealers: Vec<Participant> = vec![dealer1.clone(), dealer2.clone(), dealer3.clone()];
-            let (dealer1_state, _participant_lists) =
-                DistributedKeyGeneration::<RoundOne>::new_initial(
-                    &params_dealers,
-                    &dealer1_dh_sk,
-                    &dealer1.index,
-                    &dealer1coeffs,
-                    &dealers,
-                    "Φ",
-                    &mut rng,
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+
+        let session = aggregator.session();
+        let p1_partial = secret_keys[0]
+            .sign_session(&session, &group_key, &mut p1_secret_comshares, 0)
+            .unwrap();
+        let p2_partial = secret_keys[1]
+            .sign_session(&session, &group_key, &mut p2_secret_comshares, 0)
+            .unwrap();
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+
+        // The Edwards encodings of the points of small order, as the group
+        // commitment or the group key, are either rejected on decoding, or
+        // decode to an element of the prime-order group which does not verify.
+        let bytes = threshold_signature.to_bytes();
+        for torsion in EIGHT_TORSION.iter() {
+            let encoding = torsion.compress().to_bytes();
+
+            let mut tampered = bytes;
+            tampered[..32].copy_from_slice(&encoding);
+            if let Ok(signature) = ThresholdSignature::from_bytes(&tampered) {
+                assert!(signature.verify(&group_key, &message_hash).is_err());
+            }
+
+            if let Ok(key) = GroupKey::from_bytes(&encoding) {
+                assert!(threshold_signature.verify(&key, &message_hash).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn signing_with_bound_parameters() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, mut p2_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 2, 1);
+
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator.bind_parameters();
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+
+        let session = aggregator.session();
+        assert_eq!(session.parameters(), Some(&params));
+
+        let p1_partial = secret_keys[0]
+            .sign_session(&session, &group_key, &mut p1_secret_comshares, 0)
+            .unwrap();
+        let p2_partial = secret_keys[1]
+            .sign_session(&session, &group_key, &mut p2_secret_comshares, 0)
+            .unwrap();
+        aggregator.set_partial_budget(1);
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
+
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+
+        assert!(threshold_signature
+            .verify_with_parameters(&group_key, &params, &message_hash)
+            .is_ok());
+        assert_eq!(
+            threshold_signature.verify_with_parameters(
+                &group_key,
+                &Parameters { n: 5, t: 3 },
+                &message_hash
+            ),
+            Err(SignatureError::InvalidSignature)
+        );
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_err());
+    }
+
+    #[test]
+    fn deterministic_signer_selection() {
+        let params = Parameters { n: 5, t: 3 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+
+        let mut public_comshares = Vec::new();
+        let mut secret_comshares = Vec::new();
+        for i in 1..=params.n {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, i, 1);
+            public_comshares.push(public.commitments[0]);
+            secret_comshares.push(secret);
+        }
+
+        let run = |order: &[usize], policy: &SubsetPolicy| {
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+            for i in order.iter() {
+                aggregator
+                    .include_signer(
+                        *i as u32 + 1,
+                        public_comshares[*i],
+                        (&secret_keys[*i]).into(),
+                    )
+                    .unwrap();
+            }
+            let excluded = aggregator.select_signers(policy);
+            (aggregator, excluded)
+        };
+
+        for (policy, expected) in [
+            (SubsetPolicy::SmallestIndices, [1, 2, 3]),
+            (SubsetPolicy::Priority(vec![5, 2]), [1, 2, 5]),
+        ] {
+            let (mut first, first_excluded) = run(&[4, 0, 3, 2, 1], &policy);
+            let (mut second, second_excluded) = run(&[1, 3, 0, 4, 2], &policy);
+
+            let session = first.session();
+            assert_eq!(session, second.session());
+            let selected: Vec<u32> = session
+                .signers()
+                .iter()
+                .map(|s| s.participant_index)
+                .collect();
+            assert_eq!(selected, expected);
+            assert_eq!(first_excluded, second_excluded);
+            assert_eq!(first_excluded.len(), 2);
+
+            // Every selected signer signs once, and the partials reach both
+            // aggregators in a different order.
+            let mut partials = Vec::new();
+            for index in selected.iter() {
+                let i = *index as usize - 1;
+                let mut secret = secret_comshares[i].duplicate();
+                partials.push(
+                    secret_keys[i]
+                        .sign_session(&session, &group_key, &mut secret, 0)
+                        .unwrap(),
+                );
+            }
+            for partial in partials.iter() {
+                first
+                    .include_partial_signature(
+                        PartialThresholdSignature::from_bytes(&partial.to_bytes()).unwrap(),
+                    )
+                    .unwrap();
+            }
+            for partial in partials.iter().rev() {
+                second
+                    .include_partial_signature(
+                        PartialThresholdSignature::from_bytes(&partial.to_bytes()).unwrap(),
+                    )
+                    .unwrap();
+            }
+
+            let first = first.finalize().unwrap().aggregate().unwrap();
+            let second = second.finalize().unwrap().aggregate().unwrap();
+            assert_eq!(first.to_bytes(), second.to_bytes());
+            assert!(first
+                .verify(
+                    &group_key,
+                    &compute_message_hash(&context[..], &message[..])
                 )
-                .or(Err(()))?;
-            let dealer1_their_encrypted_secret_shares =
-                dealer1_state.their_encrypted_secret_shares().or(Err(()))?;
+                .is_ok());
+        }
+    }
 
-            let (dealer2_state, _participant_lists) =
-                DistributedKeyGeneration::<RoundOne>::new_initial(
-                    &params_dealers,
-                    &dealer2_dh_sk,
-                    &dealer2.index,
-                    &dealer2coeffs,
-                    &dealers,
-                    "Φ",
-                    &mut rng,
+    #[test]
+    fn signing_twice_with_renewed_session() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let metadata: [(&str, &[u8]); 1] = [("request_id", b"42")];
+
+        fn sign(
+            mut aggregator: SignatureAggregator<Initial>,
+            group_key: &GroupKey,
+            secret_keys: &[SecretKey],
+        ) -> (SigningSession, ThresholdSignature) {
+            let mut secret_comshares = Vec::new();
+            for sk in secret_keys.iter() {
+                let pk: IndividualPublicKey = sk.into();
+                let (public, secret) = generate_commitment_share_lists(&mut OsRng, pk.index, 1);
+                aggregator
+                    .include_signer(pk.index, public.commitments[0], pk)
+                    .unwrap();
+                secret_comshares.push(secret);
+            }
+
+            let session = aggregator.session();
+            for (sk, secret) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+                let partial = sk.sign_session(&session, group_key, secret, 0).unwrap();
+                aggregator.include_partial_signature(partial).unwrap();
+            }
+
+            let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            (session, signature)
+        }
+
+        let mut aggregator = SignatureAggregator::new_with_metadata(
+            params,
+            group_key,
+            &context[..],
+            &message[..],
+            &metadata,
+        )
+        .unwrap();
+        aggregator.bind_parameters();
+        let (session, first) = sign(aggregator, &group_key, &secret_keys[..2]);
+
+        let renewed = session.renew(OsRng);
+        assert_ne!(renewed.nonce(), session.nonce());
+        assert_eq!(renewed.message_hash(), session.message_hash());
+        assert!(renewed.signers().is_empty());
+
+        let aggregator = SignatureAggregator::from_session(params, group_key, &renewed);
+        let (renewed, second) = sign(aggregator, &group_key, &secret_keys[1..]);
+        assert_eq!(renewed.message_hash(), session.message_hash());
+        assert_eq!(renewed.parameters(), Some(&params));
+        assert_ne!(renewed.id(), session.id());
+
+        let message_hash = compute_message_hash(&context[..], &message[..]);
+        for signature in [&first, &second] {
+            assert!(signature
+                .verify_with_parameters(
+                    &group_key,
+                    &params,
+                    &bind_metadata(&message_hash, &metadata).unwrap()
                 )
-                .or(Err(()))?;
-            let dealer2_their_encrypted_secret_shares =
-                dealer2_state.their_encrypted_secret_shares().or(Err(()))?;
+                .is_ok());
+        }
+        assert_ne!(first.R.compress(), second.R.compress());
+    }
 
-            let (dealer3_state, _participant_lists) =
-                DistributedKeyGeneration::<RoundOne>::new_initial(
-                    &params_dealers,
-                    &dealer3_dh_sk,
-                    &dealer3.index,
-                    &dealer3coeffs,
-                    &dealers,
-                    "Φ",
-                    &mut rng,
+    #[test]
+    fn batch_verifier_with_bounded_memory() {
+        // Signatures are produced directly from the group secret, to keep the
+        // test fast.
+        let secret = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &secret);
+        let sign = |message_hash: &[u8; 32]| {
+            let r = Scalar::random(&mut OsRng);
+            let R = &RISTRETTO_BASEPOINT_TABLE * &r;
+            let c = compute_challenge::<Ristretto25519>(message_hash, &group_key, &R, None);
+            ThresholdSignature {
+                R,
+                z: r + c * secret,
+            }
+        };
+
+        let mut batch = BatchVerifier::new(OsRng);
+        assert!(batch.is_empty());
+        for i in 0..10_000u32 {
+            let message_hash = compute_message_hash(b"batch", &i.to_le_bytes());
+            batch.add(&group_key, &message_hash, &sign(&message_hash));
+        }
+        assert_eq!(batch.len(), 10_000);
+        assert!(batch.verify().is_ok());
+        assert!(BatchVerifier::new(OsRng).verify().is_ok());
+
+        // A single bad signature makes the whole batch fail.
+        let mut batch = BatchVerifier::new(OsRng);
+        for i in 0..100u32 {
+            let message_hash = compute_message_hash(b"batch", &i.to_le_bytes());
+            let signature = sign(&message_hash);
+            if i == 42 {
+                let wrong_hash = compute_message_hash(b"batch", b"wrong");
+                batch.add(&group_key, &wrong_hash, &signature);
+            } else {
+                batch.add(&group_key, &message_hash, &signature);
+            }
+        }
+        assert_eq!(batch.verify(), Err(SignatureError::InvalidSignature));
+    }
+
+    #[test]
+    fn verify_and_report() {
+        let secret = Scalar::from(7u64);
+        let r = Scalar::from(11u64);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &secret);
+        let R = &RISTRETTO_BASEPOINT_TABLE * &r;
+        let message_hash = compute_message_hash(b"report", b"known signature");
+
+        // The challenge, as specified for verifiers.
+        let mut h = Sha512::new();
+        h.update(b"FROST-SHA512");
+        h.update(R.compress().as_bytes());
+        h.update(group_key.to_bytes());
+        h.update(message_hash);
+        let challenge = Scalar::from_hash(h);
+
+        let signature = ThresholdSignature {
+            R,
+            z: r + challenge * secret,
+        };
+
+        let report = signature
+            .verify_and_report(&group_key, &message_hash)
+            .unwrap();
+        assert_eq!(
+            report,
+            VerifyReport {
+                message_hash,
+                challenge,
+                R,
+            }
+        );
+        assert_eq!(report.R.compress().to_bytes(), signature.to_bytes()[..32]);
+        assert!(report.binds(&group_key, &message_hash));
+
+        let other_hash = compute_message_hash(b"report", b"another message");
+        assert!(!report.binds(&group_key, &other_hash));
+        assert!(!report.binds(&GroupKey::new(R), &message_hash));
+        assert_eq!(
+            signature.verify_and_report(&group_key, &other_hash),
+            Err(SignatureError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn heterogeneous_batch_verification() {
+        // Signatures are produced directly from each group secret, to keep
+        // the test fast.
+        let item = |i: u32| {
+            let secret = Scalar::random(&mut OsRng);
+            let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &secret);
+            let message_hash = compute_message_hash(b"batch", &i.to_le_bytes());
+            let r = Scalar::random(&mut OsRng);
+            let R = &RISTRETTO_BASEPOINT_TABLE * &r;
+            let c = compute_challenge::<Ristretto25519>(&message_hash, &group_key, &R, None);
+
+            (
+                group_key,
+                message_hash,
+                ThresholdSignature {
+                    R,
+                    z: r + c * secret,
+                },
+            )
+        };
+
+        let mut items: Vec<(GroupKey, [u8; 32], ThresholdSignature)> = (0..64).map(item).collect();
+        assert!(verify_heterogeneous_batch(&items, OsRng).is_ok());
+        assert!(verify_heterogeneous_batch(&[], OsRng).is_ok());
+
+        // The bad item is identified by bisection.
+        items[42].1 = compute_message_hash(b"batch", b"wrong");
+        assert_eq!(
+            verify_heterogeneous_batch(&items, OsRng),
+            Err(SignatureError::InvalidBatchItems(vec![42]))
+        );
+
+        // Swapping keys between two valid items invalidates both.
+        let mut items: Vec<(GroupKey, [u8; 32], ThresholdSignature)> = (0..5).map(item).collect();
+        let first_key = items[0].0;
+        items[0].0 = items[4].0;
+        items[4].0 = first_key;
+        assert_eq!(
+            verify_heterogeneous_batch(&items, OsRng),
+            Err(SignatureError::InvalidBatchItems(vec![0, 4]))
+        );
+    }
+
+    #[test]
+    fn signing_after_reloading_commitment_shares() {
+        let params = Parameters { n: 1, t: 1 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 2);
+
+        for published_index in 0..2u32 {
+            // The signer restarts between each signature.
+            let bytes = secret_comshares.to_bytes();
+            secret_comshares = SecretCommitmentShareList::from_bytes(&bytes).unwrap();
+            let position = secret_comshares.position(published_index).unwrap();
+            if published_index == 1 {
+                assert!(secret_comshares.is_consumed(0));
+                assert_eq!(secret_comshares.position(0), None);
+            }
+
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &context[..], b"message");
+            aggregator
+                .include_signer(
+                    1,
+                    public_comshares.commitments[published_index as usize],
+                    (&secret_keys[0]).into(),
                 )
-                .or(Err(()))?;
-            let dealer3_their_encrypted_secret_shares =
-                dealer3_state.their_encrypted_secret_shares().or(Err(()))?;
+                .unwrap();
+            let signers = aggregator.get_signers().clone();
+            let partial = secret_keys[0]
+                .sign(
+                    &message_hash,
+                    &group_key,
+                    &mut secret_comshares,
+                    position,
+                    &signers,
+                )
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
 
-            let dealer1_my_encrypted_secret_shares = vec![
-                dealer1_their_encrypted_secret_shares[0].clone(),
-                dealer2_their_encrypted_secret_shares[0].clone(),
-                dealer3_their_encrypted_secret_shares[0].clone(),
-            ];
-            let dealer2_my_encrypted_secret_shares = vec![
-                dealer1_their_encrypted_secret_shares[1].clone(),
-                dealer2_their_encrypted_secret_shares[1].clone(),
-                dealer3_their_encrypted_secret_shares[1].clone(),
-            ];
-            let dealer3_my_encrypted_secret_shares = vec![
-                dealer1_their_encrypted_secret_shares[2].clone(),
-                dealer2_their_encrypted_secret_shares[2].clone(),
-                dealer3_their_encrypted_secret_shares[2].clone(),
-            ];
+            let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+            assert!(signature.verify(&group_key, &message_hash).is_ok());
+        }
+        assert!(secret_comshares.commitments.is_empty());
+    }
+
+    #[test]
+    fn signature_error_kinds() {
+        let errors = vec![
+            SignatureError::MissingCommitmentShares,
+            SignatureError::InvalidBindingFactor,
+            SignatureError::InvalidSignature,
+            SignatureError::InvalidPartialSignature(1),
+            SignatureError::PartialBudgetExceeded(2),
+            SignatureError::DuplicateCommitment { signers: (3, 4) },
+            SignatureError::DegenerateCommitment(5),
+            SignatureError::UnsignedCommitment(13),
+            SignatureError::DuplicateMetadataKey("key".to_string()),
+            SignatureError::UnknownSigner(6),
+            SignatureError::SessionMismatch(7),
+            SignatureError::CommitmentPoolLow(0),
+            SignatureError::DuplicatePartial(8),
+            SignatureError::StorageError("storage".to_string()),
+            SignatureError::ConcurrentUpdate,
+            SignatureError::InterruptedSession,
+            SignatureError::UnauthorizedSession,
+            SignatureError::UnsupportedNonceCount(3),
+            SignatureError::InvalidBatchItems(vec![9]),
+            SignatureError::MessageCommitmentMismatch,
+            SignatureError::ConflictingIntent {
+                recorded: [1; 32],
+                requested: [2; 32],
+            },
+            SignatureError::OverlappingReservation {
+                signer: 10,
+                commitment_share_index: 3,
+            },
+            SignatureError::PackageMismatch,
+            SignatureError::CommitmentShareSignerMismatch {
+                signer: 11,
+                owner: 12,
+            },
+            SignatureError::StaleCommitment {
+                expected: Box::default(),
+                listed: Box::default(),
+            },
+            SignatureError::UnsupportedDescriptor("ciphersuite"),
+            SignatureError::MessageTooLarge { length: 2, max: 1 },
+            SignatureError::SessionExpired {
+                opened_at: 1,
+                now: 2,
+            },
+            SignatureError::SequenceMismatch {
+                expected: 2,
+                found: Some(3),
+            },
+            SignatureError::SequenceFork(2),
+            SignatureError::Custom("custom".to_string()),
+        ];
+
+        for error in errors.iter() {
+            // This match has no wildcard, so that new variants must be
+            // classified here as well.
+            let (kind, participant) = match error {
+                SignatureError::MissingCommitmentShares => (ErrorKind::Configuration, None),
+                SignatureError::InvalidBindingFactor => (ErrorKind::Configuration, None),
+                SignatureError::InvalidSignature => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::InvalidPartialSignature(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(1),
+                    },
+                    Some(1),
+                ),
+                SignatureError::PartialBudgetExceeded(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(2),
+                    },
+                    Some(2),
+                ),
+                SignatureError::DuplicateCommitment { .. } => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(4),
+                    },
+                    Some(4),
+                ),
+                SignatureError::DegenerateCommitment(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(5),
+                    },
+                    Some(5),
+                ),
+                SignatureError::UnsignedCommitment(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(13),
+                    },
+                    Some(13),
+                ),
+                SignatureError::DuplicateMetadataKey(_) => (ErrorKind::Configuration, None),
+                SignatureError::UnknownSigner(_) => (ErrorKind::Configuration, Some(6)),
+                SignatureError::SessionMismatch(_) => (ErrorKind::Retryable, Some(7)),
+                SignatureError::CommitmentPoolLow(_) => (ErrorKind::Configuration, None),
+                SignatureError::DuplicatePartial(_) => (
+                    ErrorKind::Misbehaviour {
+                        participant: Some(8),
+                    },
+                    Some(8),
+                ),
+                SignatureError::StorageError(_) => (ErrorKind::Retryable, None),
+                SignatureError::ConcurrentUpdate => (ErrorKind::Retryable, None),
+                SignatureError::InterruptedSession => (ErrorKind::Configuration, None),
+                SignatureError::UnauthorizedSession => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::UnsupportedNonceCount(_) => (ErrorKind::Configuration, None),
+                SignatureError::InvalidBatchItems(_) => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::MessageCommitmentMismatch => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::ConflictingIntent { .. } => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::OverlappingReservation { .. } => {
+                    (ErrorKind::Configuration, Some(10))
+                }
+                SignatureError::PackageMismatch => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::CommitmentShareSignerMismatch { .. } => {
+                    (ErrorKind::Configuration, Some(11))
+                }
+                SignatureError::StaleCommitment { .. } => (ErrorKind::Configuration, None),
+                SignatureError::UnsupportedDescriptor(_) => (ErrorKind::Configuration, None),
+                SignatureError::MessageTooLarge { .. } => (ErrorKind::Configuration, None),
+                SignatureError::SessionExpired { .. } => (ErrorKind::Configuration, None),
+                SignatureError::SequenceMismatch { .. } => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::SequenceFork(_) => {
+                    (ErrorKind::Misbehaviour { participant: None }, None)
+                }
+                SignatureError::Custom(_) => (ErrorKind::Configuration, None),
+            };
+            assert_eq!(error.kind(), kind, "{:?}", error);
+            assert_eq!(error.participant(), participant, "{:?}", error);
+        }
+    }
 
-            let dealer1_state = dealer1_state
-                .to_round_two(dealer1_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let dealer2_state = dealer2_state
-                .to_round_two(dealer2_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let dealer3_state = dealer3_state
-                .to_round_two(dealer3_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
+    #[test]
+    fn signed_commitment_shares() {
+        let key = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &key);
+        let secret_key = SecretKey { index: 1, key };
+        let dh_private_key = DHPrivateKey(Scalar::random(&mut OsRng));
+        let roster = [(1, DHPublicKey::from(&dh_private_key))];
+
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let commitment_share = public_comshares.commitments[0];
+        let signature = sign_commitment_share(1, &commitment_share, &dh_private_key, OsRng);
+        assert!(verify_commitment_share(1, &commitment_share, &signature, &roster).is_ok());
 
-            let (dealer1_group_key, dealer1_secret_key) = dealer1_state.finish().or(Err(()))?;
-            let (dealer2_group_key, dealer2_secret_key) = dealer2_state.finish().or(Err(()))?;
-            let (dealer3_group_key, dealer3_secret_key) = dealer3_state.finish().or(Err(()))?;
+        let mut aggregator =
+            SignatureAggregator::new(Parameters { n: 1, t: 1 }, group_key, b"CONTEXT", b"MESSAGE");
 
-            assert!(dealer1_group_key.0.compress() == dealer2_group_key.0.compress());
-            assert!(dealer2_group_key.0.compress() == dealer3_group_key.0.compress());
+        // Signed commitment shares are only accepted once required.
+        assert_eq!(
+            aggregator.include_signed_signer(1, commitment_share, &signature, (&secret_key).into()),
+            Err(SignatureError::UnsignedCommitment(1))
+        );
+        aggregator.require_signed_commitments(&roster);
 
-            let params_signers = Parameters { n: 5, t: 3 };
-            let (signer1, signer1_dh_sk) =
-                Participant::new_signer(&params_signers, 1, "Φ", &mut rng);
-            let (signer2, signer2_dh_sk) =
-                Participant::new_signer(&params_signers, 2, "Φ", &mut rng);
-            let (signer3, signer3_dh_sk) =
-                Participant::new_signer(&params_signers, 3, "Φ", &mut rng);
-            let (signer4, signer4_dh_sk) =
-                Participant::new_signer(&params_signers, 4, "Φ", &mut rng);
-            let (signer5, signer5_dh_sk) =
-                Participant::new_signer(&params_signers, 5, "Φ", &mut rng);
-
-            let signers: Vec<Participant> = vec![
-                signer1.clone(),
-                signer2.clone(),
-                signer3.clone(),
-                signer4.clone(),
-                signer5.clone(),
-            ];
+        // An unsigned commitment share, e.g. one chosen by the coordinator, is
+        // rejected.
+        let (substituted, _) = generate_commitment_share_lists(&mut OsRng, 1, 1);
+        assert_eq!(
+            aggregator.include_signer(1, substituted.commitments[0], (&secret_key).into()),
+            Err(SignatureError::UnsignedCommitment(1))
+        );
 
-            let (dealer1_for_signers, dealer1_encrypted_shares_for_signers, _participant_lists) =
-                Participant::reshare(
-                    &params_signers,
-                    dealer1_secret_key.clone(),
-                    &signers,
-                    "Φ",
-                    &mut rng,
-                )
-                .map_err(|_| ())?;
-            let (dealer2_for_signers, dealer2_encrypted_shares_for_signers, _participant_lists) =
-                Participant::reshare(
-                    &params_signers,
-                    dealer2_secret_key.clone(),
-                    &signers,
-                    "Φ",
-                    &mut rng,
-                )
-                .map_err(|_| ())?;
-            let (dealer3_for_signers, dealer3_encrypted_shares_for_signers, _participant_lists) =
-                Participant::reshare(
-                    &params_signers,
-                    dealer3_secret_key.clone(),
-                    &signers,
-                    "Φ",
-                    &mut rng,
-                )
-                .map_err(|_| ())?;
+        // So is a substituted commitment share carrying the signature of the
+        // genuine one.
+        assert_eq!(
+            aggregator.include_signed_signer(
+                1,
+                substituted.commitments[0],
+                &signature,
+                (&secret_key).into()
+            ),
+            Err(SignatureError::UnsignedCommitment(1))
+        );
 
-            let dealers: Vec<Participant> = vec![
-                dealer1_for_signers,
-                dealer2_for_signers,
-                dealer3_for_signers,
-            ];
-            let (signer1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params_dealers,
-                &signer1_dh_sk,
-                &signer1.index,
-                &dealers,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
+        // And a commitment share signed by another key.
+        let other_key = DHPrivateKey(Scalar::random(&mut OsRng));
+        let forged = sign_commitment_share(1, &commitment_share, &other_key, OsRng);
+        assert_eq!(
+            aggregator.include_signed_signer(1, commitment_share, &forged, (&secret_key).into()),
+            Err(SignatureError::UnsignedCommitment(1))
+        );
+        assert!(aggregator.get_signers().is_empty());
 
-            let (signer2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params_dealers,
-                &signer2_dh_sk,
-                &signer2.index,
-                &dealers,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
+        aggregator
+            .include_signed_signer(1, commitment_share, &signature, (&secret_key).into())
+            .unwrap();
 
-            let (signer3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params_dealers,
-                &signer3_dh_sk,
-                &signer3.index,
-                &dealers,
-                "Φ",
-                &mut rng,
+        let message_hash = compute_message_hash(b"CONTEXT", b"MESSAGE");
+        let signers = aggregator.get_signers().clone();
+        let partial = secret_key
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares,
+                0,
+                &signers,
             )
-            .or(Err(()))?;
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+        let threshold_signature = aggregator.finalize().unwrap().aggregate().unwrap();
 
-            let (signer4_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params_dealers,
-                &signer4_dh_sk,
-                &signer4.index,
-                &dealers,
-                "Φ",
-                &mut rng,
+        assert!(threshold_signature
+            .verify(&group_key, &message_hash)
+            .is_ok());
+    }
+
+    #[test]
+    fn verify_with_descriptor() {
+        type Uniform = UniformRistretto25519<Sha512>;
+
+        let key = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &key);
+        let secret_key = SecretKey { index: 1, key };
+        let metadata: [(&str, &[u8]); 1] = [("chain_id", b"mainnet")];
+
+        let sign = |uniform: bool, metadata: &[(&str, &[u8])], bind_parameters: bool| {
+            let (public_comshares, mut secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, 1, 1);
+            let mut aggregator = SignatureAggregator::new_with_metadata(
+                Parameters { n: 1, t: 1 },
+                group_key,
+                b"CONTEXT",
+                b"MESSAGE",
+                metadata,
             )
-            .or(Err(()))?;
+            .unwrap();
+            if uniform {
+                aggregator.set_ciphersuite::<Uniform>().unwrap();
+            }
+            if bind_parameters {
+                aggregator.bind_parameters();
+            }
+            aggregator
+                .include_signer(1, public_comshares.commitments[0], (&secret_key).into())
+                .unwrap();
+            let session = aggregator.session();
+            let partial = match uniform {
+                true => secret_key.sign_with::<Uniform>(
+                    session.message_hash(),
+                    &group_key,
+                    &mut secret_comshares,
+                    0,
+                    session.signers(),
+                ),
+                false => secret_key.sign_session(&session, &group_key, &mut secret_comshares, 0),
+            }
+            .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+            let signature = aggregator.finalize().unwrap().aggregate().unwrap();
 
-            let (signer5_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new(
-                &params_dealers,
-                &signer5_dh_sk,
-                &signer5.index,
-                &dealers,
-                "Φ",
-                &mut rng,
+            (VerificationDescriptor::for_session(&session), signature)
+        };
+
+        // Ristretto-native, with and without metadata and parameters.
+        for (metadata, bind_parameters) in [(&metadata[..], true), (&[][..], false)] {
+            let (descriptor, signature) = sign(false, metadata, bind_parameters);
+            assert_eq!(descriptor.suite_id, VerificationDescriptor::DEFAULT_SUITE);
+            assert_eq!(descriptor.metadata_digest.is_some(), !metadata.is_empty());
+            assert_eq!(descriptor.parameters.is_some(), bind_parameters);
+            assert!(signature
+                .verify_with_descriptor(&descriptor, &group_key, b"MESSAGE", metadata)
+                .is_ok());
+            assert_eq!(
+                signature.verify_with_descriptor(&descriptor, &group_key, b"OTHER", metadata),
+                Err(SignatureError::InvalidSignature)
+            );
+        }
+
+        // Uniform ciphersuite.
+        let (mut descriptor, signature) = sign(true, &metadata, false);
+        assert_eq!(
+            signature.verify_with_descriptor(&descriptor, &group_key, b"MESSAGE", &metadata),
+            Err(SignatureError::InvalidSignature)
+        );
+        descriptor.suite_id = VerificationDescriptor::UNIFORM_SHA512_SUITE;
+        assert!(signature
+            .verify_with_descriptor(&descriptor, &group_key, b"MESSAGE", &metadata)
+            .is_ok());
+
+        // Descriptors which do not match the signature.
+        let (descriptor, signature) = sign(false, &metadata, true);
+        let mismatches = [
+            VerificationDescriptor {
+                parameters: None,
+                ..descriptor.clone()
+            },
+            VerificationDescriptor {
+                metadata_digest: None,
+                ..descriptor.clone()
+            },
+            VerificationDescriptor {
+                context: b"OTHER".to_vec(),
+                ..descriptor.clone()
+            },
+        ];
+        for mismatch in mismatches.iter() {
+            assert_eq!(
+                signature.verify_with_descriptor(mismatch, &group_key, b"MESSAGE", &metadata),
+                Err(SignatureError::InvalidSignature)
+            );
+        }
+        let other: [(&str, &[u8]); 1] = [("chain_id", b"testnet")];
+        assert_eq!(
+            signature.verify_with_descriptor(&descriptor, &group_key, b"MESSAGE", &other),
+            Err(SignatureError::InvalidSignature)
+        );
+
+        // Unsupported descriptors.
+        let unsupported = [
+            (
+                VerificationDescriptor {
+                    key_encoding: VerificationDescriptor::ED25519_KEY,
+                    ..descriptor.clone()
+                },
+                "key encoding",
+            ),
+            (
+                VerificationDescriptor {
+                    key_encoding: VerificationDescriptor::X_ONLY_KEY,
+                    ..descriptor.clone()
+                },
+                "key encoding",
+            ),
+            (
+                VerificationDescriptor {
+                    tweak: Some([1; 32]),
+                    ..descriptor.clone()
+                },
+                "tweaked key",
+            ),
+            (
+                VerificationDescriptor {
+                    suite_id: 2,
+                    ..descriptor.clone()
+                },
+                "ciphersuite",
+            ),
+        ];
+        for (descriptor, feature) in unsupported.iter() {
+            assert_eq!(
+                signature.verify_with_descriptor(descriptor, &group_key, b"MESSAGE", &metadata),
+                Err(SignatureError::UnsupportedDescriptor(feature))
+            );
+        }
+    }
+
+    #[test]
+    fn weighted_signer_aggregation() {
+        let params = Parameters { n: 5, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let message_hash = compute_message_hash(b"CONTEXT", b"MESSAGE");
+
+        // The weighted signer holds indices 1 and 2, and another signer
+        // index 4.
+        let mut comshares: Vec<(PublicCommitmentShareList, SecretCommitmentShareList)> = [0, 1, 3]
+            .iter()
+            .map(|&i| generate_commitment_share_lists(&mut OsRng, secret_keys[i].index, 1))
+            .collect();
+        let commitments: Vec<(RistrettoPoint, RistrettoPoint)> = comshares
+            .iter()
+            .map(|(public, _)| public.commitments[0])
+            .collect();
+        let new_aggregator = || {
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, b"CONTEXT", b"MESSAGE");
+            aggregator
+                .include_weighted_signer(
+                    &[2, 1],
+                    &[commitments[1], commitments[0]],
+                    &[(&secret_keys[1]).into(), (&secret_keys[0]).into()],
+                )
+                .unwrap();
+            aggregator
+                .include_signer(4, commitments[2], (&secret_keys[3]).into())
+                .unwrap();
+            aggregator
+        };
+        let mut aggregator = new_aggregator();
+        aggregator.set_partial_budget(2);
+        let signers = aggregator.get_signers().clone();
+        assert_eq!(signers.len(), 3);
+
+        let partials: Vec<PartialThresholdSignature> = [0, 1, 3]
+            .iter()
+            .zip(comshares.iter_mut())
+            .map(|(&i, (_, secret))| {
+                secret_keys[i]
+                    .sign(&message_hash, &group_key, secret, 0, &signers)
+                    .unwrap()
+            })
+            .collect();
+        let combined = PartialThresholdSignature::combine(&partials[..2]).unwrap();
+        assert_eq!(combined.index, 1);
+        assert!(PartialThresholdSignature::combine(&[]).is_none());
+
+        // The weighted signer only signs under its lowest index, and with
+        // all of its indices.
+        assert_eq!(
+            aggregator.include_partial_signature(partials[1]),
+            Err(SignatureError::UnknownSigner(2))
+        );
+        assert_eq!(
+            aggregator.include_partial_signature(partials[0]),
+            Err(SignatureError::InvalidPartialSignature(1))
+        );
+        assert!(aggregator.verify_partial(&combined).is_ok());
+        aggregator.include_partial_signature(combined).unwrap();
+        aggregator.include_partial_signature(partials[2]).unwrap();
+        assert!(aggregator.get_remaining_signers().is_empty());
+
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+
+        // Without a budget, an incomplete combination is caught on
+        // aggregation, and blamed on the weighted signer.
+        let mut aggregator = new_aggregator();
+        aggregator.include_partial_signature(partials[0]).unwrap();
+        aggregator.include_partial_signature(partials[2]).unwrap();
+        let misbehaving = aggregator.finalize().unwrap().aggregate().unwrap_err();
+        assert_eq!(misbehaving.keys().copied().collect::<Vec<u32>>(), vec![1]);
+
+        // The indices of a weighted signer must come with as many
+        // commitment shares and public keys, and be distinct.
+        let mut aggregator = SignatureAggregator::new(params, group_key, b"CONTEXT", b"MESSAGE");
+        assert!(aggregator
+            .include_weighted_signer(&[1, 2], &[commitments[0]], &[])
+            .is_err());
+        assert!(aggregator
+            .include_weighted_signer(
+                &[1, 1],
+                &commitments[..2],
+                &[(&secret_keys[0]).into(), (&secret_keys[0]).into()],
             )
-            .or(Err(()))?;
+            .is_err());
+        assert!(aggregator.get_signers().is_empty());
+    }
 
-            let signer1_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[0].clone(),
-                dealer2_encrypted_shares_for_signers[0].clone(),
-                dealer3_encrypted_shares_for_signers[0].clone(),
-            ];
-            let signer2_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[1].clone(),
-                dealer2_encrypted_shares_for_signers[1].clone(),
-                dealer3_encrypted_shares_for_signers[1].clone(),
-            ];
-            let signer3_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[2].clone(),
-                dealer2_encrypted_shares_for_signers[2].clone(),
-                dealer3_encrypted_shares_for_signers[2].clone(),
-            ];
-            let signer4_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[3].clone(),
-                dealer2_encrypted_shares_for_signers[3].clone(),
-                dealer3_encrypted_shares_for_signers[3].clone(),
-            ];
-            let signer5_my_encrypted_secret_shares = vec![
-                dealer1_encrypted_shares_for_signers[4].clone(),
-                dealer2_encrypted_shares_for_signers[4].clone(),
-                dealer3_encrypted_shares_for_signers[4].clone(),
-            ];
+    #[test]
+    fn zero_partial_signature() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let message_hash = compute_message_hash(b"CONTEXT", b"MESSAGE");
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, b"CONTEXT", b"MESSAGE");
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys[..2].iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+        let signers = aggregator.get_signers().clone();
 
-            let signer1_state = signer1_state
-                .to_round_two(signer1_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let signer2_state = signer2_state
-                .to_round_two(signer2_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let signer3_state = signer3_state
-                .to_round_two(signer3_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let signer4_state = signer4_state
-                .to_round_two(signer4_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let signer5_state = signer5_state
-                .to_round_two(signer5_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
+        let honest = secret_keys[0]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut secret_comshares[0],
+                0,
+                &signers,
+            )
+            .unwrap();
+        assert!(aggregator.verify_partial(&honest).is_ok());
+        aggregator.include_partial_signature(honest).unwrap();
 
-            let (signer1_group_key, signer1_secret_key) = signer1_state.finish().or(Err(()))?;
-            let (signer2_group_key, signer2_secret_key) = signer2_state.finish().or(Err(()))?;
-            let (signer3_group_key, signer3_secret_key) = signer3_state.finish().or(Err(()))?;
-            let (signer4_group_key, signer4_secret_key) = signer4_state.finish().or(Err(()))?;
-            let (signer5_group_key, signer5_secret_key) = signer5_state.finish().or(Err(()))?;
+        // Without a budget, the zero partial is flagged but only rejected
+        // once verified.
+        let zero = PartialThresholdSignature {
+            index: 2,
+            z: Scalar::zero(),
+        };
+        assert_eq!(
+            aggregator.verify_partial(&zero),
+            Err(SignatureError::InvalidPartialSignature(2))
+        );
+        aggregator.include_partial_signature(zero).unwrap();
+        let counters = aggregator.partial_signature_counters(2).unwrap();
+        assert_eq!(counters.suspicious, 1);
+        assert_eq!(
+            aggregator.partial_signature_counters(1).unwrap().suspicious,
+            0
+        );
+        assert!(aggregator.finalize().unwrap().aggregate().is_err());
+
+        // With a budget, it is rejected right away.
+        let mut aggregator = SignatureAggregator::new(params, group_key, b"CONTEXT", b"MESSAGE");
+        aggregator.set_partial_budget(1);
+        for secret_key in secret_keys[..2].iter() {
+            let (public, _) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+        }
+        assert_eq!(
+            aggregator.include_partial_signature(zero),
+            Err(SignatureError::InvalidPartialSignature(2))
+        );
+        let counters = aggregator.partial_signature_counters(2).unwrap();
+        assert_eq!((counters.suspicious, counters.invalid), (1, 1));
+    }
 
-            assert!(signer1_group_key.0.compress() == signer2_group_key.0.compress());
-            assert!(signer2_group_key.0.compress() == signer3_group_key.0.compress());
-            assert!(signer3_group_key.0.compress() == signer4_group_key.0.compress());
-            assert!(signer4_group_key.0.compress() == signer5_group_key.0.compress());
+    #[test]
+    fn sign_prehashed_message() {
+        struct Artifact(Vec<u8>);
 
-            assert!(signer1_group_key.0.compress() == dealer1_group_key.0.compress());
+        impl Signable for Artifact {
+            fn to_signing_bytes(&self) -> (Vec<u8>, Vec<u8>) {
+                (b"ARTIFACT".to_vec(), self.0.clone())
+            }
+        }
 
-            Ok((
-                params_dealers,
-                params_signers,
-                dealer1_secret_key,
-                dealer2_secret_key,
-                dealer3_secret_key,
-                signer1_secret_key,
-                signer2_secret_key,
-                signer3_secret_key,
-                signer4_secret_key,
-                signer5_secret_key,
-                dealer1_group_key,
-            ))
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+
+        // The message is streamed through the hasher, one chunk at a time.
+        let chunk = [7u8; 4096];
+        let mut hasher = Sha256::new();
+        hasher.update(b"ARTIFACT");
+        for _ in 0..64 {
+            hasher.update(chunk);
+        }
+        let mut message_hash = [0u8; 32];
+        message_hash.copy_from_slice(hasher.finalize().as_slice());
+        let artifact = Artifact(chunk.repeat(64));
+        assert_eq!(message_hash, compute_message_hash(b"ARTIFACT", &artifact.0));
+
+        let mut aggregator =
+            SignatureAggregator::new_prehashed(params, group_key, b"ARTIFACT", message_hash);
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys[..2].iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
         }
-        let keygen_protocol = do_keygen();
+        let session = aggregator.session();
+        assert!(session.message().is_empty());
+        assert_eq!(session.message_hash(), &message_hash);
+        for (secret_key, secret) in secret_keys[..2].iter().zip(secret_comshares.iter_mut()) {
+            let partial = secret_key
+                .sign_session(&session, &group_key, secret, 0)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+        assert!(signature.verify_signable(&group_key, &artifact).is_ok());
 
-        assert!(keygen_protocol.is_ok());
+        // The owned message is bounded.
+        assert_eq!(
+            SignatureAggregator::from_signable_with_limit(params, group_key, &artifact, 4096).err(),
+            Some(SignatureError::MessageTooLarge {
+                length: 64 * 4096,
+                max: 4096,
+            })
+        );
+        let aggregator =
+            SignatureAggregator::from_signable_with_limit(params, group_key, &artifact, 64 * 4096)
+                .unwrap();
+        assert_eq!(aggregator.message_hash(), message_hash);
+        assert!(SignatureAggregator::from_signable(params, group_key, &artifact).is_ok());
+    }
 
-        let (
-            d_params,
-            s_params,
-            d1_sk,
-            d2_sk,
-            _d3_sk,
-            s1_sk,
-            s2_sk,
-            s3_sk,
-            _s4_sk,
-            _s5_sk,
-            group_key,
-        ) = keygen_protocol.unwrap();
+    #[test]
+    fn sign_signable() {
+        struct Transfer {
+            from: u32,
+            to: u32,
+            amount: u64,
+        }
 
-        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (d1_public_comshares, mut d1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
-        let (d2_public_comshares, mut d2_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 2, 1);
+        impl Signable for Transfer {
+            fn to_signing_bytes(&self) -> (Vec<u8>, Vec<u8>) {
+                let mut message = Vec::with_capacity(16);
+                message.extend_from_slice(&self.from.to_le_bytes());
+                message.extend_from_slice(&self.to.to_le_bytes());
+                message.extend_from_slice(&self.amount.to_le_bytes());
+
+                (b"TRANSFER-V1".to_vec(), message)
+            }
+        }
+
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let transfer = Transfer {
+            from: 1,
+            to: 2,
+            amount: 100,
+        };
 
         let mut aggregator =
-            SignatureAggregator::new(d_params, group_key, &context[..], &message[..]);
+            SignatureAggregator::from_signable(params, group_key, &transfer).unwrap();
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys[..2].iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+        let session = aggregator.session();
+        assert_eq!(session.context(), b"TRANSFER-V1");
+        for (secret_key, secret) in secret_keys[..2].iter().zip(secret_comshares.iter_mut()) {
+            let partial = secret_key
+                .sign_session(&session, &group_key, secret, 0)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
 
-        aggregator.include_signer(1, d1_public_comshares.commitments[0], (&d1_sk).into());
-        aggregator.include_signer(2, d2_public_comshares.commitments[0], (&d2_sk).into());
+        assert!(signature.verify_signable(&group_key, &transfer).is_ok());
+        let (context, message) = transfer.to_signing_bytes();
+        assert!(signature
+            .verify_unhashed(&group_key, (&context, &message))
+            .is_ok());
 
-        let signers = aggregator.get_signers();
-        let message_hash = compute_message_hash(&context[..], &message[..]);
+        let other = Transfer {
+            amount: 1000,
+            ..transfer
+        };
+        assert_eq!(
+            signature.verify_signable(&group_key, &other),
+            Err(SignatureError::InvalidSignature)
+        );
+    }
 
-        let d1_partial = d1_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut d1_secret_comshares,
-                0,
-                signers,
-            )
+    #[test]
+    fn coordinator_bound_signature() {
+        let key = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &key);
+        let secret_key = SecretKey { index: 1, key };
+        let message_hash = compute_message_hash(b"CONTEXT", b"MESSAGE");
+
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let mut aggregator =
+            SignatureAggregator::new(Parameters { n: 1, t: 1 }, group_key, b"CONTEXT", b"MESSAGE");
+        aggregator.bind_coordinator(b"COORDINATOR A");
+        aggregator
+            .include_signer(1, public_comshares.commitments[0], (&secret_key).into())
             .unwrap();
-        let d2_partial = d2_sk
-            .sign(
-                &message_hash,
-                &group_key,
-                &mut d2_secret_comshares,
-                0,
-                signers,
-            )
+        let session = aggregator.session();
+        assert_eq!(session.coordinator_id(), Some(&b"COORDINATOR A"[..]));
+        assert_eq!(
+            session.message_hash(),
+            &bind_coordinator_id(&message_hash, b"COORDINATOR A")
+        );
+
+        let partial = secret_key
+            .sign_session(&session, &group_key, &mut secret_comshares, 0)
             .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
 
-        aggregator.include_partial_signature(d1_partial);
-        aggregator.include_partial_signature(d2_partial);
+        assert!(signature
+            .verify_with_coordinator(&group_key, &message_hash, b"COORDINATOR A")
+            .is_ok());
+        assert_eq!(
+            signature.verify_with_coordinator(&group_key, &message_hash, b"COORDINATOR B"),
+            Err(SignatureError::InvalidSignature)
+        );
+        assert_eq!(
+            signature.verify(&group_key, &message_hash),
+            Err(SignatureError::InvalidSignature)
+        );
 
-        let aggregator = aggregator.finalize().unwrap();
-        let signing_result = aggregator.aggregate();
+        // The descriptor of the session carries the coordinator identity.
+        let descriptor = VerificationDescriptor::for_session(&session);
+        assert!(signature
+            .verify_with_descriptor(&descriptor, &group_key, b"MESSAGE", &[])
+            .is_ok());
+        let other = VerificationDescriptor {
+            coordinator_id: Some(b"COORDINATOR B".to_vec()),
+            ..descriptor
+        };
+        assert_eq!(
+            signature.verify_with_descriptor(&other, &group_key, b"MESSAGE", &[]),
+            Err(SignatureError::InvalidSignature)
+        );
+    }
 
-        assert!(signing_result.is_ok());
+    #[test]
+    fn verify_with_key_bytes() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], b"message");
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys[1..].iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+        let signers = aggregator.get_signers().clone();
+        for (secret_key, secret) in secret_keys[1..].iter().zip(secret_comshares.iter_mut()) {
+            let partial = secret_key
+                .sign(&message_hash, &group_key, secret, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        let key_bytes = group_key.to_bytes();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+        assert!(signature
+            .verify_with_key_bytes(&key_bytes, &message_hash)
+            .is_ok());
+        assert!(signature
+            .verify_with_key_bytes(&key_bytes, &compute_message_hash(&context[..], b"other"))
+            .is_err());
+
+        let other_key = (&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng))
+            .compress()
+            .to_bytes();
+        for bytes in [other_key, [0u8; 32], [0xffu8; 32]] {
+            assert_eq!(
+                signature.verify_with_key_bytes(&bytes, &message_hash),
+                Err(SignatureError::InvalidSignature)
+            );
+        }
+    }
 
-        let threshold_signature = signing_result.unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+    #[test]
+    fn dry_run_aggregation() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], b"message");
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys[1..].iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+        let signers = aggregator.get_signers().clone();
+        for (secret_key, secret) in secret_keys[1..].iter().zip(secret_comshares.iter_mut()) {
+            let report = aggregator.dry_run().unwrap_err();
+            assert_eq!(report.get(&0), Some(&"Missing remaining signer(s)"));
+            assert_eq!(
+                report.get(&secret_key.index),
+                Some(&"Missing partial signature")
+            );
+
+            let partial = secret_key
+                .sign(&message_hash, &group_key, secret, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
 
-        println!("Dealer's signing session: {:?}", verification_result);
+        // The dry run leaves the aggregator untouched, so that it can be
+        // repeated and followed by the actual aggregation.
+        let dry_run = aggregator.dry_run().unwrap();
+        assert!(dry_run.verify(&group_key, &message_hash).is_ok());
+        assert_eq!(aggregator.dry_run().unwrap(), dry_run);
 
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert_eq!(signature, dry_run);
+    }
+
+    #[test]
+    fn sign_with_stale_commitment() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
         let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
-        let message = b"This is a test of the tsunami alert system. This is only a test.";
-        let (s1_public_comshares, mut s1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
-        let (s2_public_comshares, mut s2_secret_comshares) =
+
+        // The aggregator uses last week's upload of signer 1, which has
+        // since been replaced by a fresh one.
+        let (last_week, _) = generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (current, mut p1_secret_comshares) = generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p2_public_comshares, mut p2_secret_comshares) =
             generate_commitment_share_lists(&mut OsRng, 2, 1);
-        let (s3_public_comshares, mut s3_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 3, 1);
 
-        let mut aggregator =
-            SignatureAggregator::new(s_params, group_key, &context[..], &message[..]);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], b"message");
+        aggregator
+            .include_signer(1, last_week.commitments[0], (&secret_keys[0]).into())
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
+        let session = aggregator.session();
 
-        aggregator.include_signer(1, s1_public_comshares.commitments[0], (&s1_sk).into());
-        aggregator.include_signer(2, s2_public_comshares.commitments[0], (&s2_sk).into());
-        aggregator.include_signer(3, s3_public_comshares.commitments[0], (&s3_sk).into());
+        assert_eq!(
+            secret_keys[0].sign_session(&session, &group_key, &mut p1_secret_comshares, 0),
+            Err(SignatureError::StaleCommitment {
+                expected: Box::new((
+                    current.commitments[0].0.compress(),
+                    current.commitments[0].1.compress()
+                )),
+                listed: Box::new((
+                    last_week.commitments[0].0.compress(),
+                    last_week.commitments[0].1.compress()
+                )),
+            })
+        );
+        // The commitment share was not consumed.
+        assert_eq!(p1_secret_comshares.commitments.len(), 1);
 
-        let signers = aggregator.get_signers();
-        let message_hash = compute_message_hash(&context[..], &message[..]);
+        // Signer 2 is listed with its current upload.
+        assert!(secret_keys[1]
+            .sign_session(&session, &group_key, &mut p2_secret_comshares, 0)
+            .is_ok());
+    }
 
-        let s1_partial = s1_sk
-            .sign(
-                &message_hash,
+    #[test]
+    fn verify_unhashed() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], b"message");
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys[1..].iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+        let signers = aggregator.get_signers().clone();
+        for (secret_key, secret) in secret_keys[1..].iter().zip(secret_comshares.iter_mut()) {
+            let partial = secret_key
+                .sign(&message_hash, &group_key, secret, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        // The one-step path agrees with the two-step one.
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+        assert!(signature
+            .verify_unhashed(&group_key, (&context[..], b"message"))
+            .is_ok());
+
+        // A mismatched context, a mismatched message, or swapping them is
+        // rejected.
+        for (context, message) in [
+            (&b"another context"[..], &b"message"[..]),
+            (&context[..], &b"other"[..]),
+            (&b"message"[..], &context[..]),
+        ] {
+            assert_eq!(
+                signature.verify_unhashed(&group_key, (context, message)),
+                Err(SignatureError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn verify_with_package_hash() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+
+        let public_keys: Vec<IndividualPublicKey> =
+            secret_keys.iter().rev().map(|sk| sk.to_public()).collect();
+        let package = PublicKeyPackage::new(params, group_key, public_keys.clone()).unwrap();
+        assert_eq!(
+            PublicKeyPackage::from_bytes(&package.to_bytes()).unwrap(),
+            package
+        );
+        let package_hash = package.hash();
+
+        let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], b"message");
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys[1..].iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+        let signers = aggregator.get_signers().clone();
+        for (secret_key, secret) in secret_keys[1..].iter().zip(secret_comshares.iter_mut()) {
+            let partial = secret_key
+                .sign(&message_hash, &group_key, secret, 0, &signers)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        assert!(signature
+            .verify_with_package_hash(&group_key, &package, &package_hash, &message_hash)
+            .is_ok());
+
+        // A group key which is not the one committed to by the package hash,
+        // even within an otherwise identical package.
+        let other_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng));
+        let other_package = PublicKeyPackage::new(params, other_key, public_keys).unwrap();
+        assert_ne!(other_package.hash(), package_hash);
+        assert_eq!(
+            signature.verify_with_package_hash(&other_key, &package, &package_hash, &message_hash),
+            Err(SignatureError::PackageMismatch)
+        );
+        assert_eq!(
+            signature.verify_with_package_hash(
+                &other_key,
+                &other_package,
+                &package_hash,
+                &message_hash
+            ),
+            Err(SignatureError::PackageMismatch)
+        );
+        assert_eq!(
+            signature.verify_with_package_hash(
                 &group_key,
-                &mut s1_secret_comshares,
-                0,
-                signers,
+                &package,
+                &package_hash,
+                &compute_message_hash(&context[..], b"other")
+            ),
+            Err(SignatureError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn commitment_share_signer_mismatch() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+
+        let (p1_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p3_public_comshares, mut p3_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 3, 1);
+        let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], b"message");
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
             )
             .unwrap();
-        let s2_partial = s2_sk
-            .sign(
+        aggregator
+            .include_signer(
+                3,
+                p3_public_comshares.commitments[0],
+                (&secret_keys[2]).into(),
+            )
+            .unwrap();
+        let signers = aggregator.get_signers().clone();
+
+        // Signer 1 picking up the commitment shares of signer 3.
+        assert_eq!(
+            secret_keys[0].sign(
                 &message_hash,
                 &group_key,
-                &mut s2_secret_comshares,
+                &mut p3_secret_comshares,
                 0,
-                signers,
-            )
-            .unwrap();
-        let s3_partial = s3_sk
+                &signers
+            ),
+            Err(SignatureError::CommitmentShareSignerMismatch {
+                signer: 1,
+                owner: 3,
+            })
+        );
+        // The commitment share was not consumed.
+        assert_eq!(p3_secret_comshares.commitments.len(), 1);
+        assert!(secret_keys[2]
             .sign(
                 &message_hash,
                 &group_key,
-                &mut s3_secret_comshares,
+                &mut p3_secret_comshares,
                 0,
-                signers,
+                &signers
             )
-            .unwrap();
+            .is_ok());
+    }
+
+    #[test]
+    fn half_aggregate_signatures() {
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+        let sign = |params: Parameters| {
+            let (group_key, secret_keys) = run_dkg(&params);
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &context[..], b"message");
+            let mut secret_comshares = Vec::new();
+            for secret_key in secret_keys[..params.t as usize].iter() {
+                let (public, secret) =
+                    generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+                aggregator
+                    .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                    .unwrap();
+                secret_comshares.push(secret);
+            }
+            let signers = aggregator.get_signers().clone();
+            for (secret_key, secret) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+                let partial = secret_key
+                    .sign(&message_hash, &group_key, secret, 0, &signers)
+                    .unwrap();
+                aggregator.include_partial_signature(partial).unwrap();
+            }
 
-        aggregator.include_partial_signature(s1_partial);
-        aggregator.include_partial_signature(s2_partial);
-        aggregator.include_partial_signature(s3_partial);
+            (
+                group_key,
+                aggregator.finalize().unwrap().aggregate().unwrap(),
+            )
+        };
 
-        let aggregator = aggregator.finalize().unwrap();
-        let signing_result = aggregator.aggregate();
+        let (first_key, first) = sign(Parameters { n: 3, t: 2 });
+        let (second_key, second) = sign(Parameters { n: 2, t: 2 });
 
-        assert!(signing_result.is_ok());
+        let aggregate = ThresholdSignature::batch_aggregate(
+            &message_hash,
+            &[(&first_key, &first), (&second_key, &second)],
+        );
+        assert_eq!(aggregate.len(), 2);
+        assert!(aggregate
+            .verify(&message_hash, &[&first_key, &second_key])
+            .is_ok());
 
-        let threshold_signature = signing_result.unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
+        let bytes = aggregate.to_bytes();
+        assert_eq!(bytes.len(), 4 + 32 * 3);
+        assert_eq!(HalfAggregate::from_bytes(&bytes).unwrap(), aggregate);
+        assert!(HalfAggregate::from_bytes(&bytes[..bytes.len() - 1]).is_err());
 
-        println!("Signers's signing session: {:?}", verification_result);
+        // The group keys must be given in order, and the message must match.
+        assert_eq!(
+            aggregate.verify(&message_hash, &[&second_key, &first_key]),
+            Err(SignatureError::InvalidSignature)
+        );
+        assert_eq!(
+            aggregate.verify(&message_hash, &[&first_key]),
+            Err(SignatureError::InvalidSignature)
+        );
+        assert_eq!(
+            aggregate.verify(
+                &compute_message_hash(&context[..], b"other"),
+                &[&first_key, &second_key]
+            ),
+            Err(SignatureError::InvalidSignature)
+        );
 
-        assert!(verification_result.is_ok());
+        // An aggregate including a signature under the wrong key fails.
+        let aggregate = ThresholdSignature::batch_aggregate(
+            &message_hash,
+            &[(&first_key, &first), (&first_key, &second)],
+        );
+        assert!(aggregate
+            .verify(&message_hash, &[&first_key, &first_key])
+            .is_err());
+
+        let empty = ThresholdSignature::batch_aggregate(&message_hash, &[]);
+        assert!(empty.is_empty());
+        assert!(empty.verify(&message_hash, &[]).is_err());
     }
 
     #[test]
-    fn aggregator_get_signers() {
+    fn public_lagrange_coefficient() {
+        let params = Parameters { n: 5, t: 3 };
+        let (group_key, secret_keys) = run_dkg(&params);
+
+        for signers in [[1u32, 2, 3], [1, 3, 5], [5, 2, 4]] {
+            let mut interpolated = RistrettoPoint::identity();
+            for index in signers.iter() {
+                let coefficient = lagrange_coefficient(*index, &signers).unwrap();
+                assert_eq!(
+                    Ok(coefficient),
+                    calculate_lagrange_coefficients(index, &signers)
+                );
+
+                let public_key = secret_keys[*index as usize - 1].to_public();
+                interpolated += public_key.share * coefficient;
+            }
+            assert_eq!(interpolated, group_key.0);
+        }
+
+        assert_eq!(
+            lagrange_coefficient(4, &[1, 2, 3]),
+            Err(SignatureError::UnknownSigner(4))
+        );
+        assert!(lagrange_coefficient(1, &[1, 2, 2]).is_err());
+    }
+
+    #[test]
+    fn committed_message() {
         let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
         let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
         let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let message_commitment = compute_message_hash(&context[..], &message[..]);
+
+        let signed_aggregator = || {
+            let mut aggregator = SignatureAggregator::new_committed_message(
+                params,
+                group_key,
+                &context[..],
+                message_commitment,
+            );
+            let mut secret_comshares = Vec::new();
+            for secret_key in secret_keys[1..].iter() {
+                let (public, secret) =
+                    generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+                aggregator
+                    .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                    .unwrap();
+                secret_comshares.push(secret);
+            }
+
+            // The signers only see the commitment.
+            let session = aggregator.session();
+            assert!(session.message().is_empty());
+            assert_eq!(session.message_hash(), &message_commitment);
+            for (secret_key, secret) in secret_keys[1..].iter().zip(secret_comshares.iter_mut()) {
+                let partial = secret_key
+                    .sign_session(&session, &group_key, secret, 0)
+                    .unwrap();
+                aggregator.include_partial_signature(partial).unwrap();
+            }
+
+            aggregator
+        };
+
+        // The aggregator cannot be finalized before the message is revealed.
+        let misbehaving = signed_aggregator().finalize().unwrap_err();
+        assert_eq!(misbehaving.get(&0), Some(&"Committed message not revealed"));
+
+        let mut aggregator = signed_aggregator();
+        // A message which does not match the commitment is rejected.
+        assert_eq!(
+            aggregator.reveal_message(b"This is not the message."),
+            Err(SignatureError::MessageCommitmentMismatch)
+        );
+        aggregator.reveal_message(&message[..]).unwrap();
+
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&group_key, &message_commitment).is_ok());
+
+        // Only aggregators over a commitment accept a revealed message.
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        assert_eq!(
+            aggregator.reveal_message(&message[..]),
+            Err(SignatureError::MessageCommitmentMismatch)
+        );
+    }
+
+    #[test]
+    fn signing_down_to_the_low_watermark() {
+        let params = Parameters { n: 1, t: 1 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message_hash = compute_message_hash(&context[..], b"message");
+
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 5);
+        secret_comshares.set_low_watermark(2);
+
+        let sign = |secret_comshares: &mut SecretCommitmentShareList, published_index: usize| {
+            let mut aggregator =
+                SignatureAggregator::new(params, group_key, &context[..], b"message");
+            aggregator
+                .include_signer(
+                    1,
+                    public_comshares.commitments[published_index],
+                    (&secret_keys[0]).into(),
+                )
+                .unwrap();
+            let signers = aggregator.get_signers().clone();
+            secret_keys[0].sign(&message_hash, &group_key, secret_comshares, 0, &signers)
+        };
+
+        for published_index in 0..3 {
+            assert!(!secret_comshares.is_low());
+            assert!(sign(&mut secret_comshares, published_index).is_ok());
+        }
+        assert!(secret_comshares.is_low());
+        assert_eq!(
+            sign(&mut secret_comshares, 3),
+            Err(SignatureError::CommitmentPoolLow(2))
+        );
+        // The refused signature did not consume a commitment share.
+        assert_eq!(secret_comshares.commitments.len(), 2);
+
+        // The operator may still drain the pool deliberately.
+        secret_comshares.clear_low_watermark();
+        assert!(sign(&mut secret_comshares, 3).is_ok());
+    }
 
-        let (p1_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 1, 1);
-        let (p2_public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 2, 1);
+    #[test]
+    fn session_authorization() {
+        let params = Parameters { n: 1, t: 1 };
+        let (group_key, secret_keys) = run_dkg(&params);
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
 
-        let mut aggregator = SignatureAggregator::new(
-            params,
-            GroupKey(RistrettoPoint::identity()),
-            &context[..],
-            &message[..],
+        let authorizer = AuthorizerKey::new(OsRng);
+        let public_key = authorizer.public_key();
+        assert_eq!(
+            AuthorizerPublicKey::from_bytes(&public_key.to_bytes()),
+            Ok(public_key)
         );
 
-        let p1_sk = SecretKey {
-            index: 1,
-            key: Scalar::random(&mut OsRng),
-        };
-        let p2_sk = SecretKey {
-            index: 2,
-            key: Scalar::random(&mut OsRng),
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let session = |message: &[u8]| {
+            let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], message);
+            aggregator
+                .include_signer(1, public_comshares.commitments[0], (&secret_keys[0]).into())
+                .unwrap();
+            aggregator.session()
         };
 
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
+        // A missing authorization.
+        let mut authorized = session(b"message");
+        assert_eq!(
+            secret_keys[0].sign_authorized_session(
+                &authorized,
+                &public_key,
+                &group_key,
+                &mut secret_comshares,
+                0
+            ),
+            Err(SignatureError::UnauthorizedSession)
+        );
 
-        let signers = aggregator.get_signers();
+        // Forged authorizations: by another key, for another session, or
+        // with a modified signature.
+        let other = AuthorizerKey::new(OsRng);
+        authorized.set_authorization(other.authorize(&authorized, OsRng));
+        assert_eq!(
+            public_key.verify(&authorized),
+            Err(SignatureError::UnauthorizedSession)
+        );
 
-        // The signers should be deduplicated.
-        assert!(signers.len() == 2);
+        authorized.set_authorization(authorizer.authorize(&session(b"other"), OsRng));
+        assert_eq!(
+            public_key.verify(&authorized),
+            Err(SignatureError::UnauthorizedSession)
+        );
 
-        // The indices should match and be in sorted order.
-        assert!(signers[0].participant_index == 1);
-        assert!(signers[1].participant_index == 2);
+        let mut forged = authorizer.authorize(&authorized, OsRng);
+        forged.z += Scalar::one();
+        authorized.set_authorization(forged);
+        assert_eq!(
+            secret_keys[0].sign_authorized_session(
+                &authorized,
+                &public_key,
+                &group_key,
+                &mut secret_comshares,
+                0
+            ),
+            Err(SignatureError::UnauthorizedSession)
+        );
+        // The rejected sessions did not consume the commitment share.
+        assert_eq!(secret_comshares.commitments.len(), 1);
 
-        // Participant 1 should have the correct precomputed shares.
-        assert!(signers[0].published_commitment_share.0 == p1_public_comshares.commitments[0].0);
-        assert!(signers[0].published_commitment_share.1 == p1_public_comshares.commitments[0].1);
+        // A valid authorization.
+        let authorization = authorizer.authorize(&authorized, OsRng);
+        assert_eq!(
+            SessionAuthorization::from_bytes(&authorization.to_bytes()),
+            Ok(authorization)
+        );
+        authorized.set_authorization(authorization);
+        assert_eq!(authorized.authorized_by(), Some(&authorization));
+        let partial = secret_keys[0]
+            .sign_authorized_session(
+                &authorized,
+                &public_key,
+                &group_key,
+                &mut secret_comshares,
+                0,
+            )
+            .unwrap();
 
-        // Same for participant 2.
-        assert!(signers[1].published_commitment_share.0 == p2_public_comshares.commitments[0].0);
-        assert!(signers[1].published_commitment_share.1 == p2_public_comshares.commitments[0].1);
+        let mut aggregator = SignatureAggregator::from_session(params, group_key, &authorized);
+        aggregator
+            .include_signer(1, public_comshares.commitments[0], (&secret_keys[0]).into())
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature
+            .verify(&group_key, authorized.message_hash())
+            .is_ok());
+
+        // A renewed session must be authorized again.
+        assert_eq!(authorized.renew(OsRng).authorized_by(), None);
     }
 
     #[test]
-    fn serialisation() {
-        fn do_keygen() -> Result<(Parameters, SecretKey, SecretKey, SecretKey, GroupKey), ()> {
-            let params = Parameters { n: 3, t: 2 };
-            let mut rng = OsRng;
-
-            let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
-            let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
-            let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
-
-            p2.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&p2.index, p2.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
-            p3.proof_of_secret_key
-                .as_ref()
-                .unwrap()
-                .verify(&p3.index, p3.public_key().unwrap(), "Φ")
-                .or(Err(()))?;
+    fn partial_signature_batch() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
 
-            let participants: Vec<Participant> = vec![p1.clone(), p2.clone(), p3.clone()];
-            let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-                &params,
-                &p1_dh_sk,
-                &p1.index,
-                &p1coeffs,
-                &participants,
-                "Φ",
-                &mut rng,
-            )
-            .or(Err(()))?;
-            let p1_their_encrypted_secret_shares =
-                p1_state.their_encrypted_secret_shares().or(Err(()))?;
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let (p3_public_comshares, mut p3_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 3, 1);
 
-            let (p2_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-                &params,
-                &p2_dh_sk,
-                &p2.index,
-                &p2coeffs,
-                &participants,
-                "Φ",
-                &mut rng,
+        let mut aggregator =
+            SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
             )
-            .or(Err(()))?;
-            let p2_their_encrypted_secret_shares =
-                p2_state.their_encrypted_secret_shares().or(Err(()))?;
-
-            let (p3_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
-                &params,
-                &p3_dh_sk,
-                &p3.index,
-                &p3coeffs,
-                &participants,
-                "Φ",
-                &mut rng,
+            .unwrap();
+        aggregator
+            .include_signer(
+                3,
+                p3_public_comshares.commitments[0],
+                (&secret_keys[2]).into(),
             )
-            .or(Err(()))?;
-            let p3_their_encrypted_secret_shares =
-                p3_state.their_encrypted_secret_shares().or(Err(()))?;
-
-            let p1_my_encrypted_secret_shares = vec![
-                p1_their_encrypted_secret_shares[0].clone(),
-                p2_their_encrypted_secret_shares[0].clone(),
-                p3_their_encrypted_secret_shares[0].clone(),
-            ];
-            let p2_my_encrypted_secret_shares = vec![
-                p1_their_encrypted_secret_shares[1].clone(),
-                p2_their_encrypted_secret_shares[1].clone(),
-                p3_their_encrypted_secret_shares[1].clone(),
-            ];
-            let p3_my_encrypted_secret_shares = vec![
-                p1_their_encrypted_secret_shares[2].clone(),
-                p2_their_encrypted_secret_shares[2].clone(),
-                p3_their_encrypted_secret_shares[2].clone(),
-            ];
-
-            let p1_state = p1_state
-                .to_round_two(p1_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let p2_state = p2_state
-                .to_round_two(p2_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
-            let p3_state = p3_state
-                .to_round_two(p3_my_encrypted_secret_shares, &mut rng)
-                .or(Err(()))?;
+            .unwrap();
 
-            let (p1_group_key, p1_secret_key) = p1_state.finish().or(Err(()))?;
-            let (p2_group_key, p2_secret_key) = p2_state.finish().or(Err(()))?;
-            let (p3_group_key, p3_secret_key) = p3_state.finish().or(Err(()))?;
+        let signers = aggregator.get_signers().clone();
+        let message_hash = compute_message_hash(&context[..], &message[..]);
 
-            assert!(p1_group_key.0.compress() == p2_group_key.0.compress());
-            assert!(p2_group_key.0.compress() == p3_group_key.0.compress());
+        let p1_partial = secret_keys[0]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
+        let p3_partial = secret_keys[2]
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p3_secret_comshares,
+                0,
+                &signers,
+            )
+            .unwrap();
 
-            Ok((
-                params,
-                p1_secret_key,
-                p2_secret_key,
-                p3_secret_key,
-                p1_group_key,
+        assert_eq!(
+            PartialSignatureBatch::new(vec![p1_partial, p3_partial, p1_partial]),
+            Err(SignatureError::DuplicatePartial(1))
+        );
+        let mut duplicated = PartialSignatureBatch::new(vec![p1_partial])
+            .unwrap()
+            .to_bytes();
+        duplicated[0] = 2;
+        duplicated.extend_from_slice(&p1_partial.to_bytes());
+        assert!(PartialSignatureBatch::from_bytes(&duplicated).is_err());
+
+        let batch = PartialSignatureBatch::new(vec![p3_partial, p1_partial]).unwrap();
+        let bytes = batch.to_bytes();
+        assert_eq!(bytes.len(), 4 + 2 * 36);
+        let batch = PartialSignatureBatch::from_bytes(&bytes).unwrap();
+        assert!(PartialSignatureBatch::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert_eq!(
+            PartialSignatureBatch::from_bytes_with_parameters(&bytes, &params).unwrap(),
+            batch
+        );
+        assert_eq!(
+            PartialSignatureBatch::from_bytes_with_parameters(&u32::MAX.to_le_bytes(), &params),
+            Err(Error::InvalidNumberOfParticipants(
+                u32::MAX as usize,
+                params.n
             ))
-        }
-        let keygen_protocol = do_keygen();
+        );
 
-        assert!(keygen_protocol.is_ok());
+        aggregator.include_partial_batch(batch).unwrap();
 
-        let (params, p1_sk, p2_sk, _p3_sk, group_key) = keygen_protocol.unwrap();
+        let aggregator = aggregator.finalize().unwrap();
+        let signature = aggregator.aggregate().unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
+    }
+
+    #[test]
+    fn duplicate_partial_signature_is_rejected() {
+        let params = Parameters { n: 3, t: 2 };
+        let (group_key, secret_keys) = run_dkg(&params);
 
         let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
         let message = b"This is a test of the tsunami alert system. This is only a test.";
         let (p1_public_comshares, mut p1_secret_comshares) =
-            generate_commitment_share_lists(&mut OsRng, 1, 1);
+            generate_commitment_share_lists(&mut OsRng, 1, 2);
         let (p2_public_comshares, mut p2_secret_comshares) =
             generate_commitment_share_lists(&mut OsRng, 2, 1);
 
         let mut aggregator =
             SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+        aggregator
+            .include_signer(
+                1,
+                p1_public_comshares.commitments[0],
+                (&secret_keys[0]).into(),
+            )
+            .unwrap();
+        aggregator
+            .include_signer(
+                2,
+                p2_public_comshares.commitments[0],
+                (&secret_keys[1]).into(),
+            )
+            .unwrap();
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(2, p2_public_comshares.commitments[0], (&p2_sk).into());
-
-        let signers = aggregator.get_signers();
+        let signers = aggregator.get_signers().clone();
         let message_hash = compute_message_hash(&context[..], &message[..]);
 
-        let p1_partial = p1_sk
+        let p1_partial = secret_keys[0]
             .sign(
                 &message_hash,
                 &group_key,
                 &mut p1_secret_comshares,
                 0,
-                signers,
+                &signers,
             )
             .unwrap();
-        let p2_partial = p2_sk
+        let p1_second_partial = PartialThresholdSignature {
+            index: 1,
+            z: p1_partial.z + Scalar::one(),
+        };
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        assert_eq!(
+            aggregator.include_partial_signature(p1_second_partial),
+            Err(SignatureError::DuplicatePartial(1))
+        );
+
+        let p2_partial = secret_keys[1]
             .sign(
                 &message_hash,
                 &group_key,
                 &mut p2_secret_comshares,
                 0,
-                signers,
+                &signers,
             )
             .unwrap();
+        aggregator.include_partial_signature(p2_partial).unwrap();
 
-        // Check serialisation
+        let aggregator = aggregator.finalize().unwrap();
+        let signature = aggregator.aggregate().unwrap();
+        assert!(signature.verify(&group_key, &message_hash).is_ok());
 
-        let bytes = p1_secret_comshares.to_bytes();
+        // The logged nonce commitments reconstruct the group commitment.
+        let contributions = aggregator.nonce_contributions();
         assert_eq!(
-            p1_secret_comshares,
-            SecretCommitmentShareList::from_bytes(&bytes).unwrap()
+            contributions.iter().map(|(i, _)| *i).collect::<Vec<u32>>(),
+            vec![1, 2]
+        );
+        let (binding_factors, _) = compute_binding_factors_and_group_commitment::<Ristretto25519>(
+            &message_hash,
+            &group_key,
+            &signers,
         );
+        let R: RistrettoPoint = contributions
+            .iter()
+            .map(|(index, (hiding, binding))| hiding + binding * binding_factors[index])
+            .sum();
+        assert_eq!(R, signature.R);
+    }
 
-        let bytes = p1_public_comshares.to_bytes();
+    #[test]
+    fn signer_commitment_encoding() {
+        let (public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 1, 2);
+        let mut signer = Signer::new(1, public_comshares.commitments[0]);
+        let mut fresh = Signer::new(1, public_comshares.commitments[1]);
+        let message_hash = compute_message_hash(b"CONTEXT", b"MESSAGE");
+        let group_key = GroupKey::new(RistrettoPoint::random(&mut OsRng));
+
+        // Replacing the commitments through the public field must also
+        // replace their encodings.
+        signer.published_commitment_share = public_comshares.commitments[1];
+        let (hiding, binding) = public_comshares.commitments[1];
         assert_eq!(
-            p1_public_comshares,
-            PublicCommitmentShareList::from_bytes(&bytes).unwrap()
+            signer.compressed_commitment_share(),
+            (hiding.compress(), binding.compress())
         );
-
-        let bytes = p1_partial.to_bytes();
         assert_eq!(
-            p1_partial,
-            PartialThresholdSignature::from_bytes(&bytes).unwrap()
+            compute_binding_factors_and_group_commitment::<Ristretto25519>(
+                &message_hash,
+                &group_key,
+                &[signer]
+            )
+            .0,
+            compute_binding_factors_and_group_commitment::<Ristretto25519>(
+                &message_hash,
+                &group_key,
+                &[fresh]
+            )
+            .0,
         );
 
-        // Continue signature
-
-        aggregator.include_partial_signature(p1_partial);
-        aggregator.include_partial_signature(p2_partial);
-
-        let aggregator = aggregator.finalize().unwrap();
-        let signing_result = aggregator.aggregate();
-
-        assert!(signing_result.is_ok());
-
-        let threshold_signature = signing_result.unwrap();
-        let verification_result = threshold_signature.verify(&group_key, &message_hash);
-
-        println!("{:?}", verification_result);
+        fresh.published_commitment_share = public_comshares.commitments[0];
+        assert_ne!(
+            compute_binding_factors_and_group_commitment::<Ristretto25519>(
+                &message_hash,
+                &group_key,
+                &[signer]
+            )
+            .0,
+            compute_binding_factors_and_group_commitment::<Ristretto25519>(
+                &message_hash,
+                &group_key,
+                &[fresh]
+            )
+            .0,
+        );
+    }
 
-        assert!(verification_result.is_ok());
+    #[cfg(feature = "test-vectors")]
+    #[test]
+    fn aggregate_rfc_test_vector() {
+        let hex = |s: &str| -> Vec<u8> {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+                .collect()
+        };
+        let partial = |index: u32, z: &str| {
+            let mut bytes = [0u8; 36];
+            bytes[..4].copy_from_slice(&index.to_le_bytes());
+            bytes[4..].copy_from_slice(&hex(z));
+            PartialThresholdSignature::from_bytes(&bytes).unwrap()
+        };
 
-        // Check serialisation
+        // The FROST(ristretto255, SHA-512) vector of RFC 9591, whose group
+        // commitment is derived with another binding factor than ours.
+        let expected: [u8; 64] = hex(
+            "fa954853693068803615803a06e2c23a6228f7d6d6b442b72b26696aa776fe75\
+             532350f49b27a123b0c811d54671f6c008e319741a59918baf3c5455a5ec2603",
+        )
+        .try_into()
+        .unwrap();
+        let partials = [
+            partial(
+                1,
+                "1f5adbfd775a95ce4c95c7d81b3898d89bdce160adece3168b38dc9367a20502",
+            ),
+            partial(
+                3,
+                "34c974f623cd0b5563334afc2a395ee86c0638136d6cad74240478c13d4a2101",
+            ),
+        ];
+        let R = ThresholdSignature::from_bytes(&expected).unwrap().R;
 
-        let bytes = threshold_signature.to_bytes();
-        assert_eq!(
-            threshold_signature,
-            ThresholdSignature::from_bytes(&bytes).unwrap()
-        );
+        let signature = ThresholdSignature::aggregate_with_nonce(R, &partials);
+        assert_eq!(signature.to_bytes(), expected);
     }
 }
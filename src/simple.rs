@@ -0,0 +1,220 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! The shortest path through the crate: a trusted-dealer key generation and
+//! an in-process signing helper.
+//!
+//! These helpers run every step of the protocol in the same process, so they
+//! are only meant for evaluation and for the integration tests of higher
+//! level systems.  They are built on the production primitives, with all of
+//! their validation, and are gated behind the `simple` feature.
+//!
+//! ```rust
+//! # use rand::rngs::OsRng;
+//! let (group_key, shares) = ice_frost::simple::keygen(5, 3, &mut OsRng).unwrap();
+//!
+//! let signature = ice_frost::simple::sign(&shares[..3], b"message", &mut OsRng).unwrap();
+//! assert!(ice_frost::simple::verify(&group_key, b"message", &signature).is_ok());
+//! ```
+
+#[cfg(feature = "std")]
+use std::string::ToString;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::scalar::Scalar;
+
+use rand::CryptoRng;
+use rand::Rng;
+
+use crate::keygen::Coefficients;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::SecretKey;
+use crate::keygen::SecretShare;
+use crate::parameters::Parameters;
+use crate::precomputation::generate_commitment_share_lists;
+use crate::signature::compute_message_hash;
+use crate::signature::SignatureAggregator;
+use crate::signature::SignatureError;
+use crate::signature::ThresholdSignature;
+
+/// The context string of the messages signed with [`sign`].
+pub const CONTEXT: &[u8] = b"ICE-FROST-SIMPLE";
+
+/// A participant's share of a group key generated by [`keygen`].
+#[derive(Debug)]
+pub struct KeyShare {
+    /// The parameters of the group.
+    pub parameters: Parameters,
+    /// The group public key.
+    pub group_key: GroupKey,
+    /// The participant's secret key.
+    pub secret_key: SecretKey,
+}
+
+impl KeyShare {
+    /// Create another copy of this key share, whose secret key is zeroed out
+    /// when dropped independently of this one.
+    pub fn duplicate(&self) -> KeyShare {
+        KeyShare {
+            parameters: self.parameters,
+            group_key: self.group_key,
+            secret_key: self.secret_key.duplicate(),
+        }
+    }
+}
+
+/// Generate a \\(t\\)-out-of-\\(n\\) group key with a trusted dealer, and
+/// the shares of participants \\(1\\) to \\(n\\).
+///
+/// # Returns
+///
+/// An `InvalidNumberOfParticipants` error unless \\(1 \leq t \leq n\\).
+pub fn keygen(
+    n: u32,
+    t: u32,
+    mut rng: impl Rng + CryptoRng,
+) -> Result<(GroupKey, Vec<KeyShare>), Error> {
+    if t == 0 || t > n {
+        return Err(Error::InvalidNumberOfParticipants(t as usize, n));
+    }
+    let parameters = Parameters { n, t };
+
+    let coefficients = Coefficients((0..t).map(|_| Scalar::random(&mut rng)).collect());
+    let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &coefficients.0[0]);
+
+    let shares = (1..=n)
+        .map(|index| {
+            let share = SecretShare::evaluate_polynomial(&0, &index, &coefficients);
+
+            KeyShare {
+                parameters,
+                group_key,
+                secret_key: SecretKey {
+                    index,
+                    key: share.polynomial_evaluation,
+                },
+            }
+        })
+        .collect();
+
+    Ok((group_key, shares))
+}
+
+/// Sign a `message` with the given key shares, which must all belong to the
+/// same group and be at least as many as its threshold.
+///
+/// The commitment shares, signing session, partial signatures and their
+/// aggregation are all handled in-process.
+pub fn sign(
+    shares: &[KeyShare],
+    message: &[u8],
+    mut rng: impl Rng + CryptoRng,
+) -> Result<ThresholdSignature, SignatureError> {
+    let first = shares
+        .first()
+        .ok_or_else(|| SignatureError::Custom("No key shares were given".to_string()))?;
+    if shares
+        .iter()
+        .any(|s| s.parameters != first.parameters || s.group_key != first.group_key)
+    {
+        return Err(SignatureError::Custom(
+            "The key shares belong to different groups".to_string(),
+        ));
+    }
+
+    let mut aggregator =
+        SignatureAggregator::new(first.parameters, first.group_key, CONTEXT, message);
+
+    let mut secret_comshares = Vec::with_capacity(shares.len());
+    for share in shares.iter() {
+        let (public_comshares, secret) =
+            generate_commitment_share_lists(&mut rng, share.secret_key.index, 1);
+        aggregator.include_signer(
+            share.secret_key.index,
+            public_comshares.commitments[0],
+            share.secret_key.to_public(),
+        )?;
+        secret_comshares.push(secret);
+    }
+
+    let signers = aggregator.get_signers().clone();
+    let message_hash = compute_message_hash(CONTEXT, message);
+    for (share, secret) in shares.iter().zip(secret_comshares.iter_mut()) {
+        let partial =
+            share
+                .secret_key
+                .sign(&message_hash, &first.group_key, secret, 0, &signers)?;
+        aggregator.include_partial_signature(partial)?;
+    }
+
+    let aggregator = aggregator
+        .finalize()
+        .map_err(|_| SignatureError::InvalidSignature)?;
+    let signature = aggregator
+        .aggregate()
+        .map_err(|_| SignatureError::InvalidSignature)?;
+
+    verify(&first.group_key, message, &signature)?;
+
+    Ok(signature)
+}
+
+/// Verify a `signature` over a `message` produced by [`sign`].
+pub fn verify(
+    group_key: &GroupKey,
+    message: &[u8],
+    signature: &ThresholdSignature,
+) -> Result<(), SignatureError> {
+    signature.verify(group_key, &compute_message_hash(CONTEXT, message))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn simple_three_out_of_five() {
+        let (group_key, shares) = keygen(5, 3, OsRng).unwrap();
+
+        let signers = [
+            shares[0].duplicate(),
+            shares[3].duplicate(),
+            shares[4].duplicate(),
+        ];
+        let signature = sign(&signers, b"message", OsRng).unwrap();
+        assert!(verify(&group_key, b"message", &signature).is_ok());
+        assert!(verify(&group_key, b"another message", &signature).is_err());
+
+        assert!(sign(&signers[..2], b"message", OsRng).is_err());
+        assert!(sign(&[], b"message", OsRng).is_err());
+
+        let (_, other_shares) = keygen(5, 3, OsRng).unwrap();
+        let mixed = [
+            shares[0].duplicate(),
+            shares[3].duplicate(),
+            other_shares[4].duplicate(),
+        ];
+        assert!(sign(&mixed, b"message", OsRng).is_err());
+
+        assert!(keygen(2, 3, OsRng).is_err());
+        assert!(keygen(2, 0, OsRng).is_err());
+    }
+}
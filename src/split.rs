@@ -0,0 +1,525 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Splitting a group into two independent sub-groups.
+//!
+//! Each sub-group runs its own distributed key generation, resulting in a
+//! fresh group key, but its participants keep their index and long-term
+//! Diffie-Hellman key from the parent group.  The key generations are
+//! bound to the parent group through context strings derived from the hash
+//! of its [`GroupPublicInfo`], and their outcomes are linked to it by a
+//! [`SplitRecord`], which auditors can check.
+
+#[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::convert::TryInto;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+
+use rand::CryptoRng;
+use rand::RngCore;
+
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::attestation::GroupPublicInfo;
+use crate::keygen::Coefficients;
+use crate::keygen::DHPrivateKey;
+use crate::keygen::DHPublicKey;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::Participant;
+use crate::parameters::Parameters;
+
+/// The layout of the split of a parent group into two sub-groups, from which
+/// each participant derives the key generation of its sub-group.
+#[derive(Clone, Debug)]
+pub struct GroupSplit {
+    /// The public information of the parent group.
+    parent: GroupPublicInfo,
+    /// The parameters and sorted indices of the two sub-groups.
+    children: [(Parameters, Vec<u32>); 2],
+}
+
+impl GroupSplit {
+    /// Split the `parent` group into a sub-group of the participants with
+    /// the `first` indices, and one of those with the `second` indices, with
+    /// the given `thresholds`.
+    ///
+    /// # Returns
+    ///
+    /// An `UnknownParticipant` error if an index is not in the parent roster,
+    /// a `DuplicateParticipant` error if an index is given twice, or an
+    /// `InvalidNumberOfParticipants` error if a threshold is zero or larger
+    /// than its sub-group.
+    pub fn new(
+        parent: &GroupPublicInfo,
+        first: &[u32],
+        second: &[u32],
+        thresholds: [u32; 2],
+    ) -> Result<GroupSplit, Error> {
+        parent.roster_tree()?;
+
+        let mut seen: Vec<u32> = Vec::with_capacity(first.len() + second.len());
+        for index in first.iter().chain(second.iter()) {
+            if !parent.roster.iter().any(|(i, _)| i == index) {
+                return Err(Error::UnknownParticipant(*index));
+            }
+            if seen.contains(index) {
+                return Err(Error::DuplicateParticipant(*index));
+            }
+            seen.push(*index);
+        }
+
+        let child = |indices: &[u32], t: u32| {
+            let n = indices.len() as u32;
+            if t == 0 || t > n {
+                return Err(Error::InvalidNumberOfParticipants(t as usize, n));
+            }
+            let mut indices = indices.to_vec();
+            indices.sort_unstable();
+
+            Ok((Parameters { n, t }, indices))
+        };
+
+        Ok(GroupSplit {
+            parent: parent.clone(),
+            children: [child(first, thresholds[0])?, child(second, thresholds[1])?],
+        })
+    }
+
+    /// The parameters of the sub-group `child`, which is 0 or 1.
+    pub fn parameters(&self, child: usize) -> Parameters {
+        self.children[child].0
+    }
+
+    /// The sorted indices of the participants of the sub-group `child`,
+    /// which is 0 or 1.
+    pub fn indices(&self, child: usize) -> &[u32] {
+        &self.children[child].1
+    }
+
+    /// The sub-group the participant with the given `index` belongs to, if
+    /// any.
+    pub fn child_of(&self, index: u32) -> Option<usize> {
+        self.children
+            .iter()
+            .position(|(_, indices)| indices.contains(&index))
+    }
+
+    /// The session identifier of the key generation of the sub-group
+    /// `child`, which is 0 or 1, derived from the hash of the parent group
+    /// public information and from the layout of the sub-group.
+    pub fn session_id(&self, child: usize) -> [u8; 32] {
+        let (parameters, indices) = &self.children[child];
+
+        session_id(&self.parent.hash(), child as u8, parameters, indices)
+    }
+
+    /// The context string of the key generation of the sub-group `child`,
+    /// which is 0 or 1, i.e. the hexadecimal encoding of its session
+    /// identifier.
+    pub fn context_string(&self, child: usize) -> String {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        let mut context_string = String::with_capacity(64);
+        for byte in self.session_id(child).iter() {
+            context_string.push(HEX[(byte >> 4) as usize] as char);
+            context_string.push(HEX[(byte & 0xf) as usize] as char);
+        }
+        context_string
+    }
+
+    /// Construct the dealer of the participant with the given `index` for
+    /// the key generation of its sub-group, reusing its long-term
+    /// Diffie-Hellman private key from the parent group.
+    ///
+    /// The dealer should then run
+    /// [`DistributedKeyGeneration::new_initial`](crate::keygen::DistributedKeyGeneration::new_initial)
+    /// with the other participants of its sub-group, the parameters and the
+    /// context string of the sub-group.
+    ///
+    /// # Returns
+    ///
+    /// The sub-group of the participant, its [`Participant`] and secret
+    /// polynomial [`Coefficients`], or an `UnknownParticipant` error if the
+    /// participant is in no sub-group, or is in the parent roster under
+    /// another Diffie-Hellman key.
+    pub fn new_dealer(
+        &self,
+        index: u32,
+        dh_private_key: &DHPrivateKey,
+        rng: impl RngCore + CryptoRng,
+    ) -> Result<(usize, Participant, Coefficients), Error> {
+        let child = self
+            .child_of(index)
+            .ok_or(Error::UnknownParticipant(index))?;
+        let dh_public_key = DHPublicKey(&RISTRETTO_BASEPOINT_TABLE * &dh_private_key.0);
+        if self.parent_dh_public_key(index) != Some(&dh_public_key) {
+            return Err(Error::UnknownParticipant(index));
+        }
+
+        let (participant, coefficients) = Participant::new_dealer_with_dh_private_key(
+            &self.parameters(child),
+            index,
+            dh_private_key,
+            &self.context_string(child),
+            rng,
+        );
+
+        Ok((child, participant, coefficients))
+    }
+
+    /// Gather the outcomes of the key generations of both sub-groups, each
+    /// being the resulting group key and the participants of the sub-group.
+    ///
+    /// # Returns
+    ///
+    /// The [`GroupPublicInfo`] of both sub-groups and the [`SplitRecord`]
+    /// linking them to the parent group, or an `UnknownParticipant` error if
+    /// a participant does not belong to the sub-group under its parent
+    /// Diffie-Hellman key.
+    pub fn finish(
+        &self,
+        outcomes: [(GroupKey, &[Participant]); 2],
+    ) -> Result<([GroupPublicInfo; 2], SplitRecord), Error> {
+        let mut children: Vec<GroupPublicInfo> = Vec::with_capacity(2);
+        for (child, (group_key, participants)) in outcomes.iter().enumerate() {
+            for p in participants.iter() {
+                if self.child_of(p.index) != Some(child)
+                    || self.parent_dh_public_key(p.index) != Some(&p.dh_public_key)
+                {
+                    return Err(Error::UnknownParticipant(p.index));
+                }
+            }
+            children.push(GroupPublicInfo::new(
+                self.parameters(child),
+                *group_key,
+                participants,
+            )?);
+        }
+
+        let record = SplitRecord {
+            parent: self.parent.hash(),
+            session_ids: [self.session_id(0), self.session_id(1)],
+            children: [children[0].hash(), children[1].hash()],
+        };
+        let second = children.pop().unwrap();
+        let first = children.pop().unwrap();
+
+        Ok(([first, second], record))
+    }
+
+    fn parent_dh_public_key(&self, index: u32) -> Option<&DHPublicKey> {
+        self.parent
+            .roster
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, key)| key)
+    }
+}
+
+fn session_id(parent: &[u8; 32], child: u8, parameters: &Parameters, indices: &[u32]) -> [u8; 32] {
+    let mut h = Sha256::new();
+
+    h.update(b"ICE-FROST-SPLIT");
+    h.update(parent);
+    h.update([child]);
+    h.update(parameters.to_bytes());
+    for index in indices.iter() {
+        h.update(index.to_le_bytes());
+    }
+
+    let mut output = [0u8; 32];
+
+    output.copy_from_slice(h.finalize().as_slice());
+    output
+}
+
+/// An audit record linking the two sub-groups of a split to their parent
+/// group.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SplitRecord {
+    /// The hash of the parent [`GroupPublicInfo`].
+    pub parent: [u8; 32],
+    /// The session identifiers of the key generations of both sub-groups.
+    pub session_ids: [[u8; 32]; 2],
+    /// The hashes of the [`GroupPublicInfo`] of both sub-groups.
+    pub children: [[u8; 32]; 2],
+}
+
+impl SplitRecord {
+    /// Check that this record links the two `children` to the `parent` group,
+    /// and that their session identifiers derive from the parent group and
+    /// their own layout.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidSplitRecord` error if any hash or session identifier does
+    /// not match, if the sub-groups share a participant, or if one of their
+    /// participants is not in the parent roster under the same
+    /// Diffie-Hellman key.
+    pub fn verify(
+        &self,
+        parent: &GroupPublicInfo,
+        children: &[GroupPublicInfo; 2],
+    ) -> Result<(), Error> {
+        let parent_hash = parent.hash();
+        if self.parent != parent_hash {
+            return Err(Error::InvalidSplitRecord);
+        }
+
+        for (child, info) in children.iter().enumerate() {
+            for entry in info.roster.iter() {
+                if !parent.roster.contains(entry) {
+                    return Err(Error::InvalidSplitRecord);
+                }
+            }
+            let indices: Vec<u32> = info.roster.iter().map(|(index, _)| *index).collect();
+            if session_id(&parent_hash, child as u8, &info.parameters, &indices)
+                != self.session_ids[child]
+                || info.hash() != self.children[child]
+            {
+                return Err(Error::InvalidSplitRecord);
+            }
+        }
+
+        if children[0]
+            .roster
+            .iter()
+            .any(|(i, _)| children[1].roster.iter().any(|(j, _)| i == j))
+        {
+            return Err(Error::InvalidSplitRecord);
+        }
+
+        Ok(())
+    }
+
+    /// Serialise this split record to an array of bytes
+    pub fn to_bytes(&self) -> [u8; 160] {
+        let mut res = [0u8; 160];
+        res[0..32].copy_from_slice(&self.parent);
+        res[32..64].copy_from_slice(&self.session_ids[0]);
+        res[64..96].copy_from_slice(&self.session_ids[1]);
+        res[96..128].copy_from_slice(&self.children[0]);
+        res[128..160].copy_from_slice(&self.children[1]);
+
+        res
+    }
+
+    /// Deserialise this array of bytes to a `SplitRecord`
+    pub fn from_bytes(bytes: &[u8; 160]) -> Result<SplitRecord, Error> {
+        let hash = |start: usize| -> Result<[u8; 32], Error> {
+            bytes[start..start + 32]
+                .try_into()
+                .map_err(|_| Error::SerialisationError)
+        };
+
+        Ok(SplitRecord {
+            parent: hash(0)?,
+            session_ids: [hash(32)?, hash(64)?],
+            children: [hash(96)?, hash(128)?],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keygen::DistributedKeyGeneration;
+    use crate::keygen::RoundOne;
+    use crate::keygen::SecretKey;
+    use crate::precomputation::generate_commitment_share_lists;
+    use crate::signature::SignatureAggregator;
+
+    use curve25519_dalek::scalar::Scalar;
+
+    use rand::rngs::OsRng;
+
+    /// Run the key generation of the sub-group `child` among the given
+    /// parent participants and their Diffie-Hellman private keys.
+    fn run_child_dkg(
+        split: &GroupSplit,
+        child: usize,
+        dh_private_keys: &[DHPrivateKey],
+    ) -> (GroupKey, Vec<Participant>, Vec<SecretKey>) {
+        let parameters = split.parameters(child);
+        let context_string = split.context_string(child);
+
+        let mut dealers = Vec::new();
+        for index in split.indices(child).iter() {
+            let dh_private_key = &dh_private_keys[*index as usize - 1];
+            let (c, participant, coefficients) =
+                split.new_dealer(*index, dh_private_key, OsRng).unwrap();
+            assert_eq!(c, child);
+            dealers.push((participant, coefficients, dh_private_key));
+        }
+        let participants: Vec<Participant> = dealers.iter().map(|d| d.0.clone()).collect();
+
+        let mut states = Vec::new();
+        let mut shares = Vec::new();
+        for (participant, coefficients, dh_private_key) in dealers.iter() {
+            let (state, _) = DistributedKeyGeneration::<RoundOne>::new_initial(
+                &parameters,
+                dh_private_key,
+                &participant.index,
+                coefficients,
+                &participants,
+                &context_string,
+                OsRng,
+            )
+            .unwrap();
+            shares.extend(state.their_encrypted_secret_shares().unwrap().clone());
+            states.push(state);
+        }
+
+        let mut group_keys = Vec::new();
+        let mut secret_keys = Vec::new();
+        for (state, participant) in states.into_iter().zip(participants.iter()) {
+            let my_shares = shares
+                .iter()
+                .filter(|s| s.receiver_index == participant.index)
+                .cloned()
+                .collect();
+            let state = state.to_round_two(my_shares, OsRng).unwrap();
+            let (group_key, secret_key) = state.finish().unwrap();
+            group_keys.push(group_key);
+            secret_keys.push(secret_key);
+        }
+        assert!(group_keys.iter().all(|key| *key == group_keys[0]));
+
+        (group_keys[0], participants, secret_keys)
+    }
+
+    fn sign(
+        parameters: Parameters,
+        group_key: GroupKey,
+        secret_keys: &[SecretKey],
+        message: &[u8],
+    ) -> bool {
+        let mut aggregator = SignatureAggregator::new(parameters, group_key, b"SPLIT", message);
+        let mut secret_comshares = Vec::new();
+        for secret_key in secret_keys.iter() {
+            let (public, secret) = generate_commitment_share_lists(&mut OsRng, secret_key.index, 1);
+            aggregator
+                .include_signer(secret_key.index, public.commitments[0], secret_key.into())
+                .unwrap();
+            secret_comshares.push(secret);
+        }
+
+        let session = aggregator.session();
+        for (secret_key, secret) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+            let partial = secret_key
+                .sign_session(&session, &group_key, secret, 0)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+        }
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+        signature.verify(&group_key, session.message_hash()).is_ok()
+    }
+
+    #[test]
+    fn split_nine_into_five_and_four() {
+        let parameters = Parameters { n: 9, t: 5 };
+        let mut parent_participants = Vec::new();
+        let mut dh_private_keys = Vec::new();
+        for index in 1..=9 {
+            let (participant, _, dh_private_key) =
+                Participant::new_dealer(&parameters, index, "Φ", OsRng);
+            parent_participants.push(participant);
+            dh_private_keys.push(dh_private_key);
+        }
+        let parent_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &Scalar::random(&mut OsRng));
+        let parent = GroupPublicInfo::new(parameters, parent_key, &parent_participants).unwrap();
+
+        assert_eq!(
+            GroupSplit::new(&parent, &[1, 2, 3, 10], &[4, 5], [2, 2]).unwrap_err(),
+            Error::UnknownParticipant(10)
+        );
+        assert_eq!(
+            GroupSplit::new(&parent, &[1, 2, 3], &[3, 4], [2, 2]).unwrap_err(),
+            Error::DuplicateParticipant(3)
+        );
+        assert!(GroupSplit::new(&parent, &[1, 2, 3], &[4, 5], [4, 2]).is_err());
+
+        let split = GroupSplit::new(&parent, &[1, 3, 5, 7, 9], &[8, 6, 4, 2], [3, 3]).unwrap();
+        assert_eq!(split.indices(1), &[2, 4, 6, 8]);
+        assert_ne!(split.session_id(0), split.session_id(1));
+        assert_eq!(split.context_string(0).len(), 64);
+
+        // A participant must reuse its parent Diffie-Hellman key.
+        assert!(matches!(
+            split.new_dealer(1, &dh_private_keys[1], OsRng),
+            Err(Error::UnknownParticipant(1))
+        ));
+
+        let (first_key, first_participants, first_secret_keys) =
+            run_child_dkg(&split, 0, &dh_private_keys);
+        let (second_key, second_participants, second_secret_keys) =
+            run_child_dkg(&split, 1, &dh_private_keys);
+        assert_ne!(first_key, second_key);
+        assert_ne!(first_key, parent_key);
+
+        assert!(split
+            .finish([
+                (first_key, &second_participants),
+                (second_key, &first_participants)
+            ])
+            .is_err());
+        let (children, record) = split
+            .finish([
+                (first_key, &first_participants),
+                (second_key, &second_participants),
+            ])
+            .unwrap();
+        assert_eq!(children[0].group_key, first_key);
+        assert_eq!(children[1].parameters, Parameters { n: 4, t: 3 });
+
+        assert!(record.verify(&parent, &children).is_ok());
+        let record = SplitRecord::from_bytes(&record.to_bytes()).unwrap();
+        assert!(record.verify(&parent, &children).is_ok());
+        let swapped = [children[1].clone(), children[0].clone()];
+        assert_eq!(
+            record.verify(&parent, &swapped),
+            Err(Error::InvalidSplitRecord)
+        );
+        let mut tampered = record;
+        tampered.parent[0] ^= 1;
+        assert_eq!(
+            tampered.verify(&parent, &children),
+            Err(Error::InvalidSplitRecord)
+        );
+
+        // Both sub-groups sign independently under their own keys.
+        assert!(sign(
+            split.parameters(0),
+            first_key,
+            &first_secret_keys[1..4],
+            b"first"
+        ));
+        assert!(sign(
+            split.parameters(1),
+            second_key,
+            &second_secret_keys[..3],
+            b"second"
+        ));
+    }
+}
@@ -0,0 +1,1854 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Persistence of a signer's commitment shares and open sessions.
+//!
+//! A [`StateStore`] is a key-value store of blobs with a compare-and-swap
+//! primitive, which applications can implement over their own database.
+//! A [`CommitmentPool`] keeps a [`SecretCommitmentShareList`] in such a
+//! store, and only releases a partial signature once the commitment share it
+//! used has been durably marked as consumed.  A process dying at any point
+//! can thus never lead to the same nonces being used for two signatures.
+//!
+//! A [`SignerSessionManager`] additionally records the sessions a signer
+//! takes part in, so that a partial signature can be sent again for the same
+//! session, but never computed twice.  With a [`ConflictPolicy`], it also
+//! records the message signed for each application intent, so that no two
+//! different messages are ever signed for the same intent.  With a session
+//! lifetime, it refuses to sign sessions opened too long ago, according to
+//! its [`Clock`].
+//!
+//! On the aggregator side, a [`PublicCommitmentBatchValidator`] validates a
+//! large batch of public commitment shares uploaded by a signer one share at
+//! a time, spooling it to a store from which the share used by a session is
+//! later fetched.  A [`SequenceTracker`] records the last signature of each
+//! group, so that the signatures of a group form a single hash chain, see
+//! [`SequenceBinding`].
+
+#[cfg(feature = "std")]
+use std::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+
+use core::convert::TryInto;
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::traits::Identity;
+
+use sha2::{Digest, Sha256};
+
+use zeroize::Zeroize;
+
+use crate::clock::Clock;
+use crate::clock::ClockHandle;
+use crate::encoding::decode_point_checked;
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::keygen::SecretKey;
+use crate::metrics;
+use crate::metrics::Metrics;
+use crate::metrics::MetricsHandle;
+use crate::precomputation::SecretCommitmentShareList;
+use crate::signature::AuthorizerPublicKey;
+use crate::signature::PartialThresholdSignature;
+use crate::signature::SequenceBinding;
+use crate::signature::SignatureError;
+use crate::signature::SigningSession;
+use crate::signature::ThresholdSignature;
+
+/// A key-value store of blobs, in which a signer's state is persisted.
+///
+/// Implementations must make every single operation atomic and durable: once
+/// it has returned, its effect must survive a crash of the process.
+pub trait StateStore {
+    /// Get the value stored under `key`, if any.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
+
+    /// Store `value` under `key`, replacing any previous value.
+    fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), Error>;
+
+    /// Remove the value stored under `key`, if any.
+    fn delete(&mut self, key: &[u8]) -> Result<(), Error>;
+
+    /// Replace the value stored under `key` with `new`, or remove it if
+    /// `new` is `None`, only if its current value is `current`, where `None`
+    /// stands for the absence of a value.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the value was replaced, and `false` if the current value
+    /// differed.
+    fn compare_and_swap(
+        &mut self,
+        key: &[u8],
+        current: Option<&[u8]>,
+        new: Option<&[u8]>,
+    ) -> Result<bool, Error>;
+}
+
+/// A [`StateStore`] held in memory, mostly useful for tests.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl MemoryStore {
+    /// Construct a new, empty store.
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.entries.get(key).cloned())
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        if let Some(mut old) = self.entries.insert(key.to_vec(), value.to_vec()) {
+            old.zeroize();
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &[u8]) -> Result<(), Error> {
+        if let Some(mut old) = self.entries.remove(key) {
+            old.zeroize();
+        }
+        Ok(())
+    }
+
+    fn compare_and_swap(
+        &mut self,
+        key: &[u8],
+        current: Option<&[u8]>,
+        new: Option<&[u8]>,
+    ) -> Result<bool, Error> {
+        if self.entries.get(key).map(|v| v.as_slice()) != current {
+            return Ok(false);
+        }
+        match new {
+            Some(value) => self.put(key, value)?,
+            None => self.delete(key)?,
+        }
+        Ok(true)
+    }
+}
+
+impl Drop for MemoryStore {
+    fn drop(&mut self) {
+        for value in self.entries.values_mut() {
+            value.zeroize();
+        }
+    }
+}
+
+/// A [`StateStore`] keeping each value in its own file of a directory.
+///
+/// Values are written to a temporary file which is synced and then renamed
+/// over the previous one, so that a crash never leaves a partially written
+/// value behind.  The directory must only be used by one store at a time.
+///
+/// # Warning
+///
+/// Values are written in the clear: the directory should be on an encrypted
+/// volume when it holds secret commitment shares.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    directory: std::path::PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl FileStore {
+    /// Open a store in the given `directory`, creating it if needed.
+    pub fn open(directory: impl AsRef<std::path::Path>) -> Result<FileStore, Error> {
+        let directory = directory.as_ref().to_path_buf();
+        std::fs::create_dir_all(&directory).map_err(io_error)?;
+
+        Ok(FileStore { directory })
+    }
+
+    fn path(&self, key: &[u8]) -> std::path::PathBuf {
+        let mut name = std::string::String::with_capacity(2 * key.len() + 2);
+        name.push('k');
+        for byte in key.iter() {
+            name.push_str(&std::format!("{:02x}", byte));
+        }
+        self.directory.join(name)
+    }
+}
+
+#[cfg(feature = "std")]
+fn io_error(error: std::io::Error) -> Error {
+    Error::StorageError(error.to_string())
+}
+
+#[cfg(feature = "std")]
+impl StateStore for FileStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match std::fs::read(self.path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(io_error(error)),
+        }
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        use std::io::Write;
+
+        let path = self.path(key);
+        let temporary = path.with_extension("tmp");
+        let mut file = std::fs::File::create(&temporary).map_err(io_error)?;
+        file.write_all(value).map_err(io_error)?;
+        file.sync_all().map_err(io_error)?;
+        std::fs::rename(&temporary, &path).map_err(io_error)
+    }
+
+    fn delete(&mut self, key: &[u8]) -> Result<(), Error> {
+        match std::fs::remove_file(self.path(key)) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(io_error(error)),
+        }
+    }
+
+    fn compare_and_swap(
+        &mut self,
+        key: &[u8],
+        current: Option<&[u8]>,
+        new: Option<&[u8]>,
+    ) -> Result<bool, Error> {
+        // The directory is owned by this store, which is borrowed mutably,
+        // so nothing can change the value between this read and the write.
+        let mut value = self.get(key)?;
+        let matches = value.as_deref() == current;
+        value.zeroize();
+        if !matches {
+            return Ok(false);
+        }
+        match new {
+            Some(value) => self.put(key, value)?,
+            None => self.delete(key)?,
+        }
+        Ok(true)
+    }
+}
+
+fn storage_error(error: Error) -> SignatureError {
+    match error {
+        Error::StorageError(message) => SignatureError::StorageError(message),
+        error => SignatureError::Custom(error.to_string()),
+    }
+}
+
+/// A [`SecretCommitmentShareList`] persisted in a [`StateStore`].
+///
+/// Every operation reads the list from the store and writes it back with a
+/// compare-and-swap, so that the store is the only source of truth.
+#[derive(Debug)]
+pub struct CommitmentPool<S: StateStore> {
+    store: S,
+    key: Vec<u8>,
+    low_watermark: Option<usize>,
+    authorizer: Option<AuthorizerPublicKey>,
+    metrics: MetricsHandle,
+}
+
+impl<S: StateStore> CommitmentPool<S> {
+    /// Construct a pool kept under `key` in the given `store`.
+    pub fn new(store: S, key: &[u8]) -> CommitmentPool<S> {
+        CommitmentPool {
+            store,
+            key: key.to_vec(),
+            low_watermark: None,
+            authorizer: None,
+            metrics: MetricsHandle::default(),
+        }
+    }
+
+    /// Store a freshly generated `list` of commitment shares in the pool.
+    ///
+    /// # Returns
+    ///
+    /// A `ConcurrentUpdate` error if the pool already holds a list, which
+    /// must never be overwritten lest its consumed shares be used again.
+    pub fn initialise(&mut self, list: &SecretCommitmentShareList) -> Result<(), SignatureError> {
+        let mut bytes = list.to_bytes();
+        let swapped = self.store.compare_and_swap(&self.key, None, Some(&bytes));
+        bytes.zeroize();
+
+        match swapped.map_err(storage_error)? {
+            true => Ok(()),
+            false => Err(SignatureError::ConcurrentUpdate),
+        }
+    }
+
+    /// Refuse to sign when doing so would leave fewer than `n` unused
+    /// commitment shares, as with
+    /// [`SecretCommitmentShareList::set_low_watermark`].
+    pub fn set_low_watermark(&mut self, n: usize) {
+        self.low_watermark = Some(n);
+    }
+
+    /// Only sign the sessions authorized by the given `authorizer`, as with
+    /// [`SecretKey::sign_authorized_session`].
+    pub fn set_authorizer(&mut self, authorizer: AuthorizerPublicKey) {
+        self.authorizer = Some(authorizer);
+    }
+
+    /// Report the depletion of this pool, and the number of commitment
+    /// shares left after each signature, to `metrics`.
+    pub fn set_metrics<M: Metrics + 'static>(&mut self, metrics: M) {
+        self.metrics = MetricsHandle::new(metrics);
+    }
+
+    /// Load the list of commitment shares currently in the pool.
+    pub fn load(&self) -> Result<SecretCommitmentShareList, SignatureError> {
+        let mut bytes = self
+            .store
+            .get(&self.key)
+            .map_err(storage_error)?
+            .ok_or(SignatureError::MissingCommitmentShares)?;
+        let list = SecretCommitmentShareList::from_bytes(&bytes);
+        bytes.zeroize();
+
+        list.map_err(|e| SignatureError::Custom(e.to_string()))
+    }
+
+    /// Whether the commitment share published at `published_index` was
+    /// already used.
+    pub fn is_consumed(&self, published_index: u32) -> Result<bool, SignatureError> {
+        Ok(self.load()?.is_consumed(published_index))
+    }
+
+    /// Compute a partial signature for a `session` with the commitment share
+    /// published at `published_index`, as with [`SecretKey::sign_session`].
+    ///
+    /// The partial signature is only returned once the commitment share has
+    /// been marked as consumed in the store.  If the process dies before
+    /// that, the partial signature was never released, and the commitment
+    /// share may safely be used again.
+    ///
+    /// # Returns
+    ///
+    /// A `MissingCommitmentShares` error if the commitment share was already
+    /// used, an `UnauthorizedSession` error if an authorizer was set and did
+    /// not authorize the session, or a `ConcurrentUpdate` error if the pool
+    /// was modified while signing, in which case nothing was released.
+    pub fn sign(
+        &mut self,
+        secret_key: &SecretKey,
+        session: &SigningSession,
+        group_key: &GroupKey,
+        published_index: u32,
+    ) -> Result<PartialThresholdSignature, SignatureError> {
+        if let Some(authorizer) = self.authorizer.as_ref() {
+            authorizer.verify(session)?;
+        }
+
+        let mut current = self
+            .store
+            .get(&self.key)
+            .map_err(storage_error)?
+            .ok_or(SignatureError::MissingCommitmentShares)?;
+        let list = SecretCommitmentShareList::from_bytes(&current);
+        let mut list = match list {
+            Ok(list) => list,
+            Err(error) => {
+                current.zeroize();
+                return Err(SignatureError::Custom(error.to_string()));
+            }
+        };
+        if let Some(n) = self.low_watermark {
+            list.set_low_watermark(n);
+        }
+
+        let partial = list
+            .position(published_index)
+            .ok_or(SignatureError::MissingCommitmentShares)
+            .and_then(|position| secret_key.sign_session(session, group_key, &mut list, position));
+        let signer = secret_key.index.to_string();
+        let partial = match partial {
+            Ok(partial) => partial,
+            Err(error) => {
+                current.zeroize();
+                if let SignatureError::MissingCommitmentShares
+                | SignatureError::CommitmentPoolLow(_) = error
+                {
+                    self.metrics
+                        .increment_counter(metrics::POOL_DEPLETED, &[("signer", &signer)]);
+                }
+                return Err(error);
+            }
+        };
+
+        let mut new = list.to_bytes();
+        let swapped = self
+            .store
+            .compare_and_swap(&self.key, Some(&current), Some(&new));
+        new.zeroize();
+        current.zeroize();
+
+        match swapped.map_err(storage_error)? {
+            true => {
+                self.metrics.set_gauge(
+                    metrics::POOL_REMAINING,
+                    list.commitments.len() as u64,
+                    &[("signer", &signer)],
+                );
+                Ok(partial)
+            }
+            false => Err(SignatureError::ConcurrentUpdate),
+        }
+    }
+
+    /// Get a reference to the underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Consume this pool, returning the underlying store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+/// The persisted state of a session a signer takes part in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SessionState {
+    /// The session was opened with the commitment share published at this
+    /// index, and no partial signature was released yet.
+    Open(u32),
+    /// A partial signature was released for the session, with the
+    /// commitment share published at this index.
+    Signed(u32, PartialThresholdSignature),
+}
+
+impl SessionState {
+    /// Serialise this session state to a Vec of bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(41);
+        match self {
+            SessionState::Open(index) => {
+                res.push(0);
+                res.extend_from_slice(&index.to_le_bytes());
+            }
+            SessionState::Signed(index, partial) => {
+                res.push(1);
+                res.extend_from_slice(&index.to_le_bytes());
+                res.extend_from_slice(&partial.to_bytes());
+            }
+        }
+
+        res
+    }
+
+    /// Deserialise this slice of bytes to a `SessionState`
+    pub fn from_bytes(bytes: &[u8]) -> Result<SessionState, Error> {
+        let index = u32::from_le_bytes(
+            bytes
+                .get(1..5)
+                .ok_or(Error::SerialisationError)?
+                .try_into()
+                .map_err(|_| Error::SerialisationError)?,
+        );
+        match (bytes[0], bytes.len()) {
+            (0, 5) => Ok(SessionState::Open(index)),
+            (1, 41) => Ok(SessionState::Signed(
+                index,
+                PartialThresholdSignature::from_bytes(
+                    bytes[5..41]
+                        .try_into()
+                        .map_err(|_| Error::SerialisationError)?,
+                )?,
+            )),
+            _ => Err(Error::SerialisationError),
+        }
+    }
+}
+
+/// A signer-side policy refusing to sign two different messages for the
+/// same application intent, e.g. two checkpoints at the same height, even
+/// when they are requested by different aggregators.
+///
+/// The intent of a session is identified by the value of one of its metadata
+/// keys.  Sessions without this key are not subject to the policy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConflictPolicy {
+    metadata_key: String,
+    prefix: Vec<u8>,
+}
+
+impl ConflictPolicy {
+    /// Construct a policy identifying intents by the value of the
+    /// `metadata_key` of sessions, and recording the message hash signed for
+    /// each of them under keys starting with `prefix`.
+    pub fn new(metadata_key: &str, prefix: &[u8]) -> ConflictPolicy {
+        ConflictPolicy {
+            metadata_key: metadata_key.to_string(),
+            prefix: prefix.to_vec(),
+        }
+    }
+
+    fn intent_key(&self, intent_id: &[u8]) -> Vec<u8> {
+        let mut key = Vec::with_capacity(self.prefix.len() + intent_id.len());
+        key.extend_from_slice(&self.prefix);
+        key.extend_from_slice(intent_id);
+        key
+    }
+}
+
+/// The persisted record of a session, with its raw bytes.
+struct SessionRecord {
+    bytes: Vec<u8>,
+    state: SessionState,
+    opened_at: Option<u64>,
+}
+
+/// Serialise the persisted record of a session, i.e. its `state` and the
+/// time at which it was opened.
+fn encode_session_record(state: &SessionState, opened_at: u64) -> Vec<u8> {
+    let mut res = state.to_bytes();
+    res.extend_from_slice(&opened_at.to_le_bytes());
+    res
+}
+
+/// Deserialise the persisted record of a session.  Records written before
+/// sessions were timed only hold a [`SessionState`], without a time.
+fn decode_session_record(bytes: &[u8]) -> Result<(SessionState, Option<u64>), Error> {
+    if let Ok(state) = SessionState::from_bytes(bytes) {
+        return Ok((state, None));
+    }
+    let split = bytes
+        .len()
+        .checked_sub(8)
+        .ok_or(Error::SerialisationError)?;
+    let opened_at = u64::from_le_bytes(
+        bytes[split..]
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?,
+    );
+
+    Ok((SessionState::from_bytes(&bytes[..split])?, Some(opened_at)))
+}
+
+/// A signer's open sessions and [`CommitmentPool`], persisted in the same
+/// [`StateStore`].
+///
+/// Sessions are identified by their [`SigningSession::id`].
+#[derive(Debug)]
+pub struct SignerSessionManager<S: StateStore> {
+    pool: CommitmentPool<S>,
+    prefix: Vec<u8>,
+    conflict_policy: Option<ConflictPolicy>,
+    clock: ClockHandle,
+    session_lifetime: Option<u64>,
+}
+
+impl<S: StateStore> SignerSessionManager<S> {
+    /// Construct a session manager over the given `pool`, storing the state
+    /// of its sessions in the same store, under keys starting with `prefix`.
+    pub fn new(pool: CommitmentPool<S>, prefix: &[u8]) -> SignerSessionManager<S> {
+        SignerSessionManager {
+            pool,
+            prefix: prefix.to_vec(),
+            conflict_policy: None,
+            clock: ClockHandle::default(),
+            session_lifetime: None,
+        }
+    }
+
+    /// Read the time from the given `clock`, rather than from the
+    /// [`SystemClock`](crate::clock::SystemClock).
+    ///
+    /// Without the `std` feature, there is no system clock, and a clock must
+    /// be set for a session lifetime to be enforced.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.clock = ClockHandle::new(clock);
+    }
+
+    /// Refuse to sign a session opened `millis` milliseconds ago or earlier.
+    ///
+    /// A session opened later than the current time, e.g. before the clock
+    /// was set back, cannot be dated and is refused as well, as are the
+    /// sessions opened before sessions were timed.  A partial signature
+    /// already released is still released again after its session expired.
+    pub fn set_session_lifetime(&mut self, millis: u64) {
+        self.session_lifetime = Some(millis);
+    }
+
+    /// Refuse to sign a session whose intent was already signed for with
+    /// another message, according to the given `policy`.
+    ///
+    /// The policy must use a prefix distinct from the one of the sessions.
+    pub fn set_conflict_policy(&mut self, policy: ConflictPolicy) {
+        self.conflict_policy = Some(policy);
+    }
+
+    /// Report the sessions opened, completed and aborted by this signer to
+    /// `metrics`, along with the events of its pool as with
+    /// [`CommitmentPool::set_metrics`].
+    pub fn set_metrics<M: Metrics + 'static>(&mut self, metrics: M) {
+        self.pool.set_metrics(metrics);
+    }
+
+    fn session_aborted(&self, error: &SignatureError) {
+        self.pool.metrics.increment_counter(
+            metrics::SESSION_ABORTED,
+            &[
+                ("role", metrics::ROLE_SIGNER),
+                ("reason", metrics::error_reason(error)),
+            ],
+        );
+    }
+
+    /// Record the message hash of the `session` for its intent, if any,
+    /// unless another one was already recorded.
+    fn record_intent(&mut self, session: &SigningSession) -> Result<(), SignatureError> {
+        let policy = match self.conflict_policy.as_ref() {
+            Some(policy) => policy,
+            None => return Ok(()),
+        };
+        let key = match session.get_metadata(&policy.metadata_key) {
+            Some(intent_id) => policy.intent_key(intent_id),
+            None => return Ok(()),
+        };
+        let requested = *session.message_hash();
+
+        let swapped = self
+            .pool
+            .store
+            .compare_and_swap(&key, None, Some(&requested))
+            .map_err(storage_error)?;
+        if swapped {
+            return Ok(());
+        }
+
+        let recorded: [u8; 32] = self
+            .pool
+            .store
+            .get(&key)
+            .map_err(storage_error)?
+            .ok_or(SignatureError::ConcurrentUpdate)?
+            .as_slice()
+            .try_into()
+            .map_err(|_| storage_error(Error::SerialisationError))?;
+
+        match recorded == requested {
+            true => Ok(()),
+            false => Err(SignatureError::ConflictingIntent {
+                recorded,
+                requested,
+            }),
+        }
+    }
+
+    fn session_key(&self, session_id: &[u8; 32]) -> Vec<u8> {
+        let mut key = Vec::with_capacity(self.prefix.len() + 32);
+        key.extend_from_slice(&self.prefix);
+        key.extend_from_slice(session_id);
+        key
+    }
+
+    /// Get the persisted record of the session with the given identifier.
+    fn record(&self, session_id: &[u8; 32]) -> Result<Option<SessionRecord>, SignatureError> {
+        self.pool
+            .store
+            .get(&self.session_key(session_id))
+            .map_err(storage_error)?
+            .map(|bytes| {
+                let (state, opened_at) = decode_session_record(&bytes)
+                    .map_err(|e| SignatureError::Custom(e.to_string()))?;
+                Ok(SessionRecord {
+                    bytes,
+                    state,
+                    opened_at,
+                })
+            })
+            .transpose()
+    }
+
+    /// Get the persisted state of the session with the given identifier.
+    pub fn state(&self, session_id: &[u8; 32]) -> Result<Option<SessionState>, SignatureError> {
+        Ok(self.record(session_id)?.map(|record| record.state))
+    }
+
+    /// Get the time at which the session with the given identifier was
+    /// opened, in milliseconds since the Unix epoch, if it is known.
+    pub fn opened_at(&self, session_id: &[u8; 32]) -> Result<Option<u64>, SignatureError> {
+        Ok(self.record(session_id)?.and_then(|record| record.opened_at))
+    }
+
+    /// Check that a session opened at `opened_at` has not expired.
+    fn check_lifetime(&self, opened_at: Option<u64>) -> Result<(), SignatureError> {
+        let lifetime = match self.session_lifetime {
+            Some(lifetime) => lifetime,
+            None => return Ok(()),
+        };
+        let now = self.clock.now();
+        let opened_at = opened_at.unwrap_or(0);
+
+        match opened_at <= now && now - opened_at < lifetime {
+            true => Ok(()),
+            false => Err(SignatureError::SessionExpired { opened_at, now }),
+        }
+    }
+
+    /// Record that the signer takes part in the `session`, with the
+    /// commitment share published at `published_index`.
+    ///
+    /// Opening the same session again with the same commitment share has no
+    /// effect.
+    ///
+    /// # Returns
+    ///
+    /// A `ConcurrentUpdate` error if the session was opened with another
+    /// commitment share.
+    pub fn open(
+        &mut self,
+        session: &SigningSession,
+        published_index: u32,
+    ) -> Result<(), SignatureError> {
+        let key = self.session_key(&session.id());
+        let state = encode_session_record(&SessionState::Open(published_index), self.clock.now());
+        let swapped = self
+            .pool
+            .store
+            .compare_and_swap(&key, None, Some(&state))
+            .map_err(storage_error)?;
+
+        match swapped {
+            true => {
+                self.pool
+                    .metrics
+                    .increment_counter(metrics::SESSION_CREATED, &[("role", metrics::ROLE_SIGNER)]);
+                Ok(())
+            }
+            false => match self.state(&session.id())? {
+                Some(SessionState::Open(index)) | Some(SessionState::Signed(index, _))
+                    if index == published_index =>
+                {
+                    Ok(())
+                }
+                _ => Err(SignatureError::ConcurrentUpdate),
+            },
+        }
+    }
+
+    /// Compute, or retrieve if it was already released, the partial
+    /// signature for an opened `session`.
+    ///
+    /// The commitment share is consumed before the partial signature is
+    /// recorded.  If the process dies in between, the partial signature is
+    /// lost and cannot be computed again.
+    ///
+    /// # Returns
+    ///
+    /// An `UnknownSigner` error if the session was not opened, an
+    /// `InterruptedSession` error if its partial signature was lost, in
+    /// which case the session should be renewed, a `ConflictingIntent`
+    /// error if a conflict policy was set and another message was already
+    /// signed for the intent of the session, or a `SessionExpired` error if
+    /// a session lifetime was set and the session outlived it.
+    pub fn sign(
+        &mut self,
+        secret_key: &SecretKey,
+        session: &SigningSession,
+        group_key: &GroupKey,
+    ) -> Result<PartialThresholdSignature, SignatureError> {
+        let session_id = session.id();
+        let (current, published_index, opened_at) = match self.record(&session_id)? {
+            None => return Err(SignatureError::UnknownSigner(secret_key.index)),
+            Some(SessionRecord {
+                state: SessionState::Signed(_, partial),
+                ..
+            }) => return Ok(partial),
+            Some(SessionRecord {
+                bytes,
+                state: SessionState::Open(index),
+                opened_at,
+            }) => (bytes, index, opened_at),
+        };
+        if let Err(error) = self.check_lifetime(opened_at) {
+            self.session_aborted(&error);
+            return Err(error);
+        }
+        if self.pool.is_consumed(published_index)? {
+            self.session_aborted(&SignatureError::InterruptedSession);
+            return Err(SignatureError::InterruptedSession);
+        }
+        let partial = match self.record_intent(session).and_then(|()| {
+            self.pool
+                .sign(secret_key, session, group_key, published_index)
+        }) {
+            Ok(partial) => partial,
+            Err(error) => {
+                self.session_aborted(&error);
+                return Err(error);
+            }
+        };
+
+        let key = self.session_key(&session_id);
+        let signed = SessionState::Signed(published_index, partial);
+        let new = match opened_at {
+            Some(opened_at) => encode_session_record(&signed, opened_at),
+            None => signed.to_bytes(),
+        };
+        self.pool
+            .store
+            .compare_and_swap(&key, Some(&current), Some(&new))
+            .map_err(storage_error)
+            .and_then(|swapped| match swapped {
+                true => {
+                    self.pool.metrics.increment_counter(
+                        metrics::SESSION_COMPLETED,
+                        &[("role", metrics::ROLE_SIGNER)],
+                    );
+                    Ok(partial)
+                }
+                false => Err(SignatureError::ConcurrentUpdate),
+            })
+    }
+
+    /// Forget the session with the given identifier.
+    pub fn close(&mut self, session_id: &[u8; 32]) -> Result<(), SignatureError> {
+        let key = self.session_key(session_id);
+        self.pool.store.delete(&key).map_err(storage_error)
+    }
+
+    /// Get a reference to the commitment pool.
+    pub fn pool(&self) -> &CommitmentPool<S> {
+        &self.pool
+    }
+
+    /// Get a mutable reference to the commitment pool.
+    pub fn pool_mut(&mut self) -> &mut CommitmentPool<S> {
+        &mut self.pool
+    }
+
+    /// Consume this session manager, returning the underlying pool.
+    pub fn into_pool(self) -> CommitmentPool<S> {
+        self.pool
+    }
+}
+
+/// An aggregator-side record of the sequence of signatures of each group,
+/// persisted in a [`StateStore`].
+///
+/// The tracker hands out the [`SequenceBinding`] of the next signature of a
+/// group, to be bound with
+/// [`SignatureAggregator::bind_sequence`](crate::signature::SignatureAggregator::bind_sequence),
+/// and only records a signature made with exactly that binding, so that the
+/// sequence of a group never skips, repeats or forks.
+#[derive(Debug)]
+pub struct SequenceTracker<S: StateStore> {
+    store: S,
+    prefix: Vec<u8>,
+}
+
+impl<S: StateStore> SequenceTracker<S> {
+    /// Construct a tracker persisting the binding of the next signature of
+    /// each group in the given `store`, under keys starting with `prefix`.
+    pub fn new(store: S, prefix: &[u8]) -> SequenceTracker<S> {
+        SequenceTracker {
+            store,
+            prefix: prefix.to_vec(),
+        }
+    }
+
+    fn sequence_key(&self, group_key: &GroupKey) -> Vec<u8> {
+        let mut key = Vec::with_capacity(self.prefix.len() + 32);
+        key.extend_from_slice(&self.prefix);
+        key.extend_from_slice(&group_key.to_bytes());
+        key
+    }
+
+    /// The persisted binding of the next signature of the group, with its
+    /// raw bytes.
+    fn record(
+        &self,
+        group_key: &GroupKey,
+    ) -> Result<Option<(Vec<u8>, SequenceBinding)>, SignatureError> {
+        self.store
+            .get(&self.sequence_key(group_key))
+            .map_err(storage_error)?
+            .map(|bytes| {
+                if bytes.len() != 40 {
+                    return Err(storage_error(Error::SerialisationError));
+                }
+                let binding = SequenceBinding {
+                    seq: u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+                    prev: bytes[8..].try_into().unwrap(),
+                };
+                Ok((bytes, binding))
+            })
+            .transpose()
+    }
+
+    /// The binding of the next signature of the group with the given
+    /// `group_key`, which is [`SequenceBinding::genesis`] for a group without
+    /// recorded signatures.
+    pub fn next(&self, group_key: &GroupKey) -> Result<SequenceBinding, SignatureError> {
+        Ok(self
+            .record(group_key)?
+            .map_or_else(SequenceBinding::genesis, |(_, binding)| binding))
+    }
+
+    /// Record the `signature` of the group with the given `group_key`, made
+    /// with the given `binding`, as the last signature of its sequence.
+    ///
+    /// # Returns
+    ///
+    /// A `SequenceMismatch` error if the binding does not have the next
+    /// sequence number of the group, a `SequenceFork` error if it does not
+    /// link to the last recorded signature, or a `ConcurrentUpdate` error if
+    /// another signature was recorded concurrently.
+    pub fn record_signature(
+        &mut self,
+        group_key: &GroupKey,
+        binding: &SequenceBinding,
+        signature: &ThresholdSignature,
+    ) -> Result<(), SignatureError> {
+        let record = self.record(group_key)?;
+        let expected = record
+            .as_ref()
+            .map_or_else(SequenceBinding::genesis, |(_, binding)| *binding);
+
+        if binding.seq != expected.seq {
+            return Err(SignatureError::SequenceMismatch {
+                expected: expected.seq,
+                found: Some(binding.seq),
+            });
+        }
+        if binding.prev != expected.prev {
+            return Err(SignatureError::SequenceFork(binding.seq));
+        }
+
+        let next = binding.next(signature);
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(&next.seq.to_le_bytes());
+        bytes.extend_from_slice(&next.prev);
+
+        let swapped = self
+            .store
+            .compare_and_swap(
+                &self.sequence_key(group_key),
+                record.as_ref().map(|(current, _)| current.as_slice()),
+                Some(&bytes),
+            )
+            .map_err(storage_error)?;
+
+        match swapped {
+            true => Ok(()),
+            false => Err(SignatureError::ConcurrentUpdate),
+        }
+    }
+
+    /// Get a reference to the underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Consume this tracker, returning the underlying store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+/// A streaming validator of a large batch of public commitment shares
+/// uploaded by a signer, which spools them to a [`StateStore`] rather than
+/// holding the whole list in memory.
+///
+/// Each commitment share is checked for canonicality and against the
+/// identity as it is pushed, and duplicates are detected through a set of
+/// 8-byte fingerprints of the points, resolved against the store on a match.
+/// Once the batch is complete, only its [`PublicCommitmentBatch`] digest
+/// needs to be kept, and the commitment share used by a signing session is
+/// fetched from the store on demand.
+#[derive(Debug)]
+pub struct PublicCommitmentBatchValidator<'s, S: StateStore> {
+    store: &'s mut S,
+    prefix: Vec<u8>,
+    signer_index: u32,
+    expected_count: u32,
+    count: u32,
+    hasher: Sha256,
+    fingerprints: BTreeSet<(u64, u32)>,
+}
+
+impl<'s, S: StateStore> PublicCommitmentBatchValidator<'s, S> {
+    /// Construct a validator of a batch of `expected_count` commitment shares
+    /// of the signer with index `signer_index`, spooling them to the given
+    /// `store` under keys starting with `prefix`.
+    pub fn new(
+        store: &'s mut S,
+        prefix: &[u8],
+        signer_index: u32,
+        expected_count: u32,
+    ) -> PublicCommitmentBatchValidator<'s, S> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"ICE-FROST-COMMITMENT-BATCH");
+        hasher.update(signer_index.to_le_bytes());
+
+        PublicCommitmentBatchValidator {
+            store,
+            prefix: prefix.to_vec(),
+            signer_index,
+            expected_count,
+            count: 0,
+            hasher,
+            fingerprints: BTreeSet::new(),
+        }
+    }
+
+    /// Validate the next `commitment` share of the batch, i.e. the encodings
+    /// of its hiding and binding commitments, and spool it to the store.
+    ///
+    /// # Returns
+    ///
+    /// A `SerialisationError` or an `IdentityPoint` error if a commitment is
+    /// not canonically encoded or is the identity, a
+    /// `DuplicateCommitmentShare` error if a commitment was already uploaded,
+    /// and a `CommitmentBatchLength` error if the batch is already complete.
+    pub fn push(&mut self, commitment: &[u8; 64]) -> Result<(), Error> {
+        if self.count == self.expected_count {
+            return Err(Error::CommitmentBatchLength {
+                signer: self.signer_index,
+                expected: self.expected_count,
+                got: self.count + 1,
+            });
+        }
+
+        let commitment_share = decode_commitment_share(commitment)?;
+        let duplicate = Error::DuplicateCommitmentShare {
+            signer: self.signer_index,
+            position: self.count,
+        };
+        if commitment_share.0 == commitment_share.1 {
+            return Err(duplicate);
+        }
+        for point in commitment.chunks(32) {
+            let fingerprint = fingerprint(point);
+            let matches = self
+                .fingerprints
+                .range((fingerprint, 0)..=(fingerprint, u32::MAX));
+            for (_, position) in matches {
+                let stored =
+                    load_commitment_share(self.store, &self.prefix, self.signer_index, *position)?;
+                if stored[..32] == *point || stored[32..] == *point {
+                    return Err(duplicate);
+                }
+            }
+        }
+
+        let key = commitment_share_key(&self.prefix, self.signer_index, self.count);
+        self.store.put(&key, commitment)?;
+        self.hasher.update(commitment);
+        for point in commitment.chunks(32) {
+            self.fingerprints.insert((fingerprint(point), self.count));
+        }
+        self.count += 1;
+
+        Ok(())
+    }
+
+    /// Complete the batch.
+    ///
+    /// # Returns
+    ///
+    /// The digest of the batch, or a `CommitmentBatchLength` error if fewer
+    /// commitment shares were pushed than expected.
+    pub fn finish(mut self) -> Result<PublicCommitmentBatch, Error> {
+        if self.count != self.expected_count {
+            return Err(Error::CommitmentBatchLength {
+                signer: self.signer_index,
+                expected: self.expected_count,
+                got: self.count,
+            });
+        }
+        self.hasher.update(self.count.to_le_bytes());
+
+        Ok(PublicCommitmentBatch {
+            prefix: self.prefix,
+            signer_index: self.signer_index,
+            count: self.count,
+            digest: self.hasher.finalize().into(),
+        })
+    }
+}
+
+/// A batch of public commitment shares validated by a
+/// [`PublicCommitmentBatchValidator`] and spooled to a [`StateStore`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicCommitmentBatch {
+    prefix: Vec<u8>,
+    signer_index: u32,
+    count: u32,
+    digest: [u8; 32],
+}
+
+impl PublicCommitmentBatch {
+    /// The index of the signer who uploaded the batch.
+    pub fn signer_index(&self) -> u32 {
+        self.signer_index
+    }
+
+    /// The number of commitment shares in the batch.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// The digest of the batch, committing to the signer and to its
+    /// commitment shares in order.
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+
+    /// Fetch the commitment share at `position` in the batch from the
+    /// `store`, e.g. to include the signer in a session with
+    /// [`SignatureAggregator::include_signer`].
+    ///
+    /// # Returns
+    ///
+    /// A `StorageError` if there is no such commitment share in the store.
+    ///
+    /// [`SignatureAggregator::include_signer`]: crate::signature::SignatureAggregator::include_signer
+    pub fn commitment_share<T: StateStore>(
+        &self,
+        store: &T,
+        position: u32,
+    ) -> Result<(RistrettoPoint, RistrettoPoint), Error> {
+        if position >= self.count {
+            return Err(missing_commitment_share());
+        }
+        let bytes = load_commitment_share(store, &self.prefix, self.signer_index, position)?;
+
+        decode_commitment_share(&bytes)
+    }
+}
+
+fn commitment_share_key(prefix: &[u8], signer_index: u32, position: u32) -> Vec<u8> {
+    let mut key = Vec::with_capacity(prefix.len() + 8);
+    key.extend_from_slice(prefix);
+    key.extend_from_slice(&signer_index.to_le_bytes());
+    key.extend_from_slice(&position.to_le_bytes());
+    key
+}
+
+fn load_commitment_share<S: StateStore>(
+    store: &S,
+    prefix: &[u8],
+    signer_index: u32,
+    position: u32,
+) -> Result<[u8; 64], Error> {
+    store
+        .get(&commitment_share_key(prefix, signer_index, position))?
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(missing_commitment_share)
+}
+
+fn missing_commitment_share() -> Error {
+    Error::StorageError("missing commitment share".to_string())
+}
+
+fn decode_commitment_share(bytes: &[u8; 64]) -> Result<(RistrettoPoint, RistrettoPoint), Error> {
+    let mut points = [RistrettoPoint::identity(); 2];
+    for (point, encoding) in points.iter_mut().zip(bytes.chunks(32)) {
+        *point = decode_point_checked(encoding.try_into().unwrap())?;
+        if *point == RistrettoPoint::identity() {
+            return Err(Error::IdentityPoint);
+        }
+    }
+
+    Ok((points[0], points[1]))
+}
+
+/// The fingerprint of the encoding of a point, by which duplicates are
+/// detected.
+fn fingerprint(point: &[u8]) -> u64 {
+    u64::from_le_bytes(point[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::clock::ManualClock;
+    use crate::parameters::Parameters;
+    use crate::precomputation::generate_commitment_share_lists;
+    use crate::precomputation::PublicCommitmentShareList;
+    use crate::signature::verify_sequence;
+    use crate::signature::AuthorizerKey;
+    use crate::signature::SignatureAggregator;
+    use crate::signature::VerificationDescriptor;
+
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+    use curve25519_dalek::scalar::Scalar;
+
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    /// A store which fails every modification once its budget is spent,
+    /// standing for a process dying before the modification was applied.
+    struct CrashingStore<S: StateStore> {
+        inner: S,
+        budget: usize,
+    }
+
+    impl<S: StateStore> CrashingStore<S> {
+        fn spend(&mut self) -> Result<(), Error> {
+            match self.budget {
+                0 => Err(Error::StorageError("crashed".to_string())),
+                _ => {
+                    self.budget -= 1;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    impl<S: StateStore> StateStore for CrashingStore<S> {
+        fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+            self.inner.get(key)
+        }
+
+        fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+            self.spend()?;
+            self.inner.put(key, value)
+        }
+
+        fn delete(&mut self, key: &[u8]) -> Result<(), Error> {
+            self.spend()?;
+            self.inner.delete(key)
+        }
+
+        fn compare_and_swap(
+            &mut self,
+            key: &[u8],
+            current: Option<&[u8]>,
+            new: Option<&[u8]>,
+        ) -> Result<bool, Error> {
+            self.spend()?;
+            self.inner.compare_and_swap(key, current, new)
+        }
+    }
+
+    fn temporary_directory() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ice-frost-store-{:016x}", OsRng.next_u64()))
+    }
+
+    fn signer() -> (SecretKey, GroupKey) {
+        let key = Scalar::random(&mut OsRng);
+        let group_key = GroupKey::new(&RISTRETTO_BASEPOINT_TABLE * &key);
+
+        (SecretKey { index: 1, key }, group_key)
+    }
+
+    fn session(
+        group_key: &GroupKey,
+        secret_key: &SecretKey,
+        public_comshares: &PublicCommitmentShareList,
+        published_index: usize,
+        message: &[u8],
+    ) -> SigningSession {
+        let mut aggregator =
+            SignatureAggregator::new(Parameters { n: 1, t: 1 }, *group_key, b"CONTEXT", message);
+        aggregator
+            .include_signer(
+                1,
+                public_comshares.commitments[published_index],
+                secret_key.into(),
+            )
+            .unwrap();
+        aggregator.session()
+    }
+
+    fn check_store(mut store: impl StateStore) {
+        assert_eq!(store.get(b"key").unwrap(), None);
+        assert!(store.compare_and_swap(b"key", None, Some(b"one")).unwrap());
+        assert!(!store.compare_and_swap(b"key", None, Some(b"two")).unwrap());
+        assert!(!store
+            .compare_and_swap(b"key", Some(b"two"), Some(b"three"))
+            .unwrap());
+        assert_eq!(store.get(b"key").unwrap(), Some(b"one".to_vec()));
+
+        assert!(store
+            .compare_and_swap(b"key", Some(b"one"), Some(b"two"))
+            .unwrap());
+        store.put(b"other", b"value").unwrap();
+        assert!(store.compare_and_swap(b"key", Some(b"two"), None).unwrap());
+        assert_eq!(store.get(b"key").unwrap(), None);
+        assert_eq!(store.get(b"other").unwrap(), Some(b"value".to_vec()));
+
+        store.delete(b"other").unwrap();
+        store.delete(b"other").unwrap();
+        assert_eq!(store.get(b"other").unwrap(), None);
+    }
+
+    #[test]
+    fn memory_and_file_stores() {
+        check_store(MemoryStore::new());
+
+        let directory = temporary_directory();
+        check_store(FileStore::open(&directory).unwrap());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn session_manager() {
+        let (secret_key, group_key) = signer();
+        let (public_comshares, secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 2);
+
+        let mut pool = CommitmentPool::new(MemoryStore::new(), b"pool");
+        pool.initialise(&secret_comshares).unwrap();
+        assert_eq!(
+            pool.initialise(&secret_comshares),
+            Err(SignatureError::ConcurrentUpdate)
+        );
+        let mut manager = SignerSessionManager::new(pool, b"session/");
+
+        let first = session(&group_key, &secret_key, &public_comshares, 0, b"first");
+        assert_eq!(
+            manager.sign(&secret_key, &first, &group_key),
+            Err(SignatureError::UnknownSigner(1))
+        );
+        manager.open(&first, 0).unwrap();
+        manager.open(&first, 0).unwrap();
+        assert_eq!(
+            manager.open(&first, 1),
+            Err(SignatureError::ConcurrentUpdate)
+        );
+
+        let partial = manager.sign(&secret_key, &first, &group_key).unwrap();
+        assert_eq!(
+            manager.state(&first.id()).unwrap(),
+            Some(SessionState::Signed(0, partial))
+        );
+        assert!(manager.pool().is_consumed(0).unwrap());
+
+        // The same partial signature is released again for the same session.
+        assert_eq!(manager.sign(&secret_key, &first, &group_key), Ok(partial));
+
+        // But the commitment share cannot be used for another one.
+        let second = session(&group_key, &secret_key, &public_comshares, 0, b"second");
+        assert_eq!(
+            manager.pool_mut().sign(&secret_key, &second, &group_key, 0),
+            Err(SignatureError::MissingCommitmentShares)
+        );
+
+        let second = session(&group_key, &secret_key, &public_comshares, 1, b"second");
+        manager.open(&second, 1).unwrap();
+        assert!(manager.sign(&secret_key, &second, &group_key).is_ok());
+        assert_eq!(manager.pool().load().unwrap().commitments.len(), 0);
+
+        manager.close(&first.id()).unwrap();
+        assert_eq!(manager.state(&first.id()).unwrap(), None);
+
+        for state in [SessionState::Open(3), SessionState::Signed(4, partial)] {
+            assert_eq!(SessionState::from_bytes(&state.to_bytes()), Ok(state));
+        }
+        assert!(SessionState::from_bytes(&[2, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn session_expiry() {
+        let (secret_key, group_key) = signer();
+        let (public_comshares, secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 4);
+
+        let mut pool = CommitmentPool::new(MemoryStore::new(), b"pool");
+        pool.initialise(&secret_comshares).unwrap();
+        let mut manager = SignerSessionManager::new(pool, b"session/");
+        let clock = ManualClock::new(1_000);
+        manager.set_clock(clock.clone());
+        manager.set_session_lifetime(500);
+
+        // A session may be signed until just before its lifetime is over.
+        let first = session(&group_key, &secret_key, &public_comshares, 0, b"first");
+        manager.open(&first, 0).unwrap();
+        assert_eq!(manager.opened_at(&first.id()).unwrap(), Some(1_000));
+        clock.advance(499);
+        let partial = manager.sign(&secret_key, &first, &group_key).unwrap();
+        assert_eq!(manager.opened_at(&first.id()).unwrap(), Some(1_000));
+
+        // But not once it is over, and its commitment share is not consumed.
+        let second = session(&group_key, &secret_key, &public_comshares, 1, b"second");
+        manager.open(&second, 1).unwrap();
+        clock.advance(500);
+        assert_eq!(
+            manager.sign(&secret_key, &second, &group_key),
+            Err(SignatureError::SessionExpired {
+                opened_at: 1_499,
+                now: 1_999,
+            })
+        );
+        assert!(!manager.pool().is_consumed(1).unwrap());
+
+        // A partial signature already released is released again.
+        assert_eq!(manager.sign(&secret_key, &first, &group_key), Ok(partial));
+
+        // A session opened in the future of the clock cannot be dated.
+        let third = session(&group_key, &secret_key, &public_comshares, 2, b"third");
+        clock.set(5_000);
+        manager.open(&third, 2).unwrap();
+        clock.set(4_000);
+        assert_eq!(
+            manager.sign(&secret_key, &third, &group_key),
+            Err(SignatureError::SessionExpired {
+                opened_at: 5_000,
+                now: 4_000,
+            })
+        );
+        clock.set(5_000);
+        assert!(manager.sign(&secret_key, &third, &group_key).is_ok());
+
+        // Neither can a session opened before sessions were timed.
+        let fourth = session(&group_key, &secret_key, &public_comshares, 3, b"fourth");
+        let key = manager.session_key(&fourth.id());
+        manager
+            .pool
+            .store
+            .put(&key, &SessionState::Open(3).to_bytes())
+            .unwrap();
+        assert_eq!(
+            manager.state(&fourth.id()).unwrap(),
+            Some(SessionState::Open(3))
+        );
+        assert_eq!(manager.opened_at(&fourth.id()).unwrap(), None);
+        assert!(matches!(
+            manager.sign(&secret_key, &fourth, &group_key),
+            Err(SignatureError::SessionExpired { opened_at: 0, .. })
+        ));
+
+        // Without a lifetime, sessions never expire.
+        let mut manager = SignerSessionManager::new(manager.into_pool(), b"session/");
+        manager.set_clock(clock);
+        assert!(manager.sign(&secret_key, &fourth, &group_key).is_ok());
+    }
+
+    #[test]
+    fn conflicting_intents() {
+        let (secret_key, group_key) = signer();
+        let (public_comshares, secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 3);
+        let intent_session = |published_index: usize, message: &'static [u8]| {
+            let mut aggregator = SignatureAggregator::new_with_metadata(
+                Parameters { n: 1, t: 1 },
+                group_key,
+                b"CONTEXT",
+                message,
+                &[("height", b"42")],
+            )
+            .unwrap();
+            aggregator
+                .include_signer(
+                    1,
+                    public_comshares.commitments[published_index],
+                    (&secret_key).into(),
+                )
+                .unwrap();
+            aggregator.session()
+        };
+
+        let mut pool = CommitmentPool::new(MemoryStore::new(), b"pool");
+        pool.initialise(&secret_comshares).unwrap();
+        let mut manager = SignerSessionManager::new(pool, b"session/");
+        manager.set_conflict_policy(ConflictPolicy::new("height", b"intent/"));
+
+        let first = intent_session(0, b"checkpoint");
+        manager.open(&first, 0).unwrap();
+        manager.sign(&secret_key, &first, &group_key).unwrap();
+
+        // The same message may be signed again for the same intent, e.g. in
+        // a renewed session.
+        let again = intent_session(1, b"checkpoint");
+        manager.open(&again, 1).unwrap();
+        manager.sign(&secret_key, &again, &group_key).unwrap();
+
+        // A different message for the same intent is refused, even after a
+        // restart, and its commitment share is not consumed.
+        let store = manager.into_pool().into_store();
+        let mut manager =
+            SignerSessionManager::new(CommitmentPool::new(store, b"pool"), b"session/");
+        manager.set_conflict_policy(ConflictPolicy::new("height", b"intent/"));
+        let conflicting = intent_session(2, b"another checkpoint");
+        manager.open(&conflicting, 2).unwrap();
+        assert_eq!(
+            manager.sign(&secret_key, &conflicting, &group_key),
+            Err(SignatureError::ConflictingIntent {
+                recorded: *first.message_hash(),
+                requested: *conflicting.message_hash(),
+            })
+        );
+        assert!(!manager.pool().is_consumed(2).unwrap());
+
+        // Sessions without an intent are not subject to the policy.
+        let unrelated = session(&group_key, &secret_key, &public_comshares, 2, b"unrelated");
+        manager.open(&unrelated, 2).unwrap();
+        assert!(manager.sign(&secret_key, &unrelated, &group_key).is_ok());
+    }
+
+    #[test]
+    fn pool_with_authorizer() {
+        let (secret_key, group_key) = signer();
+        let (public_comshares, secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let authorizer = AuthorizerKey::new(OsRng);
+
+        let mut pool = CommitmentPool::new(MemoryStore::new(), b"pool");
+        pool.initialise(&secret_comshares).unwrap();
+        pool.set_authorizer(authorizer.public_key());
+
+        let mut session = session(&group_key, &secret_key, &public_comshares, 0, b"message");
+        assert_eq!(
+            pool.sign(&secret_key, &session, &group_key, 0),
+            Err(SignatureError::UnauthorizedSession)
+        );
+        assert!(!pool.is_consumed(0).unwrap());
+
+        session.set_authorization(authorizer.authorize(&session, OsRng));
+        assert!(pool.sign(&secret_key, &session, &group_key, 0).is_ok());
+    }
+
+    /// Sign two sessions with the same commitment share, letting the process
+    /// die after every possible number of modifications of the store, and
+    /// check that partial signatures are never released for both.
+    fn crash_simulation<S: StateStore>(new_store: impl Fn() -> S) {
+        let (secret_key, group_key) = signer();
+        let (public_comshares, secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let first = session(&group_key, &secret_key, &public_comshares, 0, b"first");
+        let second = session(&group_key, &secret_key, &public_comshares, 0, b"second");
+
+        for budget in 0..4 {
+            let mut store = new_store();
+            store.delete(b"pool").unwrap();
+            store
+                .delete(&[b"session/".as_ref(), &first.id()].concat())
+                .unwrap();
+            store
+                .delete(&[b"session/".as_ref(), &second.id()].concat())
+                .unwrap();
+            let mut pool = CommitmentPool::new(store, b"pool");
+            pool.initialise(&secret_comshares).unwrap();
+
+            let mut released = Vec::new();
+
+            let store = CrashingStore {
+                inner: pool.into_store(),
+                budget,
+            };
+            let mut manager =
+                SignerSessionManager::new(CommitmentPool::new(store, b"pool"), b"session/");
+            let crashed = manager
+                .open(&first, 0)
+                .and_then(|_| manager.sign(&secret_key, &first, &group_key));
+            match crashed {
+                Ok(partial) => released.push((first.id(), partial)),
+                Err(error) => assert!(matches!(error, SignatureError::StorageError(_))),
+            }
+
+            // Restart from whatever reached the store, and try both sessions.
+            let store = manager.into_pool().into_store().inner;
+            let mut manager =
+                SignerSessionManager::new(CommitmentPool::new(store, b"pool"), b"session/");
+            for session in [&first, &second] {
+                let result = manager
+                    .open(session, 0)
+                    .and_then(|_| manager.sign(&secret_key, session, &group_key));
+                if let Ok(partial) = result {
+                    released.push((session.id(), partial));
+                }
+            }
+
+            // The partial signature may have been lost with the process, but
+            // only after its commitment share was consumed.
+            assert!(
+                released.iter().all(|(id, _)| *id == released[0].0),
+                "the commitment share was used for two sessions after {} writes",
+                budget
+            );
+            assert!(manager.pool().is_consumed(0).unwrap());
+        }
+    }
+
+    #[test]
+    fn nonce_is_never_reused_across_crashes() {
+        crash_simulation(MemoryStore::new);
+
+        let directory = temporary_directory();
+        crash_simulation(|| FileStore::open(&directory).unwrap());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn streamed_commitment_batch() {
+        const COUNT: u32 = 10_000;
+
+        let (secret_key, group_key) = signer();
+        let (public_comshares, mut secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, COUNT as usize);
+        let bytes = public_comshares.to_bytes();
+        let uploads: Vec<[u8; 64]> = bytes[8..]
+            .chunks(64)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect();
+
+        let mut store = MemoryStore::new();
+        let mut validator = PublicCommitmentBatchValidator::new(&mut store, b"batch/", 1, COUNT);
+        for upload in uploads.iter() {
+            validator.push(upload).unwrap();
+        }
+        assert_eq!(
+            validator.push(&uploads[0]),
+            Err(Error::CommitmentBatchLength {
+                signer: 1,
+                expected: COUNT,
+                got: COUNT + 1,
+            })
+        );
+        let batch = validator.finish().unwrap();
+        assert_eq!(batch.signer_index(), 1);
+        assert_eq!(batch.count(), COUNT);
+
+        // The digest commits to the order of the commitment shares.
+        let mut other_store = MemoryStore::new();
+        let mut validator = PublicCommitmentBatchValidator::new(&mut other_store, b"batch/", 1, 2);
+        validator.push(&uploads[1]).unwrap();
+        validator.push(&uploads[0]).unwrap();
+        assert_ne!(validator.finish().unwrap().digest(), batch.digest());
+
+        // Serve a mid-batch commitment share to a signing session.
+        let position = COUNT / 2;
+        let commitment_share = batch.commitment_share(&store, position).unwrap();
+        assert_eq!(
+            commitment_share,
+            public_comshares.commitments[position as usize]
+        );
+        assert!(batch.commitment_share(&store, COUNT).is_err());
+
+        let mut aggregator =
+            SignatureAggregator::new(Parameters { n: 1, t: 1 }, group_key, b"CONTEXT", b"MESSAGE");
+        aggregator
+            .include_signer(1, commitment_share, (&secret_key).into())
+            .unwrap();
+        let session = aggregator.session();
+        let partial = secret_key
+            .sign_session(
+                &session,
+                &group_key,
+                &mut secret_comshares,
+                position as usize,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+        let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+        assert!(signature.verify(&group_key, session.message_hash()).is_ok());
+    }
+
+    #[test]
+    fn invalid_commitment_batches() {
+        let (public_comshares, _) = generate_commitment_share_lists(&mut OsRng, 1, 100);
+        let bytes = public_comshares.to_bytes();
+        let uploads: Vec<[u8; 64]> = bytes[8..]
+            .chunks(64)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect();
+
+        let mut store = MemoryStore::new();
+        let mut validator = PublicCommitmentBatchValidator::new(&mut store, b"batch/", 1, 101);
+        for upload in uploads.iter() {
+            validator.push(upload).unwrap();
+        }
+
+        // An injected duplicate, even with its commitments swapped, or
+        // reusing a single one of them.
+        let duplicate = Err(Error::DuplicateCommitmentShare {
+            signer: 1,
+            position: 100,
+        });
+        let mut swapped = [0u8; 64];
+        swapped[..32].copy_from_slice(&uploads[42][32..]);
+        swapped[32..].copy_from_slice(&uploads[42][..32]);
+        let mut reused = uploads[0];
+        reused[32..].copy_from_slice(&uploads[99][32..]);
+        let mut doubled = [0u8; 64];
+        doubled[..32].copy_from_slice(&uploads[7][..32]);
+        doubled[32..].copy_from_slice(&uploads[7][..32]);
+        for upload in [uploads[42], swapped, reused, doubled] {
+            assert_eq!(validator.push(&upload), duplicate);
+        }
+
+        // Non-canonical encodings and the identity.
+        let mut non_canonical = uploads[0];
+        non_canonical[..32].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+            validator.push(&non_canonical),
+            Err(Error::SerialisationError)
+        );
+        let mut identity = uploads[0];
+        identity[32..].copy_from_slice(&[0; 32]);
+        assert_eq!(validator.push(&identity), Err(Error::IdentityPoint));
+
+        assert_eq!(
+            validator.finish(),
+            Err(Error::CommitmentBatchLength {
+                signer: 1,
+                expected: 101,
+                got: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn sequence_of_signatures() {
+        let (secret_key, group_key) = signer();
+        let mut tracker = SequenceTracker::new(MemoryStore::new(), b"sequence/");
+
+        let sign = |binding: Option<SequenceBinding>| {
+            let (public_comshares, mut secret_comshares) =
+                generate_commitment_share_lists(&mut OsRng, 1, 1);
+            let mut aggregator = SignatureAggregator::new(
+                Parameters { n: 1, t: 1 },
+                group_key,
+                b"CONTEXT",
+                b"CHECKPOINT",
+            );
+            if let Some(binding) = binding {
+                aggregator.bind_sequence(binding).unwrap();
+            }
+            aggregator
+                .include_signer(1, public_comshares.commitments[0], (&secret_key).into())
+                .unwrap();
+            let session = aggregator.session();
+            assert_eq!(session.sequence(), binding);
+            let partial = secret_key
+                .sign_session(&session, &group_key, &mut secret_comshares, 0)
+                .unwrap();
+            aggregator.include_partial_signature(partial).unwrap();
+            let signature = aggregator.finalize().unwrap().aggregate().unwrap();
+
+            (VerificationDescriptor::for_session(&session), signature)
+        };
+
+        // A chain of three signatures.
+        let mut chain = Vec::new();
+        for seq in 0..3 {
+            let binding = tracker.next(&group_key).unwrap();
+            assert_eq!(binding.seq, seq);
+            let (descriptor, signature) = sign(Some(binding));
+            tracker
+                .record_signature(&group_key, &binding, &signature)
+                .unwrap();
+
+            let seq_bytes = binding.seq.to_le_bytes();
+            let metadata: [(&str, &[u8]); 2] = [
+                (SequenceBinding::SEQ_KEY, &seq_bytes),
+                (SequenceBinding::PREV_KEY, &binding.prev),
+            ];
+            assert!(signature
+                .verify_with_descriptor(&descriptor, &group_key, b"CHECKPOINT", &metadata)
+                .is_ok());
+            // The descriptor cannot claim another position.
+            let moved = VerificationDescriptor {
+                sequence: Some(SequenceBinding { seq: 7, ..binding }),
+                ..descriptor.clone()
+            };
+            assert_eq!(
+                signature.verify_with_descriptor(&moved, &group_key, b"CHECKPOINT", &metadata),
+                Err(SignatureError::InvalidSignature)
+            );
+
+            chain.push((descriptor, signature));
+        }
+        assert_eq!(chain[0].0.sequence, Some(SequenceBinding::genesis()));
+        for link in chain.windows(2) {
+            assert!(verify_sequence(&link[0].1, &link[0].0, &link[1].0).is_ok());
+        }
+
+        // Gaps and unsequenced signatures are detected.
+        assert_eq!(
+            verify_sequence(&chain[0].1, &chain[0].0, &chain[2].0),
+            Err(SignatureError::SequenceMismatch {
+                expected: 1,
+                found: Some(2),
+            })
+        );
+        let (unsequenced, _) = sign(None);
+        assert_eq!(unsequenced.sequence, None);
+        assert_eq!(
+            verify_sequence(&chain[2].1, &chain[2].0, &unsequenced),
+            Err(SignatureError::SequenceMismatch {
+                expected: 3,
+                found: None,
+            })
+        );
+
+        // A replayed sequence number is refused by the tracker.
+        let replayed = chain[1].0.sequence.unwrap();
+        let (_, signature) = sign(Some(replayed));
+        assert_eq!(
+            tracker.record_signature(&group_key, &replayed, &signature),
+            Err(SignatureError::SequenceMismatch {
+                expected: 3,
+                found: Some(1),
+            })
+        );
+
+        // A fork of the sequence after the second signature is detected by
+        // the tracker and by the verifier.
+        let forked = chain[0].0.sequence.unwrap().next(&chain[0].1);
+        let forked = SequenceBinding { seq: 3, ..forked };
+        let (descriptor, signature) = sign(Some(forked));
+        assert_eq!(
+            tracker.record_signature(&group_key, &forked, &signature),
+            Err(SignatureError::SequenceFork(3))
+        );
+        assert_eq!(
+            verify_sequence(&chain[2].1, &chain[2].0, &descriptor),
+            Err(SignatureError::SequenceFork(3))
+        );
+
+        // The sequence resumes from the last recorded signature.
+        assert_eq!(
+            tracker.next(&group_key).unwrap(),
+            chain[2].0.sequence.unwrap().next(&chain[2].1)
+        );
+
+        // The binding keys are reserved.
+        let mut aggregator = SignatureAggregator::new_with_metadata(
+            Parameters { n: 1, t: 1 },
+            group_key,
+            b"CONTEXT",
+            b"CHECKPOINT",
+            &[(SequenceBinding::SEQ_KEY, &[0u8; 8])],
+        )
+        .unwrap();
+        assert_eq!(
+            aggregator.bind_sequence(SequenceBinding::genesis()),
+            Err(SignatureError::DuplicateMetadataKey(
+                SequenceBinding::SEQ_KEY.to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,103 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Fixtures shared by the unit tests of several modules.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use rand::rngs::OsRng;
+
+use crate::attestation::DkgReceipt;
+use crate::keygen::DHPrivateKey;
+use crate::keygen::DistributedKeyGeneration;
+use crate::keygen::GroupKey;
+use crate::keygen::Participant;
+use crate::keygen::RoundOne;
+use crate::keygen::SecretKey;
+use crate::parameters::Parameters;
+
+/// The outcome of a distributed key generation with honest dealers, indexed
+/// by participant index minus one.
+pub(crate) struct Dkg {
+    pub(crate) group_key: GroupKey,
+    pub(crate) participants: Vec<Participant>,
+    pub(crate) dh_secret_keys: Vec<DHPrivateKey>,
+    pub(crate) secret_keys: Vec<SecretKey>,
+    pub(crate) receipts: Vec<DkgReceipt>,
+}
+
+/// Run a full distributed key generation with `params.n` honest dealers,
+/// each of which also issues a [`DkgReceipt`].
+pub(crate) fn run_full_dkg(params: &Parameters) -> Dkg {
+    let rng = OsRng;
+
+    let mut participants = Vec::new();
+    let mut coefficients = Vec::new();
+    let mut dh_secret_keys = Vec::new();
+    for i in 1..=params.n {
+        let (p, coeffs, dh_sk) = Participant::new_dealer(params, i, "Φ", rng);
+        participants.push(p);
+        coefficients.push(coeffs);
+        dh_secret_keys.push(dh_sk);
+    }
+
+    let mut states = Vec::new();
+    let mut encrypted_shares = Vec::new();
+    for i in 0..params.n as usize {
+        let (state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            params,
+            &dh_secret_keys[i],
+            &participants[i].index,
+            &coefficients[i],
+            &participants,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        encrypted_shares.extend_from_slice(state.their_encrypted_secret_shares().unwrap());
+        states.push(state);
+    }
+
+    let mut group_key = None;
+    let mut secret_keys = Vec::new();
+    let mut receipts = Vec::new();
+    for (i, state) in states.into_iter().enumerate() {
+        let my_encrypted_secret_shares = encrypted_shares
+            .iter()
+            .filter(|s| s.receiver_index == participants[i].index)
+            .cloned()
+            .collect();
+        let state = state.to_round_two(my_encrypted_secret_shares, rng).unwrap();
+        let (gk, sk, receipt) = state.finish_with_receipt(&dh_secret_keys[i], rng).unwrap();
+        group_key = Some(gk);
+        secret_keys.push(sk);
+        receipts.push(receipt);
+    }
+
+    Dkg {
+        group_key: group_key.unwrap(),
+        participants,
+        dh_secret_keys,
+        secret_keys,
+        receipts,
+    }
+}
+
+/// Run a full distributed key generation with `params.n` honest dealers.
+pub(crate) fn run_dkg(params: &Parameters) -> (GroupKey, Vec<SecretKey>) {
+    let dkg = run_full_dkg(params);
+
+    (dkg.group_key, dkg.secret_keys)
+}
@@ -0,0 +1,303 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Verification of threshold signatures against a pinned group and policy.
+//!
+//! A [`ThresholdVerifier`] checks not only that a signature is valid for a
+//! message, but also that it was produced by a given group, over a message
+//! with one of the allowed context strings, and that it has not already been
+//! accepted before.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use std::boxed::Box;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use core::convert::TryInto;
+use core::fmt;
+
+use crate::keygen::Error;
+use crate::keygen::GroupKey;
+use crate::signature::compute_message_hash;
+use crate::signature::ThresholdSignature;
+
+/// A storage for the signatures which have already been accepted by a
+/// [`ThresholdVerifier`], in order to detect replays.
+pub trait SeenSignatures {
+    /// Record a serialised `signature` as seen.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this signature had not been recorded before, and `false`
+    /// otherwise.
+    fn insert(&mut self, signature: &[u8; 64]) -> bool;
+}
+
+impl SeenSignatures for BTreeSet<[u8; 64]> {
+    fn insert(&mut self, signature: &[u8; 64]) -> bool {
+        BTreeSet::insert(self, *signature)
+    }
+}
+
+/// A message whose [`ThresholdSignature`] has been accepted by a
+/// [`ThresholdVerifier`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifiedMessage {
+    /// The allowed context string the message was signed with.
+    pub context: Vec<u8>,
+    /// The hashed context and message which was signed.
+    pub message_hash: [u8; 32],
+}
+
+/// A verifier of [`ThresholdSignature`]s for a pinned [`GroupKey`].
+pub struct ThresholdVerifier {
+    /// The group public key signatures must verify under.
+    group_key: GroupKey,
+    /// The context strings a message may be signed with.
+    contexts: Vec<Vec<u8>>,
+    /// The signatures accepted thus far, if replay protection is enabled.
+    seen_signatures: Option<Box<dyn SeenSignatures>>,
+}
+
+impl fmt::Debug for ThresholdVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ThresholdVerifier")
+            .field("group_key", &self.group_key)
+            .field("contexts", &self.contexts)
+            .field("replay_protection", &self.seen_signatures.is_some())
+            .finish()
+    }
+}
+
+impl ThresholdVerifier {
+    /// Construct a new verifier for signatures from the group with the given
+    /// `group_key`.
+    ///
+    /// At least one context string must be allowed with
+    /// [`ThresholdVerifier::allow_context`] before any signature is accepted.
+    pub fn new(group_key: GroupKey) -> ThresholdVerifier {
+        ThresholdVerifier {
+            group_key,
+            contexts: Vec::new(),
+            seen_signatures: None,
+        }
+    }
+
+    /// Allow messages signed with the given `context` string.
+    pub fn allow_context(&mut self, context: &[u8]) {
+        if !self.contexts.iter().any(|c| c == context) {
+            self.contexts.push(context.to_vec());
+        }
+    }
+
+    /// Enable replay protection, recording the accepted signatures in
+    /// `seen_signatures`.
+    pub fn set_seen_signatures(&mut self, seen_signatures: Box<dyn SeenSignatures>) {
+        self.seen_signatures = Some(seen_signatures);
+    }
+
+    /// Check a serialised [`ThresholdSignature`] over a `message`.
+    ///
+    /// The signature is strictly decoded, and must verify under the pinned
+    /// group key for a message hashed with one of the allowed contexts.  If
+    /// replay protection is enabled, the signature must not have been accepted
+    /// before.
+    ///
+    /// # Returns
+    ///
+    /// A [`VerifiedMessage`] if the signature was accepted, otherwise a
+    /// `SerialisationError` if the signature is malformed, an
+    /// `InvalidSignature` if it does not verify under any allowed context, or
+    /// a `ReplayedSignature` if it was already accepted.
+    pub fn check(&mut self, message: &[u8], signature: &[u8]) -> Result<VerifiedMessage, Error> {
+        let signature_bytes: [u8; 64] = signature
+            .try_into()
+            .map_err(|_| Error::SerialisationError)?;
+        let threshold_signature = ThresholdSignature::from_bytes(&signature_bytes)?;
+
+        let verified_message = self
+            .contexts
+            .iter()
+            .map(|context| (context, compute_message_hash(context, message)))
+            .find(|(_, message_hash)| {
+                threshold_signature
+                    .verify(&self.group_key, message_hash)
+                    .is_ok()
+            })
+            .map(|(context, message_hash)| VerifiedMessage {
+                context: context.clone(),
+                message_hash,
+            })
+            .ok_or(Error::InvalidSignature)?;
+
+        if let Some(seen_signatures) = self.seen_signatures.as_mut() {
+            if !seen_signatures.insert(&signature_bytes) {
+                return Err(Error::ReplayedSignature);
+            }
+        }
+
+        Ok(verified_message)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::keygen::Participant;
+    use crate::keygen::{DistributedKeyGeneration, RoundOne};
+    use crate::parameters::Parameters;
+    use crate::precomputation::generate_commitment_share_lists;
+    use crate::signature::SignatureAggregator;
+
+    use rand::rngs::OsRng;
+
+    fn sign_single_party(context: &[u8], message: &[u8]) -> (GroupKey, [u8; 64]) {
+        let params = Parameters { n: 1, t: 1 };
+        let rng = OsRng;
+
+        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", rng);
+        let participants: Vec<Participant> = vec![p1.clone()];
+        let (p1_state, _participant_lists) = DistributedKeyGeneration::<RoundOne>::new_initial(
+            &params,
+            &p1_dh_sk,
+            &p1.index,
+            &p1coeffs,
+            &participants,
+            "Φ",
+            rng,
+        )
+        .unwrap();
+        let p1_my_encrypted_secret_shares =
+            p1_state.their_encrypted_secret_shares().unwrap().clone();
+        let p1_state = p1_state
+            .to_round_two(p1_my_encrypted_secret_shares, rng)
+            .unwrap();
+        let (group_key, p1_sk) = p1_state.finish().unwrap();
+
+        let (p1_public_comshares, mut p1_secret_comshares) =
+            generate_commitment_share_lists(&mut OsRng, 1, 1);
+        let mut aggregator = SignatureAggregator::new(params, group_key, context, message);
+        aggregator
+            .include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into())
+            .unwrap();
+
+        let signers = aggregator.get_signers();
+        let message_hash = compute_message_hash(context, message);
+        let p1_partial = p1_sk
+            .sign(
+                &message_hash,
+                &group_key,
+                &mut p1_secret_comshares,
+                0,
+                signers,
+            )
+            .unwrap();
+        aggregator.include_partial_signature(p1_partial).unwrap();
+
+        let aggregator = aggregator.finalize().unwrap();
+        let threshold_signature = aggregator.aggregate().unwrap();
+
+        (group_key, threshold_signature.to_bytes())
+    }
+
+    #[test]
+    fn check_happy_path() {
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (group_key, signature) = sign_single_party(&context[..], &message[..]);
+
+        let mut verifier = ThresholdVerifier::new(group_key);
+        verifier.allow_context(b"another context");
+        verifier.allow_context(&context[..]);
+
+        let verified_message = verifier.check(&message[..], &signature[..]).unwrap();
+        assert_eq!(verified_message.context, context.to_vec());
+        assert_eq!(
+            verified_message.message_hash,
+            compute_message_hash(&context[..], &message[..])
+        );
+
+        // Without replay protection the same signature is accepted again.
+        assert!(verifier.check(&message[..], &signature[..]).is_ok());
+
+        // But not for another message, nor under another group key.
+        assert_eq!(
+            verifier.check(b"another message", &signature[..]),
+            Err(Error::InvalidSignature)
+        );
+        let (other_group_key, _) = sign_single_party(&context[..], &message[..]);
+        let mut verifier = ThresholdVerifier::new(other_group_key);
+        verifier.allow_context(&context[..]);
+        assert_eq!(
+            verifier.check(&message[..], &signature[..]),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn check_wrong_context() {
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (group_key, signature) = sign_single_party(&context[..], &message[..]);
+
+        let mut verifier = ThresholdVerifier::new(group_key);
+        assert_eq!(
+            verifier.check(&message[..], &signature[..]),
+            Err(Error::InvalidSignature)
+        );
+
+        verifier.allow_context(b"another context");
+        assert_eq!(
+            verifier.check(&message[..], &signature[..]),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn check_replayed_and_malformed_signatures() {
+        let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+        let message = b"This is a test of the tsunami alert system. This is only a test.";
+        let (group_key, signature) = sign_single_party(&context[..], &message[..]);
+
+        let mut verifier = ThresholdVerifier::new(group_key);
+        verifier.allow_context(&context[..]);
+        let seen_signatures: BTreeSet<[u8; 64]> = BTreeSet::new();
+        verifier.set_seen_signatures(Box::new(seen_signatures));
+
+        assert!(verifier.check(&message[..], &signature[..]).is_ok());
+        assert_eq!(
+            verifier.check(&message[..], &signature[..]),
+            Err(Error::ReplayedSignature)
+        );
+
+        assert_eq!(
+            verifier.check(&message[..], &signature[..63]),
+            Err(Error::SerialisationError)
+        );
+        let mut non_canonical = signature;
+        non_canonical[32..].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+            verifier.check(&message[..], &non_canonical[..]),
+            Err(Error::SerialisationError)
+        );
+    }
+}
@@ -0,0 +1,316 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Differential tests against `frost-ristretto255`, run with the
+//! `differential` feature.
+//!
+//! Each seeded session signs the same 32 bytes with the same key shares and
+//! the same nonces through both implementations, this crate under its
+//! [`Rfc9591Ristretto255`] ciphersuite, and compares every intermediate
+//! value: the public key material, the nonce commitments, the binding
+//! factors, the group commitment, the signature shares and the final
+//! signature.  Every mismatching value is printed with both encodings, and
+//! fails the test, which thereby gates any change to the hashing or
+//! commitment formulas of the RFC-compatible mode.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+use frost_core::round1::Nonce;
+use frost_core::Ciphersuite;
+use frost_core::Group;
+use frost_ristretto255::keys::KeyPackage;
+use frost_ristretto255::keys::PublicKeyPackage;
+use frost_ristretto255::keys::SigningShare;
+use frost_ristretto255::keys::VerifyingShare;
+use frost_ristretto255::round1::SigningNonces;
+use frost_ristretto255::Identifier;
+use frost_ristretto255::Ristretto255Sha512;
+use frost_ristretto255::SigningPackage;
+use frost_ristretto255::VerifyingKey;
+
+use sha2::Digest;
+use sha2::Sha512;
+
+use ice_frost::ciphersuite::Rfc9591Ristretto255;
+use ice_frost::compute_message_hash;
+use ice_frost::precomputation::SecretCommitmentShareList;
+use ice_frost::GroupKey;
+use ice_frost::IndividualSecretKey;
+use ice_frost::Parameters;
+use ice_frost::SignatureAggregator;
+
+/// A scalar derived from the `seed` of a session, a `label` and an `index`.
+fn seeded_scalar(seed: u64, label: &str, index: u32) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(b"ICE-FROST-DIFFERENTIAL");
+    h.update(seed.to_le_bytes());
+    h.update(label.as_bytes());
+    h.update(index.to_le_bytes());
+
+    Scalar::from_hash(h)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        hex.push_str(&format!("{:02x}", byte));
+        hex
+    })
+}
+
+fn identifier(index: u32) -> Identifier {
+    Identifier::try_from(index as u16).unwrap()
+}
+
+/// A value computed by both implementations, as (kind, index, ice-frost
+/// encoding, frost-core encoding).
+type Value = (&'static str, Option<u32>, Vec<u8>, Vec<u8>);
+
+/// Sign through both implementations in the session of seed `seed`, and
+/// collect every intermediate value.
+fn session(seed: u64) -> Vec<Value> {
+    let params = Parameters { n: 3, t: 2 };
+    let signers = [1u32, 3];
+    let context = b"ICE-FROST DIFFERENTIAL TEST";
+    let message = b"This is a test of the tsunami alert system. This is only a test.";
+    let message_hash = compute_message_hash(&context[..], &message[..]);
+
+    // The shares of a polynomial of degree t - 1, dealt to every index.
+    let coefficients: Vec<Scalar> = (0..params.t)
+        .map(|k| seeded_scalar(seed, "coefficient", k))
+        .collect();
+    let share = |index: u32| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |acc, a| acc * Scalar::from(index) + a)
+    };
+    let group_point = &RISTRETTO_BASEPOINT_TABLE * &coefficients[0];
+    let verifying_key = VerifyingKey::deserialize(group_point.compress().to_bytes()).unwrap();
+    let group_key = GroupKey::try_from(&verifying_key).unwrap();
+
+    let signing_shares: BTreeMap<u32, SigningShare> = (1..=params.n)
+        .map(|i| (i, SigningShare::deserialize(share(i).to_bytes()).unwrap()))
+        .collect();
+    let verifying_shares: BTreeMap<Identifier, VerifyingShare> = signing_shares
+        .iter()
+        .map(|(i, signing_share)| (identifier(*i), VerifyingShare::from(*signing_share)))
+        .collect();
+    let secret_keys: BTreeMap<u32, IndividualSecretKey> = signing_shares
+        .iter()
+        .map(|(i, signing_share)| {
+            let secret_key =
+                IndividualSecretKey::try_from((identifier(*i), *signing_share)).unwrap();
+            (*i, secret_key)
+        })
+        .collect();
+
+    // The hiding and binding nonces of each signer.
+    let nonces: BTreeMap<u32, (Scalar, Scalar)> = signers
+        .iter()
+        .map(|i| {
+            let hiding = seeded_scalar(seed, "hiding", *i);
+            let binding = seeded_scalar(seed, "binding", *i);
+            (*i, (hiding, binding))
+        })
+        .collect();
+    let commitments: BTreeMap<u32, (RistrettoPoint, RistrettoPoint)> = nonces
+        .iter()
+        .map(|(i, (d, e))| {
+            let hiding = &RISTRETTO_BASEPOINT_TABLE * d;
+            let binding = &RISTRETTO_BASEPOINT_TABLE * e;
+            (*i, (hiding, binding))
+        })
+        .collect();
+
+    // The ice-frost session.
+    let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    aggregator.set_ciphersuite::<Rfc9591Ristretto255>().unwrap();
+    for i in signers.iter() {
+        aggregator
+            .include_signer(*i, commitments[i], secret_keys[i].to_public())
+            .unwrap();
+    }
+    let ice_signers = aggregator.get_signers().clone();
+
+    let mut ice_shares = BTreeMap::new();
+    for i in signers.iter() {
+        let (d, e) = nonces[i];
+        let (hiding, binding) = commitments[i];
+
+        let mut list = Vec::new();
+        list.extend_from_slice(&1u32.to_le_bytes());
+        list.extend_from_slice(d.as_bytes());
+        list.extend_from_slice(hiding.compress().as_bytes());
+        list.extend_from_slice(e.as_bytes());
+        list.extend_from_slice(binding.compress().as_bytes());
+        list.extend_from_slice(&0u32.to_le_bytes());
+        list.extend_from_slice(&i.to_le_bytes());
+        let mut list = SecretCommitmentShareList::from_bytes(&list).unwrap();
+
+        let partial = secret_keys[i]
+            .sign_with::<Rfc9591Ristretto255>(&message_hash, &group_key, &mut list, 0, &ice_signers)
+            .unwrap();
+        ice_shares.insert(*i, partial.to_bytes()[4..].to_vec());
+        aggregator.include_partial_signature(partial).unwrap();
+    }
+    let aggregator = aggregator.finalize().unwrap();
+    let ice_binding_factors = aggregator.binding_factors();
+    let ice_signature = aggregator.aggregate().unwrap();
+    assert!(ice_signature
+        .verify_with::<Rfc9591Ristretto255>(&group_key, &message_hash)
+        .is_ok());
+
+    // The frost-core session, over the same 32 bytes.
+    let signing_nonces: BTreeMap<u32, SigningNonces> = nonces
+        .iter()
+        .map(|(i, (d, e))| {
+            let hiding = Nonce::<Ristretto255Sha512>::deserialize(d.to_bytes()).unwrap();
+            let binding = Nonce::<Ristretto255Sha512>::deserialize(e.to_bytes()).unwrap();
+            (*i, SigningNonces::from_nonces(hiding, binding))
+        })
+        .collect();
+    let signing_package = SigningPackage::new(
+        signing_nonces
+            .iter()
+            .map(|(i, nonces)| (identifier(*i), *nonces.commitments()))
+            .collect(),
+        &message_hash,
+    );
+    let binding_factors =
+        frost_core::compute_binding_factor_list(&signing_package, &verifying_key, &[]);
+    let group_commitment =
+        frost_core::compute_group_commitment(&signing_package, &binding_factors).unwrap();
+
+    let mut frost_shares = BTreeMap::new();
+    for i in signers.iter() {
+        let key_package = KeyPackage::new(
+            identifier(*i),
+            signing_shares[i],
+            verifying_shares[&identifier(*i)],
+            verifying_key,
+            params.t as u16,
+        );
+        let signature_share =
+            frost_ristretto255::round2::sign(&signing_package, &signing_nonces[i], &key_package)
+                .unwrap();
+        frost_shares.insert(identifier(*i), signature_share);
+    }
+    let public_key_package = PublicKeyPackage::new(verifying_shares.clone(), verifying_key);
+    let frost_signature =
+        frost_ristretto255::aggregate(&signing_package, &frost_shares, &public_key_package)
+            .unwrap();
+    assert!(verifying_key
+        .verify(&message_hash, &frost_signature)
+        .is_ok());
+
+    // Every value computed by both implementations.
+    let mut values: Vec<Value> = Vec::new();
+    values.push((
+        "group key",
+        None,
+        group_key.to_bytes().to_vec(),
+        verifying_key.serialize().to_vec(),
+    ));
+    for i in 1..=params.n {
+        values.push((
+            "verifying share",
+            Some(i),
+            secret_keys[&i].to_public().to_bytes()[4..].to_vec(),
+            verifying_shares[&identifier(i)].serialize().to_vec(),
+        ));
+    }
+    for i in signers.iter() {
+        let frost_commitments = signing_nonces[i].commitments();
+        values.push((
+            "hiding commitment",
+            Some(*i),
+            commitments[i].0.compress().to_bytes().to_vec(),
+            frost_commitments.hiding().serialize().to_vec(),
+        ));
+        values.push((
+            "binding commitment",
+            Some(*i),
+            commitments[i].1.compress().to_bytes().to_vec(),
+            frost_commitments.binding().serialize().to_vec(),
+        ));
+        values.push((
+            "binding factor",
+            Some(*i),
+            ice_binding_factors[i].to_bytes().to_vec(),
+            binding_factors
+                .get(&identifier(*i))
+                .unwrap()
+                .serialize()
+                .to_vec(),
+        ));
+    }
+    values.push((
+        "group commitment",
+        None,
+        ice_signature.to_bytes()[..32].to_vec(),
+        <Ristretto255Sha512 as Ciphersuite>::Group::serialize(&group_commitment.to_element())
+            .to_vec(),
+    ));
+    for i in signers.iter() {
+        values.push((
+            "signature share",
+            Some(*i),
+            ice_shares[i].clone(),
+            frost_shares[&identifier(*i)].serialize().to_vec(),
+        ));
+    }
+    values.push((
+        "signature",
+        None,
+        ice_signature.to_bytes().to_vec(),
+        frost_signature.serialize().to_vec(),
+    ));
+
+    values
+}
+
+#[test]
+fn differential_ristretto255() {
+    for seed in 0..4 {
+        let mut mismatches = Vec::new();
+
+        for (kind, index, ice_frost, frost_core) in session(seed) {
+            if ice_frost == frost_core {
+                continue;
+            }
+
+            let name = match index {
+                Some(index) => format!("{} of signer {}", kind, index),
+                None => kind.to_string(),
+            };
+            eprintln!(
+                "seed {}: mismatch in {}\n  ice-frost:  {}\n  frost-core: {}",
+                seed,
+                name,
+                hex(&ice_frost),
+                hex(&frost_core)
+            );
+            mismatches.push(name);
+        }
+
+        assert!(
+            mismatches.is_empty(),
+            "seed {}: mismatches in {:?}",
+            seed,
+            mismatches
+        );
+    }
+}
@@ -11,33 +11,56 @@
 
 //! Integration tests for FROST.
 
+// The tests pass `&mut rng` to the generic constructors for readability.
+#![allow(clippy::needless_borrows_for_generic_args, clippy::get_first)]
+
+#[cfg(not(feature = "strict"))]
 use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
 use ed25519_dalek::Verifier;
 
+use ice_frost::bundle::{SigningRequestBundle, SigningResponseBundle};
+#[cfg(not(feature = "strict"))]
 use ice_frost::keygen::EncryptedSecretShare;
+#[cfg(not(feature = "strict"))]
+use ice_frost::keygen::ShareVersion;
+use ice_frost::metrics::{self, Metrics};
 use rand::rngs::OsRng;
+use std::sync::{Arc, Mutex};
 
 use ice_frost::compute_message_hash;
 use ice_frost::generate_commitment_share_lists;
 
 use ice_frost::DistributedKeyGeneration;
+use ice_frost::GroupKey;
+use ice_frost::IndividualPublicKey;
+use ice_frost::IndividualSecretKey;
 use ice_frost::Parameters;
 use ice_frost::Participant;
 
 use ice_frost::SignatureAggregator;
 
+// This attack relies on the malleability of the legacy, unauthenticated
+// share format, which the `strict` feature compiles out.
 #[test]
+#[cfg(not(feature = "strict"))]
 fn keygen_rogue_key_attack_2_out_of_3_second_is_malicious() {
     loop {
         let params = Parameters { n: 3, t: 2 };
         let mut rng = OsRng;
 
-        let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
-        let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
-        let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
+        let (mut p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", &mut rng);
+        let (mut p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", &mut rng);
+        let (mut p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", &mut rng);
+
+        // The dealers only speak the legacy share format, whose shares carry
+        // no authentication tag.
+        for p in [&mut p1, &mut p2, &mut p3] {
+            p.share_versions = vec![ShareVersion::V0];
+        }
 
         let mut p3_injected = p3.clone();
-        *p3_injected.commitments.as_mut().unwrap().points.get_mut(1).unwrap() += &RISTRETTO_BASEPOINT_POINT;
+        let injected = p3_injected.commitments.point(1).unwrap() + RISTRETTO_BASEPOINT_POINT;
+        p3_injected.commitments.set_point(1, injected).unwrap();
 
         let participants_injected : Vec<Participant> = vec![p1.clone(), p2.clone(), p3_injected];
         let participants: Vec<Participant> = vec![p1.clone(), p2.clone(), p3.clone()];
@@ -136,8 +159,8 @@ fn keygen_rogue_key_attack_2_out_of_3_second_is_malicious() {
         
         let mut aggregator = SignatureAggregator::new(params, p1_group_key, &context[..], &message[..]);
 
-        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-        aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into());
+        aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into()).unwrap();
+        aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into()).unwrap();
 
         let signers = aggregator.get_signers();
         let message_hash = compute_message_hash(&context[..], &message[..]);
@@ -161,8 +184,8 @@ fn keygen_rogue_key_attack_2_out_of_3_second_is_malicious() {
             )
             .unwrap();
 
-        aggregator.include_partial_signature(p1_partial);
-        aggregator.include_partial_signature(p3_partial);
+        aggregator.include_partial_signature(p1_partial).unwrap();
+        aggregator.include_partial_signature(p3_partial).unwrap();
 
         let aggregator = aggregator.finalize().unwrap();
         let threshold_signature_res = aggregator.aggregate();
@@ -317,9 +340,9 @@ fn signing_and_verification_3_out_of_5() {
 
     let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
 
-    aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-    aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into());
-    aggregator.include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into());
+    aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into()).unwrap();
+    aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into()).unwrap();
+    aggregator.include_signer(4, p4_public_comshares.commitments[0], (&p4_sk).into()).unwrap();
 
     let signers = aggregator.get_signers();
     let message_hash = compute_message_hash(&context[..], &message[..]);
@@ -352,9 +375,9 @@ fn signing_and_verification_3_out_of_5() {
         )
         .unwrap();
 
-    aggregator.include_partial_signature(p1_partial);
-    aggregator.include_partial_signature(p3_partial);
-    aggregator.include_partial_signature(p4_partial);
+    aggregator.include_partial_signature(p1_partial).unwrap();
+    aggregator.include_partial_signature(p3_partial).unwrap();
+    aggregator.include_partial_signature(p4_partial).unwrap();
 
     let aggregator = aggregator.finalize().unwrap();
     let threshold_signature = aggregator.aggregate().unwrap();
@@ -451,8 +474,8 @@ fn signing_and_verification_with_ed25519_dalek_2_out_of_3() {
 
     let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
 
-    aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into());
-    aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into());
+    aggregator.include_signer(1, p1_public_comshares.commitments[0], (&p1_sk).into()).unwrap();
+    aggregator.include_signer(3, p3_public_comshares.commitments[0], (&p3_sk).into()).unwrap();
 
     let signers = aggregator.get_signers();
     let message_hash = compute_message_hash(&context[..], &message[..]);
@@ -476,8 +499,8 @@ fn signing_and_verification_with_ed25519_dalek_2_out_of_3() {
         )
         .unwrap();
 
-    aggregator.include_partial_signature(p1_partial);
-    aggregator.include_partial_signature(p3_partial);
+    aggregator.include_partial_signature(p1_partial).unwrap();
+    aggregator.include_partial_signature(p3_partial).unwrap();
 
     let aggregator = aggregator.finalize().unwrap();
     let threshold_signature = aggregator.aggregate().unwrap();
@@ -506,3 +529,324 @@ fn signing_and_verification_with_ed25519_dalek_2_out_of_3() {
         }
     }
 }
+
+fn keygen_3_out_of_3() -> (Parameters, GroupKey, Vec<IndividualSecretKey>) {
+    let params = Parameters { n: 3, t: 3 };
+    let rng = OsRng;
+
+    let (p1, p1coeffs, p1_dh_sk) = Participant::new_dealer(&params, 1, "Φ", rng);
+    let (p2, p2coeffs, p2_dh_sk) = Participant::new_dealer(&params, 2, "Φ", rng);
+    let (p3, p3coeffs, p3_dh_sk) = Participant::new_dealer(&params, 3, "Φ", rng);
+
+    let participants: Vec<Participant> = vec![p1.clone(), p2.clone(), p3.clone()];
+    let (p1_state, p1_participant_lists) = DistributedKeyGeneration::<_>::new_initial(
+        &params,
+        &p1_dh_sk,
+        &p1.index,
+        &p1coeffs,
+        &participants,
+        "Φ",
+        rng,
+    )
+    .unwrap();
+    let p1_their_encrypted_secret_shares = p1_state.their_encrypted_secret_shares().unwrap();
+
+    let (p2_state, _participant_lists) = DistributedKeyGeneration::<_>::new_initial(
+        &params,
+        &p2_dh_sk,
+        &p2.index,
+        &p2coeffs,
+        &participants,
+        "Φ",
+        rng,
+    )
+    .unwrap();
+    let p2_their_encrypted_secret_shares = p2_state.their_encrypted_secret_shares().unwrap();
+
+    let (p3_state, _participant_lists) = DistributedKeyGeneration::<_>::new_initial(
+        &params,
+        &p3_dh_sk,
+        &p3.index,
+        &p3coeffs,
+        &participants,
+        "Φ",
+        rng,
+    )
+    .unwrap();
+    let p3_their_encrypted_secret_shares = p3_state.their_encrypted_secret_shares().unwrap();
+
+    // No dealer may be missing when t = n.
+    assert_eq!(p1_participant_lists.valid_participants.len(), 3);
+    assert!(p1_participant_lists.misbehaving_participants.is_none());
+
+    let p1_my_encrypted_secret_shares = vec![
+        p1_their_encrypted_secret_shares[0].clone(),
+        p2_their_encrypted_secret_shares[0].clone(),
+        p3_their_encrypted_secret_shares[0].clone(),
+    ];
+    let p2_my_encrypted_secret_shares = vec![
+        p1_their_encrypted_secret_shares[1].clone(),
+        p2_their_encrypted_secret_shares[1].clone(),
+        p3_their_encrypted_secret_shares[1].clone(),
+    ];
+    let p3_my_encrypted_secret_shares = vec![
+        p1_their_encrypted_secret_shares[2].clone(),
+        p2_their_encrypted_secret_shares[2].clone(),
+        p3_their_encrypted_secret_shares[2].clone(),
+    ];
+
+    let p1_state = p1_state
+        .to_round_two(p1_my_encrypted_secret_shares, rng)
+        .unwrap();
+    let p2_state = p2_state
+        .to_round_two(p2_my_encrypted_secret_shares, rng)
+        .unwrap();
+    let p3_state = p3_state
+        .to_round_two(p3_my_encrypted_secret_shares, rng)
+        .unwrap();
+
+    let (p1_group_key, p1_sk) = p1_state.finish().unwrap();
+    let (p2_group_key, p2_sk) = p2_state.finish().unwrap();
+    let (p3_group_key, p3_sk) = p3_state.finish().unwrap();
+
+    assert!(p1_group_key == p2_group_key);
+    assert!(p2_group_key == p3_group_key);
+
+    (params, p1_group_key, vec![p1_sk, p2_sk, p3_sk])
+}
+
+#[test]
+fn signing_and_verification_3_out_of_3() {
+    let (params, group_key, secret_keys) = keygen_3_out_of_3();
+
+    let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+    let message = b"This is a test of the tsunami alert system. This is only a test.";
+
+    let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    let mut secret_comshares = Vec::new();
+    for sk in secret_keys.iter() {
+        let (public_comshares, secret_comshare) =
+            generate_commitment_share_lists(&mut OsRng, sk.to_public().index, 1);
+        aggregator.include_signer(sk.to_public().index, public_comshares.commitments[0], sk.into()).unwrap();
+        secret_comshares.push(secret_comshare);
+    }
+
+    let signers = aggregator.get_signers().clone();
+    let message_hash = compute_message_hash(&context[..], &message[..]);
+
+    for (sk, secret_comshare) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+        let partial = sk
+            .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+    }
+
+    let aggregator = aggregator.finalize().unwrap();
+    let threshold_signature = aggregator.aggregate().unwrap();
+
+    assert!(threshold_signature.verify(&group_key, &message_hash).is_ok());
+}
+
+#[test]
+fn signing_3_out_of_3_with_two_partials() {
+    let (params, group_key, secret_keys) = keygen_3_out_of_3();
+
+    let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+    let message = b"This is a test of the tsunami alert system. This is only a test.";
+    let message_hash = compute_message_hash(&context[..], &message[..]);
+
+    // All three signers were expected, but only two partials arrive.
+    let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    let mut secret_comshares = Vec::new();
+    for sk in secret_keys.iter() {
+        let (public_comshares, secret_comshare) =
+            generate_commitment_share_lists(&mut OsRng, sk.to_public().index, 1);
+        aggregator.include_signer(sk.to_public().index, public_comshares.commitments[0], sk.into()).unwrap();
+        secret_comshares.push(secret_comshare);
+    }
+
+    let signers = aggregator.get_signers().clone();
+    for (sk, secret_comshare) in secret_keys.iter().zip(secret_comshares.iter_mut()).take(2) {
+        let partial = sk
+            .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+    }
+
+    let errors = aggregator.finalize().unwrap_err();
+    assert_eq!(errors.get(&0), Some(&"Missing remaining signer(s)"));
+    assert_eq!(errors.get(&3), Some(&"Missing partial signature"));
+
+    // Only two signers were included in the first place.
+    let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    let mut secret_comshares = Vec::new();
+    for sk in secret_keys.iter().take(2) {
+        let (public_comshares, secret_comshare) =
+            generate_commitment_share_lists(&mut OsRng, sk.to_public().index, 1);
+        aggregator.include_signer(sk.to_public().index, public_comshares.commitments[0], sk.into()).unwrap();
+        secret_comshares.push(secret_comshare);
+    }
+
+    let signers = aggregator.get_signers().clone();
+    for (sk, secret_comshare) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+        let partial = sk
+            .sign(&message_hash, &group_key, secret_comshare, 0, &signers)
+            .unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+    }
+
+    let errors = aggregator.finalize().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors.get(&0), Some(&"Missing partial signatures"));
+}
+
+#[test]
+fn offline_signing_with_bundles() {
+    let (params, group_key, secret_keys) = keygen_3_out_of_3();
+
+    let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+    let message = b"This is a test of the tsunami alert system. This is only a test.";
+    let metadata: [(&str, &[u8]); 1] = [("chain_id", b"mainnet")];
+
+    let mut aggregator =
+        SignatureAggregator::new_with_metadata(params, group_key, &context[..], &message[..], &metadata).unwrap();
+
+    let mut secret_comshares = Vec::new();
+    for sk in secret_keys.iter() {
+        let pk: IndividualPublicKey = sk.into();
+        let (public_comshares, secret) = generate_commitment_share_lists(&mut OsRng, pk.index, 2);
+        aggregator.include_signer(pk.index, public_comshares.commitments[1], pk).unwrap();
+        secret_comshares.push(secret);
+    }
+
+    for (sk, secret) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+        let pk: IndividualPublicKey = sk.into();
+
+        // The request travels to the air-gapped signer...
+        let request_bytes = aggregator.signing_request(pk.index, 1).unwrap().to_bytes();
+        let request = SigningRequestBundle::from_bytes(&request_bytes).unwrap();
+        assert_eq!(request.session().get_metadata("chain_id"), Some(&b"mainnet"[..]));
+        assert_eq!(request.message, message.to_vec());
+
+        // ... and the response travels back.
+        let response_bytes = sk.sign_request(&request, &group_key, secret).unwrap().to_bytes();
+        let response = SigningResponseBundle::from_bytes(&response_bytes).unwrap();
+        aggregator.include_signing_response(response).unwrap();
+    }
+
+    let aggregator = aggregator.finalize().unwrap();
+    let threshold_signature = aggregator.aggregate().unwrap();
+    let message_hash = compute_message_hash(&context[..], &message[..]);
+
+    assert!(threshold_signature.verify_with_metadata(&group_key, &message_hash, &metadata).is_ok());
+}
+
+/// A counter increment, with its labels.
+type Event = (&'static str, Vec<(&'static str, String)>);
+
+/// A `Metrics` implementation recording every counter increment.
+#[derive(Default)]
+struct RecordingMetrics {
+    events: Mutex<Vec<Event>>,
+}
+
+impl Metrics for RecordingMetrics {
+    fn increment_counter(&self, name: &'static str, labels: &[(&'static str, &str)]) {
+        let labels = labels.iter().map(|(key, value)| (*key, value.to_string())).collect();
+        self.events.lock().unwrap().push((name, labels));
+    }
+}
+
+impl RecordingMetrics {
+    fn count(&self, name: &str, labels: &[(&str, &str)]) -> usize {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(event, event_labels)| {
+                *event == name
+                    && labels
+                        .iter()
+                        .all(|(key, value)| event_labels.iter().any(|(k, v)| k == key && v == value))
+            })
+            .count()
+    }
+}
+
+#[test]
+fn metrics_of_a_failed_session() {
+    let (params, group_key, secret_keys) = keygen_3_out_of_3();
+
+    let context = b"CONTEXT STRING STOLEN FROM DALEK TEST SUITE";
+    let message = b"This is a test of the tsunami alert system. This is only a test.";
+    let message_hash = compute_message_hash(&context[..], &message[..]);
+    let wrong_hash = compute_message_hash(&context[..], b"Not the message");
+
+    // Signer 2 signs another message, and signer 3 never answers.
+    let recorder = Arc::new(RecordingMetrics::default());
+    let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    aggregator.set_metrics(recorder.clone());
+    aggregator.set_partial_budget(1);
+    let mut secret_comshares = Vec::new();
+    for sk in secret_keys.iter() {
+        let (public_comshares, secret_comshare) =
+            generate_commitment_share_lists(&mut OsRng, sk.to_public().index, 1);
+        aggregator.include_signer(sk.to_public().index, public_comshares.commitments[0], sk.into()).unwrap();
+        secret_comshares.push(secret_comshare);
+    }
+
+    let signers = aggregator.get_signers().clone();
+    for (sk, secret_comshare) in secret_keys.iter().zip(secret_comshares.iter_mut()).take(2) {
+        let hash = match sk.to_public().index {
+            2 => &wrong_hash,
+            _ => &message_hash,
+        };
+        let partial = sk.sign(hash, &group_key, secret_comshare, 0, &signers).unwrap();
+        let _ = aggregator.include_partial_signature(partial);
+    }
+    assert!(aggregator.finalize().is_err());
+
+    assert_eq!(recorder.count(metrics::SESSION_CREATED, &[("role", "aggregator")]), 1);
+    assert_eq!(recorder.count(metrics::PARTIAL_ACCEPTED, &[("signer", "1")]), 1);
+    assert_eq!(recorder.count(metrics::PARTIAL_ACCEPTED, &[]), 1);
+    assert_eq!(
+        recorder.count(metrics::PARTIAL_REJECTED, &[("signer", "2"), ("reason", "invalid_partial")]),
+        1
+    );
+    assert_eq!(recorder.count(metrics::PARTIAL_MISSING, &[("signer", "2")]), 1);
+    assert_eq!(recorder.count(metrics::PARTIAL_MISSING, &[("signer", "3")]), 1);
+    assert_eq!(recorder.count(metrics::PARTIAL_MISSING, &[("signer", "1")]), 0);
+    assert_eq!(recorder.count(metrics::SESSION_ABORTED, &[("reason", "incomplete")]), 1);
+    assert_eq!(recorder.count(metrics::SESSION_COMPLETED, &[]), 0);
+
+    // Without a budget, the invalid partial is only caught on aggregation.
+    let recorder = Arc::new(RecordingMetrics::default());
+    let mut aggregator = SignatureAggregator::new(params, group_key, &context[..], &message[..]);
+    aggregator.set_metrics(recorder.clone());
+    let mut secret_comshares = Vec::new();
+    for sk in secret_keys.iter() {
+        let (public_comshares, secret_comshare) =
+            generate_commitment_share_lists(&mut OsRng, sk.to_public().index, 1);
+        aggregator.include_signer(sk.to_public().index, public_comshares.commitments[0], sk.into()).unwrap();
+        secret_comshares.push(secret_comshare);
+    }
+
+    let signers = aggregator.get_signers().clone();
+    for (sk, secret_comshare) in secret_keys.iter().zip(secret_comshares.iter_mut()) {
+        let hash = match sk.to_public().index {
+            2 => &wrong_hash,
+            _ => &message_hash,
+        };
+        let partial = sk.sign(hash, &group_key, secret_comshare, 0, &signers).unwrap();
+        aggregator.include_partial_signature(partial).unwrap();
+    }
+    assert!(aggregator.finalize().unwrap().aggregate().is_err());
+
+    assert_eq!(recorder.count(metrics::PARTIAL_ACCEPTED, &[]), 3);
+    assert_eq!(
+        recorder.count(metrics::PARTIAL_REJECTED, &[("signer", "2"), ("reason", "invalid_partial")]),
+        1
+    );
+    assert_eq!(recorder.count(metrics::PARTIAL_REJECTED, &[]), 1);
+    assert_eq!(recorder.count(metrics::SESSION_ABORTED, &[("reason", "invalid_signature")]), 1);
+}
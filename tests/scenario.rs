@@ -0,0 +1,108 @@
+// -*- mode: rust; -*-
+//
+// This file is part of ice-frost.
+// Copyright (c) 2020 isis lovecruft
+// Copyright (c) 2021-2023 Toposware Inc.
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Toposware developers <dev@toposware.com>
+
+//! Scripted scenarios, loaded from the fixtures in `tests/scenarios`.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use ice_frost::scenario::{run, Outcome, Scenario, ScenarioReport};
+
+#[derive(Deserialize)]
+struct Fixture {
+    scenario: Scenario,
+    expected: Outcome,
+}
+
+fn fixtures() -> Vec<Fixture> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+    let mut paths: Vec<_> = fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |e| e == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap())
+        .collect()
+}
+
+#[test]
+fn scenario_fixtures() {
+    let fixtures = fixtures();
+    assert!(fixtures.len() >= 6);
+
+    for fixture in fixtures {
+        let name = fixture.scenario.name.clone();
+        let report = run(fixture.scenario);
+
+        assert_eq!(report.name, name);
+        assert_eq!(report.outcome, fixture.expected, "{}", name);
+        assert_eq!(
+            report.transitions.is_empty(),
+            matches!(report.outcome, Outcome::InvalidScenario { .. }),
+            "{}",
+            name
+        );
+        assert_eq!(
+            report.signature.is_some(),
+            report.outcome == Outcome::Signed,
+            "{}",
+            name
+        );
+        if let Some(signature) = report.signature {
+            let group_key = report.group_key.unwrap();
+            assert!(signature
+                .verify_unhashed(
+                    &group_key,
+                    (
+                        b"ICE-FROST-SCENARIO",
+                        b"This is a test of the tsunami alert system. This is only a test.",
+                    ),
+                )
+                .is_ok());
+        }
+    }
+}
+
+#[test]
+fn scenario_excluded_dealers() {
+    let scenario: Scenario =
+        serde_json::from_str(&fs::read_to_string("tests/scenarios/invalid_proof.json").unwrap())
+            .map(|fixture: serde_json::Value| {
+                serde_json::from_value(fixture["scenario"].clone()).unwrap()
+            })
+            .unwrap();
+
+    let report = run(scenario);
+    assert_eq!(report.excluded_dealers, vec![2]);
+    assert_eq!(report.outcome, Outcome::Signed);
+}
+
+#[test]
+fn scenario_report_serde() {
+    for fixture in fixtures() {
+        let report = run(fixture.scenario);
+        let json = serde_json::to_string(&report).unwrap();
+        let decoded: ScenarioReport = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded.name, report.name);
+        assert_eq!(decoded.transitions, report.transitions);
+        assert_eq!(decoded.excluded_dealers, report.excluded_dealers);
+        assert_eq!(decoded.group_key, report.group_key);
+        assert_eq!(decoded.signature, report.signature);
+        assert_eq!(decoded.outcome, report.outcome);
+    }
+}